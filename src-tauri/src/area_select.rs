@@ -0,0 +1,126 @@
+//! Picking the recording area for `RecordingMode::Area` on screen.
+//!
+//! A borderless, transparent, always-on-top window is laid over every
+//! monitor (the Win32 virtual screen) and loads the frontend's area
+//! selector (`index.html?overlay=area`). The user drags a rectangle; the
+//! overlay reports it with `finish_area_selection` in its own physical
+//! pixels, which are moved to screen coordinates here. The main window is
+//! hidden meanwhile so it doesn't cover what the user wants to select.
+
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+use tokio::sync::oneshot;
+
+const OVERLAY_LABEL: &str = "area-selector";
+
+/// Smaller selections (physical px) are treated as a stray click
+const MIN_AREA_SIZE: i32 = 16;
+
+/// Where the running selection delivers its result (x, y, width, height)
+static PENDING: Mutex<Option<oneshot::Sender<Option<[i32; 4]>>>> = Mutex::new(None);
+
+/// Screen rect covering all monitors: (x, y, width, height).
+#[cfg(windows)]
+fn virtual_screen(_app: &AppHandle) -> (i32, i32, i32, i32) {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+    };
+    unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    }
+}
+
+#[cfg(not(windows))]
+fn virtual_screen(app: &AppHandle) -> (i32, i32, i32, i32) {
+    match app.primary_monitor() {
+        Ok(Some(monitor)) => {
+            let (pos, size) = (monitor.position(), monitor.size());
+            (pos.x, pos.y, size.width as i32, size.height as i32)
+        }
+        _ => (0, 0, 1920, 1080),
+    }
+}
+
+/// Deliver the selection result (None = cancelled) to the waiting
+/// [`select_area`], once.
+fn deliver(rect: Option<[i32; 4]>) {
+    if let Some(sender) = PENDING.lock().ok().and_then(|mut pending| pending.take()) {
+        let _ = sender.send(rect);
+    }
+}
+
+/// Show the overlay and wait for the user's selection: `Some([x, y, width,
+/// height])` in screen pixels, or None when it was cancelled.
+pub async fn select_area(app: &AppHandle) -> Result<Option<[i32; 4]>, String> {
+    if app.get_webview_window(OVERLAY_LABEL).is_some() {
+        return Err("Area selection is already open".to_string());
+    }
+    let (sender, receiver) = oneshot::channel();
+    *PENDING.lock().map_err(|e| e.to_string())? = Some(sender);
+
+    let builder = WebviewWindowBuilder::new(app, OVERLAY_LABEL, WebviewUrl::App("index.html?overlay=area".into()))
+        .title("Snappi - Select Area")
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(false)
+        .shadow(false)
+        .visible(false);
+    #[cfg(not(target_os = "macos"))]
+    let builder = builder.transparent(true);
+    let overlay = builder.build().map_err(|e| {
+        deliver(None);
+        e.to_string()
+    })?;
+    // Closing the overlay any other way (Alt+F4) cancels
+    overlay.on_window_event(|event| {
+        if matches!(event, WindowEvent::CloseRequested { .. } | WindowEvent::Destroyed) {
+            deliver(None);
+        }
+    });
+
+    let (x, y, width, height) = virtual_screen(app);
+    let _ = overlay.set_position(PhysicalPosition::new(x, y));
+    let _ = overlay.set_size(PhysicalSize::new(width.max(1) as u32, height.max(1) as u32));
+    let main = app.get_webview_window("main");
+    if let Some(main) = &main {
+        let _ = main.hide();
+    }
+    let _ = overlay.show();
+    let _ = overlay.set_focus();
+
+    let selected = receiver.await.unwrap_or(None);
+
+    let _ = overlay.close();
+    if let Some(main) = &main {
+        let _ = main.show();
+        let _ = main.set_focus();
+    }
+    if let Some([x, y, w, h]) = selected {
+        log::info!("Area selected: {},{} {}x{}", x, y, w, h);
+    }
+    Ok(selected)
+}
+
+/// The overlay's result: `[left, top, right, bottom]` in the overlay's
+/// physical pixels, or None when the user cancelled.
+pub fn finish(app: &AppHandle, rect: Option<[f64; 4]>) {
+    let origin = app
+        .get_webview_window(OVERLAY_LABEL)
+        .and_then(|overlay| overlay.inner_position().ok())
+        .unwrap_or(PhysicalPosition::new(0, 0));
+    let selected = rect.and_then(|[x1, y1, x2, y2]| {
+        let left = x1.min(x2).round() as i32;
+        let top = y1.min(y2).round() as i32;
+        let width = (x1 - x2).abs().round() as i32;
+        let height = (y1 - y2).abs().round() as i32;
+        (width >= MIN_AREA_SIZE && height >= MIN_AREA_SIZE)
+            .then_some([origin.x + left, origin.y + top, width, height])
+    });
+    deliver(selected);
+}
@@ -0,0 +1,253 @@
+//! Headless command line: `snappi export <recording_id> ...` renders a
+//! recording with the saved settings and exits without opening a window,
+//! for scripts and CI rendering of demo videos.
+//!
+//! Only a recognized subcommand switches to the CLI; any other arguments
+//! (or none) start the app as usual.
+
+use crate::config::{ExportFormat, QualityPreset};
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+
+const USAGE: &str = "\
+Usage:
+  snappi export <recording_id> [options]   Export a recording and print the output path
+  snappi list                              List recordings (ID, date, duration, title)
+  snappi --help | --version
+
+Export options:
+  -f, --format <mp4|webm|mov|gif|webp|apng|audio>   Output format (default: mp4)
+  -q, --quality <social|high|light>                 Quality preset (default: social)
+  -o, --output <dir>                                Output directory (default: from settings)
+  -p, --preset <name>                               Apply a saved style preset
+      --plain                                       Raw frames, no effects (MP4)
+  -v, --verbose                                     Log progress details";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportArgs {
+    pub recording_id: String,
+    pub format: ExportFormat,
+    pub quality: QualityPreset,
+    pub output_dir: Option<String>,
+    pub preset: Option<String>,
+    pub plain: bool,
+    pub verbose: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    Export(ExportArgs),
+    List,
+    Help,
+    Version,
+}
+
+fn parse_format(value: &str) -> Result<ExportFormat> {
+    Ok(match value.to_ascii_lowercase().as_str() {
+        "mp4" => ExportFormat::Mp4,
+        "webm" => ExportFormat::WebM,
+        "mov" => ExportFormat::Mov,
+        "gif" => ExportFormat::Gif,
+        "webp" => ExportFormat::WebP,
+        "apng" | "png" => ExportFormat::Apng,
+        "audio" => ExportFormat::AudioOnly,
+        _ => bail!("Unknown format: {}", value),
+    })
+}
+
+fn parse_quality(value: &str) -> Result<QualityPreset> {
+    Ok(match value.to_ascii_lowercase().as_str() {
+        "social" => QualityPreset::Social,
+        "high" | "highquality" => QualityPreset::HighQuality,
+        "light" | "lightweight" => QualityPreset::Lightweight,
+        _ => bail!("Unknown quality: {}", value),
+    })
+}
+
+fn parse_export(args: &[String]) -> Result<ExportArgs> {
+    let mut recording_id = None;
+    let mut export = ExportArgs {
+        recording_id: String::new(),
+        format: ExportFormat::Mp4,
+        quality: QualityPreset::Social,
+        output_dir: None,
+        preset: None,
+        plain: false,
+        verbose: false,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        // Both "--format gif" and "--format=gif"
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with('-') => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || inline.clone().or_else(|| args.next().cloned()).with_context(|| format!("{} needs a value", flag));
+        match flag {
+            "-f" | "--format" => export.format = parse_format(&value()?)?,
+            "-q" | "--quality" => export.quality = parse_quality(&value()?)?,
+            "-o" | "--output" => export.output_dir = Some(value()?),
+            "-p" | "--preset" => export.preset = Some(value()?),
+            "--plain" => export.plain = true,
+            "-v" | "--verbose" => export.verbose = true,
+            _ if flag.starts_with('-') => bail!("Unknown option: {}", flag),
+            _ if recording_id.is_none() => recording_id = Some(arg.clone()),
+            _ => bail!("Unexpected argument: {}", arg),
+        }
+    }
+    export.recording_id = recording_id.context("Missing recording ID")?;
+    Ok(export)
+}
+
+/// The CLI command in `args` (without the program name), or `None` to
+/// start the app.
+pub fn parse(args: &[String]) -> Result<Option<CliCommand>> {
+    let Some(first) = args.first() else {
+        return Ok(None);
+    };
+    Ok(Some(match first.as_str() {
+        "export" => CliCommand::Export(parse_export(&args[1..])?),
+        "list" => CliCommand::List,
+        "help" | "--help" | "-h" => CliCommand::Help,
+        "--version" | "-V" => CliCommand::Version,
+        _ => return Ok(None),
+    }))
+}
+
+/// A release build is a GUI program without a console: write to the one
+/// it was started from.
+#[cfg(windows)]
+fn attach_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    let _ = unsafe { AttachConsole(ATTACH_PARENT_PROCESS) };
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+fn export(args: ExportArgs) -> Result<String> {
+    let mut settings = crate::commands::export_settings(
+        crate::commands::load_settings_from_disk(),
+        &crate::commands::recording_dir(&args.recording_id),
+        args.preset.as_deref(),
+    )
+    .map_err(anyhow::Error::msg)?;
+    if let Some(dir) = args.output_dir {
+        settings.output.save_directory = dir;
+    }
+
+    // One line per stage and every 10% to stderr; stdout gets only the path
+    let last = std::sync::Mutex::new((String::new(), -1i64));
+    let progress: crate::export::encoder::ProgressFn = Box::new(move |stage: &str, p: f64| {
+        let step = (p * 10.0).floor() as i64;
+        let mut last = last.lock().unwrap();
+        if last.0 != stage || last.1 != step {
+            *last = (stage.to_string(), step);
+            eprintln!("{:>4}%  {}", (p * 100.0).round() as i64, stage);
+        }
+    });
+
+    let path = if args.plain {
+        crate::export::encoder::export_plain(&args.recording_id, &args.quality, &settings, Some(&progress))?
+    } else {
+        crate::export::encoder::export(
+            &args.recording_id,
+            &args.format,
+            &args.quality,
+            &settings,
+            &[],
+            &Default::default(),
+            None,
+            Some(&progress),
+        )?
+    };
+    if let Err(e) = crate::export::storage::mark_exported(&args.recording_id, &path) {
+        log::warn!("Failed to mark {} as exported: {}", args.recording_id, e);
+    }
+    Ok(path)
+}
+
+fn list() -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    for rec in crate::recording::session::list_recordings()? {
+        writeln!(
+            out,
+            "{}\t{}\t{:.1}s\t{}",
+            rec.id,
+            rec.date,
+            rec.duration_ms as f64 / 1000.0,
+            rec.title.unwrap_or_default()
+        )?;
+    }
+    Ok(())
+}
+
+/// Run the CLI when the process arguments ask for it. Returns the exit
+/// code, or `None` to start the app.
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse(&args) {
+        Ok(command) => command?,
+        Err(e) => {
+            attach_console();
+            eprintln!("snappi: {}\n\n{}", e, USAGE);
+            return Some(2);
+        }
+    };
+    attach_console();
+    let verbose = matches!(&command, CliCommand::Export(a) if a.verbose);
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(if verbose { "info" } else { "warn" }))
+        .try_init();
+
+    let result = match command {
+        CliCommand::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        CliCommand::Version => {
+            println!("snappi {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        CliCommand::List => list(),
+        CliCommand::Export(args) => export(args).map(|path| println!("{}", path)),
+    };
+    Some(match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("snappi: {:#}", e);
+            1
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(&[]).unwrap(), None);
+        // Unknown first arguments start the app
+        assert_eq!(parse(&args("C:\\demo.snappi")).unwrap(), None);
+        assert_eq!(parse(&args("--help")).unwrap(), Some(CliCommand::Help));
+
+        let Some(CliCommand::Export(export)) = parse(&args("export rec-1 --format=gif -q high -o out --plain")).unwrap()
+        else {
+            panic!("not an export");
+        };
+        assert_eq!(export.recording_id, "rec-1");
+        assert_eq!(export.format, ExportFormat::Gif);
+        assert_eq!(export.quality, QualityPreset::HighQuality);
+        assert_eq!(export.output_dir.as_deref(), Some("out"));
+        assert!(export.plain && !export.verbose);
+
+        assert!(parse(&args("export")).is_err());
+        assert!(parse(&args("export rec-1 --format")).is_err());
+        assert!(parse(&args("export rec-1 --format avi")).is_err());
+        assert!(parse(&args("export rec-1 rec-2")).is_err());
+    }
+}
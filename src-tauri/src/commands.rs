@@ -1,15 +1,21 @@
 use crate::config::{
-    AppSettings, ExportFormat, ExportProgress, QualityPreset, RecordingInfo, RecordingState,
+    AppSettings, ExportFormat, ExportProgress, FinalizingProgress, GifOptions, OutputSize,
+    QualityPreset, RecordingInfo, RecordingMode, RecordingState, ScheduledRecording, SetupDiagnostics,
     WindowInfo,
 };
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 pub struct AppState {
     pub recording_state: Mutex<RecordingState>,
     pub settings: Mutex<AppSettings>,
     pub export_progress: Arc<Mutex<Option<ExportProgress>>>,
     pub current_session: Mutex<Option<crate::recording::session::RecordingSession>>,
+    pub scroll_capture: Mutex<Option<crate::recording::scroll_capture::ScrollCapture>>,
+    pub live_stream: Mutex<Option<crate::export::stream::LiveStream>>,
+    /// Running while `replay_buffer_seconds` is set (see [`crate::recording::replay`])
+    pub replay_buffer: Mutex<Option<crate::recording::replay::ReplayBuffer>>,
 }
 
 /// Settings file path: %APPDATA%\Snappi\settings.json
@@ -20,8 +26,24 @@ fn settings_file_path() -> std::path::PathBuf {
         .join("settings.json")
 }
 
+/// Folder of the recording `recording_id`
+pub(crate) fn recording_dir(recording_id: &str) -> std::path::PathBuf {
+    dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id)
+}
+
+/// The current settings with the template settings saved with
+/// `recording_id` applied (see [`crate::recording::template`]).
+fn recording_settings(state: &AppState, recording_id: &str) -> Result<AppSettings, String> {
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    Ok(crate::recording::template::with_recording_settings(settings, &recording_dir(recording_id)))
+}
+
 /// Load settings from disk, falling back to defaults if file missing or invalid.
-fn load_settings_from_disk() -> AppSettings {
+pub(crate) fn load_settings_from_disk() -> AppSettings {
     let path = settings_file_path();
     if path.exists() {
         match std::fs::read_to_string(&path) {
@@ -65,34 +87,370 @@ impl Default for AppState {
             settings: Mutex::new(load_settings_from_disk()),
             export_progress: Arc::new(Mutex::new(None)),
             current_session: Mutex::new(None),
+            scroll_capture: Mutex::new(None),
+            live_stream: Mutex::new(None),
+            replay_buffer: Mutex::new(None),
         }
     }
 }
 
 #[tauri::command]
-pub fn start_recording(state: State<'_, AppState>) -> Result<(), String> {
+pub fn start_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
+    start_session(&state, SessionLink::None, &app_handle)
+}
+
+/// Record another take of `recording_id`; the new recording joins its take
+/// group when it is stopped.
+#[tauri::command]
+pub fn start_retake(recording_id: String, state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
+    start_session(&state, SessionLink::RetakeOf(recording_id), &app_handle)
+}
+
+/// Continue `recording_id`: the new session is appended to its end when it
+/// is stopped, and `stop_recording` returns `recording_id`.
+#[tauri::command]
+pub fn append_to_recording(recording_id: String, state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
+    let recording_dir = recording_dir(&recording_id);
+    if !recording_dir.join("meta.json").exists() {
+        return Err(format!("Recording not found: {}", recording_id));
+    }
+    start_session(&state, SessionLink::AppendTo(recording_id), &app_handle)
+}
+
+/// How a new session relates to an existing recording
+enum SessionLink {
+    None,
+    RetakeOf(String),
+    AppendTo(String),
+}
+
+/// Start a recording session after the configured countdown (see
+/// [`start_after_countdown`]). While it runs, captured clicks, keys and
+/// window focus changes are emitted as `recording-live-events` batches, and
+/// low disk space as `recording-warning` ([`DiskSpaceWarning`]). The
+/// maximum duration is announced by `recording-limit-warning` (remaining
+/// seconds) and `recording-limit-reached` (the limit in seconds). When the
+/// drive drops below the minimum or the limit is reached the recording is
+/// stopped and finalized, then `recording-auto-stopped` carries its ID.
+///
+/// [`DiskSpaceWarning`]: crate::recording::disk_space::DiskSpaceWarning
+fn start_session(state: &AppState, link: SessionLink, app_handle: &AppHandle) -> Result<(), String> {
+    let countdown = state.settings.lock().map_err(|e| e.to_string())?.recording.countdown_seconds;
+    start_after_countdown(state, link, app_handle, countdown, None)
+}
+
+/// Bumped by every countdown and by `cancel_countdown`; a countdown task
+/// that sees another value was cancelled or replaced.
+static COUNTDOWN_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Start a session after `seconds` of countdown. Returns as soon as the
+/// countdown is running: `recording-countdown` carries the remaining seconds
+/// once per second (then 0), and `recording-started` is emitted when the
+/// capture begins, or `recording-start-failed` with the error. A
+/// `template` gives the settings for this session only, and the template
+/// settings saved with the recording.
+fn start_after_countdown(
+    state: &AppState,
+    link: SessionLink,
+    app_handle: &AppHandle,
+    seconds: u32,
+    template: Option<(AppSettings, crate::recording::template::TemplateSettings)>,
+) -> Result<(), String> {
+    if seconds == 0 {
+        return begin_session(state, link, app_handle, RecordingState::Idle, template, None);
+    }
+    {
+        let mut rec_state = state.recording_state.lock().map_err(|e| e.to_string())?;
+        if *rec_state != RecordingState::Idle {
+            return Err("Already recording".to_string());
+        }
+        *rec_state = RecordingState::Countdown;
+    }
+    let generation = COUNTDOWN_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let cancelled = || COUNTDOWN_GENERATION.load(Ordering::SeqCst) != generation;
+        for remaining in (1..=seconds).rev() {
+            if cancelled() {
+                return;
+            }
+            let _ = handle.emit("recording-countdown", remaining);
+            crate::tray::set_countdown(&handle, remaining);
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+        if cancelled() {
+            return;
+        }
+        crate::tray::set_countdown(&handle, 0);
+        let _ = handle.emit("recording-countdown", 0);
+        if let Err(e) = begin_session(&handle.state::<AppState>(), link, &handle, RecordingState::Countdown, template, None) {
+            log::warn!("Failed to start recording after countdown: {}", e);
+            if let Ok(mut rec_state) = handle.state::<AppState>().recording_state.lock() {
+                if *rec_state == RecordingState::Countdown {
+                    *rec_state = RecordingState::Idle;
+                }
+            }
+            let _ = handle.emit("recording-start-failed", e);
+        }
+    });
+    Ok(())
+}
+
+/// Stop a running countdown before the capture begins.
+#[tauri::command]
+pub fn cancel_countdown(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
     let mut rec_state = state.recording_state.lock().map_err(|e| e.to_string())?;
-    if *rec_state != RecordingState::Idle {
+    if *rec_state != RecordingState::Countdown {
+        return Err("No countdown running".to_string());
+    }
+    COUNTDOWN_GENERATION.fetch_add(1, Ordering::SeqCst);
+    *rec_state = RecordingState::Idle;
+    drop(rec_state);
+    crate::tray::set_countdown(&app_handle, 0);
+    let _ = app_handle.emit("recording-countdown", 0);
+    Ok(())
+}
+
+/// Create and start the session; the recording state must be `from`. The
+/// session uses a `template`'s settings when given and saves its template
+/// settings with the recording, otherwise the saved settings. A `schedule` overrides the recording mode, and its duration
+/// becomes the maximum duration so the session stops on its own.
+fn begin_session(
+    state: &AppState,
+    link: SessionLink,
+    app_handle: &AppHandle,
+    from: RecordingState,
+    template: Option<(AppSettings, crate::recording::template::TemplateSettings)>,
+    schedule: Option<&ScheduledRecording>,
+) -> Result<(), String> {
+    let mut rec_state = state.recording_state.lock().map_err(|e| e.to_string())?;
+    if *rec_state != from {
         return Err("Already recording".to_string());
     }
 
-    let settings = state.settings.lock().map_err(|e| e.to_string())?;
-    let session = crate::recording::session::RecordingSession::new(&settings)
+    let (mut settings, template_settings) = match template {
+        Some((settings, template_settings)) => (settings, Some(template_settings)),
+        None => (state.settings.lock().map_err(|e| e.to_string())?.clone(), None),
+    };
+    if let Some(schedule) = schedule {
+        settings.recording.recording_mode = schedule.mode.clone();
+        settings.recording.max_duration_seconds = schedule.duration_seconds;
+    }
+    let mut session = crate::recording::session::RecordingSession::new(&settings)
         .map_err(|e| e.to_string())?;
+    match link {
+        SessionLink::None => {}
+        SessionLink::RetakeOf(recording_id) => session.set_retake_of(recording_id),
+        SessionLink::AppendTo(recording_id) => session.set_append_to(recording_id),
+    }
+    if let Some(template_settings) = template_settings {
+        session.set_template_settings(template_settings);
+    }
+    let handle = app_handle.clone();
+    session.set_live_feed(crate::recording::live_feed::LiveFeed::new(Arc::new(
+        move |events: &[crate::config::RecordingEvent]| {
+            let _ = handle.emit("recording-live-events", events);
+        },
+    )));
+
+    let handle = app_handle.clone();
+    session.set_disk_warning(Arc::new(move |warning: crate::recording::disk_space::DiskSpaceWarning| {
+        let _ = handle.emit("recording-warning", &warning);
+        if warning.level == crate::recording::disk_space::DiskSpaceLevel::Critical {
+            auto_stop(&handle, "low disk space");
+        }
+    }));
+    let handle = app_handle.clone();
+    session.set_limit_sink(Arc::new(move |notice: crate::recording::duration_limit::LimitNotice| {
+        use crate::recording::duration_limit::LimitNotice;
+        match notice {
+            LimitNotice::Warning { remaining_seconds } => {
+                let _ = handle.emit("recording-limit-warning", remaining_seconds);
+            }
+            LimitNotice::Reached { limit_seconds } => {
+                let _ = handle.emit("recording-limit-reached", limit_seconds);
+                auto_stop(&handle, "maximum duration");
+            }
+        }
+    }));
 
+    session.start().map_err(|e| e.to_string())?;
     let mut current = state.current_session.lock().map_err(|e| e.to_string())?;
     *current = Some(session);
 
-    if let Some(ref session) = *current {
-        session.start().map_err(|e| e.to_string())?;
+    *rec_state = RecordingState::Recording;
+    crate::tray::set_recording(app_handle, true);
+    let _ = app_handle.emit("recording-started", ());
+    Ok(())
+}
+
+/// Stop and finalize the current recording from the backend (`reason` is
+/// logged); `recording-auto-stopped` then carries the recording ID.
+fn auto_stop(app_handle: &AppHandle, reason: &'static str) {
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        match stop_recording(handle.state::<AppState>(), handle.clone()).await {
+            Ok(id) => {
+                log::info!("Recording {} stopped automatically ({})", id, reason);
+                let _ = handle.emit("recording-auto-stopped", id);
+            }
+            Err(e) => log::warn!("Failed to stop recording on {}: {}", reason, e),
+        }
+    });
+}
+
+/// Schedule a recording of `mode` starting at `start_time` (RFC 3339) for
+/// `duration_seconds`. The schedule is saved with the settings and started
+/// by [`run_scheduler`].
+#[tauri::command]
+pub fn schedule_recording(
+    start_time: String,
+    duration_seconds: u64,
+    mode: RecordingMode,
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<ScheduledRecording, String> {
+    let now = chrono::Local::now().fixed_offset();
+    let schedule = crate::recording::scheduler::new_schedule(&start_time, duration_seconds, mode, now)
+        .map_err(|e| e.to_string())?;
+    update_schedules(&state, &app_handle, |schedules| schedules.push(schedule.clone()))?;
+    log::info!("Recording scheduled at {} for {} s", schedule.start_time, schedule.duration_seconds);
+    Ok(schedule)
+}
+
+/// Scheduled recordings that haven't started yet, earliest first.
+#[tauri::command]
+pub fn list_scheduled_recordings(state: State<'_, AppState>) -> Result<Vec<ScheduledRecording>, String> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.scheduled_recordings.clone())
+}
+
+#[tauri::command]
+pub fn cancel_scheduled_recording(id: String, state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
+    let mut found = false;
+    update_schedules(&state, &app_handle, |schedules| {
+        let before = schedules.len();
+        schedules.retain(|s| s.id != id);
+        found = schedules.len() != before;
+    })?;
+    if !found {
+        return Err(format!("Scheduled recording not found: {}", id));
     }
+    Ok(())
+}
 
-    *rec_state = RecordingState::Recording;
+/// Apply `change` to the scheduled recordings, keep them sorted, save the
+/// settings and show the next one in the tray.
+fn update_schedules(
+    state: &AppState,
+    app_handle: &AppHandle,
+    change: impl FnOnce(&mut Vec<ScheduledRecording>),
+) -> Result<(), String> {
+    let next = {
+        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+        change(&mut settings.scheduled_recordings);
+        settings
+            .scheduled_recordings
+            .sort_by_key(|s| crate::recording::scheduler::parse_start(&s.start_time).ok());
+        save_settings_to_disk(&settings)?;
+        crate::recording::scheduler::next_upcoming(&settings.scheduled_recordings).map(crate::recording::scheduler::label)
+    };
+    crate::tray::set_next_schedule(app_handle, next);
     Ok(())
 }
 
+/// Start scheduled recordings when they are due, until the app exits.
+/// `scheduled-recording-started` carries the schedule when its session
+/// begins, `scheduled-recording-missed` a schedule that could not start
+/// (already recording, start failed, or its start time passed while the
+/// app wasn't running). The session stops after the schedule's duration
+/// like at the maximum duration (`recording-auto-stopped`).
+pub fn run_scheduler(app_handle: AppHandle) {
+    use crate::recording::scheduler;
+    tauri::async_runtime::spawn(async move {
+        let mut shown: Option<String> = None;
+        loop {
+            let state = app_handle.state::<AppState>();
+            let now = chrono::Local::now().fixed_offset();
+            let taken = state.settings.lock().ok().map(|mut settings| {
+                let (due, missed) = scheduler::take_due(&mut settings.scheduled_recordings, now);
+                if due.is_some() || !missed.is_empty() {
+                    if let Err(e) = save_settings_to_disk(&settings) {
+                        log::warn!("Failed to save scheduled recordings: {}", e);
+                    }
+                }
+                let next = scheduler::next_upcoming(&settings.scheduled_recordings).map(scheduler::label);
+                (due, missed, next)
+            });
+            if let Some((due, missed, next)) = taken {
+                for schedule in missed {
+                    log::warn!("Scheduled recording at {} was missed", schedule.start_time);
+                    let _ = app_handle.emit("scheduled-recording-missed", &schedule);
+                }
+                if let Some(schedule) = due {
+                    match begin_session(&state, SessionLink::None, &app_handle, RecordingState::Idle, None, Some(&schedule)) {
+                        Ok(()) => {
+                            log::info!("Scheduled recording started ({} s)", schedule.duration_seconds);
+                            crate::tray::notify(
+                                &app_handle,
+                                &format!("Scheduled recording started: {}", scheduler::label(&schedule)),
+                            );
+                            let _ = app_handle.emit("scheduled-recording-started", &schedule);
+                        }
+                        Err(e) => {
+                            log::warn!("Failed to start scheduled recording: {}", e);
+                            let _ = app_handle.emit("scheduled-recording-missed", &schedule);
+                        }
+                    }
+                }
+                if next != shown {
+                    crate::tray::set_next_schedule(&app_handle, next.clone());
+                    shown = next;
+                }
+            }
+            tokio::time::sleep(scheduler::CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Start recording from a template: find the target window, apply the
+/// template's effects/output settings to this recording only (the saved
+/// settings stay as they are; previews and exports of the recording use
+/// them too), then start after the template's countdown (see
+/// [`start_after_countdown`] for the events).
 #[tauri::command]
-pub fn stop_recording(state: State<'_, AppState>) -> Result<String, String> {
+pub fn start_recording_from_template(
+    template_id: String,
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    if *state.recording_state.lock().map_err(|e| e.to_string())? != RecordingState::Idle {
+        return Err("Already recording".to_string());
+    }
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    let template = settings
+        .templates
+        .iter()
+        .find(|t| t.id == template_id)
+        .cloned()
+        .ok_or_else(|| format!("Template not found: {}", template_id))?;
+    let windows = list_windows()?;
+    let session_settings =
+        crate::recording::template::apply_template(&settings, &template, &windows).map_err(|e| e.to_string())?;
+
+    let template_settings = crate::recording::template::TemplateSettings::from_template(&template);
+    start_after_countdown(
+        &state,
+        SessionLink::None,
+        &app_handle,
+        template.countdown_seconds,
+        Some((session_settings, template_settings)),
+    )
+}
+
+/// Stop recording and finalize it. Emits `recording-finalizing`
+/// ([`FinalizingProgress`]) while threads are flushed and files written.
+#[tauri::command]
+pub async fn stop_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<String, String> {
     // Check state and take session while holding locks briefly
     let session = {
         let mut rec_state = state.recording_state.lock().map_err(|e| e.to_string())?;
@@ -105,12 +463,25 @@ pub fn stop_recording(state: State<'_, AppState>) -> Result<String, String> {
         current.take()
     }; // Both locks released here
 
+    crate::tray::set_recording(&app_handle, false);
     if let Some(session) = session {
-        let recording_id = session.id().to_string();
+        let mut recording_id = session.id().to_string();
 
-        // stop() sleeps 500ms and writes meta.json - do this without holding locks
-        if let Err(e) = session.stop() {
-            log::error!("Error during session stop: {}", e);
+        // stop() joins capture threads and writes meta.json - off the async
+        // runtime so progress events reach the UI while it runs
+        let result = tokio::task::spawn_blocking(move || {
+            session.stop(&|stage: &str, progress: f64| {
+                let _ = app_handle.emit(
+                    "recording-finalizing",
+                    FinalizingProgress { stage: stage.to_string(), progress },
+                );
+            })
+        })
+        .await;
+        match result {
+            Ok(Err(e)) => log::error!("Error during session stop: {}", e),
+            Err(e) => log::error!("Session stop task failed: {}", e),
+            Ok(Ok(id)) => recording_id = id,
         }
 
         let mut rec_state = state.recording_state.lock().map_err(|e| e.to_string())?;
@@ -123,6 +494,63 @@ pub fn stop_recording(state: State<'_, AppState>) -> Result<String, String> {
     }
 }
 
+/// Check a hotkey before it's saved for `action` (format, our other
+/// hotkeys, Windows-reserved combinations, other apps). Returns it in the
+/// canonical form to save.
+#[tauri::command]
+pub fn validate_hotkey(
+    accelerator: String,
+    action: crate::shortcuts::HotkeyAction,
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let recording = state.settings.lock().map_err(|e| e.to_string())?.recording.clone();
+    crate::shortcuts::check(&app_handle, &accelerator, action, &recording)
+}
+
+/// Suspend our hotkeys while the settings page captures a new one, so a
+/// press of a current hotkey reaches the page. End with
+/// [`end_hotkey_capture`].
+#[tauri::command]
+pub fn begin_hotkey_capture(app_handle: AppHandle) -> Result<(), String> {
+    crate::shortcuts::suspend(&app_handle)
+}
+
+/// Register the saved hotkeys again after [`begin_hotkey_capture`].
+#[tauri::command]
+pub fn end_hotkey_capture(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
+    let recording = state.settings.lock().map_err(|e| e.to_string())?.recording.clone();
+    crate::shortcuts::register(&app_handle, &recording)
+}
+
+/// Stop the recording and delete it (the cancel hotkey). Nothing is
+/// finalized: a retake isn't linked and an appended session leaves its
+/// target untouched.
+#[tauri::command]
+pub async fn cancel_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
+    let session = {
+        let mut rec_state = state.recording_state.lock().map_err(|e| e.to_string())?;
+        if *rec_state != RecordingState::Recording && *rec_state != RecordingState::Paused {
+            return Err("Not recording".to_string());
+        }
+        *rec_state = RecordingState::Processing;
+
+        let mut current = state.current_session.lock().map_err(|e| e.to_string())?;
+        current.take()
+    };
+
+    crate::tray::set_recording(&app_handle, false);
+    let result = match session {
+        Some(session) => match tokio::task::spawn_blocking(move || session.discard()).await {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        },
+        None => Err("No active session".to_string()),
+    };
+    *state.recording_state.lock().map_err(|e| e.to_string())? = RecordingState::Idle;
+    result
+}
+
 #[tauri::command]
 pub fn pause_recording(state: State<'_, AppState>) -> Result<(), String> {
     let mut rec_state = state.recording_state.lock().map_err(|e| e.to_string())?;
@@ -157,9 +585,53 @@ pub fn get_recording_state(state: State<'_, AppState>) -> Result<RecordingState,
     Ok(rec_state.clone())
 }
 
+/// The recordings, narrowed to those matching `filter` when given.
+#[tauri::command]
+pub fn get_recordings_list(filter: Option<crate::config::RecordingFilter>) -> Result<Vec<RecordingInfo>, String> {
+    let mut recordings = crate::recording::session::list_recordings().map_err(|e| e.to_string())?;
+    if let Some(filter) = filter {
+        recordings.retain(|r| crate::recording::library::matches(&filter, r));
+    }
+    Ok(recordings)
+}
+
+/// One page of the recordings list: filtered, sorted (newest first by
+/// default), then `limit` recordings from `offset`.
+#[tauri::command]
+pub fn get_recordings_page(
+    filter: Option<crate::config::RecordingFilter>,
+    sort: Option<crate::config::RecordingSort>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> Result<crate::config::RecordingPage, String> {
+    use crate::recording::library;
+    let mut recordings = get_recordings_list(filter)?;
+    library::sort_recordings(&mut recordings, sort.unwrap_or_default());
+    Ok(library::page(recordings, offset.unwrap_or(0), limit.unwrap_or(library::MAX_PAGE_SIZE)))
+}
+
+/// Every tag used by a recording, for suggestions and the tag filter.
+#[tauri::command]
+pub fn list_recording_tags() -> Result<Vec<String>, String> {
+    let recordings = crate::recording::session::list_recordings().map_err(|e| e.to_string())?;
+    Ok(crate::recording::library::all_tags(&recordings))
+}
+
+/// Name a recording (a blank title shows the date again). Returns the saved title.
 #[tauri::command]
-pub fn get_recordings_list() -> Result<Vec<RecordingInfo>, String> {
-    crate::recording::session::list_recordings().map_err(|e| e.to_string())
+pub fn rename_recording(recording_id: String, title: String) -> Result<Option<String>, String> {
+    crate::recording::library::rename(&recording_id, &title).map_err(|e| e.to_string())
+}
+
+/// Replace a recording's tags. Returns them trimmed and deduplicated.
+#[tauri::command]
+pub fn set_recording_tags(recording_id: String, tags: Vec<String>) -> Result<Vec<String>, String> {
+    crate::recording::library::set_tags(&recording_id, &tags).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_recording_notes(recording_id: String, notes: String) -> Result<(), String> {
+    crate::recording::library::set_notes(&recording_id, &notes).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -167,6 +639,11 @@ pub async fn export_recording(
     recording_id: String,
     format: ExportFormat,
     quality: QualityPreset,
+    cuts: Option<Vec<crate::engine::timeline_cut::CutRange>>,
+    gif_options: Option<GifOptions>,
+    output_size: Option<OutputSize>,
+    plain: Option<bool>,
+    style_preset: Option<String>,
     state: State<'_, AppState>,
     app_handle: AppHandle,
 ) -> Result<(), String> {
@@ -178,6 +655,7 @@ pub async fn export_recording(
         }
         state.settings.lock().map_err(|e| e.to_string())?.clone()
     };
+    let settings = export_settings(settings, &recording_dir(&recording_id), style_preset.as_deref())?;
     {
         let mut prog = state.export_progress.lock().map_err(|e| e.to_string())?;
         *prog = Some(ExportProgress {
@@ -188,6 +666,7 @@ pub async fn export_recording(
     }
     let export_progress = state.export_progress.clone();
     let handle = app_handle;
+    let exported_id = recording_id.clone();
 
     // Fire-and-forget: tokio::spawn returns immediately, heavy work runs in background
     tokio::spawn(async move {
@@ -211,11 +690,18 @@ pub async fn export_recording(
                 })
             };
 
+            // Plain export always writes an MP4 of the raw frames
+            if plain.unwrap_or(false) {
+                return crate::export::encoder::export_plain(&recording_id, &quality, &settings, Some(&progress_cb));
+            }
             crate::export::encoder::export(
                 &recording_id,
                 &format,
                 &quality,
                 &settings,
+                &cuts.unwrap_or_default(),
+                &gif_options.unwrap_or_default(),
+                output_size.as_ref(),
                 Some(&progress_cb),
             )
         }).await;
@@ -226,6 +712,9 @@ pub async fn export_recording(
         }
         match result {
             Ok(Ok(path)) => {
+                if let Err(e) = crate::export::storage::mark_exported(&exported_id, &path) {
+                    log::warn!("Failed to mark {} as exported: {}", exported_id, e);
+                }
                 let _ = handle.emit("export-complete", serde_json::json!({ "output_path": path }));
             }
             Ok(Err(e)) => {
@@ -241,6 +730,80 @@ pub async fn export_recording(
     Ok(())
 }
 
+/// Export several recordings one after another in the background with the
+/// current settings. Emits `batch-export-progress` for the whole batch and
+/// `batch-export-complete` with a report; a failed recording doesn't stop
+/// the others. Shares the export slot with `export_recording`.
+#[tauri::command]
+pub async fn batch_export(
+    recording_ids: Vec<String>,
+    format: ExportFormat,
+    quality: QualityPreset,
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    if recording_ids.is_empty() {
+        return Err("No recordings selected".to_string());
+    }
+    let settings = {
+        let mut prog = state.export_progress.lock().map_err(|e| e.to_string())?;
+        if prog.is_some() {
+            return Err("Export already in progress".to_string());
+        }
+        *prog = Some(ExportProgress { stage: "starting".to_string(), progress: 0.0, output_path: None });
+        state.settings.lock().map_err(|e| e.to_string())?.clone()
+    };
+    let export_progress = state.export_progress.clone();
+
+    tokio::spawn(async move {
+        let ep = Arc::clone(&export_progress);
+        let h = app_handle.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let on_progress = move |p: crate::config::BatchExportProgress| {
+                if let Ok(mut lock) = ep.lock() {
+                    *lock = Some(ExportProgress { stage: p.stage.clone(), progress: p.overall, output_path: None });
+                }
+                let _ = h.emit("batch-export-progress", p);
+            };
+            crate::export::batch::run(
+                &recording_ids,
+                |id, progress| {
+                    let settings = crate::recording::template::with_recording_settings(settings.clone(), &recording_dir(id));
+                    let path = crate::export::encoder::export(
+                        id,
+                        &format,
+                        &quality,
+                        &settings,
+                        &[],
+                        &GifOptions::default(),
+                        None,
+                        Some(progress),
+                    )?;
+                    if let Err(e) = crate::export::storage::mark_exported(id, &path) {
+                        log::warn!("Failed to mark {} as exported: {}", id, e);
+                    }
+                    Ok(path)
+                },
+                on_progress,
+            )
+        })
+        .await;
+
+        if let Ok(mut lock) = export_progress.lock() {
+            *lock = None;
+        }
+        match result {
+            Ok(report) => {
+                let _ = app_handle.emit("batch-export-complete", report);
+            }
+            Err(e) => {
+                let _ = app_handle.emit("export-error", serde_json::json!({ "message": e.to_string() }));
+            }
+        }
+    });
+    Ok(())
+}
+
 #[tauri::command]
 pub fn get_export_progress(state: State<'_, AppState>) -> Result<Option<ExportProgress>, String> {
     let progress = state.export_progress.lock().map_err(|e| e.to_string())?;
@@ -253,25 +816,724 @@ pub fn get_settings(state: State<'_, AppState>) -> Result<AppSettings, String> {
     Ok(settings.clone())
 }
 
+/// Settings `recording_id` is previewed and exported with: the current
+/// settings with its template settings applied (Preview's default format
+/// and quality come from here).
+#[tauri::command]
+pub fn get_recording_settings(recording_id: String, state: State<'_, AppState>) -> Result<AppSettings, String> {
+    recording_settings(&state, &recording_id)
+}
+
 #[tauri::command]
 pub fn save_settings(
     new_settings: AppSettings,
     state: State<'_, AppState>,
+    app_handle: AppHandle,
 ) -> Result<(), String> {
-    save_settings_to_disk(&new_settings)?;
+    let mut new_settings = new_settings;
+    crate::shortcuts::validate(&new_settings.recording).map_err(|e| e.to_string())?;
+    let hotkeys_changed;
+    let replay_changed;
+    {
+        let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+        hotkeys_changed = crate::shortcuts::bindings(&settings.recording) != crate::shortcuts::bindings(&new_settings.recording);
+        replay_changed = settings.recording.replay_buffer_seconds != new_settings.recording.replay_buffer_seconds;
+        // Schedules change behind the settings page (started, missed):
+        // the schedule commands own them
+        new_settings.scheduled_recordings = settings.scheduled_recordings.clone();
+        save_settings_to_disk(&new_settings)?;
+        *settings = new_settings;
+    }
+    // Templates may have changed → rebuild the tray menu
+    if let Err(e) = crate::tray::refresh_menu(&app_handle) {
+        log::warn!("Failed to refresh tray menu: {}", e);
+    }
+    if replay_changed {
+        apply_replay_buffer(&state)?;
+    }
+    if hotkeys_changed {
+        let recording = state.settings.lock().map_err(|e| e.to_string())?.recording.clone();
+        crate::shortcuts::register(&app_handle, &recording)?;
+    }
+    Ok(())
+}
+
+/// Start, restart or stop the replay buffer for the current
+/// `replay_buffer_seconds` (see [`crate::recording::replay`]).
+pub fn apply_replay_buffer(state: &AppState) -> Result<(), String> {
+    let seconds = state.settings.lock().map_err(|e| e.to_string())?.recording.replay_buffer_seconds;
+    let mut replay = state.replay_buffer.lock().map_err(|e| e.to_string())?;
+    // Dropping the previous buffer stops its capture
+    *replay = (seconds > 0).then(|| crate::recording::replay::ReplayBuffer::start(seconds));
+    Ok(())
+}
+
+/// Start the replay buffer at launch when it is on.
+pub fn setup_replay_buffer(app_handle: &AppHandle) {
+    if let Err(e) = apply_replay_buffer(&app_handle.state::<AppState>()) {
+        log::warn!("Failed to start the replay buffer: {}", e);
+    }
+}
+
+/// Save the replay buffer as a new recording (the replay hotkey). Returns
+/// its ID.
+pub async fn save_replay(app: &AppHandle) -> Result<String, String> {
+    let handle = app.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let state = handle.state::<AppState>();
+        let replay = state.replay_buffer.lock().map_err(|e| e.to_string())?;
+        match replay.as_ref() {
+            Some(replay) => replay.save().map_err(|e| e.to_string()),
+            None => Err("The replay buffer is off".to_string()),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+    match &result {
+        Ok(id) => {
+            let _ = app.emit("replay-saved", id);
+        }
+        Err(e) => log::warn!("Saving the replay buffer failed: {}", e),
+    }
+    result
+}
+
+/// `settings` with the imported style preset `name` applied (unchanged
+/// when `name` is `None`).
+pub(crate) fn with_style_preset(mut settings: AppSettings, name: Option<&str>) -> Result<AppSettings, String> {
+    if let Some(name) = name {
+        let preset = crate::config::style_preset::find_preset(&crate::config::style_preset::presets_dir(), name)
+            .map_err(|e| e.to_string())?;
+        preset.apply_to(&mut settings);
+    }
+    Ok(settings)
+}
+
+/// Settings to export the recording in `recording_dir` with: the template
+/// settings saved with it, then the style preset `style_preset` on top.
+pub(crate) fn export_settings(
+    settings: AppSettings,
+    recording_dir: &std::path::Path,
+    style_preset: Option<&str>,
+) -> Result<AppSettings, String> {
+    with_style_preset(crate::recording::template::with_recording_settings(settings, recording_dir), style_preset)
+}
+
+/// Write the current style, effects and output settings to a shareable
+/// `.snappi-style` file at `path`.
+#[tauri::command]
+pub fn export_style_preset(
+    name: String,
+    description: Option<String>,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Preset name is empty".to_string());
+    }
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    crate::config::style_preset::StylePreset::from_settings(name.trim(), description, &settings)
+        .write(std::path::Path::new(&path))
+        .map_err(|e| e.to_string())
+}
+
+/// Import a `.snappi-style` file so exports can use it by name.
+#[tauri::command]
+pub fn import_style_preset(path: String) -> Result<crate::config::style_preset::StylePreset, String> {
+    crate::config::style_preset::import_preset(
+        &crate::config::style_preset::presets_dir(),
+        std::path::Path::new(&path),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_style_presets() -> Vec<crate::config::style_preset::StylePreset> {
+    crate::config::style_preset::list_presets(&crate::config::style_preset::presets_dir())
+}
+
+/// Replace the current style, effects and output settings with an
+/// imported preset and save them.
+#[tauri::command]
+pub fn apply_style_preset(name: String, state: State<'_, AppState>) -> Result<AppSettings, String> {
+    let current = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    let settings = with_style_preset(current, Some(&name))?;
+    save_settings_to_disk(&settings)?;
+    *state.settings.lock().map_err(|e| e.to_string())? = settings.clone();
+    Ok(settings)
+}
+
+#[tauri::command]
+pub fn list_profiles() -> Vec<crate::config::profiles::SettingsProfile> {
+    crate::config::profiles::list_profiles(&crate::config::profiles::profiles_dir())
+}
+
+/// Save the current style, effects and output settings as the profile
+/// `name`, replacing one with the same name.
+#[tauri::command]
+pub fn save_profile(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<crate::config::profiles::SettingsProfile, String> {
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    crate::config::profiles::save_profile(&crate::config::profiles::profiles_dir(), &name, &settings)
+        .map_err(|e| e.to_string())
+}
+
+/// Switch to the profile `name` (see [`crate::config::profiles`]) and save
+/// the settings.
+#[tauri::command]
+pub fn apply_profile(name: String, state: State<'_, AppState>) -> Result<AppSettings, String> {
+    let profile = crate::config::profiles::find_profile(&crate::config::profiles::profiles_dir(), &name)
+        .map_err(|e| e.to_string())?;
     let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
-    *settings = new_settings;
+    let mut updated = settings.clone();
+    profile.apply_to(&mut updated);
+    save_settings_to_disk(&updated)?;
+    *settings = updated.clone();
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn delete_profile(name: String) -> Result<(), String> {
+    crate::config::profiles::delete_profile(&crate::config::profiles::profiles_dir(), &name).map_err(|e| e.to_string())
+}
+
+/// True until settings have been saved once (no settings file yet).
+#[tauri::command]
+pub fn is_first_run() -> bool {
+    !settings_file_path().exists()
+}
+
+/// First-run checks: FFmpeg, input hooks, screen capture speed.
+#[tauri::command]
+pub async fn run_setup_diagnostics(state: State<'_, AppState>) -> Result<SetupDiagnostics, String> {
+    // The hook test and capture benchmark would interfere with a recording
+    if *state.recording_state.lock().map_err(|e| e.to_string())? != RecordingState::Idle {
+        return Err("Cannot run setup checks while recording".to_string());
+    }
+    tokio::task::spawn_blocking(crate::recording::setup::run_diagnostics)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Write the settings recommended by `run_setup_diagnostics` and return them.
+#[tauri::command]
+pub fn apply_recommended_settings(
+    diagnostics: SetupDiagnostics,
+    state: State<'_, AppState>,
+) -> Result<AppSettings, String> {
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let updated = crate::recording::setup::apply_recommendations(&settings, &diagnostics);
+    save_settings_to_disk(&updated)?;
+    *settings = updated.clone();
+    Ok(updated)
+}
+
+/// How long the main window gets to disappear before a screenshot
+const SCREENSHOT_HIDE_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Grab the screen and save it as a PNG styled like an export (see
+/// [`crate::export::screenshot`]). Returns the saved path.
+#[tauri::command]
+pub async fn take_screenshot(state: State<'_, AppState>, app_handle: AppHandle) -> Result<String, String> {
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    screenshot(settings, &app_handle).await
+}
+
+/// Take a screenshot with the main window hidden, so it isn't in the
+/// picture, and emit `screenshot-saved`. Shared with the hotkey.
+pub async fn screenshot(settings: AppSettings, app: &AppHandle) -> Result<String, String> {
+    let main = app
+        .get_webview_window("main")
+        .filter(|window| window.is_visible().unwrap_or(false));
+    if let Some(window) = &main {
+        let _ = window.hide();
+        tokio::time::sleep(SCREENSHOT_HIDE_DELAY).await;
+    }
+    let result = tokio::task::spawn_blocking(move || crate::export::screenshot::take_screenshot(&settings))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.map_err(|e| e.to_string()));
+    if let Some(window) = &main {
+        let _ = window.show();
+    }
+    match &result {
+        Ok(path) => {
+            let _ = app.emit("screenshot-saved", serde_json::json!({ "output_path": path }));
+        }
+        Err(e) => log::warn!("Screenshot failed: {}", e),
+    }
+    result
+}
+
+/// Start a scrolling screenshot of the recording target (see
+/// [`crate::recording::scroll_capture`]); the user scrolls, then calls
+/// `stop_scrolling_capture`.
+#[tauri::command]
+pub fn start_scrolling_capture(state: State<'_, AppState>) -> Result<(), String> {
+    if *state.recording_state.lock().map_err(|e| e.to_string())? != RecordingState::Idle {
+        return Err("Cannot start a scrolling capture while recording".to_string());
+    }
+    let mut capture = state.scroll_capture.lock().map_err(|e| e.to_string())?;
+    if capture.is_some() {
+        return Err("A scrolling capture is already running".to_string());
+    }
+    let mode = state.settings.lock().map_err(|e| e.to_string())?.recording.recording_mode.clone();
+    *capture = Some(crate::recording::scroll_capture::ScrollCapture::start(mode).map_err(|e| e.to_string())?);
     Ok(())
 }
 
+/// Stop the scrolling capture, save the stitched PNG to the output directory
+/// and emit `screenshot-saved`. Returns the saved path.
+#[tauri::command]
+pub async fn stop_scrolling_capture(state: State<'_, AppState>, app_handle: AppHandle) -> Result<String, String> {
+    let capture = state
+        .scroll_capture
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take()
+        .ok_or("No scrolling capture is running")?;
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    let path = tokio::task::spawn_blocking(move || {
+        let image = capture.stop()?;
+        crate::export::screenshot::save_png(&image, &settings, "scroll")
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    let _ = app_handle.emit("screenshot-saved", serde_json::json!({ "output_path": path }));
+    Ok(path)
+}
+
+/// Start streaming the recording target to the RTMP server in
+/// `OutputSettings::stream` (see [`crate::export::stream`]). Recording can
+/// run at the same time. If the stream ends on its own (connection lost,
+/// FFmpeg failed), `stream-ended` carries the error.
+#[tauri::command]
+pub fn start_stream(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
+    let mut live = state.live_stream.lock().map_err(|e| e.to_string())?;
+    if live.is_some() {
+        return Err("Already streaming".to_string());
+    }
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    let handle = app_handle.clone();
+    let on_end: crate::export::stream::EndSink = Arc::new(move |error: String| {
+        // Runs on the stream's own thread: drop the handle, don't join it
+        if let Ok(mut live) = handle.state::<AppState>().live_stream.lock() {
+            live.take();
+        }
+        let _ = handle.emit("stream-ended", error);
+    });
+    let stream = crate::export::stream::LiveStream::start(settings.recording.recording_mode.clone(), &settings, on_end)
+        .map_err(|e| e.to_string())?;
+    *live = Some(stream);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_stream(state: State<'_, AppState>) -> Result<(), String> {
+    let stream = state
+        .live_stream
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take()
+        .ok_or("Not streaming")?;
+    tokio::task::spawn_blocking(move || stream.stop())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Whether a live stream is running (e.g. after the page was reloaded).
+#[tauri::command]
+pub fn is_streaming(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.live_stream.lock().map_err(|e| e.to_string())?.is_some())
+}
+
+/// Let the user drag the recording area on screen (see
+/// [`crate::area_select`]). The selection is saved as `RecordingMode::Area`
+/// and returned; None when it was cancelled.
+#[tauri::command]
+pub async fn select_area(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Option<RecordingMode>, String> {
+    if *state.recording_state.lock().map_err(|e| e.to_string())? != RecordingState::Idle {
+        return Err("Cannot select an area while recording".to_string());
+    }
+    let Some([x, y, width, height]) = crate::area_select::select_area(&app_handle).await? else {
+        return Ok(None);
+    };
+    let mode = RecordingMode::Area { x, y, width, height };
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let mut updated = settings.clone();
+    updated.recording.recording_mode = mode.clone();
+    save_settings_to_disk(&updated)?;
+    *settings = updated;
+    Ok(Some(mode))
+}
+
+/// Called by the area selection overlay: `[left, top, right, bottom]` in
+/// its physical pixels, or None to cancel.
+#[tauri::command]
+pub fn finish_area_selection(rect: Option<[f64; 4]>, app_handle: AppHandle) {
+    crate::area_select::finish(&app_handle, rect);
+}
+
+/// The Windows accent color and light/dark theme, with a matching export
+/// background and click ring color.
+#[tauri::command]
+pub fn get_system_theme() -> crate::recording::system_theme::SystemTheme {
+    crate::recording::system_theme::read_system_theme()
+}
+
+/// Write the background and click ring color proposed by
+/// `get_system_theme` and return the settings.
+#[tauri::command]
+pub fn apply_system_theme(state: State<'_, AppState>) -> Result<AppSettings, String> {
+    let theme = crate::recording::system_theme::read_system_theme();
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let updated = crate::recording::system_theme::apply_theme(&settings, &theme);
+    save_settings_to_disk(&updated)?;
+    *settings = updated.clone();
+    Ok(updated)
+}
+
 #[tauri::command]
 pub fn delete_recording(recording_id: String) -> Result<(), String> {
     crate::recording::session::delete_recording(&recording_id).map_err(|e| e.to_string())
 }
 
+/// Open the folder of `recording_id` in the system file manager.
+#[tauri::command]
+pub fn open_recording_dir(recording_id: String) -> Result<(), String> {
+    let recording_dir = recording_dir(&recording_id);
+    if !recording_dir.join("meta.json").exists() {
+        return Err(format!("Recording not found: {}", recording_id));
+    }
+    tauri_plugin_opener::open_path(&recording_dir, None::<&str>).map_err(|e| e.to_string())
+}
+
+/// Show an exported file selected in the system file manager.
+#[tauri::command]
+pub fn reveal_export(path: String) -> Result<(), String> {
+    let path = std::path::PathBuf::from(path);
+    if !path.exists() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    tauri_plugin_opener::reveal_item_in_dir(&path).map_err(|e| e.to_string())
+}
+
+/// Put an exported file on the clipboard (and GIF/PNG exports as image
+/// data too, see [`crate::export::clipboard`]).
+#[tauri::command]
+pub async fn copy_export_to_clipboard(path: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || crate::export::clipboard::copy_file(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Upload an exported file to `target` (see [`crate::export::share`]),
+/// emitting `upload-progress` as it goes. Returns the URL to share.
+#[tauri::command]
+pub async fn upload_export(
+    path: String,
+    target: crate::config::ShareTarget,
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let share = state.settings.lock().map_err(|e| e.to_string())?.share.clone();
+    tokio::task::spawn_blocking(move || {
+        let file = std::path::Path::new(&path);
+        let total_bytes = std::fs::metadata(file).map(|m| m.len()).unwrap_or(0);
+        // Emit at most once per percent
+        let last_percent = AtomicU64::new(u64::MAX);
+        let progress = |uploaded_bytes: u64| {
+            let percent = uploaded_bytes * 100 / total_bytes.max(1);
+            if last_percent.swap(percent, Ordering::Relaxed) != percent {
+                let _ = app_handle.emit(
+                    "upload-progress",
+                    crate::config::UploadProgress { target, path: path.clone(), uploaded_bytes, total_bytes },
+                );
+            }
+        };
+        crate::export::share::upload(file, target, &share, &progress)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// Connect YouTube uploads: opens Google's consent page in the browser and
+/// stores the refresh token once the user allows access.
+#[tauri::command]
+pub async fn authorize_youtube(state: State<'_, AppState>) -> Result<AppSettings, String> {
+    let youtube = state.settings.lock().map_err(|e| e.to_string())?.share.youtube.clone();
+    let refresh_token = tokio::task::spawn_blocking(move || {
+        crate::export::share::authorize_youtube(&youtube, |url| {
+            tauri_plugin_opener::open_url(url, None::<&str>).map_err(|e| anyhow::anyhow!("{}", e))
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let mut updated = settings.clone();
+    updated.share.youtube.refresh_token = refresh_token;
+    save_settings_to_disk(&updated)?;
+    *settings = updated.clone();
+    Ok(updated)
+}
+
+/// Remove orphaned export temp dirs, stale preview proxies and caches of
+/// deleted recordings (see [`crate::export::cleanup`]).
+#[tauri::command]
+pub async fn cleanup_workspace(state: State<'_, AppState>) -> Result<crate::config::CleanupReport, String> {
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    tokio::task::spawn_blocking(move || crate::export::cleanup::cleanup_workspace(&settings))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Pack a recording into a `.snappi` archive (see
+/// [`crate::export::project_archive`]). Without `path` it goes to the output
+/// directory. Returns the archive's path.
+#[tauri::command]
+pub async fn export_project(
+    recording_id: String,
+    path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    tokio::task::spawn_blocking(move || {
+        let output = path.as_deref().map(std::path::Path::new);
+        crate::export::project_archive::export_project(&recording_id, output, &settings)
+            .map(|p| p.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// Restore a recording from a `.snappi` archive. Returns its ID.
+#[tauri::command]
+pub async fn import_project(path: String) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || crate::export::project_archive::import_project(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Import a video file (MP4, WebM, …) as a recording without input events
+/// (see [`crate::recording::video_import`]). Returns its ID.
+#[tauri::command]
+pub async fn import_video(path: String) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || crate::recording::video_import::import_video(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Disk usage of each recording and of the whole library.
+#[tauri::command]
+pub async fn get_storage_usage() -> Result<crate::config::StorageUsage, String> {
+    tokio::task::spawn_blocking(crate::export::storage::storage_usage)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Apply the storage cleanup policies now (they also run periodically, see
+/// [`run_storage_policies`]).
+#[tauri::command]
+pub async fn apply_storage_policies(state: State<'_, AppState>) -> Result<crate::config::StoragePolicyReport, String> {
+    let storage = state.settings.lock().map_err(|e| e.to_string())?.storage.clone();
+    tokio::task::spawn_blocking(move || crate::export::storage::apply_policies(&storage))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Apply the storage cleanup policies every
+/// [`POLICY_INTERVAL`](crate::export::storage::POLICY_INTERVAL), starting at
+/// launch, until the app exits.
+pub fn run_storage_policies(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let storage = app_handle.state::<AppState>().settings.lock().ok().map(|s| s.storage.clone());
+            if let Some(storage) = storage {
+                if let Err(e) =
+                    tokio::task::spawn_blocking(move || crate::export::storage::apply_policies(&storage)).await
+                {
+                    log::warn!("Storage policies failed: {}", e);
+                }
+            }
+            tokio::time::sleep(crate::export::storage::POLICY_INTERVAL).await;
+        }
+    });
+}
+
+/// Link recordings as takes of the same scenario (see `recording::takes`).
+#[tauri::command]
+pub fn link_takes(recording_ids: Vec<String>, scenario: Option<String>) -> Result<crate::config::TakeInfo, String> {
+    crate::recording::takes::link_takes(&recording_ids, scenario.as_deref()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn unlink_take(recording_id: String) -> Result<(), String> {
+    crate::recording::takes::unlink_take(&recording_id).map_err(|e| e.to_string())
+}
+
+/// Mark the take to use from its group.
+#[tauri::command]
+pub fn choose_take(recording_id: String) -> Result<(), String> {
+    crate::recording::takes::choose_take(&recording_id).map_err(|e| e.to_string())
+}
+
+/// Frame on screen at `time_ms` on the recording timeline.
+#[tauri::command]
+pub fn map_time_to_frame(recording_id: String, time_ms: u64) -> Result<u64, String> {
+    let index = crate::export::encoder::load_frame_index(&recording_id).map_err(|e| e.to_string())?;
+    Ok(index.frame_at(time_ms))
+}
+
+/// Recording time (ms) at which `frame` starts being shown.
+#[tauri::command]
+pub fn map_frame_to_time(recording_id: String, frame: u64) -> Result<u64, String> {
+    let index = crate::export::encoder::load_frame_index(&recording_id).map_err(|e| e.to_string())?;
+    Ok(index.time_of(frame))
+}
+
+#[tauri::command]
+pub fn get_redactions(recording_id: String) -> Result<Vec<crate::engine::redaction::Redaction>, String> {
+    Ok(crate::export::encoder::get_redactions(&recording_id))
+}
+
+#[tauri::command]
+pub fn save_redactions(
+    recording_id: String,
+    redactions: Vec<crate::engine::redaction::Redaction>,
+) -> Result<(), String> {
+    crate::export::encoder::save_redactions(&recording_id, &redactions).map_err(|e| e.to_string())
+}
+
+/// Scan a recording for things that may leak when it is shared (see
+/// [`crate::engine::privacy_review`]).
+#[tauri::command]
+pub async fn review_recording_privacy(
+    recording_id: String,
+) -> Result<crate::engine::privacy_review::PrivacyReport, String> {
+    tokio::task::spawn_blocking(move || crate::export::encoder::review_privacy(&recording_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_annotations(recording_id: String) -> Result<Vec<crate::engine::annotation::Annotation>, String> {
+    Ok(crate::export::encoder::get_annotations(&recording_id))
+}
+
+#[tauri::command]
+pub fn save_annotations(
+    recording_id: String,
+    annotations: Vec<crate::engine::annotation::Annotation>,
+) -> Result<(), String> {
+    crate::export::encoder::save_annotations(&recording_id, &annotations).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_bookmarks(recording_id: String) -> Result<Vec<crate::config::Bookmark>, String> {
+    Ok(crate::export::encoder::get_bookmarks(&recording_id))
+}
+
+/// Add a bookmark at `time_ms` and return it with its new ID.
+#[tauri::command]
+pub fn add_bookmark(
+    recording_id: String,
+    time_ms: u64,
+    label: String,
+    color: Option<String>,
+) -> Result<crate::config::Bookmark, String> {
+    let bookmark = crate::config::Bookmark { id: uuid::Uuid::new_v4().to_string(), time_ms, label, color };
+    let mut bookmarks = crate::export::encoder::get_bookmarks(&recording_id);
+    bookmarks.push(bookmark.clone());
+    bookmarks.sort_by_key(|b| b.time_ms);
+    crate::export::encoder::save_bookmarks(&recording_id, &bookmarks).map_err(|e| e.to_string())?;
+    Ok(bookmark)
+}
+
+/// Replace the bookmark with the same ID (time, label and color).
+#[tauri::command]
+pub fn update_bookmark(recording_id: String, bookmark: crate::config::Bookmark) -> Result<(), String> {
+    let mut bookmarks = crate::export::encoder::get_bookmarks(&recording_id);
+    let existing = bookmarks
+        .iter_mut()
+        .find(|b| b.id == bookmark.id)
+        .ok_or_else(|| format!("Bookmark not found: {}", bookmark.id))?;
+    *existing = bookmark;
+    bookmarks.sort_by_key(|b| b.time_ms);
+    crate::export::encoder::save_bookmarks(&recording_id, &bookmarks).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_bookmark(recording_id: String, bookmark_id: String) -> Result<(), String> {
+    let mut bookmarks = crate::export::encoder::get_bookmarks(&recording_id);
+    let before = bookmarks.len();
+    bookmarks.retain(|b| b.id != bookmark_id);
+    if bookmarks.len() == before {
+        return Err(format!("Bookmark not found: {}", bookmark_id));
+    }
+    crate::export::encoder::save_bookmarks(&recording_id, &bookmarks).map_err(|e| e.to_string())
+}
+
+/// Per-scene background, border radius and effect toggles for export.
+#[tauri::command]
+pub fn get_scene_styles(recording_id: String) -> Result<Vec<crate::engine::scene_style::SceneStyleOverride>, String> {
+    Ok(crate::export::encoder::get_scene_styles(&recording_id))
+}
+
+#[tauri::command]
+pub fn save_scene_styles(
+    recording_id: String,
+    overrides: Vec<crate::engine::scene_style::SceneStyleOverride>,
+) -> Result<(), String> {
+    crate::export::encoder::save_scene_styles(&recording_id, &overrides).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn load_keyframes(recording_id: String) -> Result<Vec<crate::engine::zoom_planner::ZoomKeyframe>, String> {
+    Ok(crate::export::encoder::get_keyframes(&recording_id))
+}
+
+#[tauri::command]
+pub fn save_keyframes(
+    recording_id: String,
+    keyframes: Vec<crate::engine::zoom_planner::ZoomKeyframe>,
+) -> Result<(), String> {
+    crate::export::encoder::save_keyframes(&recording_id, &keyframes).map_err(|e| e.to_string())
+}
+
+/// Scene edits saved by `apply_scene_edits` (empty when none).
+#[tauri::command]
+pub fn load_scene_edits(recording_id: String) -> Result<Vec<crate::engine::scene_splitter::SceneEditOp>, String> {
+    Ok(crate::export::encoder::get_scene_edits(&recording_id))
+}
+
+/// Low-resolution MP4 for playback in the editor (cached; built on first use).
+#[tauri::command]
+pub async fn generate_preview(recording_id: String, state: State<'_, AppState>) -> Result<String, String> {
+    let settings = recording_settings(&state, &recording_id)?;
+    tokio::task::spawn_blocking(move || crate::export::encoder::generate_preview(&recording_id, &settings))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_recording_thumbnail(recording_id: String) -> Result<String, String> {
-    crate::export::encoder::generate_thumbnail(&recording_id).map_err(|e| e.to_string())
+    crate::export::encoder::ensure_thumbnail(&recording_id).map_err(|e| e.to_string())
 }
 
 /// Get zoom keyframes for a recording (for Timeline UI).
@@ -280,7 +1542,7 @@ pub fn get_zoom_keyframes(
     recording_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<crate::engine::zoom_planner::ZoomKeyframe>, String> {
-    let settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let settings = recording_settings(&state, &recording_id)?;
     crate::export::encoder::generate_keyframes_for_recording(&recording_id, &settings)
         .map_err(|e| e.to_string())
 }
@@ -291,28 +1553,81 @@ pub fn get_recording_scenes(
     recording_id: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<crate::engine::scene_splitter::Scene>, String> {
-    let settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let settings = recording_settings(&state, &recording_id)?;
     crate::export::encoder::get_recording_scenes(&recording_id, &settings)
         .map_err(|e| e.to_string())
 }
 
+/// Render before/after preview images for the boundary after `scene_id`
+/// (Timeline UI shows them when deciding to merge/split).
+#[tauri::command]
+pub fn get_scene_boundary_preview(
+    recording_id: String,
+    scene_id: u32,
+    state: State<'_, AppState>,
+) -> Result<crate::config::SceneBoundaryPreview, String> {
+    let settings = recording_settings(&state, &recording_id)?;
+    crate::export::encoder::generate_scene_boundary_preview(&recording_id, scene_id, &settings)
+        .map_err(|e| e.to_string())
+}
+
+/// Render the frame at `time_ms` as it would be exported (Timeline scrubbing).
+/// Returns the path of the preview JPEG.
+#[tauri::command]
+pub fn render_preview_frame(
+    recording_id: String,
+    time_ms: u64,
+    keyframes: Option<Vec<crate::engine::zoom_planner::ZoomKeyframe>>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let settings = recording_settings(&state, &recording_id)?;
+    crate::export::encoder::render_preview_frame(&recording_id, time_ms, keyframes, &settings)
+        .map_err(|e| e.to_string())
+}
+
+/// Suggest cut ranges for long idle periods with no screen changes.
+#[tauri::command]
+pub fn get_suggested_cuts(
+    recording_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::engine::timeline_cut::CutRange>, String> {
+    let settings = recording_settings(&state, &recording_id)?;
+    crate::export::encoder::suggest_cuts_for_recording(&recording_id, &settings)
+        .map_err(|e| e.to_string())
+}
+
 /// Get recording events for Timeline UI visualization.
 #[tauri::command]
 pub fn get_recording_events(
     recording_id: String,
+    state: State<'_, AppState>,
 ) -> Result<Vec<crate::config::TimelineEvent>, String> {
-    crate::export::encoder::get_recording_events(&recording_id)
+    let settings = recording_settings(&state, &recording_id)?;
+    crate::export::encoder::get_recording_events(&recording_id, &settings)
+        .map_err(|e| e.to_string())
+}
+
+/// Compare two recordings: aligned stats and a similarity score.
+#[tauri::command]
+pub fn compare_recordings(
+    id_a: String,
+    id_b: String,
+    state: State<'_, AppState>,
+) -> Result<crate::config::RecordingComparison, String> {
+    let settings = state.settings.lock().map_err(|e| e.to_string())?;
+    crate::export::encoder::compare_recordings(&id_a, &id_b, &settings)
         .map_err(|e| e.to_string())
 }
 
 /// Apply scene edits (merge/split) and get updated scenes + keyframes.
+/// The edit list is saved and replayed on later loads and exports.
 #[tauri::command]
 pub fn apply_scene_edits(
     recording_id: String,
     edits: Vec<crate::engine::scene_splitter::SceneEditOp>,
     state: State<'_, AppState>,
 ) -> Result<serde_json::Value, String> {
-    let settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let settings = recording_settings(&state, &recording_id)?;
     let (scenes, keyframes) =
         crate::export::encoder::apply_scene_edits_for_recording(&recording_id, edits, &settings)
             .map_err(|e| e.to_string())?;
@@ -331,7 +1646,7 @@ pub fn compute_activity_center(
     end_ms: u64,
     state: State<'_, AppState>,
 ) -> Result<serde_json::Value, String> {
-    let settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let settings = recording_settings(&state, &recording_id)?;
     let (cx, cy, zoom) =
         crate::export::encoder::compute_activity_center_for_recording(
             &recording_id, start_ms, end_ms, &settings,
@@ -344,6 +1659,16 @@ pub fn compute_activity_center(
     }))
 }
 
+/// Compact an edited keyframe list (merge equivalent keyframes, enforce
+/// min spacing). Pinned keyframes are kept as-is.
+#[tauri::command]
+pub fn optimize_keyframes(
+    keyframes: Vec<crate::engine::zoom_planner::ZoomKeyframe>,
+) -> Result<Vec<crate::engine::zoom_planner::ZoomKeyframe>, String> {
+    use crate::engine::keyframe_optimizer::{self, OptimizeConfig};
+    Ok(keyframe_optimizer::optimize_keyframes(&keyframes, &OptimizeConfig::default()))
+}
+
 /// Export with custom keyframes from timeline UI.
 #[tauri::command]
 pub async fn export_with_keyframes(
@@ -351,6 +1676,10 @@ pub async fn export_with_keyframes(
     keyframes: Vec<crate::engine::zoom_planner::ZoomKeyframe>,
     format: ExportFormat,
     quality: QualityPreset,
+    cuts: Option<Vec<crate::engine::timeline_cut::CutRange>>,
+    gif_options: Option<GifOptions>,
+    output_size: Option<OutputSize>,
+    style_preset: Option<String>,
     state: State<'_, AppState>,
     app_handle: AppHandle,
 ) -> Result<(), String> {
@@ -361,6 +1690,7 @@ pub async fn export_with_keyframes(
         }
         state.settings.lock().map_err(|e| e.to_string())?.clone()
     };
+    let settings = export_settings(settings, &recording_dir(&recording_id), style_preset.as_deref())?;
     {
         let mut prog = state.export_progress.lock().map_err(|e| e.to_string())?;
         *prog = Some(ExportProgress {
@@ -371,6 +1701,7 @@ pub async fn export_with_keyframes(
     }
     let export_progress = state.export_progress.clone();
     let handle = app_handle;
+    let exported_id = recording_id.clone();
 
     tokio::spawn(async move {
         let ep = Arc::clone(&export_progress);
@@ -399,6 +1730,9 @@ pub async fn export_with_keyframes(
                 &format,
                 &quality,
                 &settings,
+                &cuts.unwrap_or_default(),
+                &gif_options.unwrap_or_default(),
+                output_size.as_ref(),
                 Some(&progress_cb),
             )
         }).await;
@@ -408,6 +1742,9 @@ pub async fn export_with_keyframes(
         }
         match result {
             Ok(Ok(path)) => {
+                if let Err(e) = crate::export::storage::mark_exported(&exported_id, &path) {
+                    log::warn!("Failed to mark {} as exported: {}", exported_id, e);
+                }
                 let _ = handle.emit("export-complete", serde_json::json!({ "output_path": path }));
             }
             Ok(Err(e)) => {
@@ -535,6 +1872,42 @@ mod tests {
         assert!((loaded.effects.max_zoom - 4.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_export_uses_template_settings_saved_with_recording() {
+        let mut effects = AppSettings::default().effects;
+        effects.auto_zoom_enabled = false;
+        effects.max_zoom = 4.5;
+        let template = crate::config::RecordingTemplate {
+            id: "t".to_string(),
+            name: "Docs".to_string(),
+            window_title_pattern: None,
+            countdown_seconds: 0,
+            effects: Some(effects),
+            output_format: Some(ExportFormat::Gif),
+            output_quality: Some(QualityPreset::Lightweight),
+        };
+        // What a session started from the template saves
+        let recording = tempfile::TempDir::new().expect("create temp dir");
+        crate::recording::template::TemplateSettings::from_template(&template)
+            .save(recording.path())
+            .expect("save template settings");
+
+        let current = AppSettings::default();
+        assert!(current.effects.auto_zoom_enabled);
+        let settings = export_settings(current.clone(), recording.path(), None).expect("export settings");
+        assert!(!settings.effects.auto_zoom_enabled);
+        assert!((settings.effects.max_zoom - 4.5).abs() < 0.01);
+        assert_eq!(settings.output.default_format, ExportFormat::Gif);
+        assert_eq!(settings.output.default_quality, QualityPreset::Lightweight);
+
+        // A recording not started from a template exports with the current settings
+        let other = tempfile::TempDir::new().expect("create temp dir");
+        let settings = export_settings(current.clone(), other.path(), None).expect("export settings");
+        assert!(settings.effects.auto_zoom_enabled);
+        assert!((settings.effects.max_zoom - current.effects.max_zoom).abs() < 0.01);
+        assert_eq!(settings.output.default_format, current.output.default_format);
+    }
+
     #[test]
     fn test_settings_path_exists() {
         let path = settings_file_path();
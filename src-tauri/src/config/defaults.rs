@@ -8,6 +8,22 @@ impl Default for AppSettings {
             style: StyleSettings::default(),
             effects: EffectsSettings::default(),
             output: OutputSettings::default(),
+            templates: Vec::new(),
+            scheduled_recordings: Vec::new(),
+            storage: StorageSettings::default(),
+            share: ShareSettings::default(),
+            stream: StreamSettings::default(),
+        }
+    }
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            delete_frames_after_export: false,
+            keep_last_recordings: 0, // unlimited
+            max_library_gb: 0.0,     // unlimited
+            delete_unexported: false,
         }
     }
 }
@@ -16,12 +32,22 @@ impl Default for RecordingSettings {
     fn default() -> Self {
         Self {
             hotkey: "Ctrl+Shift+R".to_string(),
+            pause_hotkey: String::new(),
+            cancel_hotkey: String::new(),
+            screenshot_hotkey: "Ctrl+Shift+S".to_string(),
+            replay_hotkey: String::new(),
             fps: 60,
             capture_system_audio: true,
             capture_microphone: false,
-            max_duration_seconds: 300, // 5 minutes
+            max_duration_seconds: 0, // unlimited
             recording_mode: RecordingMode::Display,
             record_key_labels: false,
+            session_webhook_url: None,
+            frame_storage: FrameStorage::default(),
+            min_free_disk_mb: 1024,
+            countdown_seconds: 0,
+            timelapse_fps: 0,
+            replay_buffer_seconds: 0,
         }
     }
 }
@@ -41,6 +67,120 @@ impl Default for StyleSettings {
             cursor_image_path: None,
             cursor_hotspot_x: 0,
             cursor_hotspot_y: 0,
+            disclaimer: DisclaimerSettings::default(),
+            key_badge: KeyBadgeStyle::default(),
+            spotlight: SpotlightSettings::default(),
+            watermark: WatermarkSettings::default(),
+            intro: TitleCardSettings::default(),
+            outro: TitleCardSettings::default(),
+            padding: CanvasPadding::default(),
+            window_chrome: WindowChromeSettings::default(),
+            progress_bar: ProgressBarSettings::default(),
+        }
+    }
+}
+
+impl Default for StreamSettings {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            stream_key: String::new(),
+            fps: 30,
+            video_bitrate_kbps: 4500,
+            apply_style: true,
+        }
+    }
+}
+
+impl Default for ProgressBarSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            height: 8,
+            color: [255, 255, 255, 230],
+            track_color: [0, 0, 0, 80],
+        }
+    }
+}
+
+impl Default for WindowChromeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            theme: ChromeTheme::Light,
+            title_bar_height: 40,
+            show_buttons: true,
+            url: String::new(),
+        }
+    }
+}
+
+impl Default for CanvasPadding {
+    fn default() -> Self {
+        Self::Pixels { value: 64 }
+    }
+}
+
+impl Default for SpotlightSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            radius: 160.0,
+            dim_opacity: 0.55,
+            click_pulse: 0.35,
+        }
+    }
+}
+
+impl Default for WatermarkSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            image_path: None,
+            position: WatermarkPosition::BottomRight,
+            opacity: 0.8,
+            margin: 32.0,
+            width: 160.0,
+        }
+    }
+}
+
+impl Default for TitleCardSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            duration_ms: 2000,
+            image_path: None,
+            title: String::new(),
+            subtitle: String::new(),
+            font_size: 64.0,
+            text_color: [255, 255, 255, 255],
+            background: None,
+        }
+    }
+}
+
+impl Default for KeyBadgeStyle {
+    fn default() -> Self {
+        Self {
+            font_size: 20.0,
+            text_color: [255, 255, 255, 255],
+            background_color: [30, 30, 30, 200],
+            position: BadgePosition::BottomCenter,
+        }
+    }
+}
+
+impl Default for DisclaimerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            text: "キーボード・マウス操作を記録しています".to_string(),
+            duration_ms: 5000,
+            position: RibbonPosition::Bottom,
+            font_size: 22.0,
+            text_color: [255, 255, 255, 255],
+            background_color: [0, 0, 0, 170],
         }
     }
 }
@@ -61,12 +201,32 @@ impl Default for EffectsSettings {
             smart_zoom_enabled: true,
             motion_blur_enabled: false,
             frame_diff_enabled: true,
+            frame_diff_pixel_threshold: 10,
+            frame_diff_min_region_size: 50,
+            frame_diff_max_change_fraction: 0.5,
             idle_zoom_out_ms: 5000,
             idle_overview_ms: 8000,
             min_workarea_dwell_ms: 2000,
             min_window_dwell_ms: 1500,
             cluster_lifetime_ms: 5000,
             cluster_stability_ms: 1000,
+            move_thin_distance_px: 3.0,
+            move_thin_interval_ms: 200,
+            drag_min_distance_px: 20.0,
+            drag_min_duration_ms: 0,
+            click_ring_buttons: vec!["left".to_string(), "right".to_string(), "middle".to_string()],
+            click_ring_alt_buttons: Vec::new(),
+            click_ring_color: [59, 130, 246, 180],
+            click_ring_alt_color: [249, 115, 22, 180],
+            ui_highlight_enabled: false,
+            typed_text_overlay: false,
+            auto_redact_inputs: false,
+            plugins: Vec::new(),
+            script_path: None,
+            overview_zoom: None,
+            profile: EffectsProfile::Standard,
+            reduced_motion: false,
+            zoom_excluded_apps: Vec::new(),
         }
     }
 }
@@ -80,6 +240,29 @@ impl Default for OutputSettings {
             default_format: ExportFormat::Mp4,
             default_quality: QualityPreset::Social,
             save_directory: save_dir.to_string_lossy().to_string(),
+            auto_trim_idle: false,
+            auto_trim_min_idle_ms: 5000,
+            idle_speedup: 1.0,
+            idle_speedup_min_ms: 3000,
+            aspect: OutputAspect::default(),
+            aspect_fit: AspectFit::default(),
+            filename_template: default_filename_template(),
+            audio_only_codec: AudioOnlyCodec::default(),
+            compositor_backend: CompositorBackend::default(),
+            split_max_minutes: 0,
+            bookmark_chapters: false,
+        }
+    }
+}
+
+impl Default for GifOptions {
+    fn default() -> Self {
+        Self {
+            fps: 15,
+            max_width: 640,
+            dither: GifDither::Sierra2_4a,
+            loop_count: 0,
+            max_file_size_kb: None,
         }
     }
 }
@@ -99,8 +282,25 @@ pub struct OutputStyle {
     pub click_ring_max_radius: f64,
     pub click_ring_duration_ms: u64,
     pub click_ring_color: [u8; 4],
+    pub click_ring_alt_color: [u8; 4],
     pub click_ring_stroke_width: f64,
     pub key_badge_duration_ms: u64,
+    pub key_badge: KeyBadgeStyle,
+    /// Viewport crop aspect for zoom-crop output (see `EncodingParams::crop_aspect`)
+    pub crop_aspect: Option<f64>,
+    /// Input-captured ribbon; `None` when disabled
+    pub disclaimer: Option<DisclaimerSettings>,
+    /// Dim-around-cursor effect; `None` when disabled
+    pub spotlight: Option<SpotlightSettings>,
+    /// Logo overlay; `None` when disabled or no image is set
+    pub watermark: Option<WatermarkSettings>,
+    /// Cards before / after the recording; `None` when disabled or empty
+    pub intro: Option<TitleCardSettings>,
+    pub outro: Option<TitleCardSettings>,
+    /// Mock window title bar above the recording; `None` when disabled
+    pub window_chrome: Option<WindowChromeSettings>,
+    /// Playback position bar along the bottom; `None` when disabled
+    pub progress_bar: Option<ProgressBarSettings>,
 }
 
 impl Default for OutputStyle {
@@ -123,8 +323,18 @@ impl Default for OutputStyle {
             click_ring_max_radius: 30.0,
             click_ring_duration_ms: 400,
             click_ring_color: [59, 130, 246, 180],
+            click_ring_alt_color: [249, 115, 22, 180],
             click_ring_stroke_width: 2.5,
             key_badge_duration_ms: 1500,
+            key_badge: KeyBadgeStyle::default(),
+            crop_aspect: None,
+            disclaimer: None,
+            spotlight: None,
+            watermark: None,
+            intro: None,
+            outro: None,
+            window_chrome: None,
+            progress_bar: None,
         }
     }
 }
@@ -132,6 +342,7 @@ impl Default for OutputStyle {
 impl OutputStyle {
     pub fn from_settings(params: &EncodingParams, settings: &AppSettings) -> Self {
         let shadow_enabled = settings.style.shadow_enabled;
+        let high_visibility = settings.effects.profile == EffectsProfile::HighVisibility;
         Self {
             output_width: params.width.unwrap_or(1920),
             output_height: params.height.unwrap_or(1080),
@@ -142,12 +353,26 @@ impl OutputStyle {
             shadow_offset_y: if shadow_enabled { settings.style.shadow_offset_y } else { 0.0 },
             shadow_color: if shadow_enabled { [0, 0, 0, 80] } else { [0, 0, 0, 0] },
             background: settings.style.background.clone(),
-            cursor_size_multiplier: 1.5,
-            click_ring_max_radius: 30.0,
-            click_ring_duration_ms: 400,
-            click_ring_color: [59, 130, 246, 180],
-            click_ring_stroke_width: 2.5,
+            cursor_size_multiplier: if high_visibility { 2.25 } else { 1.5 },
+            click_ring_max_radius: if high_visibility { 45.0 } else { 30.0 },
+            click_ring_duration_ms: if high_visibility { 600 } else { 400 },
+            click_ring_color: if high_visibility { [255, 214, 0, 255] } else { settings.effects.click_ring_color },
+            click_ring_alt_color: if high_visibility { [255, 0, 170, 255] } else { settings.effects.click_ring_alt_color },
+            click_ring_stroke_width: if high_visibility { 5.0 } else { 2.5 },
             key_badge_duration_ms: 1500,
+            key_badge: settings.style.key_badge.clone(),
+            crop_aspect: params.crop_aspect,
+            disclaimer: Some(settings.style.disclaimer.clone())
+                .filter(|d| d.enabled && !d.text.trim().is_empty()),
+            spotlight: Some(settings.style.spotlight.clone())
+                .filter(|s| s.enabled && s.dim_opacity > 0.0),
+            watermark: Some(settings.style.watermark.clone())
+                .filter(|w| w.enabled && w.opacity > 0.0 && w.image_path.as_deref().is_some_and(|p| !p.is_empty())),
+            intro: Some(settings.style.intro.clone()).filter(TitleCardSettings::is_shown),
+            outro: Some(settings.style.outro.clone()).filter(TitleCardSettings::is_shown),
+            window_chrome: Some(settings.style.window_chrome.clone())
+                .filter(|c| c.enabled && c.title_bar_height > 0),
+            progress_bar: Some(settings.style.progress_bar.clone()).filter(|p| p.enabled && p.height > 0),
         }
     }
 }
@@ -1,4 +1,6 @@
 pub mod defaults;
+pub mod profiles;
+pub mod style_preset;
 
 use serde::{Deserialize, Serialize};
 
@@ -17,14 +19,164 @@ pub struct AppSettings {
     pub style: StyleSettings,
     pub effects: EffectsSettings,
     pub output: OutputSettings,
+    /// 録画テンプレート（トレイから 1 クリックで録画開始）
+    #[serde(default)]
+    pub templates: Vec<RecordingTemplate>,
+    /// 予約録画（開始時刻の早い順）。開始した・開始時刻を過ぎて逃したものは消える
+    #[serde(default)]
+    pub scheduled_recordings: Vec<ScheduledRecording>,
+    /// 録画フォルダの自動整理
+    #[serde(default)]
+    pub storage: StorageSettings,
+    /// 書き出したファイルのアップロード先
+    #[serde(default)]
+    pub share: ShareSettings,
+    /// ライブ配信（RTMP）の送信先と画質。ストリームキーを含むので、スタイルプリセットや
+    /// プロファイルに入る `output` には置かない
+    #[serde(default)]
+    pub stream: StreamSettings,
+}
+
+/// 書き出したファイルのアップロード先（`upload_export`）。使う先だけ設定すればよい
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShareSettings {
+    pub s3: S3ShareSettings,
+    pub http: HttpShareSettings,
+    pub youtube: YouTubeShareSettings,
+}
+
+/// S3 互換ストレージ（AWS S3・Cloudflare R2・MinIO など）。パス形式の URL で PUT する
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct S3ShareSettings {
+    /// エンドポイント（例: `https://s3.ap-northeast-1.amazonaws.com`）
+    pub endpoint: String,
+    /// リージョン（R2 は `auto`）
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// オブジェクトキーの前に付ける文字列（例: `snappi/`）
+    pub key_prefix: String,
+    /// 共有用 URL の先頭（CDN・公開バケットのドメインなど）。空ならエンドポイント/バケット/キー
+    pub public_url_base: String,
+}
+
+/// 任意の HTTP サーバーへの PUT
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpShareSettings {
+    /// アップロード先 URL。`{filename}` がファイル名に置き換わる
+    pub url: String,
+    /// Authorization ヘッダーの値（例: `Bearer xxx`）。空なら付けない
+    pub authorization: String,
+    /// 共有用 URL。`{filename}` が使える。空ならアップロード先 URL
+    pub public_url: String,
+}
+
+/// YouTube（OAuth）。`authorize_youtube` でブラウザから許可すると `refresh_token` が保存される
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct YouTubeShareSettings {
+    /// Google Cloud で作成した OAuth クライアント（デスクトップ アプリ）の ID
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+    pub privacy: YouTubePrivacy,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YouTubePrivacy {
+    Private,
+    #[default]
+    Unlisted,
+    Public,
+}
+
+/// `upload_export` のアップロード先
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub enum ShareTarget {
+    S3,
+    Http,
+    YouTube,
+}
+
+/// 録画フォルダの自動整理（バックグラウンドで定期的に実行する）。録画中の録画には触れない
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageSettings {
+    /// 書き出しに成功した録画のフレームを削除する。一覧・書き出し済みファイルは残るが、
+    /// プレビュー・再書き出しはできなくなる
+    pub delete_frames_after_export: bool,
+    /// 新しい順にこの件数だけ残し、それより古い録画を削除する（0 = 無制限）
+    pub keep_last_recordings: u32,
+    /// 録画フォルダ全体の上限 (GB)。超えたら古い録画から削除する（0 = 無制限）
+    pub max_library_gb: f64,
+    /// 件数・容量の上限で、まだ書き出していない録画も削除する。false（既定）なら
+    /// 書き出し済みの録画だけを削除する
+    pub delete_unexported: bool,
+}
+
+/// 予約録画: 開始時刻になったら `mode` で録画を始め、`duration_seconds` で止める
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct ScheduledRecording {
+    pub id: String,
+    /// 開始時刻（RFC 3339）
+    pub start_time: String,
+    /// 録画する長さ（秒）
+    pub duration_seconds: u64,
+    /// 録画対象（全画面・ウィンドウ・範囲）
+    pub mode: RecordingMode,
+}
+
+/// 録画テンプレート: 録画対象・カウントダウン・エフェクト・出力設定をまとめたもの
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingTemplate {
+    pub id: String,
+    pub name: String,
+    /// 録画するウィンドウのタイトルパターン（大文字小文字無視、`*` ワイルドカード可）。
+    /// None の場合は全画面を録画する
+    #[serde(default)]
+    pub window_title_pattern: Option<String>,
+    /// 録画開始前のカウントダウン（秒）
+    #[serde(default)]
+    pub countdown_seconds: u32,
+    /// 適用するエフェクト設定（None = 現在の設定のまま）
+    #[serde(default)]
+    pub effects: Option<EffectsSettings>,
+    #[serde(default)]
+    pub output_format: Option<ExportFormat>,
+    #[serde(default)]
+    pub output_quality: Option<QualityPreset>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordingSettings {
+    /// 録画の開始・停止のホットキー。各ホットキーは空文字で無効
     pub hotkey: String,
+    /// 一時停止・再開のホットキー（エディタやブラウザの Ctrl+Shift+P と重ならないよう既定は無効）
+    #[serde(default)]
+    pub pause_hotkey: String,
+    /// 録画を取り消して破棄するホットキー（誤操作で消えないよう既定は無効）
+    #[serde(default)]
+    pub cancel_hotkey: String,
+    /// スクリーンショットのホットキー
+    #[serde(default = "default_screenshot_hotkey")]
+    pub screenshot_hotkey: String,
+    /// リプレイバッファを録画として保存するホットキー（既定: 無効）
+    #[serde(default)]
+    pub replay_hotkey: String,
     pub fps: u32,
     pub capture_system_audio: bool,
     pub capture_microphone: bool,
+    /// 録画時間の上限（秒、一時停止中は数えない）。上限の 60 秒前と 10 秒前に
+    /// 予告し、達したら録画を自動停止する。0 = 無制限
     pub max_duration_seconds: u64,
     #[serde(default)]
     pub recording_mode: RecordingMode,
@@ -33,6 +185,44 @@ pub struct RecordingSettings {
     /// 記録し、パスワードやチャット内容などが録画ディレクトリに残らないようにする。
     #[serde(default)]
     pub record_key_labels: bool,
+    /// 録画の開始・停止時に session.json を POST する URL（テスト自動化ツール連携用）。
+    /// 未設定なら送信しない
+    #[serde(default)]
+    pub session_webhook_url: Option<String>,
+    /// 録画フレームの保存形式（全画面録画のみ。ウィンドウ・範囲録画は常に JPEG）
+    #[serde(default)]
+    pub frame_storage: FrameStorage,
+    /// 録画先ドライブの空き容量の下限（MB）。これを下回ると録画を自動停止し、
+    /// 2 倍を下回った時点で警告する。0 = 確認しない
+    #[serde(default = "default_min_free_disk_mb")]
+    pub min_free_disk_mb: u64,
+    /// 録画開始前のカウントダウン（秒、0 = なし）。設定画面では 3 / 5 / 10 秒から選ぶ
+    #[serde(default)]
+    pub countdown_seconds: u32,
+    /// タイムラプス録画のキャプチャ fps（0 = 通常録画）。1〜2 fps で撮り、書き出しでは
+    /// [`TIMELAPSE_OUTPUT_FPS`] に詰めて早送りにする。音声は録らない
+    #[serde(default)]
+    pub timelapse_fps: u32,
+    /// リプレイバッファの長さ（秒、0 = 無効）。録画していない間も画面をメモリに
+    /// 録り続け、リプレイのホットキーで直前のこの秒数を録画として保存する
+    #[serde(default)]
+    pub replay_buffer_seconds: u32,
+}
+
+/// タイムラプス録画を書き出す fps
+pub const TIMELAPSE_OUTPUT_FPS: u32 = 30;
+
+/// How captured frames are written to disk
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub enum FrameStorage {
+    /// One JPEG per frame
+    #[default]
+    Jpeg,
+    /// All frames LZ4-compressed in one `frames.pack`: far less CPU per
+    /// frame (no dropped frames on large screens), more disk space
+    Packed,
 }
 
 /// Recording mode: full display or specific window
@@ -88,9 +278,217 @@ pub struct StyleSettings {
     /// Hotspot Y coordinate within the cursor image (tip position).
     #[serde(default)]
     pub cursor_hotspot_y: u32,
+    /// 入力記録の注意書きリボン（コンプライアンス向け）
+    #[serde(default)]
+    pub disclaimer: DisclaimerSettings,
+    /// キー表示バッジの見た目
+    #[serde(default)]
+    pub key_badge: KeyBadgeStyle,
+    /// カーソル周辺以外を暗くするスポットライト
+    #[serde(default)]
+    pub spotlight: SpotlightSettings,
+    /// ロゴ画像（PNG）の透かし
+    #[serde(default)]
+    pub watermark: WatermarkSettings,
+    /// 書き出し動画の先頭に挿入するカード
+    #[serde(default)]
+    pub intro: TitleCardSettings,
+    /// 書き出し動画の末尾に挿入するカード
+    #[serde(default)]
+    pub outro: TitleCardSettings,
+    /// 録画とキャンバス端の間の余白（背景が見える幅）
+    #[serde(default)]
+    pub padding: CanvasPadding,
+    /// 録画の周りに描くブラウザ風のウィンドウ枠
+    #[serde(default)]
+    pub window_chrome: WindowChromeSettings,
+    /// 書き出し動画の下端に描く再生位置のバー
+    #[serde(default)]
+    pub progress_bar: ProgressBarSettings,
+}
+
+/// 書き出し動画の下端に沿って伸びる進捗バー（SNS 向けの短い動画でよく使われる）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProgressBarSettings {
+    pub enabled: bool,
+    /// バーの高さ（出力キャンバスでのpx）
+    pub height: u32,
+    pub color: [u8; 4],
+    /// まだ再生していない部分の帯の色。アルファ 0 で帯なし
+    pub track_color: [u8; 4],
+}
+
+/// 録画の上に重ねるタイトルバー（信号機ボタン・アドレスバー）。
+/// タイトルバーはキャンバスの余白側に描かれ、録画の大きさは変わらない
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowChromeSettings {
+    pub enabled: bool,
+    pub theme: ChromeTheme,
+    /// タイトルバーの高さ（出力キャンバスでのpx）
+    pub title_bar_height: u32,
+    /// 左上の閉じる・最小化・最大化ボタンを表示するか
+    pub show_buttons: bool,
+    /// アドレスバーに表示する文字。空ならアドレスバーを表示しない
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum ChromeTheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// 録画の四辺に確保する余白
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum CanvasPadding {
+    /// 出力キャンバスでのpx
+    Pixels { value: u32 },
+    /// キャンバスの短辺に対する割合（%）
+    Percent { value: f64 },
+}
+
+impl CanvasPadding {
+    /// Upper bound for [`CanvasPadding::Percent`], so some of the recording
+    /// is always left on the canvas
+    const MAX_PERCENT: f64 = 40.0;
+
+    fn percent(value: f64) -> f64 {
+        value.clamp(0.0, Self::MAX_PERCENT) / 100.0
+    }
+
+    /// Margin on each side of a canvas whose shorter side is `canvas_short`.
+    pub fn margin_in(&self, canvas_short: u32) -> u32 {
+        match *self {
+            Self::Pixels { value } => value,
+            Self::Percent { value } => (canvas_short as f64 * Self::percent(value)).round() as u32,
+        }
+    }
+
+    /// Margin on each side of a frame whose shorter side is `frame_short`,
+    /// i.e. the margin that is `Percent` of the resulting canvas.
+    pub fn margin_around(&self, frame_short: u32) -> u32 {
+        match *self {
+            Self::Pixels { value } => value,
+            Self::Percent { value } => {
+                let p = Self::percent(value);
+                (frame_short as f64 * p / (1.0 - 2.0 * p)).round() as u32
+            }
+        }
+    }
+}
+
+/// 書き出し動画の前後に挿入するカード（画像、またはタイトル文字＋背景）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TitleCardSettings {
+    pub enabled: bool,
+    /// 表示時間 (ms)
+    pub duration_ms: u64,
+    /// 画像のパス。指定するとタイトル文字の代わりに画像を表示する
+    pub image_path: Option<String>,
+    pub title: String,
+    /// タイトルの下に小さく表示する文字
+    pub subtitle: String,
+    /// タイトルの文字サイズ（出力キャンバスでのpx）
+    pub font_size: f64,
+    pub text_color: [u8; 4],
+    /// 背景。None の場合は書き出しの背景と同じ
+    pub background: Option<BackgroundConfig>,
 }
 
+impl TitleCardSettings {
+    /// Enabled, with a duration and something to show.
+    pub fn is_shown(&self) -> bool {
+        self.enabled
+            && self.duration_ms > 0
+            && (self.image_path.as_deref().is_some_and(|p| !p.is_empty())
+                || !self.title.trim().is_empty()
+                || !self.subtitle.trim().is_empty())
+    }
+}
+
+/// 書き出し動画の隅に重ねるロゴ画像
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatermarkSettings {
+    pub enabled: bool,
+    /// 透過 PNG のパス
+    pub image_path: Option<String>,
+    pub position: WatermarkPosition,
+    /// 不透明度 (0.0 – 1.0)
+    pub opacity: f64,
+    /// キャンバスの端からの余白（出力キャンバスでのpx）
+    pub margin: f64,
+    /// ロゴの幅（出力キャンバスでのpx）。0で画像の元の大きさ
+    pub width: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum WatermarkPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// カーソルの周囲だけを明るく残し、それ以外を暗くする効果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpotlightSettings {
+    pub enabled: bool,
+    /// 明るく残す半径（出力フレームでのpx）
+    pub radius: f64,
+    /// 周囲を暗くする強さ (0.0 – 1.0)
+    pub dim_opacity: f64,
+    /// クリック時に半径を一時的に広げる割合（0 でアニメーションなし）
+    pub click_pulse: f64,
+}
+
+/// キー表示バッジ（Ctrl+C など）の文字サイズ・色・位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBadgeStyle {
+    /// 文字サイズ（出力フレームでのpx）
+    pub font_size: f64,
+    pub text_color: [u8; 4],
+    pub background_color: [u8; 4],
+    pub position: BadgePosition,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum BadgePosition {
+    BottomCenter,
+    BottomLeft,
+    BottomRight,
+    TopCenter,
+}
+
+/// 書き出し動画の先頭に表示する「キーボード・マウス操作を記録しています」表示
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisclaimerSettings {
+    pub enabled: bool,
+    pub text: String,
+    /// 表示する長さ（書き出し後の時間, ms）。0で全編に表示
+    pub duration_ms: u64,
+    pub position: RibbonPosition,
+    /// 文字サイズ（出力キャンバスでのpx）
+    pub font_size: f64,
+    pub text_color: [u8; 4],
+    pub background_color: [u8; 4],
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum RibbonPosition {
+    Top,
+    Bottom,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type")]
 pub enum BackgroundConfig {
     Gradient {
@@ -102,6 +500,26 @@ pub enum BackgroundConfig {
         color: [u8; 3],
     },
     Transparent,
+    /// 画像ファイルを背景にする
+    Image {
+        path: String,
+        #[serde(default)]
+        fit: ImageFit,
+    },
+    /// 録画の最初のフレームをぼかして背景にする
+    Blurred,
+}
+
+/// 背景画像をキャンバスに合わせる方法
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum ImageFit {
+    /// 縦横比を保ってキャンバス全体を覆う（はみ出た部分は切り取る）
+    #[default]
+    Cover,
+    /// 縦横比を保って全体が収まるように配置する
+    Contain,
+    /// キャンバスの大きさに引き伸ばす
+    Stretch,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -125,6 +543,15 @@ pub struct EffectsSettings {
     /// 画面差分(frame diff)を考慮してシーンBBoxを拡張するか
     #[serde(default = "default_true")]
     pub frame_diff_enabled: bool,
+    /// 画面差分で変化とみなすピクセル差のしきい値 (0-255)。小さいほど敏感
+    #[serde(default = "default_frame_diff_pixel_threshold")]
+    pub frame_diff_pixel_threshold: u8,
+    /// 変化領域として扱う最小サイズ (px)。点滅カーソルなど小さな変化を無視する
+    #[serde(default = "default_frame_diff_min_region_size")]
+    pub frame_diff_min_region_size: u32,
+    /// 画面のこの割合以上が変化したフレームは除外する (0.0-1.0)。動く壁紙や画面切り替え対策
+    #[serde(default = "default_frame_diff_max_change_fraction")]
+    pub frame_diff_max_change_fraction: f64,
     /// WorkArea→Window のアイドルしきい値 (ms)
     #[serde(default = "default_idle_zoom_out_ms")]
     pub idle_zoom_out_ms: u64,
@@ -143,15 +570,109 @@ pub struct EffectsSettings {
     /// クラスタが安定するまでの時間 (ms)
     #[serde(default = "default_cluster_stability_ms")]
     pub cluster_stability_ms: u64,
+    /// マウス移動の間引き: これ未満の移動は除去 (px)
+    #[serde(default = "default_move_thin_distance_px")]
+    pub move_thin_distance_px: f64,
+    /// マウス移動の間引き: 前回からこれ以上空いた移動は停止位置として残す (ms)
+    #[serde(default = "default_move_thin_interval_ms")]
+    pub move_thin_interval_ms: u64,
+    /// ドラッグ判定の最小移動距離 (px)
+    #[serde(default = "default_drag_min_distance_px")]
+    pub drag_min_distance_px: f64,
+    /// ドラッグ判定の最小時間 (ms)
+    #[serde(default)]
+    pub drag_min_duration_ms: u64,
+    /// クリックリングを表示するボタン ("left" / "right" / "middle")
+    #[serde(default = "default_click_ring_buttons")]
+    pub click_ring_buttons: Vec<String>,
+    /// 別の色・形（二重リング）で表示するボタン
+    #[serde(default)]
+    pub click_ring_alt_buttons: Vec<String>,
+    /// クリックリングの色 (RGBA)。ハイビジビリティでは使わない
+    #[serde(default = "default_click_ring_color")]
+    pub click_ring_color: [u8; 4],
+    /// 上記ボタン用のリング色 (RGBA)
+    #[serde(default = "default_click_ring_alt_color")]
+    pub click_ring_alt_color: [u8; 4],
+    /// ダイアログ・メニューが開いている間、その範囲を枠線とラベルで示す
+    #[serde(default)]
+    pub ui_highlight_enabled: bool,
+    /// 入力した文字をまとめて、入力欄の近くに字幕として表示する
+    /// （文字の復元にはキー名の記録 `record_key_labels` が必要。未記録なら伏せ字）
+    #[serde(default)]
+    pub typed_text_overlay: bool,
+    /// パスワード入力欄にフォーカスがある間、その範囲を自動でぼかす
+    /// （字幕 `typed_text_overlay` もその間は出さない）
+    #[serde(default)]
+    pub auto_redact_inputs: bool,
+    /// 有効にするエフェクトプラグイン名（`effect-plugins` feature 付きビルドのみ有効）
+    #[serde(default)]
+    pub plugins: Vec<String>,
+    /// エクスポートごとに実行する Rhai スクリプトのパス（`scripting` feature 付きビルドのみ有効）
+    #[serde(default)]
+    pub script_path: Option<String>,
+    /// ディスプレイ録画の全体表示（ズームアウト時）の倍率。
+    /// None = 画面の縦横比から自動（16:9 より横長なら中央を 16:9 相当で切り出す）
+    #[serde(default)]
+    pub overview_zoom: Option<f64>,
+    /// 見やすさのプロファイル
+    #[serde(default)]
+    pub profile: EffectsProfile,
+    /// 動きを抑えるモード: ズーム・パンをバネで動かさず、短いフェードで切り替える
+    #[serde(default)]
+    pub reduced_motion: bool,
+    /// ズームの計画に使わないアプリ（実行ファイル名またはウィンドウタイトルの一部）。
+    /// 画面には映るが、操作してもカメラは追わない
+    #[serde(default)]
+    pub zoom_excluded_apps: Vec<String>,
+}
+
+impl EffectsSettings {
+    /// プロファイル反映後のアニメーション速度（HighVisibility は常に Slow）
+    pub fn effective_animation_speed(&self) -> AnimationSpeed {
+        match self.profile {
+            EffectsProfile::Standard => self.animation_speed.clone(),
+            EffectsProfile::HighVisibility => AnimationSpeed::Slow,
+        }
+    }
+
+    /// モーションブラーを実際にかけるか（HighVisibility・動きを抑えるモードでは無効）
+    pub fn motion_blur_active(&self) -> bool {
+        self.motion_blur_enabled
+            && self.profile == EffectsProfile::Standard
+            && !self.reduced_motion
+    }
+}
+
+/// エフェクトの見やすさプロファイル
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum EffectsProfile {
+    #[default]
+    Standard,
+    /// 大きいカーソル・高コントラストのクリックリング・ゆっくりしたアニメーション・モーションブラーなし
+    HighVisibility,
 }
 
 fn default_true() -> bool { true }
+fn default_min_free_disk_mb() -> u64 { 1024 }
+fn default_screenshot_hotkey() -> String { "Ctrl+Shift+S".to_string() }
+fn default_frame_diff_pixel_threshold() -> u8 { 10 }
+fn default_frame_diff_min_region_size() -> u32 { 50 }
+fn default_frame_diff_max_change_fraction() -> f64 { 0.5 }
 fn default_idle_zoom_out_ms() -> u64 { 5000 }
 fn default_idle_overview_ms() -> u64 { 8000 }
 fn default_min_workarea_dwell_ms() -> u64 { 2000 }
 fn default_min_window_dwell_ms() -> u64 { 1500 }
 fn default_cluster_lifetime_ms() -> u64 { 5000 }
 fn default_cluster_stability_ms() -> u64 { 1000 }
+fn default_move_thin_distance_px() -> f64 { 3.0 }
+fn default_move_thin_interval_ms() -> u64 { 200 }
+fn default_drag_min_distance_px() -> f64 { 20.0 }
+fn default_click_ring_buttons() -> Vec<String> {
+    vec!["left".to_string(), "right".to_string(), "middle".to_string()]
+}
+fn default_click_ring_color() -> [u8; 4] { [59, 130, 246, 180] }
+fn default_click_ring_alt_color() -> [u8; 4] { [249, 115, 22, 180] }
 
 /// Controls how frequently auto-zoom triggers
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -271,6 +792,115 @@ pub struct OutputSettings {
     pub default_format: ExportFormat,
     pub default_quality: QualityPreset,
     pub save_directory: String,
+    /// 書き出し時、イベントも画面変化も無い長い無操作区間を自動でカットするか
+    #[serde(default)]
+    pub auto_trim_idle: bool,
+    /// 自動カットの対象とする無操作区間の最小長 (ms)
+    #[serde(default = "default_auto_trim_min_idle_ms")]
+    pub auto_trim_min_idle_ms: u64,
+    /// 無操作区間の早送り倍率（1.0 = 無効）。カットせずに早送りで見せる
+    #[serde(default = "default_idle_speedup")]
+    pub idle_speedup: f64,
+    /// 早送りの対象とする無操作区間の最小長 (ms)
+    #[serde(default = "default_idle_speedup_min_ms")]
+    pub idle_speedup_min_ms: u64,
+    /// 出力キャンバスの縦横比（縦型・正方形などSNS向け）
+    #[serde(default)]
+    pub aspect: OutputAspect,
+    /// 縦横比が元映像と異なる場合の収め方
+    #[serde(default)]
+    pub aspect_fit: AspectFit,
+    /// 書き出しファイル名のテンプレート。{date} {time} {title} {id} が使える
+    #[serde(default = "default_filename_template")]
+    pub filename_template: String,
+    /// 音声のみ書き出しのコーデック
+    #[serde(default)]
+    pub audio_only_codec: AudioOnlyCodec,
+    /// フレーム合成のバックエンド。Gpu は gpu フィーチャー付きビルドでのみ有効
+    #[serde(default)]
+    pub compositor_backend: CompositorBackend,
+    /// 長い書き出しをこの長さ（分）以下の連番ファイルに分割する。0 = 分割しない。
+    /// 動画形式（MP4 / WebM / MOV）のみ。できるだけシーンの切れ目で分ける
+    #[serde(default)]
+    pub split_max_minutes: u32,
+    /// 録画のブックマークを書き出した動画のチャプターにする（MP4 / MOV のみ）
+    #[serde(default)]
+    pub bookmark_chapters: bool,
+}
+
+/// ライブ配信の設定。録画対象（`RecordingSettings::recording_mode`）をそのまま配信する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreamSettings {
+    /// 配信先のサーバー URL（`rtmp://` または `rtmps://`）
+    pub url: String,
+    /// ストリームキー。URL の末尾に付けて送る（空なら URL だけ）
+    pub stream_key: String,
+    pub fps: u32,
+    /// 映像のビットレート (kbps)
+    pub video_bitrate_kbps: u32,
+    /// 書き出しと同じ背景・角丸・影・カーソルを付けて配信するか。
+    /// ズームなど録画後の解析が要るエフェクトは付かない
+    pub apply_style: bool,
+}
+
+fn default_auto_trim_min_idle_ms() -> u64 { 5000 }
+fn default_idle_speedup() -> f64 { 1.0 }
+fn default_idle_speedup_min_ms() -> u64 { 3000 }
+fn default_filename_template() -> String { "{date}_{time}".to_string() }
+
+/// Per-export resolution override. Explicit `width`/`height` take precedence
+/// over `scale`; giving only one of them keeps the source aspect ratio, and
+/// both of another aspect crop the view to it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+#[serde(default)]
+pub struct OutputSize {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Factor applied to the recorded screen size (e.g. 0.5)
+    pub scale: Option<f64>,
+}
+
+/// Output canvas aspect ratio
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub enum OutputAspect {
+    /// Same as the recorded screen
+    #[default]
+    Source,
+    /// 9:16 (Shorts / Reels / TikTok)
+    Vertical9x16,
+    /// 1:1
+    Square1x1,
+    /// 4:5 (Instagram feed)
+    Portrait4x5,
+}
+
+impl OutputAspect {
+    /// Width:height ratio, `None` for [`OutputAspect::Source`]
+    pub fn ratio(&self) -> Option<(u32, u32)> {
+        match self {
+            OutputAspect::Source => None,
+            OutputAspect::Vertical9x16 => Some((9, 16)),
+            OutputAspect::Square1x1 => Some((1, 1)),
+            OutputAspect::Portrait4x5 => Some((4, 5)),
+        }
+    }
+}
+
+/// How the recording is placed on a canvas of a different aspect ratio
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub enum AspectFit {
+    /// Whole screen fitted inside the canvas, background fills the rest
+    Letterbox,
+    /// Viewport cropped to the canvas aspect, following the activity center
+    #[default]
+    ZoomCrop,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -280,6 +910,45 @@ pub enum ExportFormat {
     Mp4,
     Gif,
     WebM,
+    WebP,
+    Apng,
+    /// QuickTime (ProRes 4444)
+    Mov,
+    /// Audio track only (codec from `OutputSettings::audio_only_codec`)
+    AudioOnly,
+}
+
+/// Where export frames are composed
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub enum CompositorBackend {
+    #[default]
+    Cpu,
+    /// wgpu (crop/scale, cursor, rounded corners, shadow); falls back to
+    /// the CPU when no GPU is available
+    Gpu,
+}
+
+/// Codec / container for [`ExportFormat::AudioOnly`]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub enum AudioOnlyCodec {
+    /// AAC in .m4a
+    #[default]
+    Aac,
+    /// Opus in .opus
+    Opus,
+}
+
+impl AudioOnlyCodec {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioOnlyCodec::Aac => "m4a",
+            AudioOnlyCodec::Opus => "opus",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -291,6 +960,44 @@ pub enum QualityPreset {
     Lightweight,
 }
 
+/// GIF dithering mode (FFmpeg `paletteuse` dither)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub enum GifDither {
+    None,
+    Bayer,
+    FloydSteinberg,
+    Sierra2_4a,
+}
+
+impl GifDither {
+    pub fn ffmpeg_name(&self) -> &'static str {
+        match self {
+            GifDither::None => "none",
+            GifDither::Bayer => "bayer",
+            GifDither::FloydSteinberg => "floyd_steinberg",
+            GifDither::Sierra2_4a => "sierra2_4a",
+        }
+    }
+}
+
+/// Animated image export options (GIF, and fps / max width / loop for WebP and APNG)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+#[serde(default)]
+pub struct GifOptions {
+    pub fps: u32,
+    /// 出力幅の上限 (px)。キャンバスがこれより小さければ縮小しない
+    pub max_width: u32,
+    pub dither: GifDither,
+    /// ループ回数（0 = 無限ループ、-1 = ループしない）
+    pub loop_count: i32,
+    /// ファイルサイズ上限 (KB)。超えた場合は fps・幅を下げて再エンコードする
+    pub max_file_size_kb: Option<u32>,
+}
+
 /// Metadata about a completed recording session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
@@ -304,6 +1011,7 @@ pub struct RecordingMeta {
     pub start_time: String,
     pub duration_ms: u64,
     pub has_audio: bool,
+    /// 録画したモニターの表示スケール（100% = 1.0）。旧録画では常に 1.0。
     pub monitor_scale: f64,
     pub recording_dir: String,
     #[serde(default)]
@@ -316,10 +1024,103 @@ pub struct RecordingMeta {
     /// None の場合、リスト表示は frame_count.txt にフォールバックする。
     #[serde(default)]
     pub frame_count: Option<u32>,
+    /// 録画時の OS のマウスポインターサイズ（既定サイズ 32px に対する倍率）。
+    /// エクスポート時のカーソル描画サイズに掛ける。旧録画では None（= 1.0）。
+    #[serde(default)]
+    pub cursor_scale: Option<f64>,
+    /// 同じシナリオの撮り直し（テイク）としてのグループ情報
+    #[serde(default)]
+    pub take: Option<TakeInfo>,
+    /// 続きを追記録画した継ぎ目の時刻 (ms)。追記のたびに末尾へ足す
+    #[serde(default)]
+    pub splices: Vec<u64>,
+    /// 一時停止した位置。version 3 以降の録画はタイムスタンプが一時停止中に進まない
+    #[serde(default)]
+    pub pauses: Vec<PauseMarker>,
+    /// タイムラプス録画の早送り倍率（書き出し fps ÷ キャプチャ fps）。通常録画では None
+    #[serde(default)]
+    pub timelapse_speed: Option<f64>,
+    /// ユーザーが付けた名前（None = 日時で表示）
+    #[serde(default)]
+    pub title: Option<String>,
+    /// ユーザーが付けたタグ（前後の空白を除き、重複なし）
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// メモ
+    #[serde(default)]
+    pub notes: String,
+    /// 録画中のチェックポイント。停止処理まで終わらなかった（クラッシュした）録画では
+    /// true のまま残り、フレーム数・長さは最後のチェックポイント時点の値になる
+    #[serde(default)]
+    pub in_progress: bool,
+}
+
+/// 録画を一時停止した位置
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct PauseMarker {
+    /// 一時停止した時刻 (ms、録画のタイムライン上。再開後もこの時刻から続く)
+    pub at_ms: u64,
+    /// 一時停止していた実時間 (ms)
+    pub duration_ms: u64,
+}
+
+/// タイムライン上のブックマーク（録画ごとに bookmarks.json へ保存）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct Bookmark {
+    pub id: String,
+    /// 録画のタイムライン上の時刻 (ms)
+    pub time_ms: u64,
+    #[serde(default)]
+    pub label: String,
+    /// 表示色（CSS カラー）。None = 既定の色
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// 同じシナリオを撮り直した録画（テイク）のグループ情報
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct TakeInfo {
+    /// 同じグループのテイクで共通の ID
+    pub group_id: String,
+    /// シナリオ名（グループ内で共通）
+    pub scenario: String,
+    /// グループ内で採用されたテイクか
+    pub chosen: bool,
+}
+
+impl RecordingMeta {
+    pub fn os_cursor_scale(&self) -> f64 {
+        self.cursor_scale.filter(|s| s.is_finite() && *s > 0.0).unwrap_or(1.0)
+    }
+
+    pub fn display_scale(&self) -> f64 {
+        if self.monitor_scale.is_finite() && self.monitor_scale > 0.0 {
+            self.monitor_scale
+        } else {
+            1.0
+        }
+    }
+
+    /// タイムスタンプから一時停止時間が除かれている録画か（version 3 以降）。
+    /// 旧録画はフレーム・イベントの時刻が一時停止中も進んでいる
+    pub fn pauses_excluded(&self) -> bool {
+        self.version >= 3
+    }
+
+    /// タイムラプス録画の早送り倍率（通常録画・不正な値では None）
+    pub fn timelapse_speed(&self) -> Option<f64> {
+        self.timelapse_speed.filter(|s| s.is_finite() && *s > 1.0)
+    }
 }
 
 /// Lightweight event representation for Timeline UI visualization.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
 pub struct TimelineEvent {
@@ -328,6 +1129,61 @@ pub struct TimelineEvent {
     pub x: Option<f64>,
     pub y: Option<f64>,
     pub label: Option<String>,
+    /// Span end for "drag" events (x/y is the start, end_x/end_y the drop point)
+    #[serde(default)]
+    pub end_ms: Option<u64>,
+    #[serde(default)]
+    pub end_x: Option<f64>,
+    #[serde(default)]
+    pub end_y: Option<f64>,
+    /// Display color for "bookmark" events
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Before/after preview images around a scene boundary (Timeline merge/split helper).
+/// Paths point to small PNGs with each scene's bbox drawn on top of the raw frame.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct SceneBoundaryPreview {
+    /// Scene before the boundary (the boundary is between scene_id and scene_id + 1)
+    pub scene_id: u32,
+    pub boundary_ms: u64,
+    pub before_path: String,
+    pub after_path: String,
+}
+
+/// Per-recording summary used by `compare_recordings`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct RecordingStats {
+    pub id: String,
+    pub duration_ms: u64,
+    pub scene_count: u32,
+    pub click_count: u32,
+    pub key_count: u32,
+    pub scroll_count: u32,
+    /// Distinct window titles focused during the recording, in first-seen order
+    pub windows: Vec<String>,
+}
+
+/// Side-by-side stats of two recordings (QA: two runs of the same flow).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct RecordingComparison {
+    pub a: RecordingStats,
+    pub b: RecordingStats,
+    /// b - a
+    pub duration_delta_ms: i64,
+    pub scene_count_delta: i32,
+    pub click_count_delta: i32,
+    /// Windows used in both recordings
+    pub common_windows: Vec<String>,
+    /// 0.0 (unrelated) – 1.0 (identical stats)
+    pub similarity: f64,
 }
 
 /// Event types recorded during screen capture
@@ -377,6 +1233,9 @@ pub enum RecordingEvent {
         t: u64,
         title: String,
         rect: [f64; 4],
+        /// 実行ファイル名（例: spotify.exe）。旧録画では空
+        #[serde(default)]
+        process: String,
     },
     #[serde(rename = "ui_focus")]
     UiFocus {
@@ -386,6 +1245,9 @@ pub enum RecordingEvent {
         rect: [f64; 4],
         #[serde(default)]
         automation_id: String,
+        /// パスワード入力欄（UIA IsPassword）。旧録画では常に false
+        #[serde(default)]
+        password: bool,
     },
     #[serde(rename = "ui_menu_open")]
     UiMenuOpen {
@@ -428,19 +1290,215 @@ pub struct RecordingInfo {
     pub recording_dir: String,
     pub screen_width: u32,
     pub screen_height: u32,
+    /// 録画したモニターの表示スケール。書き出しの解像度候補（論理解像度）に使う
+    #[serde(default = "default_monitor_scale")]
+    pub monitor_scale: f64,
+    #[serde(default)]
+    pub take: Option<TakeInfo>,
+    /// タイムラプス録画の早送り倍率（一覧の「×30」表示用）
+    #[serde(default)]
+    pub timelapse_speed: Option<f64>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: String,
 }
 
+/// 録画一覧の並び順
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub enum RecordingSort {
+    /// 新しい順（テイクはグループごとにまとめる）
+    #[default]
+    Newest,
+    Oldest,
+    /// 長い順
+    Longest,
+    Shortest,
+    /// 名前順（名前のない録画は最後に新しい順）
+    Title,
+}
+
+/// 録画一覧の 1 ページ（`get_recordings_page`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct RecordingPage {
+    pub recordings: Vec<RecordingInfo>,
+    /// 絞り込み後の全件数
+    pub total: u32,
+    /// このページの先頭の位置
+    pub offset: u32,
+}
+
+/// 録画一覧の絞り込み（`get_recordings_list`）。指定した条件をすべて満たす録画だけを返す
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct RecordingFilter {
+    /// 名前・タグ・メモのどれかに含まれる文字列（大文字小文字無視）
+    #[serde(default)]
+    pub query: Option<String>,
+    /// すべて付いている必要があるタグ（大文字小文字無視）
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn default_monitor_scale() -> f64 { 1.0 }
+
 /// Recording state
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
 pub enum RecordingState {
     Idle,
+    /// 録画開始前のカウントダウン中
+    Countdown,
     Recording,
     Paused,
     Processing,
 }
 
+/// Recording finalization progress (`recording-finalizing` event)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct FinalizingProgress {
+    pub stage: String,
+    pub progress: f64,
+}
+
+/// First-run setup checks (`run_setup_diagnostics`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct SetupDiagnostics {
+    /// 見つかった FFmpeg（None = 未検出。書き出しに必要）
+    pub ffmpeg_path: Option<String>,
+    /// `ffmpeg -version` の 1 行目
+    pub ffmpeg_version: Option<String>,
+    /// マウス・キーボードの低レベルフックを設定できたか
+    pub input_hooks_ok: bool,
+    pub input_hooks_error: Option<String>,
+    /// GDI 画面キャプチャが動いたか
+    pub capture_ok: bool,
+    pub capture_error: Option<String>,
+    pub screen_width: u32,
+    pub screen_height: u32,
+    /// キャプチャ + JPEG 保存を全力で回したときの fps
+    pub measured_fps: Option<f64>,
+    /// 録画 fps の推奨値（計測できなければ None）
+    pub recommended_fps: Option<u32>,
+}
+
+/// `cleanup_workspace` で消したものと空いた容量
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct CleanupReport {
+    /// 異常終了した書き出しが残した一時フォルダ
+    pub temp_dirs: u32,
+    /// 録画や設定が変わって使われなくなったプレビュー用プロキシ
+    pub stale_proxies: u32,
+    /// 削除済みの録画のキャッシュだけが残ったフォルダ
+    pub orphaned_caches: u32,
+    /// 空いた容量 (bytes)
+    pub reclaimed_bytes: u64,
+}
+
+/// 録画 1 件のディスク使用量（`get_storage_usage`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct RecordingUsage {
+    pub id: String,
+    pub title: Option<String>,
+    /// 録画開始日時（RFC 3339）
+    pub date: String,
+    /// 録画フォルダ全体 (bytes)
+    pub bytes: u64,
+    /// うちフレーム (bytes)
+    pub frames_bytes: u64,
+    /// 書き出しに成功したことがあるか
+    pub exported: bool,
+    /// フレームが自動整理で削除済みか
+    pub frames_removed: bool,
+}
+
+/// 録画フォルダのディスク使用量（新しい録画順）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct StorageUsage {
+    pub recordings: Vec<RecordingUsage>,
+    /// 録画フォルダ全体 (bytes)
+    pub total_bytes: u64,
+}
+
+/// 自動整理（`apply_storage_policies`）で消したものと空いた容量
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct StoragePolicyReport {
+    /// フレームを削除した録画
+    pub frames_removed: u32,
+    /// 削除した録画
+    pub recordings_deleted: u32,
+    /// 空いた容量 (bytes)
+    pub reclaimed_bytes: u64,
+}
+
+/// `batch-export-progress` イベントのペイロード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct BatchExportProgress {
+    /// 書き出し中の録画の番号（0 始まり）
+    pub index: u32,
+    pub count: u32,
+    pub recording_id: String,
+    /// 書き出し中の録画のステージと進捗 (0〜1)
+    pub stage: String,
+    pub progress: f64,
+    /// 一括書き出し全体の進捗 (0〜1)
+    pub overall: f64,
+}
+
+/// 一括書き出しの 1 件の結果。成功なら `output_path`、失敗なら `error`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct BatchExportItem {
+    pub recording_id: String,
+    pub output_path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// `batch-export-complete` イベントのペイロード
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct BatchExportReport {
+    /// 指定した順
+    pub items: Vec<BatchExportItem>,
+    pub succeeded: u32,
+    pub failed: u32,
+}
+
+/// `upload-progress` イベントのペイロード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-export", ts(export, export_to = "../../src/lib/generated/"))]
+pub struct UploadProgress {
+    pub target: ShareTarget,
+    pub path: String,
+    pub uploaded_bytes: u64,
+    pub total_bytes: u64,
+}
+
 /// Export progress
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-export", derive(ts_rs::TS))]
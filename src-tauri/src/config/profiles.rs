@@ -0,0 +1,155 @@
+//! 設定プロファイル（仕事用・SNS 用・チュートリアル用など）。
+//!
+//! スタイル・エフェクト・出力設定をまとめて名前を付け、`settings.json` と同じ
+//! フォルダの `profiles\` に保存する。適用すると 3 つとも丸ごと入れ替わる。
+//!
+//! 共有用のスタイルプリセット（[`super::style_preset`]）と違い、このマシン
+//! だけで使うため保存先フォルダも含めて切り替える（「仕事用は別フォルダへ」
+//! ができる）。合成エンジンは GPU の有無で決まるので今の値を残す。
+
+use super::{AppSettings, EffectsSettings, OutputSettings, StyleSettings};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// このビルドが書き出す（読み込める最大の）フォーマットバージョン
+pub const PROFILE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub version: u32,
+    pub name: String,
+    /// 保存した日時（RFC 3339）
+    #[serde(default)]
+    pub saved_at: String,
+    /// 欠けている項目は既定値で補う（古いバージョンで作ったプロファイル用）
+    #[serde(default)]
+    pub style: StyleSettings,
+    #[serde(default)]
+    pub effects: EffectsSettings,
+    #[serde(default)]
+    pub output: OutputSettings,
+}
+
+impl SettingsProfile {
+    /// 現在の設定からプロファイルを作る
+    pub fn from_settings(name: &str, settings: &AppSettings) -> Self {
+        Self {
+            version: PROFILE_VERSION,
+            name: name.trim().to_string(),
+            saved_at: chrono::Local::now().to_rfc3339(),
+            style: settings.style.clone(),
+            effects: settings.effects.clone(),
+            output: settings.output.clone(),
+        }
+    }
+
+    /// プロファイルを設定に適用する。合成エンジンは `settings` の値を残す
+    pub fn apply_to(&self, settings: &mut AppSettings) {
+        let compositor_backend = settings.output.compositor_backend;
+        settings.style = self.style.clone();
+        settings.effects = self.effects.clone();
+        settings.output = self.output.clone();
+        settings.output.compositor_backend = compositor_backend;
+    }
+
+    fn read(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read profile {}", path.display()))?;
+        let profile: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Not a valid profile: {}", path.display()))?;
+        if profile.version > PROFILE_VERSION {
+            bail!(
+                "Profile \"{}\" was made with a newer version of Snappi (format {})",
+                profile.name,
+                profile.version
+            );
+        }
+        Ok(profile)
+    }
+}
+
+/// プロファイルの保存先: %APPDATA%\Snappi\profiles
+pub fn profiles_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Snappi")
+        .join("profiles")
+}
+
+fn profile_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", super::style_preset::file_stem(name)))
+}
+
+/// `dir` にあるプロファイル（名前順）。読めないファイルは読み飛ばす
+pub fn list_profiles(dir: &Path) -> Vec<SettingsProfile> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<SettingsProfile> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|p| match SettingsProfile::read(&p) {
+            Ok(profile) => Some(profile),
+            Err(e) => {
+                log::warn!("Skipping profile: {}", e);
+                None
+            }
+        })
+        .collect();
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    profiles
+}
+
+/// 現在の設定を `name` のプロファイルとして `dir` に保存する。同名のプロファイルは上書きする
+pub fn save_profile(dir: &Path, name: &str, settings: &AppSettings) -> Result<SettingsProfile> {
+    if name.trim().is_empty() {
+        bail!("Profile name is empty");
+    }
+    let profile = SettingsProfile::from_settings(name, settings);
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(profile_path(dir, &profile.name), serde_json::to_string_pretty(&profile)?)?;
+    Ok(profile)
+}
+
+/// `dir` から名前でプロファイルを探す
+pub fn find_profile(dir: &Path, name: &str) -> Result<SettingsProfile> {
+    list_profiles(dir)
+        .into_iter()
+        .find(|p| p.name == name)
+        .with_context(|| format!("Profile not found: {}", name))
+}
+
+pub fn delete_profile(dir: &Path, name: &str) -> Result<()> {
+    let path = profile_path(dir, &find_profile(dir, name)?.name);
+    std::fs::remove_file(&path).with_context(|| format!("Failed to delete profile {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_apply_and_delete() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut social = AppSettings::default();
+        social.style.border_radius = 32;
+        social.output.save_directory = "D:\\social".to_string();
+        save_profile(dir.path(), " SNS 用 ", &social).unwrap();
+        save_profile(dir.path(), "仕事用", &AppSettings::default()).unwrap();
+        assert!(save_profile(dir.path(), "  ", &social).is_err());
+
+        let names: Vec<String> = list_profiles(dir.path()).into_iter().map(|p| p.name).collect();
+        assert_eq!(names, ["SNS 用", "仕事用"]);
+
+        let mut current = AppSettings::default();
+        find_profile(dir.path(), "SNS 用").unwrap().apply_to(&mut current);
+        assert_eq!(current.style.border_radius, 32);
+        assert_eq!(current.output.save_directory, "D:\\social");
+
+        delete_profile(dir.path(), "SNS 用").unwrap();
+        assert_eq!(list_profiles(dir.path()).len(), 1);
+        assert!(find_profile(dir.path(), "SNS 用").is_err());
+    }
+}
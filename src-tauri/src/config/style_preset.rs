@@ -0,0 +1,191 @@
+//! 共有用スタイルプリセット（`.snappi-style`）。
+//!
+//! スタイル・エフェクト・出力設定を 1 つの JSON ファイルにまとめ、チームで
+//! 同じ見た目の動画を書き出せるようにする。読み込んだプリセットは
+//! `%APPDATA%\Snappi\presets\` に保存され、書き出し時に名前で指定できる。
+//!
+//! 保存先フォルダ・合成エンジン・スクリプトのパスなど、マシンごとに違う
+//! 設定はプリセットに含めても適用しない。背景・透かし・タイトルカードの
+//! 画像パスはそのまま共有されるため、読み込む側にも同じファイルが必要。
+
+use super::{AppSettings, EffectsSettings, OutputSettings, StyleSettings};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// プリセットファイルの拡張子
+pub const PRESET_EXTENSION: &str = "snappi-style";
+
+/// このビルドが書き出す（読み込める最大の）フォーマットバージョン
+pub const PRESET_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StylePreset {
+    pub version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// 欠けている項目は既定値で補う（古いバージョンで作ったプリセット用）
+    #[serde(default)]
+    pub style: StyleSettings,
+    #[serde(default)]
+    pub effects: EffectsSettings,
+    #[serde(default)]
+    pub output: OutputSettings,
+}
+
+impl StylePreset {
+    /// 現在の設定からプリセットを作る
+    pub fn from_settings(name: &str, description: Option<String>, settings: &AppSettings) -> Self {
+        Self {
+            version: PRESET_VERSION,
+            name: name.to_string(),
+            description,
+            style: settings.style.clone(),
+            effects: settings.effects.clone(),
+            output: settings.output.clone(),
+        }
+    }
+
+    /// プリセットを設定に適用する。マシン固有の設定は `settings` の値を残す
+    pub fn apply_to(&self, settings: &mut AppSettings) {
+        let save_directory = std::mem::take(&mut settings.output.save_directory);
+        let compositor_backend = settings.output.compositor_backend;
+        let script_path = settings.effects.script_path.take();
+
+        settings.style = self.style.clone();
+        settings.effects = self.effects.clone();
+        settings.output = self.output.clone();
+
+        settings.output.save_directory = save_directory;
+        settings.output.compositor_backend = compositor_backend;
+        settings.effects.script_path = script_path;
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read preset {}", path.display()))?;
+        let preset: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Not a valid .{} file: {}", PRESET_EXTENSION, path.display()))?;
+        if preset.version > PRESET_VERSION {
+            bail!(
+                "Preset \"{}\" was made with a newer version of Snappi (format {})",
+                preset.name,
+                preset.version
+            );
+        }
+        if preset.name.trim().is_empty() {
+            bail!("Preset has no name: {}", path.display());
+        }
+        Ok(preset)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// 読み込んだプリセットの保存先: %APPDATA%\Snappi\presets
+pub fn presets_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Snappi")
+        .join("presets")
+}
+
+/// プリセット名をファイル名に使える形にする（プロファイルも使う）
+pub(super) fn file_stem(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect()
+}
+
+fn preset_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.{}", file_stem(name), PRESET_EXTENSION))
+}
+
+/// `dir` にあるプリセット（名前順）。読めないファイルは読み飛ばす
+pub fn list_presets(dir: &Path) -> Vec<StylePreset> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut presets: Vec<StylePreset> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == PRESET_EXTENSION))
+        .filter_map(|p| match StylePreset::read(&p) {
+            Ok(preset) => Some(preset),
+            Err(e) => {
+                log::warn!("Skipping preset: {}", e);
+                None
+            }
+        })
+        .collect();
+    presets.sort_by(|a, b| a.name.cmp(&b.name));
+    presets
+}
+
+/// `dir` から名前でプリセットを探す
+pub fn find_preset(dir: &Path, name: &str) -> Result<StylePreset> {
+    list_presets(dir)
+        .into_iter()
+        .find(|p| p.name == name)
+        .with_context(|| format!("Style preset not found: {}", name))
+}
+
+/// `source` のプリセットを `dir` に取り込む。同名のプリセットは上書きする
+pub fn import_preset(dir: &Path, source: &Path) -> Result<StylePreset> {
+    let preset = StylePreset::read(source)?;
+    preset.write(&preset_path(dir, &preset.name))?;
+    Ok(preset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_and_apply_keeps_machine_settings() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut shared = AppSettings::default();
+        shared.style.border_radius = 24;
+        shared.effects.max_zoom = 3.5;
+        shared.output.save_directory = "C:\\Users\\alice\\Videos".to_string();
+        shared.stream.stream_key = "live_secret".to_string();
+        let exported = dir.path().join("team.snappi-style");
+        StylePreset::from_settings("Team / Docs", None, &shared).write(&exported).unwrap();
+        // The stream key never leaves this machine
+        assert!(!std::fs::read_to_string(&exported).unwrap().contains("live_secret"));
+
+        let presets = dir.path().join("presets");
+        import_preset(&presets, &exported).unwrap();
+        let preset = find_preset(&presets, "Team / Docs").unwrap();
+
+        let mut mine = AppSettings::default();
+        mine.output.save_directory = "D:\\clips".to_string();
+        mine.stream.stream_key = "mine".to_string();
+        preset.apply_to(&mut mine);
+        assert_eq!(mine.style.border_radius, 24);
+        assert!((mine.effects.max_zoom - 3.5).abs() < 1e-9);
+        assert_eq!(mine.output.save_directory, "D:\\clips");
+        assert_eq!(mine.stream.stream_key, "mine");
+    }
+
+    #[test]
+    fn test_newer_format_rejected() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("future.snappi-style");
+        std::fs::write(&path, r#"{"version": 99, "name": "future"}"#).unwrap();
+        assert!(StylePreset::read(&path).is_err());
+
+        // Missing sections fall back to defaults
+        std::fs::write(&path, r#"{"version": 1, "name": "minimal"}"#).unwrap();
+        let preset = StylePreset::read(&path).unwrap();
+        assert_eq!(preset.style.border_radius, StyleSettings::default().border_radius);
+    }
+}
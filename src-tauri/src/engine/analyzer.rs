@@ -624,6 +624,7 @@ mod tests {
                 t: 0,
                 title: "Notepad".to_string(),
                 rect: [100.0, 100.0, 600.0, 500.0],
+                process: String::new(),
             },
             click(200, 300.0, 300.0),
         ];
@@ -640,6 +641,7 @@ mod tests {
                 t: 0,
                 title: "Notepad".to_string(),
                 rect: [100.0, 100.0, 600.0, 500.0],
+                process: String::new(),
             },
             click(1000, 300.0, 300.0),
         ];
@@ -0,0 +1,122 @@
+//! Annotations: arrows, boxes and text callouts shown over a time range.
+//!
+//! Stored per recording in `annotations.json` next to `meta.json`. Positions
+//! are in recorded frame pixels (like redactions), so a callout stays on the
+//! UI element it points at while the camera zooms and pans; sizes in
+//! [`AnnotationStyle`] are output pixels. Times are on the recording
+//! timeline and re-timed with the export's cuts.
+
+use serde::{Deserialize, Serialize};
+
+/// Fade in/out length at both ends of an annotation
+const ANNOTATION_FADE_MS: f64 = 150.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum AnnotationShape {
+    /// Arrow pointing from `from` to `to` (the text sits at the tail)
+    Arrow { from: [f64; 2], to: [f64; 2] },
+    /// Outline around [left, top, right, bottom] (the text sits above it)
+    Box { rect: [f64; 4] },
+    /// Text callout with its top-left corner at `at`
+    Text { at: [f64; 2] },
+}
+
+impl AnnotationShape {
+    /// The same shape with every point passed through `f`.
+    pub fn map_points(&self, f: impl Fn(f64, f64) -> (f64, f64)) -> Self {
+        match *self {
+            Self::Arrow { from, to } => {
+                let (fx, fy) = f(from[0], from[1]);
+                let (tx, ty) = f(to[0], to[1]);
+                Self::Arrow { from: [fx, fy], to: [tx, ty] }
+            }
+            Self::Box { rect } => {
+                let (x0, y0) = f(rect[0], rect[1]);
+                let (x1, y1) = f(rect[2], rect[3]);
+                Self::Box { rect: [x0, y0, x1, y1] }
+            }
+            Self::Text { at } => {
+                let (x, y) = f(at[0], at[1]);
+                Self::Text { at: [x, y] }
+            }
+        }
+    }
+}
+
+fn default_color() -> [u8; 4] { [239, 68, 68, 255] }
+fn default_stroke_width() -> f64 { 4.0 }
+fn default_font_size() -> f64 { 24.0 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationStyle {
+    /// Stroke / callout background color (RGBA)
+    #[serde(default = "default_color")]
+    pub color: [u8; 4],
+    #[serde(default = "default_stroke_width")]
+    pub stroke_width: f64,
+    #[serde(default = "default_font_size")]
+    pub font_size: f64,
+}
+
+impl Default for AnnotationStyle {
+    fn default() -> Self {
+        Self {
+            color: default_color(),
+            stroke_width: default_stroke_width(),
+            font_size: default_font_size(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// Visible over `[start_ms, end_ms)`
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub shape: AnnotationShape,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub style: AnnotationStyle,
+}
+
+impl Annotation {
+    /// 0.0 – 1.0, fading in and out over [`ANNOTATION_FADE_MS`].
+    pub fn opacity(&self, time_ms: u64) -> f64 {
+        if time_ms < self.start_ms || time_ms >= self.end_ms {
+            return 0.0;
+        }
+        let fade_in = (time_ms - self.start_ms) as f64 / ANNOTATION_FADE_MS;
+        let fade_out = (self.end_ms - time_ms) as f64 / ANNOTATION_FADE_MS;
+        fade_in.min(fade_out).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_format_and_defaults() {
+        let json = r#"[{"start_ms":1000,"end_ms":3000,"shape":{"type":"Arrow","from":[10,20],"to":[110,120]},"text":"Click here"}]"#;
+        let annotations: Vec<Annotation> = serde_json::from_str(json).unwrap();
+        assert_eq!(annotations[0].shape, AnnotationShape::Arrow { from: [10.0, 20.0], to: [110.0, 120.0] });
+        assert_eq!(annotations[0].style, AnnotationStyle::default());
+    }
+
+    #[test]
+    fn test_opacity_fades_at_both_ends() {
+        let annotation = Annotation {
+            start_ms: 1000,
+            end_ms: 2000,
+            shape: AnnotationShape::Text { at: [0.0, 0.0] },
+            text: None,
+            style: AnnotationStyle::default(),
+        };
+        assert_eq!(annotation.opacity(999), 0.0);
+        assert!((annotation.opacity(1075) - 0.5).abs() < 1e-9);
+        assert_eq!(annotation.opacity(1500), 1.0);
+        assert_eq!(annotation.opacity(2000), 0.0);
+    }
+}
@@ -0,0 +1,159 @@
+//! Excluding apps from zoom planning (`EffectsSettings::zoom_excluded_apps`).
+//!
+//! Input in some windows shouldn't steer the camera: a music player clicked
+//! now and then, a chat window answered in passing. Events that happened in
+//! an excluded window are removed from the events used to split scenes and
+//! plan zoom; the frames, cursor and click effects still show them.
+//!
+//! The foreground window comes from `window_events.jsonl`, polled every
+//! 100 ms, so the click that brings a window to the front is recorded
+//! slightly before its focus event. Pointer events inside the window's
+//! rect up to [`FOCUS_LAG_MS`] before it gains focus count as its input.
+
+use crate::config::RecordingEvent;
+use crate::engine::analyzer::event_timestamp;
+
+/// How long before its focus event a click can have activated a window
+pub const FOCUS_LAG_MS: u64 = 300;
+
+/// Whether a window matches one of `patterns`: the process name (with or
+/// without `.exe`) or part of the title, ignoring case.
+pub fn is_excluded(title: &str, process: &str, patterns: &[String]) -> bool {
+    let title = title.to_lowercase();
+    let process = process.to_lowercase();
+    let process_stem = process.strip_suffix(".exe").unwrap_or(&process);
+    patterns.iter().map(|p| p.trim().to_lowercase()).filter(|p| !p.is_empty()).any(|p| {
+        let stem = p.strip_suffix(".exe").unwrap_or(&p);
+        (!process_stem.is_empty() && stem == process_stem) || title.contains(&p)
+    })
+}
+
+/// A period during which an excluded window was in the foreground
+#[derive(Debug, Clone)]
+struct ExcludedSpan {
+    start_ms: u64,
+    /// None: until the end of the recording
+    end_ms: Option<u64>,
+    rect: [f64; 4],
+}
+
+impl ExcludedSpan {
+    fn contains_time(&self, t: u64) -> bool {
+        t >= self.start_ms && self.end_ms.is_none_or(|end| t < end)
+    }
+
+    fn activated_by(&self, t: u64, x: f64, y: f64) -> bool {
+        t < self.start_ms
+            && t + FOCUS_LAG_MS >= self.start_ms
+            && x >= self.rect[0]
+            && x <= self.rect[2]
+            && y >= self.rect[1]
+            && y <= self.rect[3]
+    }
+}
+
+/// The excluded windows of one recording.
+#[derive(Debug, Clone, Default)]
+pub struct AppExclusion {
+    spans: Vec<ExcludedSpan>,
+}
+
+impl AppExclusion {
+    /// Foreground periods of windows matching `patterns`, from the
+    /// recording's window focus events.
+    pub fn new(window_events: &[RecordingEvent], patterns: &[String]) -> Self {
+        let mut spans: Vec<ExcludedSpan> = Vec::new();
+        let mut open = false;
+        for event in window_events {
+            let RecordingEvent::WindowFocus { t, title, rect, process } = event else {
+                continue;
+            };
+            if open {
+                if let Some(span) = spans.last_mut() {
+                    span.end_ms = Some(*t);
+                }
+            }
+            open = is_excluded(title, process, patterns);
+            if open {
+                spans.push(ExcludedSpan { start_ms: *t, end_ms: None, rect: *rect });
+            }
+        }
+        Self { spans }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Whether `event` was input to an excluded window.
+    pub fn excludes(&self, event: &RecordingEvent) -> bool {
+        let t = event_timestamp(event);
+        let point = match event {
+            RecordingEvent::MouseMove { x, y, .. }
+            | RecordingEvent::Click { x, y, .. }
+            | RecordingEvent::ClickRelease { x, y, .. }
+            | RecordingEvent::Scroll { x, y, .. } => Some((*x, *y)),
+            _ => None,
+        };
+        self.spans.iter().any(|span| {
+            span.contains_time(t) || point.is_some_and(|(x, y)| span.activated_by(t, x, y))
+        })
+    }
+
+    /// `events` without the input to excluded windows.
+    pub fn filter(&self, events: &[RecordingEvent]) -> Vec<RecordingEvent> {
+        if self.is_empty() {
+            return events.to_vec();
+        }
+        events.iter().filter(|e| !self.excludes(e)).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn focus(t: u64, title: &str, process: &str) -> RecordingEvent {
+        RecordingEvent::WindowFocus {
+            t,
+            title: title.into(),
+            rect: [1500.0, 800.0, 1900.0, 1050.0],
+            process: process.into(),
+        }
+    }
+
+    fn click(t: u64, x: f64, y: f64) -> RecordingEvent {
+        RecordingEvent::Click { t, btn: "left".into(), x, y }
+    }
+
+    #[test]
+    fn test_matches_process_or_title() {
+        let patterns = vec!["Spotify".to_string(), "slack".to_string()];
+        assert!(is_excluded("Daily Mix", "Spotify.exe", &patterns));
+        assert!(is_excluded("general - Slack", "", &patterns));
+        assert!(!is_excluded("main.rs - Visual Studio Code", "Code.exe", &patterns));
+        assert!(!is_excluded("Anything", "", &[" ".to_string()]));
+    }
+
+    #[test]
+    fn test_filters_input_while_excluded_window_is_in_front() {
+        let windows = vec![
+            focus(0, "main.rs - Visual Studio Code", "Code.exe"),
+            focus(5000, "Daily Mix", "Spotify.exe"),
+            focus(8000, "main.rs - Visual Studio Code", "Code.exe"),
+        ];
+        let exclusion = AppExclusion::new(&windows, &["spotify".to_string()]);
+        let events = vec![
+            click(1000, 400.0, 300.0),
+            // Activates the player: just before its focus event, inside its rect
+            click(4900, 1700.0, 900.0),
+            click(6000, 1700.0, 900.0),
+            RecordingEvent::Key { t: 7000, key: "Space".into(), modifiers: vec![] },
+            click(9000, 400.0, 300.0),
+        ];
+        let kept: Vec<u64> = exclusion.filter(&events).iter().map(event_timestamp).collect();
+        assert_eq!(kept, vec![1000, 9000]);
+
+        assert_eq!(AppExclusion::new(&windows, &[]).filter(&events).len(), 5);
+    }
+}
@@ -1,7 +1,25 @@
+use super::annotation::{Annotation, AnnotationShape, AnnotationStyle};
 use super::effects::background::create_background_image;
+#[cfg(feature = "effect-plugins")]
+use super::effects::plugin::{EffectContext, EffectPlugin};
+use super::effects::text::{draw_text, load_system_font, measure_text};
+use super::effects::title_card;
+use super::effects::window_chrome::wrap_in_window_chrome;
+#[cfg(feature = "gpu")]
+use super::gpu_compositor::GpuCompositor;
+use super::redaction::{self, Redaction};
+use super::scene_style::SceneStyleOverride;
+#[cfg(feature = "scripting")]
+use super::scripting::{ExportScript, OverlayPrimitive};
 use super::spring::AnimatedViewport;
+use super::typed_text::TypedCaption;
 use super::zoom_planner::ZoomKeyframe;
 use crate::config::defaults::OutputStyle;
+use crate::config::{
+    BackgroundConfig, BadgePosition, CompositorBackend, DisclaimerSettings, KeyBadgeStyle, ProgressBarSettings, RibbonPosition, SpotlightSettings,
+    TitleCardSettings, WatermarkPosition, WatermarkSettings,
+};
+use ab_glyph::FontArc;
 use image::{Rgba, RgbaImage};
 
 /// Cursor sprite base size in pixels (before zoom scaling)
@@ -11,6 +29,30 @@ const CURSOR_BASE_SIZE: u32 = 32;
 const EMBEDDED_CURSOR_PNG: &[u8] = include_bytes!("../../../icon/カーソル.png");
 const EMBEDDED_CURSOR_HOTSPOT: (u32, u32) = (35, 22);
 
+/// Fade duration (ms) of UI highlight outlines
+const UI_HIGHLIGHT_FADE_MS: f64 = 150.0;
+const UI_HIGHLIGHT_COLOR: [u8; 3] = [250, 204, 21];
+const UI_HIGHLIGHT_LABEL_SIZE: f32 = 15.0;
+
+/// Reduced motion: cross-fade length and the smallest re-framing that is
+/// applied (smaller pans/zooms are skipped to keep the picture still)
+const REDUCED_MOTION_FADE_MS: f64 = 300.0;
+const REDUCED_MOTION_MIN_PAN: f64 = 0.05;
+const REDUCED_MOTION_MIN_ZOOM: f64 = 0.15;
+
+/// Spotlight click pulse length and soft edge width (fraction of the radius)
+const SPOTLIGHT_PULSE_MS: u64 = 400;
+const SPOTLIGHT_FEATHER: f64 = 0.35;
+
+/// Arrowhead length and half-width, in multiples of the stroke width
+const ARROW_HEAD_LENGTH: f64 = 4.0;
+const ARROW_HEAD_HALF_WIDTH: f64 = 2.5;
+
+/// Typed-text caption bubble
+const TYPED_CAPTION_SIZE: f32 = 22.0;
+const TYPED_CAPTION_BG: [u8; 4] = [20, 20, 20, 215];
+const TYPED_CAPTION_TEXT: [u8; 4] = [255, 255, 255, 255];
+
 pub struct Compositor {
     style: OutputStyle,
     viewport: AnimatedViewport,
@@ -22,20 +64,68 @@ pub struct Compositor {
     cursor_sprite: RgbaImage,
     /// Cursor hotspot offset within sprite (tip position)
     cursor_hotspot: (u32, u32),
+    /// The user's OS cursor size relative to the default, so the rendered
+    /// cursor is as large as the one seen while recording
+    os_cursor_scale: f64,
+    /// Display scaling of the recorded monitor (see `overlay_scale`)
+    monitor_scale: f64,
     /// Previous composed frame for motion blur
     prev_output: Option<RgbaImage>,
     /// Previous viewport state for motion amount calculation
     prev_vp_center: Option<(f64, f64, f64)>, // (cx, cy, zoom)
     /// Whether motion blur is enabled
     motion_blur_enabled: bool,
+    /// Cut + cross-fade to keyframes instead of spring animation
+    reduced_motion: bool,
+    /// Last composed canvas (reduced motion only), the next fade's source
+    last_canvas: Option<RgbaImage>,
+    /// Cross-fade in progress: source frame and start time (set on the
+    /// first frame after the jump)
+    fade_from: Option<RgbaImage>,
+    fade_start_ms: Option<u64>,
+    /// Logo image scaled to its output width (see `WatermarkSettings`)
+    watermark: Option<RgbaImage>,
+    /// Font for text overlays (loaded on first use, see `ensure_font`)
+    font: Option<FontArc>,
+    font_loaded: bool,
+    /// Dialog / menu outlines
+    ui_highlights: Vec<UiHighlight>,
+    /// Typed-text captions
+    typed_captions: Vec<TypedCaption>,
+    /// Click times (output timeline, ascending) that pulse the spotlight
+    spotlight_clicks: Vec<u64>,
+    /// Redacted regions (output timeline), applied to the raw frame
+    redactions: Vec<Redaction>,
+    /// Arrows / boxes / callouts (output timeline)
+    annotations: Vec<Annotation>,
+    /// Per-scene background / border radius (output timeline)
+    scene_styles: Vec<SceneStyleOverride>,
+    /// Background and border radius from settings, restored after a scene
+    base_frame_style: (BackgroundConfig, u32),
+    /// Index into `scene_styles` of the override applied to `style`
+    active_scene_style: Option<usize>,
+    /// Output duration of the clip the progress bar spans (0 = unknown)
+    clip_duration_ms: u64,
+    /// wgpu backend for crop/scale, cursor, corners and shadow (`None` = CPU)
+    #[cfg(feature = "gpu")]
+    gpu: Option<GpuCompositor>,
+    /// User export script (overlay hook)
+    #[cfg(feature = "scripting")]
+    script: Option<ExportScript>,
+    #[cfg(feature = "effect-plugins")]
+    plugins: Vec<Box<dyn EffectPlugin>>,
+    /// Recording events handed to plugin hooks
+    #[cfg(feature = "effect-plugins")]
+    plugin_events: Vec<crate::config::RecordingEvent>,
 }
 
 impl Compositor {
     pub fn new(style: OutputStyle, screen_width: u32, screen_height: u32) -> Self {
-        let viewport = AnimatedViewport::new(
+        let mut viewport = AnimatedViewport::new(
             screen_width as f64,
             screen_height as f64,
         );
+        viewport.crop_aspect = style.crop_aspect;
 
         // Load embedded cursor PNG, fallback to system capture, then to SDF sprite
         let (cursor_sprite, cursor_hotspot) = load_embedded_cursor()
@@ -52,6 +142,9 @@ impl Compositor {
                 (create_cursor_sprite(CURSOR_BASE_SIZE), (6, 6))
             });
 
+        let watermark = style.watermark.as_ref().and_then(load_watermark);
+        let base_frame_style = (style.background.clone(), style.border_radius);
+
         Self {
             style,
             viewport,
@@ -60,9 +153,143 @@ impl Compositor {
             cached_background: None,
             cursor_sprite,
             cursor_hotspot,
+            os_cursor_scale: 1.0,
+            monitor_scale: 1.0,
             prev_output: None,
             prev_vp_center: None,
             motion_blur_enabled: false,
+            reduced_motion: false,
+            last_canvas: None,
+            fade_from: None,
+            fade_start_ms: None,
+            watermark,
+            font: None,
+            font_loaded: false,
+            ui_highlights: Vec::new(),
+            typed_captions: Vec::new(),
+            spotlight_clicks: Vec::new(),
+            redactions: Vec::new(),
+            annotations: Vec::new(),
+            scene_styles: Vec::new(),
+            base_frame_style,
+            active_scene_style: None,
+            clip_duration_ms: 0,
+            #[cfg(feature = "gpu")]
+            gpu: None,
+            #[cfg(feature = "scripting")]
+            script: None,
+            #[cfg(feature = "effect-plugins")]
+            plugins: Vec::new(),
+            #[cfg(feature = "effect-plugins")]
+            plugin_events: Vec::new(),
+        }
+    }
+
+    /// Draw the script's `overlay` primitives on every frame.
+    #[cfg(feature = "scripting")]
+    pub fn set_script(&mut self, script: Option<ExportScript>) {
+        if script.is_some() {
+            self.ensure_font();
+        }
+        self.script = script;
+    }
+
+    /// Run `plugins` on every frame, in order.
+    #[cfg(feature = "effect-plugins")]
+    pub fn set_plugins(&mut self, plugins: Vec<Box<dyn EffectPlugin>>, events: Vec<crate::config::RecordingEvent>) {
+        if !plugins.is_empty() {
+            let names: Vec<&str> = plugins.iter().map(|p| p.name()).collect();
+            log::info!("Effect plugins enabled: {:?}", names);
+        }
+        self.plugins = plugins;
+        self.plugin_events = events;
+    }
+
+    /// Outline dialogs/menus while open (times on the output timeline).
+    pub fn set_ui_highlights(&mut self, highlights: Vec<UiHighlight>) {
+        if !highlights.is_empty() {
+            self.ensure_font();
+        }
+        self.ui_highlights = highlights;
+    }
+
+    /// Show typed text near the focused field (times on the output timeline).
+    pub fn set_typed_captions(&mut self, captions: Vec<TypedCaption>) {
+        if !captions.is_empty() {
+            self.ensure_font();
+        }
+        self.typed_captions = captions;
+    }
+
+    /// Clicks that briefly widen the spotlight (times on the output timeline).
+    pub fn set_spotlight_clicks(&mut self, mut clicks: Vec<u64>) {
+        clicks.sort_unstable();
+        self.spotlight_clicks = clicks;
+    }
+
+    /// Pixelate/blur regions of the raw frame (times on the output timeline).
+    pub fn set_redactions(&mut self, redactions: Vec<Redaction>) {
+        if !redactions.is_empty() {
+            log::info!("Redacting {} regions", redactions.len());
+        }
+        self.redactions = redactions;
+    }
+
+    /// Draw user annotations (times on the output timeline).
+    pub fn set_annotations(&mut self, annotations: Vec<Annotation>) {
+        if annotations.iter().any(|a| a.text.is_some()) {
+            self.ensure_font();
+        }
+        self.annotations = annotations;
+    }
+
+    /// Per-scene background and border radius (times on the output
+    /// timeline). Effect toggles are applied to the effect lists instead.
+    pub fn set_scene_styles(&mut self, overrides: Vec<SceneStyleOverride>) {
+        self.scene_styles = overrides;
+    }
+
+    /// Output duration of the clip, for the progress bar. The bar is not
+    /// drawn while this is 0.
+    pub fn set_clip_duration(&mut self, duration_ms: u64) {
+        self.clip_duration_ms = duration_ms;
+    }
+
+    /// Switch the background and border radius to the scene at `time_ms`.
+    /// The background is rebuilt only when the scene changes.
+    fn update_scene_style(&mut self, time_ms: u64) {
+        let active = self.scene_styles.iter().rposition(|o| o.contains(time_ms));
+        if active == self.active_scene_style {
+            return;
+        }
+        self.active_scene_style = active;
+        let (ref base_background, base_radius) = self.base_frame_style;
+        let scene = active.map(|i| &self.scene_styles[i]);
+        let background = scene.and_then(|o| o.background.clone()).unwrap_or_else(|| base_background.clone());
+        let border_radius = scene.and_then(|o| o.border_radius).unwrap_or(base_radius);
+        if background != self.style.background {
+            self.style.background = background;
+            self.cached_background = None;
+        }
+        self.style.border_radius = border_radius;
+    }
+
+    /// Spotlight radius at `time_ms`: widened by a sine pulse after clicks.
+    fn spotlight_radius(&self, spotlight: &SpotlightSettings, time_ms: u64) -> f64 {
+        let recent = self.spotlight_clicks.partition_point(|&t| t <= time_ms);
+        let pulse = recent
+            .checked_sub(1)
+            .map(|i| time_ms - self.spotlight_clicks[i])
+            .filter(|&dt| dt < SPOTLIGHT_PULSE_MS)
+            .map_or(0.0, |dt| (std::f64::consts::PI * dt as f64 / SPOTLIGHT_PULSE_MS as f64).sin());
+        spotlight.radius.max(1.0) * (1.0 + spotlight.click_pulse.max(0.0) * pulse)
+    }
+
+    /// Look up the system font once, the first time text is drawn.
+    fn ensure_font(&mut self) {
+        if !self.font_loaded {
+            self.font = load_system_font();
+            self.font_loaded = true;
         }
     }
 
@@ -107,7 +334,58 @@ impl Compositor {
         self.motion_blur_enabled = enabled;
     }
 
+    pub fn set_reduced_motion(&mut self, enabled: bool) {
+        self.reduced_motion = enabled;
+    }
+
+    /// Select the CPU or GPU backend. The GPU backend needs the `gpu`
+    /// feature and a usable adapter; otherwise the CPU path is kept.
+    pub fn set_backend(&mut self, backend: CompositorBackend) {
+        #[cfg(feature = "gpu")]
+        {
+            self.gpu = match backend {
+                CompositorBackend::Cpu => None,
+                CompositorBackend::Gpu => GpuCompositor::new(&self.cursor_sprite)
+                    .map_err(|e| log::warn!("GPU compositor unavailable, using the CPU: {}", e))
+                    .ok(),
+            };
+        }
+        #[cfg(not(feature = "gpu"))]
+        if backend == CompositorBackend::Gpu {
+            log::warn!("Built without the gpu feature; using the CPU compositor");
+        }
+    }
+
+    pub fn set_os_cursor_scale(&mut self, scale: f64) {
+        self.os_cursor_scale = scale;
+    }
+
+    pub fn set_monitor_scale(&mut self, scale: f64) {
+        self.monitor_scale = scale;
+    }
+
+    /// Size factor for the cursor and click rings, so they keep the physical
+    /// size they had on the recorded screen: a 150%-scaled screen exported at
+    /// native resolution gets 1.5x. Never below 1.0, so exports scaled down
+    /// from large screens keep the overlay size they always had.
+    fn overlay_scale(&self) -> f64 {
+        let output_per_screen = (self.style.output_width as f64 / self.screen_width)
+            .min(self.style.output_height as f64 / self.screen_height);
+        (self.monitor_scale * output_per_screen).max(1.0)
+    }
+
+    /// Full-canvas intro / outro card on this export's background.
+    pub fn render_title_card(&mut self, card: &TitleCardSettings) -> RgbaImage {
+        self.ensure_font();
+        let background = self.get_or_create_background(None).clone();
+        title_card::render_title_card(card, &background, self.font.as_ref())
+    }
+
     pub fn apply_keyframe(&mut self, kf: &ZoomKeyframe) {
+        if self.reduced_motion {
+            self.jump_to_keyframe(kf);
+            return;
+        }
         if let Some(ref hint) = kf.spring_hint {
             self.viewport.set_target_with_half_life(
                 kf.target_x,
@@ -121,6 +399,33 @@ impl Compositor {
         }
     }
 
+    /// Move the camera straight to its current target, e.g. where the
+    /// recording was paused and the footage jumps.
+    pub fn cut_camera(&mut self) {
+        let (x, y, zoom) =
+            (self.viewport.center_x.target, self.viewport.center_y.target, self.viewport.zoom.target);
+        self.viewport.snap_to(x, y, zoom);
+    }
+
+    /// Run the camera springs for `dt` seconds without composing a frame
+    /// (brings a single preview frame to the state the export would reach).
+    pub fn advance_camera(&mut self, dt: f64) {
+        self.viewport.update(dt);
+    }
+
+    /// Reduced motion: cut to the keyframe and cross-fade from the last frame.
+    fn jump_to_keyframe(&mut self, kf: &ZoomKeyframe) {
+        let dx = (kf.target_x - self.viewport.center_x.target) / self.screen_width;
+        let dy = (kf.target_y - self.viewport.center_y.target) / self.screen_height;
+        let dz = (kf.zoom_level - self.viewport.zoom.target).abs();
+        if (dx * dx + dy * dy).sqrt() < REDUCED_MOTION_MIN_PAN && dz < REDUCED_MOTION_MIN_ZOOM {
+            return;
+        }
+        self.viewport.snap_to(kf.target_x, kf.target_y, kf.zoom_level);
+        self.fade_from = self.last_canvas.take();
+        self.fade_start_ms = None;
+    }
+
     pub fn compose_frame(
         &mut self,
         raw_frame: &RgbaImage,
@@ -130,25 +435,66 @@ impl Compositor {
         key_overlay: Option<&KeyOverlay>,
         dt: f64,
     ) -> RgbaImage {
+        // (0) Redactions are burned into a copy of the raw frame, before
+        // plugins and crop/zoom can see it
+        let redacted;
+        let raw_frame = if self.redactions.iter().any(|r| r.is_active(frame_time_ms)) {
+            let mut frame = raw_frame.clone();
+            redaction::apply_redactions(&mut frame, &self.redactions, frame_time_ms);
+            redacted = frame;
+            &redacted
+        } else {
+            raw_frame
+        };
+
+        // (0b) Plugin pre-compose hooks work on a copy of the raw frame
+        #[cfg(feature = "effect-plugins")]
+        let pre_composed;
+        #[cfg(feature = "effect-plugins")]
+        let raw_frame = if self.plugins.is_empty() {
+            raw_frame
+        } else {
+            let mut frame = raw_frame.clone();
+            let ctx = EffectContext {
+                time_ms: frame_time_ms,
+                events: &self.plugin_events,
+                screen_width: self.screen_width as u32,
+                screen_height: self.screen_height as u32,
+            };
+            for plugin in &mut self.plugins {
+                plugin.pre_compose(&mut frame, &ctx);
+            }
+            pre_composed = frame;
+            &pre_composed
+        };
+
         // (1) Update spring animation
         self.viewport.update(dt);
 
         let vp = self.viewport.current_viewport(self.screen_width, self.screen_height);
         let zoom = vp.zoom;
 
-        // (2) Crop and scale to output size (using Triangle filter for speed)
-        let mut output = crop_and_scale(
-            raw_frame,
-            vp.x,
-            vp.y,
-            vp.width,
-            vp.height,
-            self.style.output_width,
-            self.style.output_height,
-        );
+        // Cursor position in output px and its sprite scale
+        let cursor = cursor_pos.map(|(cx, cy)| {
+            let (out_x, out_y) = self.viewport.to_output_coords(
+                cx,
+                cy,
+                self.style.output_width as f64,
+                self.style.output_height as f64,
+                self.screen_width,
+                self.screen_height,
+            );
+            let cursor_scale = self.style.cursor_size_multiplier * self.os_cursor_scale * self.overlay_scale() * zoom;
+            (out_x, out_y, cursor_scale)
+        });
 
-        // (3) Draw cursor — scale with zoom to maintain consistent visual size
-        if let Some((cx, cy)) = cursor_pos {
+        // (2) Crop and scale to output size. The GPU backend draws the cursor
+        // in the same pass, unless the spotlight has to go under it.
+        let gpu_cursor = if self.style.spotlight.is_none() { cursor } else { None };
+        let (mut output, cursor_drawn) = self.crop_and_scale_stage(raw_frame, vp.x, vp.y, vp.width, vp.height, gpu_cursor);
+
+        // (2b) Spotlight: dim everything but the area around the cursor
+        if let (Some(spotlight), Some((cx, cy))) = (self.style.spotlight.as_ref(), cursor_pos) {
             let (out_x, out_y) = self.viewport.to_output_coords(
                 cx,
                 cy,
@@ -157,7 +503,12 @@ impl Compositor {
                 self.screen_width,
                 self.screen_height,
             );
-            let cursor_scale = self.style.cursor_size_multiplier * zoom;
+            let radius = self.spotlight_radius(spotlight, frame_time_ms);
+            draw_spotlight(&mut output, out_x, out_y, radius, spotlight.dim_opacity);
+        }
+
+        // (3) Draw cursor — scale with zoom to maintain consistent visual size
+        if let Some((out_x, out_y, cursor_scale)) = cursor.filter(|_| !cursor_drawn) {
             draw_cursor_sprite(
                 &mut output,
                 &self.cursor_sprite,
@@ -169,6 +520,7 @@ impl Compositor {
         }
 
         // (4) Click ring effects — scale with zoom
+        let ring_scale = self.overlay_scale() * zoom;
         for effect in click_effects {
             if effect.is_active(frame_time_ms) {
                 let (out_x, out_y) = self.viewport.to_output_coords(
@@ -180,57 +532,149 @@ impl Compositor {
                     self.screen_height,
                 );
                 let progress = effect.progress(frame_time_ms);
+                let color = if effect.alt {
+                    &self.style.click_ring_alt_color
+                } else {
+                    &self.style.click_ring_color
+                };
                 draw_click_ring(
                     &mut output,
                     out_x,
                     out_y,
                     progress,
-                    self.style.click_ring_max_radius * zoom,
-                    &self.style.click_ring_color,
-                    self.style.click_ring_stroke_width * zoom,
+                    self.style.click_ring_max_radius * ring_scale,
+                    color,
+                    self.style.click_ring_stroke_width * ring_scale,
                 );
+                if effect.alt {
+                    // Inner second ring distinguishes the shape, not just the color
+                    draw_click_ring(
+                        &mut output,
+                        out_x,
+                        out_y,
+                        progress * 0.6,
+                        self.style.click_ring_max_radius * ring_scale,
+                        color,
+                        self.style.click_ring_stroke_width * ring_scale,
+                    );
+                }
             }
         }
 
         // (5) Key badge overlay
         if let Some(overlay) = key_overlay {
             if overlay.is_visible(frame_time_ms) {
-                draw_key_badge(
-                    &mut output,
-                    &overlay.keys,
-                    self.style.output_width,
-                    self.style.output_height,
-                );
+                self.ensure_font();
+                draw_key_badge(&mut output, &overlay.keys, &self.style.key_badge, self.font.as_ref());
             }
         }
 
-        // (6) Rounded corners with anti-aliasing
-        if self.style.border_radius > 0 {
-            apply_rounded_corners_aa(&mut output, self.style.border_radius);
+        // (5b) Dialog / menu outlines
+        for highlight in &self.ui_highlights {
+            let opacity = highlight.opacity(frame_time_ms);
+            if opacity <= 0.0 {
+                continue;
+            }
+            let [left, top, right, bottom] = highlight.rect;
+            let to_out = |x: f64, y: f64| {
+                self.viewport.to_output_coords(
+                    x,
+                    y,
+                    self.style.output_width as f64,
+                    self.style.output_height as f64,
+                    self.screen_width,
+                    self.screen_height,
+                )
+            };
+            let (x0, y0) = to_out(left, top);
+            let (x1, y1) = to_out(right, bottom);
+            draw_ui_highlight(&mut output, (x0, y0, x1, y1), &highlight.label, opacity, self.font.as_ref());
         }
 
-        // (7) Shadow + background composition (with caching)
-        let canvas = self.get_or_create_background();
-        let mut canvas = canvas.clone();
+        // (5c) Typed-text caption (the latest caption wins if two overlap)
+        if let Some(font) = self.font.as_ref() {
+            let visible = self
+                .typed_captions
+                .iter()
+                .rev()
+                .find_map(|c| c.text_at(frame_time_ms).map(|text| (c, text)));
+            if let Some((caption, text)) = visible {
+                let anchor = caption.anchor.map(|[left, top, right, bottom]| {
+                    let to_out = |x: f64, y: f64| {
+                        self.viewport.to_output_coords(
+                            x,
+                            y,
+                            self.style.output_width as f64,
+                            self.style.output_height as f64,
+                            self.screen_width,
+                            self.screen_height,
+                        )
+                    };
+                    let (x0, y0) = to_out(left, top);
+                    let (x1, y1) = to_out(right, bottom);
+                    (x0, y0, x1, y1)
+                });
+                draw_typed_caption(&mut output, text, anchor, font);
+            }
+        }
 
-        let offset_x = (self.style.canvas_width - self.style.output_width) / 2;
-        let offset_y = (self.style.canvas_height - self.style.output_height) / 2;
+        // (5d) Annotations, in the order they were defined
+        for annotation in &self.annotations {
+            let opacity = annotation.opacity(frame_time_ms);
+            if opacity <= 0.0 {
+                continue;
+            }
+            let shape = annotation.shape.map_points(|x, y| {
+                self.viewport.to_output_coords(
+                    x,
+                    y,
+                    self.style.output_width as f64,
+                    self.style.output_height as f64,
+                    self.screen_width,
+                    self.screen_height,
+                )
+            });
+            draw_annotation(
+                &mut output,
+                &shape,
+                annotation.text.as_deref(),
+                &annotation.style,
+                opacity,
+                self.font.as_ref(),
+            );
+        }
 
-        // Draw rounded-rectangle shadow (matches border_radius)
-        draw_drop_shadow(
-            &mut canvas,
-            offset_x,
-            offset_y,
-            self.style.output_width,
-            self.style.output_height,
-            self.style.shadow_blur,
-            self.style.shadow_offset_y,
-            &self.style.shadow_color,
-            self.style.border_radius,
-        );
+        // (6) Window chrome
+        if self.style.window_chrome.is_some() {
+            self.ensure_font();
+        }
+        if let Some(ref chrome) = self.style.window_chrome {
+            output = wrap_in_window_chrome(&output, chrome, self.font.as_ref());
+        }
+        // (7) Rounded corners, shadow + background composition (with caching)
+        if !self.scene_styles.is_empty() {
+            self.update_scene_style(frame_time_ms);
+        }
+        let mut canvas = self.canvas_stage(raw_frame, output);
 
-        // Composite the output frame onto the canvas
-        composite(&mut canvas, &output, offset_x, offset_y);
+        // (7b) Logo watermark
+        if let (Some(ref logo), Some(ref settings)) = (&self.watermark, &self.style.watermark) {
+            draw_watermark(&mut canvas, logo, settings);
+        }
+
+        // Reduced motion: cross-fade from the frame before the last jump
+        if self.reduced_motion {
+            if let Some(ref from) = self.fade_from {
+                let start = *self.fade_start_ms.get_or_insert(frame_time_ms);
+                let progress = (frame_time_ms - start) as f64 / REDUCED_MOTION_FADE_MS;
+                if progress >= 1.0 || from.dimensions() != canvas.dimensions() {
+                    self.fade_from = None;
+                } else {
+                    crossfade(&mut canvas, from, 1.0 - progress);
+                }
+            }
+            self.last_canvas = Some(canvas.clone());
+        }
 
         // Motion blur: blend with previous frame when viewport is moving fast
         if self.motion_blur_enabled {
@@ -259,15 +703,133 @@ impl Compositor {
             self.prev_output = Some(canvas.clone());
         }
 
+        // (8) Input-captured disclaimer ribbon (after motion blur so it stays crisp)
+        let show_disclaimer = self.style.disclaimer.as_ref()
+            .is_some_and(|d| d.duration_ms == 0 || frame_time_ms < d.duration_ms);
+        if show_disclaimer {
+            self.ensure_font();
+            if let Some(ref disclaimer) = self.style.disclaimer {
+                draw_disclaimer_ribbon(&mut canvas, disclaimer, self.font.as_ref());
+            }
+        }
+
+        // (8a) Playback progress bar
+        if let Some(ref bar) = self.style.progress_bar {
+            if self.clip_duration_ms > 0 {
+                let progress = frame_time_ms as f64 / self.clip_duration_ms as f64;
+                draw_progress_bar(&mut canvas, bar, progress);
+            }
+        }
+
+        // (8b) Export script overlays
+        #[cfg(feature = "scripting")]
+        if let Some(script) = self.script.as_mut() {
+            let primitives = script.overlays(frame_time_ms, canvas.width(), canvas.height());
+            draw_script_overlays(&mut canvas, &primitives, self.font.as_ref());
+        }
+
+        // (9) Plugin post-compose hooks
+        #[cfg(feature = "effect-plugins")]
+        {
+            let ctx = EffectContext {
+                time_ms: frame_time_ms,
+                events: &self.plugin_events,
+                screen_width: self.screen_width as u32,
+                screen_height: self.screen_height as u32,
+            };
+            for plugin in &mut self.plugins {
+                plugin.post_compose(&mut canvas, &ctx);
+            }
+        }
+
         canvas
     }
 
-    fn get_or_create_background(&mut self) -> &RgbaImage {
+    /// Stage (2): the viewport cropped and scaled to the output size, plus
+    /// whether `cursor` (output x, y, sprite scale) was drawn as well.
+    fn crop_and_scale_stage(
+        &mut self,
+        raw_frame: &RgbaImage,
+        x: f64,
+        y: f64,
+        w: f64,
+        h: f64,
+        cursor: Option<(f64, f64, f64)>,
+    ) -> (RgbaImage, bool) {
+        let (out_w, out_h) = (self.style.output_width, self.style.output_height);
+        #[cfg(feature = "gpu")]
+        if let (Some(gpu), Some(crop)) = (self.gpu.as_mut(), crop_rect(raw_frame, x, y, w, h)) {
+            let cursor_rect = cursor.and_then(|(cx, cy, scale)| {
+                cursor_sprite_rect(&self.cursor_sprite, cx, cy, scale, self.cursor_hotspot)
+            });
+            match gpu.crop_and_scale(raw_frame, crop, out_w, out_h, cursor_rect) {
+                Ok(output) => return (output, cursor.is_some()),
+                Err(e) => {
+                    log::warn!("GPU compositor failed, falling back to the CPU: {}", e);
+                    self.gpu = None;
+                }
+            }
+        }
+        #[cfg(not(feature = "gpu"))]
+        let _ = cursor;
+        // Triangle filter for speed
+        (crop_and_scale(raw_frame, x, y, w, h, out_w, out_h), false)
+    }
+
+    /// Stage (7): the window with anti-aliased rounded corners and its drop
+    /// shadow, centered on the background.
+    fn canvas_stage(&mut self, raw_frame: &RgbaImage, mut output: RgbaImage) -> RgbaImage {
+        let (window_w, window_h) = output.dimensions();
+        let offset_x = self.style.canvas_width.saturating_sub(window_w) / 2;
+        let offset_y = self.style.canvas_height.saturating_sub(window_h) / 2;
+
+        #[cfg(feature = "gpu")]
+        if self.gpu.is_some() {
+            self.get_or_create_background(Some(raw_frame));
+        }
+        #[cfg(feature = "gpu")]
+        if let (Some(gpu), Some(background)) = (self.gpu.as_mut(), self.cached_background.as_ref()) {
+            match gpu.compose_canvas(background, &output, (offset_x, offset_y), &self.style) {
+                Ok(canvas) => return canvas,
+                Err(e) => {
+                    log::warn!("GPU compositor failed, falling back to the CPU: {}", e);
+                    self.gpu = None;
+                }
+            }
+        }
+
+        if self.style.border_radius > 0 {
+            apply_rounded_corners_aa(&mut output, self.style.border_radius);
+        }
+        let mut canvas = self.get_or_create_background(Some(raw_frame)).clone();
+
+        // Draw rounded-rectangle shadow (matches border_radius)
+        draw_drop_shadow(
+            &mut canvas,
+            offset_x,
+            offset_y,
+            window_w,
+            window_h,
+            self.style.shadow_blur,
+            self.style.shadow_offset_y,
+            &self.style.shadow_color,
+            self.style.border_radius,
+        );
+
+        // Composite the output frame onto the canvas
+        composite(&mut canvas, &output, offset_x, offset_y);
+        canvas
+    }
+
+    /// The canvas background, built on first use. A blurred background is
+    /// made from the first frame passed in.
+    fn get_or_create_background(&mut self, frame: Option<&RgbaImage>) -> &RgbaImage {
         if self.cached_background.is_none() {
             self.cached_background = Some(create_background_image(
                 self.style.canvas_width,
                 self.style.canvas_height,
                 &self.style.background,
+                frame,
             ));
         }
         self.cached_background.as_ref().unwrap()
@@ -280,6 +842,8 @@ pub struct ClickEffect {
     pub y: f64,
     pub start_ms: u64,
     pub duration_ms: u64,
+    /// Drawn with the alternate color as a double ring (e.g. right click)
+    pub alt: bool,
 }
 
 impl ClickEffect {
@@ -302,6 +866,32 @@ fn ease_out_cubic(t: f64) -> f64 {
     1.0 - (1.0 - t).powi(3)
 }
 
+/// Transient UI (dialog / context menu) outlined while it is open.
+#[derive(Debug, Clone)]
+pub struct UiHighlight {
+    /// Screen rect [left, top, right, bottom]
+    pub rect: [f64; 4],
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub label: String,
+}
+
+impl UiHighlight {
+    pub fn is_visible(&self, time_ms: u64) -> bool {
+        time_ms >= self.start_ms && time_ms < self.end_ms
+    }
+
+    /// Opacity with a short fade in/out at both ends
+    fn opacity(&self, time_ms: u64) -> f64 {
+        if !self.is_visible(time_ms) {
+            return 0.0;
+        }
+        let fade_in = (time_ms - self.start_ms) as f64 / UI_HIGHLIGHT_FADE_MS;
+        let fade_out = (self.end_ms - time_ms) as f64 / UI_HIGHLIGHT_FADE_MS;
+        fade_in.min(fade_out).min(1.0)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct KeyOverlay {
     pub keys: String,
@@ -315,6 +905,15 @@ impl KeyOverlay {
     }
 }
 
+/// The viewport clamped to whole source pixels, `None` when empty
+fn crop_rect(src: &RgbaImage, x: f64, y: f64, w: f64, h: f64) -> Option<(u32, u32, u32, u32)> {
+    let src_x = x.max(0.0) as u32;
+    let src_y = y.max(0.0) as u32;
+    let src_w = (w as u32).min(src.width().saturating_sub(src_x));
+    let src_h = (h as u32).min(src.height().saturating_sub(src_y));
+    (src_w > 0 && src_h > 0).then_some((src_x, src_y, src_w, src_h))
+}
+
 fn crop_and_scale(
     src: &RgbaImage,
     x: f64,
@@ -324,14 +923,9 @@ fn crop_and_scale(
     out_w: u32,
     out_h: u32,
 ) -> RgbaImage {
-    let src_x = x.max(0.0) as u32;
-    let src_y = y.max(0.0) as u32;
-    let src_w = (w as u32).min(src.width().saturating_sub(src_x));
-    let src_h = (h as u32).min(src.height().saturating_sub(src_y));
-
-    if src_w == 0 || src_h == 0 {
+    let Some((src_x, src_y, src_w, src_h)) = crop_rect(src, x, y, w, h) else {
         return RgbaImage::new(out_w, out_h);
-    }
+    };
 
     let cropped = image::imageops::crop_imm(src, src_x, src_y, src_w, src_h).to_image();
     // Use Triangle (bilinear) filter instead of Lanczos3 for 2-3x speedup
@@ -481,6 +1075,20 @@ fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64 {
 }
 
 /// Draw cursor by scaling the pre-rendered sprite and alpha-compositing it.
+/// Where the scaled cursor sprite goes: top-left (hotspot at `x`, `y`)
+/// and size in output px. `None` when it scales to nothing.
+fn cursor_sprite_rect(sprite: &RgbaImage, x: f64, y: f64, size_mult: f64, hotspot: (u32, u32)) -> Option<(i32, i32, u32, u32)> {
+    let scale = size_mult * (CURSOR_BASE_SIZE as f64) / (sprite.width().max(1) as f64);
+    let target_w = ((sprite.width() as f64) * scale) as u32;
+    let target_h = ((sprite.height() as f64) * scale) as u32;
+    if target_w == 0 || target_h == 0 {
+        return None;
+    }
+    let hotspot_x = (hotspot.0 as f64 * scale) as i32;
+    let hotspot_y = (hotspot.1 as f64 * scale) as i32;
+    Some((x as i32 - hotspot_x, y as i32 - hotspot_y, target_w, target_h))
+}
+
 fn draw_cursor_sprite(
     img: &mut RgbaImage,
     sprite: &RgbaImage,
@@ -489,12 +1097,9 @@ fn draw_cursor_sprite(
     size_mult: f64,
     hotspot: (u32, u32),
 ) {
-    let scale = size_mult * (CURSOR_BASE_SIZE as f64) / (sprite.width().max(1) as f64);
-    let target_w = ((sprite.width() as f64) * scale) as u32;
-    let target_h = ((sprite.height() as f64) * scale) as u32;
-    if target_w == 0 || target_h == 0 {
+    let Some((start_x, start_y, target_w, target_h)) = cursor_sprite_rect(sprite, x, y, size_mult, hotspot) else {
         return;
-    }
+    };
 
     // Use CatmullRom for high-quality cursor scaling (better than Triangle for sharp edges)
     let scaled = image::imageops::resize(
@@ -504,11 +1109,6 @@ fn draw_cursor_sprite(
         image::imageops::FilterType::CatmullRom,
     );
 
-    let hotspot_x = (hotspot.0 as f64 * scale) as i32;
-    let hotspot_y = (hotspot.1 as f64 * scale) as i32;
-    let start_x = x as i32 - hotspot_x;
-    let start_y = y as i32 - hotspot_y;
-
     for sy in 0..scaled.height() {
         for sx in 0..scaled.width() {
             let px = start_x + sx as i32;
@@ -581,15 +1181,55 @@ fn draw_click_ring(
     }
 }
 
-fn draw_key_badge(img: &mut RgbaImage, keys: &str, output_width: u32, output_height: u32) {
-    // Draw a simple key badge at bottom center
-    let badge_height = 32u32;
-    let badge_width = (keys.len() as u32 * 10 + 20).min(output_width);
-    let x_start = (output_width - badge_width) / 2;
-    let y_start = output_height - badge_height - 20;
+/// Distance (px) between the key badge and the frame edge
+const KEY_BADGE_MARGIN: i64 = 20;
+
+/// Darken the frame outside a soft-edged circle at (cx, cy).
+fn draw_spotlight(img: &mut RgbaImage, cx: f64, cy: f64, radius: f64, dim_opacity: f64) {
+    let dim = dim_opacity.clamp(0.0, 1.0);
+    let feather = radius * SPOTLIGHT_FEATHER;
+    for (x, y, pixel) in img.enumerate_pixels_mut() {
+        let dx = x as f64 + 0.5 - cx;
+        let dy = y as f64 + 0.5 - cy;
+        let d = (dx * dx + dy * dy).sqrt();
+        let factor = 1.0 - dim * smoothstep(radius, radius + feather, d);
+        if factor < 1.0 {
+            for c in 0..3 {
+                pixel[c] = (pixel[c] as f64 * factor) as u8;
+            }
+        }
+    }
+}
 
-    // Badge background with rounded corners (8px)
-    let badge_radius = 8u32;
+/// Key combo badge: rounded box with the key text. Without a font the box is
+/// sized from an estimated glyph width and drawn empty.
+fn draw_key_badge(img: &mut RgbaImage, keys: &str, style: &KeyBadgeStyle, font: Option<&FontArc>) {
+    let size = style.font_size.max(8.0) as f32;
+    let (text_w, text_h) = match font {
+        Some(font) => measure_text(font, keys, size),
+        None => ((keys.chars().count() as f32 * size * 0.6) as u32, size as u32),
+    };
+    let pad_x = (size * 0.75) as u32;
+    let pad_y = (size * 0.4) as u32;
+    let badge_width = (text_w + pad_x * 2).min(img.width());
+    let badge_height = (text_h + pad_y * 2).min(img.height());
+
+    let (w, h) = (img.width() as i64, img.height() as i64);
+    let (bw, bh) = (badge_width as i64, badge_height as i64);
+    let x_start = match style.position {
+        BadgePosition::BottomLeft => KEY_BADGE_MARGIN,
+        BadgePosition::BottomRight => w - bw - KEY_BADGE_MARGIN,
+        BadgePosition::BottomCenter | BadgePosition::TopCenter => (w - bw) / 2,
+    }
+    .max(0) as u32;
+    let y_start = match style.position {
+        BadgePosition::TopCenter => KEY_BADGE_MARGIN,
+        _ => h - bh - KEY_BADGE_MARGIN,
+    }
+    .max(0) as u32;
+
+    // Badge background with rounded corners
+    let badge_radius = (badge_height / 3).clamp(1, 8);
     for y in y_start..y_start + badge_height {
         for x in x_start..x_start + badge_width {
             if x < img.width() && y < img.height() {
@@ -617,11 +1257,352 @@ fn draw_key_badge(img: &mut RgbaImage, keys: &str, output_width: u32, output_hei
                 }
 
                 let pixel = img.get_pixel(x, y);
-                let blended = blend_pixel(*pixel, Rgba([30, 30, 30, 200]));
+                let blended = blend_pixel(*pixel, Rgba(style.background_color));
                 img.put_pixel(x, y, blended);
             }
         }
     }
+
+    if let Some(font) = font {
+        let text_x = x_start as i32 + (badge_width as i32 - text_w as i32) / 2;
+        let text_y = y_start as i32 + (badge_height as i32 - text_h as i32) / 2;
+        draw_text(img, font, keys, text_x, text_y, size, style.text_color);
+    }
+}
+
+/// Outline a rect (output coords) with a small label tab on its top edge.
+fn draw_ui_highlight(
+    img: &mut RgbaImage,
+    rect: (f64, f64, f64, f64),
+    label: &str,
+    opacity: f64,
+    font: Option<&FontArc>,
+) {
+    let (w, h) = (img.width() as i64, img.height() as i64);
+    let x0 = (rect.0.round() as i64).clamp(0, w - 1);
+    let y0 = (rect.1.round() as i64).clamp(0, h - 1);
+    let x1 = (rect.2.round() as i64).clamp(0, w - 1);
+    let y1 = (rect.3.round() as i64).clamp(0, h - 1);
+    if x1 <= x0 || y1 <= y0 {
+        return;
+    }
+
+    let [r, g, b] = UI_HIGHLIGHT_COLOR;
+    let color = Rgba([r, g, b, (220.0 * opacity) as u8]);
+    let stroke = 2;
+    for y in y0..=y1 {
+        for x in x0..=x1 {
+            let on_edge = x - x0 < stroke || x1 - x < stroke || y - y0 < stroke || y1 - y < stroke;
+            if on_edge {
+                let blended = blend_pixel(*img.get_pixel(x as u32, y as u32), color);
+                img.put_pixel(x as u32, y as u32, blended);
+            }
+        }
+    }
+
+    let Some(font) = font.filter(|_| !label.is_empty()) else {
+        return;
+    };
+    let (text_w, text_h) = measure_text(font, label, UI_HIGHLIGHT_LABEL_SIZE);
+    let pad = 4;
+    let tab_h = text_h as i64 + pad;
+    // Above the outline when there is room, otherwise just inside it
+    let tab_y = if y0 >= tab_h { y0 - tab_h } else { y0 };
+    let tab_w = (text_w as i64 + pad * 2).min(w - x0);
+    let tab_bg = Rgba([r, g, b, (200.0 * opacity) as u8]);
+    for y in tab_y..(tab_y + tab_h).min(h) {
+        for x in x0..x0 + tab_w {
+            let blended = blend_pixel(*img.get_pixel(x as u32, y as u32), tab_bg);
+            img.put_pixel(x as u32, y as u32, blended);
+        }
+    }
+    draw_text(
+        img,
+        font,
+        label,
+        (x0 + pad) as i32,
+        (tab_y + pad / 2) as i32,
+        UI_HIGHLIGHT_LABEL_SIZE,
+        [20, 20, 20, (255.0 * opacity) as u8],
+    );
+}
+
+/// Caption bubble with the typed text: just below the focused field (above
+/// it when there is no room), or at the bottom center without a field.
+fn draw_typed_caption(
+    img: &mut RgbaImage,
+    text: &str,
+    anchor: Option<(f64, f64, f64, f64)>,
+    font: &FontArc,
+) {
+    let (w, h) = (img.width() as i64, img.height() as i64);
+    let (text_w, text_h) = measure_text(font, text, TYPED_CAPTION_SIZE);
+    let pad_x = (TYPED_CAPTION_SIZE * 0.6) as i64;
+    let pad_y = (TYPED_CAPTION_SIZE * 0.3) as i64;
+    let bw = (text_w as i64 + pad_x * 2).min(w);
+    let bh = (text_h as i64 + pad_y * 2).min(h);
+    let gap = 8;
+
+    let (x, y) = match anchor {
+        // Field visible in the frame
+        Some((x0, y0, x1, y1)) if x1 > 0.0 && y1 > 0.0 && x0 < w as f64 && y0 < h as f64 => {
+            let below = y1.round() as i64 + gap;
+            let y = if below + bh <= h { below } else { y0.round() as i64 - gap - bh };
+            (x0.round() as i64, y)
+        }
+        // Above the key badge area
+        _ => ((w - bw) / 2, h - bh - KEY_BADGE_MARGIN * 4),
+    };
+    let x = x.clamp(0, (w - bw).max(0));
+    let y = y.clamp(0, (h - bh).max(0));
+
+    let bg = Rgba(TYPED_CAPTION_BG);
+    for py in y..y + bh {
+        for px in x..x + bw {
+            let blended = blend_pixel(*img.get_pixel(px as u32, py as u32), bg);
+            img.put_pixel(px as u32, py as u32, blended);
+        }
+    }
+    // Keep the caret end visible when the text is wider than the frame
+    let text_x = (x + pad_x).min(x + bw - pad_x - text_w as i64);
+    draw_text(
+        img,
+        font,
+        text,
+        text_x as i32,
+        (y + pad_y) as i32,
+        TYPED_CAPTION_SIZE,
+        TYPED_CAPTION_TEXT,
+    );
+}
+
+/// Annotation in output coordinates: the shape plus an optional text
+/// callout (at the arrow tail, above the box, or at the text position).
+fn draw_annotation(
+    img: &mut RgbaImage,
+    shape: &AnnotationShape,
+    text: Option<&str>,
+    style: &AnnotationStyle,
+    opacity: f64,
+    font: Option<&FontArc>,
+) {
+    let [r, g, b, a] = style.color;
+    let color = Rgba([r, g, b, (a as f64 * opacity) as u8]);
+    let stroke = style.stroke_width.max(1.0);
+    let size = style.font_size.max(8.0) as f32;
+
+    let label_at = match *shape {
+        AnnotationShape::Arrow { from, to } => {
+            let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+            let len = (dx * dx + dy * dy).sqrt();
+            if len > 0.0 {
+                let (ux, uy) = (dx / len, dy / len);
+                let head_len = (stroke * ARROW_HEAD_LENGTH).min(len);
+                let half_w = stroke * ARROW_HEAD_HALF_WIDTH;
+                let (bx, by) = (to[0] - ux * head_len, to[1] - uy * head_len);
+                draw_segment(img, (from[0], from[1]), (bx, by), stroke, color);
+                let head = [
+                    (to[0], to[1]),
+                    (bx - uy * half_w, by + ux * half_w),
+                    (bx + uy * half_w, by - ux * half_w),
+                ];
+                fill_polygon(img, &head, color);
+            }
+            // Text on the side of the tail facing away from the arrow
+            let above = to[1] >= from[1];
+            (from[0], from[1], above)
+        }
+        AnnotationShape::Box { rect: [x0, y0, x1, y1] } => {
+            let (left, top, right, bottom) = (x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1));
+            let corners = [(left, top), (right, top), (right, bottom), (left, bottom)];
+            for i in 0..4 {
+                draw_segment(img, corners[i], corners[(i + 1) % 4], stroke, color);
+            }
+            (left, top - stroke / 2.0, true)
+        }
+        AnnotationShape::Text { at } => (at[0], at[1], false),
+    };
+
+    let (Some(text), Some(font)) = (text.filter(|t| !t.is_empty()), font) else {
+        return;
+    };
+    let (w, h) = (img.width() as i64, img.height() as i64);
+    let (text_w, text_h) = measure_text(font, text, size);
+    let pad_x = (size * 0.5) as i64;
+    let pad_y = (size * 0.3) as i64;
+    let bw = (text_w as i64 + pad_x * 2).min(w);
+    let bh = (text_h as i64 + pad_y * 2).min(h);
+    let (lx, ly, above) = label_at;
+    let x = (lx.round() as i64).clamp(0, (w - bw).max(0));
+    let y = if above { ly.round() as i64 - bh } else { ly.round() as i64 };
+    let y = y.clamp(0, (h - bh).max(0));
+    for py in y..y + bh {
+        for px in x..x + bw {
+            let blended = blend_pixel(*img.get_pixel(px as u32, py as u32), color);
+            img.put_pixel(px as u32, py as u32, blended);
+        }
+    }
+    draw_text(img, font, text, (x + pad_x) as i32, (y + pad_y) as i32, size, [255, 255, 255, (255.0 * opacity) as u8]);
+}
+
+/// Anti-aliased thick line segment with round caps.
+fn draw_segment(img: &mut RgbaImage, p: (f64, f64), q: (f64, f64), width: f64, color: Rgba<u8>) {
+    let half = width / 2.0;
+    let (w, h) = (img.width() as f64, img.height() as f64);
+    let x_min = (p.0.min(q.0) - half - 1.0).floor().max(0.0) as u32;
+    let y_min = (p.1.min(q.1) - half - 1.0).floor().max(0.0) as u32;
+    let x_max = (p.0.max(q.0) + half + 1.0).ceil().min(w) as u32;
+    let y_max = (p.1.max(q.1) + half + 1.0).ceil().min(h) as u32;
+    let (ex, ey) = (q.0 - p.0, q.1 - p.1);
+    let len_sq = ex * ex + ey * ey;
+    for py in y_min..y_max {
+        for px in x_min..x_max {
+            let (wx, wy) = (px as f64 + 0.5 - p.0, py as f64 + 0.5 - p.1);
+            let t = if len_sq > 0.0 { ((wx * ex + wy * ey) / len_sq).clamp(0.0, 1.0) } else { 0.0 };
+            let (dx, dy) = (wx - ex * t, wy - ey * t);
+            let coverage = (half + 0.5 - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                let src = Rgba([color[0], color[1], color[2], (color[3] as f64 * coverage) as u8]);
+                let blended = blend_pixel(*img.get_pixel(px, py), src);
+                img.put_pixel(px, py, blended);
+            }
+        }
+    }
+}
+
+/// Anti-aliased filled convex polygon.
+fn fill_polygon(img: &mut RgbaImage, pts: &[(f64, f64)], color: Rgba<u8>) {
+    let (w, h) = (img.width() as f64, img.height() as f64);
+    let x_min = pts.iter().map(|p| p.0).fold(f64::MAX, f64::min).floor().max(0.0) as u32;
+    let y_min = pts.iter().map(|p| p.1).fold(f64::MAX, f64::min).floor().max(0.0) as u32;
+    let x_max = pts.iter().map(|p| p.0).fold(f64::MIN, f64::max).ceil().min(w) as u32;
+    let y_max = pts.iter().map(|p| p.1).fold(f64::MIN, f64::max).ceil().min(h) as u32;
+    for py in y_min..y_max {
+        for px in x_min..x_max {
+            let d = signed_distance_to_polygon(pts, px as f64 + 0.5, py as f64 + 0.5);
+            let coverage = (0.5 - d).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                let src = Rgba([color[0], color[1], color[2], (color[3] as f64 * coverage) as u8]);
+                let blended = blend_pixel(*img.get_pixel(px, py), src);
+                img.put_pixel(px, py, blended);
+            }
+        }
+    }
+}
+
+/// Draw `overlay` hook primitives (canvas coords). Text is skipped without
+/// a font.
+#[cfg(feature = "scripting")]
+fn draw_script_overlays(img: &mut RgbaImage, primitives: &[OverlayPrimitive], font: Option<&FontArc>) {
+    let (w, h) = (img.width() as i64, img.height() as i64);
+    for primitive in primitives {
+        match primitive {
+            OverlayPrimitive::Rect { x, y, w: rw, h: rh, color } => {
+                let x0 = (x.round() as i64).clamp(0, w);
+                let y0 = (y.round() as i64).clamp(0, h);
+                let x1 = ((x + rw).round() as i64).clamp(0, w);
+                let y1 = ((y + rh).round() as i64).clamp(0, h);
+                for py in y0..y1 {
+                    for px in x0..x1 {
+                        let blended = blend_pixel(*img.get_pixel(px as u32, py as u32), Rgba(*color));
+                        img.put_pixel(px as u32, py as u32, blended);
+                    }
+                }
+            }
+            OverlayPrimitive::Text { x, y, text, size, color } => {
+                if let Some(font) = font {
+                    draw_text(img, font, text, x.round() as i32, y.round() as i32, size.max(1.0), *color);
+                }
+            }
+        }
+    }
+}
+
+/// Full-width ribbon at the top or bottom of the canvas with centered text.
+/// The band is still drawn when no font is available.
+fn draw_disclaimer_ribbon(img: &mut RgbaImage, disclaimer: &DisclaimerSettings, font: Option<&FontArc>) {
+    let size = disclaimer.font_size.max(8.0) as f32;
+    let padding = (size * 0.5).ceil() as u32;
+    let band_height = (size.ceil() as u32 + padding * 2).min(img.height());
+    let y_start = match disclaimer.position {
+        RibbonPosition::Top => 0,
+        RibbonPosition::Bottom => img.height() - band_height,
+    };
+
+    let bg = Rgba(disclaimer.background_color);
+    for y in y_start..y_start + band_height {
+        for x in 0..img.width() {
+            let blended = blend_pixel(*img.get_pixel(x, y), bg);
+            img.put_pixel(x, y, blended);
+        }
+    }
+
+    if let Some(font) = font {
+        let (text_w, text_h) = measure_text(font, &disclaimer.text, size);
+        let x = (img.width() as i32 - text_w as i32) / 2;
+        let y = y_start as i32 + (band_height as i32 - text_h as i32) / 2;
+        draw_text(img, font, &disclaimer.text, x.max(0), y, size, disclaimer.text_color);
+    }
+}
+
+/// Bar along the bottom edge of the canvas, filled to `progress` (0.0 – 1.0)
+/// over its track.
+fn draw_progress_bar(img: &mut RgbaImage, bar: &ProgressBarSettings, progress: f64) {
+    let height = bar.height.min(img.height());
+    let filled = (img.width() as f64 * progress.clamp(0.0, 1.0)).round() as u32;
+    for y in img.height() - height..img.height() {
+        for x in 0..img.width() {
+            let color = if x < filled { bar.color } else { bar.track_color };
+            if color[3] > 0 {
+                let blended = blend_pixel(*img.get_pixel(x, y), Rgba(color));
+                img.put_pixel(x, y, blended);
+            }
+        }
+    }
+}
+
+/// Load the watermark PNG and scale it to `settings.width`.
+fn load_watermark(settings: &WatermarkSettings) -> Option<RgbaImage> {
+    let path = settings.image_path.as_deref()?;
+    let logo = match image::open(path) {
+        Ok(img) => img.to_rgba8(),
+        Err(e) => {
+            log::warn!("Failed to load watermark from '{}': {}", path, e);
+            return None;
+        }
+    };
+    if settings.width <= 0.0 || logo.width() == 0 {
+        return Some(logo);
+    }
+    let width = settings.width.round().max(1.0) as u32;
+    let height = ((logo.height() as f64 * width as f64 / logo.width() as f64).round() as u32).max(1);
+    Some(image::imageops::resize(&logo, width, height, image::imageops::FilterType::Lanczos3))
+}
+
+/// Blend the logo into the chosen canvas corner at `settings.opacity`.
+fn draw_watermark(canvas: &mut RgbaImage, logo: &RgbaImage, settings: &WatermarkSettings) {
+    let margin = settings.margin.max(0.0).round() as i64;
+    let (cw, ch) = (canvas.width() as i64, canvas.height() as i64);
+    let (lw, lh) = (logo.width() as i64, logo.height() as i64);
+    let x0 = match settings.position {
+        WatermarkPosition::TopLeft | WatermarkPosition::BottomLeft => margin,
+        WatermarkPosition::TopRight | WatermarkPosition::BottomRight => cw - margin - lw,
+    };
+    let y0 = match settings.position {
+        WatermarkPosition::TopLeft | WatermarkPosition::TopRight => margin,
+        WatermarkPosition::BottomLeft | WatermarkPosition::BottomRight => ch - margin - lh,
+    };
+    let opacity = settings.opacity.clamp(0.0, 1.0);
+    for (x, y, src) in logo.enumerate_pixels() {
+        let (cx, cy) = (x0 + x as i64, y0 + y as i64);
+        if src[3] == 0 || cx < 0 || cy < 0 || cx >= cw || cy >= ch {
+            continue;
+        }
+        let alpha = (src[3] as f64 * opacity).round() as u8;
+        let dst = canvas.get_pixel(cx as u32, cy as u32);
+        let blended = blend_pixel(*dst, Rgba([src[0], src[1], src[2], alpha]));
+        canvas.put_pixel(cx as u32, cy as u32, blended);
+    }
 }
 
 /// Rounded corners with anti-aliasing.
@@ -811,6 +1792,16 @@ fn motion_blur_blend(current: &mut RgbaImage, prev: &RgbaImage, amount: f64) {
     }
 }
 
+/// Mix `prev` into `current` with weight `prev_weight` (0.0 – 1.0).
+fn crossfade(current: &mut RgbaImage, prev: &RgbaImage, prev_weight: f64) {
+    let w = prev_weight.clamp(0.0, 1.0);
+    for (c_pixel, p_pixel) in current.pixels_mut().zip(prev.pixels()) {
+        for c in 0..4 {
+            c_pixel[c] = (c_pixel[c] as f64 * (1.0 - w) + p_pixel[c] as f64 * w).round() as u8;
+        }
+    }
+}
+
 fn blend_pixel(dst: Rgba<u8>, src: Rgba<u8>) -> Rgba<u8> {
     let sa = src[3] as f64 / 255.0;
     let da = dst[3] as f64 / 255.0;
@@ -995,6 +1986,21 @@ mod tests {
         assert!(ease_out_cubic(0.5) > 0.5);
     }
 
+    #[test]
+    fn test_overlay_scale_follows_monitor_scale() {
+        let style = OutputStyle { output_width: 2880, output_height: 1800, ..OutputStyle::default() };
+        let mut compositor = Compositor::new(style, 2880, 1800);
+        assert_eq!(compositor.overlay_scale(), 1.0);
+        compositor.set_monitor_scale(1.5);
+        assert!((compositor.overlay_scale() - 1.5).abs() < 1e-9);
+
+        // Scaled down to 1920x1200: the physical size matches 100% again
+        let style = OutputStyle { output_width: 1920, output_height: 1200, ..OutputStyle::default() };
+        let mut compositor = Compositor::new(style, 2880, 1800);
+        compositor.set_monitor_scale(1.5);
+        assert!((compositor.overlay_scale() - 1.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_click_effect_eased_progress() {
         let effect = ClickEffect {
@@ -1002,6 +2008,7 @@ mod tests {
             y: 100.0,
             start_ms: 0,
             duration_ms: 400,
+            alt: false,
         };
 
         // At 50% time, eased progress should be > 0.5 (ease-out)
@@ -1060,4 +2067,163 @@ mod tests {
         let d = signed_distance_to_polygon(&triangle, 50.0, 50.0);
         assert!(d > 0.0, "Far away should be outside: {}", d);
     }
+
+    #[test]
+    fn test_watermark_corner_and_opacity() {
+        let settings = WatermarkSettings {
+            enabled: true,
+            image_path: None,
+            position: WatermarkPosition::BottomRight,
+            opacity: 0.5,
+            margin: 10.0,
+            width: 0.0,
+        };
+        let logo = RgbaImage::from_pixel(20, 10, Rgba([255, 255, 255, 255]));
+        let mut canvas = RgbaImage::from_pixel(100, 50, Rgba([0, 0, 0, 255]));
+        draw_watermark(&mut canvas, &logo, &settings);
+        // Logo covers x 70..90, y 30..40
+        assert_eq!(canvas.get_pixel(69, 35)[0], 0);
+        assert_eq!(canvas.get_pixel(89, 39)[0], 128);
+        assert_eq!(canvas.get_pixel(90, 39)[0], 0);
+
+        let top_left = WatermarkSettings { position: WatermarkPosition::TopLeft, opacity: 1.0, ..settings };
+        let mut canvas = RgbaImage::from_pixel(100, 50, Rgba([0, 0, 0, 255]));
+        draw_watermark(&mut canvas, &logo, &top_left);
+        assert_eq!(canvas.get_pixel(10, 10)[0], 255);
+        assert_eq!(canvas.get_pixel(9, 10)[0], 0);
+    }
+
+    #[test]
+    fn test_disclaimer_ribbon_band_position() {
+        let disclaimer = DisclaimerSettings {
+            enabled: true,
+            position: RibbonPosition::Bottom,
+            background_color: [0, 0, 0, 255],
+            ..Default::default()
+        };
+        let mut img = RgbaImage::from_pixel(200, 100, Rgba([255, 255, 255, 255]));
+        draw_disclaimer_ribbon(&mut img, &disclaimer, None);
+        assert_eq!(img.get_pixel(100, 99)[0], 0);
+        assert_eq!(img.get_pixel(100, 0)[0], 255);
+
+        let top = DisclaimerSettings { position: RibbonPosition::Top, ..disclaimer };
+        let mut img = RgbaImage::from_pixel(200, 100, Rgba([255, 255, 255, 255]));
+        draw_disclaimer_ribbon(&mut img, &top, None);
+        assert_eq!(img.get_pixel(100, 0)[0], 0);
+        assert_eq!(img.get_pixel(100, 99)[0], 255);
+    }
+
+    #[test]
+    fn test_progress_bar_fills_to_position() {
+        let bar = ProgressBarSettings {
+            enabled: true,
+            height: 4,
+            color: [255, 0, 0, 255],
+            track_color: [0, 0, 0, 0],
+        };
+        let mut img = RgbaImage::from_pixel(200, 100, Rgba([255, 255, 255, 255]));
+        draw_progress_bar(&mut img, &bar, 0.25);
+        // Filled up to x = 50 along the bottom 4 rows
+        assert_eq!(img.get_pixel(49, 99).0, [255, 0, 0, 255]);
+        assert_eq!(img.get_pixel(49, 96).0, [255, 0, 0, 255]);
+        assert_eq!(img.get_pixel(49, 95).0, [255, 255, 255, 255]);
+        // The track is transparent: the rest is untouched
+        assert_eq!(img.get_pixel(50, 99).0, [255, 255, 255, 255]);
+
+        // Clamped past the end (clock drift can run output time over)
+        draw_progress_bar(&mut img, &bar, 1.2);
+        assert_eq!(img.get_pixel(199, 99).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_key_badge_position_and_text() {
+        let style = KeyBadgeStyle {
+            position: BadgePosition::TopCenter,
+            background_color: [0, 0, 0, 255],
+            text_color: [255, 0, 0, 255],
+            ..Default::default()
+        };
+        let font = load_system_font();
+        let mut img = RgbaImage::from_pixel(400, 200, Rgba([255, 255, 255, 255]));
+        draw_key_badge(&mut img, "Ctrl+C", &style, font.as_ref());
+
+        // Badge sits at the top, the bottom half is untouched
+        assert_eq!(img.get_pixel(200, KEY_BADGE_MARGIN as u32 + 2)[1], 0);
+        assert!((100..200).all(|y| img.get_pixel(200, y)[1] == 255));
+
+        if font.is_some() {
+            let has_text = img.pixels().any(|p| p[0] > 200 && p[1] < 50 && p[2] < 50);
+            assert!(has_text, "key text should be rendered in the text color");
+        }
+    }
+
+    #[test]
+    fn test_reduced_motion_cuts_and_skips_small_reframing() {
+        use crate::engine::zoom_planner::TransitionType;
+        let kf = |x: f64, zoom_level: f64| ZoomKeyframe {
+            time_ms: 0,
+            target_x: x,
+            target_y: 540.0,
+            zoom_level,
+            transition: TransitionType::Smooth,
+            spring_hint: None,
+            pinned: false,
+        };
+        let mut compositor = Compositor::new(OutputStyle::default(), 1920, 1080);
+        compositor.set_reduced_motion(true);
+        compositor.last_canvas = Some(RgbaImage::new(4, 4));
+
+        compositor.apply_keyframe(&kf(400.0, 2.0));
+        assert_eq!(compositor.viewport.zoom.position, 2.0, "jumps without animating");
+        assert_eq!(compositor.viewport.center_x.position, 400.0);
+        assert!(compositor.fade_from.is_some());
+
+        compositor.apply_keyframe(&kf(420.0, 2.05));
+        assert_eq!(compositor.viewport.center_x.position, 400.0, "small re-framing is skipped");
+    }
+
+    #[test]
+    fn test_spotlight_dims_outside_radius() {
+        let mut img = RgbaImage::from_pixel(400, 200, Rgba([200, 200, 200, 255]));
+        draw_spotlight(&mut img, 100.0, 100.0, 40.0, 0.5);
+        assert_eq!(img.get_pixel(100, 100)[0], 200, "center stays bright");
+        assert_eq!(img.get_pixel(390, 100)[0], 100, "far pixels are dimmed");
+        let edge = img.get_pixel(148, 100)[0];
+        assert!(edge > 100 && edge < 200, "soft edge, got {}", edge);
+    }
+
+    #[test]
+    fn test_spotlight_radius_pulses_after_click() {
+        let spotlight = SpotlightSettings { enabled: true, radius: 100.0, dim_opacity: 0.5, click_pulse: 0.5 };
+        let mut compositor = Compositor::new(OutputStyle::default(), 1920, 1080);
+        compositor.set_spotlight_clicks(vec![1000]);
+        assert_eq!(compositor.spotlight_radius(&spotlight, 900), 100.0);
+        assert!((compositor.spotlight_radius(&spotlight, 1200) - 150.0).abs() < 0.01);
+        assert_eq!(compositor.spotlight_radius(&spotlight, 1400), 100.0);
+    }
+
+    #[test]
+    fn test_annotation_arrow_and_box_strokes() {
+        let mut img = RgbaImage::from_pixel(200, 200, Rgba([0, 0, 0, 255]));
+        let style = AnnotationStyle { color: [255, 0, 0, 255], stroke_width: 4.0, font_size: 24.0 };
+        let arrow = AnnotationShape::Arrow { from: [20.0, 100.0], to: [180.0, 100.0] };
+        draw_annotation(&mut img, &arrow, None, &style, 1.0, None);
+        assert_eq!(img.get_pixel(60, 100)[0], 255, "shaft");
+        assert_eq!(img.get_pixel(168, 104)[0], 255, "head is wider than the shaft");
+        assert_eq!(img.get_pixel(60, 104)[0], 0);
+
+        let boxed = AnnotationShape::Box { rect: [40.0, 20.0, 120.0, 60.0] };
+        draw_annotation(&mut img, &boxed, None, &style, 0.5, None);
+        let edge = img.get_pixel(80, 20)[0];
+        assert!(edge > 100 && edge < 160, "half-opaque outline, got {}", edge);
+        assert_eq!(img.get_pixel(80, 40)[0], 0, "inside stays untouched");
+    }
+
+    #[test]
+    fn test_crossfade_weights() {
+        let mut current = RgbaImage::from_pixel(1, 1, Rgba([200, 0, 0, 255]));
+        let prev = RgbaImage::from_pixel(1, 1, Rgba([0, 100, 0, 255]));
+        crossfade(&mut current, &prev, 0.25);
+        assert_eq!(current.get_pixel(0, 0).0, [150, 25, 0, 255]);
+    }
 }
@@ -1,10 +1,23 @@
-use crate::config::BackgroundConfig;
+use crate::config::{BackgroundConfig, ImageFit};
+use image::imageops::FilterType;
 use image::{Rgba, RgbaImage};
 
+/// Fill for areas an image background does not cover, and for image /
+/// blurred backgrounds whose source is unavailable
+const FALLBACK_COLOR: Rgba<u8> = Rgba([24, 24, 27, 255]);
+
+/// The blurred background is blurred at 1/N resolution and scaled back up,
+/// which is much cheaper than a wide blur at full size
+const BLUR_DOWNSCALE: u32 = 8;
+const BLUR_SIGMA: f32 = 5.0;
+
+/// `first_frame` is the recording's first (redacted) frame, used by
+/// [`BackgroundConfig::Blurred`].
 pub fn create_background_image(
     width: u32,
     height: u32,
     config: &BackgroundConfig,
+    first_frame: Option<&RgbaImage>,
 ) -> RgbaImage {
     match config {
         BackgroundConfig::Gradient { from, to, angle } => {
@@ -16,9 +29,52 @@ pub fn create_background_image(
         BackgroundConfig::Transparent => {
             RgbaImage::from_pixel(width, height, Rgba([0, 0, 0, 0]))
         }
+        BackgroundConfig::Image { path, fit } => match image::open(path) {
+            Ok(img) => fit_image(&img.to_rgba8(), width, height, *fit),
+            Err(e) => {
+                log::warn!("Failed to load background image '{}': {}", path, e);
+                RgbaImage::from_pixel(width, height, FALLBACK_COLOR)
+            }
+        },
+        BackgroundConfig::Blurred => match first_frame {
+            Some(frame) => create_blurred(frame, width, height),
+            None => RgbaImage::from_pixel(width, height, FALLBACK_COLOR),
+        },
     }
 }
 
+/// Scale `img` onto a `width` x `height` canvas according to `fit`.
+fn fit_image(img: &RgbaImage, width: u32, height: u32, fit: ImageFit) -> RgbaImage {
+    let (iw, ih) = (img.width().max(1) as f64, img.height().max(1) as f64);
+    let scale_x = width as f64 / iw;
+    let scale_y = height as f64 / ih;
+    let scale = match fit {
+        ImageFit::Stretch => {
+            return image::imageops::resize(img, width, height, FilterType::Triangle);
+        }
+        ImageFit::Cover => scale_x.max(scale_y),
+        ImageFit::Contain => scale_x.min(scale_y),
+    };
+    let sw = ((iw * scale).round() as u32).max(1);
+    let sh = ((ih * scale).round() as u32).max(1);
+    let scaled = image::imageops::resize(img, sw, sh, FilterType::Triangle);
+
+    let mut canvas = RgbaImage::from_pixel(width, height, FALLBACK_COLOR);
+    let x = (width as i64 - sw as i64) / 2;
+    let y = (height as i64 - sh as i64) / 2;
+    image::imageops::overlay(&mut canvas, &scaled, x, y);
+    canvas
+}
+
+/// Heavily blurred copy of `frame` covering the canvas.
+fn create_blurred(frame: &RgbaImage, width: u32, height: u32) -> RgbaImage {
+    let small_w = (width / BLUR_DOWNSCALE).max(1);
+    let small_h = (height / BLUR_DOWNSCALE).max(1);
+    let small = fit_image(frame, small_w, small_h, ImageFit::Cover);
+    let blurred = image::imageops::blur(&small, BLUR_SIGMA);
+    image::imageops::resize(&blurred, width, height, FilterType::Triangle)
+}
+
 fn create_gradient(
     width: u32,
     height: u32,
@@ -49,3 +105,34 @@ fn create_gradient(
 
     img
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_fit_modes() {
+        // 4x2 image: left half red, right half blue
+        let img = RgbaImage::from_fn(4, 2, |x, _| {
+            if x < 2 { Rgba([255, 0, 0, 255]) } else { Rgba([0, 0, 255, 255]) }
+        });
+        let contain = fit_image(&img, 8, 8, ImageFit::Contain);
+        assert_eq!(*contain.get_pixel(0, 0), FALLBACK_COLOR);
+        assert_eq!(contain.get_pixel(0, 4)[0], 255);
+
+        let cover = fit_image(&img, 8, 8, ImageFit::Cover);
+        assert_ne!(*cover.get_pixel(0, 0), FALLBACK_COLOR);
+        assert_eq!(cover.get_pixel(4, 0)[3], 255);
+    }
+
+    #[test]
+    fn test_blurred_without_frame_falls_back() {
+        let bg = create_background_image(16, 8, &BackgroundConfig::Blurred, None);
+        assert_eq!(*bg.get_pixel(3, 3), FALLBACK_COLOR);
+
+        let frame = RgbaImage::from_pixel(32, 16, Rgba([200, 100, 50, 255]));
+        let bg = create_background_image(64, 32, &BackgroundConfig::Blurred, Some(&frame));
+        assert_eq!(bg.dimensions(), (64, 32));
+        assert_eq!(*bg.get_pixel(32, 16), Rgba([200, 100, 50, 255]));
+    }
+}
@@ -2,4 +2,11 @@ pub mod background;
 pub mod click_ring;
 pub mod cursor;
 pub mod key_badge;
+#[cfg(feature = "effect-plugins")]
+pub mod plugin;
+pub mod text;
+pub mod title_card;
+#[cfg(feature = "effect-plugins")]
+pub mod vignette;
 pub mod viewport;
+pub mod window_chrome;
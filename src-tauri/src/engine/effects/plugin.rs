@@ -0,0 +1,88 @@
+//! Effect plugin interface (`effect-plugins` feature).
+//!
+//! Niche effects implement [`EffectPlugin`] and register a factory with
+//! [`inventory::submit!`], from this crate or any crate linked into the app:
+//!
+//! ```ignore
+//! inventory::submit! {
+//!     PluginRegistration { name: "my-effect", create: || Box::new(MyEffect::default()) }
+//! }
+//! ```
+//!
+//! Registration happens at link time; which plugins actually run is chosen
+//! per user in `EffectsSettings::plugins`.
+
+use crate::config::RecordingEvent;
+use image::RgbaImage;
+
+/// Per-frame data handed to plugin hooks.
+pub struct EffectContext<'a> {
+    /// Frame time on the output timeline (after cuts / speed ranges)
+    pub time_ms: u64,
+    /// All recording events. Their timestamps are on the recording timeline,
+    /// which differs from `time_ms` when the export has cuts or speed ranges.
+    pub events: &'a [RecordingEvent],
+    pub screen_width: u32,
+    pub screen_height: u32,
+}
+
+pub trait EffectPlugin: Send {
+    fn name(&self) -> &'static str;
+
+    /// Runs on the raw screen frame before crop/zoom, cursor and overlays.
+    fn pre_compose(&mut self, frame: &mut RgbaImage, ctx: &EffectContext) {
+        let _ = (frame, ctx);
+    }
+
+    /// Runs on the final canvas (background, shadow and overlays included).
+    fn post_compose(&mut self, canvas: &mut RgbaImage, ctx: &EffectContext) {
+        let _ = (canvas, ctx);
+    }
+}
+
+/// A plugin factory collected at link time.
+pub struct PluginRegistration {
+    pub name: &'static str,
+    pub create: fn() -> Box<dyn EffectPlugin>,
+}
+
+inventory::collect!(PluginRegistration);
+
+/// Names of all plugins compiled into this build.
+pub fn registered_plugin_names() -> Vec<&'static str> {
+    inventory::iter::<PluginRegistration>.into_iter().map(|r| r.name).collect()
+}
+
+/// Instantiate the registered plugins listed in `enabled`, in that order.
+/// Unknown names are logged and skipped.
+pub fn instantiate_plugins(enabled: &[String]) -> Vec<Box<dyn EffectPlugin>> {
+    enabled
+        .iter()
+        .filter_map(|name| {
+            let registration = inventory::iter::<PluginRegistration>
+                .into_iter()
+                .find(|r| r.name == name);
+            if registration.is_none() {
+                log::warn!("Effect plugin '{}' is not registered in this build", name);
+            }
+            registration.map(|r| (r.create)())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_plugin_is_registered() {
+        assert!(registered_plugin_names().contains(&"vignette"));
+    }
+
+    #[test]
+    fn test_instantiate_skips_unknown_names() {
+        let plugins = instantiate_plugins(&["missing".to_string(), "vignette".to_string()]);
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].name(), "vignette");
+    }
+}
@@ -0,0 +1,103 @@
+//! Text rasterization for overlays (disclaimer ribbon, badges).
+//!
+//! No font is bundled: a system font is looked up once and shared. When none
+//! is found, text overlays are skipped and only their backgrounds are drawn.
+
+use ab_glyph::{point, Font, FontArc, PxScale, ScaleFont};
+use image::RgbaImage;
+
+/// Candidate font files, in preference order. CJK-capable fonts come first
+/// so Japanese overlay text renders.
+const FONT_CANDIDATES: &[&str] = &[
+    "C:\\Windows\\Fonts\\YuGothM.ttc",
+    "C:\\Windows\\Fonts\\meiryo.ttc",
+    "C:\\Windows\\Fonts\\msgothic.ttc",
+    "C:\\Windows\\Fonts\\segoeui.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/System/Library/Fonts/Helvetica.ttc",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+];
+
+/// Load the first usable system font (index 0 of collections).
+pub fn load_system_font() -> Option<FontArc> {
+    for path in FONT_CANDIDATES {
+        let Ok(data) = std::fs::read(path) else {
+            continue;
+        };
+        match FontArc::try_from_vec(data) {
+            Ok(font) => {
+                log::info!("Overlay font loaded from '{}'", path);
+                return Some(font);
+            }
+            Err(e) => log::warn!("Failed to parse font '{}': {}", path, e),
+        }
+    }
+    log::warn!("No system font found, overlay text will not be drawn");
+    None
+}
+
+/// Width and height (px) of a single line of `text` at `size` px.
+pub fn measure_text(font: &FontArc, text: &str, size: f32) -> (u32, u32) {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut width = 0.0f32;
+    let mut prev = None;
+    for ch in text.chars() {
+        let id = scaled.glyph_id(ch);
+        if let Some(p) = prev {
+            width += scaled.kern(p, id);
+        }
+        width += scaled.h_advance(id);
+        prev = Some(id);
+    }
+    (width.ceil() as u32, scaled.height().ceil() as u32)
+}
+
+/// Draw a single line of `text` with its top-left corner at (x, y).
+/// Pixels outside the image are clipped.
+pub fn draw_text(
+    img: &mut RgbaImage,
+    font: &FontArc,
+    text: &str,
+    x: i32,
+    y: i32,
+    size: f32,
+    color: [u8; 4],
+) {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let baseline = y as f32 + scaled.ascent();
+    let mut caret = x as f32;
+    let mut prev = None;
+
+    for ch in text.chars() {
+        let id = scaled.glyph_id(ch);
+        if let Some(p) = prev {
+            caret += scaled.kern(p, id);
+        }
+        let glyph = id.with_scale_and_position(PxScale::from(size), point(caret, baseline));
+        caret += scaled.h_advance(id);
+        prev = Some(id);
+
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i32 + gx as i32;
+            let py = bounds.min.y as i32 + gy as i32;
+            if px < 0 || py < 0 || px >= img.width() as i32 || py >= img.height() as i32 {
+                return;
+            }
+            let a = coverage.clamp(0.0, 1.0) * color[3] as f32 / 255.0;
+            if a <= 0.0 {
+                return;
+            }
+            let dst = img.get_pixel_mut(px as u32, py as u32);
+            for c in 0..3 {
+                dst[c] = (color[c] as f32 * a + dst[c] as f32 * (1.0 - a)) as u8;
+            }
+            dst[3] = (255.0 * a + dst[3] as f32 * (1.0 - a)) as u8;
+        });
+    }
+}
@@ -0,0 +1,107 @@
+//! Intro / outro cards shown before and after the recording.
+//!
+//! A card is either a user image, scaled to fit inside the canvas, or a
+//! centered title (and optional subtitle) over a background. Without its own
+//! background a card uses the export's canvas background, so it matches the
+//! frame around the recording.
+
+use super::background::create_background_image;
+use super::text::{draw_text, measure_text};
+use crate::config::TitleCardSettings;
+use ab_glyph::FontArc;
+use image::RgbaImage;
+
+/// Subtitle size relative to the title
+const SUBTITLE_SCALE: f64 = 0.5;
+
+/// Gap between title and subtitle, relative to the title size
+const LINE_GAP: f64 = 0.4;
+
+/// `canvas_background` is the export's background image; its size is the
+/// card's size.
+pub fn render_title_card(card: &TitleCardSettings, canvas_background: &RgbaImage, font: Option<&FontArc>) -> RgbaImage {
+    let (width, height) = canvas_background.dimensions();
+    let mut canvas = match card.background {
+        Some(ref background) => create_background_image(width, height, background, None),
+        None => canvas_background.clone(),
+    };
+
+    if let Some(path) = card.image_path.as_deref().filter(|p| !p.is_empty()) {
+        match image::open(path) {
+            Ok(img) => {
+                draw_fitted_image(&mut canvas, &img.to_rgba8());
+                return canvas;
+            }
+            Err(e) => log::warn!("Failed to load title card image '{}': {}", path, e),
+        }
+    }
+
+    let Some(font) = font else {
+        return canvas;
+    };
+    let title_size = card.font_size.max(8.0);
+    let subtitle_size = title_size * SUBTITLE_SCALE;
+    let lines: Vec<(&str, f32)> = [(card.title.trim(), title_size), (card.subtitle.trim(), subtitle_size)]
+        .into_iter()
+        .filter(|(text, _)| !text.is_empty())
+        .map(|(text, size)| (text, size as f32))
+        .collect();
+    let sizes: Vec<(u32, u32)> = lines.iter().map(|(text, size)| measure_text(font, text, *size)).collect();
+    let gap = (title_size * LINE_GAP) as i32;
+    let block_height: i32 =
+        sizes.iter().map(|(_, h)| *h as i32).sum::<i32>() + gap * (lines.len() as i32 - 1).max(0);
+
+    let mut y = (height as i32 - block_height) / 2;
+    for ((text, size), (w, h)) in lines.iter().zip(&sizes) {
+        let x = (width as i32 - *w as i32) / 2;
+        draw_text(&mut canvas, font, text, x.max(0), y, *size, card.text_color);
+        y += *h as i32 + gap;
+    }
+    canvas
+}
+
+/// Scale `img` to fit inside `canvas` (never upscaling) and blend it centered.
+fn draw_fitted_image(canvas: &mut RgbaImage, img: &RgbaImage) {
+    let (cw, ch) = canvas.dimensions();
+    let scale = (cw as f64 / img.width().max(1) as f64)
+        .min(ch as f64 / img.height().max(1) as f64)
+        .min(1.0);
+    let w = ((img.width() as f64 * scale).round() as u32).max(1);
+    let h = ((img.height() as f64 * scale).round() as u32).max(1);
+    let fitted = if (w, h) == img.dimensions() {
+        img.clone()
+    } else {
+        image::imageops::resize(img, w, h, image::imageops::FilterType::Lanczos3)
+    };
+    image::imageops::overlay(canvas, &fitted, ((cw - w) / 2) as i64, ((ch - h) / 2) as i64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BackgroundConfig;
+    use image::Rgba;
+
+    #[test]
+    fn test_own_background_and_fitted_image() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("logo.png");
+        RgbaImage::from_pixel(400, 100, Rgba([255, 0, 0, 255])).save(&path).unwrap();
+
+        let card = TitleCardSettings {
+            enabled: true,
+            image_path: Some(path.to_string_lossy().to_string()),
+            background: Some(BackgroundConfig::Solid { color: [0, 0, 255] }),
+            ..Default::default()
+        };
+        let export_bg = RgbaImage::from_pixel(200, 100, Rgba([0, 255, 0, 255]));
+        let canvas = render_title_card(&card, &export_bg, None);
+        // 400x100 fits as 200x50, centered vertically
+        assert_eq!(*canvas.get_pixel(100, 50), Rgba([255, 0, 0, 255]));
+        assert_eq!(*canvas.get_pixel(100, 10), Rgba([0, 0, 255, 255]));
+
+        let missing = TitleCardSettings { image_path: Some("/nonexistent.png".into()), background: None, ..card };
+        let canvas = render_title_card(&missing, &export_bg, None);
+        assert_eq!(*canvas.get_pixel(100, 50), Rgba([0, 255, 0, 255]));
+    }
+}
@@ -0,0 +1,55 @@
+//! Vignette: darkens the canvas edges. Reference [`EffectPlugin`]
+//! implementation, enabled with `"vignette"` in `EffectsSettings::plugins`.
+
+use super::plugin::{EffectContext, EffectPlugin, PluginRegistration};
+use image::RgbaImage;
+
+/// Darkening at the corners (0.0 – 1.0)
+const VIGNETTE_STRENGTH: f64 = 0.35;
+
+#[derive(Default)]
+pub struct Vignette {
+    /// Per-pixel darkening factors, cached for the canvas size
+    mask: Option<(u32, u32, Vec<f32>)>,
+}
+
+impl Vignette {
+    fn mask(&mut self, width: u32, height: u32) -> &[f32] {
+        let stale = !matches!(self.mask, Some((w, h, _)) if w == width && h == height);
+        if stale {
+            let (cx, cy) = (width as f64 / 2.0, height as f64 / 2.0);
+            let max_dist = (cx * cx + cy * cy).sqrt();
+            let mut factors = Vec::with_capacity((width * height) as usize);
+            for y in 0..height {
+                for x in 0..width {
+                    let dx = x as f64 - cx;
+                    let dy = y as f64 - cy;
+                    let d = (dx * dx + dy * dy).sqrt() / max_dist;
+                    factors.push((1.0 - VIGNETTE_STRENGTH * d * d) as f32);
+                }
+            }
+            self.mask = Some((width, height, factors));
+        }
+        &self.mask.as_ref().unwrap().2
+    }
+}
+
+impl EffectPlugin for Vignette {
+    fn name(&self) -> &'static str {
+        "vignette"
+    }
+
+    fn post_compose(&mut self, canvas: &mut RgbaImage, _ctx: &EffectContext) {
+        let (width, height) = canvas.dimensions();
+        let mask = self.mask(width, height);
+        for (pixel, factor) in canvas.pixels_mut().zip(mask) {
+            for c in 0..3 {
+                pixel[c] = (pixel[c] as f32 * factor) as u8;
+            }
+        }
+    }
+}
+
+inventory::submit! {
+    PluginRegistration { name: "vignette", create: || Box::new(Vignette::default()) }
+}
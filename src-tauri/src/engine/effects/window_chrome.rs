@@ -0,0 +1,136 @@
+//! Mock browser window frame drawn around the recording.
+//!
+//! The title bar is added above the output frame (taking its room from the
+//! canvas padding), so the recording itself keeps its size and zoom. Rounded
+//! corners and the drop shadow are applied to the whole window afterwards.
+
+use super::text::{draw_text, measure_text};
+use crate::config::{ChromeTheme, WindowChromeSettings};
+use ab_glyph::FontArc;
+use image::{Rgba, RgbaImage};
+
+/// Traffic-light button colors (close, minimize, zoom)
+const BUTTON_COLORS: [[u8; 4]; 3] = [[255, 95, 87, 255], [254, 188, 46, 255], [40, 200, 64, 255]];
+
+/// Button radius and spacing, relative to the title bar height
+const BUTTON_RADIUS: f64 = 0.15;
+const BUTTON_SPACING: f64 = 0.5;
+
+/// Address bar height and text size, relative to the title bar height
+const URL_BAR_HEIGHT: f64 = 0.6;
+const URL_TEXT_SIZE: f64 = 0.35;
+
+struct ThemeColors {
+    bar: [u8; 4],
+    url_bar: [u8; 4],
+    url_text: [u8; 4],
+}
+
+fn theme_colors(theme: ChromeTheme) -> ThemeColors {
+    match theme {
+        ChromeTheme::Light => ThemeColors {
+            bar: [236, 236, 236, 255],
+            url_bar: [255, 255, 255, 255],
+            url_text: [90, 90, 90, 255],
+        },
+        ChromeTheme::Dark => ThemeColors {
+            bar: [45, 45, 48, 255],
+            url_bar: [28, 28, 30, 255],
+            url_text: [200, 200, 200, 255],
+        },
+    }
+}
+
+/// `frame` with a title bar of `chrome.title_bar_height` px on top.
+pub fn wrap_in_window_chrome(frame: &RgbaImage, chrome: &WindowChromeSettings, font: Option<&FontArc>) -> RgbaImage {
+    let bar_h = chrome.title_bar_height;
+    let (w, h) = frame.dimensions();
+    let colors = theme_colors(chrome.theme);
+
+    let mut window = RgbaImage::from_pixel(w, h + bar_h, Rgba(colors.bar));
+    image::imageops::replace(&mut window, frame, 0, bar_h as i64);
+
+    let bar = bar_h as f64;
+    let cy = bar / 2.0;
+    let mut content_left = bar * BUTTON_SPACING;
+    if chrome.show_buttons {
+        let r = bar * BUTTON_RADIUS;
+        for (i, color) in BUTTON_COLORS.iter().enumerate() {
+            let cx = bar * BUTTON_SPACING * (i as f64 + 1.0);
+            fill_rounded_rect(&mut window, cx - r, cy - r, r * 2.0, r * 2.0, r, *color);
+        }
+        content_left = bar * BUTTON_SPACING * 4.0;
+    }
+
+    let url = chrome.url.trim();
+    if !url.is_empty() {
+        // Centered on the window, but never over the buttons
+        let url_w = (w as f64 * 0.6).min(w as f64 - content_left * 2.0);
+        if url_w > 0.0 {
+            let url_h = bar * URL_BAR_HEIGHT;
+            let url_x = (w as f64 - url_w) / 2.0;
+            let url_y = cy - url_h / 2.0;
+            fill_rounded_rect(&mut window, url_x, url_y, url_w, url_h, url_h / 2.0, colors.url_bar);
+
+            if let Some(font) = font {
+                let size = (bar * URL_TEXT_SIZE) as f32;
+                let (text_w, text_h) = measure_text(font, url, size);
+                let x = url_x + ((url_w - text_w as f64) / 2.0).max(url_h / 2.0);
+                let y = cy - text_h as f64 / 2.0;
+                draw_text(&mut window, font, url, x as i32, y as i32, size, colors.url_text);
+            }
+        }
+    }
+    window
+}
+
+/// Anti-aliased rounded rectangle (a circle when `r` is half the size).
+fn fill_rounded_rect(img: &mut RgbaImage, x: f64, y: f64, w: f64, h: f64, r: f64, color: [u8; 4]) {
+    let r = r.min(w / 2.0).min(h / 2.0);
+    let x0 = x.floor().max(0.0) as u32;
+    let y0 = y.floor().max(0.0) as u32;
+    let x1 = ((x + w).ceil() as u32).min(img.width());
+    let y1 = ((y + h).ceil() as u32).min(img.height());
+    for py in y0..y1 {
+        for px in x0..x1 {
+            // Distance outside the rounded rect, negative inside
+            let qx = ((px as f64 + 0.5 - (x + w / 2.0)).abs() - (w / 2.0 - r)).max(0.0);
+            let qy = ((py as f64 + 0.5 - (y + h / 2.0)).abs() - (h / 2.0 - r)).max(0.0);
+            let dist = (qx * qx + qy * qy).sqrt() - r;
+            let coverage = (0.5 - dist).clamp(0.0, 1.0);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let a = coverage * color[3] as f64 / 255.0;
+            let dst = img.get_pixel_mut(px, py);
+            for c in 0..3 {
+                dst[c] = (color[c] as f64 * a + dst[c] as f64 * (1.0 - a)).round() as u8;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_bar_added_above_frame() {
+        let frame = RgbaImage::from_pixel(400, 200, Rgba([0, 0, 255, 255]));
+        let chrome = WindowChromeSettings {
+            enabled: true,
+            theme: ChromeTheme::Dark,
+            title_bar_height: 40,
+            show_buttons: true,
+            url: "example.com".into(),
+        };
+        let window = wrap_in_window_chrome(&frame, &chrome, None);
+        assert_eq!(window.dimensions(), (400, 240));
+        // Frame below the bar, bar color at the right end
+        assert_eq!(*window.get_pixel(10, 239), Rgba([0, 0, 255, 255]));
+        assert_eq!(*window.get_pixel(395, 5), Rgba([45, 45, 48, 255]));
+        // Close button centered at (20, 20), address bar in the middle
+        assert_eq!(*window.get_pixel(20, 20), Rgba(BUTTON_COLORS[0]));
+        assert_eq!(*window.get_pixel(200, 20), Rgba([28, 28, 30, 255]));
+    }
+}
@@ -4,15 +4,35 @@
 //! visual changes occur. These change regions are used to expand scene BBoxes
 //! beyond just event coordinates, resulting in more appropriate zoom levels.
 //!
-//! Uses rayon for parallel frame loading and comparison.
+//! Uses rayon for parallel frame loading and comparison. Each worker takes a
+//! run of consecutive pairs so every sampled frame is decoded once, and the
+//! pixel comparison runs over fixed-width lanes that the compiler turns into
+//! SIMD instructions; lanes without any change are skipped after one vector
+//! compare, so mostly static screens cost little more than decoding.
 
+use crate::config::{EffectsSettings, RecordingMeta};
 use crate::engine::analyzer::Rect;
+use crate::export::frame_index::FrameIndex;
+use crate::recording::frame_store::FrameSource;
 use anyhow::Result;
-use image::GrayImage;
+use image::{DynamicImage, GrayImage};
 use rayon::prelude::*;
 use std::path::Path;
 
+/// Consecutive sample pairs handled by one rayon task (frames shared by
+/// neighbouring pairs are decoded once per task).
+const PAIRS_PER_TASK: usize = 8;
+
+/// Pixels compared per vector step in [`compute_pair_diff`].
+const DIFF_LANES: usize = 32;
+
+/// Change regions computed while recording (see
+/// [`crate::recording::change_tracker`]): a `DiffConfig` line followed by
+/// one [`RecordedPair`] per compared frame pair.
+pub const CHANGES_FILE: &str = "changes.jsonl";
+
 /// Configuration for frame differencing.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct DiffConfig {
     /// Sample every Nth frame for comparison.
     pub sample_interval: u64,
@@ -41,8 +61,28 @@ impl Default for DiffConfig {
     }
 }
 
+impl DiffConfig {
+    /// Sensitivity from the user's effects settings; sampling and
+    /// downsampling keep their defaults.
+    pub fn from_settings(effects: &EffectsSettings) -> Self {
+        Self {
+            pixel_threshold: effects.frame_diff_pixel_threshold,
+            min_region_size: effects.frame_diff_min_region_size,
+            max_change_fraction: effects.frame_diff_max_change_fraction.clamp(0.0, 1.0),
+            ..Self::default()
+        }
+    }
+
+    /// Whether results computed with `self` can stand in for `other`: same
+    /// sensitivity, sampled at least as densely.
+    pub fn covers(&self, other: &DiffConfig) -> bool {
+        self.sample_interval <= other.sample_interval
+            && DiffConfig { sample_interval: other.sample_interval, ..self.clone() } == *other
+    }
+}
+
 /// A detected change region between two frames.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ChangeRegion {
     /// Time of the change (midpoint of the two compared frames).
     pub time_ms: u64,
@@ -52,7 +92,55 @@ pub struct ChangeRegion {
     pub changed_pixel_count: u64,
 }
 
+/// Where the captured frames sit in event (screen) coordinates.
+///
+/// Window and area recordings store frames of the captured region only,
+/// while events keep screen coordinates: frame pixel = (screen - origin) * scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureMapping {
+    pub origin_x: f64,
+    pub origin_y: f64,
+    /// Frame pixels per screen unit (2.0 when frames are captured at 200% DPI)
+    pub scale: f64,
+}
+
+impl CaptureMapping {
+    /// Full-screen capture: frames and events share coordinates.
+    pub const IDENTITY: Self = Self { origin_x: 0.0, origin_y: 0.0, scale: 1.0 };
+
+    pub fn from_meta(meta: &RecordingMeta) -> Self {
+        let region_mode = matches!(meta.recording_mode.as_deref(), Some("window") | Some("area"));
+        match meta.window_initial_rect {
+            Some([left, top, right, _]) if region_mode => {
+                let width = right - left;
+                let scale = if width > 0.0 { meta.screen_width as f64 / width } else { 1.0 };
+                Self { origin_x: left, origin_y: top, scale }
+            }
+            _ => Self::IDENTITY,
+        }
+    }
+
+    pub fn screen_to_frame(&self, x: f64, y: f64) -> (f64, f64) {
+        ((x - self.origin_x) * self.scale, (y - self.origin_y) * self.scale)
+    }
+
+    pub fn frame_rect_to_screen(&self, rect: &Rect) -> Rect {
+        Rect {
+            x: rect.x / self.scale + self.origin_x,
+            y: rect.y / self.scale + self.origin_y,
+            width: rect.width / self.scale,
+            height: rect.height / self.scale,
+        }
+    }
+
+    /// The captured area in screen coordinates, for frames of `frame_w` x `frame_h`.
+    pub fn screen_bounds(&self, frame_w: f64, frame_h: f64) -> Rect {
+        self.frame_rect_to_screen(&Rect { x: 0.0, y: 0.0, width: frame_w, height: frame_h })
+    }
+}
+
 /// Result of frame differencing analysis.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DiffResult {
     /// Detected change regions.
     pub regions: Vec<ChangeRegion>,
@@ -63,15 +151,22 @@ pub struct DiffResult {
 }
 
 /// Detect visual change regions across frames using parallel processing.
+///
+/// `cursor_positions` are in event coordinates and `frame_w`/`frame_h` are
+/// the stored frame size; returned regions are mapped back to event
+/// coordinates through `mapping` so they line up with scene bboxes. Region
+/// times are the frames' capture times from `frame_index`, so they stay on
+/// the events clock when frames were dropped.
 pub fn detect_frame_changes(
     frames_dir: &Path,
-    frame_count: u64,
-    duration_ms: u64,
+    frame_index: &FrameIndex,
     cursor_positions: &[(u64, f64, f64)],
-    screen_w: u32,
-    screen_h: u32,
+    frame_w: u32,
+    frame_h: u32,
+    mapping: &CaptureMapping,
     config: &DiffConfig,
 ) -> Result<DiffResult> {
+    let frame_count = frame_index.frame_count();
     if frame_count < 2 {
         return Ok(DiffResult {
             regions: Vec::new(),
@@ -80,12 +175,6 @@ pub fn detect_frame_changes(
         });
     }
 
-    let frame_time_step_ms = if frame_count > 1 && duration_ms > 0 {
-        duration_ms / frame_count
-    } else {
-        33
-    };
-
     // Generate sampling pairs
     let pairs: Vec<(u64, u64)> = (0..frame_count)
         .step_by(config.sample_interval as usize)
@@ -105,38 +194,52 @@ pub fn detect_frame_changes(
     }
 
     let ds = config.downsample_factor;
+    let source = FrameSource::open(frames_dir)?;
+    let load = |idx: u64| source.load(idx).ok().and_then(|img| downsample_gray(img, ds).ok());
+
+    // Cursor exclusion works on frame pixels
+    let frame_cursor: Vec<(u64, f64, f64)> = cursor_positions
+        .iter()
+        .map(|&(t, x, y)| {
+            let (fx, fy) = mapping.screen_to_frame(x, y);
+            (t, fx, fy)
+        })
+        .collect();
 
-    // Process pairs in parallel with rayon
+    // Process runs of consecutive pairs in parallel with rayon
     let results: Vec<Option<ChangeRegion>> = pairs
-        .par_iter()
-        .map(|(idx_a, idx_b)| {
-            // 新録画は .jpg、旧録画は .png で保存されるため両方をチェック
-            let path_a = crate::export::encoder::recording_frame_path(frames_dir, *idx_a);
-            let path_b = crate::export::encoder::recording_frame_path(frames_dir, *idx_b);
-
-            let img_a = load_downsampled_gray(&path_a, ds).ok()?;
-            let img_b = load_downsampled_gray(&path_b, ds).ok()?;
-
-            let time_a = idx_a * frame_time_step_ms;
-            let time_b = idx_b * frame_time_step_ms;
-            let cursor_a = find_cursor_nearest(cursor_positions, time_a);
-            let cursor_b = find_cursor_nearest(cursor_positions, time_b);
-
-            let (bbox, count) = compute_pair_diff(
-                &img_a,
-                &img_b,
-                cursor_a,
-                cursor_b,
-                config,
-                screen_w,
-                screen_h,
-            )?;
-
-            Some(ChangeRegion {
-                time_ms: (time_a + time_b) / 2,
-                bbox,
-                changed_pixel_count: count,
-            })
+        .par_chunks(PAIRS_PER_TASK)
+        .flat_map_iter(|run| {
+            let mut prev: Option<(u64, GrayImage)> = None;
+            let mut out = Vec::with_capacity(run.len());
+            for &(idx_a, idx_b) in run {
+                // FrameSource が .jpg / .png（旧録画）/ パック形式を解決する
+                let img_a = match prev.take() {
+                    Some((idx, img)) if idx == idx_a => Some(img),
+                    _ => load(idx_a),
+                };
+                let img_b = load(idx_b);
+
+                let region = match (&img_a, &img_b) {
+                    (Some(img_a), Some(img_b)) => {
+                        let time_a = frame_index.time_of(idx_a);
+                        let time_b = frame_index.time_of(idx_b);
+                        let cursor_a = find_cursor_nearest(&frame_cursor, time_a);
+                        let cursor_b = find_cursor_nearest(&frame_cursor, time_b);
+                        compute_pair_diff(img_a, img_b, cursor_a, cursor_b, config, frame_w, frame_h).map(
+                            |(bbox, count)| ChangeRegion {
+                                time_ms: (time_a + time_b) / 2,
+                                bbox: mapping.frame_rect_to_screen(&bbox),
+                                changed_pixel_count: count,
+                            },
+                        )
+                    }
+                    _ => None,
+                };
+                out.push(region);
+                prev = img_b.map(|img| (idx_b, img));
+            }
+            out
         })
         .collect();
 
@@ -151,12 +254,65 @@ pub fn detect_frame_changes(
     })
 }
 
-/// Expand a BBox by merging it with change region BBoxes.
+/// One compared frame pair in [`CHANGES_FILE`]. `bbox` is in frame pixels
+/// and absent when nothing changed or the pair was excluded.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RecordedPair {
+    pub from: u64,
+    pub to: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<Rect>,
+    #[serde(default)]
+    pub changed_pixel_count: u64,
+}
+
+/// The result [`detect_frame_changes`] would give, built from the pairs
+/// recorded in [`CHANGES_FILE`]. `None` when the file is missing, was
+/// written with a config that doesn't cover `config`, or doesn't reach the
+/// last sampled frame (e.g. after appending to the recording).
+pub fn load_recorded_changes(
+    recording_dir: &Path,
+    frame_index: &FrameIndex,
+    mapping: &CaptureMapping,
+    config: &DiffConfig,
+) -> Option<DiffResult> {
+    let content = std::fs::read_to_string(recording_dir.join(CHANGES_FILE)).ok()?;
+    let mut lines = content.lines();
+    let recorded: DiffConfig = serde_json::from_str(lines.next()?).ok()?;
+    if !recorded.covers(config) {
+        return None;
+    }
+    let pairs: Vec<RecordedPair> = lines.filter_map(|l| serde_json::from_str(l).ok()).collect();
+    let frame_count = frame_index.frame_count();
+    let last_sample = frame_count.saturating_sub(1) / recorded.sample_interval.max(1) * recorded.sample_interval;
+    let reaches_end = matches!(pairs.last(), Some(p) if p.to >= last_sample);
+    if frame_count >= 2 && !reaches_end {
+        return None;
+    }
+
+    let regions: Vec<ChangeRegion> = pairs
+        .iter()
+        .filter_map(|p| {
+            p.bbox.as_ref().map(|bbox| ChangeRegion {
+                time_ms: (frame_index.time_of(p.from) + frame_index.time_of(p.to)) / 2,
+                bbox: mapping.frame_rect_to_screen(bbox),
+                changed_pixel_count: p.changed_pixel_count,
+            })
+        })
+        .collect();
+    Some(DiffResult {
+        pairs_analyzed: pairs.len(),
+        pairs_excluded: pairs.len() - regions.len(),
+        regions,
+    })
+}
+
+/// Expand a BBox by merging it with change region BBoxes, clamped to
+/// `bounds` (the captured area in event coordinates).
 pub fn expand_bbox_with_changes(
     event_bbox: &Rect,
     regions: &[&ChangeRegion],
-    screen_w: f64,
-    screen_h: f64,
+    bounds: &Rect,
 ) -> Rect {
     if regions.is_empty() {
         return event_bbox.clone();
@@ -174,11 +330,11 @@ pub fn expand_bbox_with_changes(
         max_y = max_y.max(region.bbox.y + region.bbox.height);
     }
 
-    // Clamp to screen bounds
-    min_x = min_x.max(0.0);
-    min_y = min_y.max(0.0);
-    max_x = max_x.min(screen_w);
-    max_y = max_y.min(screen_h);
+    // Clamp to the captured area
+    min_x = min_x.max(bounds.x);
+    min_y = min_y.max(bounds.y);
+    max_x = max_x.min(bounds.x + bounds.width);
+    max_y = max_y.min(bounds.y + bounds.height);
 
     Rect {
         x: min_x,
@@ -190,24 +346,47 @@ pub fn expand_bbox_with_changes(
 
 // --- Internal functions ---
 
-/// Load a frame as downsampled grayscale.
-fn load_downsampled_gray(path: &Path, downsample_factor: u32) -> Result<GrayImage> {
-    let img = image::open(path)?;
+/// Load a frame as downsampled grayscale: the luma of every Nth pixel,
+/// computed straight from the decoded buffer (no full-size gray copy).
+pub(crate) fn load_downsampled_gray(path: &Path, downsample_factor: u32) -> Result<GrayImage> {
+    downsample_gray(image::open(path)?, downsample_factor)
+}
+
+pub(crate) fn downsample_gray(img: DynamicImage, downsample_factor: u32) -> Result<GrayImage> {
+    let ds = downsample_factor.max(1);
     let (w, h) = (img.width(), img.height());
-    let new_w = (w / downsample_factor).max(1);
-    let new_h = (h / downsample_factor).max(1);
-    let resized = image::imageops::resize(
-        &img.to_luma8(),
-        new_w,
-        new_h,
-        image::imageops::FilterType::Nearest,
-    );
-    Ok(resized)
+    let new_w = (w / ds).max(1);
+    let new_h = (h / ds).max(1);
+
+    let (raw, channels) = match img {
+        DynamicImage::ImageRgb8(rgb) => (rgb.into_raw(), 3),
+        DynamicImage::ImageRgba8(rgba) => (rgba.into_raw(), 4),
+        other => (other.to_rgb8().into_raw(), 3),
+    };
+    let row_bytes = w as usize * channels;
+    let mut out = Vec::with_capacity((new_w * new_h) as usize);
+    for y in 0..new_h {
+        let sy = (y * ds + ds / 2).min(h - 1) as usize;
+        let row = &raw[sy * row_bytes..(sy + 1) * row_bytes];
+        for x in 0..new_w {
+            let sx = (x * ds + ds / 2).min(w - 1) as usize * channels;
+            let (r, g, b) = (row[sx] as u32, row[sx + 1] as u32, row[sx + 2] as u32);
+            // Rec. 709 luma in 8-bit fixed point (54 + 183 + 19 = 256)
+            out.push(((54 * r + 183 * g + 19 * b) >> 8) as u8);
+        }
+    }
+    GrayImage::from_raw(new_w, new_h, out).ok_or_else(|| anyhow::anyhow!("Bad downsampled frame size"))
+}
+
+/// Largest absolute difference in a lane (vectorizes to a few SIMD ops).
+#[inline]
+fn lane_max_diff(a: &[u8], b: &[u8]) -> u8 {
+    a.iter().zip(b).fold(0u8, |m, (&pa, &pb)| m.max(pa.abs_diff(pb)))
 }
 
 /// Compute difference between two grayscale frames.
 /// Returns (bbox in original coords, changed_pixel_count) or None if filtered out.
-fn compute_pair_diff(
+pub(crate) fn compute_pair_diff(
     img_a: &GrayImage,
     img_b: &GrayImage,
     cursor_a: Option<(f64, f64)>,
@@ -227,6 +406,12 @@ fn compute_pair_diff(
     let ds = config.downsample_factor as f64;
     let cursor_radius_ds = config.cursor_exclude_radius as f64 / ds;
     let cursor_radius_sq = cursor_radius_ds * cursor_radius_ds;
+    let cursors: Vec<(f64, f64)> = [cursor_a, cursor_b]
+        .into_iter()
+        .flatten()
+        .map(|(cx, cy)| (cx / ds, cy / ds))
+        .collect();
+    let threshold = config.pixel_threshold;
 
     let total_pixels = (w * h) as u64;
     let mut changed_count: u64 = 0;
@@ -235,40 +420,38 @@ fn compute_pair_diff(
     let mut max_cx: u32 = 0;
     let mut max_cy: u32 = 0;
 
-    for y in 0..h {
-        for x in 0..w {
-            let pa = img_a.get_pixel(x, y).0[0];
-            let pb = img_b.get_pixel(x, y).0[0];
-            let diff = (pa as i16 - pb as i16).unsigned_abs() as u8;
-
-            if diff < config.pixel_threshold {
+    let rows_a = img_a.as_raw().chunks_exact(w as usize);
+    let rows_b = img_b.as_raw().chunks_exact(w as usize);
+    for (y, (row_a, row_b)) in rows_a.zip(rows_b).enumerate() {
+        let y = y as u32;
+        let lanes = row_a.chunks(DIFF_LANES).zip(row_b.chunks(DIFF_LANES));
+        for (lane, (lane_a, lane_b)) in lanes.enumerate() {
+            if lane_max_diff(lane_a, lane_b) < threshold {
                 continue;
             }
+            for (i, (&pa, &pb)) in lane_a.iter().zip(lane_b).enumerate() {
+                if pa.abs_diff(pb) < threshold {
+                    continue;
+                }
 
-            // Check cursor exclusion for both frames
-            let fx = x as f64;
-            let fy = y as f64;
-            let in_cursor = [cursor_a, cursor_b].iter().any(|cp| {
-                if let Some((cx, cy)) = cp {
-                    let dcx = cx / ds;
-                    let dcy = cy / ds;
-                    let dx = fx - dcx;
-                    let dy = fy - dcy;
+                // Check cursor exclusion for both frames
+                let x = (lane * DIFF_LANES + i) as u32;
+                let (fx, fy) = (x as f64, y as f64);
+                let in_cursor = cursors.iter().any(|&(cx, cy)| {
+                    let dx = fx - cx;
+                    let dy = fy - cy;
                     dx * dx + dy * dy < cursor_radius_sq
-                } else {
-                    false
+                });
+                if in_cursor {
+                    continue;
                 }
-            });
 
-            if in_cursor {
-                continue;
+                changed_count += 1;
+                min_cx = min_cx.min(x);
+                min_cy = min_cy.min(y);
+                max_cx = max_cx.max(x);
+                max_cy = max_cy.max(y);
             }
-
-            changed_count += 1;
-            min_cx = min_cx.min(x);
-            min_cy = min_cy.min(y);
-            max_cx = max_cx.max(x);
-            max_cy = max_cy.max(y);
         }
     }
 
@@ -311,7 +494,7 @@ fn compute_pair_diff(
 }
 
 /// Find the nearest cursor position to a given time.
-fn find_cursor_nearest(positions: &[(u64, f64, f64)], time_ms: u64) -> Option<(f64, f64)> {
+pub(crate) fn find_cursor_nearest(positions: &[(u64, f64, f64)], time_ms: u64) -> Option<(f64, f64)> {
     if positions.is_empty() {
         return None;
     }
@@ -337,6 +520,10 @@ mod tests {
     use super::*;
     use image::{GrayImage, Luma};
 
+    fn full_hd() -> Rect {
+        Rect { x: 0.0, y: 0.0, width: 1920.0, height: 1080.0 }
+    }
+
     fn make_config(min_region: u32, cursor_radius: u32) -> DiffConfig {
         DiffConfig {
             sample_interval: 1,
@@ -445,6 +632,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lane_skipping_matches_every_pixel() {
+        // Width not a multiple of DIFF_LANES; changes scattered across lanes
+        let img_a = GrayImage::from_pixel(70, 60, Luma([100]));
+        let mut img_b = img_a.clone();
+        let changed = [(3, 0), (31, 10), (32, 10), (69, 59), (40, 30)];
+        for &(x, y) in &changed {
+            img_b.put_pixel(x, y, Luma([200]));
+        }
+        img_b.put_pixel(10, 10, Luma([110])); // below threshold
+        let (bbox, count) =
+            compute_pair_diff(&img_a, &img_b, None, None, &make_config(1, 0), 70, 60).unwrap();
+        assert_eq!(count, changed.len() as u64);
+        assert_eq!((bbox.x, bbox.y, bbox.width, bbox.height), (3.0, 0.0, 67.0, 60.0));
+    }
+
+    #[test]
+    fn test_load_downsampled_gray_samples_luma() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("frame.png");
+        let img = image::RgbImage::from_fn(8, 4, |x, _| {
+            if x < 4 { image::Rgb([255, 255, 255]) } else { image::Rgb([0, 0, 255]) }
+        });
+        img.save(&path).unwrap();
+        let gray = load_downsampled_gray(&path, 2).unwrap();
+        assert_eq!(gray.dimensions(), (4, 2));
+        assert_eq!(gray.get_pixel(0, 0)[0], 255);
+        assert_eq!(gray.get_pixel(3, 1)[0], 18);
+    }
+
+    #[test]
+    fn test_region_time_follows_capture_timestamps() {
+        let dir = tempfile::TempDir::new().unwrap();
+        // Frames 2-3 arrive late (dropped frames); the change is between 1 and 2
+        for idx in 0..4u32 {
+            let shade = if idx >= 2 { 0 } else { 255 };
+            image::RgbImage::from_pixel(40, 40, image::Rgb([shade, shade, shade]))
+                .save(dir.path().join(format!("frame_{:08}.png", idx)))
+                .unwrap();
+        }
+        let mut config = make_config(1, 0);
+        config.max_change_fraction = 1.0;
+        let index = FrameIndex::new(4, Some(vec![0, 100, 900, 1000]), 1000, 30);
+        let result =
+            detect_frame_changes(dir.path(), &index, &[], 40, 40, &CaptureMapping::IDENTITY, &config).unwrap();
+        assert_eq!(result.regions.len(), 1);
+        assert_eq!(result.regions[0].time_ms, 500);
+    }
+
     #[test]
     fn test_expand_bbox_no_regions() {
         let bbox = Rect {
@@ -453,7 +689,7 @@ mod tests {
             width: 360.0,
             height: 360.0,
         };
-        let expanded = expand_bbox_with_changes(&bbox, &[], 1920.0, 1080.0);
+        let expanded = expand_bbox_with_changes(&bbox, &[], &full_hd());
         assert_eq!(expanded.x, bbox.x);
         assert_eq!(expanded.width, bbox.width);
     }
@@ -477,7 +713,7 @@ mod tests {
             changed_pixel_count: 5000,
         };
         let refs = vec![&region];
-        let expanded = expand_bbox_with_changes(&bbox, &refs, 1920.0, 1080.0);
+        let expanded = expand_bbox_with_changes(&bbox, &refs, &full_hd());
         assert_eq!(expanded.x, 300.0);
         assert_eq!(expanded.y, 150.0);
         assert!(expanded.width >= 600.0);
@@ -503,13 +739,68 @@ mod tests {
             changed_pixel_count: 50000,
         };
         let refs = vec![&region];
-        let expanded = expand_bbox_with_changes(&bbox, &refs, 1920.0, 1080.0);
+        let expanded = expand_bbox_with_changes(&bbox, &refs, &full_hd());
         assert!(expanded.x >= 0.0);
         assert!(expanded.y >= 0.0);
         assert!(expanded.x + expanded.width <= 1920.0);
         assert!(expanded.y + expanded.height <= 1080.0);
     }
 
+    #[test]
+    fn test_capture_mapping_window_region() {
+        let mut meta = RecordingMeta {
+            version: 2,
+            id: "test".to_string(),
+            screen_width: 1600,
+            screen_height: 1200,
+            fps: 30,
+            start_time: "2024-01-01T00:00:00Z".to_string(),
+            duration_ms: 0,
+            has_audio: false,
+            monitor_scale: 1.0,
+            recording_dir: "/tmp".to_string(),
+            recording_mode: Some("window".to_string()),
+            window_title: None,
+            window_initial_rect: Some([100.0, 50.0, 900.0, 650.0]),
+            frame_count: None,
+            cursor_scale: None,
+            take: None,
+            splices: Vec::new(),
+            pauses: Vec::new(),
+            timelapse_speed: None,
+            title: None,
+            tags: Vec::new(),
+            notes: String::new(),
+            in_progress: false,
+        };
+        // 800x600 window captured at 200%
+        let mapping = CaptureMapping::from_meta(&meta);
+        assert_eq!(mapping, CaptureMapping { origin_x: 100.0, origin_y: 50.0, scale: 2.0 });
+        assert_eq!(mapping.screen_to_frame(150.0, 60.0), (100.0, 20.0));
+        let r = mapping.frame_rect_to_screen(&Rect { x: 200.0, y: 100.0, width: 400.0, height: 200.0 });
+        assert_eq!((r.x, r.y, r.width, r.height), (200.0, 100.0, 200.0, 100.0));
+        let b = mapping.screen_bounds(1600.0, 1200.0);
+        assert_eq!((b.x, b.y, b.width, b.height), (100.0, 50.0, 800.0, 600.0));
+
+        meta.recording_mode = Some("display".to_string());
+        assert_eq!(CaptureMapping::from_meta(&meta), CaptureMapping::IDENTITY);
+    }
+
+    #[test]
+    fn test_expand_bbox_clamps_to_window_bounds() {
+        let bbox = Rect { x: 700.0, y: 300.0, width: 100.0, height: 100.0 };
+        let region = ChangeRegion {
+            time_ms: 0,
+            bbox: Rect { x: 750.0, y: 250.0, width: 400.0, height: 100.0 },
+            changed_pixel_count: 1000,
+        };
+        let bounds = Rect { x: 100.0, y: 50.0, width: 800.0, height: 600.0 };
+        let expanded = expand_bbox_with_changes(&bbox, &[&region], &bounds);
+        // Right edge reaches the window edge at x=900 (not the frame width 800)
+        assert_eq!(expanded.x + expanded.width, 900.0);
+        assert_eq!(expanded.y, 250.0);
+    }
+
     #[test]
     fn test_find_cursor_nearest_empty() {
         assert!(find_cursor_nearest(&[], 1000).is_none());
@@ -0,0 +1,181 @@
+//! Golden-image tests for the compositor.
+//!
+//! Each case composes a synthetic fixture frame with fixed settings and
+//! compares the result with a reference PNG in `tests/golden/`. Pixels are
+//! compared in CIELAB (ΔE76), so tiny rounding differences from float math
+//! pass while visible changes to blending, shadows, rounded corners or the
+//! cursor fail.
+//!
+//! After an intended visual change, regenerate the references with
+//! `SNAPPI_UPDATE_GOLDEN=1 cargo test golden` and review the PNGs in the
+//! diff. On a mismatch the actual image is written to the system temp
+//! dir (`snappi-golden/<case>.png`) for inspection.
+
+use super::compositor::{ClickEffect, Compositor};
+use super::zoom_planner::{TransitionType, ZoomKeyframe};
+use crate::config::defaults::OutputStyle;
+use image::{Rgba, RgbaImage};
+use std::path::PathBuf;
+
+/// Color difference (ΔE76) below which two pixels count as equal; ~2.3 is
+/// the just-noticeable difference
+const DELTA_E_TOLERANCE: f64 = 3.0;
+
+/// Share of pixels allowed above the tolerance (anti-aliased edges)
+const MAX_DIFF_RATIO: f64 = 0.002;
+
+const SCREEN: (u32, u32) = (480, 270);
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+/// Small output so the references stay a few KB each
+fn style() -> OutputStyle {
+    OutputStyle {
+        output_width: 240,
+        output_height: 135,
+        canvas_width: 272,
+        canvas_height: 160,
+        border_radius: 8,
+        shadow_blur: 12.0,
+        shadow_offset_y: 4.0,
+        ..OutputStyle::default()
+    }
+}
+
+/// A mock app window: title bar, sidebar and a few content blocks
+fn fixture_frame() -> RgbaImage {
+    let (w, h) = SCREEN;
+    RgbaImage::from_fn(w, h, |x, y| {
+        let color = if y < 24 {
+            [40, 44, 52]
+        } else if x < 96 {
+            [230, 232, 236]
+        } else if (120..300).contains(&x) && (48..64).contains(&y) {
+            [59, 130, 246]
+        } else if ((120..440).contains(&x) && (88..96).contains(&y)) || ((120..380).contains(&x) && (108..116).contains(&y)) {
+            [120, 120, 120]
+        } else if (340..440).contains(&x) && (200..232).contains(&y) {
+            [34, 197, 94]
+        } else {
+            [255, 255, 255]
+        };
+        Rgba([color[0], color[1], color[2], 255])
+    })
+}
+
+fn compose(compositor: &mut Compositor, time_ms: u64, cursor: Option<(f64, f64)>, clicks: &[ClickEffect]) -> RgbaImage {
+    compositor.compose_frame(&fixture_frame(), time_ms, cursor, clicks, None, 1.0 / 30.0)
+}
+
+/// CIELAB of an sRGB pixel, alpha-blended over black so transparent
+/// and opaque pixels of the same color differ
+fn to_lab(p: &Rgba<u8>) -> [f64; 3] {
+    let a = p[3] as f64 / 255.0;
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0 * a;
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    let (r, g, b) = (linear(p[0]), linear(p[1]), linear(p[2]));
+    // D65 white
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+    let f = |t: f64| if t > 0.008856 { t.cbrt() } else { 7.787 * t + 16.0 / 116.0 };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Share of pixels whose ΔE76 exceeds [`DELTA_E_TOLERANCE`]
+fn perceptual_diff(a: &RgbaImage, b: &RgbaImage) -> f64 {
+    let differing = a
+        .pixels()
+        .zip(b.pixels())
+        .filter(|(pa, pb)| {
+            let (la, lb) = (to_lab(pa), to_lab(pb));
+            let d2: f64 = (0..3).map(|i| (la[i] - lb[i]).powi(2)).sum();
+            d2.sqrt() > DELTA_E_TOLERANCE
+        })
+        .count();
+    differing as f64 / (a.width() * a.height()).max(1) as f64
+}
+
+fn assert_golden(name: &str, actual: &RgbaImage) {
+    let path = golden_dir().join(format!("{}.png", name));
+    if std::env::var_os("SNAPPI_UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(golden_dir()).unwrap();
+        actual.save(&path).unwrap();
+        return;
+    }
+
+    let expected = image::open(&path)
+        .unwrap_or_else(|e| panic!("Missing reference {} ({}); run with SNAPPI_UPDATE_GOLDEN=1", path.display(), e))
+        .to_rgba8();
+    let mismatch = if expected.dimensions() != actual.dimensions() {
+        Some(format!("size {:?} != {:?}", actual.dimensions(), expected.dimensions()))
+    } else {
+        let ratio = perceptual_diff(&expected, actual);
+        (ratio > MAX_DIFF_RATIO).then(|| format!("{:.2}% of pixels differ", ratio * 100.0))
+    };
+    if let Some(mismatch) = mismatch {
+        let out = std::env::temp_dir().join("snappi-golden").join(format!("{}.png", name));
+        let _ = std::fs::create_dir_all(out.parent().unwrap());
+        let _ = actual.save(&out);
+        panic!("Golden image {} changed: {} (actual: {})", name, mismatch, out.display());
+    }
+}
+
+#[test]
+fn test_perceptual_diff_tolerates_rounding() {
+    let a = fixture_frame();
+    let mut b = a.clone();
+    for p in b.pixels_mut() {
+        p[0] = p[0].saturating_sub(1);
+    }
+    assert_eq!(perceptual_diff(&a, &b), 0.0);
+
+    // A moved block is a real difference
+    let mut c = a.clone();
+    for y in 48..64 {
+        for x in 300..340 {
+            c.put_pixel(x, y, Rgba([59, 130, 246, 255]));
+        }
+    }
+    assert!(perceptual_diff(&a, &c) > MAX_DIFF_RATIO);
+}
+
+/// Background gradient, shadow and rounded corners
+#[test]
+fn golden_plain_frame() {
+    let mut compositor = Compositor::new(style(), SCREEN.0, SCREEN.1);
+    assert_golden("plain", &compose(&mut compositor, 0, None, &[]));
+}
+
+/// Cursor sprite and a click ring halfway through
+#[test]
+fn golden_cursor_and_click() {
+    let mut compositor = Compositor::new(style(), SCREEN.0, SCREEN.1);
+    let click = ClickEffect { x: 210.0, y: 56.0, start_ms: 800, duration_ms: 400, alt: false };
+    let frame = compose(&mut compositor, 1000, Some((210.0, 56.0)), &[click]);
+    assert_golden("cursor_click", &frame);
+}
+
+/// 2x zoom on the content area once the camera has settled
+#[test]
+fn golden_zoomed() {
+    let mut compositor = Compositor::new(style(), SCREEN.0, SCREEN.1);
+    compositor.apply_keyframe(&ZoomKeyframe {
+        time_ms: 0,
+        target_x: 300.0,
+        target_y: 100.0,
+        zoom_level: 2.0,
+        transition: TransitionType::SpringIn,
+        spring_hint: None,
+        pinned: false,
+    });
+    for _ in 0..100 {
+        compositor.advance_camera(0.1);
+    }
+    assert_golden("zoomed", &compose(&mut compositor, 0, Some((300.0, 100.0)), &[]));
+}
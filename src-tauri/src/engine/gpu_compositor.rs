@@ -0,0 +1,509 @@
+//! wgpu backend for the heavy per-pixel stages of [`super::compositor`].
+//!
+//! Two render passes replace the CPU loops that dominate export time on
+//! large recordings:
+//!
+//! - **window pass**: crop the viewport out of the raw frame, scale it to
+//!   the output size (bilinear) and draw the cursor sprite on top;
+//! - **canvas pass**: background, rounded-rect drop shadow and the window
+//!   with anti-aliased rounded corners.
+//!
+//! Overlays that need fonts or per-frame state (click rings, badges,
+//! captions, annotations, window chrome...) still run on the CPU between
+//! the two passes, so both backends produce the same picture (up to the
+//! scaling filter: bilinear here, triangle on the CPU). Textures and
+//! readback buffers are kept between frames; the background is uploaded
+//! once. Built only with the `gpu` feature.
+
+use crate::config::defaults::OutputStyle;
+use anyhow::{anyhow, Context, Result};
+use image::RgbaImage;
+use std::borrow::Cow;
+
+const SHADER: &str = r#"
+struct VsOut {
+    @builtin(position) pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// One triangle covering the whole target
+@vertex
+fn vs_main(@builtin(vertex_index) i: u32) -> VsOut {
+    let uv = vec2<f32>(f32((i << 1u) & 2u), f32(i & 2u));
+    var out: VsOut;
+    out.pos = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+// Same "over" operator as blend_pixel() on the CPU (straight alpha)
+fn over(dst: vec4<f32>, src: vec4<f32>) -> vec4<f32> {
+    let a = src.a + dst.a * (1.0 - src.a);
+    if (a <= 0.0) {
+        return vec4<f32>(0.0);
+    }
+    let rgb = (src.rgb * src.a + dst.rgb * dst.a * (1.0 - src.a)) / a;
+    return vec4<f32>(rgb, a);
+}
+
+struct WindowParams {
+    // Viewport crop in source UV (x, y, w, h)
+    src_rect: vec4<f32>,
+    // Cursor sprite in output px (x, y, w, h); w = 0 when hidden
+    cursor_rect: vec4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> window_params: WindowParams;
+@group(0) @binding(1) var src_tex: texture_2d<f32>;
+@group(0) @binding(2) var cursor_tex: texture_2d<f32>;
+@group(0) @binding(3) var linear_sampler: sampler;
+
+@fragment
+fn fs_window(in: VsOut) -> @location(0) vec4<f32> {
+    let src_uv = window_params.src_rect.xy + in.uv * window_params.src_rect.zw;
+    var color = textureSampleLevel(src_tex, linear_sampler, src_uv, 0.0);
+    let rect = window_params.cursor_rect;
+    if (rect.z > 0.0) {
+        let c = (in.pos.xy - rect.xy) / rect.zw;
+        if (c.x >= 0.0 && c.y >= 0.0 && c.x < 1.0 && c.y < 1.0) {
+            color = over(color, textureSampleLevel(cursor_tex, linear_sampler, c, 0.0));
+        }
+    }
+    return color;
+}
+
+struct CanvasParams {
+    // Window position and size in canvas px
+    window_rect: vec4<f32>,
+    // Shadow color (0..1)
+    shadow_color: vec4<f32>,
+    radius: f32,
+    shadow_blur: f32,
+    shadow_offset_y: f32,
+    _pad: f32,
+};
+
+@group(0) @binding(0) var<uniform> canvas_params: CanvasParams;
+@group(0) @binding(1) var background_tex: texture_2d<f32>;
+@group(0) @binding(2) var window_tex: texture_2d<f32>;
+
+// Signed distance to a rounded rectangle (negative inside)
+fn rounded_rect_dist(p: vec2<f32>, rect: vec4<f32>, r: f32) -> f32 {
+    let half = rect.zw * 0.5;
+    let q = abs(p - (rect.xy + half)) - (half - vec2<f32>(r));
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - r;
+}
+
+@fragment
+fn fs_canvas(in: VsOut) -> @location(0) vec4<f32> {
+    let p = in.pos.xy;
+    let rect = canvas_params.window_rect;
+    let r = canvas_params.radius;
+    var color = textureLoad(background_tex, vec2<i32>(p), 0);
+
+    let blur = canvas_params.shadow_blur;
+    if (blur > 0.0 && canvas_params.shadow_color.a > 0.0) {
+        let shadow_rect = vec4<f32>(rect.x, rect.y + canvas_params.shadow_offset_y, rect.z, rect.w);
+        let d = rounded_rect_dist(p, shadow_rect, r);
+        if (d > 0.0 && d <= blur) {
+            let t = 1.0 - d / blur;
+            color = over(color, vec4<f32>(canvas_params.shadow_color.rgb, t * t * canvas_params.shadow_color.a));
+        }
+    }
+
+    let local = p - rect.xy;
+    if (local.x >= 0.0 && local.y >= 0.0 && local.x < rect.z && local.y < rect.w) {
+        var win = textureLoad(window_tex, vec2<i32>(local), 0);
+        if (r > 0.0) {
+            win.a = win.a * clamp(0.5 - rounded_rect_dist(p, rect, r), 0.0, 1.0);
+        }
+        color = over(color, win);
+    }
+    return color;
+}
+"#;
+
+/// A texture reused while the size stays the same
+struct SizedTexture {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    size: (u32, u32),
+}
+
+pub struct GpuCompositor {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    window_pipeline: wgpu::RenderPipeline,
+    canvas_pipeline: wgpu::RenderPipeline,
+    window_layout: wgpu::BindGroupLayout,
+    canvas_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    window_uniforms: wgpu::Buffer,
+    canvas_uniforms: wgpu::Buffer,
+    cursor: SizedTexture,
+    source: Option<SizedTexture>,
+    window_input: Option<SizedTexture>,
+    background: Option<SizedTexture>,
+    target: Option<SizedTexture>,
+    readback: Option<(wgpu::Buffer, u64)>,
+}
+
+impl GpuCompositor {
+    /// Open the default high-performance adapter. Fails when the system has
+    /// no usable GPU (the caller then stays on the CPU path).
+    pub fn new(cursor_sprite: &RgbaImage) -> Result<Self> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .context("No GPU adapter available")?;
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("snappi-compositor"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::downlevel_defaults().using_resolution(adapter.limits()),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        ))?;
+        log::info!("GPU compositor on {}", adapter.get_info().name);
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("compositor"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(SHADER)),
+        });
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let uniform_entry = wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let window_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("window"),
+            entries: &[
+                uniform_entry,
+                texture_entry(1),
+                texture_entry(2),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let canvas_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("canvas"),
+            entries: &[uniform_entry, texture_entry(1), texture_entry(2)],
+        });
+        let pipeline = |layout: &wgpu::BindGroupLayout, entry_point: &'static str| {
+            let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some(entry_point),
+                bind_group_layouts: &[layout],
+                push_constant_ranges: &[],
+            });
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_main",
+                    compilation_options: Default::default(),
+                    buffers: &[],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point,
+                    compilation_options: Default::default(),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let window_pipeline = pipeline(&window_layout, "fs_window");
+        let canvas_pipeline = pipeline(&canvas_layout, "fs_canvas");
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("linear"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let uniforms = |label, size| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+        let window_uniforms = uniforms("window params", 32);
+        let canvas_uniforms = uniforms("canvas params", 48);
+
+        let mut cursor = None;
+        upload(&device, &queue, &mut cursor, cursor_sprite);
+        let cursor = cursor.expect("upload always creates the texture");
+
+        Ok(Self {
+            device,
+            queue,
+            window_pipeline,
+            canvas_pipeline,
+            window_layout,
+            canvas_layout,
+            sampler,
+            window_uniforms,
+            canvas_uniforms,
+            cursor,
+            source: None,
+            window_input: None,
+            background: None,
+            target: None,
+            readback: None,
+        })
+    }
+
+    /// Crop `crop` (x, y, w, h in source px) out of `src`, scale it to
+    /// `out_w` x `out_h` and draw the cursor sprite at `cursor` (x, y, w, h
+    /// in output px).
+    pub fn crop_and_scale(
+        &mut self,
+        src: &RgbaImage,
+        crop: (u32, u32, u32, u32),
+        out_w: u32,
+        out_h: u32,
+        cursor: Option<(i32, i32, u32, u32)>,
+    ) -> Result<RgbaImage> {
+        upload(&self.device, &self.queue, &mut self.source, src);
+        let (sw, sh) = (src.width() as f32, src.height() as f32);
+        let (x, y, w, h) = crop;
+        let cursor = cursor.map_or([0.0; 4], |(cx, cy, cw, ch)| [cx as f32, cy as f32, cw as f32, ch as f32]);
+        let params = [x as f32 / sw, y as f32 / sh, w as f32 / sw, h as f32 / sh, cursor[0], cursor[1], cursor[2], cursor[3]];
+        self.queue.write_buffer(&self.window_uniforms, 0, &f32_bytes(&params));
+
+        let source = self.source.as_ref().expect("uploaded above");
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("window"),
+            layout: &self.window_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: self.window_uniforms.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&source.view) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::TextureView(&self.cursor.view) },
+                wgpu::BindGroupEntry { binding: 3, resource: wgpu::BindingResource::Sampler(&self.sampler) },
+            ],
+        });
+        self.render(&bind_group, true, out_w, out_h)
+    }
+
+    /// Background, drop shadow and `window` (with rounded corners) at
+    /// `offset` on a canvas the size of `background`.
+    pub fn compose_canvas(
+        &mut self,
+        background: &RgbaImage,
+        window: &RgbaImage,
+        offset: (u32, u32),
+        style: &OutputStyle,
+    ) -> Result<RgbaImage> {
+        // The background is the same for the whole export
+        if self.background.as_ref().is_none_or(|t| t.size != background.dimensions()) {
+            upload(&self.device, &self.queue, &mut self.background, background);
+        }
+        upload(&self.device, &self.queue, &mut self.window_input, window);
+        let c = style.shadow_color.map(|v| v as f32 / 255.0);
+        let params = [
+            offset.0 as f32,
+            offset.1 as f32,
+            window.width() as f32,
+            window.height() as f32,
+            c[0],
+            c[1],
+            c[2],
+            c[3],
+            style.border_radius as f32,
+            style.shadow_blur as f32,
+            style.shadow_offset_y as f32,
+            0.0,
+        ];
+        self.queue.write_buffer(&self.canvas_uniforms, 0, &f32_bytes(&params));
+
+        let background_view = &self.background.as_ref().expect("uploaded above").view;
+        let window_view = &self.window_input.as_ref().expect("uploaded above").view;
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("canvas"),
+            layout: &self.canvas_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: self.canvas_uniforms.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(background_view) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::TextureView(window_view) },
+            ],
+        });
+        self.render(&bind_group, false, background.width(), background.height())
+    }
+
+    /// Run one pass into a `width` x `height` target and read it back.
+    fn render(&mut self, bind_group: &wgpu::BindGroup, window_pass: bool, width: u32, height: u32) -> Result<RgbaImage> {
+        if self.target.as_ref().is_none_or(|t| t.size != (width, height)) {
+            self.target = Some(create_texture(
+                &self.device,
+                (width, height),
+                wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            ));
+        }
+        let target = self.target.as_ref().expect("created above");
+
+        // Rows of a texture-to-buffer copy are padded to 256 bytes
+        let row_bytes = width * 4;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_row = row_bytes.div_ceil(align) * align;
+        let buffer_size = padded_row as u64 * height as u64;
+        if self.readback.as_ref().is_none_or(|(_, size)| *size != buffer_size) {
+            let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("readback"),
+                size: buffer_size,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+            self.readback = Some((buffer, buffer_size));
+        }
+        let (readback, _) = self.readback.as_ref().expect("created above");
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("compose") });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("compose"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations { load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT), store: wgpu::StoreOp::Store },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(if window_pass { &self.window_pipeline } else { &self.canvas_pipeline });
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+        encoder.copy_texture_to_buffer(
+            target.texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        rx.recv()?.map_err(|e| anyhow!("GPU readback failed: {}", e))?;
+
+        let mut pixels = Vec::with_capacity((row_bytes * height) as usize);
+        {
+            let mapped = slice.get_mapped_range();
+            for row in mapped.chunks(padded_row as usize) {
+                pixels.extend_from_slice(&row[..row_bytes as usize]);
+            }
+        }
+        readback.unmap();
+        RgbaImage::from_raw(width, height, pixels).context("GPU readback has the wrong size")
+    }
+}
+
+fn create_texture(device: &wgpu::Device, size: (u32, u32), usage: wgpu::TextureUsages) -> SizedTexture {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d { width: size.0, height: size.1, depth_or_array_layers: 1 },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    SizedTexture { texture, view, size }
+}
+
+/// Copy `image` into `slot`, (re)creating the texture when the size changed.
+fn upload(device: &wgpu::Device, queue: &wgpu::Queue, slot: &mut Option<SizedTexture>, image: &RgbaImage) {
+    let size = image.dimensions();
+    if slot.as_ref().is_none_or(|t| t.size != size) {
+        *slot = Some(create_texture(
+            device,
+            size,
+            wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        ));
+    }
+    let texture = slot.as_ref().expect("created above");
+    queue.write_texture(
+        texture.texture.as_image_copy(),
+        image.as_raw(),
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(size.0 * 4),
+            rows_per_image: Some(size.1),
+        },
+        wgpu::Extent3d { width: size.0, height: size.1, depth_or_array_layers: 1 },
+    );
+}
+
+fn f32_bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_canvas_pass_places_window_with_rounded_corners() {
+        // Skipped on machines without any adapter
+        let Ok(mut gpu) = GpuCompositor::new(&RgbaImage::new(8, 8)) else {
+            return;
+        };
+        let background = RgbaImage::from_pixel(120, 80, Rgba([0, 0, 255, 255]));
+        let window = RgbaImage::from_pixel(80, 40, Rgba([255, 0, 0, 255]));
+        let style = OutputStyle { border_radius: 10, shadow_blur: 0.0, ..OutputStyle::default() };
+        let canvas = gpu.compose_canvas(&background, &window, (20, 20), &style).unwrap();
+
+        assert_eq!(canvas.dimensions(), (120, 80));
+        assert_eq!(*canvas.get_pixel(60, 40), Rgba([255, 0, 0, 255]));
+        assert_eq!(*canvas.get_pixel(5, 5), Rgba([0, 0, 255, 255]));
+        // Outside the rounded corner the background shows through
+        assert_eq!(*canvas.get_pixel(20, 20), Rgba([0, 0, 255, 255]));
+    }
+}
@@ -0,0 +1,151 @@
+//! Keyframe compaction for edited zoom plans.
+//!
+//! Manual Timeline edits tend to leave redundant keyframes behind: several
+//! consecutive keyframes that target the same viewport, or bursts of
+//! keyframes too close together for the spring to settle in between.
+//! [`optimize_keyframes`] removes them while never touching keyframes the
+//! user pinned (`ZoomKeyframe::pinned`).
+
+use super::zoom_planner::ZoomKeyframe;
+
+/// Thresholds used by [`optimize_keyframes`].
+#[derive(Debug, Clone)]
+pub struct OptimizeConfig {
+    /// Max target distance (px) for two keyframes to be considered equivalent.
+    pub position_epsilon: f64,
+    /// Max zoom difference for two keyframes to be considered equivalent.
+    pub zoom_epsilon: f64,
+    /// Minimum spacing between consecutive keyframes (ms).
+    pub min_interval_ms: u64,
+}
+
+impl Default for OptimizeConfig {
+    fn default() -> Self {
+        Self {
+            position_epsilon: 2.0,
+            zoom_epsilon: 0.01,
+            min_interval_ms: 800,
+        }
+    }
+}
+
+/// Same viewport target within the configured epsilons.
+fn is_equivalent(a: &ZoomKeyframe, b: &ZoomKeyframe, config: &OptimizeConfig) -> bool {
+    let dist = ((a.target_x - b.target_x).powi(2) + (a.target_y - b.target_y).powi(2)).sqrt();
+    dist <= config.position_epsilon && (a.zoom_level - b.zoom_level).abs() <= config.zoom_epsilon
+}
+
+/// Drop keyframes that re-target the viewport the previous keyframe already
+/// moves to. The first keyframe of a run is kept since it starts the motion.
+fn merge_equivalent(keyframes: Vec<ZoomKeyframe>, config: &OptimizeConfig) -> Vec<ZoomKeyframe> {
+    let mut out: Vec<ZoomKeyframe> = Vec::with_capacity(keyframes.len());
+    for kf in keyframes {
+        match out.last() {
+            Some(prev) if !kf.pinned && is_equivalent(prev, &kf, config) => {}
+            _ => out.push(kf),
+        }
+    }
+    out
+}
+
+/// Enforce `min_interval_ms` between consecutive keyframes. Like the
+/// planner's own dedup pass the later keyframe wins, unless only the earlier
+/// one is pinned. Two pinned keyframes are always kept.
+fn enforce_min_spacing(keyframes: Vec<ZoomKeyframe>, config: &OptimizeConfig) -> Vec<ZoomKeyframe> {
+    let mut out: Vec<ZoomKeyframe> = Vec::with_capacity(keyframes.len());
+    for kf in keyframes {
+        let Some(prev) = out.last() else {
+            out.push(kf);
+            continue;
+        };
+        if kf.time_ms.saturating_sub(prev.time_ms) >= config.min_interval_ms {
+            out.push(kf);
+            continue;
+        }
+        match (prev.pinned, kf.pinned) {
+            (true, true) => out.push(kf),
+            (true, false) => {}
+            (false, _) => {
+                out.pop();
+                out.push(kf);
+            }
+        }
+    }
+    out
+}
+
+/// Compact a keyframe list: sort by time, merge equivalent consecutive
+/// keyframes and enforce the minimum spacing. Pinned keyframes are preserved.
+pub fn optimize_keyframes(keyframes: &[ZoomKeyframe], config: &OptimizeConfig) -> Vec<ZoomKeyframe> {
+    let mut sorted = keyframes.to_vec();
+    sorted.sort_by_key(|kf| kf.time_ms);
+
+    let merged = merge_equivalent(sorted, config);
+    let spaced = enforce_min_spacing(merged, config);
+    // Spacing removals can leave new equivalent neighbours behind
+    merge_equivalent(spaced, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::zoom_planner::TransitionType;
+
+    fn kf(time_ms: u64, x: f64, zoom_level: f64) -> ZoomKeyframe {
+        ZoomKeyframe {
+            time_ms,
+            target_x: x,
+            target_y: 500.0,
+            zoom_level,
+            transition: TransitionType::Smooth,
+            spring_hint: None,
+            pinned: false,
+        }
+    }
+
+    fn pinned(mut k: ZoomKeyframe) -> ZoomKeyframe {
+        k.pinned = true;
+        k
+    }
+
+    #[test]
+    fn test_merges_equivalent_consecutive_keyframes() {
+        let kfs = vec![kf(0, 100.0, 2.0), kf(2000, 101.0, 2.005), kf(4000, 800.0, 2.0)];
+        let out = optimize_keyframes(&kfs, &OptimizeConfig::default());
+        let times: Vec<u64> = out.iter().map(|k| k.time_ms).collect();
+        assert_eq!(times, vec![0, 4000]);
+    }
+
+    #[test]
+    fn test_min_spacing_keeps_later_keyframe() {
+        let kfs = vec![kf(0, 100.0, 1.0), kf(1000, 300.0, 2.0), kf(1300, 600.0, 2.5)];
+        let out = optimize_keyframes(&kfs, &OptimizeConfig::default());
+        let times: Vec<u64> = out.iter().map(|k| k.time_ms).collect();
+        assert_eq!(times, vec![0, 1300]);
+    }
+
+    #[test]
+    fn test_pinned_keyframes_are_preserved() {
+        // Equivalent to the previous one, but pinned → kept
+        let kfs = vec![kf(0, 100.0, 2.0), pinned(kf(2000, 100.0, 2.0))];
+        assert_eq!(optimize_keyframes(&kfs, &OptimizeConfig::default()).len(), 2);
+
+        // Too close: the unpinned later keyframe yields to the pinned one
+        let kfs = vec![pinned(kf(1000, 300.0, 2.0)), kf(1300, 600.0, 2.5)];
+        let out = optimize_keyframes(&kfs, &OptimizeConfig::default());
+        assert_eq!(out.len(), 1);
+        assert!(out[0].pinned);
+
+        // Two pinned keyframes are never merged
+        let kfs = vec![pinned(kf(1000, 300.0, 2.0)), pinned(kf(1100, 600.0, 2.5))];
+        assert_eq!(optimize_keyframes(&kfs, &OptimizeConfig::default()).len(), 2);
+    }
+
+    #[test]
+    fn test_unsorted_input_is_sorted() {
+        let kfs = vec![kf(4000, 800.0, 2.0), kf(0, 100.0, 2.0)];
+        let out = optimize_keyframes(&kfs, &OptimizeConfig::default());
+        assert_eq!(out[0].time_ms, 0);
+        assert_eq!(out[1].time_ms, 4000);
+    }
+}
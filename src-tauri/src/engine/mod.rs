@@ -1,10 +1,27 @@
 pub mod analyzer;
+pub mod annotation;
+pub mod app_exclusion;
 pub mod compositor;
 pub mod cursor_smoother;
 pub mod frame_differ;
+#[cfg(test)]
+mod golden;
+#[cfg(feature = "gpu")]
+pub mod gpu_compositor;
+pub mod keyframe_optimizer;
+pub mod ocr;
 pub mod preprocessor;
+pub mod privacy_review;
+pub mod recording_compare;
+pub mod redaction;
 pub mod scene_splitter;
+pub mod scene_style;
+pub mod scroll_stitch;
+#[cfg(feature = "scripting")]
+pub mod scripting;
 pub mod spring;
+pub mod timeline_cut;
+pub mod typed_text;
 pub mod ui_context;
 pub mod zoom_planner;
 pub mod effects;
@@ -0,0 +1,100 @@
+//! Text recognition on recorded frames.
+//!
+//! Uses the OCR engine built into Windows (`Windows.Media.Ocr`) with the
+//! user's profile languages. Elsewhere, or when no OCR language pack is
+//! installed, [`TextRecognizer::new`] returns `None` and callers skip the
+//! text checks.
+
+use image::RgbaImage;
+
+/// A recognized line of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrLine {
+    pub text: String,
+    /// [left, top, right, bottom] in frame pixels
+    pub rect: [f64; 4],
+}
+
+pub struct TextRecognizer {
+    #[cfg(windows)]
+    engine: windows::Media::Ocr::OcrEngine,
+}
+
+impl TextRecognizer {
+    pub fn new() -> Option<Self> {
+        #[cfg(windows)]
+        {
+            use windows::Win32::System::Com::{CoInitializeEx, COINIT_MULTITHREADED};
+            // Already initialized on this thread is fine too
+            let _ = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+            let engine = windows::Media::Ocr::OcrEngine::TryCreateFromUserProfileLanguages().ok()?;
+            Some(Self { engine })
+        }
+        #[cfg(not(windows))]
+        None
+    }
+
+    /// Lines of text in `frame`, or `None` if recognition failed.
+    #[cfg(windows)]
+    pub fn recognize(&self, frame: &RgbaImage) -> Option<Vec<OcrLine>> {
+        use windows::Graphics::Imaging::{BitmapPixelFormat, SoftwareBitmap};
+        use windows::Media::Ocr::OcrEngine;
+        use windows::Storage::Streams::DataWriter;
+
+        // The engine rejects images larger than this (4K screens)
+        let max_dimension = OcrEngine::MaxImageDimension().ok()?.max(1);
+        let scale = (max_dimension as f64 / frame.width().max(frame.height()).max(1) as f64).min(1.0);
+        let scaled;
+        let image = if scale < 1.0 {
+            let w = ((frame.width() as f64 * scale) as u32).max(1);
+            let h = ((frame.height() as f64 * scale) as u32).max(1);
+            scaled = image::imageops::resize(frame, w, h, image::imageops::FilterType::Triangle);
+            &scaled
+        } else {
+            frame
+        };
+
+        let mut bgra = image.as_raw().clone();
+        for px in bgra.chunks_exact_mut(4) {
+            px.swap(0, 2);
+        }
+        let writer = DataWriter::new().ok()?;
+        writer.WriteBytes(&bgra).ok()?;
+        let buffer = writer.DetachBuffer().ok()?;
+        let bitmap = SoftwareBitmap::CreateCopyFromBuffer(
+            &buffer,
+            BitmapPixelFormat::Bgra8,
+            image.width() as i32,
+            image.height() as i32,
+        )
+        .ok()?;
+        let result = self.engine.RecognizeAsync(&bitmap).ok()?.get().ok()?;
+
+        let mut lines = Vec::new();
+        for line in result.Lines().ok()? {
+            let mut rect: Option<[f64; 4]> = None;
+            for word in line.Words().ok()? {
+                let r = word.BoundingRect().ok()?;
+                let w = [
+                    r.X as f64 / scale,
+                    r.Y as f64 / scale,
+                    (r.X + r.Width) as f64 / scale,
+                    (r.Y + r.Height) as f64 / scale,
+                ];
+                rect = Some(match rect {
+                    Some(a) => [a[0].min(w[0]), a[1].min(w[1]), a[2].max(w[2]), a[3].max(w[3])],
+                    None => w,
+                });
+            }
+            if let Some(rect) = rect {
+                lines.push(OcrLine { text: line.Text().ok()?.to_string(), rect });
+            }
+        }
+        Some(lines)
+    }
+
+    #[cfg(not(windows))]
+    pub fn recognize(&self, _frame: &RgbaImage) -> Option<Vec<OcrLine>> {
+        None
+    }
+}
@@ -1,6 +1,45 @@
-use crate::config::RecordingEvent;
+use crate::config::{EffectsSettings, RecordingEvent};
 use std::collections::HashSet;
 
+/// A drag without a matching release must move this many times further
+/// than `drag_min_distance_px` to count as a drag.
+const UNRELEASED_DRAG_FACTOR: f64 = 2.5;
+
+/// Preprocessing thresholds (exposed as advanced settings)
+#[derive(Debug, Clone)]
+pub struct PreprocessConfig {
+    /// Mouse moves shorter than this are thinned out (px)
+    pub move_thin_distance_px: f64,
+    /// A mouse move this long after the previous kept one is always kept (stop position, ms)
+    pub move_thin_interval_ms: u64,
+    /// Minimum press → release displacement for a drag (px)
+    pub drag_min_distance_px: f64,
+    /// Minimum press → release duration for a drag (ms)
+    pub drag_min_duration_ms: u64,
+}
+
+impl Default for PreprocessConfig {
+    fn default() -> Self {
+        Self {
+            move_thin_distance_px: 3.0,
+            move_thin_interval_ms: 200,
+            drag_min_distance_px: 20.0,
+            drag_min_duration_ms: 0,
+        }
+    }
+}
+
+impl PreprocessConfig {
+    pub fn from_settings(effects: &EffectsSettings) -> Self {
+        Self {
+            move_thin_distance_px: effects.move_thin_distance_px,
+            move_thin_interval_ms: effects.move_thin_interval_ms,
+            drag_min_distance_px: effects.drag_min_distance_px,
+            drag_min_duration_ms: effects.drag_min_duration_ms,
+        }
+    }
+}
+
 /// Drag event detected by the preprocessor
 #[derive(Debug, Clone)]
 pub struct DragEvent {
@@ -19,9 +58,9 @@ pub struct PreprocessedEvents {
 }
 
 /// Run all preprocessing steps on raw events.
-pub fn preprocess(events: &[RecordingEvent]) -> PreprocessedEvents {
-    let thinned = thin_mouse_moves(events, 3.0);
-    let drags = detect_drags(events);
+pub fn preprocess(events: &[RecordingEvent], config: &PreprocessConfig) -> PreprocessedEvents {
+    let thinned = thin_mouse_moves(events, config.move_thin_distance_px, config.move_thin_interval_ms);
+    let drags = detect_drags(events, config.drag_min_distance_px, config.drag_min_duration_ms);
     PreprocessedEvents {
         events: thinned,
         drags,
@@ -33,6 +72,7 @@ pub fn preprocess(events: &[RecordingEvent]) -> PreprocessedEvents {
 pub fn thin_mouse_moves(
     events: &[RecordingEvent],
     distance_threshold: f64,
+    stop_interval_ms: u64,
 ) -> Vec<RecordingEvent> {
     // Collect timestamps of significant events and create protection windows
     let mut protected_times = HashSet::new();
@@ -69,8 +109,8 @@ pub fn thin_mouse_moves(
                 let is_protected = protected_times.contains(t);
 
                 // Keep if: sufficient distance, or near significant event,
-                // or 200ms+ gap (stop position)
-                if dist >= distance_threshold || is_protected || time_gap >= 200 {
+                // or a long enough gap (stop position)
+                if dist >= distance_threshold || is_protected || time_gap >= stop_interval_ms {
                     result.push(event.clone());
                     last_x = *x;
                     last_y = *y;
@@ -85,8 +125,12 @@ pub fn thin_mouse_moves(
     result
 }
 
-/// Detect drag operations from Click → MouseMove(>20px) → ClickRelease patterns.
-pub fn detect_drags(events: &[RecordingEvent]) -> Vec<DragEvent> {
+/// Detect drag operations from Click → MouseMove(>min_distance) → ClickRelease patterns.
+pub fn detect_drags(
+    events: &[RecordingEvent],
+    min_distance: f64,
+    min_duration_ms: u64,
+) -> Vec<DragEvent> {
     let mut drags = Vec::new();
 
     for (i, event) in events.iter().enumerate() {
@@ -135,9 +179,15 @@ pub fn detect_drags(events: &[RecordingEvent]) -> Vec<DragEvent> {
             }
         }
 
-        // Classify as drag if total displacement > 20px
+        // Classify as drag if total displacement > min_distance
         let total_dist = ((end_x - x).powi(2) + (end_y - y).powi(2)).sqrt();
-        if (found_release && total_dist > 20.0) || (!found_release && max_dist > 50.0) {
+        let long_enough = end_time.saturating_sub(t) >= min_duration_ms;
+        let far_enough = if found_release {
+            total_dist > min_distance
+        } else {
+            max_dist > min_distance * UNRELEASED_DRAG_FACTOR
+        };
+        if far_enough && long_enough {
             drags.push(DragEvent {
                 start_ms: t,
                 end_ms: end_time,
@@ -194,7 +244,7 @@ mod tests {
             mm(20, 101.0, 101.0),  // < 3px, should be removed
             mm(30, 104.0, 100.0),  // > 3px, should be kept
         ];
-        let result = thin_mouse_moves(&events, 3.0);
+        let result = thin_mouse_moves(&events, 3.0, 200);
         assert_eq!(result.len(), 2); // first + 104.0
     }
 
@@ -206,7 +256,7 @@ mod tests {
             click(100, 100.0, 100.0),
             mm(110, 100.5, 100.5),  // < 3px but within 100ms of click
         ];
-        let result = thin_mouse_moves(&events, 3.0);
+        let result = thin_mouse_moves(&events, 3.0, 200);
         // All should be preserved due to protection window
         assert_eq!(result.len(), 4);
     }
@@ -217,7 +267,7 @@ mod tests {
             mm(0, 100.0, 100.0),
             mm(300, 100.5, 100.5),  // > 200ms gap = stop position
         ];
-        let result = thin_mouse_moves(&events, 3.0);
+        let result = thin_mouse_moves(&events, 3.0, 200);
         assert_eq!(result.len(), 2);
     }
 
@@ -230,7 +280,7 @@ mod tests {
             mm(30, 150.0, 100.0),  // 50px from start
             click_release(40, 150.0, 100.0),
         ];
-        let drags = detect_drags(&events);
+        let drags = detect_drags(&events, 20.0, 0);
         assert_eq!(drags.len(), 1);
         assert_eq!(drags[0].start_ms, 0);
         assert_eq!(drags[0].end_ms, 40);
@@ -243,7 +293,7 @@ mod tests {
             mm(10, 105.0, 100.0),  // only 5px
             click_release(20, 105.0, 100.0),
         ];
-        let drags = detect_drags(&events);
+        let drags = detect_drags(&events, 20.0, 0);
         assert_eq!(drags.len(), 0);
     }
 
@@ -256,7 +306,7 @@ mod tests {
             mm(30, 160.0, 100.0),  // 60px max distance > 50px threshold
             click(1000, 200.0, 200.0), // next click breaks window
         ];
-        let drags = detect_drags(&events);
+        let drags = detect_drags(&events, 20.0, 0);
         assert_eq!(drags.len(), 1);
     }
 
@@ -269,8 +319,27 @@ mod tests {
             click_release(200, 160.0, 100.0),
             key(300),
         ];
-        let result = preprocess(&events);
+        let result = preprocess(&events, &PreprocessConfig::default());
         assert!(!result.events.is_empty());
         assert_eq!(result.drags.len(), 1);
     }
+
+    #[test]
+    fn test_drag_thresholds_from_config() {
+        let events = vec![
+            click(0, 100.0, 100.0),
+            mm(50, 130.0, 100.0),
+            click_release(100, 130.0, 100.0), // 30px in 100ms
+        ];
+        assert_eq!(detect_drags(&events, 20.0, 0).len(), 1);
+        assert_eq!(detect_drags(&events, 40.0, 0).len(), 0);
+        assert_eq!(detect_drags(&events, 20.0, 150).len(), 0);
+    }
+
+    #[test]
+    fn test_thin_stop_interval_from_config() {
+        let events = vec![mm(0, 100.0, 100.0), mm(300, 100.5, 100.5)];
+        assert_eq!(thin_mouse_moves(&events, 3.0, 200).len(), 2);
+        assert_eq!(thin_mouse_moves(&events, 3.0, 500).len(), 1);
+    }
 }
@@ -0,0 +1,280 @@
+//! Privacy review of a recording before it is shared.
+//!
+//! Lists what may leak, with times, so the user can add redactions:
+//! windows whose title looks sensitive, password fields, typed text, and
+//! (with OCR) email addresses visible on screen. Each finding carries a
+//! suggested [`Redaction`] in frame pixels when its location is known.
+//! The review only reports; nothing is redacted until the user saves it.
+
+use crate::config::RecordingEvent;
+use crate::engine::frame_differ::CaptureMapping;
+use crate::engine::ocr::OcrLine;
+use crate::engine::redaction::{self, Redaction, RedactionStyle};
+use crate::engine::typed_text;
+use serde::{Deserialize, Serialize};
+
+/// Window titles containing one of these are reported (lower case)
+const SENSITIVE_WINDOW_HINTS: &[&str] = &[
+    "password",
+    "1password",
+    "bitwarden",
+    "keepass",
+    "lastpass",
+    "credential",
+    "secret",
+    ".env",
+    "private",
+    "inbox",
+    "gmail",
+    "outlook",
+    "slack",
+    "teams",
+    "discord",
+    "bank",
+    "パスワード",
+    "受信トレイ",
+    "銀行",
+];
+
+/// Visible text is checked once per this interval of the recording
+pub const OCR_SAMPLE_MS: u64 = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PrivacyRiskKind {
+    /// A focused window whose title matches a sensitive pattern
+    SensitiveWindow,
+    /// A password (or password-like) input field had focus
+    SecretInput,
+    /// Text was typed
+    TypedText,
+    /// An email address was read on screen
+    VisibleEmail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyFinding {
+    pub kind: PrivacyRiskKind,
+    pub start_ms: u64,
+    /// None: until the end of the recording
+    pub end_ms: Option<u64>,
+    /// What was found: window title, field name, typed text or address
+    pub detail: String,
+    /// Redaction covering the finding, ready for `save_redactions`
+    pub redaction: Option<Redaction>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrivacyReport {
+    /// Ordered by start time
+    pub findings: Vec<PrivacyFinding>,
+    /// False when OCR was unavailable and visible text was not checked
+    pub ocr_checked: bool,
+}
+
+fn is_sensitive_title(title: &str) -> bool {
+    let title = title.to_lowercase();
+    SENSITIVE_WINDOW_HINTS.iter().any(|h| title.contains(h))
+}
+
+fn screen_redaction(rect: &[f64; 4], mapping: &CaptureMapping, start_ms: u64, end_ms: Option<u64>) -> Option<Redaction> {
+    if rect[2] <= rect[0] || rect[3] <= rect[1] {
+        return None;
+    }
+    let (left, top) = mapping.screen_to_frame(rect[0], rect[1]);
+    let (right, bottom) = mapping.screen_to_frame(rect[2], rect[3]);
+    Some(Redaction {
+        rect: [left, top, right, bottom],
+        start_ms: Some(start_ms),
+        end_ms,
+        style: RedactionStyle::Pixelate,
+    })
+}
+
+/// Findings from the recorded events: sensitive windows, secret inputs and
+/// typed text. Event rects are mapped into frame pixels with `mapping`.
+pub fn scan_events(events: &[RecordingEvent], mapping: &CaptureMapping) -> Vec<PrivacyFinding> {
+    let mut findings: Vec<PrivacyFinding> = Vec::new();
+
+    // A sensitive window is reported until another window gets focus
+    let mut open: Option<usize> = None;
+    for event in events {
+        let RecordingEvent::WindowFocus { t, title, rect, .. } = event else {
+            continue;
+        };
+        if let Some(i) = open.take() {
+            findings[i].end_ms = Some(*t);
+            if let Some(r) = findings[i].redaction.as_mut() {
+                r.end_ms = Some(*t);
+            }
+        }
+        if is_sensitive_title(title) {
+            open = Some(findings.len());
+            findings.push(PrivacyFinding {
+                kind: PrivacyRiskKind::SensitiveWindow,
+                start_ms: *t,
+                end_ms: None,
+                detail: title.clone(),
+                redaction: screen_redaction(rect, mapping, *t, None),
+            });
+        }
+    }
+
+    for input in redaction::detect_input_redactions(events, mapping) {
+        let start_ms = input.start_ms.unwrap_or(0);
+        let name = events.iter().find_map(|e| match e {
+            RecordingEvent::UiFocus { t, name, .. } | RecordingEvent::Focus { t, name, .. } if *t == start_ms => {
+                Some(name.clone())
+            }
+            _ => None,
+        });
+        findings.push(PrivacyFinding {
+            kind: PrivacyRiskKind::SecretInput,
+            start_ms,
+            end_ms: input.end_ms,
+            detail: name.unwrap_or_default(),
+            redaction: Some(input),
+        });
+    }
+
+    for caption in typed_text::extract_typed_captions(events) {
+        let Some(&(start_ms, _)) = caption.states.first() else {
+            continue;
+        };
+        let text = caption
+            .states
+            .iter()
+            .map(|(_, s)| s)
+            .max_by_key(|s| s.chars().count())
+            .cloned()
+            .unwrap_or_default();
+        if text.trim().is_empty() {
+            continue;
+        }
+        findings.push(PrivacyFinding {
+            kind: PrivacyRiskKind::TypedText,
+            start_ms,
+            end_ms: Some(caption.end_ms),
+            detail: text,
+            redaction: caption.anchor.and_then(|a| screen_redaction(&a, mapping, start_ms, Some(caption.end_ms))),
+        });
+    }
+    findings
+}
+
+/// Email-like strings (`local@domain.tld`) in `text`.
+pub fn find_emails(text: &str) -> Vec<String> {
+    let is_part = |c: char| c.is_ascii_alphanumeric() || "._%+-@".contains(c);
+    text.split(|c: char| !is_part(c))
+        .map(|word| word.trim_matches(|c: char| c == '.' || c == '-'))
+        .filter(|word| {
+            let Some((local, domain)) = word.split_once('@') else {
+                return false;
+            };
+            let tld = domain.rsplit('.').next().unwrap_or("");
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && tld.len() >= 2
+                && tld.chars().all(|c| c.is_ascii_alphabetic())
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Email addresses in OCR samples (`(time, lines)` every `sample_ms`). An
+/// address read in consecutive samples is one finding spanning them.
+pub fn find_visible_emails(
+    samples: impl IntoIterator<Item = (u64, Vec<OcrLine>)>,
+    sample_ms: u64,
+) -> Vec<PrivacyFinding> {
+    let mut findings: Vec<PrivacyFinding> = Vec::new();
+    // Address → (finding index, last sample it was seen in)
+    let mut open: std::collections::HashMap<String, (usize, u64)> = std::collections::HashMap::new();
+    for (t, lines) in samples {
+        for line in lines {
+            for email in find_emails(&line.text) {
+                let end_ms = t + sample_ms;
+                match open.get_mut(&email) {
+                    Some((i, last)) if t <= *last + sample_ms => {
+                        let finding = &mut findings[*i];
+                        finding.end_ms = Some(end_ms);
+                        if let Some(r) = finding.redaction.as_mut() {
+                            r.end_ms = Some(end_ms);
+                            r.rect = union(r.rect, line.rect);
+                        }
+                        *last = t;
+                    }
+                    _ => {
+                        open.insert(email.clone(), (findings.len(), t));
+                        findings.push(PrivacyFinding {
+                            kind: PrivacyRiskKind::VisibleEmail,
+                            start_ms: t,
+                            end_ms: Some(end_ms),
+                            detail: email,
+                            redaction: Some(Redaction {
+                                rect: line.rect,
+                                start_ms: Some(t),
+                                end_ms: Some(end_ms),
+                                style: RedactionStyle::Pixelate,
+                            }),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    findings
+}
+
+fn union(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+    [a[0].min(b[0]), a[1].min(b[1]), a[2].max(b[2]), a[3].max(b[3])]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_events_reports_windows_inputs_and_typing() {
+        let rect = [0.0, 0.0, 800.0, 600.0];
+        let events = vec![
+            RecordingEvent::WindowFocus { t: 100, title: "Inbox - Outlook".into(), rect, process: String::new() },
+            RecordingEvent::WindowFocus { t: 900, title: "Visual Studio Code".into(), rect, process: String::new() },
+            RecordingEvent::UiFocus {
+                t: 1000,
+                control: "Edit".into(),
+                name: "Password".into(),
+                rect: [10.0, 10.0, 200.0, 30.0],
+                automation_id: String::new(),
+                password: true,
+            },
+            RecordingEvent::WindowFocus { t: 2000, title: "Notes".into(), rect, process: String::new() },
+        ];
+        let findings = scan_events(&events, &CaptureMapping::IDENTITY);
+        let window = findings.iter().find(|f| f.kind == PrivacyRiskKind::SensitiveWindow).unwrap();
+        assert_eq!((window.start_ms, window.end_ms), (100, Some(900)));
+        assert_eq!(window.redaction.as_ref().unwrap().end_ms, Some(900));
+        let input = findings.iter().find(|f| f.kind == PrivacyRiskKind::SecretInput).unwrap();
+        assert_eq!((input.start_ms, input.end_ms, input.detail.as_str()), (1000, Some(2000), "Password"));
+        assert_eq!(findings.len(), 2);
+    }
+
+    #[test]
+    fn test_visible_emails_merge_consecutive_samples() {
+        assert_eq!(find_emails("Contact: alice.b@example.co.jp, or bob@x"), vec!["alice.b@example.co.jp"]);
+        assert!(find_emails("@handle and 1@2.34").is_empty());
+
+        let line = |text: &str, x: f64| OcrLine { text: text.into(), rect: [x, 10.0, x + 100.0, 20.0] };
+        let samples = vec![
+            (0, vec![line("To: alice@example.com", 0.0)]),
+            (2000, vec![line("alice@example.com", 40.0)]),
+            (8000, vec![line("alice@example.com", 0.0)]),
+        ];
+        let findings = find_visible_emails(samples, 2000);
+        assert_eq!(findings.len(), 2);
+        assert_eq!((findings[0].start_ms, findings[0].end_ms), (0, Some(4000)));
+        assert_eq!(findings[0].redaction.as_ref().unwrap().rect, [0.0, 10.0, 140.0, 20.0]);
+        assert_eq!(findings[1].start_ms, 8000);
+    }
+}
@@ -0,0 +1,131 @@
+//! Recording comparison for QA: summarize two recordings of the same flow
+//! and score how similar they are.
+
+use crate::config::{RecordingComparison, RecordingEvent, RecordingStats};
+
+/// Weights of the individual similarity terms (sum to 1.0).
+const W_DURATION: f64 = 0.25;
+const W_SCENES: f64 = 0.2;
+const W_CLICKS: f64 = 0.2;
+const W_KEYS: f64 = 0.1;
+const W_WINDOWS: f64 = 0.25;
+
+/// Summarize a recording from its (preprocessed) events and scene count.
+pub fn collect_stats(
+    id: &str,
+    duration_ms: u64,
+    events: &[RecordingEvent],
+    scene_count: usize,
+) -> RecordingStats {
+    let mut stats = RecordingStats {
+        id: id.to_string(),
+        duration_ms,
+        scene_count: scene_count as u32,
+        click_count: 0,
+        key_count: 0,
+        scroll_count: 0,
+        windows: Vec::new(),
+    };
+    for event in events {
+        match event {
+            RecordingEvent::Click { .. } => stats.click_count += 1,
+            RecordingEvent::Key { .. } => stats.key_count += 1,
+            RecordingEvent::Scroll { .. } => stats.scroll_count += 1,
+            RecordingEvent::WindowFocus { title, .. } => {
+                let title = title.trim();
+                if !title.is_empty() && !stats.windows.iter().any(|w| w == title) {
+                    stats.windows.push(title.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    stats
+}
+
+/// min/max ratio of two counts; two zeros are identical.
+fn ratio(a: f64, b: f64) -> f64 {
+    if a == 0.0 && b == 0.0 {
+        1.0
+    } else {
+        a.min(b) / a.max(b)
+    }
+}
+
+/// Compare two recordings. The similarity score is a weighted mean of the
+/// duration/scene/click/key count ratios and the Jaccard index of the
+/// windows used.
+pub fn compare(a: RecordingStats, b: RecordingStats) -> RecordingComparison {
+    let common_windows: Vec<String> = a
+        .windows
+        .iter()
+        .filter(|w| b.windows.contains(w))
+        .cloned()
+        .collect();
+    let union = a.windows.len() + b.windows.len() - common_windows.len();
+    let windows_similarity = if union == 0 {
+        1.0
+    } else {
+        common_windows.len() as f64 / union as f64
+    };
+
+    let similarity = W_DURATION * ratio(a.duration_ms as f64, b.duration_ms as f64)
+        + W_SCENES * ratio(a.scene_count as f64, b.scene_count as f64)
+        + W_CLICKS * ratio(a.click_count as f64, b.click_count as f64)
+        + W_KEYS * ratio(a.key_count as f64, b.key_count as f64)
+        + W_WINDOWS * windows_similarity;
+
+    RecordingComparison {
+        duration_delta_ms: b.duration_ms as i64 - a.duration_ms as i64,
+        scene_count_delta: b.scene_count as i32 - a.scene_count as i32,
+        click_count_delta: b.click_count as i32 - a.click_count as i32,
+        common_windows,
+        similarity,
+        a,
+        b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(t: u64) -> RecordingEvent {
+        RecordingEvent::Click { t, btn: "left".to_string(), x: 0.0, y: 0.0 }
+    }
+
+    fn window(t: u64, title: &str) -> RecordingEvent {
+        RecordingEvent::WindowFocus { t, title: title.to_string(), rect: [0.0, 0.0, 100.0, 100.0], process: String::new() }
+    }
+
+    #[test]
+    fn test_collect_stats_counts_and_dedups_windows() {
+        let events = vec![window(0, "Editor"), click(10), click(20), window(30, "Browser"), window(40, "Editor")];
+        let stats = collect_stats("a", 5000, &events, 3);
+        assert_eq!(stats.click_count, 2);
+        assert_eq!(stats.scene_count, 3);
+        assert_eq!(stats.windows, vec!["Editor".to_string(), "Browser".to_string()]);
+    }
+
+    #[test]
+    fn test_identical_recordings_score_one() {
+        let events = vec![window(0, "Editor"), click(10)];
+        let a = collect_stats("a", 5000, &events, 2);
+        let b = collect_stats("b", 5000, &events, 2);
+        let cmp = compare(a, b);
+        assert!((cmp.similarity - 1.0).abs() < 1e-9);
+        assert_eq!(cmp.duration_delta_ms, 0);
+        assert_eq!(cmp.common_windows, vec!["Editor".to_string()]);
+    }
+
+    #[test]
+    fn test_different_recordings_score_lower() {
+        let a = collect_stats("a", 5000, &[window(0, "Editor"), click(10)], 2);
+        let b = collect_stats("b", 10000, &[window(0, "Browser"), click(10), click(20)], 4);
+        let cmp = compare(a, b);
+        assert!(cmp.similarity < 0.6, "similarity {}", cmp.similarity);
+        assert_eq!(cmp.duration_delta_ms, 5000);
+        assert_eq!(cmp.click_count_delta, 1);
+        assert!(cmp.common_windows.is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+//! Redaction of sensitive screen regions.
+//!
+//! Regions are defined per recording (`redactions.json` next to
+//! `meta.json`) in recorded frame pixels, optionally limited to a time range.
+//! The compositor burns them into the raw frame before crop/zoom, so nothing
+//! inside a region can leak through zooming in.
+//!
+//! With `EffectsSettings::auto_redact_inputs`, password fields found in the
+//! UI focus events are redacted automatically while they have focus.
+
+use crate::config::RecordingEvent;
+use crate::engine::frame_differ::CaptureMapping;
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+
+/// Pixelation cell size in frame pixels
+const PIXELATE_BLOCK: u32 = 16;
+
+/// Gaussian blur sigma in frame pixels
+const BLUR_SIGMA: f32 = 12.0;
+
+/// Margin around an auto-redacted input field (screen pixels), so the focus
+/// ring and caret at the edge are covered too
+const INPUT_MARGIN: f64 = 4.0;
+
+/// Edit controls whose name or automation id contains one of these are
+/// treated as secret even without the UIA password flag (lower case)
+const SECRET_HINTS: &[&str] = &["password", "passwd", "passcode", "secret", "token", "パスワード", "暗証"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum RedactionStyle {
+    #[default]
+    Pixelate,
+    Blur,
+}
+
+/// A redacted rectangle. Without `start_ms` / `end_ms` it covers the whole
+/// recording from/to that end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Redaction {
+    /// [left, top, right, bottom] in recorded frame pixels
+    pub rect: [f64; 4],
+    #[serde(default)]
+    pub start_ms: Option<u64>,
+    #[serde(default)]
+    pub end_ms: Option<u64>,
+    #[serde(default)]
+    pub style: RedactionStyle,
+}
+
+impl Redaction {
+    pub fn is_active(&self, time_ms: u64) -> bool {
+        self.start_ms.is_none_or(|s| time_ms >= s) && self.end_ms.is_none_or(|e| time_ms < e)
+    }
+
+    /// Integer pixel bounds clipped to the frame, or None if empty.
+    fn pixel_bounds(&self, width: u32, height: u32) -> Option<(u32, u32, u32, u32)> {
+        let [left, top, right, bottom] = self.rect;
+        let x0 = left.floor().clamp(0.0, width as f64) as u32;
+        let y0 = top.floor().clamp(0.0, height as f64) as u32;
+        let x1 = right.ceil().clamp(0.0, width as f64) as u32;
+        let y1 = bottom.ceil().clamp(0.0, height as f64) as u32;
+        (x1 > x0 && y1 > y0).then_some((x0, y0, x1, y1))
+    }
+}
+
+fn is_secret_input(control: &str, name: &str, automation_id: &str, password: bool) -> bool {
+    if password {
+        return true;
+    }
+    let name = name.to_lowercase();
+    let automation_id = automation_id.to_lowercase();
+    control == "Edit" && SECRET_HINTS.iter().any(|h| name.contains(h) || automation_id.contains(h))
+}
+
+/// Blur redactions for password (and password-like) input fields, active
+/// from the moment the field gains focus until focus moves elsewhere.
+/// Event rects are mapped into frame pixels with `mapping`.
+pub fn detect_input_redactions(events: &[RecordingEvent], mapping: &CaptureMapping) -> Vec<Redaction> {
+    let mut redactions: Vec<Redaction> = Vec::new();
+    let mut open: Option<usize> = None;
+    for event in events {
+        let (t, rect, secret) = match event {
+            RecordingEvent::UiFocus { t, control, name, rect, automation_id, password } => {
+                (*t, rect, is_secret_input(control, name, automation_id, *password))
+            }
+            RecordingEvent::Focus { t, el, name, rect } => (*t, rect, is_secret_input(el, name, "", false)),
+            RecordingEvent::WindowFocus { t, rect, .. } => (*t, rect, false),
+            _ => continue,
+        };
+        // Any focus change ends the current redaction
+        if let Some(i) = open.take() {
+            redactions[i].end_ms = Some(t);
+        }
+        if !secret || rect[2] <= rect[0] || rect[3] <= rect[1] {
+            continue;
+        }
+        let (left, top) = mapping.screen_to_frame(rect[0] - INPUT_MARGIN, rect[1] - INPUT_MARGIN);
+        let (right, bottom) = mapping.screen_to_frame(rect[2] + INPUT_MARGIN, rect[3] + INPUT_MARGIN);
+        open = Some(redactions.len());
+        redactions.push(Redaction {
+            rect: [left, top, right, bottom],
+            start_ms: Some(t),
+            end_ms: None,
+            style: RedactionStyle::Blur,
+        });
+    }
+    redactions
+}
+
+/// Apply every redaction active at `time_ms` to `frame`.
+pub fn apply_redactions(frame: &mut RgbaImage, redactions: &[Redaction], time_ms: u64) {
+    let (width, height) = frame.dimensions();
+    for redaction in redactions.iter().filter(|r| r.is_active(time_ms)) {
+        let Some((x0, y0, x1, y1)) = redaction.pixel_bounds(width, height) else {
+            continue;
+        };
+        match redaction.style {
+            RedactionStyle::Pixelate => pixelate(frame, x0, y0, x1, y1),
+            RedactionStyle::Blur => blur(frame, x0, y0, x1, y1),
+        }
+    }
+}
+
+/// Replace each block with its average color.
+fn pixelate(frame: &mut RgbaImage, x0: u32, y0: u32, x1: u32, y1: u32) {
+    for by in (y0..y1).step_by(PIXELATE_BLOCK as usize) {
+        for bx in (x0..x1).step_by(PIXELATE_BLOCK as usize) {
+            let bx1 = (bx + PIXELATE_BLOCK).min(x1);
+            let by1 = (by + PIXELATE_BLOCK).min(y1);
+            let mut sum = [0u64; 4];
+            for y in by..by1 {
+                for x in bx..bx1 {
+                    let p = frame.get_pixel(x, y);
+                    for c in 0..4 {
+                        sum[c] += p[c] as u64;
+                    }
+                }
+            }
+            let n = ((bx1 - bx) * (by1 - by)) as u64;
+            let avg = image::Rgba(sum.map(|s| (s / n) as u8));
+            for y in by..by1 {
+                for x in bx..bx1 {
+                    frame.put_pixel(x, y, avg);
+                }
+            }
+        }
+    }
+}
+
+/// Gaussian blur of the region. The blur only samples pixels inside the
+/// region, so content outside cannot smear in (or out).
+fn blur(frame: &mut RgbaImage, x0: u32, y0: u32, x1: u32, y1: u32) {
+    let region = image::imageops::crop_imm(frame, x0, y0, x1 - x0, y1 - y0).to_image();
+    let blurred = image::imageops::blur(&region, BLUR_SIGMA);
+    image::imageops::replace(frame, &blurred, x0 as i64, y0 as i64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard(size: u32) -> RgbaImage {
+        RgbaImage::from_fn(size, size, |x, y| {
+            if (x + y) % 2 == 0 {
+                image::Rgba([255, 255, 255, 255])
+            } else {
+                image::Rgba([0, 0, 0, 255])
+            }
+        })
+    }
+
+    fn region(style: RedactionStyle, start_ms: Option<u64>, end_ms: Option<u64>) -> Redaction {
+        Redaction { rect: [0.0, 0.0, 16.0, 16.0], start_ms, end_ms, style }
+    }
+
+    #[test]
+    fn test_pixelate_flattens_block_and_leaves_outside() {
+        let mut frame = checkerboard(32);
+        apply_redactions(&mut frame, &[region(RedactionStyle::Pixelate, None, None)], 0);
+        let first = *frame.get_pixel(0, 0);
+        assert!((0..16).all(|y| (0..16).all(|x| *frame.get_pixel(x, y) == first)));
+        assert_eq!(first[0], 127);
+        assert_eq!(*frame.get_pixel(20, 20), checkerboard(32)[(20, 20)]);
+    }
+
+    #[test]
+    fn test_blur_smooths_region() {
+        let mut frame = checkerboard(32);
+        apply_redactions(&mut frame, &[region(RedactionStyle::Blur, None, None)], 0);
+        let v = frame.get_pixel(8, 8)[0];
+        assert!((100..156).contains(&v), "blurred value {}", v);
+    }
+
+    fn ui_focus(t: u64, control: &str, name: &str, password: bool) -> RecordingEvent {
+        RecordingEvent::UiFocus {
+            t,
+            control: control.to_string(),
+            name: name.to_string(),
+            rect: [100.0, 100.0, 300.0, 130.0],
+            automation_id: String::new(),
+            password,
+        }
+    }
+
+    #[test]
+    fn test_password_fields_redacted_while_focused() {
+        let events = vec![
+            ui_focus(0, "Edit", "User name", false),
+            ui_focus(1000, "Edit", "", true),
+            ui_focus(3000, "Button", "Sign in", false),
+            ui_focus(5000, "Edit", "Password", false),
+        ];
+        let mapping = CaptureMapping { origin_x: 100.0, origin_y: 0.0, scale: 2.0 };
+        let redactions = detect_input_redactions(&events, &mapping);
+        assert_eq!(redactions.len(), 2);
+        assert_eq!((redactions[0].start_ms, redactions[0].end_ms), (Some(1000), Some(3000)));
+        assert_eq!(redactions[0].rect, [-8.0, 192.0, 408.0, 268.0]);
+        assert_eq!(redactions[0].style, RedactionStyle::Blur);
+        assert_eq!((redactions[1].start_ms, redactions[1].end_ms), (Some(5000), None));
+    }
+
+    #[test]
+    fn test_time_range_and_clipping() {
+        let timed = region(RedactionStyle::Pixelate, Some(1000), Some(2000));
+        assert!(!timed.is_active(999));
+        assert!(timed.is_active(1000));
+        assert!(!timed.is_active(2000));
+
+        let mut frame = checkerboard(8);
+        let outside = Redaction { rect: [20.0, 20.0, 40.0, 40.0], ..timed.clone() };
+        apply_redactions(&mut frame, &[outside], 1500);
+        assert_eq!(frame, checkerboard(8));
+    }
+}
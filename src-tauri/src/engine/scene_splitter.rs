@@ -48,6 +48,10 @@ pub struct Scene {
     /// UI 情報が取れないアプリ（ゲーム等）では None → 従来の bbox ベースにフォールバック。
     #[serde(default)]
     pub ui_rect: Option<Rect>,
+    /// Timeline UI で bbox が直接指定されたシーン（`SceneEditOp::SetBbox`）。
+    /// true の場合、frame diff による bbox 拡張や UI 矩形の付与で上書きしない。
+    #[serde(default)]
+    pub manual_bbox: bool,
 }
 
 /// A manual scene editing operation from the Timeline UI.
@@ -58,6 +62,15 @@ pub enum SceneEditOp {
     Merge { scene_id: u32 },
     /// Split scene `scene_id` at the given time.
     Split { scene_id: u32, split_time_ms: u64 },
+    /// Replace the framing rectangle of scene `scene_id` (screen coords).
+    /// Zoom level and center are recomputed from the rectangle.
+    SetBbox { scene_id: u32, rect: Rect },
+    /// Delete scene `scene_id`. The previous scene is extended over the
+    /// deleted range so the camera stays on its framing.
+    Delete { scene_id: u32 },
+    /// Add a scene over an arbitrary time range. Overlapping scenes are
+    /// trimmed; the framing comes from `compute_activity_center`.
+    Add { start_ms: u64, end_ms: u64 },
 }
 
 /// Activity point for scene construction.
@@ -184,6 +197,7 @@ pub(crate) fn make_scene(
         zoom_level,
         event_count: points.len(),
         ui_rect: None,
+        manual_bbox: false,
     }
 }
 
@@ -278,6 +292,19 @@ pub fn split_into_scenes(
     screen_w: f64,
     screen_h: f64,
     max_zoom: f64,
+) -> Vec<Scene> {
+    split_into_scenes_with_breaks(events, &[], screen_w, screen_h, max_zoom)
+}
+
+/// [`split_into_scenes`], also starting a new scene at each time in
+/// `breaks` (ascending), e.g. where the recording was paused: activity on
+/// either side is unrelated even when it is close in time.
+pub fn split_into_scenes_with_breaks(
+    events: &[RecordingEvent],
+    breaks: &[u64],
+    screen_w: f64,
+    screen_h: f64,
+    max_zoom: f64,
 ) -> Vec<Scene> {
     let points = extract_activity_points(events);
     if points.is_empty() {
@@ -290,8 +317,11 @@ pub fn split_into_scenes(
 
     for i in 1..points.len() {
         let time_gap = points[i].time_ms.saturating_sub(points[i - 1].time_ms);
+        let crosses_break = breaks
+            .iter()
+            .any(|&b| points[i - 1].time_ms < b && b <= points[i].time_ms);
 
-        if time_gap >= SCENE_GAP_MS {
+        if time_gap >= SCENE_GAP_MS || crosses_break {
             raw_groups.push(current_group);
             current_group = Vec::new();
         }
@@ -381,8 +411,13 @@ pub fn expand_scenes_with_change_regions(
     screen_w: f64,
     screen_h: f64,
     max_zoom: f64,
+    mapping: &crate::engine::frame_differ::CaptureMapping,
 ) {
+    let bounds = mapping.screen_bounds(screen_w, screen_h);
     for scene in scenes.iter_mut() {
+        if scene.manual_bbox {
+            continue;
+        }
         let relevant: Vec<&crate::engine::frame_differ::ChangeRegion> = change_regions
             .iter()
             .filter(|cr| cr.time_ms >= scene.start_ms && cr.time_ms <= scene.end_ms)
@@ -395,8 +430,7 @@ pub fn expand_scenes_with_change_regions(
         let expanded = crate::engine::frame_differ::expand_bbox_with_changes(
             &scene.bbox,
             &relevant,
-            screen_w,
-            screen_h,
+            &bounds,
         );
 
         scene.bbox = expanded;
@@ -459,6 +493,7 @@ pub fn apply_scene_edits(
                                 event_count: a.event_count + b.event_count,
                                 bbox,
                                 ui_rect: None,
+                                manual_bbox: false,
                             };
                             result.splice(i..=i + 1, std::iter::once(merged));
                         } else {
@@ -497,6 +532,28 @@ pub fn apply_scene_edits(
                     }
                 }
             }
+            SceneEditOp::SetBbox { scene_id, rect } => {
+                if let Some(scene) = result.iter_mut().find(|s| s.id == *scene_id) {
+                    set_scene_bbox(scene, rect, screen_w, screen_h, max_zoom);
+                }
+            }
+            SceneEditOp::Delete { scene_id } => {
+                if let Some(i) = result.iter().position(|s| s.id == *scene_id) {
+                    let removed = result.remove(i);
+                    if i > 0 {
+                        let prev = &mut result[i - 1];
+                        prev.end_ms = prev.end_ms.max(removed.end_ms);
+                    }
+                }
+            }
+            SceneEditOp::Add { start_ms, end_ms } => {
+                if end_ms > start_ms {
+                    let added = make_scene_for_range(
+                        events, *start_ms, *end_ms, screen_w, screen_h, max_zoom,
+                    );
+                    insert_scene(&mut result, added);
+                }
+            }
         }
         // Reassign IDs after each edit
         for (j, scene) in result.iter_mut().enumerate() {
@@ -507,6 +564,95 @@ pub fn apply_scene_edits(
     result
 }
 
+/// Apply a user-specified framing rectangle to a scene.
+///
+/// The rectangle is clamped to the screen; zoom level and center are
+/// recomputed from it and the UI rect is dropped so zoom_planner frames
+/// exactly what the user chose.
+fn set_scene_bbox(scene: &mut Scene, rect: &Rect, screen_w: f64, screen_h: f64, max_zoom: f64) {
+    let x0 = rect.x.clamp(0.0, screen_w);
+    let y0 = rect.y.clamp(0.0, screen_h);
+    let x1 = (rect.x + rect.width).clamp(x0, screen_w);
+    let y1 = (rect.y + rect.height).clamp(y0, screen_h);
+    let bbox = Rect {
+        x: x0,
+        y: y0,
+        width: (x1 - x0).max(1.0),
+        height: (y1 - y0).max(1.0),
+    };
+    scene.center_x = bbox.center_x();
+    scene.center_y = bbox.center_y();
+    scene.zoom_level = calc_scene_zoom(&bbox, screen_w, screen_h, max_zoom);
+    scene.bbox = bbox;
+    scene.ui_rect = None;
+    scene.manual_bbox = true;
+}
+
+/// Build a scene covering `[start_ms, end_ms]` framed on the activity in
+/// that range. The bbox is the viewport implied by the activity center/zoom.
+fn make_scene_for_range(
+    events: &[RecordingEvent],
+    start_ms: u64,
+    end_ms: u64,
+    screen_w: f64,
+    screen_h: f64,
+    max_zoom: f64,
+) -> Scene {
+    let (cx, cy, zoom) =
+        compute_activity_center(events, start_ms, end_ms, screen_w, screen_h, max_zoom);
+    let width = screen_w / zoom;
+    let height = screen_h / zoom;
+    let event_count = extract_activity_points(events)
+        .iter()
+        .filter(|p| p.time_ms >= start_ms && p.time_ms <= end_ms)
+        .count();
+    Scene {
+        id: 0,
+        start_ms,
+        end_ms,
+        bbox: Rect {
+            x: (cx - width / 2.0).clamp(0.0, (screen_w - width).max(0.0)),
+            y: (cy - height / 2.0).clamp(0.0, (screen_h - height).max(0.0)),
+            width,
+            height,
+        },
+        center_x: cx,
+        center_y: cy,
+        zoom_level: zoom,
+        event_count,
+        ui_rect: None,
+        manual_bbox: false,
+    }
+}
+
+/// Insert `scene` in time order, trimming or removing scenes it overlaps.
+/// A scene that fully contains the new one is split around it.
+fn insert_scene(scenes: &mut Vec<Scene>, scene: Scene) {
+    let mut out: Vec<Scene> = Vec::with_capacity(scenes.len() + 2);
+    for existing in scenes.drain(..) {
+        if existing.end_ms < scene.start_ms || existing.start_ms > scene.end_ms {
+            out.push(existing);
+            continue;
+        }
+        if existing.start_ms < scene.start_ms {
+            let mut left = existing.clone();
+            left.end_ms = scene.start_ms;
+            out.push(left);
+        }
+        if existing.end_ms > scene.end_ms {
+            let mut right = existing;
+            right.start_ms = scene.end_ms;
+            out.push(right);
+        }
+    }
+    let pos = out
+        .iter()
+        .position(|s| s.start_ms >= scene.start_ms)
+        .unwrap_or(out.len());
+    out.insert(pos, scene);
+    *scenes = out;
+}
+
 /// Compute activity center and zoom for a given time range.
 /// Used by frontend when merging/adding zoom segments to get correct
 /// center coordinates that cover all user activity in the range.
@@ -560,6 +706,7 @@ impl Scene {
             zoom_level,
             event_count: 3,
             ui_rect: None,
+            manual_bbox: false,
         }
     }
 }
@@ -567,6 +714,7 @@ impl Scene {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::frame_differ::CaptureMapping;
 
     fn click(t: u64, x: f64, y: f64) -> RecordingEvent {
         RecordingEvent::Click {
@@ -624,6 +772,21 @@ mod tests {
         assert_eq!(scenes.len(), 2);
     }
 
+    #[test]
+    fn test_pause_break_splits_scenes() {
+        // Paused at 600ms: the timestamps continue without a gap
+        let events = vec![
+            click(0, 500.0, 300.0),
+            click(500, 520.0, 310.0),
+            click(700, 1500.0, 900.0),
+            click(1000, 1510.0, 910.0),
+        ];
+        assert_eq!(split_into_scenes(&events, 1920.0, 1080.0, 3.0).len(), 1);
+        let scenes = split_into_scenes_with_breaks(&events, &[600], 1920.0, 1080.0, 3.0);
+        assert_eq!(scenes.len(), 2);
+        assert_eq!(scenes[1].start_ms, 700);
+    }
+
     #[test]
     fn test_key_events_use_click_position() {
         let events = vec![click(0, 500.0, 300.0), key(200), key(400), key(600)];
@@ -731,7 +894,7 @@ mod tests {
     fn test_expand_with_no_regions() {
         let mut scenes = vec![Scene::for_test(0, 0, 2000, 500.0, 300.0, 2.5)];
         let original_width = scenes[0].bbox.width;
-        expand_scenes_with_change_regions(&mut scenes, &[], 1920.0, 1080.0, 3.0);
+        expand_scenes_with_change_regions(&mut scenes, &[], 1920.0, 1080.0, 3.0, &CaptureMapping::IDENTITY);
         assert_eq!(scenes[0].bbox.width, original_width);
     }
 
@@ -749,7 +912,7 @@ mod tests {
             },
             changed_pixel_count: 5000,
         }];
-        expand_scenes_with_change_regions(&mut scenes, &regions, 1920.0, 1080.0, 3.0);
+        expand_scenes_with_change_regions(&mut scenes, &regions, 1920.0, 1080.0, 3.0, &CaptureMapping::IDENTITY);
         assert!(
             scenes[0].bbox.width > original_width,
             "BBox should be wider after expansion"
@@ -774,7 +937,7 @@ mod tests {
             },
             changed_pixel_count: 100000,
         }];
-        expand_scenes_with_change_regions(&mut scenes, &regions, 1920.0, 1080.0, 3.0);
+        expand_scenes_with_change_regions(&mut scenes, &regions, 1920.0, 1080.0, 3.0, &CaptureMapping::IDENTITY);
         assert_eq!(scenes[0].zoom_level, original_zoom);
     }
 
@@ -832,7 +995,7 @@ mod tests {
             },
             changed_pixel_count: 50000,
         }];
-        expand_scenes_with_change_regions(&mut scenes, &regions, 1920.0, 1080.0, 3.0);
+        expand_scenes_with_change_regions(&mut scenes, &regions, 1920.0, 1080.0, 3.0, &CaptureMapping::IDENTITY);
         assert!(scenes[0].bbox.x >= 0.0);
         assert!(scenes[0].bbox.y >= 0.0);
         assert!(scenes[0].bbox.x + scenes[0].bbox.width <= 1920.0);
@@ -912,4 +1075,91 @@ mod tests {
         let result = apply_scene_edits(&scenes, &edits, &events, 1920.0, 1080.0, 3.0);
         assert_eq!(result.len(), scenes.len(), "Invalid merge should be no-op");
     }
+
+    #[test]
+    fn test_set_bbox_recomputes_zoom_and_center() {
+        let events = vec![click(0, 200.0, 200.0), click(500, 210.0, 210.0)];
+        let scenes = split_into_scenes(&events, 1920.0, 1080.0, 3.0);
+        let edits = vec![SceneEditOp::SetBbox {
+            scene_id: 0,
+            rect: Rect { x: 100.0, y: 100.0, width: 960.0, height: 540.0 },
+        }];
+        let result = apply_scene_edits(&scenes, &edits, &events, 1920.0, 1080.0, 3.0);
+        assert_eq!(result.len(), 1);
+        assert!((result[0].zoom_level - 2.0).abs() < 0.01);
+        assert!((result[0].center_x - 580.0).abs() < 0.01);
+        assert!((result[0].center_y - 370.0).abs() < 0.01);
+        assert!(result[0].manual_bbox);
+    }
+
+    #[test]
+    fn test_set_bbox_clamped_and_not_expanded() {
+        let events = vec![click(0, 200.0, 200.0)];
+        let scenes = split_into_scenes(&events, 1920.0, 1080.0, 3.0);
+        let edits = vec![SceneEditOp::SetBbox {
+            scene_id: 0,
+            rect: Rect { x: -100.0, y: -100.0, width: 600.0, height: 400.0 },
+        }];
+        let mut result = apply_scene_edits(&scenes, &edits, &events, 1920.0, 1080.0, 3.0);
+        assert_eq!(result[0].bbox.x, 0.0);
+        assert_eq!(result[0].bbox.width, 500.0);
+
+        // Frame diff expansion must not override a manual bbox
+        let region = crate::engine::frame_differ::ChangeRegion {
+            time_ms: 0,
+            bbox: Rect { x: 0.0, y: 0.0, width: 1800.0, height: 1000.0 },
+            changed_pixel_count: 1000,
+        };
+        expand_scenes_with_change_regions(&mut result, &[region], 1920.0, 1080.0, 3.0, &CaptureMapping::IDENTITY);
+        assert_eq!(result[0].bbox.width, 500.0);
+    }
+
+    #[test]
+    fn test_delete_extends_previous_scene() {
+        let events = vec![
+            click(0, 200.0, 200.0),
+            click(500, 210.0, 210.0),
+            click(3000, 800.0, 600.0),
+            click(3500, 810.0, 610.0),
+        ];
+        let scenes = split_into_scenes(&events, 1920.0, 1080.0, 3.0);
+        assert_eq!(scenes.len(), 2);
+        let prev_center = scenes[0].center_x;
+
+        let edits = vec![SceneEditOp::Delete { scene_id: 1 }];
+        let result = apply_scene_edits(&scenes, &edits, &events, 1920.0, 1080.0, 3.0);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].end_ms, scenes[1].end_ms, "previous framing covers the deleted range");
+        assert_eq!(result[0].center_x, prev_center);
+    }
+
+    #[test]
+    fn test_add_scene_in_gap() {
+        let events = vec![
+            click(0, 200.0, 200.0),
+            click(5000, 1500.0, 900.0),
+            click(10000, 800.0, 600.0),
+        ];
+        let scenes = split_into_scenes(&events, 1920.0, 1080.0, 3.0);
+        let before = scenes.len();
+
+        let edits = vec![SceneEditOp::Add { start_ms: 4000, end_ms: 6000 }];
+        let result = apply_scene_edits(&scenes, &edits, &events, 1920.0, 1080.0, 3.0);
+        let added = result.iter().find(|s| s.start_ms == 4000).expect("added scene");
+        assert_eq!(added.end_ms, 6000);
+        assert!((added.center_x - 1500.0).abs() < 200.0);
+        assert!(result.len() >= before);
+        assert!(result.windows(2).all(|w| w[0].start_ms <= w[1].start_ms));
+        assert!(result.iter().enumerate().all(|(i, s)| s.id == i as u32));
+    }
+
+    #[test]
+    fn test_add_scene_splits_containing_scene() {
+        let mut scenes = vec![Scene::for_test(0, 0, 10000, 500.0, 500.0, 2.0)];
+        insert_scene(&mut scenes, Scene::for_test(9, 4000, 6000, 900.0, 500.0, 2.0));
+        assert_eq!(scenes.len(), 3);
+        assert_eq!((scenes[0].start_ms, scenes[0].end_ms), (0, 4000));
+        assert_eq!((scenes[1].start_ms, scenes[1].end_ms), (4000, 6000));
+        assert_eq!((scenes[2].start_ms, scenes[2].end_ms), (6000, 10000));
+    }
 }
@@ -0,0 +1,109 @@
+//! Per-scene export style: a different background, border radius or set of
+//! effects for part of a recording (e.g. no key badges while a password is
+//! typed), on top of the style from settings.
+//!
+//! Stored per recording in `scene_styles.json` next to `meta.json`. Each
+//! override covers the time range of the scene it was made for (recording
+//! timeline, re-timed with the export's cuts), so it stays put when the
+//! scenes are re-detected. Fields left `None` keep the settings' value.
+
+use crate::config::BackgroundConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneStyleOverride {
+    /// The scene the override was made for (shown in the Timeline)
+    pub scene_id: u32,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    #[serde(default)]
+    pub background: Option<BackgroundConfig>,
+    #[serde(default)]
+    pub border_radius: Option<u32>,
+    #[serde(default)]
+    pub click_ring_enabled: Option<bool>,
+    #[serde(default)]
+    pub key_badge_enabled: Option<bool>,
+}
+
+/// Effects that can be switched per scene.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SceneEffect {
+    ClickRing,
+    KeyBadge,
+}
+
+impl SceneStyleOverride {
+    pub fn contains(&self, time_ms: u64) -> bool {
+        time_ms >= self.start_ms && time_ms < self.end_ms
+    }
+
+    fn effect(&self, effect: SceneEffect) -> Option<bool> {
+        match effect {
+            SceneEffect::ClickRing => self.click_ring_enabled,
+            SceneEffect::KeyBadge => self.key_badge_enabled,
+        }
+    }
+}
+
+/// The override in effect at `time_ms`; the last one wins where two overlap.
+pub fn override_at(overrides: &[SceneStyleOverride], time_ms: u64) -> Option<&SceneStyleOverride> {
+    overrides.iter().rev().find(|o| o.contains(time_ms))
+}
+
+/// Whether `effect` shows anywhere: on by default, or switched on for some
+/// scene. Effects that are used nowhere need not be extracted at all.
+pub fn effect_used(overrides: &[SceneStyleOverride], effect: SceneEffect, default: bool) -> bool {
+    default || overrides.iter().any(|o| o.effect(effect) == Some(true))
+}
+
+/// Whether `effect` shows at `time_ms` (`default` outside overrides and
+/// where the override leaves it unset).
+pub fn effect_enabled_at(overrides: &[SceneStyleOverride], effect: SceneEffect, default: bool, time_ms: u64) -> bool {
+    overrides
+        .iter()
+        .rev()
+        .filter(|o| o.contains(time_ms))
+        .find_map(|o| o.effect(effect))
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(start_ms: u64, end_ms: u64) -> SceneStyleOverride {
+        SceneStyleOverride {
+            scene_id: 0,
+            start_ms,
+            end_ms,
+            background: None,
+            border_radius: None,
+            click_ring_enabled: None,
+            key_badge_enabled: None,
+        }
+    }
+
+    #[test]
+    fn test_effect_toggles_apply_inside_their_scene() {
+        let overrides = vec![
+            SceneStyleOverride { key_badge_enabled: Some(false), ..scene(1000, 2000) },
+            SceneStyleOverride { click_ring_enabled: Some(true), border_radius: Some(0), ..scene(3000, 4000) },
+        ];
+
+        assert!(effect_enabled_at(&overrides, SceneEffect::KeyBadge, true, 500));
+        assert!(!effect_enabled_at(&overrides, SceneEffect::KeyBadge, true, 1500));
+        assert!(effect_enabled_at(&overrides, SceneEffect::KeyBadge, true, 2000));
+        // Unset in the scene: the default applies
+        assert!(effect_enabled_at(&overrides, SceneEffect::ClickRing, true, 1500));
+
+        // Switched on for one scene while off in settings
+        assert!(effect_used(&overrides, SceneEffect::ClickRing, false));
+        assert!(!effect_enabled_at(&overrides, SceneEffect::ClickRing, false, 1500));
+        assert!(effect_enabled_at(&overrides, SceneEffect::ClickRing, false, 3500));
+        assert!(!effect_used(&overrides, SceneEffect::KeyBadge, false));
+
+        assert_eq!(override_at(&overrides, 3500).and_then(|o| o.border_radius), Some(0));
+        assert!(override_at(&overrides, 2500).is_none());
+    }
+}
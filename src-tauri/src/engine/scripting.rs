@@ -0,0 +1,196 @@
+//! Export scripts (`scripting` feature).
+//!
+//! A user [Rhai](https://rhai.rs) script (`EffectsSettings::script_path`) is
+//! loaded once per export and may define either or both hooks:
+//!
+//! ```rhai
+//! // Custom camera: return the zoom keyframes to use (recording timeline).
+//! // `scenes` is empty when exporting keyframes edited in the timeline.
+//! fn camera(scenes, keyframes) {
+//!     for i in 0..keyframes.len() {
+//!         if keyframes[i].zoom_level > 1.8 { keyframes[i].zoom_level = 1.8; }
+//!     }
+//!     keyframes
+//! }
+//!
+//! // Overlays drawn on every frame, in canvas pixels (output timeline).
+//! fn overlay(t, width, height) {
+//!     [
+//!         #{ kind: "rect", x: 0, y: 0, w: width, h: 40, color: [0, 0, 0, 160] },
+//!         #{ kind: "text", x: 16, y: 8, text: "ACME", size: 24, color: [255, 255, 255, 255] },
+//!     ]
+//! }
+//! ```
+//!
+//! Scenes and keyframes are object maps with the same fields as their JSON
+//! form. Each hook call is limited to [`MAX_OPERATIONS`] so a runaway loop
+//! fails the export instead of hanging it.
+
+use crate::engine::scene_splitter::Scene;
+use crate::engine::zoom_planner::ZoomKeyframe;
+use anyhow::{anyhow, Context, Result};
+use rhai::{Dynamic, Engine, Scope, AST};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// Operation budget per hook call.
+const MAX_OPERATIONS: u64 = 5_000_000;
+
+/// A drawing primitive returned by the `overlay` hook.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum OverlayPrimitive {
+    Rect {
+        x: f64,
+        y: f64,
+        w: f64,
+        h: f64,
+        color: [u8; 4],
+    },
+    Text {
+        x: f64,
+        y: f64,
+        text: String,
+        #[serde(default = "default_text_size")]
+        size: f32,
+        #[serde(default = "default_text_color")]
+        color: [u8; 4],
+    },
+}
+
+fn default_text_size() -> f32 { 24.0 }
+fn default_text_color() -> [u8; 4] { [255, 255, 255, 255] }
+
+pub struct ExportScript {
+    engine: Engine,
+    ast: AST,
+    has_camera: bool,
+    has_overlay: bool,
+    /// Set after the first `overlay` error so it is logged once
+    overlay_failed: bool,
+}
+
+impl ExportScript {
+    pub fn load(path: &std::path::Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read export script {}", path.display()))?;
+        Self::compile(&source).with_context(|| format!("Export script {}", path.display()))
+    }
+
+    pub fn compile(source: &str) -> Result<Self> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let ast = engine.compile(source).map_err(|e| anyhow!("{}", e))?;
+        let has_fn = |name: &str, arity: usize| {
+            ast.iter_functions().any(|f| f.name == name && f.params.len() == arity)
+        };
+        let has_camera = has_fn("camera", 2);
+        let has_overlay = has_fn("overlay", 3);
+        if !has_camera && !has_overlay {
+            log::warn!("Export script defines neither camera(scenes, keyframes) nor overlay(t, width, height)");
+        }
+        Ok(Self { engine, ast, has_camera, has_overlay, overlay_failed: false })
+    }
+
+    /// Run the `camera` hook; keyframes pass through unchanged without one.
+    pub fn camera(&self, scenes: &[Scene], keyframes: Vec<ZoomKeyframe>) -> Result<Vec<ZoomKeyframe>> {
+        if !self.has_camera {
+            return Ok(keyframes);
+        }
+        let args = (rhai::serde::to_dynamic(scenes)?, rhai::serde::to_dynamic(&keyframes)?);
+        let result: Dynamic = self
+            .engine
+            .call_fn(&mut Scope::new(), &self.ast, "camera", args)
+            .map_err(|e| anyhow!("Export script camera(): {}", e))?;
+        let mut keyframes: Vec<ZoomKeyframe> =
+            from_script(result).context("Export script camera() must return an array of keyframes")?;
+        keyframes.sort_by_key(|kf| kf.time_ms);
+        log::info!("Export script camera() returned {} keyframes", keyframes.len());
+        Ok(keyframes)
+    }
+
+    /// Run the `overlay` hook for one frame. Errors are logged once and
+    /// produce no overlays, so a script bug does not abort a long export.
+    pub fn overlays(&mut self, time_ms: u64, width: u32, height: u32) -> Vec<OverlayPrimitive> {
+        if !self.has_overlay || self.overlay_failed {
+            return Vec::new();
+        }
+        let args = (time_ms as i64, width as i64, height as i64);
+        let result = self
+            .engine
+            .call_fn::<Dynamic>(&mut Scope::new(), &self.ast, "overlay", args)
+            .map_err(|e| anyhow!("{}", e))
+            .and_then(from_script::<Vec<OverlayPrimitive>>);
+        match result {
+            Ok(primitives) => primitives,
+            Err(e) => {
+                log::warn!("Export script overlay() failed at {}ms, overlays disabled: {}", time_ms, e);
+                self.overlay_failed = true;
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Convert a script value through JSON, so integer literals are accepted
+/// where a float field is expected (`x: 10` as well as `x: 10.0`).
+fn from_script<T: DeserializeOwned>(value: Dynamic) -> Result<T> {
+    Ok(serde_json::from_value(serde_json::to_value(&value)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::zoom_planner::TransitionType;
+
+    fn kf(time_ms: u64, zoom_level: f64) -> ZoomKeyframe {
+        ZoomKeyframe {
+            time_ms,
+            target_x: 100.0,
+            target_y: 100.0,
+            zoom_level,
+            transition: TransitionType::Smooth,
+            spring_hint: None,
+            pinned: false,
+        }
+    }
+
+    #[test]
+    fn test_camera_hook_edits_keyframes() {
+        let script = ExportScript::compile(
+            "fn camera(scenes, keyframes) { for i in 0..keyframes.len() { keyframes[i].zoom_level = 1; } keyframes.reverse(); keyframes }",
+        )
+        .unwrap();
+        let out = script.camera(&[], vec![kf(0, 2.0), kf(1000, 2.5)]).unwrap();
+        assert_eq!(out.iter().map(|k| k.time_ms).collect::<Vec<_>>(), vec![0, 1000]);
+        assert!(out.iter().all(|k| k.zoom_level == 1.0));
+    }
+
+    #[test]
+    fn test_overlay_hook_returns_primitives() {
+        let mut script = ExportScript::compile(
+            r#"fn overlay(t, width, height) {
+                if t < 1000 { return []; }
+                [#{ kind: "rect", x: 0, y: 0, w: width, h: 10, color: [0, 0, 0, 128] },
+                 #{ kind: "text", x: 4.5, y: 2, text: "hi" }]
+            }"#,
+        )
+        .unwrap();
+        assert!(script.overlays(0, 320, 240).is_empty());
+        let prims = script.overlays(1000, 320, 240);
+        assert_eq!(prims.len(), 2);
+        assert!(matches!(prims[0], OverlayPrimitive::Rect { w, .. } if w == 320.0));
+        assert!(matches!(&prims[1], OverlayPrimitive::Text { text, size, .. } if text == "hi" && *size == 24.0));
+    }
+
+    #[test]
+    fn test_runaway_script_is_stopped() {
+        let mut script = ExportScript::compile("fn overlay(t, w, h) { loop {} }").unwrap();
+        assert!(script.overlays(0, 10, 10).is_empty());
+        assert!(script.overlay_failed);
+        assert!(ExportScript::compile("fn camera(a, b) { loop {} }")
+            .unwrap()
+            .camera(&[], vec![kf(0, 1.0)])
+            .is_err());
+    }
+}
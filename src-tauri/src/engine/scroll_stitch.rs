@@ -0,0 +1,202 @@
+//! Scrolling screenshots: stitch frames grabbed while a window scrolls into
+//! one tall image.
+//!
+//! Each frame is correlated with the previous one to find how far the
+//! content moved vertically: a coarse search over a box-filtered reduction
+//! of the luma (computed like the frame differ's) followed by a
+//! full-resolution refinement around the best coarse offset. The stitched image grows by the
+//! rows that scrolled into view, at the top or at the bottom.
+//!
+//! Frames whose best offset still leaves large differences (the page
+//! changed instead of scrolling) are skipped, as are frames of a different
+//! size (the window was resized).
+
+use crate::engine::frame_differ::downsample_gray;
+use image::{DynamicImage, GrayImage, RgbaImage};
+
+/// Downsampling of the coarse offset search.
+const COARSE_FACTOR: u32 = 4;
+
+/// Fraction of the frame height that must overlap between two frames.
+const MIN_OVERLAP: f64 = 0.25;
+
+/// Mean luma difference above which the best offset is not a scroll.
+const MAX_MEAN_DIFF: f64 = 12.0;
+
+/// Mean absolute difference of `prev` rows `[shift..)` against `next` rows
+/// `[..h - shift)` (negative shift: the other way round), sampling every
+/// `step`th pixel of each row.
+fn shifted_diff(prev: &GrayImage, next: &GrayImage, shift: i64, step: usize) -> f64 {
+    let (w, h) = (prev.width() as usize, prev.height() as i64);
+    let overlap = h - shift.abs();
+    if overlap <= 0 {
+        return f64::MAX;
+    }
+    let (prev_start, next_start) = if shift >= 0 { (shift, 0) } else { (0, -shift) };
+    let rows_prev = prev.as_raw()[prev_start as usize * w..].chunks_exact(w);
+    let rows_next = next.as_raw()[next_start as usize * w..].chunks_exact(w);
+    let mut total: u64 = 0;
+    let mut count: u64 = 0;
+    for (row_prev, row_next) in rows_prev.zip(rows_next).take(overlap as usize) {
+        for (a, b) in row_prev.iter().step_by(step).zip(row_next.iter().step_by(step)) {
+            total += a.abs_diff(*b) as u64;
+            count += 1;
+        }
+    }
+    if count == 0 {
+        f64::MAX
+    } else {
+        total as f64 / count as f64
+    }
+}
+
+/// Best offset in `candidates` with its mean difference; the smaller offset
+/// wins a tie, so a still page reads as no scroll.
+fn best_shift(prev: &GrayImage, next: &GrayImage, candidates: impl Iterator<Item = i64>, step: usize) -> (i64, f64) {
+    candidates
+        .map(|shift| (shift, shifted_diff(prev, next, shift, step)))
+        .fold((0, f64::MAX), |best, (shift, diff)| {
+            if diff < best.1 || (diff == best.1 && shift.abs() < best.0.abs()) {
+                (shift, diff)
+            } else {
+                best
+            }
+        })
+}
+
+/// How many pixels the content moved up from `prev` to `next` (negative:
+/// moved down, i.e. scrolled back up). `None` when the frames don't line up
+/// at any offset.
+pub fn estimate_scroll(prev: &RgbaImage, next: &RgbaImage) -> Option<i64> {
+    if prev.dimensions() != next.dimensions() || prev.height() < COARSE_FACTOR * 4 {
+        return None;
+    }
+    let gray = |img: &RgbaImage| downsample_gray(DynamicImage::ImageRgba8(img.clone()), 1).ok();
+    let (full_prev, full_next) = (gray(prev)?, gray(next)?);
+    // Box-filtered, so thin text and lines survive the reduction
+    let coarse = |img: &GrayImage| {
+        let (w, h) = ((img.width() / COARSE_FACTOR).max(1), img.height() / COARSE_FACTOR);
+        image::imageops::resize(img, w, h, image::imageops::FilterType::Triangle)
+    };
+    let (coarse_prev, coarse_next) = (coarse(&full_prev), coarse(&full_next));
+    let max_coarse = (coarse_prev.height() as f64 * (1.0 - MIN_OVERLAP)) as i64;
+    let (coarse, _) = best_shift(&coarse_prev, &coarse_next, -max_coarse..=max_coarse, 1);
+
+    let center = coarse * COARSE_FACTOR as i64;
+    let radius = COARSE_FACTOR as i64;
+    let max_shift = (prev.height() as f64 * (1.0 - MIN_OVERLAP)) as i64;
+    let candidates = (center - radius..=center + radius).filter(|s| s.abs() <= max_shift);
+    let (shift, diff) = best_shift(&full_prev, &full_next, candidates, 2);
+    (diff <= MAX_MEAN_DIFF).then_some(shift)
+}
+
+/// Accumulates frames of a scrolling window into one tall image.
+pub struct ScrollStitcher {
+    width: u32,
+    /// Stitched RGBA rows
+    pixels: Vec<u8>,
+    /// Row of the stitched image at which the last frame's top sits
+    position: i64,
+    last: Option<RgbaImage>,
+}
+
+impl ScrollStitcher {
+    pub fn new() -> Self {
+        Self { width: 0, pixels: Vec::new(), position: 0, last: None }
+    }
+
+    /// Height of the stitched image so far.
+    pub fn height(&self) -> u32 {
+        if self.width == 0 {
+            0
+        } else {
+            (self.pixels.len() / (self.width as usize * 4)) as u32
+        }
+    }
+
+    /// Add a frame. Returns false when it was skipped.
+    pub fn push(&mut self, frame: RgbaImage) -> bool {
+        let Some(last) = self.last.as_ref() else {
+            self.width = frame.width();
+            self.pixels = frame.as_raw().clone();
+            self.last = Some(frame);
+            return true;
+        };
+        let Some(shift) = estimate_scroll(last, &frame) else {
+            return false;
+        };
+        if shift != 0 {
+            let row_bytes = self.width as usize * 4;
+            let frame_h = frame.height() as i64;
+            self.position += shift;
+            let below = self.position + frame_h - self.height() as i64;
+            if below > 0 {
+                let start = (frame_h - below) as usize * row_bytes;
+                self.pixels.extend_from_slice(&frame.as_raw()[start..]);
+            }
+            if self.position < 0 {
+                let above = (-self.position) as usize * row_bytes;
+                let mut pixels = frame.as_raw()[..above].to_vec();
+                pixels.append(&mut self.pixels);
+                self.pixels = pixels;
+                self.position = 0;
+            }
+        }
+        self.last = Some(frame);
+        true
+    }
+
+    /// The stitched image (`None` before the first frame).
+    pub fn finish(self) -> Option<RgbaImage> {
+        let height = self.height();
+        RgbaImage::from_raw(self.width, height, self.pixels).filter(|_| height > 0)
+    }
+}
+
+impl Default for ScrollStitcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tall "page" of pseudo-random blocks, so no two offsets look alike.
+    fn page(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            let mut h = (y / 6).wrapping_mul(0x9E37_79B1) ^ (x / 8);
+            h ^= h >> 15;
+            h = h.wrapping_mul(0x85EB_CA6B);
+            h ^= h >> 13;
+            let v = h as u8;
+            image::Rgba([v, v / 2, 255 - v, 255])
+        })
+    }
+
+    fn view(page: &RgbaImage, top: u32, height: u32) -> RgbaImage {
+        image::imageops::crop_imm(page, 0, top, page.width(), height).to_image()
+    }
+
+    #[test]
+    fn test_stitches_scrolled_views_back_into_the_page() {
+        let page = page(96, 400);
+        let mut stitcher = ScrollStitcher::new();
+        // Scroll down in uneven steps, back up a little, then down to the end
+        for top in [0, 37, 90, 90, 70, 150, 240] {
+            assert!(stitcher.push(view(&page, top, 160)));
+        }
+        assert_eq!(estimate_scroll(&view(&page, 10, 160), &view(&page, 50, 160)), Some(40));
+
+        let stitched = stitcher.finish().unwrap();
+        assert_eq!(stitched.dimensions(), (96, 400));
+        assert_eq!(stitched, page);
+
+        // A different page doesn't line up with the last frame
+        let mut stitcher = ScrollStitcher::new();
+        stitcher.push(view(&page, 0, 160));
+        let other = RgbaImage::from_fn(96, 160, |x, y| image::Rgba([(x * 5 + y * 3) as u8, 0, (y * 11) as u8, 255]));
+        assert!(!stitcher.push(other));
+    }
+}
@@ -84,6 +84,9 @@ pub struct AnimatedViewport {
     pub zoom: Spring,
     pub pan_half_life: f64,
     pub zoom_half_life: f64,
+    /// Width/height ratio forced on the viewport rect (zoom-crop for
+    /// vertical/square output). `None` keeps the screen aspect.
+    pub crop_aspect: Option<f64>,
 }
 
 impl AnimatedViewport {
@@ -103,6 +106,7 @@ impl AnimatedViewport {
             zoom,
             pan_half_life: SpringHalfLife::VIEWPORT_PAN,
             zoom_half_life: SpringHalfLife::ZOOM_IN,
+            crop_aspect: None,
         }
     }
 
@@ -157,8 +161,17 @@ impl AnimatedViewport {
         screen_height: f64,
     ) -> ViewportRect {
         let zoom = self.zoom.position.max(1.0);
-        let vp_width = screen_width / zoom;
-        let vp_height = screen_height / zoom;
+        let mut vp_width = screen_width / zoom;
+        let mut vp_height = screen_height / zoom;
+
+        // Crop to the target aspect around the (activity-following) center
+        if let Some(aspect) = self.crop_aspect.filter(|a| *a > 0.0) {
+            if aspect < vp_width / vp_height {
+                vp_width = vp_height * aspect;
+            } else {
+                vp_height = vp_width / aspect;
+            }
+        }
 
         let x = (self.center_x.position - vp_width / 2.0)
             .max(0.0)
@@ -309,4 +322,20 @@ mod tests {
         assert!(rect.x >= 0.0, "Viewport x should be >= 0, got {}", rect.x);
         assert!(rect.y >= 0.0, "Viewport y should be >= 0, got {}", rect.y);
     }
+
+    #[test]
+    fn test_viewport_crop_aspect_follows_center() {
+        let mut vp = AnimatedViewport::new(1920.0, 1080.0);
+        vp.crop_aspect = Some(9.0 / 16.0);
+        vp.snap_to(1500.0, 540.0, 1.0);
+        let rect = vp.current_viewport(1920.0, 1080.0);
+        assert!((rect.width / rect.height - 9.0 / 16.0).abs() < 1e-9);
+        assert_eq!(rect.height, 1080.0);
+        assert!((rect.x + rect.width / 2.0 - 1500.0).abs() < 1e-9);
+
+        // Clamped at the screen edge
+        vp.snap_to(1900.0, 540.0, 1.0);
+        let rect = vp.current_viewport(1920.0, 1080.0);
+        assert!((rect.x + rect.width - 1920.0).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,770 @@
+//! Timeline cuts: remove ranges of the recording before export.
+//!
+//! Cut ranges are expressed on the *recording* timeline (ms from recording
+//! start). The export pipeline drops frames inside the cuts and maps every
+//! remaining timestamp onto a contiguous *output* timeline, so click rings,
+//! key badges and zoom keyframes stay in sync with the shortened video.
+//!
+//! [`suggest_idle_cuts`] proposes cut ranges automatically ("auto trim dead
+//! time") from analyzer Idle segments that show no screen changes.
+//!
+//! Speed ranges ([`SpeedRange`]) are the soft alternative to cuts: the range
+//! is played back faster (e.g. 4x) with short speed ramps at both ends.
+//! [`TimeMap`] combines cuts and speed ranges into one recording → output
+//! time mapping used by the compose loop.
+
+use crate::engine::analyzer::{Segment, SegmentType};
+use crate::engine::annotation::Annotation;
+use crate::engine::compositor::{ClickEffect, KeyOverlay, UiHighlight};
+use crate::engine::frame_differ::ChangeRegion;
+use crate::engine::redaction::Redaction;
+use crate::engine::scene_style::SceneStyleOverride;
+use crate::engine::typed_text::TypedCaption;
+use crate::engine::zoom_planner::ZoomKeyframe;
+use serde::{Deserialize, Serialize};
+
+/// Idle time kept on each side of a trimmed section so the cut doesn't
+/// feel abrupt (ms).
+const TRIM_KEEP_MS: u64 = 1000;
+/// Length of the speed ramp at each end of a speed range (ms, recording time).
+/// Shorter ranges ramp over half their length.
+const SPEED_RAMP_MS: u64 = 500;
+
+/// A half-open range `[start_ms, end_ms)` removed from the exported video.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CutRange {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl CutRange {
+    pub fn duration_ms(&self) -> u64 {
+        self.end_ms.saturating_sub(self.start_ms)
+    }
+
+    pub fn contains(&self, time_ms: u64) -> bool {
+        time_ms >= self.start_ms && time_ms < self.end_ms
+    }
+}
+
+/// A range `[start_ms, end_ms)` played back `speed` times faster.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpeedRange {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub speed: f64,
+}
+
+/// Sort, clamp to `[0, duration_ms]`, merge overlapping/adjacent ranges and
+/// drop empty ones. All other helpers in this module expect normalized input.
+pub fn normalize_cuts(cuts: &[CutRange], duration_ms: u64) -> Vec<CutRange> {
+    let mut sorted: Vec<CutRange> = cuts
+        .iter()
+        .map(|c| CutRange {
+            start_ms: c.start_ms.min(duration_ms),
+            end_ms: c.end_ms.min(duration_ms),
+        })
+        .filter(|c| c.end_ms > c.start_ms)
+        .collect();
+    sorted.sort_by_key(|c| c.start_ms);
+
+    let mut merged: Vec<CutRange> = Vec::with_capacity(sorted.len());
+    for cut in sorted {
+        match merged.last_mut() {
+            Some(last) if cut.start_ms <= last.end_ms => {
+                last.end_ms = last.end_ms.max(cut.end_ms);
+            }
+            _ => merged.push(cut),
+        }
+    }
+    merged
+}
+
+/// Total removed duration (ms).
+pub fn total_cut_ms(cuts: &[CutRange]) -> u64 {
+    cuts.iter().map(|c| c.duration_ms()).sum()
+}
+
+/// Whether a recording timestamp falls inside any cut.
+pub fn is_cut(cuts: &[CutRange], time_ms: u64) -> bool {
+    cuts.iter().any(|c| c.contains(time_ms))
+}
+
+/// Map a recording timestamp onto the output timeline.
+///
+/// Timestamps inside a cut collapse onto the cut point (the output time at
+/// which the following kept section begins).
+pub fn remap_time(cuts: &[CutRange], time_ms: u64) -> u64 {
+    let mut removed = 0;
+    for cut in cuts {
+        if time_ms >= cut.end_ms {
+            removed += cut.duration_ms();
+        } else if time_ms >= cut.start_ms {
+            removed += time_ms - cut.start_ms;
+            break;
+        } else {
+            break;
+        }
+    }
+    time_ms - removed
+}
+
+/// One linear-rate piece of a [`TimeMap`]. `rate` is output ms per recording
+/// ms (1.0 = normal speed, 0.0 = cut) and varies linearly across the piece.
+#[derive(Debug, Clone)]
+struct Piece {
+    src_start: u64,
+    src_end: u64,
+    out_start: f64,
+    rate_start: f64,
+    rate_end: f64,
+}
+
+impl Piece {
+    fn out_at(&self, time_ms: u64) -> f64 {
+        let dt = (time_ms.min(self.src_end) - self.src_start) as f64;
+        let len = (self.src_end - self.src_start) as f64;
+        if len <= 0.0 {
+            return self.out_start;
+        }
+        self.out_start + self.rate_start * dt + (self.rate_end - self.rate_start) * dt * dt / (2.0 * len)
+    }
+}
+
+/// Recording → output time mapping combining cuts and speed ranges.
+#[derive(Debug, Clone)]
+pub struct TimeMap {
+    cuts: Vec<CutRange>,
+    speeds: Vec<SpeedRange>,
+    pieces: Vec<Piece>,
+    /// Output ms per recording ms outside cuts and speed ranges
+    scale: f64,
+}
+
+impl TimeMap {
+    /// `cuts` must be normalized. Speed ranges are sorted and overlapping
+    /// ones dropped; cuts take precedence where both apply.
+    pub fn new(cuts: &[CutRange], speed_ranges: &[SpeedRange]) -> Self {
+        let mut speeds: Vec<SpeedRange> = speed_ranges
+            .iter()
+            .filter(|r| r.end_ms > r.start_ms && r.speed > 0.0)
+            .copied()
+            .collect();
+        speeds.sort_by_key(|r| r.start_ms);
+        let mut kept: Vec<SpeedRange> = Vec::with_capacity(speeds.len());
+        for r in speeds {
+            if kept.last().map(|l| r.start_ms >= l.end_ms).unwrap_or(true) {
+                kept.push(r);
+            }
+        }
+
+        let mut map = Self { cuts: cuts.to_vec(), speeds: kept, pieces: Vec::new(), scale: 1.0 };
+
+        // Rate is piecewise linear between these breakpoints
+        let mut breaks: Vec<u64> = vec![0];
+        for c in &map.cuts {
+            breaks.extend([c.start_ms, c.end_ms]);
+        }
+        for r in &map.speeds {
+            let ramp = SPEED_RAMP_MS.min((r.end_ms - r.start_ms) / 2);
+            breaks.extend([r.start_ms, r.start_ms + ramp, r.end_ms - ramp, r.end_ms]);
+        }
+        breaks.sort_unstable();
+        breaks.dedup();
+
+        let mut out = 0.0;
+        for w in breaks.windows(2) {
+            let piece = Piece {
+                src_start: w[0],
+                src_end: w[1],
+                out_start: out,
+                rate_start: map.rate_at(w[0] as f64 + 1e-6),
+                rate_end: map.rate_at(w[1] as f64 - 1e-6),
+            };
+            out = piece.out_at(w[1]);
+            map.pieces.push(piece);
+        }
+        let last = *breaks.last().unwrap_or(&0);
+        map.pieces.push(Piece {
+            src_start: last,
+            src_end: u64::MAX,
+            out_start: out,
+            rate_start: 1.0,
+            rate_end: 1.0,
+        });
+        map
+    }
+
+    /// Play the whole timeline `1 / scale` times faster (timelapse
+    /// recordings): every output time, cuts and speed ranges included, is
+    /// multiplied by `scale`.
+    pub fn with_time_scale(mut self, scale: f64) -> Self {
+        if !(scale.is_finite() && scale > 0.0) {
+            return self;
+        }
+        for p in &mut self.pieces {
+            p.out_start *= scale;
+            p.rate_start *= scale;
+            p.rate_end *= scale;
+        }
+        self.scale *= scale;
+        self
+    }
+
+    /// Output ms per recording ms at `time_ms` (before the time scale).
+    fn rate_at(&self, time_ms: f64) -> f64 {
+        if self.cuts.iter().any(|c| time_ms >= c.start_ms as f64 && time_ms < c.end_ms as f64) {
+            return 0.0;
+        }
+        for r in &self.speeds {
+            let (start, end) = (r.start_ms as f64, r.end_ms as f64);
+            if time_ms >= start && time_ms < end {
+                let ramp = (SPEED_RAMP_MS as f64).min((end - start) / 2.0).max(1.0);
+                let f = ((time_ms - start).min(end - time_ms) / ramp).clamp(0.0, 1.0);
+                return 1.0 + (1.0 / r.speed - 1.0) * f;
+            }
+        }
+        1.0
+    }
+
+    pub fn cuts(&self) -> &[CutRange] {
+        &self.cuts
+    }
+
+    /// True when the map leaves the timeline untouched.
+    pub fn is_identity(&self) -> bool {
+        self.cuts.is_empty() && self.speeds.is_empty() && self.scale == 1.0
+    }
+
+    pub fn is_cut(&self, time_ms: u64) -> bool {
+        is_cut(&self.cuts, time_ms)
+    }
+
+    /// Output time (ms, fractional) for a recording timestamp.
+    pub fn remap_time_f64(&self, time_ms: u64) -> f64 {
+        let idx = self
+            .pieces
+            .partition_point(|p| p.src_end <= time_ms)
+            .min(self.pieces.len() - 1);
+        self.pieces[idx].out_at(time_ms)
+    }
+
+    /// Output time (ms) for a recording timestamp. Timestamps inside a cut
+    /// collapse onto the cut point.
+    pub fn remap_time(&self, time_ms: u64) -> u64 {
+        self.remap_time_f64(time_ms).round() as u64
+    }
+
+    /// Output duration of a recording of `duration_ms`.
+    pub fn output_duration_ms(&self, duration_ms: u64) -> u64 {
+        self.remap_time(duration_ms)
+    }
+
+    /// Recording time at which the next cut starts after `time_ms`, if any.
+    fn next_cut_start(&self, time_ms: u64) -> Option<u64> {
+        self.cuts
+            .iter()
+            .find(|c| c.start_ms > time_ms)
+            .map(|c| c.start_ms)
+    }
+
+    /// Clip an effect so it ends at the next cut instead of bleeding into the
+    /// section that follows it on the output timeline. `duration_ms` is
+    /// output time, as is the distance to the cut it is clipped to.
+    fn clipped_duration(&self, start_ms: u64, duration_ms: u64) -> u64 {
+        match self.next_cut_start(start_ms) {
+            Some(cut_start) => duration_ms.min(self.remap_time(cut_start) - self.remap_time(start_ms)),
+            None => duration_ms,
+        }
+    }
+
+    /// FFmpeg audio filter applying the map to the audio track, or None for
+    /// the identity map. Cut-only maps use a plain `aselect`; speed ranges
+    /// and time scales split the track and `atempo` each sped-up part to
+    /// its output length.
+    pub fn audio_filter(&self) -> Option<String> {
+        if self.speeds.is_empty() && self.scale == 1.0 {
+            return audio_cut_filter(&self.cuts);
+        }
+        let mut parts: Vec<String> = Vec::new();
+        for p in &self.pieces {
+            if p.rate_start == 0.0 && p.rate_end == 0.0 {
+                continue;
+            }
+            let end = if p.src_end == u64::MAX { None } else { Some(p.src_end) };
+            let trim = match end {
+                Some(e) => format!(
+                    "atrim=start={:.3}:end={:.3}",
+                    p.src_start as f64 / 1000.0,
+                    e as f64 / 1000.0
+                ),
+                None => format!("atrim=start={:.3}", p.src_start as f64 / 1000.0),
+            };
+            let tempo = match end {
+                Some(e) => {
+                    let out_len = p.out_at(e) - p.out_start;
+                    if out_len > 0.0 { (e - p.src_start) as f64 / out_len } else { 1.0 }
+                }
+                None => 1.0 / self.scale,
+            };
+            if (tempo - 1.0).abs() < 1e-3 {
+                parts.push(format!("{},asetpts=PTS-STARTPTS", trim));
+            } else {
+                parts.push(format!("{},asetpts=PTS-STARTPTS,atempo={:.4}", trim, tempo));
+            }
+        }
+        let n = parts.len();
+        let mut graph = format!(
+            "asplit={}{}",
+            n,
+            (0..n).map(|i| format!("[s{}]", i)).collect::<String>()
+        );
+        for (i, part) in parts.iter().enumerate() {
+            graph.push_str(&format!(";[s{}]{}[p{}]", i, part, i));
+        }
+        graph.push_str(&format!(
+            ";{}concat=n={}:v=0:a=1",
+            (0..n).map(|i| format!("[p{}]", i)).collect::<String>(),
+            n
+        ));
+        Some(graph)
+    }
+}
+
+/// Drop click effects that start inside a cut, clip the rest at the next cut
+/// and re-time them onto the output timeline.
+pub fn remap_click_effects(effects: &[ClickEffect], map: &TimeMap) -> Vec<ClickEffect> {
+    effects
+        .iter()
+        .filter(|e| !map.is_cut(e.start_ms))
+        .map(|e| ClickEffect {
+            x: e.x,
+            y: e.y,
+            start_ms: map.remap_time(e.start_ms),
+            duration_ms: map.clipped_duration(e.start_ms, e.duration_ms).max(1),
+            alt: e.alt,
+        })
+        .collect()
+}
+
+/// Same as [`remap_click_effects`] for key badges.
+pub fn remap_key_overlays(overlays: &[KeyOverlay], map: &TimeMap) -> Vec<KeyOverlay> {
+    overlays
+        .iter()
+        .filter(|o| !map.is_cut(o.start_ms))
+        .map(|o| KeyOverlay {
+            keys: o.keys.clone(),
+            start_ms: map.remap_time(o.start_ms),
+            duration_ms: map.clipped_duration(o.start_ms, o.duration_ms).max(1),
+        })
+        .collect()
+}
+
+/// Re-time dialog/menu highlights. Both ends are remapped independently, so
+/// a highlight spanning a cut shrinks; one lying entirely in a cut is dropped.
+pub fn remap_ui_highlights(highlights: &[UiHighlight], map: &TimeMap) -> Vec<UiHighlight> {
+    highlights
+        .iter()
+        .filter_map(|h| {
+            let start_ms = map.remap_time(h.start_ms);
+            let end_ms = map.remap_time(h.end_ms);
+            (end_ms > start_ms).then(|| UiHighlight { start_ms, end_ms, ..h.clone() })
+        })
+        .collect()
+}
+
+/// Re-time typed-text captions. Keystrokes inside a cut collapse onto the
+/// cut point, so the text right after a cut already includes them.
+pub fn remap_typed_captions(captions: &[TypedCaption], map: &TimeMap) -> Vec<TypedCaption> {
+    captions
+        .iter()
+        .filter_map(|c| {
+            let states: Vec<(u64, String)> =
+                c.states.iter().map(|(t, text)| (map.remap_time(*t), text.clone())).collect();
+            let end_ms = map.remap_time(c.end_ms);
+            (end_ms > states.first()?.0).then_some(TypedCaption { anchor: c.anchor, states, end_ms })
+        })
+        .collect()
+}
+
+/// Re-time redactions. Open ends stay open; a time-ranged redaction lying
+/// entirely in a cut is dropped.
+pub fn remap_redactions(redactions: &[Redaction], map: &TimeMap) -> Vec<Redaction> {
+    redactions
+        .iter()
+        .filter_map(|r| {
+            let start_ms = r.start_ms.map(|t| map.remap_time(t));
+            let end_ms = r.end_ms.map(|t| map.remap_time(t));
+            let empty = matches!((start_ms, end_ms), (Some(s), Some(e)) if e <= s);
+            (!empty).then(|| Redaction { start_ms, end_ms, ..r.clone() })
+        })
+        .collect()
+}
+
+/// Re-time per-scene style overrides. A scene lying entirely in a cut is
+/// dropped.
+pub fn remap_scene_styles(overrides: &[SceneStyleOverride], map: &TimeMap) -> Vec<SceneStyleOverride> {
+    overrides
+        .iter()
+        .filter_map(|o| {
+            let start_ms = map.remap_time(o.start_ms);
+            let end_ms = map.remap_time(o.end_ms);
+            (end_ms > start_ms).then(|| SceneStyleOverride { start_ms, end_ms, ..o.clone() })
+        })
+        .collect()
+}
+
+/// Re-time annotations. Both ends are remapped independently; one lying
+/// entirely in a cut is dropped.
+pub fn remap_annotations(annotations: &[Annotation], map: &TimeMap) -> Vec<Annotation> {
+    annotations
+        .iter()
+        .filter_map(|a| {
+            let start_ms = map.remap_time(a.start_ms);
+            let end_ms = map.remap_time(a.end_ms);
+            (end_ms > start_ms).then(|| Annotation { start_ms, end_ms, ..a.clone() })
+        })
+        .collect()
+}
+
+/// Re-time zoom keyframes onto the output timeline.
+///
+/// Keyframes inside a cut collapse onto the cut point; only the last one of
+/// each collapsed group is kept so the viewport resumes in the state it would
+/// have reached at the end of the removed section.
+pub fn remap_keyframes(keyframes: &[ZoomKeyframe], map: &TimeMap) -> Vec<ZoomKeyframe> {
+    let mut out: Vec<ZoomKeyframe> = Vec::with_capacity(keyframes.len());
+    for kf in keyframes {
+        let mut remapped = kf.clone();
+        remapped.time_ms = map.remap_time(kf.time_ms);
+        match out.last_mut() {
+            Some(last) if last.time_ms == remapped.time_ms => *last = remapped,
+            _ => out.push(remapped),
+        }
+    }
+    out
+}
+
+/// FFmpeg audio filter that removes the cut ranges from the audio track and
+/// closes the gaps. Returns None when there is nothing to cut.
+pub fn audio_cut_filter(cuts: &[CutRange]) -> Option<String> {
+    if cuts.is_empty() {
+        return None;
+    }
+    let terms: Vec<String> = cuts
+        .iter()
+        .map(|c| {
+            format!(
+                "between(t,{:.3},{:.3})",
+                c.start_ms as f64 / 1000.0,
+                c.end_ms as f64 / 1000.0
+            )
+        })
+        .collect();
+    Some(format!(
+        "aselect='not({})',asetpts=N/SR/TB",
+        terms.join("+")
+    ))
+}
+
+/// Propose cut ranges for long idle periods with no screen changes.
+///
+/// Each Idle segment is split at the timestamps of frame-diff change regions;
+/// every quiet interval of at least `min_idle_ms` becomes a cut, keeping
+/// `TRIM_KEEP_MS` of idle time at both ends. The result is sorted and
+/// non-overlapping.
+pub fn suggest_idle_cuts(
+    segments: &[Segment],
+    change_regions: &[ChangeRegion],
+    min_idle_ms: u64,
+) -> Vec<CutRange> {
+    let mut cuts = Vec::new();
+    for seg in segments.iter().filter(|s| s.segment_type == SegmentType::Idle) {
+        let mut boundaries: Vec<u64> = change_regions
+            .iter()
+            .map(|r| r.time_ms)
+            .filter(|&t| t > seg.start_ms && t < seg.end_ms)
+            .collect();
+        boundaries.sort_unstable();
+
+        let mut quiet_start = seg.start_ms;
+        for quiet_end in boundaries.into_iter().chain(std::iter::once(seg.end_ms)) {
+            if quiet_end.saturating_sub(quiet_start) >= min_idle_ms {
+                let cut = CutRange {
+                    start_ms: quiet_start + TRIM_KEEP_MS,
+                    end_ms: quiet_end.saturating_sub(TRIM_KEEP_MS),
+                };
+                if cut.end_ms > cut.start_ms {
+                    cuts.push(cut);
+                }
+            }
+            quiet_start = quiet_end;
+        }
+    }
+    let end = cuts.iter().map(|c| c.end_ms).max().unwrap_or(0);
+    normalize_cuts(&cuts, end)
+}
+
+/// Speed up analyzer Idle segments of at least `min_idle_ms` by `speed`.
+/// Returns nothing when `speed <= 1.0` (feature disabled).
+pub fn suggest_idle_speed_ranges(
+    segments: &[Segment],
+    min_idle_ms: u64,
+    speed: f64,
+) -> Vec<SpeedRange> {
+    if speed <= 1.0 {
+        return Vec::new();
+    }
+    segments
+        .iter()
+        .filter(|s| s.segment_type == SegmentType::Idle)
+        .filter(|s| s.end_ms.saturating_sub(s.start_ms) >= min_idle_ms)
+        .map(|s| SpeedRange { start_ms: s.start_ms, end_ms: s.end_ms, speed })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::zoom_planner::TransitionType;
+
+    fn cut(start_ms: u64, end_ms: u64) -> CutRange {
+        CutRange { start_ms, end_ms }
+    }
+
+    fn kf(time_ms: u64, zoom_level: f64) -> ZoomKeyframe {
+        ZoomKeyframe {
+            time_ms,
+            target_x: 960.0,
+            target_y: 540.0,
+            zoom_level,
+            transition: TransitionType::Smooth,
+            spring_hint: None,
+            pinned: false,
+        }
+    }
+
+    #[test]
+    fn test_normalize_merges_and_clamps() {
+        let cuts = normalize_cuts(
+            &[cut(5000, 7000), cut(1000, 2000), cut(1500, 3000), cut(9000, 20000), cut(4000, 4000)],
+            10000,
+        );
+        assert_eq!(cuts, vec![cut(1000, 3000), cut(5000, 7000), cut(9000, 10000)]);
+    }
+
+    #[test]
+    fn test_remap_time() {
+        let cuts = vec![cut(1000, 2000), cut(3000, 3500)];
+        assert_eq!(remap_time(&cuts, 500), 500);
+        assert_eq!(remap_time(&cuts, 1500), 1000, "inside a cut collapses to cut point");
+        assert_eq!(remap_time(&cuts, 2000), 1000);
+        assert_eq!(remap_time(&cuts, 2500), 1500);
+        assert_eq!(remap_time(&cuts, 4000), 2500);
+        assert_eq!(total_cut_ms(&cuts), 1500);
+    }
+
+    #[test]
+    fn test_time_map_matches_cut_remap() {
+        let cuts = vec![cut(1000, 2000), cut(3000, 3500)];
+        let map = TimeMap::new(&cuts, &[]);
+        for t in [0, 500, 1500, 2000, 2500, 3200, 4000, 10000] {
+            assert_eq!(map.remap_time(t), remap_time(&cuts, t), "t={}", t);
+        }
+        assert_eq!(map.output_duration_ms(10000), 8500);
+    }
+
+    #[test]
+    fn test_time_map_speed_range_with_ramps() {
+        // 4x over 2000-12000 with 500ms ramps at each end
+        let map = TimeMap::new(&[], &[SpeedRange { start_ms: 2000, end_ms: 12000, speed: 4.0 }]);
+        assert_eq!(map.remap_time(2000), 2000);
+        // Ramps average to (1 + 0.25) / 2 over 500ms, the middle runs at 0.25
+        let expected_end = 2000.0 + 2.0 * 500.0 * 0.625 + 9000.0 * 0.25;
+        assert!((map.remap_time_f64(12000) - expected_end).abs() < 0.5);
+        assert!((map.remap_time_f64(13000) - (expected_end + 1000.0)).abs() < 0.5);
+
+        // Monotonic, and strictly slower than real time inside the range
+        let mut prev = 0.0;
+        for t in (0..14000).step_by(50) {
+            let o = map.remap_time_f64(t);
+            assert!(o >= prev);
+            prev = o;
+        }
+        assert!(map.remap_time_f64(7050) - map.remap_time_f64(7000) < 13.0);
+    }
+
+    #[test]
+    fn test_time_scale_compresses_cuts_and_effects_alike() {
+        // Timelapse at 30x with a cut at 3000-6000
+        let map = TimeMap::new(&[cut(3000, 6000)], &[]).with_time_scale(1.0 / 30.0);
+        assert!(!map.is_identity());
+        assert_eq!(map.remap_time(1500), 50);
+        assert_eq!(map.remap_time(4000), 100);
+        assert_eq!(map.output_duration_ms(36000), 1100);
+
+        // Click rings keep their on-screen length but still end at the cut
+        let effects = vec![
+            ClickEffect { x: 0.0, y: 0.0, start_ms: 2970, duration_ms: 400, alt: false },
+            ClickEffect { x: 0.0, y: 0.0, start_ms: 9000, duration_ms: 400, alt: false },
+        ];
+        let out = remap_click_effects(&effects, &map);
+        assert_eq!((out[0].start_ms, out[0].duration_ms), (99, 1));
+        assert_eq!((out[1].start_ms, out[1].duration_ms), (200, 400));
+
+        let out = remap_keyframes(&[kf(0, 1.0), kf(9000, 2.0)], &map);
+        assert_eq!(out[1].time_ms, 200);
+        assert!(map.audio_filter().unwrap().contains("atempo=30.0000"));
+    }
+
+    #[test]
+    fn test_speed_audio_filter_segments() {
+        let map = TimeMap::new(&[], &[SpeedRange { start_ms: 1000, end_ms: 5000, speed: 2.0 }]);
+        let f = map.audio_filter().unwrap();
+        assert!(f.starts_with("asplit=5"));
+        assert!(f.contains("atempo=2.0000"));
+        assert!(f.ends_with("concat=n=5:v=0:a=1"));
+        assert!(TimeMap::new(&[], &[]).audio_filter().is_none());
+    }
+
+    #[test]
+    fn test_suggest_idle_speed_ranges() {
+        let segs = vec![idle(0, 2000), idle(5000, 15000)];
+        assert!(suggest_idle_speed_ranges(&segs, 3000, 1.0).is_empty());
+        let ranges = suggest_idle_speed_ranges(&segs, 3000, 4.0);
+        assert_eq!(ranges, vec![SpeedRange { start_ms: 5000, end_ms: 15000, speed: 4.0 }]);
+    }
+
+    #[test]
+    fn test_click_effects_dropped_and_clipped() {
+        let cuts = vec![cut(1000, 2000)];
+        let effects = vec![
+            ClickEffect { x: 0.0, y: 0.0, start_ms: 800, duration_ms: 400, alt: false },
+            ClickEffect { x: 0.0, y: 0.0, start_ms: 1200, duration_ms: 400, alt: false },
+            ClickEffect { x: 0.0, y: 0.0, start_ms: 2100, duration_ms: 400, alt: false },
+        ];
+        let out = remap_click_effects(&effects, &TimeMap::new(&cuts, &[]));
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].start_ms, 800);
+        assert_eq!(out[0].duration_ms, 200, "clipped at the cut start");
+        assert_eq!(out[1].start_ms, 1100);
+        assert_eq!(out[1].duration_ms, 400);
+    }
+
+    #[test]
+    fn test_key_overlays_remapped() {
+        let cuts = vec![cut(0, 500)];
+        let overlays = vec![KeyOverlay { keys: "Ctrl+C".into(), start_ms: 600, duration_ms: 1500 }];
+        let out = remap_key_overlays(&overlays, &TimeMap::new(&cuts, &[]));
+        assert_eq!(out[0].start_ms, 100);
+        assert_eq!(out[0].duration_ms, 1500);
+    }
+
+    #[test]
+    fn test_ui_highlights_shrink_across_cut() {
+        let cuts = vec![cut(1000, 3000)];
+        let highlight = |start_ms, end_ms| UiHighlight {
+            rect: [0.0, 0.0, 100.0, 100.0],
+            start_ms,
+            end_ms,
+            label: "Save As".into(),
+        };
+        let out = remap_ui_highlights(&[highlight(500, 4000), highlight(1200, 2800)], &TimeMap::new(&cuts, &[]));
+        assert_eq!(out.len(), 1, "highlight entirely inside the cut is dropped");
+        assert_eq!((out[0].start_ms, out[0].end_ms), (500, 2000));
+    }
+
+    #[test]
+    fn test_typed_caption_keystrokes_in_cut_collapse() {
+        let cuts = vec![cut(1000, 3000)];
+        let caption = TypedCaption {
+            anchor: None,
+            states: vec![(500, "a".into()), (1500, "ab".into()), (3500, "abc".into())],
+            end_ms: 5000,
+        };
+        let out = remap_typed_captions(&[caption], &TimeMap::new(&cuts, &[]));
+        assert_eq!(out[0].text_at(1000), Some("ab"));
+        assert_eq!(out[0].text_at(1500), Some("abc"));
+        assert_eq!(out[0].end_ms, 3000);
+    }
+
+    #[test]
+    fn test_redactions_remapped() {
+        use crate::engine::redaction::RedactionStyle;
+        let cuts = vec![cut(1000, 3000)];
+        let redaction = |start_ms, end_ms| Redaction {
+            rect: [0.0, 0.0, 10.0, 10.0],
+            start_ms,
+            end_ms,
+            style: RedactionStyle::Pixelate,
+        };
+        let out = remap_redactions(
+            &[redaction(None, None), redaction(Some(1200), Some(2800)), redaction(Some(3500), None)],
+            &TimeMap::new(&cuts, &[]),
+        );
+        assert_eq!(out.len(), 2, "time-ranged redaction inside the cut is dropped");
+        assert_eq!((out[0].start_ms, out[0].end_ms), (None, None));
+        assert_eq!((out[1].start_ms, out[1].end_ms), (Some(1500), None));
+    }
+
+    #[test]
+    fn test_keyframes_inside_cut_collapse_to_last() {
+        let cuts = vec![cut(1000, 3000)];
+        let keyframes = vec![kf(0, 1.0), kf(1200, 2.0), kf(2500, 1.5), kf(4000, 1.0)];
+        let out = remap_keyframes(&keyframes, &TimeMap::new(&cuts, &[]));
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[1].time_ms, 1000);
+        assert_eq!(out[1].zoom_level, 1.5, "state at the end of the cut survives");
+        assert_eq!(out[2].time_ms, 2000);
+    }
+
+    fn idle(start_ms: u64, end_ms: u64) -> Segment {
+        Segment {
+            segment_type: SegmentType::Idle,
+            start_ms,
+            end_ms,
+            focus_point: None,
+            idle_level: None,
+            window_rect: None,
+            window_changed: false,
+        }
+    }
+
+    fn change_at(time_ms: u64) -> ChangeRegion {
+        ChangeRegion {
+            time_ms,
+            bbox: crate::engine::analyzer::Rect { x: 0.0, y: 0.0, width: 100.0, height: 100.0 },
+            changed_pixel_count: 1000,
+        }
+    }
+
+    #[test]
+    fn test_suggest_idle_cuts_keeps_margins() {
+        let cuts = suggest_idle_cuts(&[idle(2000, 12000)], &[], 5000);
+        assert_eq!(cuts, vec![cut(3000, 11000)]);
+    }
+
+    #[test]
+    fn test_suggest_idle_cuts_skips_short_idle() {
+        let cuts = suggest_idle_cuts(&[idle(0, 3000)], &[], 5000);
+        assert!(cuts.is_empty());
+    }
+
+    #[test]
+    fn test_suggest_idle_cuts_respects_screen_changes() {
+        // Change at 6000 splits 0-20000 into two quiet intervals: 0-6000 and 6000-20000
+        let cuts = suggest_idle_cuts(&[idle(0, 20000)], &[change_at(6000)], 5000);
+        assert_eq!(cuts, vec![cut(1000, 5000), cut(7000, 19000)]);
+
+        // Frequent changes (e.g. a playing video) leave nothing to trim
+        let changes: Vec<_> = (1..20).map(|i| change_at(i * 1000)).collect();
+        assert!(suggest_idle_cuts(&[idle(0, 20000)], &changes, 5000).is_empty());
+    }
+
+    #[test]
+    fn test_audio_filter() {
+        assert!(audio_cut_filter(&[]).is_none());
+        let f = audio_cut_filter(&[cut(1000, 2500)]).unwrap();
+        assert_eq!(f, "aselect='not(between(t,1.000,2.500))',asetpts=N/SR/TB");
+    }
+}
@@ -0,0 +1,250 @@
+//! Typed-text captions: consecutive `Key` events folded into the text the
+//! user typed, shown as a caption bubble during export.
+//!
+//! Characters are reconstructed from key names with a US layout (Shift picks
+//! upper case / the shifted symbol). IME composition is not visible to the
+//! key hook, so Japanese input shows the romaji keystrokes. When key labels
+//! were not recorded (`record_key_labels` off) only categories are known and
+//! each printable key is masked as `•`.
+
+use crate::config::RecordingEvent;
+
+/// A pause longer than this starts a new caption.
+const SEGMENT_GAP_MS: u64 = 2000;
+
+/// How long the caption stays after the last keystroke.
+const CAPTION_HOLD_MS: u64 = 1500;
+
+/// Longest text kept in a caption; older characters scroll out on the left.
+const MAX_CAPTION_CHARS: usize = 40;
+
+/// Shown for printable keys whose label was not recorded.
+const MASK_CHAR: char = '•';
+
+/// One run of typing.
+#[derive(Debug, Clone)]
+pub struct TypedCaption {
+    /// Focused field rect [left, top, right, bottom] when typing started
+    pub anchor: Option<[f64; 4]>,
+    /// Caption text after each keystroke, by time (ascending)
+    pub states: Vec<(u64, String)>,
+    pub end_ms: u64,
+}
+
+impl TypedCaption {
+    /// Text shown at `time_ms`, if the caption is visible and non-empty.
+    pub fn text_at(&self, time_ms: u64) -> Option<&str> {
+        if time_ms >= self.end_ms {
+            return None;
+        }
+        self.states
+            .iter()
+            .take_while(|(t, _)| *t <= time_ms)
+            .last()
+            .map(|(_, text)| text.as_str())
+            .filter(|text| !text.is_empty())
+    }
+}
+
+enum KeyAction {
+    Char(char),
+    Backspace,
+    /// Ends the current caption (Return, Escape, Tab, shortcuts)
+    Break,
+    /// Modifier-only or navigation keys: no effect on the text
+    Ignore,
+}
+
+fn classify(key: &str, modifiers: &[String]) -> KeyAction {
+    let has = |m: &str| modifiers.iter().any(|x| x == m);
+    if has("Ctrl") || has("Alt") || has("Win") {
+        return KeyAction::Break;
+    }
+    let shift = has("Shift");
+    match key {
+        "Backspace" => KeyAction::Backspace,
+        "Space" => KeyAction::Char(' '),
+        "Return" | "Escape" | "Tab" => KeyAction::Break,
+        "Character" | "Digit" | "NumDigit" | "Symbol" => KeyAction::Char(MASK_CHAR),
+        _ => key_to_char(key, shift).map_or(KeyAction::Ignore, KeyAction::Char),
+    }
+}
+
+/// Character produced by a recorded key name (US layout).
+fn key_to_char(key: &str, shift: bool) -> Option<char> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_uppercase() {
+            return Some(if shift { c } else { c.to_ascii_lowercase() });
+        }
+        if c.is_ascii_digit() {
+            return Some(if shift { b")!@#$%^&*("[(c as u8 - b'0') as usize] as char } else { c });
+        }
+        return None;
+    }
+    if let Some(d) = key.strip_prefix("Num").and_then(|d| d.parse::<u8>().ok()) {
+        return char::from_digit(d as u32, 10);
+    }
+    let (plain, shifted) = match key {
+        "Semicolon" => (';', ':'),
+        "Equal" => ('=', '+'),
+        "Comma" => (',', '<'),
+        "Minus" => ('-', '_'),
+        "Period" => ('.', '>'),
+        "Slash" => ('/', '?'),
+        "BackQuote" => ('`', '~'),
+        "BracketLeft" => ('[', '{'),
+        "Backslash" => ('\\', '|'),
+        "BracketRight" => (']', '}'),
+        "Quote" => ('\'', '"'),
+        "NumMultiply" => ('*', '*'),
+        "NumAdd" => ('+', '+'),
+        "NumSubtract" => ('-', '-'),
+        "NumDecimal" => ('.', '.'),
+        "NumDivide" => ('/', '/'),
+        _ => return None,
+    };
+    Some(if shift { shifted } else { plain })
+}
+
+/// Fold `Key` events into captions. Each caption starts at the first
+/// printable key and is held [`CAPTION_HOLD_MS`] after a breaking key or
+/// its last change (pauses longer than [`SEGMENT_GAP_MS`] split captions),
+/// but never past the start of the next caption.
+pub fn extract_typed_captions(events: &[RecordingEvent]) -> Vec<TypedCaption> {
+    let mut captions = Vec::new();
+    let mut current: Option<TypedCaption> = None;
+    let mut text = String::new();
+    let mut last_key_ms = 0u64;
+    let mut focus_rect: Option<[f64; 4]> = None;
+
+    let finish = |current: &mut Option<TypedCaption>, captions: &mut Vec<TypedCaption>, end: u64| {
+        if let Some(mut caption) = current.take() {
+            caption.end_ms = end;
+            if caption.states.iter().any(|(_, s)| !s.is_empty()) {
+                captions.push(caption);
+            }
+        }
+    };
+
+    for event in events {
+        let (t, key, modifiers) = match event {
+            RecordingEvent::Focus { rect, .. } | RecordingEvent::UiFocus { rect, .. } => {
+                if rect[2] > rect[0] && rect[3] > rect[1] {
+                    focus_rect = Some(*rect);
+                }
+                continue;
+            }
+            RecordingEvent::Key { t, key, modifiers } => (*t, key, modifiers),
+            _ => continue,
+        };
+
+        if current.is_some() && t.saturating_sub(last_key_ms) > SEGMENT_GAP_MS {
+            finish(&mut current, &mut captions, last_key_ms + CAPTION_HOLD_MS);
+        }
+
+        match classify(key, modifiers) {
+            KeyAction::Ignore => continue,
+            KeyAction::Break => {
+                finish(&mut current, &mut captions, t + CAPTION_HOLD_MS);
+                continue;
+            }
+            KeyAction::Backspace => {
+                let Some(caption) = current.as_mut() else {
+                    continue;
+                };
+                text.pop();
+                caption.states.push((t, text.clone()));
+            }
+            KeyAction::Char(c) => {
+                if current.is_none() {
+                    // A caption still held after a breaking key gives way
+                    if let Some(prev) = captions.last_mut() {
+                        prev.end_ms = prev.end_ms.min(t);
+                    }
+                }
+                let caption = current.get_or_insert_with(|| {
+                    text.clear();
+                    TypedCaption { anchor: focus_rect, states: Vec::new(), end_ms: 0 }
+                });
+                text.push(c);
+                let overflow = text.chars().count().saturating_sub(MAX_CAPTION_CHARS);
+                if overflow > 0 {
+                    text = text.chars().skip(overflow).collect();
+                }
+                caption.states.push((t, text.clone()));
+            }
+        }
+        last_key_ms = t;
+    }
+    finish(&mut current, &mut captions, last_key_ms + CAPTION_HOLD_MS);
+    captions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(t: u64, key: &str, modifiers: &[&str]) -> RecordingEvent {
+        RecordingEvent::Key {
+            t,
+            key: key.to_string(),
+            modifiers: modifiers.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_shift_and_backspace() {
+        let events = vec![
+            key(0, "H", &["Shift"]),
+            key(100, "I", &[]),
+            key(200, "1", &["Shift"]),
+            key(300, "X", &[]),
+            key(400, "Backspace", &[]),
+            key(500, "Space", &[]),
+            key(600, "Slash", &["Shift"]),
+        ];
+        let captions = extract_typed_captions(&events);
+        assert_eq!(captions.len(), 1);
+        assert_eq!(captions[0].text_at(350), Some("Hi!x"));
+        assert_eq!(captions[0].text_at(650), Some("Hi! ?"));
+        assert_eq!(captions[0].end_ms, 600 + CAPTION_HOLD_MS);
+        assert_eq!(captions[0].text_at(600 + CAPTION_HOLD_MS), None);
+    }
+
+    #[test]
+    fn test_pause_and_return_split_captions() {
+        let events = vec![
+            key(0, "A", &[]),
+            key(100, "Return", &[]),
+            key(200, "B", &[]),
+            key(5000, "C", &[]),
+            key(5100, "V", &["Ctrl"]),
+        ];
+        let captions = extract_typed_captions(&events);
+        let texts: Vec<_> = captions.iter().map(|c| c.states.last().unwrap().1.clone()).collect();
+        assert_eq!(texts, vec!["a", "b", "c"]);
+        // Held after Return until the next caption starts
+        assert_eq!(captions[0].end_ms, 200);
+        assert_eq!(captions[2].end_ms, 5100 + CAPTION_HOLD_MS);
+    }
+
+    #[test]
+    fn test_category_keys_are_masked_and_anchor_follows_focus() {
+        let events = vec![
+            RecordingEvent::Focus {
+                t: 0,
+                el: "Edit".to_string(),
+                name: "Password".to_string(),
+                rect: [10.0, 20.0, 210.0, 50.0],
+            },
+            key(100, "Character", &[]),
+            key(200, "Digit", &[]),
+            key(300, "Navigation", &[]),
+        ];
+        let captions = extract_typed_captions(&events);
+        assert_eq!(captions.len(), 1);
+        assert_eq!(captions[0].text_at(300), Some("••"));
+        assert_eq!(captions[0].anchor, Some([10.0, 20.0, 210.0, 50.0]));
+    }
+}
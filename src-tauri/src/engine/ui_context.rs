@@ -185,6 +185,10 @@ pub fn attach_ui_rects_to_scenes(
     }
 
     for scene in scenes.iter_mut() {
+        // ユーザーが枠を直接指定したシーンは UI 矩形で上書きしない
+        if scene.manual_bbox {
+            continue;
+        }
         let window_lo = scene.start_ms.saturating_sub(SCENE_UI_MATCH_WINDOW_MS);
         let window_hi = scene.end_ms.saturating_add(SCENE_UI_MATCH_WINDOW_MS);
 
@@ -242,6 +246,7 @@ mod tests {
             name: "OK".to_string(),
             rect: [480.0, 280.0, 560.0, 320.0],
             automation_id: "btnOk".to_string(),
+            password: false,
         }];
 
         let enriched = enrich_with_ui_context(&segs, &ui_events);
@@ -272,6 +277,7 @@ mod tests {
             name: "OK".to_string(),
             rect: [480.0, 280.0, 560.0, 320.0],
             automation_id: "btnOk".to_string(),
+            password: false,
         }];
 
         let enriched = enrich_with_ui_context(&segs, &ui_events);
@@ -302,6 +308,7 @@ mod tests {
                 name: "".to_string(),
                 rect: [480.0, 280.0, 560.0, 320.0],
                 automation_id: "".to_string(),
+                password: false,
             },
             RecordingEvent::UiDialogOpen {
                 t: 1080,
@@ -331,6 +338,7 @@ mod tests {
             zoom_level: 1.0,
             event_count: 1,
             ui_rect: None,
+            manual_bbox: false,
         }
     }
 
@@ -343,6 +351,7 @@ mod tests {
             name: "Search".to_string(),
             rect: [100.0, 100.0, 500.0, 180.0],  // 400x80: MIN_UI_RECT_SIZE=60 をクリア
             automation_id: "".to_string(),
+            password: false,
         }];
 
         attach_ui_rects_to_scenes(&mut scenes, &events, 1920.0, 1080.0);
@@ -361,6 +370,7 @@ mod tests {
             name: "x".to_string(),
             rect: [0.0, 0.0, 30.0, 30.0],
             automation_id: "".to_string(),
+            password: false,
         }];
 
         attach_ui_rects_to_scenes(&mut scenes, &events, 1920.0, 1080.0);
@@ -377,6 +387,7 @@ mod tests {
             name: "".to_string(),
             rect: [0.0, 0.0, 1920.0, 1080.0],
             automation_id: "".to_string(),
+            password: false,
         }];
 
         attach_ui_rects_to_scenes(&mut scenes, &events, 1920.0, 1080.0);
@@ -393,6 +404,7 @@ mod tests {
                 name: "".to_string(),
                 rect: [100.0, 100.0, 300.0, 200.0],
                 automation_id: "".to_string(),
+                password: false,
             },
             RecordingEvent::UiDialogOpen {
                 t: 1500,
@@ -419,6 +431,7 @@ mod tests {
                 name: "".to_string(),
                 rect: [100.0, 100.0, 300.0, 200.0],
                 automation_id: "".to_string(),
+                password: false,
             },
             // シーン期間内（優先される）
             RecordingEvent::UiFocus {
@@ -427,6 +440,7 @@ mod tests {
                 name: "".to_string(),
                 rect: [600.0, 100.0, 900.0, 200.0],
                 automation_id: "".to_string(),
+                password: false,
             },
         ];
 
@@ -444,6 +458,7 @@ mod tests {
             name: "".to_string(),
             rect: [100.0, 100.0, 500.0, 200.0],
             automation_id: "".to_string(),
+            password: false,
         }];
 
         attach_ui_rects_to_scenes(&mut scenes, &events, 1920.0, 1080.0);
@@ -7,11 +7,23 @@
 //!
 //! Idle detection considers both user input events AND frame changes:
 //! zoom-out only occurs when there are no events AND no screen changes.
+//! Zooming out on idle is two-staged: after `idle_zoom_out_ms` the camera
+//! eases to a wider view around the last WorkArea, and only after
+//! `idle_overview_ms` does it return to Overview.
+//!
+//! Minimum dwell times keep the camera from committing to a target it would
+//! leave almost immediately: scenes shorter than `min_workarea_dwell_ms` are
+//! folded into a neighboring scene, and the intermediate idle view is skipped
+//! when it would last less than `min_window_dwell_ms`.
+//!
+//! When the cursor repeatedly jumps fast between two distant areas, the
+//! scenes on both ends are merged so the camera frames both at once
+//! instead of panning back and forth.
 
 use crate::config::{EffectsSettings, RecordingMeta};
 use crate::engine::analyzer::Rect;
 use crate::engine::frame_differ::ChangeRegion;
-use crate::engine::scene_splitter::{calc_window_zoom, Scene};
+use crate::engine::scene_splitter::{calc_scene_zoom, calc_window_zoom, Scene};
 use serde::{Deserialize, Serialize};
 
 // ------------------------------------------------------------------
@@ -40,6 +52,9 @@ pub struct ZoomKeyframe {
     pub transition: TransitionType,
     #[serde(default)]
     pub spring_hint: Option<SpringHint>,
+    /// Timeline UI でユーザーが固定したキーフレーム（最適化で削除しない）
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 // ------------------------------------------------------------------
@@ -62,6 +77,14 @@ mod half_lives {
 const ANTICIPATION_HALF_LIVES: f64 = 4.0;
 /// Minimum gap between keyframes to avoid jitter.
 const MIN_KEYFRAME_INTERVAL_MS: u64 = 800;
+/// Shortest cursor jump between scenes (fraction of the screen diagonal)
+/// that counts towards widening the framing.
+const FAST_JUMP_MIN_DISTANCE: f64 = 0.3;
+/// Jump speed (px/ms) above which the user is glancing between areas
+/// rather than moving on to new work.
+const FAST_JUMP_MIN_SPEED: f64 = 1.5;
+/// Back-and-forth jumps needed before both endpoints are framed together.
+const MIN_ALTERNATIONS: usize = 3;
 
 /// UI 矩形からズーム枠を作るときの余白比率（Phase A: Issue #23）。
 /// テキストボックスやボタンの矩形ちょうどにズームすると窮屈なので、
@@ -150,7 +173,7 @@ fn resolve_scene_target(scene: &Scene, screen_w: f64, screen_h: f64, max_zoom: f
 /// 1. All scenes are known upfront (no incremental discovery)
 /// 2. Camera moves BEFORE each scene starts (anticipation)
 /// 3. First scene is targeted from t=0
-/// 4. Idle gaps between scenes trigger zoom-out to overview
+/// 4. Idle gaps between scenes trigger a two-stage zoom-out to overview
 ///    (only if there are no frame changes in the gap)
 pub fn generate_zoom_plan(
     scenes: &[Scene],
@@ -164,12 +187,21 @@ pub fn generate_zoom_plan(
 
     let screen_w = meta.screen_width as f64;
     let screen_h = meta.screen_height as f64;
-    let scale = settings.animation_speed.speed_scale();
+    let scale = settings.effective_animation_speed().speed_scale();
     let idle_ms = settings.idle_zoom_out_ms;
+    let scenes = &fold_short_scenes(
+        scenes,
+        settings.min_workarea_dwell_ms,
+        idle_ms,
+        (screen_w, screen_h, settings.max_zoom),
+    );
+    let scenes = &widen_alternating_scenes(scenes, (screen_w, screen_h, settings.max_zoom));
+    // How long before a scene the zoom-in from an idle view starts
+    let zoom_in_lead_ms = (half_lives::ZOOM_IN_PAN * scale * ANTICIPATION_HALF_LIVES * 1000.0) as u64;
 
     // Compute overview target based on recording mode
-    let (overview_x, overview_y, overview_zoom) =
-        compute_overview_target(meta, screen_w, screen_h, settings.max_zoom);
+    let overview = compute_overview_target(meta, screen_w, screen_h, settings.max_zoom, settings.overview_zoom);
+    let (overview_x, overview_y, overview_zoom) = overview;
 
     let is_window_mode = meta.recording_mode.as_deref() == Some("window");
 
@@ -186,6 +218,7 @@ pub fn generate_zoom_plan(
             zoom_half_life: half_lives::ZOOMOUT_ZOOM * scale,
             pan_half_life: half_lives::ZOOMOUT_PAN * scale,
         }),
+        pinned: false,
     });
 
     for (i, scene) in scenes.iter().enumerate() {
@@ -212,19 +245,13 @@ pub fn generate_zoom_plan(
 
                 if !has_screen_changes {
                     let zoomout_time = ps.end_ms + idle_ms.min(gap_before / 3).min(2000);
-                    if should_emit(&plan, zoomout_time) {
-                        plan.push(ZoomKeyframe {
-                            time_ms: zoomout_time,
-                            target_x: overview_x,
-                            target_y: overview_y,
-                            zoom_level: overview_zoom,
-                            transition: TransitionType::SpringOut,
-                            spring_hint: Some(SpringHint {
-                                zoom_half_life: half_lives::ZOOMOUT_ZOOM * scale,
-                                pan_half_life: half_lives::ZOOMOUT_PAN * scale,
-                            }),
-                        });
-                    }
+                    let idle = IdleSpan {
+                        start_ms: ps.end_ms,
+                        len_ms: gap_before,
+                        first_ms: zoomout_time,
+                        resume_ms: scene.start_ms.saturating_sub(zoom_in_lead_ms),
+                    };
+                    push_idle_zoom_out(&mut plan, &idle, overview, settings, scale);
                 }
             }
         }
@@ -268,10 +295,11 @@ pub fn generate_zoom_plan(
 
         // Windowモード: WorkAreaのzoomがOverview以下になるようクランプ
         // （ウィンドウ全体表示より拡大しない。パンのみで追従）
+        // Displayモード: Overview より引かない（横長画面の切り出しを維持）
         let clamped_zoom = if is_window_mode {
             target.zoom_level.min(overview_zoom)
         } else {
-            target.zoom_level
+            target.zoom_level.max(overview_zoom)
         };
 
         plan.push(ZoomKeyframe {
@@ -284,6 +312,7 @@ pub fn generate_zoom_plan(
                 zoom_half_life: zoom_hl * scale,
                 pan_half_life: pan_hl * scale,
             }),
+            pinned: false,
         });
     }
 
@@ -299,19 +328,13 @@ pub fn generate_zoom_plan(
 
             if !has_screen_changes {
                 let zoomout_time = last.end_ms + idle_ms.min(remaining / 3);
-                if should_emit(&plan, zoomout_time) {
-                    plan.push(ZoomKeyframe {
-                        time_ms: zoomout_time,
-                        target_x: overview_x,
-                        target_y: overview_y,
-                        zoom_level: overview_zoom,
-                        transition: TransitionType::SpringOut,
-                        spring_hint: Some(SpringHint {
-                            zoom_half_life: half_lives::ZOOMOUT_ZOOM * scale,
-                            pan_half_life: half_lives::ZOOMOUT_PAN * scale,
-                        }),
-                    });
-                }
+                let idle = IdleSpan {
+                    start_ms: last.end_ms,
+                    len_ms: remaining,
+                    first_ms: zoomout_time,
+                    resume_ms: meta.duration_ms,
+                };
+                push_idle_zoom_out(&mut plan, &idle, overview, settings, scale);
             }
         }
     }
@@ -325,6 +348,185 @@ pub fn generate_zoom_plan(
     plan
 }
 
+/// An idle period after a WorkArea.
+struct IdleSpan {
+    /// End of the last scene
+    start_ms: u64,
+    /// Idle time before the next scene (or the recording end)
+    len_ms: u64,
+    /// When the first zoom-out stage starts
+    first_ms: u64,
+    /// When the camera leaves the idle view again (next zoom-in or recording end)
+    resume_ms: u64,
+}
+
+/// Two-stage idle zoom-out.
+///
+/// Stage 1 at `first_ms`: a wider view around the last WorkArea (zoom
+/// halfway to Overview on a log scale), eased in with a smooth transition.
+/// Stage 2, when the idle time reaches `idle_overview_ms`: Overview, the
+/// same delay after stage 1 as between the two thresholds, but within the
+/// first two thirds of the idle time so the next zoom-in still has room.
+/// Stage 1 is skipped when it would be held for less than
+/// `min_window_dwell_ms`.
+fn push_idle_zoom_out(
+    plan: &mut Vec<ZoomKeyframe>,
+    idle: &IdleSpan,
+    (overview_x, overview_y, overview_zoom): (f64, f64, f64),
+    settings: &EffectsSettings,
+    scale: f64,
+) {
+    let spring_hint = Some(SpringHint {
+        zoom_half_life: half_lives::ZOOMOUT_ZOOM * scale,
+        pan_half_life: half_lives::ZOOMOUT_PAN * scale,
+    });
+
+    let overview_time = (idle.len_ms >= settings.idle_overview_ms).then(|| {
+        let stage_delay = settings.idle_overview_ms.saturating_sub(settings.idle_zoom_out_ms);
+        (idle.first_ms + stage_delay).min(idle.start_ms + idle.len_ms * 2 / 3)
+    });
+    let window_until = overview_time.unwrap_or(idle.resume_ms);
+
+    if let Some(work) = plan.last().cloned() {
+        let long_enough = window_until.saturating_sub(idle.first_ms) >= settings.min_window_dwell_ms;
+        if long_enough && should_emit(plan, idle.first_ms) {
+            let zoom = (work.zoom_level * overview_zoom).sqrt();
+            plan.push(ZoomKeyframe {
+                time_ms: idle.first_ms,
+                target_x: (work.target_x + overview_x) / 2.0,
+                target_y: (work.target_y + overview_y) / 2.0,
+                zoom_level: zoom,
+                transition: TransitionType::Smooth,
+                spring_hint: spring_hint.clone(),
+                pinned: false,
+            });
+        }
+    }
+
+    let Some(overview_time) = overview_time else {
+        return;
+    };
+    if should_emit(plan, overview_time) {
+        plan.push(ZoomKeyframe {
+            time_ms: overview_time,
+            target_x: overview_x,
+            target_y: overview_y,
+            zoom_level: overview_zoom,
+            transition: TransitionType::SpringOut,
+            spring_hint,
+            pinned: false,
+        });
+    }
+}
+
+/// Fold scenes shorter than `min_dwell_ms` into the neighbor the camera is
+/// already on (previous scene, or else the next one, less than `idle_ms`
+/// apart). Scenes framed by hand are left alone, as are isolated short
+/// scenes: the idle zoom-out after them is at least `idle_ms` away anyway.
+fn fold_short_scenes(
+    scenes: &[Scene],
+    min_dwell_ms: u64,
+    idle_ms: u64,
+    (screen_w, screen_h, max_zoom): (f64, f64, f64),
+) -> Vec<Scene> {
+    let is_short = |s: &Scene| s.end_ms.saturating_sub(s.start_ms) < min_dwell_ms && !s.manual_bbox;
+    let close = |a: &Scene, b: &Scene| b.start_ms.saturating_sub(a.end_ms) < idle_ms && !a.manual_bbox && !b.manual_bbox;
+    let merge = |a: &Scene, b: &Scene| merge_scenes(a, b, (screen_w, screen_h, max_zoom));
+
+    // Backward: into the previous scene
+    let mut folded: Vec<Scene> = Vec::with_capacity(scenes.len());
+    for scene in scenes {
+        match folded.last_mut() {
+            Some(prev) if is_short(scene) && close(prev, scene) => *prev = merge(prev, scene),
+            _ => folded.push(scene.clone()),
+        }
+    }
+    // Forward: short scenes with no previous scene nearby, into the next one
+    let mut i = 0;
+    while i + 1 < folded.len() {
+        if is_short(&folded[i]) && close(&folded[i], &folded[i + 1]) {
+            let merged = merge(&folded[i], &folded[i + 1]);
+            folded.splice(i..=i + 1, std::iter::once(merged));
+        } else {
+            i += 1;
+        }
+    }
+    folded
+}
+
+/// One scene spanning `a` and `b` (in that order), framed on both bboxes.
+fn merge_scenes(a: &Scene, b: &Scene, (screen_w, screen_h, max_zoom): (f64, f64, f64)) -> Scene {
+    let x0 = a.bbox.x.min(b.bbox.x);
+    let y0 = a.bbox.y.min(b.bbox.y);
+    let x1 = (a.bbox.x + a.bbox.width).max(b.bbox.x + b.bbox.width);
+    let y1 = (a.bbox.y + a.bbox.height).max(b.bbox.y + b.bbox.height);
+    let bbox = Rect { x: x0, y: y0, width: x1 - x0, height: y1 - y0 };
+    Scene {
+        id: a.id,
+        start_ms: a.start_ms,
+        end_ms: b.end_ms,
+        center_x: bbox.center_x(),
+        center_y: bbox.center_y(),
+        zoom_level: calc_scene_zoom(&bbox, screen_w, screen_h, max_zoom),
+        event_count: a.event_count + b.event_count,
+        bbox,
+        ui_rect: None,
+        manual_bbox: false,
+    }
+}
+
+/// Whether the cursor got from `a` to `b` with a fast, long jump: at least
+/// [`FAST_JUMP_MIN_DISTANCE`] of the screen diagonal at
+/// [`FAST_JUMP_MIN_SPEED`] or more (the gap between the scenes is the travel
+/// time; back-to-back scenes count as instant).
+fn is_fast_jump(a: &Scene, b: &Scene, screen_w: f64, screen_h: f64) -> bool {
+    let distance = (b.center_x - a.center_x).hypot(b.center_y - a.center_y);
+    let travel_ms = b.start_ms.saturating_sub(a.end_ms).max(1) as f64;
+    distance >= screen_w.hypot(screen_h) * FAST_JUMP_MIN_DISTANCE && distance / travel_ms >= FAST_JUMP_MIN_SPEED
+}
+
+/// Widen the framing when the cursor keeps jumping back and forth between
+/// two distant areas (e.g. comparing two windows). A run of at least
+/// [`MIN_ALTERNATIONS`] fast jumps where every other scene returns near the
+/// one before becomes a single scene framed on all endpoints, instead of
+/// the camera thrashing between two WorkArea targets. Scenes framed by
+/// hand break a run.
+fn widen_alternating_scenes(scenes: &[Scene], (screen_w, screen_h, max_zoom): (f64, f64, f64)) -> Vec<Scene> {
+    // Whether the jump from scene `j` to `j + 1` continues a run starting at `start`
+    let continues = |start: usize, j: usize| {
+        let (a, b) = (&scenes[j], &scenes[j + 1]);
+        if a.manual_bbox || b.manual_bbox || !is_fast_jump(a, b, screen_w, screen_h) {
+            return false;
+        }
+        // Every jump after the first must head back to the scene before last
+        j == start || {
+            let back = &scenes[j - 1];
+            let jump = (b.center_x - a.center_x).hypot(b.center_y - a.center_y);
+            (b.center_x - back.center_x).hypot(b.center_y - back.center_y) < jump / 2.0
+        }
+    };
+
+    let mut widened: Vec<Scene> = Vec::with_capacity(scenes.len());
+    let mut i = 0;
+    while i < scenes.len() {
+        let mut end = i;
+        while end + 1 < scenes.len() && continues(i, end) {
+            end += 1;
+        }
+        if end - i >= MIN_ALTERNATIONS {
+            let merged = scenes[i + 1..=end]
+                .iter()
+                .fold(scenes[i].clone(), |acc, s| merge_scenes(&acc, s, (screen_w, screen_h, max_zoom)));
+            widened.push(merged);
+            i = end + 1;
+        } else {
+            widened.push(scenes[i].clone());
+            i += 1;
+        }
+    }
+    widened
+}
+
 /// Compute the overview (zoomed-out) target based on recording mode.
 ///
 /// - Display mode: zoom 1.0 at screen center
@@ -334,6 +536,7 @@ fn compute_overview_target(
     screen_w: f64,
     screen_h: f64,
     max_zoom: f64,
+    overview_zoom: Option<f64>,
 ) -> (f64, f64, f64) {
     let is_window_mode = meta.recording_mode.as_deref() == Some("window");
 
@@ -352,8 +555,24 @@ fn compute_overview_target(
         }
     }
 
-    // Display mode (default): full screen overview
-    (screen_w / 2.0, screen_h / 2.0, 1.0)
+    // Display mode (default): full screen, or a centered crop on screens
+    // wider than the reference aspect / with a configured zoom
+    let zoom = overview_zoom
+        .unwrap_or_else(|| auto_overview_zoom(screen_w, screen_h))
+        .clamp(1.0, max_zoom.max(1.0));
+    (screen_w / 2.0, screen_h / 2.0, zoom)
+}
+
+/// Widest aspect shown uncropped in the display-mode overview
+const OVERVIEW_REFERENCE_ASPECT: f64 = 16.0 / 9.0;
+
+/// Overview zoom that crops an ultrawide screen to the reference aspect
+/// (32:9 → 2.0); 1.0 for 16:9 and narrower screens.
+fn auto_overview_zoom(screen_w: f64, screen_h: f64) -> f64 {
+    if screen_h <= 0.0 {
+        return 1.0;
+    }
+    (screen_w / screen_h / OVERVIEW_REFERENCE_ASPECT).max(1.0)
 }
 
 /// Check if we should emit a keyframe at this time (not too close to the last one).
@@ -381,7 +600,7 @@ fn deduplicate_keyframes(plan: &mut Vec<ZoomKeyframe>, min_interval_ms: u64) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{AnimationSpeed, ZoomIntensity};
+    use crate::config::{AnimationSpeed, EffectsProfile, ZoomIntensity};
 
     #[test]
     fn test_zoom_target_from_rect_fits_small_button() {
@@ -424,6 +643,7 @@ mod tests {
             zoom_level: 2.5,
             event_count: 1,
             ui_rect: None,
+            manual_bbox: false,
         };
         // ui_rect 未設定 → scene のデフォルトが使われる
         let t = resolve_scene_target(&scene, 1920.0, 1080.0, 5.0);
@@ -454,6 +674,15 @@ mod tests {
             window_title: None,
             window_initial_rect: None,
             frame_count: None,
+            cursor_scale: None,
+            take: None,
+            splices: Vec::new(),
+            pauses: Vec::new(),
+            timelapse_speed: None,
+            title: None,
+            tags: Vec::new(),
+            notes: String::new(),
+            in_progress: false,
         }
     }
 
@@ -472,12 +701,32 @@ mod tests {
             smart_zoom_enabled: true,
             motion_blur_enabled: false,
             frame_diff_enabled: true,
+            frame_diff_pixel_threshold: 10,
+            frame_diff_min_region_size: 50,
+            frame_diff_max_change_fraction: 0.5,
             idle_zoom_out_ms: 5000,
             idle_overview_ms: 8000,
             min_workarea_dwell_ms: 2000,
             min_window_dwell_ms: 1500,
             cluster_lifetime_ms: 5000,
             cluster_stability_ms: 1000,
+            move_thin_distance_px: 3.0,
+            move_thin_interval_ms: 200,
+            drag_min_distance_px: 20.0,
+            drag_min_duration_ms: 0,
+            click_ring_buttons: vec!["left".to_string(), "right".to_string(), "middle".to_string()],
+            click_ring_alt_buttons: Vec::new(),
+            click_ring_color: [59, 130, 246, 180],
+            click_ring_alt_color: [249, 115, 22, 180],
+            ui_highlight_enabled: false,
+            typed_text_overlay: false,
+            auto_redact_inputs: false,
+            plugins: Vec::new(),
+            script_path: None,
+            overview_zoom: None,
+            profile: EffectsProfile::Standard,
+            reduced_motion: false,
+            zoom_excluded_apps: Vec::new(),
         }
     }
 
@@ -593,6 +842,138 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_idle_zoomout_is_two_staged() {
+        let scenes = vec![
+            Scene::for_test(0, 0, 2000, 500.0, 300.0, 2.0),
+            // 6000ms gap: past idle_zoom_out_ms (5000) but not idle_overview_ms (8000)
+            Scene::for_test(1, 8000, 10000, 1500.0, 800.0, 2.0),
+            // 9000ms gap: both stages
+            Scene::for_test(2, 19000, 21000, 500.0, 300.0, 2.0),
+        ];
+        let mut meta = test_meta();
+        meta.duration_ms = 22000;
+        let plan = generate_zoom_plan(&scenes, &meta, &test_settings(), &[]);
+
+        let in_gap = |from: u64, to: u64| -> Vec<&ZoomKeyframe> {
+            plan.iter().filter(|kf| kf.time_ms > from && kf.time_ms < to && kf.zoom_level < 2.0).collect()
+        };
+        // First gap: only the wider intermediate view
+        let first = in_gap(2000, 7000);
+        assert_eq!(first.len(), 1);
+        assert!(matches!(first[0].transition, TransitionType::Smooth));
+        assert!(first[0].zoom_level > 1.0 && first[0].zoom_level < 2.0);
+
+        // Second gap: intermediate, then Overview
+        let second = in_gap(10000, 18000);
+        assert_eq!(second.len(), 2);
+        assert!(second[0].zoom_level > 1.0);
+        assert!((second[1].zoom_level - 1.0).abs() < 0.01);
+        assert!(matches!(second[1].transition, TransitionType::SpringOut));
+    }
+
+    #[test]
+    fn test_short_scene_folded_into_neighbor() {
+        let scenes = vec![
+            Scene::for_test(0, 0, 3000, 500.0, 300.0, 2.0),
+            // 600ms blip 1s later: folded into the first scene
+            Scene::for_test(1, 4000, 4600, 1500.0, 800.0, 2.0),
+            // Short scene after a long idle: folded forward into the next one
+            Scene::for_test(2, 15000, 15500, 300.0, 300.0, 2.0),
+            Scene::for_test(3, 16000, 19000, 400.0, 400.0, 2.0),
+        ];
+        let folded = fold_short_scenes(&scenes, 2000, 5000, (1920.0, 1080.0, 3.0));
+        assert_eq!(folded.len(), 2);
+        assert_eq!((folded[0].start_ms, folded[0].end_ms), (0, 4600));
+        assert!((folded[0].center_x - 1000.0).abs() < 0.01);
+        assert_eq!((folded[1].start_ms, folded[1].end_ms), (15000, 19000));
+
+        let plan = generate_zoom_plan(&scenes, &test_meta(), &test_settings(), &[]);
+        assert!(plan.iter().all(|kf| (kf.target_x - 1500.0).abs() > 1.0));
+    }
+
+    #[test]
+    fn test_fast_alternation_widens_framing() {
+        // Comparing two windows: 2.5s at each side, jumping over in 200ms
+        let scenes: Vec<Scene> = (0..4)
+            .map(|i| {
+                let x = if i % 2 == 0 { 300.0 } else { 1600.0 };
+                Scene::for_test(i, i as u64 * 2700, i as u64 * 2700 + 2500, x, 500.0, 2.5)
+            })
+            .collect();
+        let widened = widen_alternating_scenes(&scenes, (1920.0, 1080.0, 3.0));
+        assert_eq!(widened.len(), 1);
+        assert_eq!((widened[0].start_ms, widened[0].end_ms), (0, 10600));
+        assert!(widened[0].bbox.x <= 200.0 && widened[0].bbox.x + widened[0].bbox.width >= 1700.0);
+
+        let mut meta = test_meta();
+        meta.duration_ms = 11000;
+        let plan = generate_zoom_plan(&scenes, &meta, &test_settings(), &[]);
+        let targets: Vec<f64> = plan.iter().skip(1).map(|kf| kf.target_x).collect();
+        assert!(targets.iter().all(|x| (x - 950.0).abs() < 1.0), "{:?}", targets);
+    }
+
+    #[test]
+    fn test_slow_or_single_jumps_keep_scenes() {
+        // Same layout, but each move takes 2s: deliberate moves, not glances
+        let slow: Vec<Scene> = (0..4)
+            .map(|i| {
+                let x = if i % 2 == 0 { 300.0 } else { 1600.0 };
+                Scene::for_test(i, i as u64 * 4500, i as u64 * 4500 + 2500, x, 500.0, 2.5)
+            })
+            .collect();
+        assert_eq!(widen_alternating_scenes(&slow, (1920.0, 1080.0, 3.0)).len(), 4);
+
+        // Fast jumps that move on instead of returning
+        let onward = vec![
+            Scene::for_test(0, 0, 2500, 200.0, 200.0, 2.5),
+            Scene::for_test(1, 2700, 5200, 1700.0, 200.0, 2.5),
+            Scene::for_test(2, 5400, 7900, 1700.0, 900.0, 2.5),
+            Scene::for_test(3, 8100, 10600, 200.0, 900.0, 2.5),
+        ];
+        assert_eq!(widen_alternating_scenes(&onward, (1920.0, 1080.0, 3.0)).len(), 4);
+    }
+
+    #[test]
+    fn test_short_intermediate_idle_view_skipped() {
+        let mut settings = test_settings();
+        settings.min_window_dwell_ms = 4000;
+        let scenes = vec![
+            Scene::for_test(0, 0, 2000, 500.0, 300.0, 2.0),
+            // 6000ms gap: the wider view would be held from 4000 to ~7200
+            Scene::for_test(1, 8000, 10000, 1500.0, 800.0, 2.0),
+        ];
+        let mut meta = test_meta();
+        meta.duration_ms = 11000;
+        let plan = generate_zoom_plan(&scenes, &meta, &settings, &[]);
+        assert!(plan.iter().all(|kf| kf.time_ms == 0 || kf.zoom_level >= 2.0));
+    }
+
+    #[test]
+    fn test_ultrawide_overview_auto_crops() {
+        let mut meta = test_meta();
+        meta.screen_width = 3840; // 32:9
+        let scenes = vec![Scene::for_test(0, 500, 3000, 500.0, 300.0, 1.2)];
+        let plan = generate_zoom_plan(&scenes, &meta, &test_settings(), &[]);
+        assert!((plan[0].zoom_level - 2.0).abs() < 0.01, "got {:.2}", plan[0].zoom_level);
+        assert!((plan[0].target_x - 1920.0).abs() < 0.01);
+        // WorkArea never zooms out wider than the overview crop
+        assert!(plan.iter().all(|kf| kf.zoom_level >= 2.0 - 0.01));
+    }
+
+    #[test]
+    fn test_configured_overview_zoom() {
+        let mut settings = test_settings();
+        settings.overview_zoom = Some(1.25);
+        let scenes = vec![Scene::for_test(0, 500, 3000, 500.0, 300.0, 2.0)];
+        let plan = generate_zoom_plan(&scenes, &test_meta(), &settings, &[]);
+        assert!((plan[0].zoom_level - 1.25).abs() < 0.01);
+
+        settings.overview_zoom = Some(0.5);
+        let plan = generate_zoom_plan(&scenes, &test_meta(), &settings, &[]);
+        assert!((plan[0].zoom_level - 1.0).abs() < 0.01, "clamped to 1.0");
+    }
+
     #[test]
     fn test_idle_gap_with_screen_changes_no_zoomout() {
         let scenes = vec![
@@ -0,0 +1,94 @@
+//! Batch export: several recordings, one after another, with one progress
+//! stream for the whole batch.
+//!
+//! Each recording counts as an equal share of the overall progress. A
+//! failed recording is recorded in the report and the batch moves on.
+
+use crate::config::{BatchExportItem, BatchExportProgress, BatchExportReport};
+use crate::export::encoder::ProgressFn;
+use anyhow::Result;
+
+/// Overall progress with item `index` of `count` at `progress`.
+pub fn overall_progress(index: usize, count: usize, progress: f64) -> f64 {
+    if count == 0 {
+        return 1.0;
+    }
+    ((index as f64 + progress.clamp(0.0, 1.0)) / count as f64).clamp(0.0, 1.0)
+}
+
+/// Export `recording_ids` in order with `export_one`, which gets a
+/// recording's ID and its progress callback and returns the output path.
+pub fn run<E, P>(recording_ids: &[String], mut export_one: E, on_progress: P) -> BatchExportReport
+where
+    E: FnMut(&str, &ProgressFn) -> Result<String>,
+    P: Fn(BatchExportProgress) + Clone + Send + 'static,
+{
+    let count = recording_ids.len();
+    let mut report = BatchExportReport::default();
+    for (index, id) in recording_ids.iter().enumerate() {
+        let progress: ProgressFn = {
+            let on_progress = on_progress.clone();
+            let recording_id = id.clone();
+            Box::new(move |stage: &str, p: f64| {
+                on_progress(BatchExportProgress {
+                    index: index as u32,
+                    count: count as u32,
+                    recording_id: recording_id.clone(),
+                    stage: stage.to_string(),
+                    progress: p,
+                    overall: overall_progress(index, count, p),
+                })
+            })
+        };
+        progress("starting", 0.0);
+        let item = match export_one(id, &progress) {
+            Ok(path) => {
+                report.succeeded += 1;
+                BatchExportItem { recording_id: id.clone(), output_path: Some(path), error: None }
+            }
+            Err(e) => {
+                log::warn!("Batch export of {} failed: {}", id, e);
+                report.failed += 1;
+                BatchExportItem { recording_id: id.clone(), output_path: None, error: Some(e.to_string()) }
+            }
+        };
+        report.items.push(item);
+    }
+    log::info!("Batch export finished: {} succeeded, {} failed", report.succeeded, report.failed);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_run_continues_after_failure() {
+        let ids: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let report = run(
+            &ids,
+            |id, progress| {
+                progress("encoding", 0.5);
+                if id == "b" {
+                    anyhow::bail!("no frames");
+                }
+                Ok(format!("{}.mp4", id))
+            },
+            move |p: BatchExportProgress| sink.lock().unwrap().push((p.index, p.overall)),
+        );
+
+        assert_eq!((report.succeeded, report.failed), (2, 1));
+        assert_eq!(report.items[0].output_path.as_deref(), Some("a.mp4"));
+        assert_eq!(report.items[1].error.as_deref(), Some("no frames"));
+        assert_eq!(report.items[2].recording_id, "c");
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 6);
+        assert_eq!(seen[3], (1, 0.5));
+        assert!((seen[5].1 - 2.5 / 3.0).abs() < 1e-9);
+        assert_eq!(overall_progress(0, 0, 0.0), 1.0);
+    }
+}
@@ -0,0 +1,180 @@
+//! Per-recording cache manifest.
+//!
+//! Derived artifacts (thumbnail, analysis results, proxies) are stored next
+//! to the recording and reused across runs. `cache.json` records, for each
+//! artifact, a fingerprint of the inputs it was built from — frame files,
+//! events, meta and any parameters such as a cut list. When the current
+//! fingerprint differs (re-record, edited cuts, changed settings) the
+//! artifact is stale and must be regenerated.
+//!
+//! Fingerprints hash file sizes and modification times rather than file
+//! contents, so checking freshness stays cheap for thousands of frames.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+const MANIFEST_FILE: &str = "cache.json";
+
+/// Artifact name of `thumbnail.png`.
+pub const THUMBNAIL: &str = "thumbnail";
+
+/// Artifact name of the editor's `preview.mp4` proxy.
+pub const PREVIEW: &str = "preview";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheManifest {
+    /// Artifact name → input fingerprint
+    #[serde(default)]
+    artifacts: HashMap<String, String>,
+}
+
+fn read_manifest(recording_dir: &Path) -> CacheManifest {
+    std::fs::read_to_string(recording_dir.join(MANIFEST_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn write_manifest(recording_dir: &Path, manifest: &CacheManifest) -> Result<()> {
+    let json = serde_json::to_string_pretty(manifest)?;
+    std::fs::write(recording_dir.join(MANIFEST_FILE), json)?;
+    Ok(())
+}
+
+/// Builds an input fingerprint (64-bit FNV-1a, stable across builds).
+pub struct Fingerprint(u64);
+
+impl Default for Fingerprint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fingerprint {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    pub fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Mix in a file's size and modification time. A missing file hashes
+    /// differently from any existing one.
+    pub fn file(mut self, path: &Path) -> Self {
+        match std::fs::metadata(path) {
+            Ok(meta) => {
+                let mtime_ns = meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_nanos());
+                self.bytes(&[1]);
+                self.bytes(&meta.len().to_le_bytes());
+                self.bytes(&mtime_ns.to_le_bytes());
+            }
+            Err(_) => self.bytes(&[0]),
+        }
+        self
+    }
+
+    /// Mix in any serializable parameter (cut list, settings, ...).
+    pub fn value<T: Serialize + ?Sized>(mut self, value: &T) -> Self {
+        let json = serde_json::to_vec(value).unwrap_or_default();
+        self.bytes(&(json.len() as u64).to_le_bytes());
+        self.bytes(&json);
+        self
+    }
+
+    pub fn finish(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+/// Fingerprint of the captured recording itself: meta, frame count and
+/// capture times, events, the window track and the frames directory (whose mtime changes when
+/// frames are rewritten).
+pub fn recording_fingerprint(recording_dir: &Path) -> Fingerprint {
+    Fingerprint::new()
+        .file(&recording_dir.join("meta.json"))
+        .file(&recording_dir.join("frame_count.txt"))
+        .file(&recording_dir.join(crate::recording::capture::FRAME_TIMESTAMPS_FILE))
+        .file(&recording_dir.join("events.jsonl"))
+        .file(&recording_dir.join(crate::recording::window_track::WINDOW_TRACK_FILE))
+        .file(&recording_dir.join("frames"))
+        .file(&recording_dir.join("frames").join(crate::recording::frame_store::FRAME_PACK_FILE))
+}
+
+/// Whether `artifact` was last built from inputs with this fingerprint.
+/// The caller still checks that the artifact file itself exists.
+pub fn is_fresh(recording_dir: &Path, artifact: &str, fingerprint: &Fingerprint) -> bool {
+    read_manifest(recording_dir)
+        .artifacts
+        .get(artifact)
+        .is_some_and(|f| *f == fingerprint.finish())
+}
+
+/// Record that `artifact` was just built from inputs with this fingerprint.
+pub fn record(recording_dir: &Path, artifact: &str, fingerprint: &Fingerprint) -> Result<()> {
+    let mut manifest = read_manifest(recording_dir);
+    manifest.artifacts.insert(artifact.to_string(), fingerprint.finish());
+    write_manifest(recording_dir, &manifest)
+}
+
+/// Forget `artifact`, forcing regeneration on next use.
+pub fn invalidate(recording_dir: &Path, artifact: &str) -> Result<()> {
+    let mut manifest = read_manifest(recording_dir);
+    if manifest.artifacts.remove(artifact).is_some() {
+        write_manifest(recording_dir, &manifest)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_until_input_changes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let events = dir.path().join("events.jsonl");
+        std::fs::write(&events, "{}\n").unwrap();
+
+        let fp = Fingerprint::new().file(&events);
+        assert!(!is_fresh(dir.path(), "a", &fp));
+        record(dir.path(), "a", &fp).unwrap();
+        assert!(is_fresh(dir.path(), "a", &Fingerprint::new().file(&events)));
+
+        std::fs::write(&events, "{}\n{}\n").unwrap();
+        assert!(!is_fresh(dir.path(), "a", &Fingerprint::new().file(&events)));
+    }
+
+    #[test]
+    fn test_value_and_invalidate() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let fp = |cuts: &[(u64, u64)]| Fingerprint::new().value(cuts);
+        record(dir.path(), "b", &fp(&[(0, 1000)])).unwrap();
+        assert!(is_fresh(dir.path(), "b", &fp(&[(0, 1000)])));
+        assert!(!is_fresh(dir.path(), "b", &fp(&[(0, 2000)])));
+
+        invalidate(dir.path(), "b").unwrap();
+        assert!(!is_fresh(dir.path(), "b", &fp(&[(0, 1000)])));
+    }
+
+    #[test]
+    fn test_missing_file_differs_from_empty_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("x");
+        let missing = Fingerprint::new().file(&path).finish();
+        std::fs::write(&path, "").unwrap();
+        assert_ne!(missing, Fingerprint::new().file(&path).finish());
+    }
+}
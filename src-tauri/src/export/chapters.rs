@@ -0,0 +1,101 @@
+//! Chapter markers in exported videos, from the recording's bookmarks
+//! (`OutputSettings::bookmark_chapters`).
+//!
+//! Chapters are written into the finished file with a stream copy: the
+//! export is remuxed with an FFmpeg metadata file (`;FFMETADATA1`) that
+//! lists one chapter per bookmark, running until the next bookmark or the
+//! end of the video.
+
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+
+/// A chapter start on the output timeline and its title
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub start_ms: u64,
+    pub title: String,
+}
+
+/// Escape `=`, `;`, `#`, `\` and newlines for an FFmpeg metadata value.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// FFmpeg metadata file content for `chapters` in a `duration_ms` video.
+/// Chapters are sorted; ones at or past the end are dropped.
+pub fn ffmetadata(chapters: &[Chapter], duration_ms: u64) -> String {
+    let mut chapters: Vec<&Chapter> = chapters.iter().filter(|c| c.start_ms < duration_ms).collect();
+    chapters.sort_by_key(|c| c.start_ms);
+    let mut content = String::from(";FFMETADATA1\n");
+    for (i, chapter) in chapters.iter().enumerate() {
+        let end = chapters.get(i + 1).map(|next| next.start_ms).unwrap_or(duration_ms);
+        content.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            chapter.start_ms,
+            end,
+            escape(&chapter.title)
+        ));
+    }
+    content
+}
+
+/// Remux `output` in place with `chapters` added.
+pub fn add_chapters(ffmpeg: &str, output: &Path, chapters: &[Chapter], duration_ms: u64) -> Result<()> {
+    if chapters.is_empty() {
+        return Ok(());
+    }
+    let metadata = tempfile::Builder::new().suffix(".txt").tempfile()?;
+    std::fs::write(metadata.path(), ffmetadata(chapters, duration_ms))?;
+    let ext = output.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+    let remuxed = output.with_extension(format!("chapters.{}", ext));
+
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(["-y", "-i"])
+        .arg(output.to_string_lossy().to_string())
+        .args(["-f", "ffmetadata", "-i"])
+        .arg(metadata.path().to_string_lossy().to_string())
+        .args(["-map", "0", "-map_chapters", "1", "-c", "copy"]);
+    if ext == "mp4" || ext == "mov" {
+        cmd.args(["-movflags", "+faststart"]);
+    }
+    cmd.arg(remuxed.to_string_lossy().to_string());
+
+    log::info!("FFmpeg chapters command: {:?}", cmd);
+    let result = cmd.output()?;
+    if !result.status.success() {
+        let _ = std::fs::remove_file(&remuxed);
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(anyhow::anyhow!("FFmpeg failed to add chapters: {}", stderr));
+    }
+    std::fs::rename(&remuxed, output)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffmetadata_chapters_run_to_the_next() {
+        let chapters = vec![
+            Chapter { start_ms: 5000, title: "Step 2; save".into() },
+            Chapter { start_ms: 0, title: "Intro".into() },
+            Chapter { start_ms: 12_000, title: "past the end".into() },
+        ];
+        let content = ffmetadata(&chapters, 9000);
+        assert_eq!(
+            content,
+            ";FFMETADATA1\n\
+             [CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=5000\ntitle=Intro\n\
+             [CHAPTER]\nTIMEBASE=1/1000\nSTART=5000\nEND=9000\ntitle=Step 2\\; save\n"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Workspace cleanup: removes what crashed or abandoned runs leave behind.
+//!
+//! - Export temp dirs: every export composes its frames into a temp dir
+//!   named `snappi-export-<pid>-…`, which is deleted when the export ends.
+//!   A crash or a killed process leaves it behind; dirs whose process is
+//!   no longer running are orphans. Another live process (a headless
+//!   `snappi export`, a second instance) keeps its dirs.
+//! - Stale preview proxies: `preview.mp4` whose inputs changed since it was
+//!   built (it would be rebuilt on next use anyway).
+//! - Orphaned caches: recording folders with neither `meta.json` nor
+//!   frames, i.e. only derived artifacts of a recording that is gone.
+//!
+//! The recording currently being captured is never touched.
+
+use crate::config::{AppSettings, CleanupReport};
+use crate::export::{cache, encoder};
+use anyhow::Result;
+use std::path::Path;
+
+/// Name prefix of export temp dirs (followed by the owning process id).
+const EXPORT_TEMP_PREFIX: &str = "snappi-export-";
+
+/// Temp dir for an export's intermediate frames, recognizable by
+/// [`cleanup_workspace`] if this process dies before dropping it.
+pub fn export_temp_dir() -> std::io::Result<tempfile::TempDir> {
+    tempfile::Builder::new()
+        .prefix(&format!("{}{}-", EXPORT_TEMP_PREFIX, std::process::id()))
+        .tempdir()
+}
+
+/// Owning process id of an export temp dir named `name`, if it is one.
+fn temp_dir_owner(name: &str) -> Option<u32> {
+    let rest = name.strip_prefix(EXPORT_TEMP_PREFIX)?;
+    rest.split('-').next()?.parse().ok()
+}
+
+/// Whether process `pid` is still running. Unknown counts as running, so
+/// a live export's frames are never removed.
+#[cfg(windows)]
+pub(crate) fn process_alive(pid: u32) -> bool {
+    use windows::Win32::Foundation::{CloseHandle, ERROR_ACCESS_DENIED};
+    use windows::Win32::System::Threading::{GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+    // GetExitCodeProcess reports this while the process runs
+    const STILL_ACTIVE: u32 = 259;
+
+    unsafe {
+        let process = match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
+            Ok(process) => process,
+            // A process of another user that we may not open still exists
+            Err(e) => return e.code() == ERROR_ACCESS_DENIED.to_hresult(),
+        };
+        let mut code = 0u32;
+        let queried = GetExitCodeProcess(process, &mut code);
+        let _ = CloseHandle(process);
+        queried.is_err() || code == STILL_ACTIVE
+    }
+}
+
+#[cfg(not(windows))]
+pub(crate) fn process_alive(pid: u32) -> bool {
+    let proc = Path::new("/proc");
+    !proc.exists() || proc.join(pid.to_string()).exists()
+}
+
+/// Total size of the files under `path`.
+pub(crate) fn size_of(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| size_of(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Remove export temp dirs under `temp_root` whose owner is neither
+/// process `pid` nor alive according to `is_alive`.
+fn prune_temp_dirs(temp_root: &Path, pid: u32, is_alive: impl Fn(u32) -> bool, report: &mut CleanupReport) {
+    let Ok(entries) = std::fs::read_dir(temp_root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let orphaned = temp_dir_owner(&name.to_string_lossy()).is_some_and(|owner| owner != pid && !is_alive(owner));
+        if !orphaned || !entry.path().is_dir() {
+            continue;
+        }
+        let size = size_of(&entry.path());
+        match std::fs::remove_dir_all(entry.path()) {
+            Ok(()) => {
+                report.temp_dirs += 1;
+                report.reclaimed_bytes += size;
+            }
+            Err(e) => log::warn!("Failed to remove {}: {}", entry.path().display(), e),
+        }
+    }
+}
+
+/// Remove stale proxies and cache-only folders under `recordings_dir`,
+/// skipping the recording `active`.
+fn prune_recordings(recordings_dir: &Path, active: Option<&str>, settings: &AppSettings, report: &mut CleanupReport) {
+    let Ok(entries) = std::fs::read_dir(recordings_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        if !dir.is_dir() || active == Some(entry.file_name().to_string_lossy().as_ref()) {
+            continue;
+        }
+
+        if !dir.join("meta.json").exists() && !dir.join("frames").exists() {
+            let size = size_of(&dir);
+            match std::fs::remove_dir_all(&dir) {
+                Ok(()) => {
+                    report.orphaned_caches += 1;
+                    report.reclaimed_bytes += size;
+                }
+                Err(e) => log::warn!("Failed to remove {}: {}", dir.display(), e),
+            }
+            continue;
+        }
+
+        let proxy = dir.join(encoder::PREVIEW_PROXY_FILE);
+        if proxy.exists() && !cache::is_fresh(&dir, cache::PREVIEW, &encoder::preview_fingerprint(&dir, settings)) {
+            let size = size_of(&proxy);
+            match std::fs::remove_file(&proxy) {
+                Ok(()) => {
+                    report.stale_proxies += 1;
+                    report.reclaimed_bytes += size;
+                    if let Err(e) = cache::invalidate(&dir, cache::PREVIEW) {
+                        log::warn!("Failed to update cache manifest: {}", e);
+                    }
+                }
+                Err(e) => log::warn!("Failed to remove {}: {}", proxy.display(), e),
+            }
+        }
+    }
+}
+
+/// Remove orphaned export temp dirs, stale proxies and caches of deleted
+/// recordings. Returns what was removed and how much space it freed.
+pub fn cleanup_workspace(settings: &AppSettings) -> Result<CleanupReport> {
+    let recordings_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings");
+    let active = crate::recording::session::active_recording_id();
+
+    let mut report = CleanupReport::default();
+    prune_temp_dirs(&std::env::temp_dir(), std::process::id(), process_alive, &mut report);
+    prune_recordings(&recordings_dir, active.as_deref(), settings, &mut report);
+    log::info!(
+        "Workspace cleanup: {} temp dirs, {} proxies, {} orphaned caches, {} bytes reclaimed",
+        report.temp_dirs,
+        report.stale_proxies,
+        report.orphaned_caches,
+        report.reclaimed_bytes,
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prunes_orphans_but_keeps_live_data() {
+        let temp_root = tempfile::TempDir::new().unwrap();
+        let ours = temp_root.path().join("snappi-export-100-abc");
+        let crashed = temp_root.path().join("snappi-export-200-def");
+        let headless = temp_root.path().join("snappi-export-300-ghi");
+        let unrelated = temp_root.path().join(".tmpXYZ");
+        for dir in [&ours, &crashed, &headless, &unrelated] {
+            std::fs::create_dir_all(dir.join("frames")).unwrap();
+            std::fs::write(dir.join("frames").join("frame_00000000.jpg"), [0u8; 10]).unwrap();
+        }
+
+        let recordings = tempfile::TempDir::new().unwrap();
+        let with_frames = recordings.path().join("rec-1");
+        let deleted = recordings.path().join("rec-2");
+        let active = recordings.path().join("rec-3");
+        std::fs::create_dir_all(with_frames.join("frames")).unwrap();
+        std::fs::write(with_frames.join("meta.json"), "{}").unwrap();
+        std::fs::write(with_frames.join(encoder::PREVIEW_PROXY_FILE), [0u8; 100]).unwrap();
+        std::fs::create_dir_all(&deleted).unwrap();
+        std::fs::write(deleted.join("thumbnail.png"), [0u8; 50]).unwrap();
+        std::fs::create_dir_all(&active).unwrap();
+
+        let mut report = CleanupReport::default();
+        // 300 is another live process (e.g. `snappi export`) mid-encode
+        prune_temp_dirs(temp_root.path(), 100, |pid| pid == 300, &mut report);
+        prune_recordings(recordings.path(), Some("rec-3"), &AppSettings::default(), &mut report);
+
+        assert!(ours.exists() && headless.exists() && unrelated.exists() && !crashed.exists());
+        assert!(process_alive(std::process::id()));
+        // The proxy was never recorded in the cache manifest: stale
+        assert!(with_frames.join("frames").exists() && !with_frames.join(encoder::PREVIEW_PROXY_FILE).exists());
+        assert!(!deleted.exists() && active.exists());
+        assert_eq!((report.temp_dirs, report.stale_proxies, report.orphaned_caches), (1, 1, 1));
+        assert_eq!(report.reclaimed_bytes, 10 + 100 + 50);
+    }
+}
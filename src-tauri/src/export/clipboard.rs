@@ -0,0 +1,171 @@
+//! Copy an exported file to the clipboard, ready to paste into chat apps.
+//!
+//! The file always goes on as a file drop (`CF_HDROP`, what Explorer's
+//! "Copy" puts there), which Slack and Teams upload as an attachment with
+//! GIF animation intact. PNG and GIF exports are also placed as image data
+//! (`CF_DIB`, plus the registered `PNG` format that keeps transparency), so
+//! image editors and rich text fields can paste them inline; for a GIF
+//! that is its first frame.
+
+use anyhow::{bail, Context, Result};
+use image::RgbaImage;
+use std::path::Path;
+
+/// Size of the `DROPFILES` header preceding the file list
+const DROPFILES_SIZE: u32 = 20;
+
+/// Size of a `BITMAPINFOHEADER`
+const BITMAPINFOHEADER_SIZE: u32 = 40;
+
+/// `CF_HDROP` payload: a `DROPFILES` header followed by the paths in
+/// UTF-16, each null-terminated, and a final null.
+pub fn dropfiles_bytes(paths: &[&Path]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&DROPFILES_SIZE.to_le_bytes()); // pFiles: offset of the list
+    bytes.extend_from_slice(&[0; 8]); // pt
+    bytes.extend_from_slice(&0u32.to_le_bytes()); // fNC
+    bytes.extend_from_slice(&1u32.to_le_bytes()); // fWide: UTF-16 paths
+    for path in paths {
+        for unit in path.to_string_lossy().encode_utf16().chain([0]) {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+    }
+    bytes.extend_from_slice(&[0, 0]);
+    bytes
+}
+
+/// `CF_DIB` payload: a `BITMAPINFOHEADER` followed by 32-bit BGRA rows,
+/// bottom-up.
+pub fn dib_bytes(image: &RgbaImage) -> Vec<u8> {
+    let (width, height) = image.dimensions();
+    let image_size = width * height * 4;
+    let mut bytes = Vec::with_capacity((BITMAPINFOHEADER_SIZE + image_size) as usize);
+    bytes.extend_from_slice(&BITMAPINFOHEADER_SIZE.to_le_bytes());
+    bytes.extend_from_slice(&(width as i32).to_le_bytes());
+    // Positive height: bottom-up rows
+    bytes.extend_from_slice(&(height as i32).to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // biPlanes
+    bytes.extend_from_slice(&32u16.to_le_bytes()); // biBitCount
+    bytes.extend_from_slice(&0u32.to_le_bytes()); // biCompression: BI_RGB
+    bytes.extend_from_slice(&image_size.to_le_bytes());
+    bytes.extend_from_slice(&[0; 16]); // resolution and palette fields
+    for row in image.as_raw().chunks_exact(width as usize * 4).rev() {
+        for px in row.chunks_exact(4) {
+            bytes.extend_from_slice(&[px[2], px[1], px[0], px[3]]);
+        }
+    }
+    bytes
+}
+
+/// Whether the file at `path` is also put on the clipboard as an image.
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("png") || e.eq_ignore_ascii_case("gif"))
+}
+
+/// Put `path` on the clipboard as a file, and as an image for PNG and GIF.
+pub fn copy_file(path: &Path) -> Result<()> {
+    if !path.is_file() {
+        bail!("File not found: {}", path.display());
+    }
+    let path = std::fs::canonicalize(path)?;
+    // `canonicalize` gives a verbatim `\\?\` path, which some apps can't open
+    let path = match path.to_str().and_then(|p| p.strip_prefix(r"\\?\")) {
+        Some(plain) if !plain.starts_with("UNC\\") => std::path::PathBuf::from(plain),
+        _ => path,
+    };
+    let image = if is_image(&path) {
+        Some(image::open(&path).with_context(|| format!("Cannot read {}", path.display()))?.to_rgba8())
+    } else {
+        None
+    };
+    set_clipboard(&path, image.as_ref())?;
+    log::info!("Copied {} to the clipboard", path.display());
+    Ok(())
+}
+
+#[cfg(windows)]
+fn set_clipboard(path: &Path, image: Option<&RgbaImage>) -> Result<()> {
+    use windows::core::w;
+    use windows::Win32::Foundation::{GlobalFree, HANDLE, HWND};
+    use windows::Win32::System::DataExchange::{
+        CloseClipboard, EmptyClipboard, OpenClipboard, RegisterClipboardFormatW, SetClipboardData,
+    };
+    use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+
+    const CF_DIB: u32 = 8;
+    const CF_HDROP: u32 = 15;
+
+    /// Hand `bytes` to the clipboard as `format`; the clipboard then owns
+    /// the memory.
+    unsafe fn set(format: u32, bytes: &[u8]) -> Result<()> {
+        let memory = GlobalAlloc(GMEM_MOVEABLE, bytes.len())?;
+        let target = GlobalLock(memory) as *mut u8;
+        if target.is_null() {
+            let _ = GlobalFree(memory);
+            bail!("Failed to lock clipboard memory");
+        }
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), target, bytes.len());
+        let _ = GlobalUnlock(memory);
+        if let Err(e) = SetClipboardData(format, HANDLE(memory.0)) {
+            let _ = GlobalFree(memory);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    unsafe {
+        OpenClipboard(HWND::default()).context("The clipboard is in use by another application")?;
+        let result = (|| -> Result<()> {
+            EmptyClipboard()?;
+            set(CF_HDROP, &dropfiles_bytes(&[path]))?;
+            if let Some(image) = image {
+                set(CF_DIB, &dib_bytes(image))?;
+                let png_format = RegisterClipboardFormatW(w!("PNG"));
+                if png_format != 0 {
+                    let mut png = Vec::new();
+                    image.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)?;
+                    set(png_format, &png)?;
+                }
+            }
+            Ok(())
+        })();
+        let _ = CloseClipboard();
+        result
+    }
+}
+
+#[cfg(not(windows))]
+fn set_clipboard(_path: &Path, _image: Option<&RgbaImage>) -> Result<()> {
+    bail!("Copying to the clipboard is only supported on Windows")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_payloads() {
+        let drop = dropfiles_bytes(&[Path::new("C:\\a.gif")]);
+        assert_eq!(&drop[..4], &DROPFILES_SIZE.to_le_bytes());
+        assert_eq!(&drop[16..20], &1u32.to_le_bytes());
+        let list: Vec<u16> = drop[20..].chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        assert_eq!(String::from_utf16(&list).unwrap(), "C:\\a.gif\0\0");
+
+        // 2x2: top row red, bottom row blue; DIB rows are bottom-up BGRA
+        let mut image = RgbaImage::new(2, 2);
+        for x in 0..2 {
+            image.put_pixel(x, 0, image::Rgba([255, 0, 0, 255]));
+            image.put_pixel(x, 1, image::Rgba([0, 0, 255, 128]));
+        }
+        let dib = dib_bytes(&image);
+        assert_eq!(dib.len(), 40 + 16);
+        assert_eq!(&dib[4..8], &2i32.to_le_bytes());
+        assert_eq!(&dib[40..44], &[255, 0, 0, 128]);
+        assert_eq!(&dib[48..52], &[0, 0, 255, 255]);
+
+        assert!(is_image(Path::new("out.GIF")));
+        assert!(!is_image(Path::new("out.mp4")));
+    }
+}
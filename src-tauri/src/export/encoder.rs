@@ -1,13 +1,29 @@
 use crate::config::defaults::OutputStyle;
-use crate::config::{AppSettings, ExportFormat, QualityPreset, RecordingEvent, RecordingMeta};
-use crate::engine::compositor::{ClickEffect, Compositor, KeyOverlay};
+use crate::config::{AppSettings, AudioOnlyCodec, Bookmark, ExportFormat, GifOptions, OutputSize, QualityPreset, RecordingEvent, RecordingMeta, TIMELAPSE_OUTPUT_FPS};
+use crate::engine::compositor::{ClickEffect, Compositor, KeyOverlay, UiHighlight};
+use crate::engine::analyzer::event_timestamp;
+use crate::engine::annotation::Annotation;
 use crate::engine::cursor_smoother::CursorSmoother;
-use crate::engine::preprocessor::preprocess;
+use crate::engine::preprocessor::{preprocess, PreprocessConfig};
+use crate::engine::privacy_review::{self, PrivacyReport};
+use crate::engine::recording_compare;
+use crate::engine::redaction::{self, Redaction};
 use crate::engine::frame_differ;
-use crate::engine::scene_splitter::{self, split_into_scenes};
-use crate::engine::zoom_planner::generate_zoom_plan;
+use crate::engine::app_exclusion::AppExclusion;
+use crate::engine::scene_splitter::{self, split_into_scenes_with_breaks};
+use crate::engine::scene_style::{self, SceneEffect, SceneStyleOverride};
+use crate::engine::timeline_cut::{self, CutRange, TimeMap};
+use crate::engine::typed_text::{self, TypedCaption};
+use crate::engine::zoom_planner::{generate_zoom_plan, ZoomKeyframe};
 use chrono::DateTime;
+use crate::export::cache;
+use crate::export::chapters::{self, Chapter};
+use crate::export::filename;
+use crate::export::frame_index::{self, read_frame_timestamps, FrameIndex, OutputClock};
 use crate::export::presets::EncodingParams;
+use crate::export::split;
+use crate::export::warm_cache::WarmCache;
+use crate::recording::frame_store::FrameSource;
 use anyhow::Result;
 use std::process::Command;
 
@@ -37,20 +53,91 @@ fn save_rgb_as_jpeg(
     Ok(())
 }
 
-/// Generate export filename from recording start_time (RFC3339) as YYYYMMDD_hhmmss.
-fn export_filename(start_time: &str, format: &ExportFormat) -> String {
-    let ext = match format {
+/// Intermediate composed-frame format. PNG keeps the alpha channel for
+/// transparent exports; everything else stays on JPEG.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IntermediateFormat {
+    Jpeg,
+    Png,
+}
+
+impl IntermediateFormat {
+    /// PNG only when the background is transparent and the output format can
+    /// carry alpha (VP9 WebM / ProRes 4444 MOV).
+    fn for_export(format: &ExportFormat, settings: &AppSettings) -> Self {
+        let transparent = matches!(settings.style.background, crate::config::BackgroundConfig::Transparent);
+        if transparent && matches!(format, ExportFormat::WebM | ExportFormat::Mov) {
+            IntermediateFormat::Png
+        } else {
+            IntermediateFormat::Jpeg
+        }
+    }
+
+    fn has_alpha(self) -> bool {
+        self == IntermediateFormat::Png
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            IntermediateFormat::Jpeg => "jpg",
+            IntermediateFormat::Png => "png",
+        }
+    }
+
+    /// ffmpeg image2 input pattern for composed frames in `frames_dir`.
+    fn input_pattern(self, frames_dir: &std::path::Path) -> String {
+        frames_dir
+            .join(format!("frame_%08d.{}", self.extension()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    fn save(self, frame: image::RgbaImage, path: &std::path::Path) -> Result<()> {
+        match self {
+            IntermediateFormat::Jpeg => {
+                // RGBA→RGB で JPEG のアルファ非対応にも対応
+                let rgb_frame = image::DynamicImage::ImageRgba8(frame).to_rgb8();
+                save_rgb_as_jpeg(&rgb_frame, path, INTERMEDIATE_JPEG_QUALITY)
+            }
+            IntermediateFormat::Png => {
+                frame.save_with_format(path, image::ImageFormat::Png)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Output path for an export: the rendered filename template inside the
+/// save directory, suffixed if a file with that name already exists.
+fn export_output_path(
+    output_dir: &std::path::Path,
+    meta: &RecordingMeta,
+    settings: &AppSettings,
+    format: &ExportFormat,
+) -> std::path::PathBuf {
+    let extension = match format {
         ExportFormat::Mp4 => "mp4",
         ExportFormat::Gif => "gif",
         ExportFormat::WebM => "webm",
+        ExportFormat::WebP => "webp",
+        ExportFormat::Apng => "png",
+        ExportFormat::Mov => "mov",
+        ExportFormat::AudioOnly => settings.output.audio_only_codec.extension(),
     };
-    if let Ok(dt) = DateTime::parse_from_rfc3339(start_time) {
-        format!("{}.{}", dt.format("%Y%m%d_%H%M%S"), ext)
-    } else {
-        // Fallback: use current time
-        let now = chrono::Local::now();
-        format!("{}.{}", now.format("%Y%m%d_%H%M%S"), ext)
-    }
+    // Fallback: use current time
+    let start_time = DateTime::parse_from_rfc3339(&meta.start_time)
+        .unwrap_or_else(|_| chrono::Local::now().fixed_offset());
+    let name = filename::render_filename(
+        &settings.output.filename_template,
+        &filename::FilenameContext {
+            start_time,
+            recording_id: &meta.id,
+            window_title: meta.window_title.as_deref(),
+            speed: meta.timelapse_speed(),
+            extension,
+        },
+    );
+    filename::unique_path(output_dir, &name)
 }
 
 pub fn export(
@@ -58,8 +145,21 @@ pub fn export(
     format: &ExportFormat,
     quality: &QualityPreset,
     settings: &AppSettings,
+    cuts: &[CutRange],
+    gif_options: &GifOptions,
+    output_size: Option<&OutputSize>,
     progress: Option<&ProgressFn>,
 ) -> Result<String> {
+    // Keyframes saved from the Timeline replace the generated zoom plan
+    let saved_keyframes = get_keyframes(recording_id);
+    if !saved_keyframes.is_empty() {
+        log::info!("Using {} saved keyframes for recording {}", saved_keyframes.len(), recording_id);
+        return export_with_custom_keyframes(
+            recording_id, saved_keyframes, format, quality, settings, cuts, gif_options, output_size, progress,
+        );
+    }
+    let _exporting = super::storage::ExportLock::acquire(recording_id);
+
     let recording_dir = dirs::video_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
         .join("Snappi")
@@ -70,19 +170,40 @@ pub fn export(
     let meta_str = std::fs::read_to_string(&meta_path)?;
     let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
 
-    let params = EncodingParams::from_preset(quality, meta.screen_width, meta.screen_height);
+    let params = EncodingParams::from_preset(quality, meta.screen_width, meta.screen_height, output_size)
+        .with_gif_options(gif_options.clone())
+        .with_padding(settings.style.padding)
+        .with_aspect(settings.output.aspect, settings.output.aspect_fit, meta.screen_width, meta.screen_height);
     let style = OutputStyle::from_settings(&params, settings);
+    let time_map = resolve_time_map(&recording_dir, &meta, settings, cuts);
+    let intermediate = IntermediateFormat::for_export(format, settings);
 
     let output_dir = std::path::PathBuf::from(&settings.output.save_directory);
     std::fs::create_dir_all(&output_dir)?;
 
-    let output_path = output_dir.join(export_filename(&meta.start_time, format));
+    let output_path = export_output_path(&output_dir, &meta, settings, format);
+
+    // Audio-only: no frames to compose
+    if *format == ExportFormat::AudioOnly {
+        if let Some(cb) = progress { cb("encoding", 0.0); }
+        let ffmpeg = find_ffmpeg()?;
+        encode_audio_only(&ffmpeg, &output_path, &recording_dir, &time_map, settings.output.audio_only_codec)?;
+        if let Some(cb) = progress { cb("complete", 1.0); }
+        return Ok(output_path.to_string_lossy().to_string());
+    }
 
     // Compose frames with effects engine
     log::info!("Starting effects composition for recording {}", recording_id);
     if let Some(cb) = progress { cb("composing", 0.0); }
-    let (temp_dir, actual_fps) = compose_frames(&recording_dir, &meta, settings, style, progress)?;
-    let composed_frames_dir = temp_dir.path().join("frames");
+    let key = composition_key(&recording_dir, settings, quality, output_size, cuts, None, intermediate);
+    let composed = compose_cached(key, || {
+        compose_frames(&recording_dir, &meta, settings, style, &time_map, intermediate, progress)
+    })?;
+    let composed_frames_dir = composed.frames_dir();
+    let actual_fps = composed.fps;
+    let audio = composed.audio_timing(&time_map);
+    let split_points = plan_export_split(recording_id, settings, format, &time_map, &composed);
+    let params = params.with_forced_keyframes(split_points.clone());
     log::info!("Effects composition complete (actual fps: {:.1}), encoding...", actual_fps);
 
     if let Some(cb) = progress { cb("encoding", 0.8); }
@@ -90,22 +211,78 @@ pub fn export(
 
     match format {
         ExportFormat::Mp4 => {
-            encode_mp4(&ffmpeg, &composed_frames_dir, &output_path, &params, &recording_dir, actual_fps)?;
+            encode_mp4(&ffmpeg, &composed_frames_dir, &output_path, &params, &recording_dir, actual_fps, &audio)?;
         }
         ExportFormat::Gif => {
             encode_gif(&ffmpeg, &composed_frames_dir, &output_path, &params, actual_fps)?;
         }
         ExportFormat::WebM => {
-            encode_webm(&ffmpeg, &composed_frames_dir, &output_path, &params, &recording_dir, actual_fps)?;
+            encode_webm(&ffmpeg, &composed_frames_dir, &output_path, &params, &recording_dir, actual_fps, &audio, intermediate)?;
+        }
+        ExportFormat::WebP => {
+            encode_webp(&ffmpeg, &composed_frames_dir, &output_path, &params, actual_fps)?;
         }
+        ExportFormat::Apng => {
+            encode_apng(&ffmpeg, &composed_frames_dir, &output_path, &params, actual_fps)?;
+        }
+        ExportFormat::Mov => {
+            encode_mov(&ffmpeg, &composed_frames_dir, &output_path, &recording_dir, actual_fps, &audio, intermediate)?;
+        }
+        ExportFormat::AudioOnly => unreachable!("audio-only exports return before composition"),
     }
-    // temp_dir dropped here → composed frames cleaned up automatically
+    let chapters = bookmark_chapters(recording_id, settings, format, &time_map, &composed, &split_points);
+    write_chapters(&ffmpeg, &output_path, &chapters, &composed);
+    // composed frames stay in COMPOSED_CACHE for a following export
+    let output_path = split_export(&ffmpeg, output_path, &split_points)?;
 
     if let Some(cb) = progress { cb("complete", 1.0); }
     log::info!("Export complete: {}", output_path.display());
     Ok(output_path.to_string_lossy().to_string())
 }
 
+/// Plain export: the recorded frames straight to an MP4 at native
+/// resolution, skipping the compositor (no zoom, cursor, background or other
+/// effects, and no cuts). A quick raw copy, and a way out when composition
+/// fails.
+pub fn export_plain(
+    recording_id: &str,
+    quality: &QualityPreset,
+    settings: &AppSettings,
+    progress: Option<&ProgressFn>,
+) -> Result<String> {
+    let _exporting = super::storage::ExportLock::acquire(recording_id);
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+
+    let meta_str = std::fs::read_to_string(recording_dir.join("meta.json"))?;
+    let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
+    let frame_count = read_frame_count(&recording_dir);
+    if frame_count == 0 {
+        return Err(anyhow::anyhow!("Recording {} has no frames", recording_id));
+    }
+    let params = EncodingParams::from_preset(quality, meta.screen_width, meta.screen_height, None);
+    let fps = if meta.duration_ms > 0 {
+        frame_count as f64 * 1000.0 / meta.duration_ms as f64
+    } else {
+        meta.fps as f64
+    };
+
+    let output_dir = std::path::PathBuf::from(&settings.output.save_directory);
+    std::fs::create_dir_all(&output_dir)?;
+    let output_path = export_output_path(&output_dir, &meta, settings, &ExportFormat::Mp4);
+
+    if let Some(cb) = progress { cb("encoding", 0.0); }
+    let ffmpeg = find_ffmpeg()?;
+    encode_plain_mp4(&ffmpeg, &recording_dir, &output_path, params.crf, fps)?;
+
+    if let Some(cb) = progress { cb("complete", 1.0); }
+    log::info!("Plain export complete: {}", output_path.display());
+    Ok(output_path.to_string_lossy().to_string())
+}
+
 /// Generate zoom keyframes for a recording (used by Timeline UI).
 pub fn generate_keyframes_for_recording(
     recording_id: &str,
@@ -122,11 +299,14 @@ pub fn generate_keyframes_for_recording(
     let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
 
     let raw_events = load_events(&recording_dir).unwrap_or_default();
-    let preprocessed = preprocess(&raw_events);
+    let preprocessed = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects));
     let events = preprocessed.events;
 
-    let mut scenes = split_into_scenes(
-        &events,
+    let exclusion = app_exclusion(&recording_dir, settings);
+    let plan_events = exclusion.filter(&events);
+    let mut scenes = split_into_scenes_with_breaks(
+        &plan_events,
+        &pause_breaks(&meta),
         meta.screen_width as f64,
         meta.screen_height as f64,
         settings.effects.max_zoom,
@@ -135,22 +315,13 @@ pub fn generate_keyframes_for_recording(
     // Frame diff pre-pass (coarser sampling for UI responsiveness)
     let mut change_regions: Vec<frame_differ::ChangeRegion> = Vec::new();
     if settings.effects.auto_zoom_enabled {
-        let frames_dir = recording_dir.join("frames");
         let frame_count = read_frame_count(&recording_dir);
         let cursor_for_diff = extract_mouse_positions(&events);
         let diff_config = frame_differ::DiffConfig {
             sample_interval: 10,
-            ..frame_differ::DiffConfig::default()
+            ..frame_differ::DiffConfig::from_settings(&settings.effects)
         };
-        if let Ok(diff_result) = frame_differ::detect_frame_changes(
-            &frames_dir,
-            frame_count,
-            meta.duration_ms,
-            &cursor_for_diff,
-            meta.screen_width,
-            meta.screen_height,
-            &diff_config,
-        ) {
+        if let Ok(diff_result) = detect_frame_changes_cached(&recording_dir, &meta, &cursor_for_diff, frame_count, &diff_config) {
             change_regions = diff_result.regions;
             if settings.effects.frame_diff_enabled {
                 scene_splitter::expand_scenes_with_change_regions(
@@ -159,16 +330,20 @@ pub fn generate_keyframes_for_recording(
                     meta.screen_width as f64,
                     meta.screen_height as f64,
                     settings.effects.max_zoom,
+                    &frame_differ::CaptureMapping::from_meta(&meta),
                 );
             }
         }
     }
 
+    let edits = load_recording_list(&recording_dir, SCENE_EDITS_FILE);
+    let mut scenes = replay_scene_edits(scenes, &edits, &plan_events, &meta, settings, &change_regions);
+
     // Phase A (Issue #23): ui_events.jsonl の矩形情報を各シーンに付与。
     // scene.ui_rect が立ったシーンは zoom_planner で矩形ベースのズーム計算に切り替わる。
     crate::engine::ui_context::attach_ui_rects_to_scenes(
         &mut scenes,
-        &raw_events,
+        &exclusion.filter(&raw_events),
         meta.screen_width as f64,
         meta.screen_height as f64,
     );
@@ -182,7 +357,8 @@ pub fn generate_keyframes_for_recording(
     Ok(keyframes)
 }
 
-/// Get scene debug info for a recording (used by Timeline UI).
+/// Get scene debug info for a recording (used by Timeline UI), with the
+/// saved scene edits applied.
 pub fn get_recording_scenes(
     recording_id: &str,
     settings: &AppSettings,
@@ -198,52 +374,356 @@ pub fn get_recording_scenes(
     let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
 
     let raw_events = load_events(&recording_dir).unwrap_or_default();
-    let preprocessed = preprocess(&raw_events);
+    let preprocessed = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects));
     let events = preprocessed.events;
 
-    let mut scenes = split_into_scenes(
-        &events,
+    let plan_events = app_exclusion(&recording_dir, settings).filter(&events);
+    let mut scenes = split_into_scenes_with_breaks(
+        &plan_events,
+        &pause_breaks(&meta),
         meta.screen_width as f64,
         meta.screen_height as f64,
         settings.effects.max_zoom,
     );
 
     // Frame diff pre-pass (coarser sampling for UI responsiveness)
+    let mut change_regions: Vec<frame_differ::ChangeRegion> = Vec::new();
     if settings.effects.auto_zoom_enabled {
-        let frames_dir = recording_dir.join("frames");
         let frame_count = read_frame_count(&recording_dir);
         let cursor_for_diff = extract_mouse_positions(&events);
         let diff_config = frame_differ::DiffConfig {
             sample_interval: 10,
-            ..frame_differ::DiffConfig::default()
+            ..frame_differ::DiffConfig::from_settings(&settings.effects)
         };
-        if let Ok(diff_result) = frame_differ::detect_frame_changes(
-            &frames_dir,
-            frame_count,
-            meta.duration_ms,
-            &cursor_for_diff,
-            meta.screen_width,
-            meta.screen_height,
-            &diff_config,
-        ) {
+        if let Ok(diff_result) = detect_frame_changes_cached(&recording_dir, &meta, &cursor_for_diff, frame_count, &diff_config) {
+            change_regions = diff_result.regions;
             if settings.effects.frame_diff_enabled {
                 scene_splitter::expand_scenes_with_change_regions(
                     &mut scenes,
-                    &diff_result.regions,
+                    &change_regions,
                     meta.screen_width as f64,
                     meta.screen_height as f64,
                     settings.effects.max_zoom,
+                    &frame_differ::CaptureMapping::from_meta(&meta),
                 );
             }
         }
     }
 
-    Ok(scenes)
+    let edits = load_recording_list(&recording_dir, SCENE_EDITS_FILE);
+    Ok(replay_scene_edits(scenes, &edits, &plan_events, &meta, settings, &change_regions))
+}
+
+/// Stats of a recording for `compare_recordings`. Scenes are split from the
+/// events only (no frame diff), which is enough to count them.
+fn recording_stats(recording_id: &str, settings: &AppSettings) -> Result<crate::config::RecordingStats> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+
+    let meta_path = recording_dir.join("meta.json");
+    let meta_str = std::fs::read_to_string(&meta_path)?;
+    let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
+
+    let raw_events = load_events(&recording_dir).unwrap_or_default();
+    let events = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects)).events;
+    let scenes = split_into_scenes_with_breaks(
+        &events,
+        &pause_breaks(&meta),
+        meta.screen_width as f64,
+        meta.screen_height as f64,
+        settings.effects.max_zoom,
+    );
+
+    Ok(recording_compare::collect_stats(&meta.id, meta.duration_ms, &events, scenes.len()))
+}
+
+/// Compare two recordings (e.g. two QA runs of the same flow).
+pub fn compare_recordings(
+    id_a: &str,
+    id_b: &str,
+    settings: &AppSettings,
+) -> Result<crate::config::RecordingComparison> {
+    let a = recording_stats(id_a, settings)?;
+    let b = recording_stats(id_b, settings)?;
+    Ok(recording_compare::compare(a, b))
+}
+
+/// シーン境界プレビュー画像の横幅 (px)。Timeline のツールチップに収まるサイズ。
+const BOUNDARY_PREVIEW_WIDTH: u32 = 320;
+
+/// Render before/after preview images for the boundary between `scene_id` and
+/// the following scene (used by Timeline UI when deciding to merge/split).
+///
+/// The "before" image is the last frame of `scene_id` with its bbox drawn, the
+/// "after" image is the first frame of the next scene with its bbox drawn.
+/// Images are written to `{recording_dir}/previews/`.
+pub fn generate_scene_boundary_preview(
+    recording_id: &str,
+    scene_id: u32,
+    settings: &AppSettings,
+) -> Result<crate::config::SceneBoundaryPreview> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+
+    let meta_path = recording_dir.join("meta.json");
+    let meta_str = std::fs::read_to_string(&meta_path)?;
+    let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
+
+    let scenes = get_recording_scenes(recording_id, settings)?;
+    scene_boundary_preview(&recording_dir, &meta, &scenes, scene_id)
+}
+
+/// [`generate_scene_boundary_preview`] for the recording in `recording_dir`
+/// split into `scenes`.
+fn scene_boundary_preview(
+    recording_dir: &std::path::Path,
+    meta: &RecordingMeta,
+    scenes: &[scene_splitter::Scene],
+    scene_id: u32,
+) -> Result<crate::config::SceneBoundaryPreview> {
+    let idx = scenes
+        .iter()
+        .position(|s| s.id == scene_id)
+        .ok_or_else(|| anyhow::anyhow!("Scene {} not found", scene_id))?;
+    let before = &scenes[idx];
+    let after = scenes
+        .get(idx + 1)
+        .ok_or_else(|| anyhow::anyhow!("Scene {} has no following scene", scene_id))?;
+
+    let previews_dir = recording_dir.join("previews");
+    std::fs::create_dir_all(&previews_dir)?;
+
+    let before_path = previews_dir.join(format!("boundary_{}_before.png", scene_id));
+    let after_path = previews_dir.join(format!("boundary_{}_after.png", scene_id));
+    render_scene_preview(recording_dir, meta, before.end_ms, &before.bbox, &before_path)?;
+    render_scene_preview(recording_dir, meta, after.start_ms, &after.bbox, &after_path)?;
+
+    Ok(crate::config::SceneBoundaryPreview {
+        scene_id,
+        boundary_ms: before.end_ms + (after.start_ms.saturating_sub(before.end_ms)) / 2,
+        before_path: before_path.to_string_lossy().to_string(),
+        after_path: after_path.to_string_lossy().to_string(),
+    })
+}
+
+/// Save a downscaled raw frame at `time_ms` with `bbox` (screen coords) outlined.
+fn render_scene_preview(
+    recording_dir: &std::path::Path,
+    meta: &RecordingMeta,
+    time_ms: u64,
+    bbox: &crate::engine::analyzer::Rect,
+    output: &std::path::Path,
+) -> Result<()> {
+    let frame_idx = FrameIndex::load(recording_dir, meta, read_frame_count(recording_dir)).frame_at(time_ms);
+    let img = FrameSource::open(&recording_dir.join("frames"))?.load(frame_idx)?.to_rgba8();
+
+    let scale = BOUNDARY_PREVIEW_WIDTH as f64 / img.width().max(1) as f64;
+    let preview_height = ((img.height() as f64 * scale) as u32).max(1);
+    let mut preview = image::imageops::resize(
+        &img,
+        BOUNDARY_PREVIEW_WIDTH,
+        preview_height,
+        image::imageops::FilterType::Triangle,
+    );
+
+    // Window mode: bbox is in screen coords, frames are window-relative
+    let (off_x, off_y) = if meta.recording_mode.as_deref() == Some("window") {
+        meta.window_initial_rect.map(|r| (r[0], r[1])).unwrap_or((0.0, 0.0))
+    } else {
+        (0.0, 0.0)
+    };
+    draw_rect_outline(
+        &mut preview,
+        (bbox.x - off_x) * scale,
+        (bbox.y - off_y) * scale,
+        bbox.width * scale,
+        bbox.height * scale,
+        image::Rgba([59, 130, 246, 255]),
+        2,
+    );
+
+    preview.save(output)?;
+    Ok(())
+}
+
+/// JPEG quality of Timeline preview frames (shown scaled down while scrubbing)
+const PREVIEW_FRAME_JPEG_QUALITY: u8 = 85;
+
+/// Render one frame as it would be exported, for the Timeline UI while
+/// scrubbing. `time_ms` is on the recording timeline (cuts are not applied).
+///
+/// The camera springs are run from the start of the recording through the
+/// keyframes up to `time_ms`, so zoom and pan match the export. Without
+/// `keyframes` the saved keyframes (or else the generated zoom plan) are
+/// used. The frame is written to `{recording_dir}/previews/` and its path
+/// returned.
+pub fn render_preview_frame(
+    recording_id: &str,
+    time_ms: u64,
+    keyframes: Option<Vec<ZoomKeyframe>>,
+    settings: &AppSettings,
+) -> Result<String> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+
+    let meta_str = std::fs::read_to_string(recording_dir.join("meta.json"))?;
+    let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
+    let frame_count = read_frame_count(&recording_dir);
+    if frame_count == 0 {
+        return Err(anyhow::anyhow!("Recording {} has no frames", recording_id));
+    }
+
+    let keyframes = match keyframes {
+        Some(keyframes) => keyframes,
+        None => {
+            let saved = get_keyframes(recording_id);
+            if saved.is_empty() { generate_keyframes_for_recording(recording_id, settings)? } else { saved }
+        }
+    };
+
+    let params = EncodingParams::from_preset(
+        &settings.output.default_quality,
+        meta.screen_width,
+        meta.screen_height,
+        None,
+    )
+    .with_padding(settings.style.padding)
+    .with_aspect(settings.output.aspect, settings.output.aspect_fit, meta.screen_width, meta.screen_height);
+    let style = OutputStyle::from_settings(&params, settings);
+    let time_map = TimeMap::new(&[], &[]);
+
+    let raw_events = load_events(&recording_dir).unwrap_or_default();
+    let events = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects)).events;
+
+    // Window mode: event coords are screen coords, frames are window-relative
+    let (off_x, off_y) = if meta.recording_mode.as_deref() == Some("window") {
+        meta.window_initial_rect.map(|r| (r[0], r[1])).unwrap_or((0.0, 0.0))
+    } else {
+        (0.0, 0.0)
+    };
+    let positions: Vec<(u64, f64, f64)> = extract_mouse_positions(&events)
+        .into_iter()
+        .map(|(t, x, y)| (t, x - off_x, y - off_y))
+        .collect();
+    let cursor_positions = if settings.effects.cursor_smoothing && !positions.is_empty() {
+        CursorSmoother::new().smooth(&positions)
+    } else {
+        positions
+    };
+    let scene_styles: Vec<SceneStyleOverride> = load_recording_list(&recording_dir, SCENE_STYLES_FILE);
+    let click_effects = {
+        let mut effects = scene_click_effects(&events, style.click_ring_duration_ms, settings, &scene_styles);
+        for eff in &mut effects {
+            eff.x -= off_x;
+            eff.y -= off_y;
+        }
+        effects
+    };
+    let key_overlays = scene_key_overlays(&events, style.key_badge_duration_ms, settings, &scene_styles);
+
+    let mut compositor = Compositor::new(style, meta.screen_width, meta.screen_height);
+    compositor.set_reduced_motion(settings.effects.reduced_motion);
+    compositor.set_os_cursor_scale(meta.os_cursor_scale());
+    compositor.set_monitor_scale(meta.display_scale());
+    compositor.set_ui_highlights(build_ui_highlights(&events, &meta, settings, &time_map));
+    compositor.set_typed_captions(build_typed_captions(&events, &meta, settings, &time_map));
+    compositor.set_spotlight_clicks(build_spotlight_clicks(&events, settings, &time_map));
+    compositor.set_redactions(build_redactions(&recording_dir, &events, &meta, settings, &time_map));
+    compositor.set_annotations(load_recording_list::<Annotation>(&recording_dir, ANNOTATIONS_FILE));
+    compositor.set_scene_styles(scene_styles);
+    compositor.set_clip_duration(meta.duration_ms);
+
+    // Camera: replay every output tick before the preview frame, including
+    // the ticks the export repeats over capture gaps
+    let frame_index = FrameIndex::load(&recording_dir, &meta, frame_count);
+    let target_idx = frame_index.frame_at(time_ms);
+    let dt = if meta.duration_ms > 0 && frame_count > 1 {
+        meta.duration_ms as f64 / frame_count as f64 / 1000.0
+    } else {
+        1.0 / meta.fps.max(1) as f64
+    };
+    let mut kf_index = 0;
+    let pauses = pause_breaks(&meta);
+    let mut pause_index = 0;
+    let mut apply_until = |compositor: &mut Compositor, t: u64| {
+        while kf_index < keyframes.len() && keyframes[kf_index].time_ms <= t {
+            compositor.apply_keyframe(&keyframes[kf_index]);
+            kf_index += 1;
+        }
+        while pause_index < pauses.len() && pauses[pause_index] <= t {
+            compositor.cut_camera();
+            pause_index += 1;
+        }
+    };
+    let mut clock = OutputClock::new(1.0 / dt, paused_ms(&frame_index, &meta));
+    for frame_idx in 0..target_idx {
+        let (start, end) = frame_output_span(&frame_index, &time_map, frame_idx, clock.step_ms());
+        for tick in clock.ticks(start, end) {
+            apply_until(&mut compositor, tick.round() as u64);
+            compositor.advance_camera(dt);
+        }
+    }
+    let frame_time_ms = frame_index.time_of(target_idx);
+    apply_until(&mut compositor, frame_time_ms);
+
+    let raw_frame = FrameSource::open(&recording_dir.join("frames"))?.load(target_idx)?.to_rgba8();
+    let composed = compositor.compose_frame(
+        &raw_frame,
+        frame_time_ms,
+        find_cursor_at_time(&cursor_positions, frame_time_ms),
+        &click_effects,
+        key_overlays.iter().rfind(|ko| ko.is_visible(frame_time_ms)),
+        dt,
+    );
+
+    let previews_dir = recording_dir.join("previews");
+    std::fs::create_dir_all(&previews_dir)?;
+    let output = previews_dir.join("frame_preview.jpg");
+    let rgb = image::DynamicImage::ImageRgba8(composed).to_rgb8();
+    save_rgb_as_jpeg(&rgb, &output, PREVIEW_FRAME_JPEG_QUALITY)?;
+    Ok(output.to_string_lossy().to_string())
+}
+
+/// Draw an axis-aligned rectangle outline, clipped to the image.
+fn draw_rect_outline(
+    img: &mut image::RgbaImage,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    color: image::Rgba<u8>,
+    thickness: u32,
+) {
+    let (w, h) = (img.width() as i64, img.height() as i64);
+    let x0 = x.round() as i64;
+    let y0 = y.round() as i64;
+    let x1 = (x + width).round() as i64 - 1;
+    let y1 = (y + height).round() as i64 - 1;
+    let t = thickness as i64;
+    for py in y0.max(0)..=y1.min(h - 1) {
+        for px in x0.max(0)..=x1.min(w - 1) {
+            let on_edge = px < x0 + t || px > x1 - t || py < y0 + t || py > y1 - t;
+            if on_edge {
+                img.put_pixel(px as u32, py as u32, color);
+            }
+        }
+    }
 }
 
 /// Apply scene edits (merge/split) and regenerate keyframes.
 ///
 /// Loads events, creates auto-detected scenes, applies edits, then runs zoom_planner.
+/// `edits` is the whole edit list (scene ids refer to the auto-detected
+/// scenes); it replaces the saved one, and an empty list clears it.
 pub fn apply_scene_edits_for_recording(
     recording_id: &str,
     edits: Vec<crate::engine::scene_splitter::SceneEditOp>,
@@ -263,11 +743,13 @@ pub fn apply_scene_edits_for_recording(
     let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
 
     let raw_events = load_events(&recording_dir).unwrap_or_default();
-    let preprocessed = preprocess(&raw_events);
+    let preprocessed = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects));
     let events = preprocessed.events;
 
-    let mut scenes = split_into_scenes(
-        &events,
+    let plan_events = app_exclusion(&recording_dir, settings).filter(&events);
+    let mut scenes = split_into_scenes_with_breaks(
+        &plan_events,
+        &pause_breaks(&meta),
         meta.screen_width as f64,
         meta.screen_height as f64,
         settings.effects.max_zoom,
@@ -276,22 +758,13 @@ pub fn apply_scene_edits_for_recording(
     // Frame diff expansion (same as get_recording_scenes)
     let mut change_regions: Vec<frame_differ::ChangeRegion> = Vec::new();
     if settings.effects.auto_zoom_enabled {
-        let frames_dir = recording_dir.join("frames");
         let frame_count = read_frame_count(&recording_dir);
         let cursor_for_diff = extract_mouse_positions(&events);
         let diff_config = frame_differ::DiffConfig {
             sample_interval: 10,
-            ..frame_differ::DiffConfig::default()
+            ..frame_differ::DiffConfig::from_settings(&settings.effects)
         };
-        if let Ok(diff_result) = frame_differ::detect_frame_changes(
-            &frames_dir,
-            frame_count,
-            meta.duration_ms,
-            &cursor_for_diff,
-            meta.screen_width,
-            meta.screen_height,
-            &diff_config,
-        ) {
+        if let Ok(diff_result) = detect_frame_changes_cached(&recording_dir, &meta, &cursor_for_diff, frame_count, &diff_config) {
             change_regions = diff_result.regions;
             if settings.effects.frame_diff_enabled {
                 scene_splitter::expand_scenes_with_change_regions(
@@ -300,37 +773,20 @@ pub fn apply_scene_edits_for_recording(
                     meta.screen_width as f64,
                     meta.screen_height as f64,
                     settings.effects.max_zoom,
+                    &frame_differ::CaptureMapping::from_meta(&meta),
                 );
             }
         }
     }
 
-    // Apply manual edits
-    let mut edited_scenes = scene_splitter::apply_scene_edits(
-        &scenes,
-        &edits,
-        &events,
-        meta.screen_width as f64,
-        meta.screen_height as f64,
-        settings.effects.max_zoom,
-    );
-
-    // Re-apply frame diff expansion to edited scenes (merge/split recomputes bbox from
-    // activity points only, so frame_diff regions need to be re-applied)
-    if settings.effects.frame_diff_enabled && !change_regions.is_empty() && !edits.is_empty() {
-        scene_splitter::expand_scenes_with_change_regions(
-            &mut edited_scenes,
-            &change_regions,
-            meta.screen_width as f64,
-            meta.screen_height as f64,
-            settings.effects.max_zoom,
-        );
-    }
+    // Apply manual edits and keep them for later exports
+    save_recording_list(recording_id, SCENE_EDITS_FILE, &edits)?;
+    let mut edited_scenes = replay_scene_edits(scenes, &edits, &plan_events, &meta, settings, &change_regions);
 
     // Phase A (Issue #23): edited_scenes にも UI 矩形を紐付け
     crate::engine::ui_context::attach_ui_rects_to_scenes(
         &mut edited_scenes,
-        &events,
+        &plan_events,
         meta.screen_width as f64,
         meta.screen_height as f64,
     );
@@ -345,6 +801,40 @@ pub fn apply_scene_edits_for_recording(
     Ok((edited_scenes, keyframes))
 }
 
+/// Apply `edits` to the auto-detected scenes. Merge/split recompute the bbox
+/// from activity points only, so frame diff regions are applied again.
+fn replay_scene_edits(
+    scenes: Vec<crate::engine::scene_splitter::Scene>,
+    edits: &[crate::engine::scene_splitter::SceneEditOp],
+    events: &[RecordingEvent],
+    meta: &RecordingMeta,
+    settings: &AppSettings,
+    change_regions: &[frame_differ::ChangeRegion],
+) -> Vec<crate::engine::scene_splitter::Scene> {
+    if edits.is_empty() {
+        return scenes;
+    }
+    let mut edited_scenes = scene_splitter::apply_scene_edits(
+        &scenes,
+        edits,
+        events,
+        meta.screen_width as f64,
+        meta.screen_height as f64,
+        settings.effects.max_zoom,
+    );
+    if settings.effects.frame_diff_enabled && !change_regions.is_empty() {
+        scene_splitter::expand_scenes_with_change_regions(
+            &mut edited_scenes,
+            change_regions,
+            meta.screen_width as f64,
+            meta.screen_height as f64,
+            settings.effects.max_zoom,
+            &frame_differ::CaptureMapping::from_meta(meta),
+        );
+    }
+    edited_scenes
+}
+
 /// Compute activity center for a time range (used by frontend segment merge/add).
 pub fn compute_activity_center_for_recording(
     recording_id: &str,
@@ -363,7 +853,7 @@ pub fn compute_activity_center_for_recording(
     let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
 
     let raw_events = load_events(&recording_dir).unwrap_or_default();
-    let preprocessed = preprocess(&raw_events);
+    let preprocessed = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects));
 
     Ok(scene_splitter::compute_activity_center(
         &preprocessed.events,
@@ -376,7 +866,10 @@ pub fn compute_activity_center_for_recording(
 }
 
 /// Get recording events for Timeline UI (lightweight representation).
-pub fn get_recording_events(recording_id: &str) -> Result<Vec<crate::config::TimelineEvent>> {
+pub fn get_recording_events(
+    recording_id: &str,
+    settings: &AppSettings,
+) -> Result<Vec<crate::config::TimelineEvent>> {
     let recording_dir = dirs::video_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
         .join("Snappi")
@@ -394,6 +887,7 @@ pub fn get_recording_events(recording_id: &str) -> Result<Vec<crate::config::Tim
                 x: Some(*x),
                 y: Some(*y),
                 label: Some(btn.clone()),
+                ..Default::default()
             }),
             RecordingEvent::Key { t, key, modifiers } => {
                 let label = if !modifiers.is_empty() {
@@ -407,6 +901,7 @@ pub fn get_recording_events(recording_id: &str) -> Result<Vec<crate::config::Tim
                     x: None,
                     y: None,
                     label: Some(label),
+                    ..Default::default()
                 })
             }
             RecordingEvent::Scroll { t, x, y, dy, .. } => Some(crate::config::TimelineEvent {
@@ -415,6 +910,7 @@ pub fn get_recording_events(recording_id: &str) -> Result<Vec<crate::config::Tim
                 x: Some(*x),
                 y: Some(*y),
                 label: Some(if *dy > 0.0 { "up" } else { "down" }.to_string()),
+                ..Default::default()
             }),
             RecordingEvent::Focus { t, name, rect, .. } => {
                 let cx = (rect[0] + rect[2]) / 2.0;
@@ -425,9 +921,10 @@ pub fn get_recording_events(recording_id: &str) -> Result<Vec<crate::config::Tim
                     x: Some(cx),
                     y: Some(cy),
                     label: Some(name.clone()),
+                    ..Default::default()
                 })
             }
-            RecordingEvent::WindowFocus { t, title, rect } => {
+            RecordingEvent::WindowFocus { t, title, rect, .. } => {
                 let cx = (rect[0] + rect[2]) / 2.0;
                 let cy = (rect[1] + rect[3]) / 2.0;
                 Some(crate::config::TimelineEvent {
@@ -436,6 +933,7 @@ pub fn get_recording_events(recording_id: &str) -> Result<Vec<crate::config::Tim
                     x: Some(cx),
                     y: Some(cy),
                     label: Some(title.clone()),
+                    ..Default::default()
                 })
             }
             _ => None,
@@ -445,6 +943,58 @@ pub fn get_recording_events(recording_id: &str) -> Result<Vec<crate::config::Tim
         }
     }
 
+    // Drag spans (preprocessor) so the Timeline can render them as ranges
+    let drags = crate::engine::preprocessor::detect_drags(
+        &raw_events,
+        settings.effects.drag_min_distance_px,
+        settings.effects.drag_min_duration_ms,
+    );
+    for drag in drags {
+        timeline_events.push(crate::config::TimelineEvent {
+            time_ms: drag.start_ms,
+            event_type: "drag".to_string(),
+            x: Some(drag.start_x),
+            y: Some(drag.start_y),
+            label: None,
+            end_ms: Some(drag.end_ms),
+            end_x: Some(drag.end_x),
+            end_y: Some(drag.end_y),
+            color: None,
+        });
+    }
+
+    // Where an appended take starts (see recording::append) and where the
+    // recording was paused
+    let meta = std::fs::read_to_string(recording_dir.join("meta.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<RecordingMeta>(&s).ok());
+    let (splices, pauses) = meta.map(|meta| (meta.splices, meta.pauses)).unwrap_or_default();
+    for t in splices {
+        timeline_events.push(crate::config::TimelineEvent {
+            time_ms: t,
+            event_type: "splice".to_string(),
+            ..Default::default()
+        });
+    }
+    for pause in pauses {
+        timeline_events.push(crate::config::TimelineEvent {
+            time_ms: pause.at_ms,
+            event_type: "pause".to_string(),
+            label: Some(format!("{:.1}s", pause.duration_ms as f64 / 1000.0)),
+            ..Default::default()
+        });
+    }
+    for bookmark in load_recording_list::<Bookmark>(&recording_dir, BOOKMARKS_FILE) {
+        timeline_events.push(crate::config::TimelineEvent {
+            time_ms: bookmark.time_ms,
+            event_type: "bookmark".to_string(),
+            label: Some(bookmark.label),
+            color: bookmark.color,
+            ..Default::default()
+        });
+    }
+    timeline_events.sort_by_key(|e| e.time_ms);
+
     Ok(timeline_events)
 }
 
@@ -455,8 +1005,12 @@ pub fn export_with_custom_keyframes(
     format: &ExportFormat,
     quality: &QualityPreset,
     settings: &AppSettings,
+    cuts: &[CutRange],
+    gif_options: &GifOptions,
+    output_size: Option<&OutputSize>,
     progress: Option<&ProgressFn>,
 ) -> Result<String> {
+    let _exporting = super::storage::ExportLock::acquire(recording_id);
     let recording_dir = dirs::video_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
         .join("Snappi")
@@ -467,63 +1021,345 @@ pub fn export_with_custom_keyframes(
     let meta_str = std::fs::read_to_string(&meta_path)?;
     let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
 
-    let params = EncodingParams::from_preset(quality, meta.screen_width, meta.screen_height);
+    let params = EncodingParams::from_preset(quality, meta.screen_width, meta.screen_height, output_size)
+        .with_gif_options(gif_options.clone())
+        .with_padding(settings.style.padding)
+        .with_aspect(settings.output.aspect, settings.output.aspect_fit, meta.screen_width, meta.screen_height);
     let style = crate::config::defaults::OutputStyle::from_settings(&params, settings);
+    let time_map = resolve_time_map(&recording_dir, &meta, settings, cuts);
+    let intermediate = IntermediateFormat::for_export(format, settings);
 
     let output_dir = std::path::PathBuf::from(&settings.output.save_directory);
     std::fs::create_dir_all(&output_dir)?;
 
-    let output_path = output_dir.join(export_filename(&meta.start_time, format));
+    let output_path = export_output_path(&output_dir, &meta, settings, format);
+
+    // Audio-only: no frames to compose
+    if *format == ExportFormat::AudioOnly {
+        if let Some(cb) = progress { cb("encoding", 0.0); }
+        let ffmpeg = find_ffmpeg()?;
+        encode_audio_only(&ffmpeg, &output_path, &recording_dir, &time_map, settings.output.audio_only_codec)?;
+        if let Some(cb) = progress { cb("complete", 1.0); }
+        return Ok(output_path.to_string_lossy().to_string());
+    }
 
     if let Some(cb) = progress { cb("composing", 0.0); }
-    let (temp_dir, actual_fps) = compose_frames_with_keyframes(
-        &recording_dir, &meta, settings, style, keyframes, progress,
-    )?;
-    let composed_frames_dir = temp_dir.path().join("frames");
+    let key = composition_key(&recording_dir, settings, quality, output_size, cuts, Some(&keyframes), intermediate);
+    let composed = compose_cached(key, || {
+        compose_frames_with_keyframes(&recording_dir, &meta, settings, style, keyframes, &time_map, intermediate, progress)
+    })?;
+    let composed_frames_dir = composed.frames_dir();
+    let actual_fps = composed.fps;
+    let audio = composed.audio_timing(&time_map);
+    let split_points = plan_export_split(recording_id, settings, format, &time_map, &composed);
+    let params = params.with_forced_keyframes(split_points.clone());
 
     if let Some(cb) = progress { cb("encoding", 0.8); }
     let ffmpeg = find_ffmpeg()?;
     match format {
-        ExportFormat::Mp4 => encode_mp4(&ffmpeg, &composed_frames_dir, &output_path, &params, &recording_dir, actual_fps)?,
+        ExportFormat::Mp4 => encode_mp4(&ffmpeg, &composed_frames_dir, &output_path, &params, &recording_dir, actual_fps, &audio)?,
         ExportFormat::Gif => encode_gif(&ffmpeg, &composed_frames_dir, &output_path, &params, actual_fps)?,
-        ExportFormat::WebM => encode_webm(&ffmpeg, &composed_frames_dir, &output_path, &params, &recording_dir, actual_fps)?,
+        ExportFormat::WebM => encode_webm(&ffmpeg, &composed_frames_dir, &output_path, &params, &recording_dir, actual_fps, &audio, intermediate)?,
+        ExportFormat::WebP => encode_webp(&ffmpeg, &composed_frames_dir, &output_path, &params, actual_fps)?,
+        ExportFormat::Apng => encode_apng(&ffmpeg, &composed_frames_dir, &output_path, &params, actual_fps)?,
+        ExportFormat::Mov => encode_mov(&ffmpeg, &composed_frames_dir, &output_path, &recording_dir, actual_fps, &audio, intermediate)?,
+        ExportFormat::AudioOnly => unreachable!("audio-only exports return before composition"),
     }
+    let chapters = bookmark_chapters(recording_id, settings, format, &time_map, &composed, &split_points);
+    write_chapters(&ffmpeg, &output_path, &chapters, &composed);
+    let output_path = split_export(&ffmpeg, output_path, &split_points)?;
 
     if let Some(cb) = progress { cb("complete", 1.0); }
     Ok(output_path.to_string_lossy().to_string())
 }
 
-/// Compose frames using custom keyframes (for timeline UI).
-fn compose_frames_with_keyframes(
+/// How long the last export's composed frames are kept for the next one
+const COMPOSED_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Composed frames of the last export, reused when the same recording is
+/// exported again with the same inputs (see [`crate::export::warm_cache`])
+static COMPOSED_CACHE: WarmCache<ComposedFrames> = WarmCache::new(COMPOSED_CACHE_TTL);
+
+/// Fingerprint of everything composition reads: the recording and its
+/// edits, the settings, quality / size (frame dimensions), cuts, the zoom
+/// keyframes and the intermediate format. The output format itself is not
+/// part of it, so MP4 and GIF exports share one composition.
+fn composition_key(
     recording_dir: &std::path::Path,
-    meta: &RecordingMeta,
     settings: &AppSettings,
-    style: crate::config::defaults::OutputStyle,
-    zoom_keyframes: Vec<crate::engine::zoom_planner::ZoomKeyframe>,
-    progress: Option<&ProgressFn>,
-) -> Result<(tempfile::TempDir, f64)> {
-    let raw_events = load_events(recording_dir).unwrap_or_default();
-    let preprocessed = preprocess(&raw_events);
-    let events = preprocessed.events;
+    quality: &QualityPreset,
+    output_size: Option<&OutputSize>,
+    cuts: &[CutRange],
+    keyframes: Option<&[ZoomKeyframe]>,
+    intermediate: IntermediateFormat,
+) -> String {
+    let mut fingerprint = cache::recording_fingerprint(recording_dir).value(&recording_dir.to_string_lossy());
+    for file in [
+        "ui_events.jsonl",
+        FRAME_DIFF_FILE,
+        REDACTIONS_FILE,
+        ANNOTATIONS_FILE,
+        SCENE_EDITS_FILE,
+        SCENE_STYLES_FILE,
+    ] {
+        fingerprint = fingerprint.file(&recording_dir.join(file));
+    }
+    if let Some(ref script) = settings.effects.script_path {
+        fingerprint = fingerprint.file(std::path::Path::new(script));
+    }
+    fingerprint
+        .value(&settings.style)
+        .value(&settings.effects)
+        .value(&settings.output)
+        .value(quality)
+        .value(&output_size)
+        .value(cuts)
+        .value(&keyframes)
+        .value(intermediate.extension())
+        .finish()
+}
 
-    let frame_count = read_frame_count(recording_dir);
-    if frame_count == 0 {
-        return Err(anyhow::anyhow!("No frames found in recording"));
+/// Composed frames for `key` from [`COMPOSED_CACHE`], or freshly composed
+/// (and cached) when there is no match.
+fn compose_cached(
+    key: String,
+    compose: impl FnOnce() -> Result<ComposedFrames>,
+) -> Result<std::sync::Arc<ComposedFrames>> {
+    if let Some(composed) = COMPOSED_CACHE.get(&key) {
+        log::info!("Reusing composed frames from the previous export");
+        return Ok(composed);
     }
+    let composed = std::sync::Arc::new(compose()?);
+    COMPOSED_CACHE.insert(key, composed.clone());
+    Ok(composed)
+}
 
-    // frame_timestamps.txt があれば「最初〜最後のフレーム間の実経過時間」で
-    // 平均 fps を算出する（末尾 sleep 分のバイアスを除外）。
-    // 無い場合は従来通り meta.duration_ms ベースにフォールバック。
-    let actual_fps = {
-        let ts = read_frame_timestamps(&recording_dir);
-        match ts {
-            Some(ref v) if v.len() >= 2 => {
-                let span_ms = v.last().copied().unwrap_or(0).saturating_sub(v.first().copied().unwrap_or(0));
-                if span_ms > 0 {
-                    ((v.len() as f64 - 1.0) * 1000.0) / span_ms as f64
-                } else if meta.duration_ms > 0 && frame_count > 1 {
-                    (frame_count as f64 * 1000.0) / meta.duration_ms as f64
-                } else {
+/// Split points for `OutputSettings::split_max_minutes` on the output
+/// timeline, preferring scene starts. Empty when splitting is off, the
+/// format is not a video or the export is short enough.
+fn plan_export_split(
+    recording_id: &str,
+    settings: &AppSettings,
+    format: &ExportFormat,
+    time_map: &TimeMap,
+    composed: &ComposedFrames,
+) -> Vec<u64> {
+    let max_ms = settings.output.split_max_minutes as u64 * 60_000;
+    if max_ms == 0 || !matches!(format, ExportFormat::Mp4 | ExportFormat::WebM | ExportFormat::Mov) {
+        return Vec::new();
+    }
+    let duration_ms = composed.duration_ms();
+    if duration_ms <= max_ms {
+        return Vec::new();
+    }
+    let boundaries: Vec<u64> = match get_recording_scenes(recording_id, settings) {
+        Ok(scenes) => scenes
+            .iter()
+            .skip(1)
+            .filter(|scene| !time_map.is_cut(scene.start_ms))
+            .map(|scene| composed.intro_ms + time_map.remap_time(scene.start_ms))
+            .collect(),
+        Err(e) => {
+            log::warn!("Scenes unavailable for split points: {}", e);
+            Vec::new()
+        }
+    };
+    let points = split::plan_split_points(duration_ms, max_ms, &boundaries);
+    log::info!("Splitting {}ms export into {} parts", duration_ms, points.len() + 1);
+    points
+}
+
+/// The recording's bookmarks as chapters on the output timeline
+/// (`OutputSettings::bookmark_chapters`, MP4 / MOV). Bookmarks inside cut
+/// ranges are dropped. Empty when the export is split into parts.
+fn bookmark_chapters(
+    recording_id: &str,
+    settings: &AppSettings,
+    format: &ExportFormat,
+    time_map: &TimeMap,
+    composed: &ComposedFrames,
+    split_points: &[u64],
+) -> Vec<Chapter> {
+    if !settings.output.bookmark_chapters || !matches!(format, ExportFormat::Mp4 | ExportFormat::Mov) {
+        return Vec::new();
+    }
+    let bookmarks = get_bookmarks(recording_id);
+    if !split_points.is_empty() && !bookmarks.is_empty() {
+        log::info!("Split export: bookmarks are not written as chapters");
+        return Vec::new();
+    }
+    let mut chapters: Vec<Chapter> = bookmarks
+        .into_iter()
+        .filter(|b| !time_map.is_cut(b.time_ms))
+        .map(|b| Chapter {
+            start_ms: composed.intro_ms + time_map.remap_time(b.time_ms),
+            title: b.label,
+        })
+        .collect();
+    // The video before the first bookmark is a chapter of its own
+    if chapters.first().is_some_and(|c| c.start_ms > 0) {
+        chapters.insert(0, Chapter { start_ms: 0, title: String::new() });
+    }
+    chapters
+}
+
+/// Add `chapters` to the encoded export. A failure only loses the chapters.
+fn write_chapters(ffmpeg: &str, output_path: &std::path::Path, chapters: &[Chapter], composed: &ComposedFrames) {
+    if let Err(e) = chapters::add_chapters(ffmpeg, output_path, chapters, composed.duration_ms()) {
+        log::warn!("Exported without chapters: {}", e);
+    }
+}
+
+/// Cut the encoded export at `points`; returns the first part (or the
+/// export itself when it isn't split).
+fn split_export(ffmpeg: &str, output_path: std::path::PathBuf, points: &[u64]) -> Result<std::path::PathBuf> {
+    if points.is_empty() {
+        return Ok(output_path);
+    }
+    let parts = split::split_at(ffmpeg, &output_path, points)?;
+    Ok(parts.into_iter().next().unwrap_or(output_path))
+}
+
+/// Propose cut ranges for long idle periods with no screen changes
+/// ("auto trim dead time", used by Timeline UI and `auto_trim_idle` export).
+pub fn suggest_cuts_for_recording(
+    recording_id: &str,
+    settings: &AppSettings,
+) -> Result<Vec<CutRange>> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+
+    let meta_path = recording_dir.join("meta.json");
+    let meta_str = std::fs::read_to_string(&meta_path)?;
+    let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
+
+    Ok(detect_idle_cuts(&recording_dir, &meta, settings))
+}
+
+/// Idle segments (analyzer) minus frame-diff activity → suggested cuts.
+fn detect_idle_cuts(
+    recording_dir: &std::path::Path,
+    meta: &RecordingMeta,
+    settings: &AppSettings,
+) -> Vec<CutRange> {
+    let raw_events = load_events(recording_dir).unwrap_or_default();
+    let events = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects)).events;
+    let segments = crate::engine::analyzer::analyze_events(&events);
+
+    // Coarse sampling is enough here: we only need to know whether the
+    // screen changed at all during an idle segment.
+    let diff_config = frame_differ::DiffConfig {
+        sample_interval: 10,
+        ..frame_differ::DiffConfig::from_settings(&settings.effects)
+    };
+    let cursor_for_diff = extract_mouse_positions(&events);
+    let change_regions =
+        detect_frame_changes_cached(recording_dir, meta, &cursor_for_diff, read_frame_count(recording_dir), &diff_config)
+            .map(|r| r.regions)
+            .unwrap_or_default();
+
+    let cuts = timeline_cut::suggest_idle_cuts(
+        &segments,
+        &change_regions,
+        settings.output.auto_trim_min_idle_ms,
+    );
+    timeline_cut::normalize_cuts(&cuts, meta.duration_ms)
+}
+
+/// User-specified cuts plus (when `auto_trim_idle` is on) suggested idle cuts,
+/// and (when `idle_speedup > 1`) sped-up idle segments, combined into the
+/// time map used by the compose/encode stages. Timelapse recordings play
+/// back at their speedup throughout.
+fn resolve_time_map(
+    recording_dir: &std::path::Path,
+    meta: &RecordingMeta,
+    settings: &AppSettings,
+    cuts: &[CutRange],
+) -> TimeMap {
+    let mut all = cuts.to_vec();
+    if settings.output.auto_trim_idle {
+        let idle_cuts = detect_idle_cuts(recording_dir, meta, settings);
+        log::info!("Auto trim: {} idle ranges suggested", idle_cuts.len());
+        all.extend(idle_cuts);
+    }
+    let cuts = timeline_cut::normalize_cuts(&all, meta.duration_ms);
+
+    let speed_ranges = if settings.output.idle_speedup > 1.0 {
+        let raw_events = load_events(recording_dir).unwrap_or_default();
+        let events = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects)).events;
+        let segments = crate::engine::analyzer::analyze_events(&events);
+        let ranges = timeline_cut::suggest_idle_speed_ranges(
+            &segments,
+            settings.output.idle_speedup_min_ms,
+            settings.output.idle_speedup,
+        );
+        log::info!(
+            "Idle speedup: {} ranges at {:.1}x",
+            ranges.len(),
+            settings.output.idle_speedup,
+        );
+        ranges
+    } else {
+        Vec::new()
+    };
+
+    let time_map = TimeMap::new(&cuts, &speed_ranges);
+    match meta.timelapse_speed() {
+        Some(speed) => {
+            log::info!("Timelapse: {:.0}x", speed);
+            time_map.with_time_scale(1.0 / speed)
+        }
+        None => time_map,
+    }
+}
+
+/// Frame rate of the composed output: the capture rate, except for
+/// timelapse recordings, whose time map compresses the 1–2 fps capture
+/// onto [`TIMELAPSE_OUTPUT_FPS`].
+fn output_fps(meta: &RecordingMeta, capture_fps: f64) -> f64 {
+    if meta.timelapse_speed().is_some() {
+        TIMELAPSE_OUTPUT_FPS as f64
+    } else {
+        capture_fps
+    }
+}
+
+/// Compose frames using custom keyframes (for timeline UI).
+fn compose_frames_with_keyframes(
+    recording_dir: &std::path::Path,
+    meta: &RecordingMeta,
+    settings: &AppSettings,
+    style: crate::config::defaults::OutputStyle,
+    zoom_keyframes: Vec<crate::engine::zoom_planner::ZoomKeyframe>,
+    time_map: &TimeMap,
+    intermediate: IntermediateFormat,
+    progress: Option<&ProgressFn>,
+) -> Result<ComposedFrames> {
+    let raw_events = load_events(recording_dir).unwrap_or_default();
+    let preprocessed = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects));
+    let events = preprocessed.events;
+
+    let frame_count = read_frame_count(recording_dir);
+    if frame_count == 0 {
+        return Err(anyhow::anyhow!("No frames found in recording"));
+    }
+
+    // frame_timestamps.txt があれば「最初〜最後のフレーム間の実経過時間」で
+    // 平均 fps を算出する（末尾 sleep 分のバイアスを除外）。
+    // 無い場合は従来通り meta.duration_ms ベースにフォールバック。
+    let actual_fps = {
+        let ts = read_frame_timestamps(&recording_dir);
+        match ts {
+            Some(ref v) if v.len() >= 2 => {
+                let span_ms = v.last().copied().unwrap_or(0).saturating_sub(v.first().copied().unwrap_or(0));
+                if span_ms > 0 {
+                    ((v.len() as f64 - 1.0) * 1000.0) / span_ms as f64
+                } else if meta.duration_ms > 0 && frame_count > 1 {
+                    (frame_count as f64 * 1000.0) / meta.duration_ms as f64
+                } else {
                     meta.fps as f64
                 }
             }
@@ -536,13 +1372,7 @@ fn compose_frames_with_keyframes(
             }
         }
     };
-    let frame_time_step_ms = if frame_count > 1 && meta.duration_ms > 0 {
-        meta.duration_ms / frame_count
-    } else if meta.fps > 0 {
-        1000 / meta.fps as u64
-    } else {
-        33
-    };
+    let actual_fps = output_fps(meta, actual_fps);
     let dt = 1.0 / actual_fps.max(1.0);
 
     let raw_positions = extract_mouse_positions(&events);
@@ -564,8 +1394,9 @@ fn compose_frames_with_keyframes(
         adjusted_positions
     };
 
-    let click_effects = if settings.effects.click_ring_enabled {
-        let mut effects = extract_click_effects(&events, 400);
+    let scene_styles: Vec<SceneStyleOverride> = load_recording_list(recording_dir, SCENE_STYLES_FILE);
+    let click_effects = {
+        let mut effects = scene_click_effects(&events, 400, settings, &scene_styles);
         if meta.recording_mode.as_deref() == Some("window") {
             if let Some(ref rect) = meta.window_initial_rect {
                 for eff in &mut effects {
@@ -574,49 +1405,99 @@ fn compose_frames_with_keyframes(
                 }
             }
         }
-        effects
-    } else {
-        Vec::new()
+        timeline_cut::remap_click_effects(&effects, time_map)
     };
-    let key_overlays = if settings.effects.key_badge_enabled {
-        extract_key_overlays(&events, 1500)
-    } else {
-        Vec::new()
+    let key_overlays =
+        timeline_cut::remap_key_overlays(&scene_key_overlays(&events, 1500, settings, &scene_styles), time_map);
+    #[cfg(feature = "scripting")]
+    let script = load_export_script(settings)?;
+    #[cfg(feature = "scripting")]
+    let zoom_keyframes = match script {
+        Some(ref script) => script.camera(&[], zoom_keyframes)?,
+        None => zoom_keyframes,
     };
+    let zoom_keyframes = timeline_cut::remap_keyframes(&zoom_keyframes, time_map);
 
+    let (intro, outro) = (style.intro.clone(), style.outro.clone());
     let mut compositor = Compositor::new(style, meta.screen_width, meta.screen_height);
-    compositor.set_motion_blur(settings.effects.motion_blur_enabled);
+    compositor.set_motion_blur(settings.effects.motion_blur_active());
+    compositor.set_reduced_motion(settings.effects.reduced_motion);
+    compositor.set_backend(settings.output.compositor_backend);
+    compositor.set_os_cursor_scale(meta.os_cursor_scale());
+    compositor.set_monitor_scale(meta.display_scale());
+    compositor.set_ui_highlights(build_ui_highlights(&events, meta, settings, time_map));
+    compositor.set_typed_captions(build_typed_captions(&events, meta, settings, time_map));
+    compositor.set_spotlight_clicks(build_spotlight_clicks(&events, settings, time_map));
+    compositor.set_redactions(build_redactions(recording_dir, &events, meta, settings, time_map));
+    compositor.set_annotations(timeline_cut::remap_annotations(
+        &load_recording_list::<Annotation>(recording_dir, ANNOTATIONS_FILE),
+        time_map,
+    ));
+    compositor.set_scene_styles(timeline_cut::remap_scene_styles(&scene_styles, time_map));
+    compositor.set_clip_duration(time_map.output_duration_ms(meta.duration_ms));
+    #[cfg(feature = "scripting")]
+    compositor.set_script(script);
+    #[cfg(feature = "effect-plugins")]
+    compositor.set_plugins(
+        crate::engine::effects::plugin::instantiate_plugins(&settings.effects.plugins),
+        events.clone(),
+    );
 
-    let temp_dir = tempfile::TempDir::new()?;
+    let temp_dir = super::cleanup::export_temp_dir()?;
     let composed_frames_dir = temp_dir.path().join("frames");
     std::fs::create_dir_all(&composed_frames_dir)?;
 
-    let frames_dir = recording_dir.join("frames");
+    let source = FrameSource::open(&recording_dir.join("frames"))?;
+    let frame_index = FrameIndex::load(recording_dir, meta, frame_count);
     let mut kf_index = 0;
-    let mut output_frame_count: u64 = 0;
+    // Frames 0..intro_frames are left for the intro card, written at the end
+    let intro_frames = intro.as_ref().map_or(0, |card| title_card_frame_count(card.duration_ms, actual_fps));
+    let mut output_frame_count: u64 = intro_frames;
+    let mut clock = OutputClock::new(actual_fps, paused_ms(&frame_index, meta));
+    let mut prev_raw: Option<image::RgbaImage> = None;
+    let pauses = output_pause_breaks(meta, time_map);
+    let mut pause_index = 0;
 
     for frame_idx in 0..frame_count {
-        let frame_time_ms = frame_idx * frame_time_step_ms;
-
-        while kf_index < zoom_keyframes.len() && zoom_keyframes[kf_index].time_ms <= frame_time_ms {
-            compositor.apply_keyframe(&zoom_keyframes[kf_index]);
-            kf_index += 1;
+        let frame_time_ms = frame_index.time_of(frame_idx);
+        if time_map.is_cut(frame_time_ms) {
+            continue;
         }
-
-        let frame_path = recording_frame_path(&frames_dir, frame_idx);
-        let raw_frame = match image::open(&frame_path) {
+        let (output_start, output_end) = frame_output_span(&frame_index, time_map, frame_idx, clock.step_ms());
+        let ticks = clock.ticks(output_start, output_end);
+        if ticks.is_empty() {
+            continue;
+        }
+        let raw_frame = match source.load(frame_idx) {
             Ok(img) => img.to_rgba8(),
-            Err(_) => continue,
+            Err(_) => match prev_raw.take() {
+                Some(prev) => prev,
+                None => continue,
+            },
         };
 
-        let cursor_pos = find_cursor_at_time(&cursor_positions, frame_time_ms);
-        let active_key = key_overlays.iter().rfind(|ko| ko.is_visible(frame_time_ms));
+        for tick in ticks {
+            let output_time_ms = tick.round() as u64;
+            while kf_index < zoom_keyframes.len() && zoom_keyframes[kf_index].time_ms <= output_time_ms {
+                compositor.apply_keyframe(&zoom_keyframes[kf_index]);
+                kf_index += 1;
+            }
+            while pause_index < pauses.len() && pauses[pause_index] <= output_time_ms {
+                compositor.cut_camera();
+                pause_index += 1;
+            }
+
+            let cursor_time_ms = tick_recording_time(&frame_index, frame_idx, (output_start, output_end), tick);
+            let cursor_pos = find_cursor_at_time(&cursor_positions, cursor_time_ms);
+            let active_key = key_overlays.iter().rfind(|ko| ko.is_visible(output_time_ms));
 
-        let composed = compositor.compose_frame(&raw_frame, frame_time_ms, cursor_pos, &click_effects, active_key, dt);
-        let rgb_frame = image::DynamicImage::ImageRgba8(composed).to_rgb8();
-        let output_path = composed_frames_dir.join(format!("frame_{:08}.jpg", output_frame_count));
-        save_rgb_as_jpeg(&rgb_frame, &output_path, INTERMEDIATE_JPEG_QUALITY)?;
-        output_frame_count += 1;
+            let composed = compositor.compose_frame(&raw_frame, output_time_ms, cursor_pos, &click_effects, active_key, dt);
+            let output_path = composed_frames_dir
+                .join(format!("frame_{:08}.{}", output_frame_count, intermediate.extension()));
+            intermediate.save(composed, &output_path)?;
+            output_frame_count += 1;
+        }
+        prev_raw = Some(raw_frame);
 
         if frame_idx % 10 == 0 {
             if let Some(cb) = progress {
@@ -626,13 +1507,179 @@ fn compose_frames_with_keyframes(
         }
     }
 
-    let final_fps = if output_frame_count > 0 && meta.duration_ms > 0 {
-        (output_frame_count as f64 * 1000.0) / meta.duration_ms as f64
+    // Output frames are on the clock's fixed grid, so the rate is exact
+    let final_fps = actual_fps;
+
+    add_title_cards(
+        &mut compositor,
+        (intro.as_ref(), intro_frames),
+        outro.as_ref(),
+        temp_dir,
+        output_frame_count,
+        final_fps,
+        intermediate,
+    )
+}
+
+/// Time the recording was paused: capture times run on through pauses,
+/// `duration_ms` (and the audio) don't. Recordings whose clock stopped
+/// while paused have nothing to close.
+fn paused_ms(frame_index: &FrameIndex, meta: &RecordingMeta) -> u64 {
+    if meta.pauses_excluded() {
+        return 0;
+    }
+    let last = frame_index.frame_count().saturating_sub(1);
+    frame_index.time_of(last).saturating_sub(meta.duration_ms)
+}
+
+/// Recording time shown by output tick `tick` of source frame `frame_idx`,
+/// on screen over `span` of the output timeline. Ticks repeating a frame
+/// across a capture gap (dropped frames) advance through the gap, also
+/// where a speed range compresses it.
+fn tick_recording_time(frame_index: &FrameIndex, frame_idx: u64, span: (f64, f64), tick: f64) -> u64 {
+    let (start, end) = span;
+    let fraction = if end > start { (tick - start) / (end - start) } else { 0.0 };
+    frame_index.time_within(frame_idx, fraction)
+}
+
+/// Recording times where the recording was paused. Scenes don't span them
+/// and the camera cuts instead of panning across them.
+fn pause_breaks(meta: &RecordingMeta) -> Vec<u64> {
+    meta.pauses.iter().map(|p| p.at_ms).collect()
+}
+
+/// [`pause_breaks`] on the output timeline (pauses inside cuts dropped).
+fn output_pause_breaks(meta: &RecordingMeta, time_map: &TimeMap) -> Vec<u64> {
+    pause_breaks(meta)
+        .into_iter()
+        .filter(|&t| !time_map.is_cut(t))
+        .map(|t| time_map.remap_time(t))
+        .collect()
+}
+
+/// Log capture gaps that the export fills with repeated frames.
+fn log_capture_gaps(frame_index: &FrameIndex, step_ms: f64) {
+    let gaps: Vec<(u64, u64)> = frame_index
+        .gaps((step_ms * 2.0).ceil() as u64)
+        .into_iter()
+        .filter(|&(_, gap)| gap <= frame_index::PAUSE_GAP_MS)
+        .collect();
+    if let Some(&(frame, longest)) = gaps.iter().max_by_key(|&&(_, gap)| gap) {
+        log::info!(
+            "{} capture gaps filled with repeated frames (longest {}ms after frame {})",
+            gaps.len(),
+            longest,
+            frame,
+        );
+    }
+}
+
+/// Output-timeline span during which source frame `frame_idx` is on
+/// screen: until the next frame's capture time (one tick for the last).
+fn frame_output_span(frame_index: &FrameIndex, time_map: &TimeMap, frame_idx: u64, step_ms: f64) -> (f64, f64) {
+    let start = time_map.remap_time_f64(frame_index.time_of(frame_idx));
+    let end = if frame_idx + 1 < frame_index.frame_count() {
+        time_map.remap_time_f64(frame_index.time_of(frame_idx + 1))
     } else {
-        actual_fps
+        start + step_ms
+    };
+    (start, end)
+}
+
+/// Composed frames of an export (`frames/` inside the temp dir, deleted on
+/// drop) and how they line up with the recording's audio.
+struct ComposedFrames {
+    temp_dir: tempfile::TempDir,
+    fps: f64,
+    /// Intro / outro card lengths (ms) around the recording
+    intro_ms: u64,
+    outro_ms: u64,
+}
+
+impl ComposedFrames {
+    fn frames_dir(&self) -> std::path::PathBuf {
+        self.temp_dir.path().join("frames")
+    }
+
+    fn duration_ms(&self) -> u64 {
+        let frames = std::fs::read_dir(self.frames_dir()).map(|d| d.count()).unwrap_or(0);
+        (frames as f64 * 1000.0 / self.fps.max(1.0)) as u64
+    }
+
+    fn audio_timing<'a>(&self, time_map: &'a TimeMap) -> AudioTiming<'a> {
+        AudioTiming { time_map, lead_ms: self.intro_ms, tail_ms: self.outro_ms }
+    }
+}
+
+/// How the recorded audio maps onto the composed frames: the export's cuts
+/// and speed ranges, then silence under the intro and outro cards.
+struct AudioTiming<'a> {
+    time_map: &'a TimeMap,
+    lead_ms: u64,
+    tail_ms: u64,
+}
+
+impl AudioTiming<'_> {
+    /// FFmpeg `-af` filter, or None when the audio is used as recorded.
+    fn filter(&self) -> Option<String> {
+        let mut filters: Vec<String> = self.time_map.audio_filter().into_iter().collect();
+        if self.lead_ms > 0 {
+            filters.push(format!("adelay={}:all=1", self.lead_ms));
+        }
+        if self.tail_ms > 0 {
+            filters.push(format!("apad=pad_dur={:.3}", self.tail_ms as f64 / 1000.0));
+        }
+        (!filters.is_empty()).then(|| filters.join(","))
+    }
+}
+
+fn title_card_frame_count(duration_ms: u64, fps: f64) -> u64 {
+    (duration_ms as f64 * fps / 1000.0).round() as u64
+}
+
+/// Write the intro card into the reserved frames `0..intro_frames` and the
+/// outro after the recording's last frame. Each card is rendered once and
+/// its file copied for the remaining frames.
+fn add_title_cards(
+    compositor: &mut Compositor,
+    (intro, intro_frames): (Option<&crate::config::TitleCardSettings>, u64),
+    outro: Option<&crate::config::TitleCardSettings>,
+    temp_dir: tempfile::TempDir,
+    next_frame: u64,
+    fps: f64,
+    intermediate: IntermediateFormat,
+) -> Result<ComposedFrames> {
+    let frames_dir = temp_dir.path().join("frames");
+    let mut write_card = |card: &crate::config::TitleCardSettings, first: u64, count: u64| -> Result<()> {
+        if count == 0 {
+            return Ok(());
+        }
+        let path = |i: u64| frames_dir.join(format!("frame_{:08}.{}", i, intermediate.extension()));
+        intermediate.save(compositor.render_title_card(card), &path(first))?;
+        for i in first + 1..first + count {
+            std::fs::copy(path(first), path(i))?;
+        }
+        Ok(())
     };
 
-    Ok((temp_dir, final_fps))
+    if let Some(card) = intro {
+        write_card(card, 0, intro_frames)?;
+    }
+    let outro_frames = outro.map_or(0, |card| title_card_frame_count(card.duration_ms, fps));
+    if let Some(card) = outro {
+        write_card(card, next_frame, outro_frames)?;
+    }
+    if intro_frames + outro_frames > 0 {
+        log::info!("Title cards: {} intro + {} outro frames", intro_frames, outro_frames);
+    }
+
+    let frames_to_ms = |frames: u64| if fps > 0.0 { (frames as f64 * 1000.0 / fps).round() as u64 } else { 0 };
+    Ok(ComposedFrames {
+        temp_dir,
+        fps,
+        intro_ms: frames_to_ms(intro_frames),
+        outro_ms: frames_to_ms(outro_frames),
+    })
 }
 
 // --- Effects composition pipeline ---
@@ -642,12 +1689,14 @@ fn compose_frames(
     meta: &RecordingMeta,
     settings: &AppSettings,
     style: OutputStyle,
+    time_map: &TimeMap,
+    intermediate: IntermediateFormat,
     progress: Option<&ProgressFn>,
-) -> Result<(tempfile::TempDir, f64)> {
+) -> Result<ComposedFrames> {
     let raw_events = load_events(recording_dir).unwrap_or_default();
 
     // Preprocess: thin mouse moves and detect drags
-    let preprocessed = preprocess(&raw_events);
+    let preprocessed = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects));
     let events = preprocessed.events;
     log::info!(
         "Preprocessed {} raw events → {} thinned events, {} drags detected",
@@ -697,11 +1746,14 @@ fn compose_frames(
     } else {
         33
     };
+    let actual_fps = output_fps(meta, actual_fps);
     let dt = 1.0 / actual_fps.max(1.0);
 
     // 1. Split events into scenes and generate lookahead zoom plan
-    let mut scenes = split_into_scenes(
-        &events,
+    let plan_events = app_exclusion(recording_dir, settings).filter(&events);
+    let mut scenes = split_into_scenes_with_breaks(
+        &plan_events,
+        &pause_breaks(meta),
         meta.screen_width as f64,
         meta.screen_height as f64,
         settings.effects.max_zoom,
@@ -713,16 +1765,8 @@ fn compose_frames(
     if settings.effects.auto_zoom_enabled {
         if let Some(cb) = progress { cb("analyzing", 0.0); }
         let cursor_for_diff = extract_mouse_positions(&events);
-        let diff_config = frame_differ::DiffConfig::default();
-        match frame_differ::detect_frame_changes(
-            &recording_dir.join("frames"),
-            frame_count,
-            meta.duration_ms,
-            &cursor_for_diff,
-            meta.screen_width,
-            meta.screen_height,
-            &diff_config,
-        ) {
+        let diff_config = frame_differ::DiffConfig::from_settings(&settings.effects);
+        match detect_frame_changes_cached(recording_dir, meta, &cursor_for_diff, frame_count, &diff_config) {
             Ok(diff_result) => {
                 log::info!(
                     "Frame diff: {} change regions detected ({} pairs analyzed, {} excluded)",
@@ -738,6 +1782,7 @@ fn compose_frames(
                         meta.screen_width as f64,
                         meta.screen_height as f64,
                         settings.effects.max_zoom,
+                        &frame_differ::CaptureMapping::from_meta(meta),
                     );
                 }
             }
@@ -747,10 +1792,14 @@ fn compose_frames(
         }
     }
 
+    // Scene edits saved from the Timeline UI
+    let scene_edits = load_recording_list(recording_dir, SCENE_EDITS_FILE);
+    let mut scenes = replay_scene_edits(scenes, &scene_edits, &plan_events, meta, settings, &change_regions);
+
     // Phase A (Issue #23): ui_events.jsonl の矩形を各シーンに紐付け
     crate::engine::ui_context::attach_ui_rects_to_scenes(
         &mut scenes,
-        &events,
+        &plan_events,
         meta.screen_width as f64,
         meta.screen_height as f64,
     );
@@ -805,8 +1854,9 @@ fn compose_frames(
     };
 
     // 3. Build effect lists (also adjust for window mode)
-    let click_effects = if settings.effects.click_ring_enabled {
-        let mut effects = extract_click_effects(&events, style.click_ring_duration_ms);
+    let scene_styles: Vec<SceneStyleOverride> = load_recording_list(recording_dir, SCENE_STYLES_FILE);
+    let click_effects = {
+        let mut effects = scene_click_effects(&events, style.click_ring_duration_ms, settings, &scene_styles);
         if meta.recording_mode.as_deref() == Some("window") {
             if let Some(ref rect) = meta.window_initial_rect {
                 for eff in &mut effects {
@@ -815,77 +1865,148 @@ fn compose_frames(
                 }
             }
         }
-        effects
-    } else {
-        Vec::new()
+        timeline_cut::remap_click_effects(&effects, time_map)
     };
-    let key_overlays = if settings.effects.key_badge_enabled {
-        extract_key_overlays(&events, style.key_badge_duration_ms)
-    } else {
-        Vec::new()
+    let key_overlays = timeline_cut::remap_key_overlays(
+        &scene_key_overlays(&events, style.key_badge_duration_ms, settings, &scene_styles),
+        time_map,
+    );
+
+    // 3.5. Cuts / speed ranges: keyframes/effects live on the output timeline from here on
+    #[cfg(feature = "scripting")]
+    let script = load_export_script(settings)?;
+    #[cfg(feature = "scripting")]
+    let zoom_keyframes = match script {
+        Some(ref script) => script.camera(&scenes, zoom_keyframes)?,
+        None => zoom_keyframes,
     };
+    let zoom_keyframes = timeline_cut::remap_keyframes(&zoom_keyframes, time_map);
+    if !time_map.is_identity() {
+        log::info!(
+            "Applying {} cut ranges ({}ms removed), output duration {}ms",
+            time_map.cuts().len(),
+            timeline_cut::total_cut_ms(time_map.cuts()),
+            time_map.output_duration_ms(meta.duration_ms),
+        );
+    }
 
     // 4. Create compositor
+    let (intro, outro) = (style.intro.clone(), style.outro.clone());
     let mut compositor = Compositor::new(style, meta.screen_width, meta.screen_height);
-    compositor.set_motion_blur(settings.effects.motion_blur_enabled);
+    compositor.set_motion_blur(settings.effects.motion_blur_active());
+    compositor.set_reduced_motion(settings.effects.reduced_motion);
+    compositor.set_backend(settings.output.compositor_backend);
+    compositor.set_os_cursor_scale(meta.os_cursor_scale());
+    compositor.set_monitor_scale(meta.display_scale());
+    compositor.set_ui_highlights(build_ui_highlights(&events, meta, settings, time_map));
+    compositor.set_typed_captions(build_typed_captions(&events, meta, settings, time_map));
+    compositor.set_spotlight_clicks(build_spotlight_clicks(&events, settings, time_map));
+    compositor.set_redactions(build_redactions(recording_dir, &events, meta, settings, time_map));
+    compositor.set_annotations(timeline_cut::remap_annotations(
+        &load_recording_list::<Annotation>(recording_dir, ANNOTATIONS_FILE),
+        time_map,
+    ));
+    compositor.set_scene_styles(timeline_cut::remap_scene_styles(&scene_styles, time_map));
+    compositor.set_clip_duration(time_map.output_duration_ms(meta.duration_ms));
+    #[cfg(feature = "scripting")]
+    compositor.set_script(script);
+    #[cfg(feature = "effect-plugins")]
+    compositor.set_plugins(
+        crate::engine::effects::plugin::instantiate_plugins(&settings.effects.plugins),
+        events.clone(),
+    );
 
     // 5. Create temp directory for composed frames
-    let temp_dir = tempfile::TempDir::new()?;
+    let temp_dir = super::cleanup::export_temp_dir()?;
     let composed_frames_dir = temp_dir.path().join("frames");
     std::fs::create_dir_all(&composed_frames_dir)?;
 
-    let frames_dir = recording_dir.join("frames");
+    let source = FrameSource::open(&recording_dir.join("frames"))?;
+    let frame_index = FrameIndex::load(recording_dir, meta, frame_count);
     let mut kf_index = 0;
-    let mut output_frame_count: u64 = 0;
+    // Frames 0..intro_frames are left for the intro card, written at the end
+    let intro_frames = intro.as_ref().map_or(0, |card| title_card_frame_count(card.duration_ms, actual_fps));
+    let mut output_frame_count: u64 = intro_frames;
+    let mut clock = OutputClock::new(actual_fps, paused_ms(&frame_index, meta));
+    let mut prev_raw: Option<image::RgbaImage> = None;
+    let pauses = output_pause_breaks(meta, time_map);
+    let mut pause_index = 0;
+    log_capture_gaps(&frame_index, clock.step_ms());
 
     // 6. Process each frame
     for frame_idx in 0..frame_count {
-        let frame_time_ms = frame_idx * frame_time_step_ms;
+        let frame_time_ms = frame_index.time_of(frame_idx);
 
-        // Apply any zoom keyframes that have been reached
-        while kf_index < zoom_keyframes.len()
-            && zoom_keyframes[kf_index].time_ms <= frame_time_ms
-        {
-            compositor.apply_keyframe(&zoom_keyframes[kf_index]);
-            kf_index += 1;
+        // Skip frames inside cut ranges; everything below uses the output timeline
+        if time_map.is_cut(frame_time_ms) {
+            continue;
+        }
+
+        // Output frames are on a fixed grid: the frame is repeated over
+        // capture gaps and skipped in sped-up ranges
+        let (output_start, output_end) = frame_output_span(&frame_index, time_map, frame_idx, clock.step_ms());
+        let ticks = clock.ticks(output_start, output_end);
+        if ticks.is_empty() {
+            continue;
         }
 
-        // Load raw frame
-        let frame_path = recording_frame_path(&frames_dir, frame_idx);
-        let raw_frame = match image::open(&frame_path) {
+        // Load raw frame (a missing one is replaced by the previous frame)
+        let raw_frame = match source.load(frame_idx) {
             Ok(img) => img.to_rgba8(),
             Err(_) => {
                 log::warn!("Frame {} not found, skipping", frame_idx);
-                continue;
+                match prev_raw.take() {
+                    Some(prev) => prev,
+                    None => continue,
+                }
             }
         };
 
-        // Find cursor position for this frame time
-        let cursor_pos = find_cursor_at_time(&cursor_positions, frame_time_ms);
+        for tick in ticks {
+            let output_time_ms = tick.round() as u64;
 
-        // Find active key overlay
-        let active_key = key_overlays.iter().rfind(|ko| ko.is_visible(frame_time_ms));
-
-        // Compose frame with all effects
-        let composed = compositor.compose_frame(
-            &raw_frame,
-            frame_time_ms,
-            cursor_pos,
-            &click_effects,
-            active_key,
-            dt,
-        );
+            // Apply any zoom keyframes that have been reached
+            while kf_index < zoom_keyframes.len()
+                && zoom_keyframes[kf_index].time_ms <= output_time_ms
+            {
+                compositor.apply_keyframe(&zoom_keyframes[kf_index]);
+                kf_index += 1;
+            }
+            // The footage jumps where the recording was paused: so does the camera
+            while pause_index < pauses.len() && pauses[pause_index] <= output_time_ms {
+                compositor.cut_camera();
+                pause_index += 1;
+            }
 
-        // 合成済みフレームを JPEG q=95 で保存。
-        // BMP 時代と比較してディスク使用量を約 15 倍削減できる。
-        // 最終出力は H.264/VP9 等で再エンコードされるため、
-        // 中間段階で q=95 以上の品質はほぼ無意味。
-        // シーケンス番号にギャップを作らないため output_frame_count を使う。
-        // RGBA→RGB で JPEG のアルファ非対応にも対応。
-        let rgb_frame = image::DynamicImage::ImageRgba8(composed).to_rgb8();
-        let output_path = composed_frames_dir.join(format!("frame_{:08}.jpg", output_frame_count));
-        save_rgb_as_jpeg(&rgb_frame, &output_path, INTERMEDIATE_JPEG_QUALITY)?;
-        output_frame_count += 1;
+            // Cursor at the tick's own time (repeated frames still show it moving)
+            let cursor_time_ms = tick_recording_time(&frame_index, frame_idx, (output_start, output_end), tick);
+            let cursor_pos = find_cursor_at_time(&cursor_positions, cursor_time_ms);
+
+            // Find active key overlay
+            let active_key = key_overlays.iter().rfind(|ko| ko.is_visible(output_time_ms));
+
+            // Compose frame with all effects
+            let composed = compositor.compose_frame(
+                &raw_frame,
+                output_time_ms,
+                cursor_pos,
+                &click_effects,
+                active_key,
+                dt,
+            );
+
+            // 合成済みフレームを JPEG q=95 で保存。
+            // BMP 時代と比較してディスク使用量を約 15 倍削減できる。
+            // 最終出力は H.264/VP9 等で再エンコードされるため、
+            // 中間段階で q=95 以上の品質はほぼ無意味。
+            // 透過背景で WebM/MOV に書き出す場合のみアルファを残すため PNG で保存する。
+            // シーケンス番号にギャップを作らないため output_frame_count を使う。
+            let output_path = composed_frames_dir
+                .join(format!("frame_{:08}.{}", output_frame_count, intermediate.extension()));
+            intermediate.save(composed, &output_path)?;
+            output_frame_count += 1;
+        }
+        prev_raw = Some(raw_frame);
 
         if frame_idx % 10 == 0 {
             log::info!("Composing frame {}/{}", frame_idx, frame_count);
@@ -896,15 +2017,19 @@ fn compose_frames(
         }
     }
 
-    // Recalculate fps based on actual output frame count (in case some frames were skipped)
-    let final_fps = if output_frame_count > 0 && meta.duration_ms > 0 {
-        (output_frame_count as f64 * 1000.0) / meta.duration_ms as f64
-    } else {
-        actual_fps
-    };
-    log::info!("Composed {} frames (final fps: {:.1})", output_frame_count, final_fps);
-
-    Ok((temp_dir, final_fps))
+    // Output frames are on the clock's fixed grid, so the rate is exact
+    let final_fps = actual_fps;
+    log::info!("Composed {} frames (final fps: {:.1})", output_frame_count - intro_frames, final_fps);
+
+    add_title_cards(
+        &mut compositor,
+        (intro.as_ref(), intro_frames),
+        outro.as_ref(),
+        temp_dir,
+        output_frame_count,
+        final_fps,
+        intermediate,
+    )
 }
 
 fn load_events(recording_dir: &std::path::Path) -> Result<Vec<RecordingEvent>> {
@@ -947,9 +2072,39 @@ fn load_events(recording_dir: &std::path::Path) -> Result<Vec<RecordingEvent>> {
 
     // Sort by timestamp
     events.sort_by_key(|e| crate::engine::analyzer::event_timestamp(e));
+
+    // Window recordings: keep events on the window's content when it moved
+    let track = crate::recording::window_track::load_window_track(recording_dir);
+    crate::recording::window_track::follow_window(&mut events, &track);
     Ok(events)
 }
 
+/// Foreground window changes (window_events.jsonl), oldest first.
+fn load_window_events(recording_dir: &std::path::Path) -> Vec<RecordingEvent> {
+    let Ok(content) = std::fs::read_to_string(recording_dir.join("window_events.jsonl")) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<RecordingEvent>(line).ok())
+        .collect()
+}
+
+/// Windows whose input is left out of zoom planning
+/// (`EffectsSettings::zoom_excluded_apps`).
+fn app_exclusion(recording_dir: &std::path::Path, settings: &AppSettings) -> AppExclusion {
+    let patterns = &settings.effects.zoom_excluded_apps;
+    if patterns.is_empty() {
+        return AppExclusion::default();
+    }
+    let exclusion = AppExclusion::new(&load_window_events(recording_dir), patterns);
+    if !exclusion.is_empty() {
+        log::info!("Excluding input to {:?} from zoom planning", patterns);
+    }
+    exclusion
+}
+
 fn extract_mouse_positions(events: &[RecordingEvent]) -> Vec<(u64, f64, f64)> {
     events
         .iter()
@@ -961,16 +2116,99 @@ fn extract_mouse_positions(events: &[RecordingEvent]) -> Vec<(u64, f64, f64)> {
         .collect()
 }
 
-fn extract_click_effects(events: &[RecordingEvent], duration_ms: u64) -> Vec<ClickEffect> {
+// --- Frame diff cache ---
+
+/// Frame-diff results kept in the recording dir, keyed by the hash of the
+/// `DiffConfig` they were computed with.
+const FRAME_DIFF_FILE: &str = "frame_diff.json";
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CachedDiff {
+    /// Fingerprint of the recording and cursor track the result belongs to
+    inputs: String,
+    result: frame_differ::DiffResult,
+}
+
+/// [`frame_differ::detect_frame_changes`] over the recording's frames.
+/// Decoding every frame is the slow part of keyframe, scene and export
+/// analysis, so the result is stored in `frame_diff.json` and reused while
+/// the recording, the cursor track and the config stay the same. Recordings
+/// analyzed during capture (`changes.jsonl`) skip the pass entirely.
+fn detect_frame_changes_cached(
+    recording_dir: &std::path::Path,
+    meta: &RecordingMeta,
+    cursor_positions: &[(u64, f64, f64)],
+    frame_count: u64,
+    config: &frame_differ::DiffConfig,
+) -> Result<frame_differ::DiffResult> {
+    let mapping = frame_differ::CaptureMapping::from_meta(meta);
+    let frame_index = FrameIndex::load(recording_dir, meta, frame_count);
+    // Computed while recording: nothing to decode
+    if let Some(result) = frame_differ::load_recorded_changes(recording_dir, &frame_index, &mapping, config) {
+        return Ok(result);
+    }
+
+    let path = recording_dir.join(FRAME_DIFF_FILE);
+    let config_key = cache::Fingerprint::new().value(config).finish();
+    let inputs = cache::recording_fingerprint(recording_dir)
+        .value(cursor_positions)
+        .value(&frame_count)
+        .finish();
+
+    let mut entries: std::collections::HashMap<String, CachedDiff> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    if let Some(entry) = entries.get(&config_key).filter(|e| e.inputs == inputs) {
+        return Ok(entry.result.clone());
+    }
+
+    let result = frame_differ::detect_frame_changes(
+        &recording_dir.join("frames"),
+        &frame_index,
+        cursor_positions,
+        meta.screen_width,
+        meta.screen_height,
+        &mapping,
+        config,
+    )?;
+
+    // Results for an older state of the recording are useless now
+    entries.retain(|_, e| e.inputs == inputs);
+    entries.insert(config_key, CachedDiff { inputs, result: result.clone() });
+    match serde_json::to_string(&entries) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                log::warn!("Failed to write {}: {}", path.display(), e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize frame diff cache: {}", e),
+    }
+    Ok(result)
+}
+
+/// Click rings for the buttons enabled in `click_ring_buttons`; buttons in
+/// `click_ring_alt_buttons` get the alternate style.
+fn extract_click_effects(
+    events: &[RecordingEvent],
+    duration_ms: u64,
+    effects: &crate::config::EffectsSettings,
+) -> Vec<ClickEffect> {
     events
         .iter()
         .filter_map(|e| match e {
-            RecordingEvent::Click { t, x, y, .. } => Some(ClickEffect {
-                x: *x,
-                y: *y,
-                start_ms: *t,
-                duration_ms,
-            }),
+            RecordingEvent::Click { t, x, y, btn } => {
+                if !effects.click_ring_buttons.iter().any(|b| b == btn) {
+                    return None;
+                }
+                Some(ClickEffect {
+                    x: *x,
+                    y: *y,
+                    start_ms: *t,
+                    duration_ms,
+                    alt: effects.click_ring_alt_buttons.iter().any(|b| b == btn),
+                })
+            }
             _ => None,
         })
         .collect()
@@ -1003,35 +2241,185 @@ fn extract_key_overlays(events: &[RecordingEvent], duration_ms: u64) -> Vec<KeyO
         .collect()
 }
 
-/// Find cursor position at a given time with linear interpolation between samples.
-fn find_cursor_at_time(positions: &[(u64, f64, f64)], time_ms: u64) -> Option<(f64, f64)> {
-    if positions.is_empty() {
-        return None;
+/// Click rings where they are on, in settings or for the scene they fall in.
+fn scene_click_effects(
+    events: &[RecordingEvent],
+    duration_ms: u64,
+    settings: &AppSettings,
+    scene_styles: &[SceneStyleOverride],
+) -> Vec<ClickEffect> {
+    let default = settings.effects.click_ring_enabled;
+    if !scene_style::effect_used(scene_styles, SceneEffect::ClickRing, default) {
+        return Vec::new();
     }
-    match positions.binary_search_by_key(&time_ms, |&(t, _, _)| t) {
-        Ok(i) => Some((positions[i].1, positions[i].2)),
-        Err(0) => Some((positions[0].1, positions[0].2)),
-        Err(i) if i >= positions.len() => {
-            let last = &positions[positions.len() - 1];
-            Some((last.1, last.2))
-        }
-        Err(i) => {
-            // Linear interpolation between positions[i-1] and positions[i]
-            let (t0, x0, y0) = positions[i - 1];
-            let (t1, x1, y1) = positions[i];
-            let dt = (t1 - t0) as f64;
-            if dt <= 0.0 {
-                return Some((x0, y0));
+    let mut effects = extract_click_effects(events, duration_ms, &settings.effects);
+    effects.retain(|e| scene_style::effect_enabled_at(scene_styles, SceneEffect::ClickRing, default, e.start_ms));
+    effects
+}
+
+/// Key badges where they are on, in settings or for the scene they fall in.
+fn scene_key_overlays(
+    events: &[RecordingEvent],
+    duration_ms: u64,
+    settings: &AppSettings,
+    scene_styles: &[SceneStyleOverride],
+) -> Vec<KeyOverlay> {
+    let default = settings.effects.key_badge_enabled;
+    if !scene_style::effect_used(scene_styles, SceneEffect::KeyBadge, default) {
+        return Vec::new();
+    }
+    let mut overlays = extract_key_overlays(events, duration_ms);
+    overlays.retain(|o| scene_style::effect_enabled_at(scene_styles, SceneEffect::KeyBadge, default, o.start_ms));
+    overlays
+}
+
+/// Dialog/menu highlights on the output timeline, in frame coordinates.
+/// Empty unless enabled in settings.
+fn build_ui_highlights(
+    events: &[RecordingEvent],
+    meta: &RecordingMeta,
+    settings: &AppSettings,
+    time_map: &TimeMap,
+) -> Vec<UiHighlight> {
+    if !settings.effects.ui_highlight_enabled {
+        return Vec::new();
+    }
+    let mut highlights = extract_ui_highlights(events);
+    if meta.recording_mode.as_deref() == Some("window") {
+        if let Some(ref rect) = meta.window_initial_rect {
+            for h in &mut highlights {
+                h.rect = [h.rect[0] - rect[0], h.rect[1] - rect[1], h.rect[2] - rect[0], h.rect[3] - rect[1]];
             }
-            let t = (time_ms - t0) as f64 / dt;
-            Some((x0 + (x1 - x0) * t, y0 + (y1 - y0) * t))
         }
     }
+    timeline_cut::remap_ui_highlights(&highlights, time_map)
 }
 
-/// 録画フレームのパスを解決する。新しい録画は `.jpg`、旧録画は `.png` で保存されている
-/// ため両方をチェックする。ファイルが見つからない場合は `.jpg` のパスを返す（呼び出し側が
-/// エラーを出す想定）。
+/// The export script from settings, if one is configured.
+#[cfg(feature = "scripting")]
+fn load_export_script(settings: &AppSettings) -> Result<Option<crate::engine::scripting::ExportScript>> {
+    match settings.effects.script_path.as_deref().map(str::trim) {
+        Some(path) if !path.is_empty() => {
+            let script = crate::engine::scripting::ExportScript::load(std::path::Path::new(path))?;
+            log::info!("Export script loaded from '{}'", path);
+            Ok(Some(script))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Click times on the output timeline for the spotlight pulse. Empty unless
+/// the spotlight is enabled.
+fn build_spotlight_clicks(events: &[RecordingEvent], settings: &AppSettings, time_map: &TimeMap) -> Vec<u64> {
+    if !settings.style.spotlight.enabled {
+        return Vec::new();
+    }
+    events
+        .iter()
+        .filter_map(|e| match e {
+            RecordingEvent::Click { t, .. } => Some(time_map.remap_time(*t)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Typed-text captions on the output timeline, anchored in frame
+/// coordinates. Empty unless enabled in settings.
+fn build_typed_captions(
+    events: &[RecordingEvent],
+    meta: &RecordingMeta,
+    settings: &AppSettings,
+    time_map: &TimeMap,
+) -> Vec<TypedCaption> {
+    if !settings.effects.typed_text_overlay {
+        return Vec::new();
+    }
+    let mut captions = typed_text::extract_typed_captions(events);
+    if settings.effects.auto_redact_inputs {
+        // Never spell out what was typed into a redacted password field
+        let secret = redaction::detect_input_redactions(events, &frame_differ::CaptureMapping::IDENTITY);
+        captions.retain(|c| {
+            let start = c.states.first().map_or(0, |(t, _)| *t);
+            !secret.iter().any(|r| r.is_active(start))
+        });
+    }
+    if meta.recording_mode.as_deref() == Some("window") {
+        if let Some(ref rect) = meta.window_initial_rect {
+            for anchor in captions.iter_mut().filter_map(|c| c.anchor.as_mut()) {
+                *anchor = [anchor[0] - rect[0], anchor[1] - rect[1], anchor[2] - rect[0], anchor[3] - rect[1]];
+            }
+        }
+    }
+    timeline_cut::remap_typed_captions(&captions, time_map)
+}
+
+/// Upper bound for a highlight whose close event was never recorded.
+const UI_HIGHLIGHT_MAX_MS: u64 = 10_000;
+
+/// Dialog / menu open spans. Each open lasts until the matching close event,
+/// the next open of the same kind, or [`UI_HIGHLIGHT_MAX_MS`].
+fn extract_ui_highlights(events: &[RecordingEvent]) -> Vec<UiHighlight> {
+    let is_close = |e: &RecordingEvent, dialog: bool| match e {
+        RecordingEvent::UiDialogClose { .. } | RecordingEvent::UiDialogOpen { .. } => dialog,
+        RecordingEvent::UiMenuClose { .. } | RecordingEvent::UiMenuOpen { .. } => !dialog,
+        _ => false,
+    };
+
+    let mut highlights = Vec::new();
+    for (i, event) in events.iter().enumerate() {
+        let (t, name, rect, dialog) = match event {
+            RecordingEvent::UiDialogOpen { t, name, rect, .. } => (*t, name, rect, true),
+            RecordingEvent::UiMenuOpen { t, name, rect, .. } => (*t, name, rect, false),
+            _ => continue,
+        };
+        if rect[2] <= rect[0] || rect[3] <= rect[1] {
+            continue;
+        }
+        let end_ms = events[i + 1..]
+            .iter()
+            .find(|e| is_close(e, dialog))
+            .map(event_timestamp)
+            .unwrap_or(u64::MAX)
+            .min(t + UI_HIGHLIGHT_MAX_MS);
+        highlights.push(UiHighlight {
+            rect: *rect,
+            start_ms: t,
+            end_ms,
+            label: name.trim().to_string(),
+        });
+    }
+    highlights
+}
+
+/// Find cursor position at a given time with linear interpolation between samples.
+fn find_cursor_at_time(positions: &[(u64, f64, f64)], time_ms: u64) -> Option<(f64, f64)> {
+    if positions.is_empty() {
+        return None;
+    }
+    match positions.binary_search_by_key(&time_ms, |&(t, _, _)| t) {
+        Ok(i) => Some((positions[i].1, positions[i].2)),
+        Err(0) => Some((positions[0].1, positions[0].2)),
+        Err(i) if i >= positions.len() => {
+            let last = &positions[positions.len() - 1];
+            Some((last.1, last.2))
+        }
+        Err(i) => {
+            // Linear interpolation between positions[i-1] and positions[i]
+            let (t0, x0, y0) = positions[i - 1];
+            let (t1, x1, y1) = positions[i];
+            let dt = (t1 - t0) as f64;
+            if dt <= 0.0 {
+                return Some((x0, y0));
+            }
+            let t = (time_ms - t0) as f64 / dt;
+            Some((x0 + (x1 - x0) * t, y0 + (y1 - y0) * t))
+        }
+    }
+}
+
+/// 録画フレームのパスを解決する。新しい録画は `.jpg`、旧録画は `.png` で保存されている
+/// ため両方をチェックする。ファイルが見つからない場合は `.jpg` のパスを返す（呼び出し側が
+/// エラーを出す想定）。
 pub fn recording_frame_path(frames_dir: &std::path::Path, idx: u64) -> std::path::PathBuf {
     let jpg = frames_dir.join(format!("frame_{:08}.jpg", idx));
     if jpg.exists() {
@@ -1044,26 +2432,6 @@ pub fn recording_frame_path(frames_dir: &std::path::Path, idx: u64) -> std::path
     jpg
 }
 
-/// capture 側が保存した frame_timestamps.txt を読み込み、録画開始からの各フレームの
-/// 経過 ms を返す。ファイルが無い・行が壊れている場合は None。
-///
-/// 返り値を使うことで「(frame_count - 1) * 1000 / 最終タイムスタンプ」として
-/// 実キャプチャ fps を算出でき、meta.duration_ms の末尾sleepバイアスを回避できる。
-fn read_frame_timestamps(recording_dir: &std::path::Path) -> Option<Vec<u64>> {
-    let path = recording_dir.join("frame_timestamps.txt");
-    let content = std::fs::read_to_string(&path).ok()?;
-    let mut out = Vec::with_capacity(1024);
-    for line in content.lines() {
-        let s = line.trim();
-        if s.is_empty() { continue; }
-        match s.parse::<u64>() {
-            Ok(v) => out.push(v),
-            Err(_) => return None,
-        }
-    }
-    if out.is_empty() { None } else { Some(out) }
-}
-
 fn read_frame_count(recording_dir: &std::path::Path) -> u64 {
     // meta.json に統合された frame_count を優先的に読む。
     // 旧録画 (frame_count フィールドなし) では frame_count.txt にフォールバックする。
@@ -1082,8 +2450,220 @@ fn read_frame_count(recording_dir: &std::path::Path) -> u64 {
         .unwrap_or(0)
 }
 
+/// The recording's time ↔ frame mapping (see [`FrameIndex`]).
+pub fn load_frame_index(recording_id: &str) -> Result<FrameIndex> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+    let meta_str = std::fs::read_to_string(recording_dir.join("meta.json"))?;
+    let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
+    Ok(FrameIndex::load(&recording_dir, &meta, read_frame_count(&recording_dir)))
+}
+
+/// Privacy review of a recording: risky events, plus email addresses read
+/// from a frame every [`privacy_review::OCR_SAMPLE_MS`] when OCR is available.
+pub fn review_privacy(recording_id: &str) -> Result<PrivacyReport> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+    let meta_str = std::fs::read_to_string(recording_dir.join("meta.json"))?;
+    let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
+    // Sensitive windows come from window_events.jsonl, which load_events leaves out
+    let mut events = load_events(&recording_dir).unwrap_or_default();
+    events.extend(load_window_events(&recording_dir));
+    events.sort_by_key(event_timestamp);
+    let mut findings = privacy_review::scan_events(&events, &frame_differ::CaptureMapping::from_meta(&meta));
+
+    let recognizer = crate::engine::ocr::TextRecognizer::new();
+    let frame_count = read_frame_count(&recording_dir);
+    if let Some(recognizer) = recognizer.as_ref().filter(|_| frame_count > 0) {
+        let frame_index = FrameIndex::load(&recording_dir, &meta, frame_count);
+        let source = FrameSource::open(&recording_dir.join("frames"))?;
+        let last_ms = frame_index.time_of(frame_count - 1);
+        let samples = (0..=last_ms).step_by(privacy_review::OCR_SAMPLE_MS as usize).filter_map(|t| {
+            let frame = source.load(frame_index.frame_at(t)).ok()?.to_rgba8();
+            Some((t, recognizer.recognize(&frame)?))
+        });
+        findings.extend(privacy_review::find_visible_emails(samples, privacy_review::OCR_SAMPLE_MS));
+    } else if recognizer.is_none() {
+        log::info!("OCR unavailable; privacy review skips visible text");
+    }
+    findings.sort_by_key(|f| f.start_ms);
+    log::info!("Privacy review of {}: {} findings", recording_id, findings.len());
+    Ok(PrivacyReport { findings, ocr_checked: recognizer.is_some() })
+}
+
+// --- Per-recording edit files (redactions, annotations, keyframes, scene edits, bookmarks) ---
+
+const REDACTIONS_FILE: &str = "redactions.json";
+const ANNOTATIONS_FILE: &str = "annotations.json";
+const KEYFRAMES_FILE: &str = "keyframes.json";
+const SCENE_EDITS_FILE: &str = "scene_edits.json";
+const BOOKMARKS_FILE: &str = "bookmarks.json";
+const SCENE_STYLES_FILE: &str = "scene_styles.json";
+
+/// A list saved next to meta.json (empty if the file is missing or
+/// unreadable).
+fn load_recording_list<T: serde::de::DeserializeOwned>(recording_dir: &std::path::Path, file: &str) -> Vec<T> {
+    let path = recording_dir.join(file);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::warn!("Ignoring unreadable {}: {}", path.display(), e);
+        Vec::new()
+    })
+}
+
+/// Save a list next to meta.json; an empty list removes the file.
+fn save_recording_list<T: serde::Serialize>(recording_id: &str, file: &str, items: &[T]) -> Result<()> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+    if !recording_dir.join("meta.json").exists() {
+        return Err(anyhow::anyhow!("Recording {} not found", recording_id));
+    }
+    let path = recording_dir.join(file);
+    if items.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(items)?)?;
+    Ok(())
+}
+
+/// Saved redactions plus, with `auto_redact_inputs`, password fields while
+/// they have focus, re-timed onto the output timeline.
+fn build_redactions(
+    recording_dir: &std::path::Path,
+    events: &[RecordingEvent],
+    meta: &RecordingMeta,
+    settings: &AppSettings,
+    time_map: &TimeMap,
+) -> Vec<Redaction> {
+    let mut redactions: Vec<Redaction> = load_recording_list(recording_dir, REDACTIONS_FILE);
+    if settings.effects.auto_redact_inputs {
+        let inputs = redaction::detect_input_redactions(events, &frame_differ::CaptureMapping::from_meta(meta));
+        log::info!("Auto redaction: {} password field focus spans", inputs.len());
+        redactions.extend(inputs);
+    }
+    timeline_cut::remap_redactions(&redactions, time_map)
+}
+
+pub fn get_redactions(recording_id: &str) -> Vec<Redaction> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+    load_recording_list(&recording_dir, REDACTIONS_FILE)
+}
+
+pub fn save_redactions(recording_id: &str, redactions: &[Redaction]) -> Result<()> {
+    save_recording_list(recording_id, REDACTIONS_FILE, redactions)
+}
+
+pub fn get_annotations(recording_id: &str) -> Vec<Annotation> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+    load_recording_list(&recording_dir, ANNOTATIONS_FILE)
+}
+
+pub fn save_annotations(recording_id: &str, annotations: &[Annotation]) -> Result<()> {
+    save_recording_list(recording_id, ANNOTATIONS_FILE, annotations)
+}
+
+/// Bookmarks set in the Timeline UI, ordered by time.
+pub fn get_bookmarks(recording_id: &str) -> Vec<Bookmark> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+    let mut bookmarks: Vec<Bookmark> = load_recording_list(&recording_dir, BOOKMARKS_FILE);
+    bookmarks.sort_by_key(|b| b.time_ms);
+    bookmarks
+}
+
+pub fn save_bookmarks(recording_id: &str, bookmarks: &[Bookmark]) -> Result<()> {
+    save_recording_list(recording_id, BOOKMARKS_FILE, bookmarks)
+}
+
+/// Per-scene style overrides set in the Timeline UI.
+pub fn get_scene_styles(recording_id: &str) -> Vec<SceneStyleOverride> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+    load_recording_list(&recording_dir, SCENE_STYLES_FILE)
+}
+
+pub fn save_scene_styles(recording_id: &str, overrides: &[SceneStyleOverride]) -> Result<()> {
+    save_recording_list(recording_id, SCENE_STYLES_FILE, overrides)
+}
+
+/// Zoom keyframes edited in the Timeline UI. When saved, `export` uses them
+/// instead of generating a zoom plan.
+pub fn get_keyframes(recording_id: &str) -> Vec<ZoomKeyframe> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+    load_recording_list(&recording_dir, KEYFRAMES_FILE)
+}
+
+/// Saving an empty list removes the file, going back to generated zoom.
+pub fn save_keyframes(recording_id: &str, keyframes: &[ZoomKeyframe]) -> Result<()> {
+    save_recording_list(recording_id, KEYFRAMES_FILE, keyframes)
+}
+
+/// Scene edits applied in the Timeline UI, replayed onto the auto-detected
+/// scenes by `get_recording_scenes` and on export.
+pub fn get_scene_edits(recording_id: &str) -> Vec<crate::engine::scene_splitter::SceneEditOp> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+    load_recording_list(&recording_dir, SCENE_EDITS_FILE)
+}
+
 // --- Thumbnail generation ---
 
+/// Path of an up-to-date thumbnail, regenerating it when missing or when the
+/// frames changed since it was built (see [`cache`]).
+pub fn ensure_thumbnail(recording_id: &str) -> Result<String> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+
+    let thumb_path = recording_dir.join("thumbnail.png");
+    // Frames deleted by the storage policies: the last thumbnail is all there is
+    if thumb_path.exists() && !recording_dir.join("frames").exists() {
+        return Ok(thumb_path.to_string_lossy().to_string());
+    }
+    let fingerprint = cache::recording_fingerprint(&recording_dir);
+    if thumb_path.exists() && cache::is_fresh(&recording_dir, cache::THUMBNAIL, &fingerprint) {
+        return Ok(thumb_path.to_string_lossy().to_string());
+    }
+    generate_thumbnail(recording_id)
+}
+
 pub fn generate_thumbnail(recording_id: &str) -> Result<String> {
     let recording_dir = dirs::video_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
@@ -1091,24 +2671,25 @@ pub fn generate_thumbnail(recording_id: &str) -> Result<String> {
         .join("recordings")
         .join(recording_id);
 
+    // 入力の指紋はフレームを読む前に取る（生成中の書き換えは次回検出される）
+    let fingerprint = cache::recording_fingerprint(&recording_dir);
     let frames_dir = recording_dir.join("frames");
     let frame_count = read_frame_count(&recording_dir).max(1);
     let target_frame = (frame_count as f64 * 0.3) as u64;
 
-    // Try target frame, then fallback to frame 0. recording_frame_path が .jpg/.png を
-    // 解決するので、旧録画（PNG 保存）と新録画（JPEG 保存）の両方に対応する。
-    let target_path = recording_frame_path(&frames_dir, target_frame);
-    let frame_path = if target_path.exists() {
-        target_path
+    // Try target frame, then fallback to frame 0. FrameSource が .jpg/.png と
+    // パック形式を解決するので、旧録画（PNG 保存）と新録画の両方に対応する。
+    let source = FrameSource::open(&frames_dir)?;
+    let frame_idx = if source.contains(target_frame) {
+        target_frame
     } else {
-        let fallback = recording_frame_path(&frames_dir, 0);
-        if !fallback.exists() {
+        if !source.contains(0) {
             return Err(anyhow::anyhow!("No frames found for thumbnail"));
         }
-        fallback
+        0
     };
 
-    let img = image::open(&frame_path)?;
+    let img = source.load(frame_idx)?;
     let thumb_width = 640u32;
     let thumb_height = (img.height() as f64 * (thumb_width as f64 / img.width() as f64)) as u32;
     let thumbnail = image::imageops::resize(
@@ -1120,13 +2701,95 @@ pub fn generate_thumbnail(recording_id: &str) -> Result<String> {
 
     let thumb_path = recording_dir.join("thumbnail.png");
     thumbnail.save(&thumb_path)?;
+    if let Err(e) = cache::record(&recording_dir, cache::THUMBNAIL, &fingerprint) {
+        log::warn!("Failed to update cache manifest: {}", e);
+    }
 
     Ok(thumb_path.to_string_lossy().to_string())
 }
 
+// --- Preview proxy ---
+
+/// Width of the editor's preview proxy (height follows the aspect ratio)
+const PREVIEW_PROXY_WIDTH: u32 = 960;
+const PREVIEW_PROXY_CRF: u32 = 32;
+pub const PREVIEW_PROXY_FILE: &str = "preview.mp4";
+
+/// Inputs the preview proxy of `recording_dir` is built from.
+pub fn preview_fingerprint(recording_dir: &std::path::Path, settings: &AppSettings) -> cache::Fingerprint {
+    cache::recording_fingerprint(recording_dir)
+        .file(&recording_dir.join(REDACTIONS_FILE))
+        .value(&settings.effects.auto_redact_inputs)
+}
+
+/// Path of a low-resolution MP4 of the recording for smooth playback in the
+/// editor, built on first use and cached next to meta.json.
+///
+/// The proxy has no zoom or other effects (the editor draws those itself);
+/// only redactions are burned in so secrets never show up in the editor
+/// either. It is rebuilt when the frames, the redactions or the
+/// `auto_redact_inputs` setting change.
+pub fn generate_preview(recording_id: &str, settings: &AppSettings) -> Result<String> {
+    let recording_dir = dirs::video_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id);
+
+    let proxy_path = recording_dir.join(PREVIEW_PROXY_FILE);
+    let fingerprint = preview_fingerprint(&recording_dir, settings);
+    if proxy_path.exists() && cache::is_fresh(&recording_dir, cache::PREVIEW, &fingerprint) {
+        return Ok(proxy_path.to_string_lossy().to_string());
+    }
+
+    let meta_str = std::fs::read_to_string(recording_dir.join("meta.json"))?;
+    let meta: RecordingMeta = serde_json::from_str(&meta_str)?;
+    let frame_count = read_frame_count(&recording_dir);
+    if frame_count == 0 {
+        return Err(anyhow::anyhow!("Recording {} has no frames", recording_id));
+    }
+    let fps = if meta.duration_ms > 0 {
+        frame_count as f64 * 1000.0 / meta.duration_ms as f64
+    } else {
+        meta.fps as f64
+    };
+
+    let raw_events = load_events(&recording_dir).unwrap_or_default();
+    let events = preprocess(&raw_events, &PreprocessConfig::from_settings(&settings.effects)).events;
+    let redactions = build_redactions(&recording_dir, &events, &meta, settings, &TimeMap::new(&[], &[]));
+
+    let temp_dir = super::cleanup::export_temp_dir()?;
+    let source = FrameSource::open_or_files(&recording_dir.join("frames"));
+    let frame_index = FrameIndex::load(&recording_dir, &meta, frame_count);
+    let mut written: u64 = 0;
+    for frame_idx in 0..frame_count {
+        let mut frame = match source.load(frame_idx) {
+            Ok(img) => img.to_rgba8(),
+            Err(_) => continue,
+        };
+        redaction::apply_redactions(&mut frame, &redactions, frame_index.time_of(frame_idx));
+        let height = ((frame.height() as f64 * PREVIEW_PROXY_WIDTH as f64 / frame.width().max(1) as f64) as u32 / 2 * 2).max(2);
+        let small = image::imageops::resize(&frame, PREVIEW_PROXY_WIDTH, height, image::imageops::FilterType::Triangle);
+        let rgb = image::DynamicImage::ImageRgba8(small).to_rgb8();
+        save_rgb_as_jpeg(&rgb, &temp_dir.path().join(format!("frame_{:08}.jpg", written)), INTERMEDIATE_JPEG_QUALITY)?;
+        written += 1;
+    }
+    if written == 0 {
+        return Err(anyhow::anyhow!("No frames found for preview"));
+    }
+
+    let ffmpeg = find_ffmpeg()?;
+    let input_pattern = temp_dir.path().join("frame_%08d.jpg").to_string_lossy().to_string();
+    encode_frames_with_recorded_audio(&ffmpeg, &input_pattern, &recording_dir, &proxy_path, PREVIEW_PROXY_CRF, fps)?;
+    if let Err(e) = cache::record(&recording_dir, cache::PREVIEW, &fingerprint) {
+        log::warn!("Failed to update cache manifest: {}", e);
+    }
+    Ok(proxy_path.to_string_lossy().to_string())
+}
+
 // --- FFmpeg discovery ---
 
-fn find_ffmpeg() -> Result<String> {
+pub fn find_ffmpeg() -> Result<String> {
     // Try bundled ffmpeg first (next to exe)
     let exe_dir = std::env::current_exe()
         .ok()
@@ -1210,6 +2873,7 @@ fn encode_mp4(
     params: &EncodingParams,
     recording_dir: &std::path::Path,
     input_fps: f64,
+    audio: &AudioTiming,
 ) -> Result<()> {
     let mut cmd = Command::new(ffmpeg);
 
@@ -1244,8 +2908,15 @@ fn encode_mp4(
         .args(["-movflags", "+faststart"])
         .args(["-r"])
         .arg(params.fps.to_string());
+    if let Some(times) = params.force_key_frames_arg() {
+        cmd.args(["-force_key_frames"]).arg(times);
+    }
 
     if has_audio {
+        // Apply cuts / speed ranges to the audio track so it stays in sync with the frames
+        if let Some(filter) = audio.filter() {
+            cmd.args(["-af"]).arg(filter);
+        }
         cmd.args(["-c:a", "aac", "-b:a", "128k", "-shortest"]);
     }
 
@@ -1261,55 +2932,276 @@ fn encode_mp4(
     Ok(())
 }
 
+/// Encode the recording's own frames (JPEG, or PNG in old recordings) and
+/// audio as recorded, rounding odd screen sizes down to even for yuv420p.
+/// Packed frames are unpacked to temporary JPEGs first.
+fn encode_plain_mp4(
+    ffmpeg: &str,
+    recording_dir: &std::path::Path,
+    output: &std::path::Path,
+    crf: u32,
+    input_fps: f64,
+) -> Result<()> {
+    let frames_dir = recording_dir.join("frames");
+    if let FrameSource::Pack(pack) = FrameSource::open(&frames_dir)? {
+        let temp_dir = super::cleanup::export_temp_dir()?;
+        for frame_idx in 0..pack.len() {
+            let rgb = image::DynamicImage::ImageRgba8(pack.read(frame_idx)?).to_rgb8();
+            save_rgb_as_jpeg(&rgb, &temp_dir.path().join(format!("frame_{:08}.jpg", frame_idx)), INTERMEDIATE_JPEG_QUALITY)?;
+        }
+        let input_pattern = temp_dir.path().join("frame_%08d.jpg").to_string_lossy().to_string();
+        return encode_frames_with_recorded_audio(ffmpeg, &input_pattern, recording_dir, output, crf, input_fps);
+    }
+    let first_frame = recording_frame_path(&frames_dir, 0);
+    let extension = first_frame.extension().and_then(|e| e.to_str()).unwrap_or("jpg");
+    let input_pattern = frames_dir.join(format!("frame_%08d.{}", extension)).to_string_lossy().to_string();
+    encode_frames_with_recorded_audio(ffmpeg, &input_pattern, recording_dir, output, crf, input_fps)
+}
+
+/// Frames matching `input_pattern` plus the recording's audio as recorded
+/// (used by the plain export and the preview proxy).
+fn encode_frames_with_recorded_audio(
+    ffmpeg: &str,
+    input_pattern: &str,
+    recording_dir: &std::path::Path,
+    output: &std::path::Path,
+    crf: u32,
+    input_fps: f64,
+) -> Result<()> {
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(["-y", "-framerate"])
+        .arg(format!("{:.2}", input_fps))
+        .args(["-i"])
+        .arg(input_pattern);
+
+    let audio_path = recording_dir.join("audio.wav");
+    let has_audio = audio_path.exists()
+        && std::fs::metadata(&audio_path)
+            .map(|m| m.len() > 44)
+            .unwrap_or(false);
+    if has_audio {
+        cmd.args(["-i"])
+            .arg(audio_path.to_string_lossy().to_string());
+    }
+
+    cmd.args(["-vf", "scale=trunc(iw/2)*2:trunc(ih/2)*2"])
+        .args(["-c:v", "libx264"])
+        .args(["-crf"])
+        .arg(crf.to_string())
+        .args(["-preset", "veryfast"])
+        .args(["-pix_fmt", "yuv420p"])
+        .args(["-movflags", "+faststart"]);
+    if has_audio {
+        cmd.args(["-c:a", "aac", "-b:a", "128k", "-shortest"]);
+    }
+    cmd.arg(output.to_string_lossy().to_string());
+
+    log::info!("FFmpeg plain MP4 command: {:?}", cmd);
+    let result = cmd.output()?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(anyhow::anyhow!("FFmpeg plain MP4 encoding failed: {}", stderr));
+    }
+    Ok(())
+}
+
+/// Lower bounds for the file-size reduction loop in [`encode_gif`].
+const GIF_MIN_FPS: u32 = 5;
+const GIF_MIN_WIDTH: u32 = 240;
+const GIF_SIZE_RETRIES: u32 = 5;
+
+/// Encode a GIF with the options in `params.gif`. When `max_file_size_kb` is
+/// set and the result is too large, fps and width are lowered and the GIF is
+/// re-encoded until it fits (or the lower bounds are reached).
 fn encode_gif(
     ffmpeg: &str,
     frames_dir: &std::path::Path,
     output: &std::path::Path,
     params: &EncodingParams,
     input_fps: f64,
+) -> Result<()> {
+    let opts = &params.gif;
+    let mut fps = opts.fps.max(1);
+    let mut width = params.canvas_width.min(opts.max_width.max(1));
+
+    for attempt in 0..=GIF_SIZE_RETRIES {
+        encode_gif_pass(ffmpeg, frames_dir, output, input_fps, fps, width, opts)?;
+
+        let Some(limit_kb) = opts.max_file_size_kb else {
+            return Ok(());
+        };
+        let limit = limit_kb as u64 * 1024;
+        let size = std::fs::metadata(output)?.len();
+        if size <= limit {
+            return Ok(());
+        }
+        if attempt == GIF_SIZE_RETRIES || (fps <= GIF_MIN_FPS && width <= GIF_MIN_WIDTH) {
+            log::warn!(
+                "GIF is {}KB, above the {}KB target even at {}fps / {}px",
+                size / 1024,
+                limit_kb,
+                fps,
+                width,
+            );
+            return Ok(());
+        }
+
+        // GIF size grows roughly with width × fps: shrink both by sqrt of the overshoot
+        let scale = (limit as f64 / size as f64).sqrt().clamp(0.5, 0.9);
+        width = ((width as f64 * scale) as u32).max(GIF_MIN_WIDTH);
+        fps = ((fps as f64 * scale).round() as u32).max(GIF_MIN_FPS);
+        log::info!(
+            "GIF {}KB exceeds {}KB target, retrying at {}fps / {}px",
+            size / 1024,
+            limit_kb,
+            fps,
+            width,
+        );
+    }
+
+    Ok(())
+}
+
+/// One palettegen + paletteuse pass.
+fn encode_gif_pass(
+    ffmpeg: &str,
+    frames_dir: &std::path::Path,
+    output: &std::path::Path,
+    input_fps: f64,
+    fps: u32,
+    width: u32,
+    opts: &GifOptions,
 ) -> Result<()> {
     let palette_path = output.with_extension("palette.png");
-    let width = params.canvas_width.min(640);
+    let frames_pattern = frames_dir
+        .join("frame_%08d.jpg")
+        .to_string_lossy()
+        .to_string();
 
     // Pass 1: Generate palette
     Command::new(ffmpeg)
         .args(["-y", "-framerate"])
         .arg(format!("{:.2}", input_fps))
         .args(["-i"])
-        .arg(
-            frames_dir
-                .join("frame_%08d.jpg")
-                .to_string_lossy()
-                .to_string(),
-        )
+        .arg(&frames_pattern)
         .args(["-vf"])
-        .arg(format!("fps=15,scale={}:-1:flags=lanczos,palettegen", width))
+        .arg(format!("fps={},scale={}:-1:flags=lanczos,palettegen", fps, width))
         .arg(palette_path.to_string_lossy().to_string())
         .output()?;
 
     // Pass 2: Generate GIF with palette
-    Command::new(ffmpeg)
+    let result = Command::new(ffmpeg)
         .args(["-y", "-framerate"])
         .arg(format!("{:.2}", input_fps))
         .args(["-i"])
-        .arg(
-            frames_dir
-                .join("frame_%08d.jpg")
-                .to_string_lossy()
-                .to_string(),
-        )
+        .arg(&frames_pattern)
         .args(["-i"])
         .arg(palette_path.to_string_lossy().to_string())
         .args(["-lavfi"])
         .arg(format!(
-            "fps=15,scale={}:-1:flags=lanczos[x];[x][1:v]paletteuse",
-            width
+            "fps={},scale={}:-1:flags=lanczos[x];[x][1:v]paletteuse=dither={}",
+            fps,
+            width,
+            opts.dither.ffmpeg_name()
         ))
+        .args(["-loop"])
+        .arg(opts.loop_count.to_string())
         .arg(output.to_string_lossy().to_string())
         .output()?;
 
     let _ = std::fs::remove_file(&palette_path);
 
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(anyhow::anyhow!("FFmpeg GIF encoding failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Input and scaling arguments shared by WebP and APNG: the composed frames
+/// at `input_fps`, resampled to `params.gif.fps` and scaled down to its max
+/// width.
+fn animated_image_input_args(frames_dir: &std::path::Path, params: &EncodingParams, input_fps: f64) -> Vec<String> {
+    let opts = &params.gif;
+    let width = params.canvas_width.min(opts.max_width.max(1));
+    vec![
+        "-y".to_string(),
+        "-framerate".to_string(),
+        format!("{:.2}", input_fps),
+        "-i".to_string(),
+        frames_dir.join("frame_%08d.jpg").to_string_lossy().to_string(),
+        "-vf".to_string(),
+        format!("fps={},scale={}:-1:flags=lanczos", opts.fps.max(1), width),
+    ]
+}
+
+/// FFmpeg arguments for an animated WebP of the composed frames.
+fn webp_args(frames_dir: &std::path::Path, output: &std::path::Path, params: &EncodingParams, input_fps: f64) -> Vec<String> {
+    // CRF 18 → 88, 23 → 75, 30 → 58
+    let quality = (100.0 - (params.crf as f64 - 13.0) * 2.5).clamp(40.0, 95.0) as u32;
+    // WebP: 0 = infinite, n = play n times
+    let loop_count = if params.gif.loop_count < 0 { 1 } else { params.gif.loop_count };
+    let mut args = animated_image_input_args(frames_dir, params, input_fps);
+    args.extend(
+        ["-c:v", "libwebp", "-lossless", "0", "-preset", "picture", "-q:v", &quality.to_string(), "-loop", &loop_count.to_string()]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
+/// FFmpeg arguments for an animated PNG of the composed frames.
+fn apng_args(frames_dir: &std::path::Path, output: &std::path::Path, params: &EncodingParams, input_fps: f64) -> Vec<String> {
+    // APNG: plays 0 = infinite, n = play n times
+    let plays = if params.gif.loop_count < 0 { 1 } else { params.gif.loop_count };
+    let mut args = animated_image_input_args(frames_dir, params, input_fps);
+    args.extend(["-f", "apng", "-plays", &plays.to_string()].iter().map(|s| s.to_string()));
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
+/// Animated WebP (libwebp). Uses the fps / max width / loop settings of
+/// `params.gif`; quality follows the preset's CRF.
+fn encode_webp(
+    ffmpeg: &str,
+    frames_dir: &std::path::Path,
+    output: &std::path::Path,
+    params: &EncodingParams,
+    input_fps: f64,
+) -> Result<()> {
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(webp_args(frames_dir, output, params, input_fps));
+
+    log::info!("FFmpeg WebP command: {:?}", cmd);
+    let result = cmd.output()?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(anyhow::anyhow!("FFmpeg WebP encoding failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Animated PNG. Uses the fps / max width / loop settings of `params.gif`.
+fn encode_apng(
+    ffmpeg: &str,
+    frames_dir: &std::path::Path,
+    output: &std::path::Path,
+    params: &EncodingParams,
+    input_fps: f64,
+) -> Result<()> {
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(apng_args(frames_dir, output, params, input_fps));
+
+    log::info!("FFmpeg APNG command: {:?}", cmd);
+    let result = cmd.output()?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(anyhow::anyhow!("FFmpeg APNG encoding failed: {}", stderr));
+    }
+
     Ok(())
 }
 
@@ -1320,19 +3212,16 @@ fn encode_webm(
     params: &EncodingParams,
     recording_dir: &std::path::Path,
     input_fps: f64,
+    audio: &AudioTiming,
+    intermediate: IntermediateFormat,
 ) -> Result<()> {
     let mut cmd = Command::new(ffmpeg);
 
-    // Input: composed BMP frames at actual recording framerate
+    // Input: composed frames at actual recording framerate
     cmd.args(["-y", "-framerate"])
         .arg(format!("{:.2}", input_fps))
         .args(["-i"])
-        .arg(
-            frames_dir
-                .join("frame_%08d.jpg")
-                .to_string_lossy()
-                .to_string(),
-        );
+        .arg(intermediate.input_pattern(frames_dir));
 
     // Add audio input if available and non-empty
     let audio_path = recording_dir.join("audio.wav");
@@ -1352,8 +3241,18 @@ fn encode_webm(
         .args(["-b:v", "0"])
         .args(["-r"])
         .arg(params.fps.to_string());
+    if let Some(times) = params.force_key_frames_arg() {
+        cmd.args(["-force_key_frames"]).arg(times);
+    }
+    if intermediate.has_alpha() {
+        // VP9 alpha: yuva420p, and alt-ref frames don't support alpha
+        cmd.args(["-pix_fmt", "yuva420p", "-auto-alt-ref", "0"]);
+    }
 
     if has_audio {
+        if let Some(filter) = audio.filter() {
+            cmd.args(["-af"]).arg(filter);
+        }
         cmd.args(["-c:a", "libopus", "-shortest"]);
     }
 
@@ -1368,3 +3267,237 @@ fn encode_webm(
 
     Ok(())
 }
+
+/// QuickTime MOV with ProRes. Transparent exports use ProRes 4444 with an
+/// alpha channel, opaque ones ProRes 422 HQ.
+fn encode_mov(
+    ffmpeg: &str,
+    frames_dir: &std::path::Path,
+    output: &std::path::Path,
+    recording_dir: &std::path::Path,
+    input_fps: f64,
+    audio: &AudioTiming,
+    intermediate: IntermediateFormat,
+) -> Result<()> {
+    let mut cmd = Command::new(ffmpeg);
+
+    cmd.args(["-y", "-framerate"])
+        .arg(format!("{:.2}", input_fps))
+        .args(["-i"])
+        .arg(intermediate.input_pattern(frames_dir));
+
+    let audio_path = recording_dir.join("audio.wav");
+    let has_audio = audio_path.exists()
+        && std::fs::metadata(&audio_path)
+            .map(|m| m.len() > 44)
+            .unwrap_or(false);
+    if has_audio {
+        cmd.args(["-i"])
+            .arg(audio_path.to_string_lossy().to_string());
+    }
+
+    cmd.args(["-c:v", "prores_ks"]);
+    if intermediate.has_alpha() {
+        cmd.args(["-profile:v", "4444", "-pix_fmt", "yuva444p10le", "-alpha_bits", "16"]);
+    } else {
+        cmd.args(["-profile:v", "3", "-pix_fmt", "yuv422p10le"]);
+    }
+
+    if has_audio {
+        if let Some(filter) = audio.filter() {
+            cmd.args(["-af"]).arg(filter);
+        }
+        cmd.args(["-c:a", "pcm_s16le", "-shortest"]);
+    }
+
+    cmd.arg(output.to_string_lossy().to_string());
+
+    log::info!("FFmpeg MOV command: {:?}", cmd);
+    let result = cmd.output()?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(anyhow::anyhow!("FFmpeg MOV encoding failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Audio track only (mic + system mix from `audio.wav`), with cuts and
+/// speed ranges applied so it matches a video export of the same recording.
+fn encode_audio_only(
+    ffmpeg: &str,
+    output: &std::path::Path,
+    recording_dir: &std::path::Path,
+    time_map: &TimeMap,
+    codec: AudioOnlyCodec,
+) -> Result<()> {
+    let audio_path = recording_dir.join("audio.wav");
+    let has_audio = audio_path.exists()
+        && std::fs::metadata(&audio_path)
+            .map(|m| m.len() > 44)
+            .unwrap_or(false);
+    if !has_audio {
+        return Err(anyhow::anyhow!("This recording has no audio track"));
+    }
+
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(["-y", "-i"])
+        .arg(audio_path.to_string_lossy().to_string())
+        .arg("-vn");
+    if let Some(filter) = time_map.audio_filter() {
+        cmd.args(["-af"]).arg(filter);
+    }
+    match codec {
+        AudioOnlyCodec::Aac => cmd.args(["-c:a", "aac", "-b:a", "160k", "-movflags", "+faststart"]),
+        AudioOnlyCodec::Opus => cmd.args(["-c:a", "libopus", "-b:a", "96k"]),
+    };
+    cmd.arg(output.to_string_lossy().to_string());
+
+    log::info!("FFmpeg audio-only command: {:?}", cmd);
+    let result = cmd.output()?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(anyhow::anyhow!("FFmpeg audio encoding failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::analyzer::Rect;
+    use crate::engine::scene_splitter::Scene;
+
+    /// A recording of solid-colour frames, one per second.
+    fn fixture_recording(dir: &std::path::Path, colors: &[[u8; 3]]) -> RecordingMeta {
+        let frames_dir = dir.join("frames");
+        std::fs::create_dir_all(&frames_dir).unwrap();
+        for (i, color) in colors.iter().enumerate() {
+            image::RgbImage::from_pixel(64, 36, image::Rgb(*color))
+                .save(frames_dir.join(format!("frame_{:08}.jpg", i)))
+                .unwrap();
+        }
+        let timestamps: String = (0..colors.len()).map(|i| format!("{}\n", i * 1000)).collect();
+        std::fs::write(dir.join(crate::recording::capture::FRAME_TIMESTAMPS_FILE), timestamps).unwrap();
+        let meta = RecordingMeta {
+            version: 3,
+            id: "fixture".to_string(),
+            screen_width: 64,
+            screen_height: 36,
+            fps: 1,
+            start_time: "2024-05-01T09:00:00+09:00".to_string(),
+            duration_ms: colors.len() as u64 * 1000,
+            has_audio: false,
+            monitor_scale: 1.0,
+            recording_dir: dir.to_string_lossy().to_string(),
+            recording_mode: Some("display".to_string()),
+            window_title: None,
+            window_initial_rect: None,
+            frame_count: Some(colors.len() as u32),
+            cursor_scale: None,
+            take: None,
+            splices: Vec::new(),
+            pauses: Vec::new(),
+            timelapse_speed: None,
+            title: None,
+            tags: Vec::new(),
+            notes: String::new(),
+            in_progress: false,
+        };
+        std::fs::write(dir.join("meta.json"), serde_json::to_string(&meta).unwrap()).unwrap();
+        meta
+    }
+
+    fn scene(id: u32, start_ms: u64, end_ms: u64) -> Scene {
+        Scene {
+            id,
+            start_ms,
+            end_ms,
+            bbox: Rect { x: 0.0, y: 0.0, width: 8.0, height: 8.0 },
+            center_x: 4.0,
+            center_y: 4.0,
+            zoom_level: 1.0,
+            event_count: 1,
+            ui_rect: None,
+            manual_bbox: false,
+        }
+    }
+
+    /// Colour in the middle of a preview, away from the bbox outline.
+    fn center_color(path: &str) -> [u8; 3] {
+        let img = image::open(path).unwrap().to_rgb8();
+        img.get_pixel(img.width() / 2, img.height() / 2).0
+    }
+
+    fn is_close(color: [u8; 3], expected: [u8; 3]) -> bool {
+        color.iter().zip(expected).all(|(c, e)| c.abs_diff(e) < 24)
+    }
+
+    #[test]
+    fn test_scene_boundary_preview_uses_frames_around_the_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        let (red, green, blue) = ([220, 30, 30], [30, 220, 30], [30, 30, 220]);
+        let meta = fixture_recording(dir.path(), &[red, green, blue]);
+        // Scene 1 ends on the green frame, scene 2 starts on the blue one
+        let scenes = [scene(1, 0, 1500), scene(2, 2000, 3000)];
+
+        let preview = scene_boundary_preview(dir.path(), &meta, &scenes, 1).unwrap();
+        assert_eq!(preview.boundary_ms, 1750);
+        assert!(is_close(center_color(&preview.before_path), green));
+        assert!(is_close(center_color(&preview.after_path), blue));
+        assert!(preview.before_path.ends_with("boundary_1_before.png"));
+
+        // The last scene has no boundary after it
+        assert!(scene_boundary_preview(dir.path(), &meta, &scenes, 2).is_err());
+        assert!(scene_boundary_preview(dir.path(), &meta, &scenes, 9).is_err());
+    }
+
+    fn animated_params(fps: u32, max_width: u32, loop_count: i32) -> EncodingParams {
+        let mut params = EncodingParams::from_preset(&QualityPreset::Social, 1920, 1080, None);
+        params.gif = GifOptions { fps, max_width, loop_count, ..GifOptions::default() };
+        params
+    }
+
+    /// The value following `flag` in `args`.
+    fn arg_after<'a>(args: &'a [String], flag: &str) -> &'a str {
+        let i = args.iter().position(|a| a == flag).unwrap_or_else(|| panic!("{} missing", flag));
+        &args[i + 1]
+    }
+
+    #[test]
+    fn test_webp_args() {
+        let frames = std::path::Path::new("frames");
+        let output = std::path::Path::new("out.webp");
+        let args = webp_args(frames, output, &animated_params(12, 800, 0), 29.97);
+        assert_eq!(arg_after(&args, "-framerate"), "29.97");
+        assert_eq!(arg_after(&args, "-vf"), "fps=12,scale=800:-1:flags=lanczos");
+        assert_eq!(arg_after(&args, "-c:v"), "libwebp");
+        assert_eq!(arg_after(&args, "-loop"), "0");
+        assert_eq!(args.last().unwrap(), "out.webp");
+
+        // Not looping plays once; a canvas narrower than the max isn't upscaled
+        let mut params = animated_params(0, 4000, -1);
+        params.canvas_width = 1280;
+        let args = webp_args(frames, output, &params, 30.0);
+        assert_eq!(arg_after(&args, "-vf"), "fps=1,scale=1280:-1:flags=lanczos");
+        assert_eq!(arg_after(&args, "-loop"), "1");
+        let args = webp_args(frames, output, &animated_params(10, 640, 3), 30.0);
+        assert_eq!(arg_after(&args, "-loop"), "3");
+    }
+
+    #[test]
+    fn test_apng_args() {
+        let frames = std::path::Path::new("frames");
+        let output = std::path::Path::new("out.png");
+        let args = apng_args(frames, output, &animated_params(15, 640, 0), 60.0);
+        assert_eq!(arg_after(&args, "-framerate"), "60.00");
+        assert_eq!(arg_after(&args, "-vf"), "fps=15,scale=640:-1:flags=lanczos");
+        assert_eq!(arg_after(&args, "-f"), "apng");
+        assert_eq!(arg_after(&args, "-plays"), "0");
+        assert_eq!(args.last().unwrap(), "out.png");
+
+        assert_eq!(arg_after(&apng_args(frames, output, &animated_params(15, 640, -1), 60.0), "-plays"), "1");
+        assert_eq!(arg_after(&apng_args(frames, output, &animated_params(15, 640, 2), 60.0), "-plays"), "2");
+    }
+}
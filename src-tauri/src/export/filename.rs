@@ -0,0 +1,183 @@
+//! Export filename generation.
+//!
+//! Filenames come from a user template (`OutputSettings::filename_template`)
+//! that may contain the recorded window title, so the result is sanitized to
+//! be valid on Windows, macOS and Linux filesystems. Non-ASCII text (e.g.
+//! Japanese window titles) is kept as is.
+
+use chrono::{DateTime, FixedOffset};
+
+/// Max stem length in bytes. Most filesystems limit names to 255 bytes;
+/// leave room for the extension and a collision suffix.
+const MAX_STEM_BYTES: usize = 200;
+
+/// Used when a template renders to nothing usable.
+const FALLBACK_STEM: &str = "snappi";
+
+/// Device names Windows refuses as file names (with any extension).
+const WINDOWS_RESERVED: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Values available to filename templates.
+pub struct FilenameContext<'a> {
+    pub start_time: DateTime<FixedOffset>,
+    pub recording_id: &'a str,
+    pub window_title: Option<&'a str>,
+    /// Timelapse speedup (`{speed}`), `None` for real-time recordings
+    pub speed: Option<f64>,
+    pub extension: &'a str,
+}
+
+/// Make `name` safe to use as a single path component: path separators,
+/// characters reserved on Windows and control characters become `_`,
+/// whitespace runs collapse to one space, trailing dots/spaces are removed
+/// and the result is truncated to [`MAX_STEM_BYTES`] on a char boundary.
+pub fn sanitize_component(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut prev_space = false;
+    for ch in name.chars() {
+        let ch = match ch {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c if c.is_whitespace() => ' ',
+            c => c,
+        };
+        if ch == ' ' && prev_space {
+            continue;
+        }
+        prev_space = ch == ' ';
+        out.push(ch);
+    }
+
+    let mut out = truncate_bytes(out.trim(), MAX_STEM_BYTES)
+        .trim_end_matches(['.', ' '])
+        .to_string();
+
+    let base = out.split('.').next().unwrap_or("").to_ascii_uppercase();
+    if WINDOWS_RESERVED.contains(&base.as_str()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+fn truncate_bytes(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Render `template` into a sanitized file name (with extension).
+///
+/// Placeholders: `{date}` (YYYYMMDD), `{time}` (HHMMSS), `{title}` (window
+/// title, empty for display recordings), `{id}` (first 8 chars of the
+/// recording id), `{speed}` (e.g. `30x` for a timelapse, empty otherwise).
+/// Separators left dangling by an empty `{title}` or `{speed}` are trimmed.
+pub fn render_filename(template: &str, ctx: &FilenameContext) -> String {
+    let title = ctx.window_title.unwrap_or("").trim();
+    let short_id: String = ctx.recording_id.chars().take(8).collect();
+    let speed = ctx.speed.map(|s| format!("{}x", s.round())).unwrap_or_default();
+    let rendered = template
+        .replace("{date}", &ctx.start_time.format("%Y%m%d").to_string())
+        .replace("{time}", &ctx.start_time.format("%H%M%S").to_string())
+        .replace("{title}", title)
+        .replace("{id}", &short_id)
+        .replace("{speed}", &speed);
+
+    // Trim first so the `_` escaping a reserved name ("_CON") survives; the
+    // inner pass turns characters that become dangling `_` into trimmable ones.
+    let stem = sanitize_component(sanitize_component(&rendered).trim_matches(['_', '-', ' ']));
+    let stem = if stem.is_empty() { FALLBACK_STEM } else { &stem };
+    format!("{}.{}", stem, ctx.extension)
+}
+
+/// `dir/name`, or `dir/stem_N.ext` with the first free N if it already exists.
+pub fn unique_path(dir: &std::path::Path, name: &str) -> std::path::PathBuf {
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((s, e)) => (s, format!(".{}", e)),
+        None => (name, String::new()),
+    };
+    (1..)
+        .map(|n| dir.join(format!("{}_{}{}", stem, n, ext)))
+        .find(|p| !p.exists())
+        .expect("unbounded range always yields a free path")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx<'a>(title: Option<&'a str>) -> FilenameContext<'a> {
+        FilenameContext {
+            start_time: DateTime::parse_from_rfc3339("2024-05-01T09:08:07+09:00").unwrap(),
+            recording_id: "0123456789abcdef",
+            window_title: title,
+            speed: None,
+            extension: "mp4",
+        }
+    }
+
+    #[test]
+    fn test_sanitize_keeps_non_ascii_and_replaces_reserved() {
+        assert_eq!(sanitize_component("設定 - メモ帳"), "設定 - メモ帳");
+        assert_eq!(sanitize_component("a/b\\c:d*e?f\"g<h>i|j"), "a_b_c_d_e_f_g_h_i_j");
+        assert_eq!(sanitize_component("tab\there\nnewline"), "tab_here_newline");
+        assert_eq!(sanitize_component("  many   spaces ...  "), "many spaces");
+        assert_eq!(sanitize_component("CON"), "_CON");
+        assert_eq!(sanitize_component("nul.txt"), "_nul.txt");
+    }
+
+    #[test]
+    fn test_sanitize_truncates_on_char_boundary() {
+        let long = "あ".repeat(100); // 300 bytes
+        let out = sanitize_component(&long);
+        assert!(out.len() <= MAX_STEM_BYTES);
+        assert!(out.chars().all(|c| c == 'あ'));
+    }
+
+    #[test]
+    fn test_render_default_template() {
+        assert_eq!(render_filename("{date}_{time}", &ctx(None)), "20240501_090807.mp4");
+    }
+
+    #[test]
+    fn test_render_title_template() {
+        assert_eq!(
+            render_filename("{title}_{date}", &ctx(Some("Visual Studio Code: main.rs"))),
+            "Visual Studio Code_ main.rs_20240501.mp4"
+        );
+        // Empty title leaves no leading separator
+        assert_eq!(render_filename("{title}_{date}", &ctx(None)), "20240501.mp4");
+        assert_eq!(render_filename("{title}", &ctx(Some("  "))), "snappi.mp4");
+        assert_eq!(render_filename("{id}", &ctx(None)), "01234567.mp4");
+        assert_eq!(render_filename("{date}_{speed}", &ctx(None)), "20240501.mp4");
+        let timelapse = FilenameContext { speed: Some(30.0), ..ctx(None) };
+        assert_eq!(render_filename("{date}_{speed}", &timelapse), "20240501_30x.mp4");
+    }
+
+    #[test]
+    fn test_render_keeps_reserved_name_escaped() {
+        assert_eq!(render_filename("{title}", &ctx(Some("CON"))), "_CON.mp4");
+        assert_eq!(render_filename("{title}_{speed}", &ctx(Some("aux"))), "_aux.mp4");
+        assert_eq!(render_filename("{title}", &ctx(Some("?CON?"))), "_CON.mp4");
+    }
+
+    #[test]
+    fn test_unique_path_adds_suffix() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(unique_path(dir.path(), "a.mp4"), dir.path().join("a.mp4"));
+        std::fs::write(dir.path().join("a.mp4"), b"").unwrap();
+        std::fs::write(dir.path().join("a_1.mp4"), b"").unwrap();
+        assert_eq!(unique_path(dir.path(), "a.mp4"), dir.path().join("a_2.mp4"));
+    }
+}
@@ -0,0 +1,226 @@
+//! Mapping between recording time and frame numbers.
+//!
+//! Frame `i` is shown from its capture time until the next frame's, so a
+//! time maps to the last frame captured at or before it. Capture times come
+//! from `frame_timestamps.txt` (ms since recording start, the same clock as
+//! `events.jsonl`); older recordings without it fall back to evenly spaced
+//! frames over `meta.duration_ms`. The export pipeline, the preview renderer
+//! and the `map_time_to_frame` / `map_frame_to_time` commands all go through
+//! [`FrameIndex`], so they agree on which frame belongs to which time.
+
+use crate::config::RecordingMeta;
+use crate::recording::capture::FRAME_TIMESTAMPS_FILE;
+use std::path::Path;
+
+/// Frame spacing when neither timestamps nor a duration are available
+const FALLBACK_STEP_MS: u64 = 33;
+
+pub struct FrameIndex {
+    frame_count: u64,
+    /// Capture time of every frame (ascending), when recorded
+    timestamps: Option<Vec<u64>>,
+    /// Even spacing used without timestamps
+    step_ms: u64,
+}
+
+impl FrameIndex {
+    pub fn load(recording_dir: &Path, meta: &RecordingMeta, frame_count: u64) -> Self {
+        Self::new(frame_count, read_frame_timestamps(recording_dir), meta.duration_ms, meta.fps)
+    }
+
+    /// Timestamps are only used when there is one for every frame; a short
+    /// or unordered file (interrupted capture) falls back to even spacing.
+    pub fn new(frame_count: u64, timestamps: Option<Vec<u64>>, duration_ms: u64, fps: u32) -> Self {
+        let timestamps = timestamps
+            .filter(|ts| ts.len() as u64 >= frame_count && ts.windows(2).all(|w| w[0] <= w[1]))
+            .map(|mut ts| {
+                ts.truncate(frame_count as usize);
+                ts
+            });
+        let step_ms = if frame_count > 1 && duration_ms > 0 {
+            (duration_ms / frame_count).max(1)
+        } else if fps > 0 {
+            (1000 / fps as u64).max(1)
+        } else {
+            FALLBACK_STEP_MS
+        };
+        Self { frame_count, timestamps, step_ms }
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Recording time (ms) at which `frame` starts being shown.
+    pub fn time_of(&self, frame: u64) -> u64 {
+        let frame = frame.min(self.frame_count.saturating_sub(1));
+        match self.timestamps {
+            Some(ref ts) => ts[frame as usize],
+            None => frame * self.step_ms,
+        }
+    }
+
+    /// Recording time `fraction` (0.0-1.0) of the way from `frame`'s
+    /// capture to the next frame's. Output frames repeated over a capture
+    /// gap use it to keep the cursor and effects moving.
+    pub fn time_within(&self, frame: u64, fraction: f64) -> u64 {
+        let start = self.time_of(frame);
+        if frame + 1 >= self.frame_count {
+            return start;
+        }
+        let end = self.time_of(frame + 1);
+        start + ((end - start) as f64 * fraction.clamp(0.0, 1.0)).round() as u64
+    }
+
+    /// Capture gaps of at least `min_ms`: (frame before the gap, gap length).
+    pub fn gaps(&self, min_ms: u64) -> Vec<(u64, u64)> {
+        (1..self.frame_count)
+            .map(|frame| (frame - 1, self.time_of(frame) - self.time_of(frame - 1)))
+            .filter(|&(_, gap)| gap >= min_ms)
+            .collect()
+    }
+
+    /// The frame on screen at recording time `time_ms`.
+    pub fn frame_at(&self, time_ms: u64) -> u64 {
+        if self.frame_count == 0 {
+            return 0;
+        }
+        let frame = match self.timestamps {
+            Some(ref ts) => ts.partition_point(|&t| t <= time_ms).saturating_sub(1) as u64,
+            None => time_ms / self.step_ms,
+        };
+        frame.min(self.frame_count - 1)
+    }
+}
+
+/// Fixed-rate output clock for frames captured at a varying rate. Each
+/// source frame covers the output ticks up to the next frame's time, so a
+/// frame is repeated over dropped-frame gaps and skipped when several fall
+/// within one tick; the video then follows the capture times exactly.
+///
+/// In recordings before version 3 pauses also leave gaps in the capture
+/// times, but the audio has none there: gaps longer than [`PAUSE_GAP_MS`]
+/// are closed (the frame is shown for one tick) until `pause_budget_ms`,
+/// the recording's paused time, is used up. Newer recordings are
+/// timestamped on a clock that stops while paused (budget 0).
+pub struct OutputClock {
+    step_ms: f64,
+    next_tick_ms: f64,
+    /// Output time closed over pauses so far
+    skipped_ms: f64,
+    pause_budget_ms: f64,
+}
+
+/// Capture gaps longer than this may be pauses rather than dropped frames
+pub const PAUSE_GAP_MS: u64 = 1000;
+
+impl OutputClock {
+    pub fn new(fps: f64, pause_budget_ms: u64) -> Self {
+        Self {
+            step_ms: 1000.0 / fps.max(1.0),
+            next_tick_ms: 0.0,
+            skipped_ms: 0.0,
+            pause_budget_ms: pause_budget_ms as f64,
+        }
+    }
+
+    pub fn step_ms(&self) -> f64 {
+        self.step_ms
+    }
+
+    /// Output times of the ticks that show a frame on screen from
+    /// `start_ms` until `end_ms` (output timeline, before pauses are closed).
+    pub fn ticks(&mut self, start_ms: f64, end_ms: f64) -> Vec<f64> {
+        let offset = self.skipped_ms;
+        let mut end = end_ms - offset;
+        let span = end_ms - start_ms;
+        if span > PAUSE_GAP_MS as f64 && self.pause_budget_ms > 0.0 {
+            let closed = (span - self.step_ms).min(self.pause_budget_ms);
+            self.pause_budget_ms -= closed;
+            self.skipped_ms += closed;
+            end -= closed;
+        }
+        let mut ticks = Vec::new();
+        while self.next_tick_ms < end {
+            ticks.push(self.next_tick_ms + offset);
+            self.next_tick_ms += self.step_ms;
+        }
+        ticks
+    }
+}
+
+/// capture 側が保存した frame_timestamps.txt を読み込み、録画開始からの各フレームの
+/// 経過 ms を返す。ファイルが無い・行が壊れている場合は None。
+///
+/// 返り値を使うことで「(frame_count - 1) * 1000 / 最終タイムスタンプ」として
+/// 実キャプチャ fps を算出でき、meta.duration_ms の末尾sleepバイアスを回避できる。
+pub fn read_frame_timestamps(recording_dir: &Path) -> Option<Vec<u64>> {
+    let path = recording_dir.join(FRAME_TIMESTAMPS_FILE);
+    let content = std::fs::read_to_string(&path).ok()?;
+    let mut out = Vec::with_capacity(1024);
+    for line in content.lines() {
+        let s = line.trim();
+        if s.is_empty() { continue; }
+        match s.parse::<u64>() {
+            Ok(v) => out.push(v),
+            Err(_) => return None,
+        }
+    }
+    if out.is_empty() { None } else { Some(out) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamps_round_trip() {
+        let index = FrameIndex::new(4, Some(vec![0, 40, 70, 5000]), 200, 30);
+        assert_eq!(index.frame_at(0), 0);
+        assert_eq!(index.frame_at(39), 0);
+        assert_eq!(index.frame_at(40), 1);
+        // A pause leaves frame 2 on screen until the next capture
+        assert_eq!(index.frame_at(4999), 2);
+        assert_eq!(index.frame_at(99_999), 3);
+        for frame in 0..4 {
+            assert_eq!(index.frame_at(index.time_of(frame)), frame);
+        }
+    }
+
+    #[test]
+    fn test_gaps_and_time_within() {
+        let index = FrameIndex::new(4, Some(vec![0, 33, 233, 266]), 300, 30);
+        assert_eq!(index.gaps(100), vec![(1, 200)]);
+        assert_eq!(index.time_within(1, 0.0), 33);
+        assert_eq!(index.time_within(1, 0.5), 133);
+        assert_eq!(index.time_within(1, 2.0), 233);
+        // The last frame has no next capture
+        assert_eq!(index.time_within(3, 0.5), 266);
+    }
+
+    #[test]
+    fn test_output_clock_repeats_skips_and_closes_pauses() {
+        // 10 fps output; frames at 0, 250 (two ticks dropped), 260, 280
+        let mut clock = OutputClock::new(10.0, 0);
+        assert_eq!(clock.ticks(0.0, 250.0), vec![0.0, 100.0, 200.0]);
+        assert!(clock.ticks(250.0, 260.0).is_empty());
+        assert_eq!(clock.ticks(260.0, 280.0), Vec::<f64>::new());
+        assert_eq!(clock.ticks(280.0, 380.0), vec![300.0]);
+
+        // A 5 s pause after the frame at 100 is closed to one tick
+        let mut clock = OutputClock::new(10.0, 4900);
+        assert_eq!(clock.ticks(0.0, 100.0), vec![0.0]);
+        assert_eq!(clock.ticks(100.0, 5100.0), vec![100.0]);
+        assert_eq!(clock.ticks(5100.0, 5300.0), vec![5100.0, 5200.0]);
+    }
+
+    #[test]
+    fn test_even_spacing_without_usable_timestamps() {
+        for timestamps in [None, Some(vec![0, 33]), Some(vec![0, 50, 20, 90])] {
+            let index = FrameIndex::new(4, timestamps, 400, 30);
+            assert_eq!(index.time_of(2), 200);
+            assert_eq!(index.frame_at(299), 2);
+            assert_eq!(index.frame_at(10_000), 3);
+        }
+    }
+}
@@ -1,2 +1,16 @@
+pub mod batch;
+pub mod cache;
+pub mod chapters;
+pub mod clipboard;
+pub mod cleanup;
 pub mod encoder;
+pub mod filename;
+pub mod frame_index;
 pub mod presets;
+pub mod project_archive;
+pub mod screenshot;
+pub mod share;
+pub mod split;
+pub mod storage;
+pub mod stream;
+pub mod warm_cache;
@@ -1,6 +1,7 @@
-use crate::config::QualityPreset;
+use crate::config::{AspectFit, CanvasPadding, GifOptions, OutputAspect, OutputSize, QualityPreset};
 
 const CANVAS_PADDING: u32 = 128; // 64px each side
+const MIN_OUTPUT_SIDE: u32 = 64;
 
 pub struct EncodingParams {
     pub width: Option<u32>,
@@ -9,10 +10,46 @@ pub struct EncodingParams {
     pub canvas_height: u32,
     pub fps: u32,
     pub crf: u32,
+    pub gif: GifOptions,
+    /// Width/height ratio the viewport is cropped to (zoom-crop output)
+    pub crop_aspect: Option<f64>,
+    /// Background margin around the frame
+    pub padding: CanvasPadding,
+    /// Output times (ms) that must start a keyframe (split points)
+    pub forced_keyframes_ms: Vec<u64>,
 }
 
 impl EncodingParams {
-    pub fn from_preset(preset: &QualityPreset, original_width: u32, original_height: u32) -> Self {
+    /// Encoding parameters for `preset`. `size` overrides the preset's frame
+    /// size while keeping its fps and quality. A size of another shape than
+    /// the screen crops the viewport to it (like `ZoomCrop`) rather than
+    /// stretching the screen.
+    pub fn from_preset(
+        preset: &QualityPreset,
+        original_width: u32,
+        original_height: u32,
+        size: Option<&OutputSize>,
+    ) -> Self {
+        let params = Self::preset_defaults(preset, original_width, original_height);
+        match size.and_then(|s| resolve_output_size(s, original_width, original_height)) {
+            Some((w, h)) => {
+                let src_aspect = original_width as f64 / original_height.max(1) as f64;
+                let out_aspect = w as f64 / h as f64;
+                Self {
+                    width: Some(w),
+                    height: Some(h),
+                    canvas_width: w + CANVAS_PADDING,
+                    canvas_height: h + CANVAS_PADDING,
+                    // Even rounding alone changes the aspect by well under 1%
+                    crop_aspect: ((out_aspect / src_aspect - 1.0).abs() > 0.01).then_some(out_aspect),
+                    ..params
+                }
+            }
+            None => params,
+        }
+    }
+
+    fn preset_defaults(preset: &QualityPreset, original_width: u32, original_height: u32) -> Self {
         match preset {
             QualityPreset::Social => {
                 let w = 1920u32;
@@ -24,6 +61,10 @@ impl EncodingParams {
                     canvas_height: h + CANVAS_PADDING,
                     fps: 30,
                     crf: 23,
+                    gif: GifOptions::default(),
+                    crop_aspect: None,
+                    padding: CanvasPadding::default(),
+                    forced_keyframes_ms: Vec::new(),
                 }
             }
             QualityPreset::HighQuality => Self {
@@ -33,6 +74,10 @@ impl EncodingParams {
                 canvas_height: original_height + CANVAS_PADDING,
                 fps: 60,
                 crf: 18,
+                gif: GifOptions::default(),
+                crop_aspect: None,
+                padding: CanvasPadding::default(),
+                forced_keyframes_ms: Vec::new(),
             },
             QualityPreset::Lightweight => {
                 let w = 1280u32;
@@ -44,8 +89,203 @@ impl EncodingParams {
                     canvas_height: h + CANVAS_PADDING,
                     fps: 24,
                     crf: 30,
+                    gif: GifOptions::default(),
+                    crop_aspect: None,
+                    padding: CanvasPadding::default(),
+                    forced_keyframes_ms: Vec::new(),
                 }
             }
         }
     }
+
+    pub fn with_gif_options(mut self, gif: GifOptions) -> Self {
+        self.gif = gif;
+        self
+    }
+
+    pub fn with_forced_keyframes(mut self, times_ms: Vec<u64>) -> Self {
+        self.forced_keyframes_ms = times_ms;
+        self
+    }
+
+    /// FFmpeg `-force_key_frames` value, or None when there are none.
+    pub fn force_key_frames_arg(&self) -> Option<String> {
+        (!self.forced_keyframes_ms.is_empty()).then(|| {
+            self.forced_keyframes_ms
+                .iter()
+                .map(|t| format!("{:.3}", *t as f64 / 1000.0))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+    }
+
+    /// Grow the canvas around the frame by `padding` on each side instead of
+    /// the default margin. Call before [`EncodingParams::with_aspect`], which
+    /// keeps its canvas size and shrinks the frame to fit the padding.
+    pub fn with_padding(mut self, padding: CanvasPadding) -> Self {
+        self.padding = padding;
+        if let (Some(w), Some(h)) = (self.width, self.height) {
+            let margin = padding.margin_around(w.min(h));
+            self.canvas_width = w + margin * 2;
+            self.canvas_height = h + margin * 2;
+        }
+        self
+    }
+
+    /// Re-shape the canvas to `aspect`. The short side of the preset's frame
+    /// becomes the canvas width (1080p → 1080x1920 for 9:16). With
+    /// `Letterbox` the whole screen is fitted inside the padded canvas; with
+    /// `ZoomCrop` the frame fills it and the viewport is cropped instead.
+    pub fn with_aspect(
+        mut self,
+        aspect: OutputAspect,
+        fit: AspectFit,
+        original_width: u32,
+        original_height: u32,
+    ) -> Self {
+        let Some((rw, rh)) = aspect.ratio() else {
+            return self;
+        };
+        let frame_w = self.width.unwrap_or(original_width);
+        let frame_h = self.height.unwrap_or(original_height);
+        let short = frame_w.min(frame_h);
+
+        let canvas_w = even(short);
+        let canvas_h = even(short * rh / rw);
+        let margin = self.padding.margin_in(canvas_w.min(canvas_h));
+        let avail_w = canvas_w.saturating_sub(margin * 2).max(2);
+        let avail_h = canvas_h.saturating_sub(margin * 2).max(2);
+
+        let (out_w, out_h) = match fit {
+            AspectFit::ZoomCrop => {
+                self.crop_aspect = Some(avail_w as f64 / avail_h as f64);
+                (avail_w, avail_h)
+            }
+            AspectFit::Letterbox => {
+                self.crop_aspect = None;
+                let src_aspect = original_width as f64 / original_height.max(1) as f64;
+                if avail_w as f64 / avail_h as f64 > src_aspect {
+                    ((avail_h as f64 * src_aspect) as u32, avail_h)
+                } else {
+                    (avail_w, (avail_w as f64 / src_aspect) as u32)
+                }
+            }
+        };
+
+        self.width = Some(even(out_w));
+        self.height = Some(even(out_h));
+        self.canvas_width = canvas_w;
+        self.canvas_height = canvas_h;
+        self
+    }
+}
+
+/// Round down to an even number (required by yuv420p encoders)
+fn even(v: u32) -> u32 {
+    v & !1
+}
+
+/// Frame size requested by `size`, or `None` when it specifies nothing usable.
+fn resolve_output_size(size: &OutputSize, original_width: u32, original_height: u32) -> Option<(u32, u32)> {
+    let aspect = original_width as f64 / original_height.max(1) as f64;
+    let (w, h) = match (size.width, size.height, size.scale) {
+        (Some(w), Some(h), _) => (w as f64, h as f64),
+        (Some(w), None, _) => (w as f64, w as f64 / aspect),
+        (None, Some(h), _) => (h as f64 * aspect, h as f64),
+        (None, None, Some(scale)) if scale > 0.0 => {
+            (original_width as f64 * scale, original_height as f64 * scale)
+        }
+        _ => return None,
+    };
+    Some((
+        even((w as u32).max(MIN_OUTPUT_SIDE)),
+        even((h as u32).max(MIN_OUTPUT_SIDE)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vertical_zoom_crop_fills_canvas() {
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 2560, 1440, None)
+            .with_aspect(OutputAspect::Vertical9x16, AspectFit::ZoomCrop, 2560, 1440);
+        assert_eq!((p.canvas_width, p.canvas_height), (1080, 1920));
+        assert_eq!(p.width, Some(1080 - CANVAS_PADDING));
+        assert_eq!(p.height, Some(1920 - CANVAS_PADDING));
+        assert!(p.crop_aspect.unwrap() < 1.0);
+    }
+
+    #[test]
+    fn test_square_letterbox_keeps_source_aspect() {
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 1920, 1080, None)
+            .with_aspect(OutputAspect::Square1x1, AspectFit::Letterbox, 1920, 1080);
+        assert_eq!((p.canvas_width, p.canvas_height), (1080, 1080));
+        let (w, h) = (p.width.unwrap(), p.height.unwrap());
+        assert_eq!(w, 1080 - CANVAS_PADDING);
+        assert!((w as f64 / h as f64 - 16.0 / 9.0).abs() < 0.02);
+        assert!(p.crop_aspect.is_none());
+    }
+
+    #[test]
+    fn test_source_aspect_is_unchanged() {
+        let p = EncodingParams::from_preset(&QualityPreset::Lightweight, 1920, 1080, None)
+            .with_aspect(OutputAspect::Source, AspectFit::ZoomCrop, 1920, 1080);
+        assert_eq!((p.canvas_width, p.canvas_height), (1280 + CANVAS_PADDING, 720 + CANVAS_PADDING));
+    }
+
+    #[test]
+    fn test_output_size_overrides_preset_resolution() {
+        let size = OutputSize { width: Some(1000), height: None, scale: None };
+        let p = EncodingParams::from_preset(&QualityPreset::Lightweight, 1920, 1080, Some(&size));
+        assert_eq!((p.width, p.height), (Some(1000), Some(562)));
+        assert_eq!(p.canvas_width, 1000 + CANVAS_PADDING);
+        // Preset fps/crf are kept
+        assert_eq!((p.fps, p.crf), (24, 30));
+
+        let size = OutputSize { width: None, height: None, scale: Some(0.5) };
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 2560, 1440, Some(&size));
+        assert_eq!((p.width, p.height), (Some(1280), Some(720)));
+
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 2560, 1440, Some(&OutputSize::default()));
+        assert_eq!((p.width, p.height), (Some(1920), Some(1080)));
+        assert!(p.crop_aspect.is_none());
+    }
+
+    #[test]
+    fn test_output_size_of_another_aspect_crops() {
+        // A square box for a 16:9 screen: crop the view instead of stretching
+        let size = OutputSize { width: Some(800), height: Some(800), scale: None };
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 1920, 1080, Some(&size));
+        assert_eq!((p.width, p.height), (Some(800), Some(800)));
+        assert_eq!(p.crop_aspect, Some(1.0));
+
+        // Same shape as the screen: nothing to crop
+        let size = OutputSize { width: Some(1280), height: Some(720), scale: None };
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 1920, 1080, Some(&size));
+        assert!(p.crop_aspect.is_none());
+        let size = OutputSize { width: Some(1001), height: None, scale: None };
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 1366, 768, Some(&size));
+        assert!(p.crop_aspect.is_none());
+    }
+
+    #[test]
+    fn test_padding_pixels_and_percent() {
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 1920, 1080, None)
+            .with_padding(CanvasPadding::Pixels { value: 0 });
+        assert_eq!((p.canvas_width, p.canvas_height), (1920, 1080));
+
+        // 10% of the canvas' short side: 1080 + 2 * 135 = 1350, 1350 * 0.1 = 135
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 1920, 1080, None)
+            .with_padding(CanvasPadding::Percent { value: 10.0 });
+        assert_eq!((p.canvas_width, p.canvas_height), (1920 + 270, 1080 + 270));
+
+        // With a fixed aspect the canvas stays and the frame shrinks instead
+        let p = EncodingParams::from_preset(&QualityPreset::Social, 1920, 1080, None)
+            .with_padding(CanvasPadding::Percent { value: 10.0 })
+            .with_aspect(OutputAspect::Square1x1, AspectFit::ZoomCrop, 1920, 1080);
+        assert_eq!((p.canvas_width, p.canvas_height), (1080, 1080));
+        assert_eq!(p.width, Some(1080 - 216));
+    }
 }
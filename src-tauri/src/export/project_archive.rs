@@ -0,0 +1,251 @@
+//! `.snappi` project archives: a whole recording (frames, events, audio,
+//! meta and edits) in one zip file, to move it to another machine.
+//!
+//! The archive holds the recording folder's files under their relative
+//! paths plus [`MANIFEST_FILE`]. Derived artifacts (cache manifest, preview
+//! proxy, export marker) are left out: their fingerprints use modification
+//! times, which don't survive the trip, so they would be rebuilt anyway.
+//!
+//! Importing extracts into a hidden folder first and moves it into place
+//! only once `meta.json` checked out, so a broken archive leaves nothing
+//! behind. The recording keeps its ID unless that ID is already taken (the
+//! archive was imported before, or comes from this machine) or isn't a
+//! plain folder name; then it gets a new one.
+
+use crate::config::{AppSettings, RecordingMeta};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+pub const ARCHIVE_EXTENSION: &str = "snappi";
+
+/// Describes the archive; written first
+const MANIFEST_FILE: &str = "snappi-project.json";
+
+const FORMAT_VERSION: u32 = 1;
+
+/// Files of the recording folder that are not archived
+const SKIPPED_FILES: &[&str] = &["cache.json", super::encoder::PREVIEW_PROXY_FILE, super::storage::EXPORTED_FILE];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    format_version: u32,
+    recording_id: String,
+    app_version: String,
+    exported_at: String,
+}
+
+fn recordings_dir() -> PathBuf {
+    dirs::video_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+}
+
+/// Files under `dir` (recursively) as paths relative to it, sorted.
+fn archived_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        for entry in std::fs::read_dir(dir.join(&relative))? {
+            let entry = entry?;
+            let path = relative.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                pending.push(path);
+            } else if !(relative.as_os_str().is_empty() && SKIPPED_FILES.iter().any(|f| entry.file_name() == *f)) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Pack the recording in `recording_dir` into `output`.
+fn pack(recording_dir: &Path, output: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(recording_dir.join("meta.json"))
+        .with_context(|| format!("Recording not found: {}", recording_dir.display()))?;
+    let meta: RecordingMeta = serde_json::from_str(&content)?;
+    if meta.in_progress {
+        bail!("Recording {} was not finished", meta.id);
+    }
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(output)?);
+    let deflated = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    // Frames are JPEG or LZ4 already
+    let stored = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        recording_id: meta.id.clone(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Local::now().to_rfc3339(),
+    };
+    zip.start_file(MANIFEST_FILE, deflated)?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+
+    for relative in archived_files(recording_dir)? {
+        // Zip entry names always use '/'
+        let name = relative.iter().map(|c| c.to_string_lossy()).collect::<Vec<_>>().join("/");
+        let options = if relative.starts_with("frames") { stored } else { deflated };
+        zip.start_file(name, options)?;
+        std::io::copy(&mut std::fs::File::open(recording_dir.join(&relative))?, &mut zip)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+/// Unpack `archive` into a new recording folder under `recordings_dir`.
+/// Returns the recording's ID.
+fn unpack(archive: &Path, recordings_dir: &Path) -> Result<String> {
+    let file = std::fs::File::open(archive).with_context(|| format!("Cannot open {}", archive.display()))?;
+    let mut zip = zip::ZipArchive::new(file).context("Not a Snappi project archive")?;
+
+    let manifest: Manifest = {
+        let mut entry = zip.by_name(MANIFEST_FILE).context("Not a Snappi project archive")?;
+        let mut content = String::new();
+        entry.read_to_string(&mut content)?;
+        serde_json::from_str(&content)?
+    };
+    if manifest.format_version > FORMAT_VERSION {
+        bail!(
+            "The archive was made by a newer Snappi ({}); update to import it",
+            manifest.app_version
+        );
+    }
+
+    std::fs::create_dir_all(recordings_dir)?;
+    let staging = tempfile::Builder::new().prefix(".import-").tempdir_in(recordings_dir)?;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        // enclosed_name rejects absolute paths and `..`
+        let Some(relative) = entry.enclosed_name() else {
+            bail!("Unsafe path in archive: {}", entry.name());
+        };
+        if entry.is_dir() || relative == Path::new(MANIFEST_FILE) {
+            continue;
+        }
+        let path = staging.path().join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::io::copy(&mut entry, &mut std::fs::File::create(&path)?)?;
+    }
+
+    let meta_path = staging.path().join("meta.json");
+    let content = std::fs::read_to_string(&meta_path).context("The archive has no meta.json")?;
+    let mut meta: RecordingMeta = serde_json::from_str(&content).context("The archive's meta.json is invalid")?;
+    // The ID names the folder: "../x" or an absolute path would land outside the library
+    let plain_name = matches!(
+        Path::new(&meta.id).components().collect::<Vec<_>>().as_slice(),
+        [std::path::Component::Normal(_)]
+    );
+    if !plain_name || recordings_dir.join(&meta.id).exists() {
+        meta.id = uuid::Uuid::new_v4().to_string();
+    }
+    let target = recordings_dir.join(&meta.id);
+    meta.recording_dir = target.to_string_lossy().to_string();
+    std::fs::write(&meta_path, serde_json::to_string_pretty(&meta)?)?;
+
+    std::fs::rename(staging.keep(), &target)?;
+    Ok(meta.id)
+}
+
+/// Pack `recording_id` into a `.snappi` file: `output` when given, else a
+/// file named after the recording's title (or ID) in the output directory.
+/// Returns the path written.
+pub fn export_project(recording_id: &str, output: Option<&Path>, settings: &AppSettings) -> Result<PathBuf> {
+    if crate::recording::session::active_recording_id().as_deref() == Some(recording_id) {
+        bail!("Recording {} is still in progress", recording_id);
+    }
+    let output = match output {
+        Some(path) => path.with_extension(ARCHIVE_EXTENSION),
+        None => {
+            let title = crate::recording::takes::read_meta(recording_id)?.title;
+            let stem = super::filename::sanitize_component(title.as_deref().unwrap_or(recording_id));
+            let dir = PathBuf::from(&settings.output.save_directory);
+            super::filename::unique_path(&dir, &format!("{}.{}", stem, ARCHIVE_EXTENSION))
+        }
+    };
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if let Err(e) = pack(&recordings_dir().join(recording_id), &output) {
+        let _ = std::fs::remove_file(&output);
+        return Err(e);
+    }
+    log::info!("Exported project {} to {}", recording_id, output.display());
+    Ok(output)
+}
+
+/// Restore a recording from a `.snappi` archive. Returns its ID.
+pub fn import_project(archive: &Path) -> Result<String> {
+    let id = unpack(archive, &recordings_dir())?;
+    log::info!("Imported project {} from {}", id, archive.display());
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_and_unpack_round_trip() {
+        let source = tempfile::TempDir::new().unwrap();
+        let dir = source.path().join("rec-1");
+        std::fs::create_dir_all(dir.join("frames")).unwrap();
+        std::fs::write(dir.join("frames").join("frames.pack"), [7u8; 64]).unwrap();
+        std::fs::write(dir.join("events.jsonl"), "{\"type\":\"click\"}\n").unwrap();
+        std::fs::write(dir.join("cache.json"), "{}").unwrap();
+        let meta = serde_json::json!({
+            "version": 3, "id": "rec-1", "screen_width": 1, "screen_height": 1, "fps": 30,
+            "start_time": "2026-01-01T00:00:00Z", "duration_ms": 0, "has_audio": false,
+            "monitor_scale": 1.0, "recording_dir": dir, "title": "Demo",
+        });
+        std::fs::write(dir.join("meta.json"), meta.to_string()).unwrap();
+
+        let archive = source.path().join("demo.snappi");
+        pack(&dir, &archive).unwrap();
+
+        let library = tempfile::TempDir::new().unwrap();
+        let id = unpack(&archive, library.path()).unwrap();
+        assert_eq!(id, "rec-1");
+        let restored = library.path().join("rec-1");
+        assert_eq!(std::fs::read(restored.join("frames/frames.pack")).unwrap(), [7u8; 64]);
+        assert!(restored.join("events.jsonl").exists());
+        assert!(!restored.join("cache.json").exists());
+        let meta: RecordingMeta =
+            serde_json::from_str(&std::fs::read_to_string(restored.join("meta.json")).unwrap()).unwrap();
+        assert_eq!(meta.title.as_deref(), Some("Demo"));
+        assert_eq!(Path::new(&meta.recording_dir), restored);
+
+        // Importing again gets a new ID; no staging folder is left behind
+        let second = unpack(&archive, library.path()).unwrap();
+        assert_ne!(second, "rec-1");
+        assert_eq!(std::fs::read_dir(library.path()).unwrap().count(), 2);
+
+        // An ID that isn't a plain folder name is replaced, keeping the import in the library
+        let meta = serde_json::json!({
+            "version": 3, "id": "../evil", "screen_width": 1, "screen_height": 1, "fps": 30,
+            "start_time": "2026-01-01T00:00:00Z", "duration_ms": 0, "has_audio": false,
+            "monitor_scale": 1.0, "recording_dir": dir,
+        });
+        std::fs::write(dir.join("meta.json"), meta.to_string()).unwrap();
+        pack(&dir, &archive).unwrap();
+        let library = tempfile::TempDir::new().unwrap();
+        let library_dir = library.path().join("recordings");
+        let id = unpack(&archive, &library_dir).unwrap();
+        assert_ne!(id, "../evil");
+        assert!(!library.path().join("evil").exists());
+        let meta: RecordingMeta =
+            serde_json::from_str(&std::fs::read_to_string(library_dir.join(&id).join("meta.json")).unwrap()).unwrap();
+        assert_eq!(meta.id, id);
+        assert_eq!(Path::new(&meta.recording_dir), library_dir.join(&id));
+
+        std::fs::write(source.path().join("bogus.snappi"), b"not a zip").unwrap();
+        assert!(unpack(&source.path().join("bogus.snappi"), library.path()).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! Styled screenshots: one grab of the screen run through the same
+//! compositor as video exports (background, padding, rounded corners,
+//! shadow, cursor, watermark) and saved as a PNG in the output directory.
+//! Scrolling screenshots (see [`crate::recording::scroll_capture`]) are
+//! saved the same way, unstyled.
+
+use crate::config::defaults::OutputStyle;
+use crate::config::{AppSettings, RecordingMode};
+use crate::engine::compositor::Compositor;
+use crate::export::filename;
+use crate::export::presets::EncodingParams;
+use anyhow::Result;
+use image::RgbaImage;
+
+/// Compositor styling `width`×`height` grabs like an export. Shared with
+/// live streaming.
+pub fn styled_compositor(
+    settings: &AppSettings,
+    width: u32,
+    height: u32,
+    monitor_scale: f64,
+    os_cursor_scale: f64,
+) -> Compositor {
+    let params = EncodingParams::from_preset(&settings.output.default_quality, width, height, None)
+        .with_padding(settings.style.padding)
+        .with_aspect(settings.output.aspect, settings.output.aspect_fit, width, height);
+    let mut style = OutputStyle::from_settings(&params, settings);
+    // Neither a still nor a stream has a timeline for the ribbon to
+    // disappear on
+    style.disclaimer = None;
+    let mut compositor = Compositor::new(style, width, height);
+    compositor.set_backend(settings.output.compositor_backend);
+    compositor.set_monitor_scale(monitor_scale);
+    compositor.set_os_cursor_scale(os_cursor_scale);
+    compositor
+}
+
+/// Compose `raw` (a full-screen grab) like the first frame of an export,
+/// with the cursor at `cursor` (screen coordinates) if given.
+pub fn compose_screenshot(
+    raw: &RgbaImage,
+    cursor: Option<(f64, f64)>,
+    settings: &AppSettings,
+    monitor_scale: f64,
+    os_cursor_scale: f64,
+) -> RgbaImage {
+    let (width, height) = raw.dimensions();
+    styled_compositor(settings, width, height, monitor_scale, os_cursor_scale).compose_frame(raw, 0, cursor, &[], None, 0.0)
+}
+
+/// Grab the screen, style it and save it as a PNG in the output directory.
+/// Returns the saved path.
+pub fn take_screenshot(settings: &AppSettings) -> Result<String> {
+    let (raw, cursor) = crate::recording::capture::grab_screen()?;
+    let monitor_scale = crate::recording::events::monitor_scale(&RecordingMode::Display);
+    let os_cursor_scale = crate::recording::events::os_cursor_scale();
+    let styled = compose_screenshot(&raw, cursor, settings, monitor_scale, os_cursor_scale);
+    save_png(&styled, settings, "screenshot")
+}
+
+/// Save `image` as a PNG in the output directory, named by the filename
+/// template with `label` standing in for the recording id. Returns the
+/// saved path.
+pub fn save_png(image: &RgbaImage, settings: &AppSettings, label: &str) -> Result<String> {
+    let output_dir = std::path::PathBuf::from(&settings.output.save_directory);
+    std::fs::create_dir_all(&output_dir)?;
+    let name = filename::render_filename(
+        &settings.output.filename_template,
+        &filename::FilenameContext {
+            start_time: chrono::Local::now().fixed_offset(),
+            recording_id: label,
+            window_title: None,
+            speed: None,
+            extension: "png",
+        },
+    );
+    let path = filename::unique_path(&output_dir, &name);
+    image.save(&path)?;
+    log::info!("Screenshot saved: {}", path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BackgroundConfig;
+
+    #[test]
+    fn test_screenshot_is_styled_like_an_export() {
+        let mut settings = AppSettings::default();
+        settings.style.background = BackgroundConfig::Solid { color: [200, 10, 10] };
+        let raw = RgbaImage::from_pixel(320, 180, image::Rgba([20, 200, 20, 255]));
+
+        let styled = compose_screenshot(&raw, None, &settings, 1.0, 1.0);
+
+        // The canvas is larger than the screen and its border is the background
+        assert!(styled.width() > raw.width() && styled.height() > raw.height());
+        let corner = styled.get_pixel(0, 0);
+        assert_eq!([corner[0], corner[1], corner[2]], [200, 10, 10]);
+        let center = styled.get_pixel(styled.width() / 2, styled.height() / 2);
+        assert!(center[1] > 150 && center[0] < 80);
+    }
+}
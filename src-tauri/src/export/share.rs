@@ -0,0 +1,487 @@
+//! Upload exported files to share them (`ShareSettings`).
+//!
+//! Each destination is an [`UploadTarget`]; [`target_for`] picks the one
+//! for a [`ShareTarget`]. Targets stream the file from disk, reporting the
+//! bytes sent through a callback, and return the URL to share:
+//! - [`S3Target`]: any S3-compatible storage, a path-style `PUT` signed
+//!   with AWS Signature V4 (unsigned payload, so the file isn't read twice).
+//! - [`HttpPutTarget`]: a plain `PUT` to a configurable URL.
+//! - [`YouTubeTarget`]: a resumable upload with an OAuth access token from
+//!   the refresh token [`authorize_youtube`] stored.
+
+use crate::config::{
+    HttpShareSettings, S3ShareSettings, ShareSettings, ShareTarget, YouTubePrivacy, YouTubeShareSettings,
+};
+use anyhow::{bail, Context, Result};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Called with the number of bytes sent so far
+pub type ProgressFn<'a> = &'a dyn Fn(u64);
+
+/// Timeout for connecting and for each read/write of an upload
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const YOUTUBE_UPLOAD_URL: &str =
+    "https://www.googleapis.com/upload/youtube/v3/videos?uploadType=resumable&part=snippet,status";
+const YOUTUBE_SCOPE: &str = "https://www.googleapis.com/auth/youtube.upload";
+
+/// How long to wait for the browser to come back to the loopback redirect
+const AUTHORIZE_TIMEOUT: Duration = Duration::from_secs(300);
+
+pub trait UploadTarget {
+    /// Upload the file at `path`; returns the URL to share.
+    fn upload(&self, path: &Path, progress: ProgressFn) -> Result<String>;
+}
+
+/// The configured target for `target`.
+pub fn target_for(target: ShareTarget, settings: &ShareSettings) -> Box<dyn UploadTarget> {
+    match target {
+        ShareTarget::S3 => Box::new(S3Target(settings.s3.clone())),
+        ShareTarget::Http => Box::new(HttpPutTarget(settings.http.clone())),
+        ShareTarget::YouTube => Box::new(YouTubeTarget(settings.youtube.clone())),
+    }
+}
+
+/// Upload `path` to `target`.
+pub fn upload(path: &Path, target: ShareTarget, settings: &ShareSettings, progress: ProgressFn) -> Result<String> {
+    if !path.is_file() {
+        bail!("File not found: {}", path.display());
+    }
+    let url = target_for(target, settings).upload(path, progress)?;
+    log::info!("Uploaded {} to {:?}: {}", path.display(), target, url);
+    Ok(url)
+}
+
+/// Wraps the file being uploaded and reports the bytes read from it.
+struct ProgressReader<'a, R> {
+    inner: R,
+    sent: u64,
+    progress: ProgressFn<'a>,
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.sent += n as u64;
+        (self.progress)(self.sent);
+        Ok(n)
+    }
+}
+
+fn open_upload<'a>(path: &Path, progress: ProgressFn<'a>) -> Result<(ProgressReader<'a, std::fs::File>, u64)> {
+    let file = std::fs::File::open(path)?;
+    let size = file.metadata()?.len();
+    Ok((ProgressReader { inner: file, sent: 0, progress }, size))
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(UPLOAD_TIMEOUT)
+        .timeout_read(UPLOAD_TIMEOUT)
+        .timeout_write(UPLOAD_TIMEOUT)
+        .build()
+}
+
+/// Turn an HTTP error into a message with the server's response body.
+fn http_error(e: ureq::Error) -> anyhow::Error {
+    match e {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            anyhow::anyhow!("Server responded {}: {}", code, body.trim())
+        }
+        e => anyhow::anyhow!("Upload failed: {}", e),
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase()).as_deref() {
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("mov") => "video/quicktime",
+        Some("gif") => "image/gif",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("mp3") => "audio/mpeg",
+        Some("m4a") => "audio/mp4",
+        Some("wav") => "audio/wav",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters (and `/`
+/// when `keep_slash`), as Signature V4 requires.
+pub fn uri_encode(s: &str, keep_slash: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+            b'/' if keep_slash => out.push('/'),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Signature V4 signing key for `date` (`YYYYMMDD`).
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let key = hmac_sha256(format!("AWS4{}", secret).as_bytes(), date);
+    let key = hmac_sha256(&key, region);
+    let key = hmac_sha256(&key, service);
+    hmac_sha256(&key, "aws4_request")
+}
+
+/// Signature V4 signature of `canonical_request` made at `amz_date`
+/// (`YYYYMMDDTHHMMSSZ`).
+fn signature(secret: &str, amz_date: &str, region: &str, service: &str, canonical_request: &str) -> String {
+    let date = &amz_date[..8];
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+    hex(&hmac_sha256(&signing_key(secret, date, region, service), &string_to_sign))
+}
+
+pub struct S3Target(pub S3ShareSettings);
+
+impl S3Target {
+    /// Object key of the uploaded file.
+    fn key(&self, path: &Path) -> String {
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        format!("{}{}-{}", self.0.key_prefix, stamp, file_name(path))
+    }
+
+    /// `Authorization` header of a `PUT` to `/{bucket}/{key}` on `host`.
+    fn authorization(&self, host: &str, canonical_uri: &str, amz_date: &str) -> String {
+        let s3 = &self.0;
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:UNSIGNED-PAYLOAD\nx-amz-date:{}\n\n{}\nUNSIGNED-PAYLOAD",
+            canonical_uri, host, amz_date, signed_headers
+        );
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}/{}/s3/aws4_request, SignedHeaders={}, Signature={}",
+            s3.access_key_id,
+            &amz_date[..8],
+            s3.region,
+            signed_headers,
+            signature(&s3.secret_access_key, amz_date, &s3.region, "s3", &canonical_request)
+        )
+    }
+}
+
+impl UploadTarget for S3Target {
+    fn upload(&self, path: &Path, progress: ProgressFn) -> Result<String> {
+        let s3 = &self.0;
+        if s3.endpoint.is_empty() || s3.bucket.is_empty() || s3.access_key_id.is_empty() {
+            bail!("S3 upload is not configured");
+        }
+        let endpoint = s3.endpoint.trim_end_matches('/');
+        let host = endpoint.split_once("://").map_or(endpoint, |(_, rest)| rest);
+        let key = self.key(path);
+        let canonical_uri = format!("/{}/{}", uri_encode(&s3.bucket, false), uri_encode(&key, true));
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+        let (reader, size) = open_upload(path, progress)?;
+        agent()
+            .put(&format!("{}{}", endpoint, canonical_uri))
+            .set("Authorization", &self.authorization(host, &canonical_uri, &amz_date))
+            .set("x-amz-content-sha256", "UNSIGNED-PAYLOAD")
+            .set("x-amz-date", &amz_date)
+            .set("Content-Type", content_type(path))
+            // S3 rejects chunked uploads without a length
+            .set("Content-Length", &size.to_string())
+            .send(reader)
+            .map_err(http_error)?;
+
+        Ok(if s3.public_url_base.is_empty() {
+            format!("{}{}", endpoint, canonical_uri)
+        } else {
+            format!("{}/{}", s3.public_url_base.trim_end_matches('/'), uri_encode(&key, true))
+        })
+    }
+}
+
+pub struct HttpPutTarget(pub HttpShareSettings);
+
+impl UploadTarget for HttpPutTarget {
+    fn upload(&self, path: &Path, progress: ProgressFn) -> Result<String> {
+        let http = &self.0;
+        if http.url.is_empty() {
+            bail!("HTTP upload is not configured");
+        }
+        let name = uri_encode(&file_name(path), false);
+        let url = http.url.replace("{filename}", &name);
+        let (reader, size) = open_upload(path, progress)?;
+        let mut request = agent()
+            .put(&url)
+            .set("Content-Type", content_type(path))
+            .set("Content-Length", &size.to_string());
+        if !http.authorization.is_empty() {
+            request = request.set("Authorization", &http.authorization);
+        }
+        request.send(reader).map_err(http_error)?;
+        Ok(if http.public_url.is_empty() { url } else { http.public_url.replace("{filename}", &name) })
+    }
+}
+
+pub struct YouTubeTarget(pub YouTubeShareSettings);
+
+/// Exchange an OAuth form for tokens at Google's token endpoint.
+fn google_token(form: &[(&str, &str)]) -> Result<serde_json::Value> {
+    let body = agent().post(GOOGLE_TOKEN_URL).send_form(form).map_err(http_error)?.into_string()?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+impl YouTubeTarget {
+    fn access_token(&self) -> Result<String> {
+        let yt = &self.0;
+        if yt.refresh_token.is_empty() {
+            bail!("YouTube is not authorized; connect it in the settings first");
+        }
+        let tokens = google_token(&[
+            ("client_id", &yt.client_id),
+            ("client_secret", &yt.client_secret),
+            ("refresh_token", &yt.refresh_token),
+            ("grant_type", "refresh_token"),
+        ])?;
+        tokens["access_token"].as_str().map(str::to_string).context("Google returned no access token")
+    }
+}
+
+impl UploadTarget for YouTubeTarget {
+    fn upload(&self, path: &Path, progress: ProgressFn) -> Result<String> {
+        if !content_type(path).starts_with("video/") {
+            bail!("Only videos can be uploaded to YouTube");
+        }
+        let token = self.access_token()?;
+        let (reader, size) = open_upload(path, progress)?;
+        let privacy = match self.0.privacy {
+            YouTubePrivacy::Private => "private",
+            YouTubePrivacy::Unlisted => "unlisted",
+            YouTubePrivacy::Public => "public",
+        };
+        let title = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let metadata = serde_json::json!({
+            "snippet": { "title": title },
+            "status": { "privacyStatus": privacy },
+        });
+
+        // Start a resumable session, then send the file to its URL
+        let session = agent()
+            .post(YOUTUBE_UPLOAD_URL)
+            .set("Authorization", &format!("Bearer {}", token))
+            .set("Content-Type", "application/json; charset=UTF-8")
+            .set("X-Upload-Content-Length", &size.to_string())
+            .set("X-Upload-Content-Type", content_type(path))
+            .send_string(&metadata.to_string())
+            .map_err(http_error)?;
+        let upload_url = session.header("Location").context("YouTube returned no upload URL")?.to_string();
+        let body = agent()
+            .put(&upload_url)
+            .set("Authorization", &format!("Bearer {}", token))
+            .set("Content-Type", content_type(path))
+            .set("Content-Length", &size.to_string())
+            .send(reader)
+            .map_err(http_error)?
+            .into_string()?;
+        let video: serde_json::Value = serde_json::from_str(&body)?;
+        let id = video["id"].as_str().context("YouTube returned no video ID")?;
+        Ok(format!("https://youtu.be/{}", id))
+    }
+}
+
+/// Query parameter `name` of the first line of an HTTP request
+/// (`GET /?code=...&state=... HTTP/1.1`), percent-decoded.
+fn query_param(request_line: &str, name: &str) -> Option<String> {
+    let target = request_line.split_whitespace().nth(1)?;
+    let query = target.split_once('?')?.1;
+    let value = query
+        .split('&')
+        .find_map(|pair| pair.split_once('=').filter(|(key, _)| *key == name).map(|(_, value)| value))?;
+    // Percent-decode; OAuth values only ever escape ASCII
+    let mut out = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            let hex: String = chars.by_ref().take(2).collect();
+            out.push(u8::from_str_radix(&hex, 16).ok()? as char);
+        } else {
+            out.push(c);
+        }
+    }
+    Some(out)
+}
+
+/// Unpadded base64url, as PKCE wants it.
+fn base64_url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut out = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+        }
+    }
+    out
+}
+
+/// PKCE S256 code challenge of `verifier`.
+fn code_challenge(verifier: &str) -> String {
+    base64_url(&Sha256::digest(verifier.as_bytes()))
+}
+
+/// A random string of URL-safe characters (32 hex digits per UUID).
+fn random_token(uuids: usize) -> String {
+    (0..uuids).map(|_| uuid::Uuid::new_v4().simple().to_string()).collect()
+}
+
+fn respond(stream: &mut std::net::TcpStream, status: &str, page: &str) {
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        page.len(),
+        page
+    );
+}
+
+/// Authorize YouTube uploads: open Google's consent page in the browser
+/// through `open_url`, wait for it to redirect back to a loopback port and
+/// exchange the code. Returns the refresh token to store.
+///
+/// A random `state` ties the redirect to this request and PKCE ties the
+/// code to this process: other connections to the port (a local program, a
+/// web page) are answered and ignored, and a stolen code can't be redeemed.
+pub fn authorize_youtube(youtube: &YouTubeShareSettings, open_url: impl FnOnce(&str) -> Result<()>) -> Result<String> {
+    if youtube.client_id.is_empty() || youtube.client_secret.is_empty() {
+        bail!("Enter the OAuth client ID and secret first");
+    }
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let redirect_uri = format!("http://127.0.0.1:{}", listener.local_addr()?.port());
+    let state = random_token(1);
+    let verifier = random_token(2);
+    let consent = format!(
+        "{}?client_id={}&redirect_uri={}&response_type=code&scope={}&access_type=offline&prompt=consent\
+         &state={}&code_challenge={}&code_challenge_method=S256",
+        GOOGLE_AUTH_URL,
+        uri_encode(&youtube.client_id, false),
+        uri_encode(&redirect_uri, false),
+        uri_encode(YOUTUBE_SCOPE, false),
+        state,
+        code_challenge(&verifier),
+    );
+    open_url(&consent)?;
+
+    // accept() has no timeout: poll a non-blocking listener instead
+    listener.set_nonblocking(true)?;
+    let started = std::time::Instant::now();
+    let code = loop {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                if started.elapsed() > AUTHORIZE_TIMEOUT {
+                    bail!("Timed out waiting for the YouTube authorization");
+                }
+                std::thread::sleep(Duration::from_millis(200));
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        stream.set_nonblocking(false)?;
+        // A client that connects and says nothing must not stall the wait
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        let mut request_line = String::new();
+        if BufReader::new(&stream).read_line(&mut request_line).is_err() {
+            continue;
+        }
+        if query_param(&request_line, "state").as_deref() != Some(state.as_str()) {
+            log::warn!("Ignoring a request to the YouTube authorization port without the expected state");
+            respond(&mut stream, "400 Bad Request", "Unexpected request.");
+            continue;
+        }
+        let code = query_param(&request_line, "code");
+        let page = if code.is_some() {
+            "Snappi is connected to YouTube. You can close this tab."
+        } else {
+            "Authorization was cancelled. You can close this tab."
+        };
+        respond(&mut stream, "200 OK", page);
+        break code.context("YouTube authorization was cancelled")?;
+    };
+
+    let tokens = google_token(&[
+        ("client_id", &youtube.client_id),
+        ("client_secret", &youtube.client_secret),
+        ("code", &code),
+        ("code_verifier", &verifier),
+        ("redirect_uri", &redirect_uri),
+        ("grant_type", "authorization_code"),
+    ])?;
+    tokens["refresh_token"].as_str().map(str::to_string).context("Google returned no refresh token")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_v4() {
+        // Example from the AWS Signature Version 4 documentation
+        let secret = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
+        assert_eq!(
+            hex(&signing_key(secret, "20150830", "us-east-1", "iam")),
+            "c4afb1cc5771d871763a393e44b703571b55cc28424d1a5e86da6ed3c154a4b9"
+        );
+        let canonical_request = "GET\n/\nAction=ListUsers&Version=2010-05-08\n\
+            content-type:application/x-www-form-urlencoded; charset=utf-8\nhost:iam.amazonaws.com\n\
+            x-amz-date:20150830T123600Z\n\ncontent-type;host;x-amz-date\n\
+            e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(
+            signature(secret, "20150830T123600Z", "us-east-1", "iam", canonical_request),
+            "5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7"
+        );
+
+        assert_eq!(uri_encode("demo clips/a+b.mp4", true), "demo%20clips/a%2Bb.mp4");
+        assert_eq!(uri_encode("a/b", false), "a%2Fb");
+    }
+
+    #[test]
+    fn test_redirect_params_and_pkce() {
+        let line = "GET /?state=abc123&code=4%2F0Ab-xyz&scope=https%3A%2F%2Fwww.googleapis.com HTTP/1.1\r\n";
+        assert_eq!(query_param(line, "code").as_deref(), Some("4/0Ab-xyz"));
+        assert_eq!(query_param(line, "state").as_deref(), Some("abc123"));
+        assert_eq!(query_param("GET /?error=access_denied HTTP/1.1", "code"), None);
+        // "xcode=" is not "code="
+        assert_eq!(query_param("GET /?xcode=1 HTTP/1.1", "code"), None);
+
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mJ92K9w9bAEfAGNzO4bH-bdl-nF53U"),
+            "2VQuDvHhMB2wlyBgJVLoH_SDw0EfP3w-oVe-MHhTvvc"
+        );
+        assert_eq!(base64_url(b"ab"), "YWI");
+        assert_eq!(random_token(2).len(), 64);
+    }
+}
@@ -0,0 +1,94 @@
+//! Splitting long exports into numbered parts of at most N minutes
+//! (`OutputSettings::split_max_minutes`), for platforms that limit upload
+//! length.
+//!
+//! Split points are chosen before encoding and passed to the encoder as
+//! forced keyframes, so each part can be cut out of the finished file with
+//! a stream copy: no second encode, and every part starts on a clean frame.
+//! A point falls on a scene start when one lies in the last part of the
+//! allowed window, otherwise exactly at the limit.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A scene start is preferred over a hard cut when it leaves the part at
+/// least this fraction of the maximum length
+const MIN_PART_FRACTION: f64 = 0.75;
+
+/// Split points (output ms) for a `duration_ms` export with parts of at
+/// most `max_ms`. `boundaries` are scene starts on the output timeline.
+pub fn plan_split_points(duration_ms: u64, max_ms: u64, boundaries: &[u64]) -> Vec<u64> {
+    let mut points = Vec::new();
+    if max_ms == 0 {
+        return points;
+    }
+    let mut start = 0;
+    while duration_ms.saturating_sub(start) > max_ms {
+        let earliest = start + (max_ms as f64 * MIN_PART_FRACTION) as u64;
+        let limit = start + max_ms;
+        let point = boundaries
+            .iter()
+            .copied()
+            .filter(|&b| b >= earliest && b <= limit)
+            .max()
+            .unwrap_or(limit);
+        points.push(point);
+        start = point;
+    }
+    points
+}
+
+/// Cut `output` at `points` into `<name>_part1.<ext>`, `<name>_part2.<ext>`, ...
+/// next to it and remove the unsplit file. The cuts must be keyframes.
+pub fn split_at(ffmpeg: &str, output: &Path, points: &[u64]) -> Result<Vec<PathBuf>> {
+    let dir = output.parent().unwrap_or(Path::new("."));
+    let stem = output.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = output.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+
+    let starts = std::iter::once(0).chain(points.iter().copied());
+    let ends = points.iter().map(|&p| Some(p)).chain(std::iter::once(None));
+    let mut parts = Vec::new();
+    for (n, (start, end)) in starts.zip(ends).enumerate() {
+        let part = crate::export::filename::unique_path(dir, &format!("{}_part{}.{}", stem, n + 1, ext));
+        let mut cmd = Command::new(ffmpeg);
+        cmd.args(["-y", "-ss"]).arg(format!("{:.3}", start as f64 / 1000.0));
+        if let Some(end) = end {
+            cmd.args(["-t"]).arg(format!("{:.3}", (end - start) as f64 / 1000.0));
+        }
+        cmd.args(["-i"])
+            .arg(output.to_string_lossy().to_string())
+            .args(["-map", "0", "-c", "copy", "-avoid_negative_ts", "make_zero"]);
+        if ext == "mp4" || ext == "mov" {
+            cmd.args(["-movflags", "+faststart"]);
+        }
+        cmd.arg(part.to_string_lossy().to_string());
+
+        log::info!("FFmpeg split command: {:?}", cmd);
+        let result = cmd.output()?;
+        if !result.status.success() {
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            return Err(anyhow::anyhow!("FFmpeg split failed at part {}: {}", n + 1, stderr));
+        }
+        parts.push(part);
+    }
+    std::fs::remove_file(output)?;
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_prefers_scene_starts_near_the_limit() {
+        let minute = 60_000;
+        // 25 min with 10 min parts: a scene at 8:30 is taken, one at 5:00 is too early
+        let boundaries = [5 * minute, 8 * minute + 30_000, 19 * minute];
+        let points = plan_split_points(25 * minute, 10 * minute, &boundaries);
+        assert_eq!(points, vec![8 * minute + 30_000, 18 * minute + 30_000]);
+
+        assert!(plan_split_points(10 * minute, 10 * minute, &boundaries).is_empty());
+        assert!(plan_split_points(25 * minute, 0, &boundaries).is_empty());
+    }
+}
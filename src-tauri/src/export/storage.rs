@@ -0,0 +1,306 @@
+//! Storage manager: disk usage of the recordings library and the automatic
+//! cleanup policies of `StorageSettings`, applied periodically in the
+//! background.
+//!
+//! Policies, in order:
+//! 1. `delete_frames_after_export`: delete the frames of recordings that
+//!    were exported successfully (marked by [`EXPORTED_FILE`]). The folder
+//!    keeps its metadata and thumbnail, so the recording stays listed.
+//! 2. `keep_last_recordings`: delete recordings older than the newest N.
+//! 3. `max_library_gb`: delete the oldest recordings until the library fits.
+//!
+//! 2 and 3 only delete exported recordings unless `delete_unexported` is
+//! set. The recording currently being captured and recordings with an
+//! export in progress (an [`ExportLock`], from any process) are never
+//! touched.
+
+use crate::config::{RecordingMeta, RecordingUsage, StoragePolicyReport, StorageSettings, StorageUsage};
+use crate::export::cleanup::size_of;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Written into a recording by every successful export
+pub const EXPORTED_FILE: &str = "exported.json";
+
+/// Prefix of the lock files an export keeps in a recording while it reads
+/// the frames (`exporting-<uuid>.lock`, holding the process id)
+const EXPORT_LOCK_PREFIX: &str = "exporting-";
+
+/// Interval between policy runs
+pub const POLICY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+fn recordings_dir() -> PathBuf {
+    dirs::video_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+}
+
+/// Record that `recording_id` was exported to `output_path`.
+pub fn mark_exported(recording_id: &str, output_path: &str) -> Result<()> {
+    let marker = serde_json::json!({
+        "exported_at": chrono::Local::now().to_rfc3339(),
+        "output_path": output_path,
+    });
+    std::fs::write(recordings_dir().join(recording_id).join(EXPORTED_FILE), marker.to_string())?;
+    Ok(())
+}
+
+/// Marks a recording as being exported until dropped, so the storage
+/// policies leave its frames alone. A lock left by a crashed process is
+/// ignored.
+pub struct ExportLock(Option<PathBuf>);
+
+impl ExportLock {
+    pub fn acquire(recording_id: &str) -> Self {
+        Self::acquire_in(&recordings_dir().join(recording_id))
+    }
+
+    fn acquire_in(recording_dir: &Path) -> Self {
+        let path = recording_dir.join(format!("{}{}.lock", EXPORT_LOCK_PREFIX, uuid::Uuid::new_v4()));
+        match std::fs::write(&path, std::process::id().to_string()) {
+            Ok(()) => Self(Some(path)),
+            Err(e) => {
+                log::warn!("Failed to write export lock {}: {}", path.display(), e);
+                Self(None)
+            }
+        }
+    }
+}
+
+impl Drop for ExportLock {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Whether a live process is exporting the recording in `recording_dir`.
+fn export_in_progress(recording_dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(recording_dir) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        name.starts_with(EXPORT_LOCK_PREFIX)
+            && name.ends_with(".lock")
+            && std::fs::read_to_string(entry.path())
+                .ok()
+                .and_then(|pid| pid.trim().parse::<u32>().ok())
+                .is_some_and(|pid| pid == std::process::id() || crate::export::cleanup::process_alive(pid))
+    })
+}
+
+/// Usage of every recording under `recordings_dir`, newest first.
+fn usage_in(recordings_dir: &Path) -> StorageUsage {
+    let Ok(entries) = std::fs::read_dir(recordings_dir) else {
+        return StorageUsage::default();
+    };
+    let mut recordings: Vec<RecordingUsage> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let content = std::fs::read_to_string(dir.join("meta.json")).ok()?;
+            let meta: RecordingMeta = serde_json::from_str(&content).ok()?;
+            let frames = dir.join("frames");
+            Some(RecordingUsage {
+                id: entry.file_name().to_string_lossy().to_string(),
+                title: meta.title,
+                date: meta.start_time,
+                bytes: size_of(&dir),
+                frames_bytes: size_of(&frames),
+                exported: dir.join(EXPORTED_FILE).exists(),
+                frames_removed: !frames.exists(),
+            })
+        })
+        .collect();
+    recordings.sort_by(|a, b| b.date.cmp(&a.date));
+    StorageUsage { recordings, total_bytes: size_of(recordings_dir) }
+}
+
+/// Disk usage of each recording and of the whole library.
+pub fn storage_usage() -> StorageUsage {
+    usage_in(&recordings_dir())
+}
+
+fn remove_recording(recordings_dir: &Path, usage: &RecordingUsage, report: &mut StoragePolicyReport) -> bool {
+    match std::fs::remove_dir_all(recordings_dir.join(&usage.id)) {
+        Ok(()) => {
+            crate::recording::library::invalidate(&usage.id);
+            report.recordings_deleted += 1;
+            report.reclaimed_bytes += usage.bytes;
+            true
+        }
+        Err(e) => {
+            log::warn!("Failed to delete recording {}: {}", usage.id, e);
+            false
+        }
+    }
+}
+
+fn apply_in(recordings_dir: &Path, settings: &StorageSettings, active: Option<&str>) -> StoragePolicyReport {
+    let mut report = StoragePolicyReport::default();
+    let usage = usage_in(recordings_dir);
+    let mut total = usage.total_bytes;
+    let mut kept: Vec<RecordingUsage> = usage.recordings.into_iter().filter(|r| active != Some(r.id.as_str())).collect();
+    let busy: Vec<String> = kept
+        .iter()
+        .filter(|r| export_in_progress(&recordings_dir.join(&r.id)))
+        .map(|r| r.id.clone())
+        .collect();
+    // Recordings the size and count limits may delete
+    let deletable = |r: &RecordingUsage| !busy.contains(&r.id) && (r.exported || settings.delete_unexported);
+
+    if settings.delete_frames_after_export {
+        for rec in kept.iter_mut().filter(|r| r.exported && !r.frames_removed && !busy.contains(&r.id)) {
+            match std::fs::remove_dir_all(recordings_dir.join(&rec.id).join("frames")) {
+                Ok(()) => {
+                    report.frames_removed += 1;
+                    report.reclaimed_bytes += rec.frames_bytes;
+                    total = total.saturating_sub(rec.frames_bytes);
+                    rec.bytes = rec.bytes.saturating_sub(rec.frames_bytes);
+                    rec.frames_bytes = 0;
+                    rec.frames_removed = true;
+                }
+                Err(e) => log::warn!("Failed to delete frames of {}: {}", rec.id, e),
+            }
+        }
+    }
+
+    // The active recording counts as one of the newest. Recordings that
+    // may not be deleted still count, so fewer than N may be left deletable
+    let keep_last = settings.keep_last_recordings as usize;
+    if keep_last > 0 {
+        let keep = keep_last.saturating_sub(usize::from(active.is_some()));
+        let mut index = kept.len();
+        while index > keep {
+            index -= 1;
+            if !deletable(&kept[index]) {
+                continue;
+            }
+            let oldest = kept.remove(index);
+            if remove_recording(recordings_dir, &oldest, &mut report) {
+                total = total.saturating_sub(oldest.bytes);
+            }
+        }
+    }
+
+    if settings.max_library_gb > 0.0 {
+        let limit = (settings.max_library_gb * BYTES_PER_GB) as u64;
+        let mut index = kept.len();
+        while total > limit && index > 0 {
+            index -= 1;
+            if !deletable(&kept[index]) {
+                continue;
+            }
+            let oldest = kept.remove(index);
+            if remove_recording(recordings_dir, &oldest, &mut report) {
+                total = total.saturating_sub(oldest.bytes);
+            }
+        }
+    }
+    report
+}
+
+/// Apply the cleanup policies of `settings` to the library.
+pub fn apply_policies(settings: &StorageSettings) -> StoragePolicyReport {
+    let active = crate::recording::session::active_recording_id();
+    let report = apply_in(&recordings_dir(), settings, active.as_deref());
+    if report.frames_removed > 0 || report.recordings_deleted > 0 {
+        log::info!(
+            "Storage policies: frames of {} recordings and {} recordings deleted, {} bytes reclaimed",
+            report.frames_removed,
+            report.recordings_deleted,
+            report.reclaimed_bytes,
+        );
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording(root: &Path, id: &str, date: &str, exported: bool) {
+        let dir = root.join(id);
+        std::fs::create_dir_all(dir.join("frames")).unwrap();
+        std::fs::write(dir.join("frames").join("frames.pack"), vec![0u8; 1000]).unwrap();
+        let meta = serde_json::json!({
+            "version": 3, "id": id, "screen_width": 1, "screen_height": 1, "fps": 30,
+            "start_time": date, "duration_ms": 0, "has_audio": false,
+            "monitor_scale": 1.0, "recording_dir": dir,
+        });
+        std::fs::write(dir.join("meta.json"), meta.to_string()).unwrap();
+        if exported {
+            std::fs::write(dir.join(EXPORTED_FILE), "{}").unwrap();
+        }
+    }
+
+    #[test]
+    fn test_policies_delete_frames_then_oldest() {
+        let root = tempfile::TempDir::new().unwrap();
+        recording(root.path(), "old", "2026-01-01T00:00:00Z", true);
+        recording(root.path(), "mid", "2026-01-02T00:00:00Z", false);
+        recording(root.path(), "new", "2026-01-03T00:00:00Z", true);
+        recording(root.path(), "live", "2026-01-04T00:00:00Z", true);
+
+        let usage = usage_in(root.path());
+        let ids: Vec<&str> = usage.recordings.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["live", "new", "mid", "old"]);
+        assert!(usage.recordings.iter().all(|r| r.frames_bytes == 1000 && r.bytes > 1000));
+        assert!(usage.total_bytes >= 4000);
+
+        let settings = StorageSettings { delete_frames_after_export: true, keep_last_recordings: 3, ..Default::default() };
+        let report = apply_in(root.path(), &settings, Some("live"));
+        assert_eq!((report.frames_removed, report.recordings_deleted), (2, 1));
+        assert!(report.reclaimed_bytes > 2000);
+        assert!(!root.path().join("old").exists());
+        assert!(root.path().join("live/frames").exists());
+        assert!(!root.path().join("new/frames").exists() && root.path().join("new/meta.json").exists());
+
+        // A tiny size limit removes only exported recordings by default...
+        let settings = StorageSettings { max_library_gb: 1e-9, ..Default::default() };
+        let report = apply_in(root.path(), &settings, Some("live"));
+        assert_eq!(report.recordings_deleted, 1);
+        assert!(root.path().join("mid").exists());
+
+        // ...and everything but the active recording when opted in
+        let settings = StorageSettings { max_library_gb: 1e-9, delete_unexported: true, ..Default::default() };
+        let report = apply_in(root.path(), &settings, Some("live"));
+        assert_eq!(report.recordings_deleted, 1);
+        assert_eq!(usage_in(root.path()).recordings.len(), 1);
+    }
+
+    #[test]
+    fn test_policies_skip_exports_in_progress() {
+        let root = tempfile::TempDir::new().unwrap();
+        recording(root.path(), "old", "2026-01-01T00:00:00Z", true);
+        recording(root.path(), "new", "2026-01-02T00:00:00Z", true);
+        // A lock left by a process that is gone doesn't count
+        std::fs::write(root.path().join("new/exporting-stale.lock"), u32::MAX.to_string()).unwrap();
+
+        let settings = StorageSettings {
+            delete_frames_after_export: true,
+            max_library_gb: 1e-9,
+            delete_unexported: true,
+            ..Default::default()
+        };
+        {
+            // Re-exporting "old" while the policies run
+            let _lock = ExportLock::acquire_in(&root.path().join("old"));
+            let report = apply_in(root.path(), &settings, None);
+            assert_eq!((report.frames_removed, report.recordings_deleted), (1, 1));
+            assert!(root.path().join("old/frames").exists());
+            assert!(!root.path().join("new").exists());
+        }
+        assert!(!export_in_progress(&root.path().join("old")));
+        let report = apply_in(root.path(), &settings, None);
+        assert_eq!(report.recordings_deleted, 1);
+        assert!(!root.path().join("old").exists());
+    }
+}
@@ -0,0 +1,233 @@
+//! Live streaming (`OutputSettings::stream`).
+//!
+//! A thread grabs the recording target at the stream's frame rate,
+//! optionally styles each grab like an export (background, rounded corners,
+//! shadow, cursor; see [`super::screenshot::styled_compositor`]) and pipes
+//! it as raw RGBA into FFmpeg. FFmpeg encodes H.264 with a silent AAC track
+//! (most services reject a stream without audio) and publishes FLV to the
+//! RTMP server.
+//!
+//! Frames keep the size of the first grab: a resized window is scaled back
+//! to it, and a failed grab (window closed) repeats the last frame.
+
+use crate::config::{AppSettings, RecordingMode, StreamSettings};
+use anyhow::Result;
+use image::RgbaImage;
+use std::io::{Read, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Called with the error when a stream ends on its own (connection
+/// closed, FFmpeg failed), not after [`LiveStream::stop`].
+pub type EndSink = Arc<dyn Fn(String) + Send + Sync>;
+
+pub struct LiveStream {
+    is_running: Arc<AtomicBool>,
+    thread: JoinHandle<Result<u64>>,
+}
+
+/// Publish URL: the server URL with the stream key appended.
+pub fn stream_url(stream: &StreamSettings) -> Result<String> {
+    let url = stream.url.trim().trim_end_matches('/');
+    if !(url.starts_with("rtmp://") || url.starts_with("rtmps://")) {
+        anyhow::bail!("Stream URL must start with rtmp:// or rtmps://");
+    }
+    let key = stream.stream_key.trim();
+    Ok(if key.is_empty() { url.to_string() } else { format!("{}/{}", url, key) })
+}
+
+/// FFmpeg arguments reading `width`×`height` RGBA frames from stdin and
+/// publishing them to `url`.
+fn ffmpeg_args(url: &str, width: u32, height: u32, stream: &StreamSettings) -> Vec<String> {
+    let fps = stream.fps.max(1);
+    let kbps = stream.video_bitrate_kbps.max(100);
+    [
+        "-loglevel", "error",
+        "-f", "rawvideo", "-pix_fmt", "rgba",
+        "-s", &format!("{}x{}", width, height),
+        "-r", &fps.to_string(),
+        "-i", "-",
+        "-f", "lavfi", "-i", "anullsrc=channel_layout=stereo:sample_rate=44100",
+        "-map", "0:v", "-map", "1:a",
+        // H.264 in 4:2:0 needs even dimensions
+        "-vf", "scale=trunc(iw/2)*2:trunc(ih/2)*2",
+        "-c:v", "libx264", "-preset", "veryfast", "-tune", "zerolatency", "-pix_fmt", "yuv420p",
+        "-b:v", &format!("{}k", kbps),
+        "-maxrate", &format!("{}k", kbps),
+        "-bufsize", &format!("{}k", kbps * 2),
+        "-g", &(fps * 2).to_string(),
+        "-c:a", "aac", "-b:a", "128k",
+        // The silent track never ends: stop with the video when stdin closes
+        "-shortest",
+        "-f", "flv", url,
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+/// Grabs the target and styles it, at a fixed frame size.
+struct FrameSource {
+    mode: RecordingMode,
+    compositor: Option<crate::engine::compositor::Compositor>,
+    size: (u32, u32),
+    last: RgbaImage,
+    started: Instant,
+}
+
+impl FrameSource {
+    fn new(mode: RecordingMode, settings: &AppSettings) -> Result<Self> {
+        let (raw, _) = crate::recording::capture::grab_target(&mode)?;
+        let size = raw.dimensions();
+        let compositor = settings.stream.apply_style.then(|| {
+            super::screenshot::styled_compositor(
+                settings,
+                size.0,
+                size.1,
+                crate::recording::events::monitor_scale(&mode),
+                crate::recording::events::os_cursor_scale(),
+            )
+        });
+        let mut source = Self { mode, compositor, size, last: raw, started: Instant::now() };
+        let first = source.last.clone();
+        source.last = source.style(&first, None, 0.0);
+        Ok(source)
+    }
+
+    fn style(&mut self, raw: &RgbaImage, cursor: Option<(f64, f64)>, dt: f64) -> RgbaImage {
+        let time_ms = self.started.elapsed().as_millis() as u64;
+        match self.compositor.as_mut() {
+            Some(compositor) => compositor.compose_frame(raw, time_ms, cursor, &[], None, dt),
+            None => raw.clone(),
+        }
+    }
+
+    /// The next frame (the last one again when the grab fails).
+    fn next(&mut self, dt: f64) -> &RgbaImage {
+        match crate::recording::capture::grab_target(&self.mode) {
+            Ok((raw, cursor)) => {
+                let raw = if raw.dimensions() == self.size {
+                    raw
+                } else {
+                    image::imageops::resize(&raw, self.size.0, self.size.1, image::imageops::FilterType::Triangle)
+                };
+                self.last = self.style(&raw, cursor, dt);
+            }
+            Err(e) => log::debug!("Stream grab failed, repeating the last frame: {}", e),
+        }
+        &self.last
+    }
+}
+
+/// FFmpeg's error output (read on a thread so the pipe never fills up).
+fn collect_stderr(child: &mut Child) -> Option<JoinHandle<String>> {
+    let mut stderr = child.stderr.take()?;
+    Some(std::thread::spawn(move || {
+        let mut out = String::new();
+        let _ = stderr.read_to_string(&mut out);
+        out
+    }))
+}
+
+/// Write frames to FFmpeg until `running` is cleared or the pipe breaks.
+/// Returns the number of frames sent.
+fn pump(running: &AtomicBool, source: &mut FrameSource, stdin: &mut ChildStdin, fps: u32) -> Result<u64> {
+    let interval = Duration::from_secs_f64(1.0 / fps.max(1) as f64);
+    let dt = interval.as_secs_f64();
+    let mut next_tick = Instant::now();
+    let mut frames = 0u64;
+    while running.load(Ordering::SeqCst) {
+        stdin.write_all(source.next(dt).as_raw())?;
+        frames += 1;
+        next_tick += interval;
+        let now = Instant::now();
+        if next_tick > now {
+            std::thread::sleep(next_tick - now);
+        } else if now - next_tick > interval {
+            // Fell behind (slow grab or compose): don't burst to catch up
+            next_tick = now;
+        }
+    }
+    Ok(frames)
+}
+
+impl LiveStream {
+    /// Start streaming the target of `mode` with `settings.stream`.
+    /// Fails when the URL is invalid, FFmpeg is missing or the first grab
+    /// fails; a connection failure ends the stream through `on_end`.
+    pub fn start(mode: RecordingMode, settings: &AppSettings, on_end: EndSink) -> Result<Self> {
+        let stream = settings.stream.clone();
+        let url = stream_url(&stream)?;
+        let ffmpeg = super::encoder::find_ffmpeg()?;
+        let mut source = FrameSource::new(mode, settings)?;
+        let (width, height) = source.last.dimensions();
+
+        let mut child = Command::new(&ffmpeg)
+            .args(ffmpeg_args(&url, width, height, &stream))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let mut stdin = child.stdin.take().ok_or_else(|| anyhow::anyhow!("FFmpeg stdin unavailable"))?;
+        let stderr = collect_stderr(&mut child);
+        // The key is a secret: log the server only
+        log::info!("Streaming {}x{} at {} fps to {}", width, height, stream.fps, stream.url.trim());
+
+        let is_running = Arc::new(AtomicBool::new(true));
+        let running = is_running.clone();
+        let thread = std::thread::spawn(move || {
+            let result = pump(&running, &mut source, &mut stdin, stream.fps);
+            // Closing stdin lets FFmpeg flush and disconnect
+            drop(stdin);
+            let _ = child.wait();
+            let errors = stderr.and_then(|h| h.join().ok()).unwrap_or_default();
+            result.map_err(|e| {
+                // The pipe broke: FFmpeg exited, usually with the reason
+                running.store(false, Ordering::SeqCst);
+                let reason = if errors.trim().is_empty() { e.to_string() } else { errors.trim().to_string() };
+                let error = anyhow::anyhow!("Stream stopped: {}", reason);
+                log::warn!("{}", error);
+                on_end(error.to_string());
+                error
+            })
+        });
+        Ok(Self { is_running, thread })
+    }
+
+    /// Stop streaming. Returns the number of frames sent.
+    pub fn stop(self) -> Result<u64> {
+        self.is_running.store(false, Ordering::SeqCst);
+        let frames = self
+            .thread
+            .join()
+            .map_err(|_| anyhow::anyhow!("Stream thread panicked"))??;
+        log::info!("Stream stopped after {} frames", frames);
+        Ok(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_url_and_args() {
+        let mut stream = StreamSettings { url: "rtmp://live.example.com/app/".to_string(), ..Default::default() };
+        assert_eq!(stream_url(&stream).unwrap(), "rtmp://live.example.com/app");
+        stream.stream_key = " abc-123 ".to_string();
+        assert_eq!(stream_url(&stream).unwrap(), "rtmp://live.example.com/app/abc-123");
+        stream.url = "https://example.com".to_string();
+        assert!(stream_url(&stream).is_err());
+
+        let args = ffmpeg_args("rtmp://x/app/key", 1280, 720, &StreamSettings::default());
+        let after = |flag: &str| args[args.iter().position(|a| a == flag).unwrap() + 1].clone();
+        assert_eq!(after("-s"), "1280x720");
+        assert_eq!(after("-b:v"), "4500k");
+        assert_eq!(after("-g"), "60");
+        assert!(args.iter().any(|a| a == "-shortest"));
+        assert_eq!(args.last().unwrap(), "rtmp://x/app/key");
+    }
+}
@@ -0,0 +1,103 @@
+//! Short-lived in-memory cache for the last export's composed frames.
+//!
+//! Exporting the same recording again with identical style, effects, cuts
+//! and keyframes (typically MP4 and then GIF) would compose every frame a
+//! second time. The composed frame set is kept for a few minutes under a
+//! fingerprint of its inputs and handed to the next export that asks for
+//! the same key.
+//!
+//! Only one entry is kept, since a composition can take gigabytes of temp
+//! space. An entry is dropped when it is replaced or when it has not been
+//! used for `ttl`; exports still holding it keep the frames alive until
+//! they finish.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+struct Entry<T> {
+    key: String,
+    value: Arc<T>,
+    expires: Instant,
+    generation: u64,
+}
+
+pub struct WarmCache<T> {
+    entry: Mutex<Option<Entry<T>>>,
+    generation: AtomicU64,
+    ttl: Duration,
+}
+
+impl<T: Send + Sync + 'static> WarmCache<T> {
+    pub const fn new(ttl: Duration) -> Self {
+        Self { entry: Mutex::new(None), generation: AtomicU64::new(0), ttl }
+    }
+
+    /// The cached value for `key`, if present and not expired. A hit
+    /// restarts the entry's lifetime.
+    pub fn get(&self, key: &str) -> Option<Arc<T>> {
+        let mut entry = self.entry.lock().ok()?;
+        let now = Instant::now();
+        match entry.as_mut() {
+            Some(e) if e.key == key && e.expires > now => {
+                e.expires = now + self.ttl;
+                Some(e.value.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Cache `value` under `key`, replacing any previous entry. A background
+    /// thread drops the entry once it expires.
+    pub fn insert(&'static self, key: String, value: Arc<T>) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Ok(mut entry) = self.entry.lock() {
+            *entry = Some(Entry { key, value, expires: Instant::now() + self.ttl, generation });
+        }
+        std::thread::spawn(move || self.expire(generation));
+    }
+
+    /// Drop the entry of `generation` once it has expired; stops early if
+    /// it was replaced.
+    fn expire(&self, generation: u64) {
+        loop {
+            let wait = {
+                let Ok(mut entry) = self.entry.lock() else {
+                    return;
+                };
+                match entry.as_ref() {
+                    Some(e) if e.generation == generation => {
+                        let now = Instant::now();
+                        if e.expires <= now {
+                            *entry = None;
+                            return;
+                        }
+                        e.expires - now
+                    }
+                    _ => return,
+                }
+            };
+            std::thread::sleep(wait);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static CACHE: WarmCache<String> = WarmCache::new(Duration::from_millis(100));
+
+    #[test]
+    fn test_hit_miss_and_expiry() {
+        CACHE.insert("a".into(), Arc::new("frames".into()));
+        assert_eq!(CACHE.get("a").as_deref().map(String::as_str), Some("frames"));
+        assert!(CACHE.get("b").is_none());
+
+        // The expiry thread drops the entry and with it the value
+        let value = CACHE.get("a").unwrap();
+        std::thread::sleep(Duration::from_millis(300));
+        assert!(CACHE.get("a").is_none());
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+}
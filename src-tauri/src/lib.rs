@@ -1,3 +1,5 @@
+pub mod area_select;
+pub mod cli;
 pub mod commands;
 pub mod config;
 pub mod engine;
@@ -17,6 +19,9 @@ pub fn run() {
             let handle = app.handle().clone();
             tray::setup_tray(&handle)?;
             shortcuts::setup_shortcuts(&handle)?;
+            commands::setup_replay_buffer(&handle);
+            commands::run_scheduler(handle.clone());
+            commands::run_storage_policies(handle.clone());
 
             // Create recordings directory
             if let Some(video_dir) = dirs::video_dir() {
@@ -36,6 +41,7 @@ pub fn run() {
             commands::export_recording,
             commands::get_export_progress,
             commands::get_settings,
+            commands::get_recording_settings,
             commands::save_settings,
             commands::delete_recording,
             commands::get_recording_thumbnail,
@@ -46,6 +52,80 @@ pub fn run() {
             commands::get_recording_events,
             commands::apply_scene_edits,
             commands::compute_activity_center,
+            commands::get_scene_boundary_preview,
+            commands::get_suggested_cuts,
+            commands::optimize_keyframes,
+            commands::start_recording_from_template,
+            commands::compare_recordings,
+            commands::get_redactions,
+            commands::save_redactions,
+            commands::get_annotations,
+            commands::save_annotations,
+            commands::is_first_run,
+            commands::run_setup_diagnostics,
+            commands::apply_recommended_settings,
+            commands::map_time_to_frame,
+            commands::map_frame_to_time,
+            commands::start_retake,
+            commands::link_takes,
+            commands::unlink_take,
+            commands::choose_take,
+            commands::load_keyframes,
+            commands::save_keyframes,
+            commands::load_scene_edits,
+            commands::render_preview_frame,
+            commands::generate_preview,
+            commands::append_to_recording,
+            commands::export_style_preset,
+            commands::import_style_preset,
+            commands::list_style_presets,
+            commands::apply_style_preset,
+            commands::review_recording_privacy,
+            commands::cancel_countdown,
+            commands::get_bookmarks,
+            commands::add_bookmark,
+            commands::update_bookmark,
+            commands::delete_bookmark,
+            commands::get_system_theme,
+            commands::apply_system_theme,
+            commands::select_area,
+            commands::finish_area_selection,
+            commands::get_scene_styles,
+            commands::save_scene_styles,
+            commands::take_screenshot,
+            commands::cleanup_workspace,
+            commands::start_scrolling_capture,
+            commands::stop_scrolling_capture,
+            commands::schedule_recording,
+            commands::list_scheduled_recordings,
+            commands::cancel_scheduled_recording,
+            commands::start_stream,
+            commands::stop_stream,
+            commands::is_streaming,
+            commands::list_recording_tags,
+            commands::rename_recording,
+            commands::set_recording_tags,
+            commands::set_recording_notes,
+            commands::get_recordings_page,
+            commands::get_storage_usage,
+            commands::apply_storage_policies,
+            commands::export_project,
+            commands::import_project,
+            commands::import_video,
+            commands::open_recording_dir,
+            commands::reveal_export,
+            commands::copy_export_to_clipboard,
+            commands::upload_export,
+            commands::authorize_youtube,
+            commands::batch_export,
+            commands::list_profiles,
+            commands::save_profile,
+            commands::apply_profile,
+            commands::delete_profile,
+            commands::cancel_recording,
+            commands::validate_hotkey,
+            commands::begin_hotkey_capture,
+            commands::end_hotkey_capture,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -2,5 +2,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // `snappi export ...` and friends run headless and exit
+    if let Some(code) = snappi_lib::cli::run_from_args() {
+        std::process::exit(code);
+    }
     snappi_lib::run()
 }
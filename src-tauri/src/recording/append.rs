@@ -0,0 +1,294 @@
+//! Append mode: continue a stopped recording.
+//!
+//! The continuation is recorded as a normal session in its own directory.
+//! When it stops, its frames, events and audio are moved to the end of the
+//! target recording, shifted by the target's length, and the session
+//! directory is removed. The join point is stored in
+//! [`RecordingMeta::splices`] so the Timeline can show where the two parts
+//! meet.
+
+use crate::config::{PauseMarker, RecordingMeta};
+use crate::recording::capture::FRAME_TIMESTAMPS_FILE;
+use crate::recording::frame_sink::save_frame_as_jpeg;
+use crate::recording::frame_store::{FrameSource, PackWriter, FRAME_PACK_FILE};
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::path::Path;
+
+/// Event logs whose lines carry a `t` timestamp (ms from recording start)
+const EVENT_FILES: &[&str] = &[
+    "events.jsonl",
+    "window_events.jsonl",
+    "ui_events.jsonl",
+    crate::recording::window_track::WINDOW_TRACK_FILE,
+];
+
+/// Size of the canonical WAV header written by the audio thread
+const WAV_HEADER_LEN: usize = 44;
+
+fn read_meta(dir: &Path) -> Result<RecordingMeta> {
+    let content = std::fs::read_to_string(dir.join("meta.json"))
+        .with_context(|| format!("Recording not found: {}", dir.display()))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn frame_count(dir: &Path, meta: &RecordingMeta) -> u64 {
+    meta.frame_count.map(u64::from).unwrap_or_else(|| {
+        std::fs::read_to_string(dir.join("frame_count.txt"))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0)
+    })
+}
+
+/// Time at which the appended part starts: after the last frame (which
+/// may lie past `duration_ms` when the recording was paused).
+fn splice_time(dir: &Path, meta: &RecordingMeta) -> u64 {
+    let frame_interval = 1000 / meta.fps.max(1) as u64;
+    let last_frame = crate::export::frame_index::read_frame_timestamps(dir)
+        .and_then(|ts| ts.last().copied())
+        .map_or(0, |t| t + frame_interval);
+    meta.duration_ms.max(last_frame)
+}
+
+/// Move the session recorded in `source_dir` to the end of `target_id`.
+/// Returns the splice time (ms on the target's timeline).
+pub fn append_recording(target_id: &str, source_dir: &Path) -> Result<u64> {
+    let target_dir = source_dir
+        .parent()
+        .context("Recording directory has no parent")?
+        .join(target_id);
+    let mut target = read_meta(&target_dir)?;
+    let source = read_meta(source_dir)?;
+    if (target.screen_width, target.screen_height) != (source.screen_width, source.screen_height) {
+        bail!(
+            "Cannot append a {}x{} recording to a {}x{} one",
+            source.screen_width,
+            source.screen_height,
+            target.screen_width,
+            target.screen_height
+        );
+    }
+
+    let offset = splice_time(&target_dir, &target);
+    let target_frames = frame_count(&target_dir, &target);
+    let source_frames = frame_count(source_dir, &source);
+
+    append_frames(&target_dir.join("frames"), &source_dir.join("frames"), target_frames, source_frames)?;
+    append_frame_timestamps(&target_dir, source_dir, offset)?;
+
+    for file in EVENT_FILES {
+        append_shifted_events(&target_dir.join(file), &source_dir.join(file), offset)?;
+    }
+    target.has_audio = append_audio(&target_dir, source_dir, &source, offset)?;
+
+    target.duration_ms = offset + source.duration_ms;
+    target.frame_count = Some((target_frames + source_frames) as u32);
+    target.splices.push(offset);
+    target.pauses.extend(source.pauses.iter().map(|p| PauseMarker { at_ms: p.at_ms + offset, ..*p }));
+    std::fs::write(target_dir.join("meta.json"), serde_json::to_string_pretty(&target)?)?;
+
+    std::fs::remove_dir_all(source_dir)?;
+    if let Err(e) = crate::export::encoder::generate_thumbnail(target_id) {
+        log::warn!("Thumbnail generation failed for {}: {}", target_id, e);
+    }
+    log::info!(
+        "Appended {} frames ({}ms) to {} at {}ms",
+        source_frames,
+        source.duration_ms,
+        target_id,
+        offset
+    );
+    Ok(offset)
+}
+
+/// Frames, renumbered after the target's. The target keeps its storage
+/// format: frames are re-encoded when the two parts were stored differently.
+fn append_frames(target_dir: &Path, source_dir: &Path, target_frames: u64, source_frames: u64) -> Result<()> {
+    let pack_path = target_dir.join(FRAME_PACK_FILE);
+    if pack_path.exists() {
+        let source = FrameSource::open(source_dir)?;
+        let mut pack = PackWriter::append(&pack_path)?;
+        for idx in 0..source_frames {
+            pack.write_frame(source.load(idx)?.to_rgba8().as_raw())?;
+        }
+        return pack.finish();
+    }
+    if let FrameSource::Pack(pack) = FrameSource::open(source_dir)? {
+        let (w, h) = pack.dimensions();
+        for idx in 0..pack.len() {
+            let to = target_dir.join(format!("frame_{:08}.jpg", target_frames + idx));
+            save_frame_as_jpeg(pack.read(idx)?.as_raw(), w, h, &to)?;
+        }
+        return Ok(());
+    }
+    for idx in 0..source_frames {
+        for ext in ["jpg", "png"] {
+            let from = source_dir.join(format!("frame_{:08}.{}", idx, ext));
+            if from.exists() {
+                let to = target_dir.join(format!("frame_{:08}.{}", target_frames + idx, ext));
+                std::fs::rename(&from, &to).or_else(|_| std::fs::copy(&from, &to).map(|_| ()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Frame times are only kept when both parts have them; otherwise frames
+/// fall back to evenly spaced times for the whole recording.
+fn append_frame_timestamps(target_dir: &Path, source_dir: &Path, offset: u64) -> Result<()> {
+    let target_path = target_dir.join(FRAME_TIMESTAMPS_FILE);
+    match (target_path.exists(), std::fs::read_to_string(source_dir.join(FRAME_TIMESTAMPS_FILE))) {
+        (true, Ok(content)) => {
+            let mut file = std::fs::OpenOptions::new().append(true).open(&target_path)?;
+            for t in content.lines().filter_map(|l| l.trim().parse::<u64>().ok()) {
+                writeln!(file, "{}", t + offset)?;
+            }
+        }
+        (true, Err(_)) => std::fs::remove_file(&target_path)?,
+        (false, _) => {}
+    }
+    Ok(())
+}
+
+fn append_shifted_events(target: &Path, source: &Path, offset: u64) -> Result<()> {
+    let Ok(content) = std::fs::read_to_string(source) else {
+        return Ok(());
+    };
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(target)?;
+    for line in content.lines() {
+        let Ok(mut event) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if let Some(t) = event.get("t").and_then(|t| t.as_u64()) {
+            event["t"] = serde_json::json!(t + offset);
+        }
+        writeln!(file, "{}", event)?;
+    }
+    Ok(())
+}
+
+/// (fmt chunk, sample data) of a recorded WAV file
+fn read_wav(path: &Path) -> Option<(Vec<u8>, Vec<u8>)> {
+    let bytes = std::fs::read(path).ok()?;
+    if bytes.len() < WAV_HEADER_LEN || &bytes[0..4] != b"RIFF" {
+        return None;
+    }
+    Some((bytes[12..36].to_vec(), bytes[WAV_HEADER_LEN..].to_vec()))
+}
+
+/// Bytes per millisecond of audio for a fmt chunk (byte rate / 1000)
+fn wav_bytes_per_ms(fmt: &[u8]) -> f64 {
+    u32::from_le_bytes([fmt[16], fmt[17], fmt[18], fmt[19]]) as f64 / 1000.0
+}
+
+fn wav_block_align(fmt: &[u8]) -> usize {
+    u16::from_le_bytes([fmt[20], fmt[21]]).max(1) as usize
+}
+
+/// Zero samples (silence for both integer and float WAV) for `ms`
+fn silence(fmt: &[u8], ms: u64) -> Vec<u8> {
+    let align = wav_block_align(fmt);
+    let len = (wav_bytes_per_ms(fmt) * ms as f64) as usize / align * align;
+    vec![0; len]
+}
+
+/// Join the audio tracks. A part without audio (or with a different sample
+/// format) becomes silence, so sound stays in sync with the frames.
+/// Returns whether the joined recording has audio.
+fn append_audio(target_dir: &Path, source_dir: &Path, source: &RecordingMeta, offset: u64) -> Result<bool> {
+    let target_path = target_dir.join("audio.wav");
+    let target_wav = read_wav(&target_path);
+    let source_wav = read_wav(&source_dir.join("audio.wav"));
+    let Some(fmt) = target_wav.as_ref().or(source_wav.as_ref()).map(|(fmt, _)| fmt.clone()) else {
+        return Ok(false);
+    };
+
+    // The target's audio (or silence) up to the splice point
+    let mut data = target_wav.map(|(_, data)| data).unwrap_or_default();
+    let head_len = silence(&fmt, offset).len();
+    if data.len() < head_len {
+        data.resize(head_len, 0);
+    }
+    match source_wav {
+        Some((source_fmt, source_data)) if source_fmt == fmt => data.extend_from_slice(&source_data),
+        other => {
+            if other.is_some() {
+                log::warn!("Appended audio has a different format; replacing it with silence");
+            }
+            data.extend(silence(&fmt, source.duration_ms));
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(WAV_HEADER_LEN + data.len());
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    bytes.extend_from_slice(&fmt);
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&data);
+    std::fs::write(&target_path, bytes)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_shifted_by_offset() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let target = dir.path().join("events.jsonl");
+        let source = dir.path().join("new_events.jsonl");
+        std::fs::write(&target, "{\"type\":\"click\",\"t\":100,\"btn\":\"left\",\"x\":1.0,\"y\":2.0}\n").unwrap();
+        std::fs::write(&source, "{\"type\":\"key\",\"t\":50,\"key\":\"a\",\"modifiers\":[]}\n").unwrap();
+
+        append_shifted_events(&target, &source, 5000).unwrap();
+        let content = std::fs::read_to_string(&target).unwrap();
+        let times: Vec<u64> = content
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["t"].as_u64().unwrap())
+            .collect();
+        assert_eq!(times, vec![100, 5050]);
+    }
+
+    fn write_wav(path: &Path, data: &[u8]) {
+        // 16-bit mono PCM, 1000 Hz: 2 bytes per ms
+        let mut fmt = b"fmt ".to_vec();
+        fmt.extend_from_slice(&16u32.to_le_bytes());
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&1000u32.to_le_bytes());
+        fmt.extend_from_slice(&2000u32.to_le_bytes());
+        fmt.extend_from_slice(&2u16.to_le_bytes());
+        fmt.extend_from_slice(&16u16.to_le_bytes());
+        let mut bytes = b"RIFF".to_vec();
+        bytes.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+        bytes.extend_from_slice(&fmt);
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(data);
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_audio_padded_to_splice_point() {
+        let target = tempfile::TempDir::new().unwrap();
+        let source = tempfile::TempDir::new().unwrap();
+        // Target audio stops 5ms before the splice at 10ms
+        write_wav(&target.path().join("audio.wav"), &[1; 10]);
+        write_wav(&source.path().join("audio.wav"), &[2; 6]);
+        let meta: RecordingMeta = serde_json::from_value(serde_json::json!({
+            "version": 2, "id": "b", "screen_width": 100, "screen_height": 100, "fps": 30,
+            "start_time": "", "duration_ms": 3, "has_audio": true, "monitor_scale": 1.0,
+            "recording_dir": ""
+        }))
+        .unwrap();
+
+        assert!(append_audio(target.path(), source.path(), &meta, 10).unwrap());
+        let (_, data) = read_wav(&target.path().join("audio.wav")).unwrap();
+        assert_eq!(data, [vec![1; 10], vec![0; 10], vec![2; 6]].concat());
+    }
+}
@@ -1,39 +1,14 @@
+use super::frame_sink::FrameRecorder;
 use anyhow::Result;
-use std::io::Write;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
 
 /// 各フレーム保存時の「録画開始からの実経過 ms」を記録するファイル名。
 /// 長時間録画で capture スレッドのジッタが蓄積した場合でも、エクスポート時に
 /// 正確な fps を算出できるようにするための補助データ。1行1フレーム。
 pub const FRAME_TIMESTAMPS_FILE: &str = "frame_timestamps.txt";
 
-/// 録画フレーム保存に使う JPEG 品質。画面コンテンツ（テキスト/UI）は q=92 で
-/// 視覚的に PNG とほぼ区別が付かず、1920x1080 で PNG ~3-4MB から JPEG ~300KB 程度に。
-/// 最終出力は H.264 等で再圧縮されるため中間品質はこれで十分。
-const RECORDING_JPEG_QUALITY: u8 = 92;
-
-/// 画面キャプチャしたフレームを JPEG として保存するヘルパー。
-/// JPEG は RGBA を扱えないので RGB に変換して保存する（アルファは画面キャプチャに不要）。
-fn save_frame_as_jpeg(buffer: &[u8], width: u32, height: u32, path: &std::path::Path) -> std::io::Result<()> {
-    // RGBA → RGB（4 バイトごとに α を破棄）
-    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
-    for chunk in buffer.chunks_exact(4) {
-        rgb.push(chunk[0]);
-        rgb.push(chunk[1]);
-        rgb.push(chunk[2]);
-    }
-
-    let file = std::fs::File::create(path)?;
-    let mut w = std::io::BufWriter::new(file);
-    let mut enc = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut w, RECORDING_JPEG_QUALITY);
-    enc.encode(&rgb, width, height, image::ExtendedColorType::Rgb8)
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-    Ok(())
-}
-
 /// Capture a specific window's frames using PrintWindow (with DWM content) + fallback to screen BitBlt.
 ///
 /// GetDC(hwnd) + BitBlt does NOT work for GPU-accelerated windows (Chrome, Edge, etc.)
@@ -47,6 +22,7 @@ pub fn capture_window(
     output_dir: &Path,
     fps: u32,
     hwnd_raw: isize,
+    mut recorder: FrameRecorder,
 ) -> Result<()> {
     use windows::Win32::Graphics::Gdi::*;
     use windows::Win32::UI::WindowsAndMessaging::*;
@@ -59,16 +35,9 @@ pub fn capture_window(
 
     log::info!("Window capture thread started (HWND: {}, {}fps)", hwnd_raw, fps);
 
-    let frames_dir = output_dir.join("frames");
-    std::fs::create_dir_all(&frames_dir)?;
+    std::fs::create_dir_all(output_dir.join("frames"))?;
 
     let frame_interval = std::time::Duration::from_nanos(1_000_000_000 / fps as u64);
-    let mut frame_count: u64 = 0;
-
-    // 録画開始時刻。各フレーム保存時の経過 ms を frame_timestamps.txt に記録する。
-    let session_start = Instant::now();
-    let timestamps_path = output_dir.join(FRAME_TIMESTAMPS_FILE);
-    let mut ts_writer = std::fs::File::create(&timestamps_path).ok().map(std::io::BufWriter::new);
 
     unsafe {
         let hwnd = HWND(hwnd_raw as *mut _);
@@ -133,6 +102,9 @@ pub fn capture_window(
         // Keep a screen DC for the fallback path (reused across frames)
         let screen_dc = GetDC(HWND::default());
 
+        // Where the window is over time, so events follow it when it moves
+        let mut track = super::window_track::WindowTrackWriter::create(output_dir);
+
         while is_running.load(Ordering::SeqCst) {
             let frame_start = std::time::Instant::now();
 
@@ -150,12 +122,7 @@ pub fn capture_window(
                 // Window may have been closed - reuse last frame or skip
                 if let Some(ref buf) = last_buffer {
                     if last_width > 0 && last_height > 0 {
-                        let frame_path = frames_dir.join(format!("frame_{:08}.jpg", frame_count));
-                        let _ = save_frame_as_jpeg(buf, last_width as u32, last_height as u32, &frame_path);
-                        if let Some(w) = ts_writer.as_mut() {
-                            let _ = writeln!(w, "{}", session_start.elapsed().as_millis() as u64);
-                        }
-                        frame_count += 1;
+                        recorder.write(buf, last_width as u32, last_height as u32);
                     }
                 }
                 let elapsed = frame_start.elapsed();
@@ -172,12 +139,7 @@ pub fn capture_window(
                 // Window is minimized - reuse last frame
                 if let Some(ref buf) = last_buffer {
                     if last_width > 0 && last_height > 0 {
-                        let frame_path = frames_dir.join(format!("frame_{:08}.jpg", frame_count));
-                        let _ = save_frame_as_jpeg(buf, last_width as u32, last_height as u32, &frame_path);
-                        if let Some(w) = ts_writer.as_mut() {
-                            let _ = writeln!(w, "{}", session_start.elapsed().as_millis() as u64);
-                        }
-                        frame_count += 1;
+                        recorder.write(buf, last_width as u32, last_height as u32);
                     }
                 }
                 let elapsed = frame_start.elapsed();
@@ -187,6 +149,11 @@ pub fn capture_window(
                 continue;
             }
 
+            track.record(
+                recorder.now_ms(),
+                [rect.left as f64, rect.top as f64, rect.right as f64, rect.bottom as f64],
+            );
+
             // Save dimensions (update on resize)
             if width != last_width || height != last_height {
                 let dims = format!("{}x{}", width, height);
@@ -211,7 +178,7 @@ pub fn capture_window(
                     mem_dc, 0, 0, width, height,
                     screen_dc, rect.left, rect.top, SRCCOPY,
                 );
-                if frame_count == 0 {
+                if recorder.frame_count() == 0 {
                     log::warn!("PrintWindow failed, falling back to screen BitBlt crop");
                 }
             }
@@ -249,15 +216,8 @@ pub fn capture_window(
             let _ = DeleteObject(bitmap);
             let _ = DeleteDC(mem_dc);
 
-            // Save frame as JPEG for drastically reduced disk usage
-            let frame_path = frames_dir.join(format!("frame_{:08}.jpg", frame_count));
-            let _ = save_frame_as_jpeg(&buffer, width as u32, height as u32, &frame_path);
-            if let Some(w) = ts_writer.as_mut() {
-                let _ = writeln!(w, "{}", session_start.elapsed().as_millis() as u64);
-            }
-
+            recorder.write(&buffer, width as u32, height as u32);
             last_buffer = Some(buffer);
-            frame_count += 1;
 
             let elapsed = frame_start.elapsed();
             if elapsed < frame_interval {
@@ -269,12 +229,8 @@ pub fn capture_window(
         ReleaseDC(HWND::default(), screen_dc);
     }
 
-    if let Some(mut w) = ts_writer {
-        let _ = w.flush();
-    }
-
+    let frame_count = recorder.finish()?;
     log::info!("Window capture stopped. Total frames: {}", frame_count);
-    std::fs::write(output_dir.join("frame_count.txt"), frame_count.to_string())?;
 
     Ok(())
 }
@@ -286,6 +242,7 @@ pub fn capture_window(
     _output_dir: &Path,
     _fps: u32,
     _hwnd_raw: isize,
+    _recorder: FrameRecorder,
 ) -> Result<()> {
     Err(anyhow::anyhow!("Window capture is only supported on Windows"))
 }
@@ -300,24 +257,18 @@ pub fn capture_area(
     area_y: i32,
     area_w: i32,
     area_h: i32,
+    mut recorder: FrameRecorder,
 ) -> Result<()> {
     log::info!("Area capture started ({},{} {}x{}, {}fps)", area_x, area_y, area_w, area_h, fps);
 
-    let frames_dir = output_dir.join("frames");
-    std::fs::create_dir_all(&frames_dir)?;
+    std::fs::create_dir_all(output_dir.join("frames"))?;
 
     let frame_interval = std::time::Duration::from_nanos(1_000_000_000 / fps as u64);
-    let mut frame_count: u64 = 0;
 
     // Save dimensions
     let dims = format!("{}x{}", area_w, area_h);
     std::fs::write(output_dir.join("dimensions.txt"), &dims)?;
 
-    // 録画開始時刻。各フレームの実経過 ms を記録
-    let session_start = Instant::now();
-    let timestamps_path = output_dir.join(FRAME_TIMESTAMPS_FILE);
-    let mut ts_writer = std::fs::File::create(&timestamps_path).ok().map(std::io::BufWriter::new);
-
     #[cfg(windows)]
     {
         use windows::Win32::Graphics::Gdi::*;
@@ -366,13 +317,7 @@ pub fn capture_area(
                     chunk.swap(0, 2);
                 }
 
-                let frame_path = frames_dir.join(format!("frame_{:08}.jpg", frame_count));
-                let _ = save_frame_as_jpeg(&buffer, area_w as u32, area_h as u32, &frame_path);
-                if let Some(w) = ts_writer.as_mut() {
-                    let _ = writeln!(w, "{}", session_start.elapsed().as_millis() as u64);
-                }
-
-                frame_count += 1;
+                recorder.write(&buffer, area_w as u32, area_h as u32);
 
                 let elapsed = frame_start.elapsed();
                 if elapsed < frame_interval {
@@ -387,36 +332,231 @@ pub fn capture_area(
         }
     }
 
-    if let Some(mut w) = ts_writer {
-        let _ = w.flush();
-    }
-
+    let frame_count = recorder.finish()?;
     log::info!("Area capture stopped. Total frames: {}", frame_count);
-    std::fs::write(output_dir.join("frame_count.txt"), frame_count.to_string())?;
 
     Ok(())
 }
 
+/// Capture and JPEG-encode `frames` full-screen frames as fast as possible
+/// (into a temporary directory) and return (width, height, frames per
+/// second). Used by the first-run setup to pick a capture fps the machine
+/// can sustain.
+#[cfg(windows)]
+pub fn benchmark_screen_capture(frames: u32) -> Result<(u32, u32, f64)> {
+    use super::frame_sink::FrameSink;
+    use windows::Win32::Foundation::*;
+    use windows::Win32::Graphics::Gdi::*;
+    use windows::Win32::UI::WindowsAndMessaging::*;
+
+    let temp_dir = tempfile::TempDir::new()?;
+    let mut sink = super::frame_sink::JpegDirSink::new(temp_dir.path());
+    unsafe {
+        let width = GetSystemMetrics(SM_CXSCREEN);
+        let height = GetSystemMetrics(SM_CYSCREEN);
+        if width <= 0 || height <= 0 {
+            return Err(anyhow::anyhow!("No screen available for GDI capture"));
+        }
+        let screen_dc = GetDC(HWND::default());
+        if screen_dc.is_invalid() {
+            return Err(anyhow::anyhow!("GetDC failed"));
+        }
+        let mem_dc = CreateCompatibleDC(screen_dc);
+        let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+        let old_bitmap = SelectObject(mem_dc, bitmap);
+
+        let mut bmi = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: 0,
+                biSizeImage: (width * height * 4) as u32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut buffer = vec![0u8; (width * height * 4) as usize];
+
+        let start = std::time::Instant::now();
+        let mut result = Ok(());
+        for i in 0..frames {
+            if BitBlt(mem_dc, 0, 0, width, height, screen_dc, 0, 0, SRCCOPY).is_err() {
+                result = Err(anyhow::anyhow!("BitBlt failed"));
+                break;
+            }
+            GetDIBits(mem_dc, bitmap, 0, height as u32, Some(buffer.as_mut_ptr() as *mut _), &mut bmi, DIB_RGB_COLORS);
+            for chunk in buffer.chunks_exact_mut(4) {
+                chunk.swap(0, 2);
+            }
+            if let Err(e) = sink.write_frame(i as u64, &buffer, width as u32, height as u32) {
+                result = Err(e);
+                break;
+            }
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+
+        SelectObject(mem_dc, old_bitmap);
+        let _ = DeleteObject(bitmap);
+        let _ = DeleteDC(mem_dc);
+        let _ = ReleaseDC(HWND::default(), screen_dc);
+
+        result?;
+        Ok((width as u32, height as u32, frames as f64 / elapsed.max(1e-3)))
+    }
+}
+
+#[cfg(not(windows))]
+pub fn benchmark_screen_capture(_frames: u32) -> Result<(u32, u32, f64)> {
+    Err(anyhow::anyhow!("Screen capture is only supported on Windows"))
+}
+
+/// Grab the primary screen once, with the cursor position (screen
+/// coordinates) when it can be read. Used for screenshots.
+#[cfg(windows)]
+pub fn grab_screen() -> Result<(image::RgbaImage, Option<(f64, f64)>)> {
+    use windows::Win32::UI::WindowsAndMessaging::*;
+
+    let (width, height) = unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) };
+    let image = grab_area(0, 0, width, height)?;
+    Ok((image, cursor_position()))
+}
+
+#[cfg(not(windows))]
+pub fn grab_screen() -> Result<(image::RgbaImage, Option<(f64, f64)>)> {
+    Err(anyhow::anyhow!("Screen capture is only supported on Windows"))
+}
+
+/// Grab a rectangle of the screen once (without the cursor).
+#[cfg(windows)]
+pub fn grab_area(x: i32, y: i32, width: i32, height: i32) -> Result<image::RgbaImage> {
+    use windows::Win32::Foundation::*;
+    use windows::Win32::Graphics::Gdi::*;
+
+    if width <= 0 || height <= 0 {
+        return Err(anyhow::anyhow!("Nothing to capture ({}x{})", width, height));
+    }
+    unsafe {
+        let screen_dc = GetDC(HWND::default());
+        if screen_dc.is_invalid() {
+            return Err(anyhow::anyhow!("GetDC failed"));
+        }
+        let mem_dc = CreateCompatibleDC(screen_dc);
+        let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+        let old_bitmap = SelectObject(mem_dc, bitmap);
+
+        let mut bmi = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: 0,
+                biSizeImage: (width * height * 4) as u32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut buffer = vec![0u8; (width * height * 4) as usize];
+        let blit = BitBlt(mem_dc, 0, 0, width, height, screen_dc, x, y, SRCCOPY);
+        if blit.is_ok() {
+            GetDIBits(mem_dc, bitmap, 0, height as u32, Some(buffer.as_mut_ptr() as *mut _), &mut bmi, DIB_RGB_COLORS);
+        }
+
+        SelectObject(mem_dc, old_bitmap);
+        let _ = DeleteObject(bitmap);
+        let _ = DeleteDC(mem_dc);
+        let _ = ReleaseDC(HWND::default(), screen_dc);
+        if blit.is_err() {
+            return Err(anyhow::anyhow!("BitBlt failed"));
+        }
+
+        for chunk in buffer.chunks_exact_mut(4) {
+            chunk.swap(0, 2);
+            // GDI leaves alpha at 0
+            chunk[3] = 255;
+        }
+        image::RgbaImage::from_raw(width as u32, height as u32, buffer)
+            .ok_or_else(|| anyhow::anyhow!("Screen buffer does not match {}x{}", width, height))
+    }
+}
+
+#[cfg(not(windows))]
+pub fn grab_area(_x: i32, _y: i32, _width: i32, _height: i32) -> Result<image::RgbaImage> {
+    Err(anyhow::anyhow!("Screen capture is only supported on Windows"))
+}
+
+/// Grab the target of `mode` once: the selected window (at its current
+/// position), the selected area, or the whole screen, with the cursor
+/// position relative to the grabbed image when it can be read.
+pub fn grab_target(mode: &crate::config::RecordingMode) -> Result<(image::RgbaImage, Option<(f64, f64)>)> {
+    use crate::config::RecordingMode;
+
+    let (x, y, w, h) = match *mode {
+        RecordingMode::Display => return grab_screen(),
+        RecordingMode::Window { hwnd, rect, .. } => window_rect(hwnd).unwrap_or((
+            rect[0] as i32,
+            rect[1] as i32,
+            (rect[2] - rect[0]) as i32,
+            (rect[3] - rect[1]) as i32,
+        )),
+        RecordingMode::Area { x, y, width, height } => (x, y, width, height),
+    };
+    let image = grab_area(x, y, w, h)?;
+    let cursor = cursor_position().map(|(cx, cy)| (cx - x as f64, cy - y as f64));
+    Ok((image, cursor))
+}
+
+/// Cursor position in screen coordinates.
+#[cfg(windows)]
+pub fn cursor_position() -> Option<(f64, f64)> {
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+    let mut point = POINT::default();
+    unsafe { GetCursorPos(&mut point) }.ok().map(|_| (point.x as f64, point.y as f64))
+}
+
+#[cfg(not(windows))]
+pub fn cursor_position() -> Option<(f64, f64)> {
+    None
+}
+
+/// Current on-screen rectangle (x, y, width, height) of a window.
+#[cfg(windows)]
+pub fn window_rect(hwnd_raw: isize) -> Option<(i32, i32, i32, i32)> {
+    use windows::Win32::Foundation::*;
+    use windows::Win32::UI::WindowsAndMessaging::GetWindowRect;
+
+    let mut rect = RECT::default();
+    unsafe { GetWindowRect(HWND(hwnd_raw as *mut _), &mut rect) }.ok()?;
+    Some((rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top))
+}
+
+#[cfg(not(windows))]
+pub fn window_rect(_hwnd_raw: isize) -> Option<(i32, i32, i32, i32)> {
+    None
+}
+
 /// Capture screen frames using Windows GDI (BitBlt)
-/// This is simpler and more compatible than Desktop Duplication API
+/// This is simpler and more compatible than Desktop Duplication API.
+/// Frames are written through `recorder` (JPEG files or `frames.pack`, see
+/// [`super::frame_sink`]).
 pub fn capture_screen(
     is_running: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
     output_dir: &Path,
     fps: u32,
+    mut recorder: FrameRecorder,
 ) -> Result<()> {
     log::info!("Screen capture thread started (GDI mode, {}fps)", fps);
 
-    let frames_dir = output_dir.join("frames");
-    std::fs::create_dir_all(&frames_dir)?;
+    std::fs::create_dir_all(output_dir.join("frames"))?;
 
     let frame_interval = std::time::Duration::from_nanos(1_000_000_000 / fps as u64);
-    let mut frame_count: u64 = 0;
-
-    // 録画開始時刻。各フレームの実経過 ms を記録
-    let session_start = Instant::now();
-    let timestamps_path = output_dir.join(FRAME_TIMESTAMPS_FILE);
-    let mut ts_writer = std::fs::File::create(&timestamps_path).ok().map(std::io::BufWriter::new);
 
     #[cfg(windows)]
     {
@@ -481,14 +621,7 @@ pub fn capture_screen(
                     chunk.swap(0, 2);
                 }
 
-                // Save frame as PNG (for FFmpeg later)
-                let frame_path = frames_dir.join(format!("frame_{:08}.jpg", frame_count));
-                let _ = save_frame_as_jpeg(&buffer, width as u32, height as u32, &frame_path);
-                if let Some(w) = ts_writer.as_mut() {
-                    let _ = writeln!(w, "{}", session_start.elapsed().as_millis() as u64);
-                }
-
-                frame_count += 1;
+                recorder.write(&buffer, width as u32, height as u32);
 
                 // Maintain frame rate
                 let elapsed = frame_start.elapsed();
@@ -505,16 +638,8 @@ pub fn capture_screen(
         }
     }
 
-    if let Some(mut w) = ts_writer {
-        let _ = w.flush();
-    }
-
+    let frame_count = recorder.finish()?;
     log::info!("Screen capture stopped. Total frames: {}", frame_count);
 
-    std::fs::write(
-        output_dir.join("frame_count.txt"),
-        frame_count.to_string(),
-    )?;
-
     Ok(())
 }
@@ -0,0 +1,278 @@
+//! Frame differencing while recording.
+//!
+//! Runs next to the capture thread and compares sampled frames as soon as
+//! they are on disk, streaming one line per pair to
+//! [`frame_differ::CHANGES_FILE`]. Export, keyframe and scene analysis
+//! read the file instead of decoding every frame again (see
+//! [`frame_differ::load_recorded_changes`]).
+//!
+//! A frame is only read once the next one exists (or the capture thread
+//! has written `frame_count.txt`), so it is never caught half-written.
+//! Cursor positions for the cursor mask are tailed from `events.jsonl`.
+
+use crate::config::RecordingEvent;
+use crate::engine::frame_differ::{self, CaptureMapping, DiffConfig, RecordedPair};
+use crate::recording::capture::FRAME_TIMESTAMPS_FILE;
+use anyhow::Result;
+use image::GrayImage;
+use std::io::{BufRead, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// How often new frames are looked for
+const POLL_INTERVAL_MS: u64 = 200;
+
+/// How long to wait for the capture thread's final frame count after stop
+const FINAL_COUNT_WAIT_MS: u64 = 3000;
+
+/// Cursor positions older than this before the oldest pending frame are dropped
+const CURSOR_HISTORY_MS: u64 = 2000;
+
+/// Reads the lines appended to a file since the last call.
+struct Tail {
+    path: PathBuf,
+    offset: u64,
+}
+
+impl Tail {
+    fn new(path: PathBuf) -> Self {
+        Self { path, offset: 0 }
+    }
+
+    /// Complete lines written since the last call (a trailing partial line
+    /// is left for the next one).
+    fn read_new_lines(&mut self) -> Vec<String> {
+        let Ok(mut file) = std::fs::File::open(&self.path) else {
+            return Vec::new();
+        };
+        if file.seek(std::io::SeekFrom::Start(self.offset)).is_err() {
+            return Vec::new();
+        }
+        let mut reader = std::io::BufReader::new(file);
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        while let Ok(n) = reader.read_line(&mut line) {
+            if n == 0 || !line.ends_with('\n') {
+                break;
+            }
+            self.offset += n as u64;
+            lines.push(line.trim_end().to_string());
+            line.clear();
+        }
+        lines
+    }
+}
+
+struct Tracker {
+    frames_dir: PathBuf,
+    config: DiffConfig,
+    /// Captured window/area in screen coordinates (`[left, top, right, bottom]`)
+    region: Option<[f64; 4]>,
+    /// Screen → frame mapping, known once the first frame has been read
+    mapping: Option<CaptureMapping>,
+    frame_w: u32,
+    frame_h: u32,
+    /// Last sampled frame: (index, downsampled image, capture time)
+    prev: Option<(u64, GrayImage, u64)>,
+    next_index: u64,
+    frame_times: Vec<u64>,
+    timestamps: Tail,
+    events: Tail,
+    cursor: Vec<(u64, f64, f64)>,
+}
+
+impl Tracker {
+    fn new(output_dir: &Path, config: DiffConfig, region: Option<[f64; 4]>) -> Self {
+        Self {
+            frames_dir: output_dir.join("frames"),
+            config,
+            region,
+            mapping: None,
+            frame_w: 0,
+            frame_h: 0,
+            prev: None,
+            next_index: 0,
+            frame_times: Vec::new(),
+            timestamps: Tail::new(output_dir.join(FRAME_TIMESTAMPS_FILE)),
+            events: Tail::new(output_dir.join("events.jsonl")),
+            cursor: Vec::new(),
+        }
+    }
+
+    fn refresh_inputs(&mut self) {
+        self.frame_times.extend(self.timestamps.read_new_lines().iter().filter_map(|l| l.parse::<u64>().ok()));
+        for line in self.events.read_new_lines() {
+            match serde_json::from_str::<RecordingEvent>(&line) {
+                Ok(RecordingEvent::MouseMove { t, x, y }) | Ok(RecordingEvent::Click { t, x, y, .. }) => {
+                    self.cursor.push((t, x, y))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Capture time of frame `idx`; evenly spaced at the nominal rate when
+    /// the timestamp isn't known.
+    fn frame_time(&self, idx: u64) -> u64 {
+        self.frame_times.get(idx as usize).copied().unwrap_or(idx * 33)
+    }
+
+    /// Compare every sampled frame that is safe to read. `frame_count` is
+    /// the final count once capture has stopped.
+    fn process(&mut self, frame_count: Option<u64>, out: &mut impl Write) -> Result<()> {
+        self.refresh_inputs();
+        loop {
+            let idx = self.next_index;
+            let ready = match frame_count {
+                Some(count) => idx < count,
+                None => frame_path(&self.frames_dir, idx + 1).is_some(),
+            };
+            let Some(path) = frame_path(&self.frames_dir, idx).filter(|_| ready) else {
+                break;
+            };
+            self.next_index += self.config.sample_interval.max(1);
+
+            let Ok(img) = frame_differ::load_downsampled_gray(&path, self.config.downsample_factor) else {
+                continue;
+            };
+            let mapping = match self.mapping {
+                Some(mapping) => mapping,
+                None => {
+                    let Ok((w, h)) = image::image_dimensions(&path) else {
+                        continue;
+                    };
+                    (self.frame_w, self.frame_h) = (w, h);
+                    let mapping = match self.region {
+                        Some([left, top, right, _]) if right > left => {
+                            CaptureMapping { origin_x: left, origin_y: top, scale: w as f64 / (right - left) }
+                        }
+                        _ => CaptureMapping::IDENTITY,
+                    };
+                    *self.mapping.insert(mapping)
+                }
+            };
+            let time = self.frame_time(idx);
+            if let Some((prev_idx, prev_img, prev_time)) = self.prev.take() {
+                let to_frame = |p: Option<(f64, f64)>| p.map(|(x, y)| mapping.screen_to_frame(x, y));
+                let cursor_a = to_frame(frame_differ::find_cursor_nearest(&self.cursor, prev_time));
+                let cursor_b = to_frame(frame_differ::find_cursor_nearest(&self.cursor, time));
+                let region = frame_differ::compute_pair_diff(
+                    &prev_img,
+                    &img,
+                    cursor_a,
+                    cursor_b,
+                    &self.config,
+                    self.frame_w,
+                    self.frame_h,
+                );
+                let pair = RecordedPair {
+                    from: prev_idx,
+                    to: idx,
+                    changed_pixel_count: region.as_ref().map_or(0, |(_, count)| *count),
+                    bbox: region.map(|(bbox, _)| bbox),
+                };
+                writeln!(out, "{}", serde_json::to_string(&pair)?)?;
+            }
+            self.prev = Some((idx, img, time));
+            self.cursor.retain(|&(t, _, _)| t + CURSOR_HISTORY_MS >= time);
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
+
+fn frame_path(frames_dir: &Path, idx: u64) -> Option<PathBuf> {
+    ["jpg", "png"]
+        .iter()
+        .map(|ext| frames_dir.join(format!("frame_{:08}.{}", idx, ext)))
+        .find(|p| p.exists())
+}
+
+/// Compare frames as they are captured until `is_running` is cleared, then
+/// finish the remaining frames once the capture thread has stopped.
+/// `region` is the captured window or area (`None` for the full screen).
+pub fn track_changes(
+    is_running: Arc<AtomicBool>,
+    output_dir: &Path,
+    config: DiffConfig,
+    region: Option<[f64; 4]>,
+) -> Result<()> {
+    let mut out = std::io::BufWriter::new(std::fs::File::create(output_dir.join(frame_differ::CHANGES_FILE))?);
+    writeln!(out, "{}", serde_json::to_string(&config)?)?;
+    let mut tracker = Tracker::new(output_dir, config, region);
+
+    while is_running.load(Ordering::SeqCst) {
+        std::thread::sleep(std::time::Duration::from_millis(POLL_INTERVAL_MS));
+        tracker.process(None, &mut out)?;
+    }
+
+    // Capture writes frame_count.txt as its last step
+    let count_path = output_dir.join("frame_count.txt");
+    let waited = std::time::Instant::now();
+    while !count_path.exists() && waited.elapsed().as_millis() < FINAL_COUNT_WAIT_MS as u128 {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    let frame_count = std::fs::read_to_string(&count_path)
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok());
+    match frame_count {
+        Some(count) => tracker.process(Some(count), &mut out)?,
+        // Without the final count the file stays incomplete and is ignored
+        None => log::warn!("Frame count not available; live frame diff left incomplete"),
+    }
+    log::info!("Live frame diff stopped after frame {}", tracker.next_index);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::frame_index::FrameIndex;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn test_recorded_changes_match_post_hoc_pass() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let frames = dir.path().join("frames");
+        std::fs::create_dir_all(&frames).unwrap();
+        // A block appears in frame 6 and stays
+        let save_frame = |idx: u64| {
+            let img = RgbImage::from_fn(320, 180, |x, y| {
+                if idx >= 6 && (100..200).contains(&x) && (60..120).contains(&y) {
+                    Rgb([20, 20, 200])
+                } else {
+                    Rgb([240, 240, 240])
+                }
+            });
+            img.save(frames.join(format!("frame_{:08}.png", idx))).unwrap();
+        };
+        (0..11).for_each(save_frame);
+        let config = DiffConfig { cursor_exclude_radius: 0, ..DiffConfig::default() };
+
+        let mut out = Vec::new();
+        writeln!(out, "{}", serde_json::to_string(&config).unwrap()).unwrap();
+        let mut tracker = Tracker::new(dir.path(), config.clone(), None);
+        tracker.process(None, &mut out).unwrap();
+        // Frame 10 has no successor yet while recording
+        assert_eq!(tracker.next_index, 10);
+        save_frame(11);
+        tracker.process(Some(12), &mut out).unwrap();
+        std::fs::write(dir.path().join(frame_differ::CHANGES_FILE), &out).unwrap();
+
+        let mapping = CaptureMapping::IDENTITY;
+        let index = FrameIndex::new(12, None, 400, 30);
+        let live = frame_differ::load_recorded_changes(dir.path(), &index, &mapping, &config).unwrap();
+        let post = frame_differ::detect_frame_changes(&frames, &index, &[], 320, 180, &mapping, &config).unwrap();
+        assert_eq!(live.pairs_analyzed, post.pairs_analyzed);
+        assert_eq!(live.regions.len(), 1);
+        assert_eq!(live.regions[0].time_ms, post.regions[0].time_ms);
+        assert_eq!(live.regions[0].bbox.x, post.regions[0].bbox.x);
+
+        // A stricter config or more frames than recorded: not usable
+        let stricter = DiffConfig { pixel_threshold: 40, ..config.clone() };
+        assert!(frame_differ::load_recorded_changes(dir.path(), &index, &mapping, &stricter).is_none());
+        let longer = FrameIndex::new(20, None, 700, 30);
+        assert!(frame_differ::load_recorded_changes(dir.path(), &longer, &mapping, &config).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+//! Periodic checkpoints of a running recording's metadata.
+//!
+//! `meta.json` and `frame_count.txt` were only written at `stop()`. If the
+//! app crashes before that, the frames are on disk but their count and the
+//! recorded duration would be lost. While recording, a checkpoint thread
+//! rewrites `meta.json` every [`CHECKPOINT_INTERVAL`] with the frames
+//! captured so far and the clock's time, marked `in_progress`; readers take
+//! the count from `meta.json` before `frame_count.txt`. The latter stays
+//! the capture thread's final word (the live frame differ waits for it), so
+//! it is only written at the end. Both are replaced by atomic rename, so a
+//! crash never leaves a half-written file behind.
+
+use crate::config::RecordingMeta;
+use anyhow::Result;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often the thread checks `is_running` between checkpoints
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Write `contents` to `path` through a temporary file in the same
+/// directory, replacing it in one step.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let temp = path.with_file_name(format!(".{}.tmp", file_name));
+    std::fs::write(&temp, contents)?;
+    if let Err(e) = std::fs::rename(&temp, path) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// `meta` as of now: frame count, duration and pauses from the running
+/// recording, and the capture size once the capture thread has written it.
+pub fn checkpoint_meta(
+    meta: &RecordingMeta,
+    dir: &Path,
+    frame_count: u64,
+    clock: &super::clock::RecordingClock,
+) -> RecordingMeta {
+    let mut meta = meta.clone();
+    if let Some((width, height)) = std::fs::read_to_string(dir.join("dimensions.txt"))
+        .ok()
+        .and_then(|dims| {
+            let (w, h) = dims.trim().split_once('x')?;
+            Some((w.parse().ok()?, h.parse().ok()?))
+        })
+    {
+        meta.screen_width = width;
+        meta.screen_height = height;
+    }
+    meta.duration_ms = clock.now_ms();
+    meta.frame_count = Some(frame_count as u32);
+    meta.pauses = clock.pauses();
+    meta.in_progress = true;
+    meta
+}
+
+/// Write one checkpoint into `dir`.
+pub fn write_checkpoint(meta: &RecordingMeta, dir: &Path) -> Result<()> {
+    write_atomic(&dir.join("meta.json"), serde_json::to_string_pretty(meta)?.as_bytes())
+}
+
+/// Checkpoint `meta` every [`CHECKPOINT_INTERVAL`] until `is_running` is
+/// cleared. `frame_count` is the capture's running count
+/// ([`super::frame_sink::FrameRecorder::counter`]).
+pub fn run(
+    is_running: Arc<AtomicBool>,
+    dir: &Path,
+    meta: RecordingMeta,
+    frame_count: Arc<AtomicU64>,
+    clock: Arc<super::clock::RecordingClock>,
+) {
+    let mut since_checkpoint = Duration::ZERO;
+    while is_running.load(Ordering::SeqCst) {
+        std::thread::sleep(POLL_INTERVAL);
+        since_checkpoint += POLL_INTERVAL;
+        if since_checkpoint < CHECKPOINT_INTERVAL || !is_running.load(Ordering::SeqCst) {
+            continue;
+        }
+        since_checkpoint = Duration::ZERO;
+        let checkpoint = checkpoint_meta(&meta, dir, frame_count.load(Ordering::SeqCst), &clock);
+        if let Err(e) = write_checkpoint(&checkpoint, dir) {
+            log::warn!("Failed to checkpoint recording {}: {}", meta.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_writes_counts_and_size() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("dimensions.txt"), "1280x720").unwrap();
+        let meta: RecordingMeta = serde_json::from_str(
+            r#"{"version":3,"id":"r","screen_width":0,"screen_height":0,"fps":30,"start_time":"",
+                "duration_ms":0,"has_audio":false,"monitor_scale":1.0,"recording_dir":""}"#,
+        )
+        .unwrap();
+        let clock = crate::recording::clock::RecordingClock::started();
+
+        let checkpoint = checkpoint_meta(&meta, dir.path(), 42, &clock);
+        write_checkpoint(&checkpoint, dir.path()).unwrap();
+
+        let saved: RecordingMeta =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("meta.json")).unwrap()).unwrap();
+        assert_eq!((saved.screen_width, saved.screen_height, saved.frame_count), (1280, 720, Some(42)));
+        assert!(saved.in_progress);
+        // No temporary file is left, and frame_count.txt is left to the capture
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+}
@@ -0,0 +1,121 @@
+//! The clock every recording thread timestamps with.
+//!
+//! Frames (`frame_timestamps.txt`), input events, window focus and UI
+//! events all store milliseconds on this clock. It stands still while the
+//! recording is paused, like the audio track, so after a resume the
+//! timestamps continue where they stopped instead of jumping by the paused
+//! time. Where the pauses were is kept as [`PauseMarker`]s (saved in
+//! `meta.json`) so the export and the Timeline can treat them as cuts in
+//! the footage.
+
+use crate::config::PauseMarker;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+#[derive(Default)]
+struct ClockState {
+    started: Option<Instant>,
+    /// Paused time before the current pause
+    paused_ms: u64,
+    /// Start of the current pause
+    paused_at: Option<Instant>,
+    pauses: Vec<PauseMarker>,
+}
+
+impl ClockState {
+    fn now_ms(&self) -> u64 {
+        let Some(started) = self.started else {
+            return 0;
+        };
+        // While paused the clock stays at the pause point
+        let wall = self.paused_at.unwrap_or_else(Instant::now);
+        (wall.duration_since(started).as_millis() as u64).saturating_sub(self.paused_ms)
+    }
+}
+
+#[derive(Default)]
+pub struct RecordingClock {
+    state: Mutex<ClockState>,
+}
+
+impl RecordingClock {
+    /// A clock that is not running yet (reads 0 until [`Self::start`]).
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// A clock started now.
+    pub fn started() -> Arc<Self> {
+        let clock = Self::new();
+        clock.start();
+        clock
+    }
+
+    pub fn start(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            *state = ClockState { started: Some(Instant::now()), ..Default::default() };
+        }
+    }
+
+    /// Recording time (ms): time since start, pauses excluded.
+    pub fn now_ms(&self) -> u64 {
+        self.state.lock().map(|s| s.now_ms()).unwrap_or(0)
+    }
+
+    pub fn pause(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            if state.paused_at.is_none() {
+                state.paused_at = Some(Instant::now());
+            }
+        }
+    }
+
+    pub fn resume(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            if let Some(paused_at) = state.paused_at {
+                let at_ms = state.now_ms();
+                let duration_ms = paused_at.elapsed().as_millis() as u64;
+                state.paused_at = None;
+                state.paused_ms += duration_ms;
+                state.pauses.push(PauseMarker { at_ms, duration_ms });
+            }
+        }
+    }
+
+    /// The pauses so far, including the current one (up to now).
+    pub fn pauses(&self) -> Vec<PauseMarker> {
+        let Ok(state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let mut pauses = state.pauses.clone();
+        if let Some(paused_at) = state.paused_at {
+            pauses.push(PauseMarker { at_ms: state.now_ms(), duration_ms: paused_at.elapsed().as_millis() as u64 });
+        }
+        pauses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_clock_stands_still_while_paused() {
+        let clock = RecordingClock::started();
+        std::thread::sleep(Duration::from_millis(50));
+        clock.pause();
+        let at_pause = clock.now_ms();
+        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(clock.now_ms(), at_pause);
+        clock.resume();
+        std::thread::sleep(Duration::from_millis(20));
+
+        let now = clock.now_ms();
+        assert!(now >= at_pause + 20 && now < at_pause + 90, "{} after pause at {}", now, at_pause);
+        let pauses = clock.pauses();
+        assert_eq!(pauses.len(), 1);
+        assert_eq!(pauses[0].at_ms, at_pause);
+        assert!(pauses[0].duration_ms >= 100);
+    }
+}
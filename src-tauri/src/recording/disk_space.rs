@@ -0,0 +1,125 @@
+//! Free disk space checks while recording.
+//!
+//! Frames are written continuously (tens of MB per minute on large
+//! screens), so a long recording can fill the drive and lose everything
+//! captured so far. The session checks the recording drive before starting
+//! and every few seconds while capturing: below twice the configured
+//! minimum (`RecordingSettings::min_free_disk_mb`) the user is warned once,
+//! below the minimum the recording is stopped while there is still room to
+//! finalize it.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Interval between checks while recording
+const CHECK_INTERVAL_MS: u64 = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DiskSpaceLevel {
+    Ok,
+    /// Below twice the minimum: the recording continues
+    Low,
+    /// Below the minimum: the recording is stopped
+    Critical,
+}
+
+/// Payload of the `recording-warning` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskSpaceWarning {
+    pub level: DiskSpaceLevel,
+    pub free_mb: u64,
+    pub min_free_mb: u64,
+}
+
+/// Receives low disk space warnings while recording
+pub type WarningSink = Arc<dyn Fn(DiskSpaceWarning) + Send + Sync>;
+
+/// Free space (bytes) available to this user on the drive holding `path`,
+/// or `None` if it can't be queried.
+#[cfg(windows)]
+pub fn free_bytes(path: &Path) -> Option<u64> {
+    use windows::core::HSTRING;
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let mut free = 0u64;
+    unsafe { GetDiskFreeSpaceExW(&HSTRING::from(path.as_os_str()), Some(&mut free as *mut u64), None, None) }.ok()?;
+    Some(free)
+}
+
+#[cfg(not(windows))]
+pub fn free_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// How `free_mb` compares to the `min_free_mb` threshold (0 = no limit).
+pub fn level(free_mb: u64, min_free_mb: u64) -> DiskSpaceLevel {
+    if min_free_mb == 0 {
+        DiskSpaceLevel::Ok
+    } else if free_mb < min_free_mb {
+        DiskSpaceLevel::Critical
+    } else if free_mb < min_free_mb.saturating_mul(2) {
+        DiskSpaceLevel::Low
+    } else {
+        DiskSpaceLevel::Ok
+    }
+}
+
+fn free_mb(dir: &Path) -> Option<u64> {
+    free_bytes(dir).map(|b| b / (1024 * 1024))
+}
+
+/// Refuse to start a recording in `dir` when the drive is already below
+/// the minimum.
+pub fn check_before_start(dir: &Path, min_free_mb: u64) -> Result<()> {
+    if let Some(free) = free_mb(dir) {
+        if level(free, min_free_mb) == DiskSpaceLevel::Critical {
+            bail!("Not enough disk space to record: {} MB free, at least {} MB required", free, min_free_mb);
+        }
+    }
+    Ok(())
+}
+
+/// Check the drive holding `dir` until `running` is cleared. `on_warning`
+/// is called each time the level gets worse; after a `Critical` warning
+/// the watch ends (the callback is expected to stop the recording).
+pub fn watch(running: Arc<AtomicBool>, dir: &Path, min_free_mb: u64, on_warning: WarningSink) {
+    let mut reported = DiskSpaceLevel::Ok;
+    let mut waited_ms = CHECK_INTERVAL_MS;
+    while running.load(Ordering::SeqCst) {
+        if waited_ms < CHECK_INTERVAL_MS {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            waited_ms += 100;
+            continue;
+        }
+        waited_ms = 0;
+
+        let Some(free) = free_mb(dir) else {
+            continue;
+        };
+        let current = level(free, min_free_mb);
+        if current > reported {
+            log::warn!("Low disk space while recording: {} MB free ({:?})", free, current);
+            reported = current;
+            on_warning(DiskSpaceWarning { level: current, free_mb: free, min_free_mb });
+            if current == DiskSpaceLevel::Critical {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_thresholds() {
+        assert_eq!(level(5000, 1024), DiskSpaceLevel::Ok);
+        assert_eq!(level(2047, 1024), DiskSpaceLevel::Low);
+        assert_eq!(level(1023, 1024), DiskSpaceLevel::Critical);
+        assert_eq!(level(0, 0), DiskSpaceLevel::Ok);
+    }
+}
@@ -0,0 +1,86 @@
+//! Enforcement of the maximum recording length
+//! (`RecordingSettings::max_duration_seconds`).
+//!
+//! The session watches the effective duration (pauses excluded). The user
+//! is warned 60 and 10 seconds before the limit, and at the limit the
+//! recording is stopped and finalized like a manual stop.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Seconds before the limit at which a warning is sent
+const WARN_BEFORE_SECONDS: [u64; 2] = [60, 10];
+
+/// Interval between checks while recording
+const CHECK_INTERVAL_MS: u64 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LimitNotice {
+    /// The recording will be stopped in `remaining_seconds`
+    Warning { remaining_seconds: u64 },
+    /// The limit was reached: the recording is stopped
+    Reached { limit_seconds: u64 },
+}
+
+/// Receives limit notices while recording
+pub type LimitSink = Arc<dyn Fn(LimitNotice) + Send + Sync>;
+
+/// The notice due `elapsed_ms` into a recording limited to `limit_seconds`,
+/// if any. `warned` counts the warnings already sent; warnings passed
+/// together (or not shorter than the limit itself) are folded into the
+/// last one.
+pub fn due_notice(limit_seconds: u64, elapsed_ms: u64, warned: &mut usize) -> Option<LimitNotice> {
+    let limit_ms = limit_seconds * 1000;
+    if elapsed_ms >= limit_ms {
+        return Some(LimitNotice::Reached { limit_seconds });
+    }
+    let mut notice = None;
+    while let Some(&before) = WARN_BEFORE_SECONDS.get(*warned) {
+        if elapsed_ms + before * 1000 < limit_ms {
+            break;
+        }
+        *warned += 1;
+        if before < limit_seconds {
+            notice = Some(LimitNotice::Warning { remaining_seconds: before });
+        }
+    }
+    notice
+}
+
+/// Check `elapsed_ms()` against the limit until `running` is cleared.
+/// After `Reached` the watch ends (the sink is expected to stop the
+/// recording).
+pub fn watch(running: Arc<AtomicBool>, limit_seconds: u64, elapsed_ms: impl Fn() -> u64, sink: LimitSink) {
+    let mut warned = 0;
+    while running.load(Ordering::SeqCst) {
+        if let Some(notice) = due_notice(limit_seconds, elapsed_ms(), &mut warned) {
+            sink(notice);
+            if matches!(notice, LimitNotice::Reached { .. }) {
+                log::info!("Recording reached its {} s limit", limit_seconds);
+                return;
+            }
+        }
+        std::thread::sleep(std::time::Duration::from_millis(CHECK_INTERVAL_MS));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warnings_then_reached() {
+        let mut warned = 0;
+        assert_eq!(due_notice(300, 100_000, &mut warned), None);
+        assert_eq!(due_notice(300, 240_000, &mut warned), Some(LimitNotice::Warning { remaining_seconds: 60 }));
+        assert_eq!(due_notice(300, 250_000, &mut warned), None);
+        assert_eq!(due_notice(300, 290_500, &mut warned), Some(LimitNotice::Warning { remaining_seconds: 10 }));
+        assert_eq!(due_notice(300, 300_000, &mut warned), Some(LimitNotice::Reached { limit_seconds: 300 }));
+
+        // A 30 s limit only gets the 10 s warning
+        let mut warned = 0;
+        assert_eq!(due_notice(30, 0, &mut warned), None);
+        assert_eq!(due_notice(30, 20_000, &mut warned), Some(LimitNotice::Warning { remaining_seconds: 10 }));
+    }
+}
@@ -48,7 +48,8 @@ mod win_hooks {
 
     pub struct HookSharedState {
         pub events: std::sync::Mutex<Vec<RecordingEvent>>,
-        pub start_time: Instant,
+        /// セッションの時計（一時停止中は進まない）
+        pub clock: Arc<crate::recording::clock::RecordingClock>,
         pub last_mouse_time: std::sync::Mutex<Instant>,
         pub modifier_state: AtomicU8,
         pub is_running: Arc<AtomicBool>,
@@ -95,7 +96,7 @@ mod win_hooks {
                 }
 
                 let mouse = &*(lparam.0 as *const MSLLHOOKSTRUCT);
-                let t = state.start_time.elapsed().as_millis() as u64;
+                let t = state.clock.now_ms();
                 let x = mouse.pt.x as f64;
                 let y = mouse.pt.y as f64;
 
@@ -209,7 +210,7 @@ mod win_hooks {
                 }
 
                 let kb = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
-                let t = state.start_time.elapsed().as_millis() as u64;
+                let t = state.clock.now_ms();
                 let vk = kb.vkCode;
 
                 match wparam.0 as u32 {
@@ -328,11 +329,116 @@ mod win_hooks {
     }
 }
 
+/// Check that the low-level mouse/keyboard hooks can be installed (they fail
+/// e.g. under some security software or without a desktop session). The
+/// hooks are removed again right away.
+#[cfg(windows)]
+pub fn test_input_hooks() -> Result<()> {
+    use windows::Win32::Foundation::HINSTANCE;
+    use windows::Win32::UI::WindowsAndMessaging::*;
+
+    unsafe {
+        let mouse_hook = SetWindowsHookExW(WH_MOUSE_LL, Some(win_hooks::mouse_hook_proc), HINSTANCE::default(), 0)
+            .map_err(|e| anyhow::anyhow!("Mouse hook failed: {}", e))?;
+        let _ = UnhookWindowsHookEx(mouse_hook);
+        let kb_hook = SetWindowsHookExW(WH_KEYBOARD_LL, Some(win_hooks::keyboard_hook_proc), HINSTANCE::default(), 0)
+            .map_err(|e| anyhow::anyhow!("Keyboard hook failed: {}", e))?;
+        let _ = UnhookWindowsHookEx(kb_hook);
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn test_input_hooks() -> Result<()> {
+    Err(anyhow::anyhow!("Input hooks are only supported on Windows"))
+}
+
+/// The user's pointer size relative to the default. Reads the accessibility
+/// pointer size (`HKCU\Control Panel\Cursors\CursorBaseSize`), falling back
+/// to the `SM_CXCURSOR` metric when the value is missing.
+#[cfg(windows)]
+pub fn os_cursor_scale() -> f64 {
+    use windows::core::w;
+    use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+    use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXCURSOR};
+
+    // Default pointer size (the first step of the pointer size slider), which
+    // the compositor's cursor sprite is drawn for
+    const DEFAULT_CURSOR_SIZE: f64 = 32.0;
+
+    let mut base_size: u32 = 0;
+    let mut len = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            w!("Control Panel\\Cursors"),
+            w!("CursorBaseSize"),
+            RRF_RT_REG_DWORD,
+            None,
+            Some(&mut base_size as *mut u32 as *mut std::ffi::c_void),
+            Some(&mut len),
+        )
+    };
+    let size = if status.is_ok() && base_size > 0 {
+        base_size as f64
+    } else {
+        unsafe { GetSystemMetrics(SM_CXCURSOR) as f64 }
+    };
+    if size <= 0.0 {
+        return 1.0;
+    }
+    let scale = (size / DEFAULT_CURSOR_SIZE).clamp(1.0, 8.0);
+    log::info!("OS cursor size: {}px (scale {:.2})", size, scale);
+    scale
+}
+
+#[cfg(not(windows))]
+pub fn os_cursor_scale() -> f64 {
+    1.0
+}
+
+/// Display scaling (1.0 = 100%, 1.5 = 150%) of the monitor being recorded:
+/// the primary monitor for display recordings, the window's monitor, or the
+/// monitor under the area's top-left corner.
+#[cfg(windows)]
+pub fn monitor_scale(mode: &crate::config::RecordingMode) -> f64 {
+    use crate::config::RecordingMode;
+    use windows::Win32::Foundation::{HWND, POINT};
+    use windows::Win32::Graphics::Gdi::{
+        MonitorFromPoint, MonitorFromWindow, MONITOR_DEFAULTTONEAREST, MONITOR_DEFAULTTOPRIMARY,
+    };
+    use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+
+    const DEFAULT_DPI: f64 = 96.0;
+
+    let monitor = unsafe {
+        match mode {
+            RecordingMode::Display => MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY),
+            RecordingMode::Window { hwnd, .. } => MonitorFromWindow(HWND(*hwnd as *mut _), MONITOR_DEFAULTTONEAREST),
+            RecordingMode::Area { x, y, .. } => MonitorFromPoint(POINT { x: *x, y: *y }, MONITOR_DEFAULTTONEAREST),
+        }
+    };
+    let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+    if unsafe { GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) }.is_err() || dpi_x == 0 {
+        return 1.0;
+    }
+    let scale = dpi_x as f64 / DEFAULT_DPI;
+    log::info!("Monitor DPI: {} (scale {:.2})", dpi_x, scale);
+    scale
+}
+
+#[cfg(not(windows))]
+pub fn monitor_scale(_mode: &crate::config::RecordingMode) -> f64 {
+    1.0
+}
+
 pub fn collect_events(
     is_running: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
+    clock: Arc<super::clock::RecordingClock>,
     output_dir: &Path,
     record_key_labels: bool,
+    live: Option<Arc<super::live_feed::LiveFeed>>,
 ) -> Result<()> {
     let events_path = output_dir.join("events.jsonl");
     let mut file = std::fs::File::create(&events_path)?;
@@ -351,7 +457,7 @@ pub fn collect_events(
 
         let shared = Arc::new(win_hooks::HookSharedState {
             events: std::sync::Mutex::new(Vec::new()),
-            start_time: Instant::now(),
+            clock,
             last_mouse_time: std::sync::Mutex::new(Instant::now()),
             modifier_state: AtomicU8::new(0),
             is_running: is_running.clone(),
@@ -424,6 +530,9 @@ pub fn collect_events(
             std::thread::sleep(std::time::Duration::from_millis(100));
 
             let mut buffer = shared.events.lock().unwrap();
+            if let Some(ref live) = live {
+                live.push(&buffer);
+            }
             for event in buffer.drain(..) {
                 if let Ok(json) = serde_json::to_string(&event) {
                     let _ = writeln!(file, "{}", json);
@@ -442,6 +551,10 @@ pub fn collect_events(
 
         // 最終フラッシュ
         if let Ok(mut buffer) = shared.events.lock() {
+            if let Some(ref live) = live {
+                live.push(&buffer);
+                live.flush(true);
+            }
             for event in buffer.drain(..) {
                 if let Ok(json) = serde_json::to_string(&event) {
                     let _ = writeln!(file, "{}", json);
@@ -458,7 +571,7 @@ pub fn collect_events(
     #[cfg(not(windows))]
     {
         // Windows以外ではイベント収集なし
-        let _ = record_key_labels; // avoid unused variable warning
+        let _ = (clock, record_key_labels, live); // avoid unused variable warning
         while is_running.load(Ordering::SeqCst) {
             std::thread::sleep(std::time::Duration::from_millis(100));
         }
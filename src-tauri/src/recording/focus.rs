@@ -10,7 +10,9 @@ use std::sync::Arc;
 pub fn track_focus(
     is_running: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
+    clock: Arc<super::clock::RecordingClock>,
     output_dir: &Path,
+    live: Option<Arc<super::live_feed::LiveFeed>>,
 ) -> Result<()> {
     log::info!("Window focus tracking thread started");
 
@@ -24,7 +26,6 @@ pub fn track_focus(
 
         let events_path = output_dir.join("window_events.jsonl");
         let mut file = std::fs::File::create(&events_path)?;
-        let start_time = std::time::Instant::now();
         let mut last_hwnd: isize = 0;
 
         while is_running.load(Ordering::SeqCst) {
@@ -57,7 +58,7 @@ pub fn track_focus(
                 // Get window rect
                 let mut rect = RECT::default();
                 if GetWindowRect(hwnd, &mut rect).is_ok() {
-                    let t = start_time.elapsed().as_millis() as u64;
+                    let t = clock.now_ms();
                     let event = RecordingEvent::WindowFocus {
                         t,
                         title,
@@ -67,12 +68,16 @@ pub fn track_focus(
                             rect.right as f64,
                             rect.bottom as f64,
                         ],
+                        process: process_name(hwnd).unwrap_or_default(),
                     };
 
                     if let Ok(json) = serde_json::to_string(&event) {
                         let _ = writeln!(file, "{}", json);
                         let _ = file.flush();
                     }
+                    if let Some(ref live) = live {
+                        live.push(std::slice::from_ref(&event));
+                    }
                 }
             }
         }
@@ -80,9 +85,36 @@ pub fn track_focus(
 
     #[cfg(not(windows))]
     {
-        let _ = (is_running, is_paused, output_dir);
+        let _ = (is_running, is_paused, clock, output_dir, live);
     }
 
     log::info!("Window focus tracking stopped");
     Ok(())
 }
+
+/// Executable file name (e.g. "spotify.exe") of the process owning `hwnd`.
+#[cfg(windows)]
+fn process_name(hwnd: windows::Win32::Foundation::HWND) -> Option<String> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
+
+    unsafe {
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        if pid == 0 {
+            return None;
+        }
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buf = [0u16; 260];
+        let mut len = buf.len() as u32;
+        let queried = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(buf.as_mut_ptr()), &mut len);
+        let _ = CloseHandle(process);
+        queried.ok()?;
+        let path = String::from_utf16_lossy(&buf[..len as usize]);
+        std::path::Path::new(&path).file_name().map(|n| n.to_string_lossy().into_owned())
+    }
+}
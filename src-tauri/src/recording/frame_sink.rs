@@ -0,0 +1,266 @@
+//! Where captured frames go.
+//!
+//! The capture loops (GDI window / area / screen) only grab pixels and hand
+//! each frame to a [`FrameRecorder`], which passes it to a [`FrameSink`]
+//! and keeps the bookkeeping every recording needs: `frame_timestamps.txt`
+//! and `frame_count.txt`. New ways of storing frames (lossless video
+//! chunks, deduplication, a replay buffer) are added as sinks without
+//! touching the capture code.
+
+use crate::config::FrameStorage;
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// 録画フレーム保存に使う JPEG 品質。画面コンテンツ（テキスト/UI）は q=92 で
+/// 視覚的に PNG とほぼ区別が付かず、1920x1080 で PNG ~3-4MB から JPEG ~300KB 程度に。
+/// 最終出力は H.264 等で再圧縮されるため中間品質はこれで十分。
+const RECORDING_JPEG_QUALITY: u8 = 92;
+
+/// 画面キャプチャしたフレームを JPEG として保存するヘルパー。
+/// JPEG は RGBA を扱えないので RGB に変換して保存する（アルファは画面キャプチャに不要）。
+pub(crate) fn save_frame_as_jpeg(buffer: &[u8], width: u32, height: u32, path: &Path) -> std::io::Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut w = std::io::BufWriter::new(file);
+    encode_frame_jpeg(buffer, width, height, &mut w)
+}
+
+/// RGBA のフレームを録画と同じ品質の JPEG にして `out` へ書く
+pub(crate) fn encode_frame_jpeg(buffer: &[u8], width: u32, height: u32, out: &mut impl Write) -> std::io::Result<()> {
+    // RGBA → RGB（4 バイトごとに α を破棄）
+    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+    for chunk in buffer.chunks_exact(4) {
+        rgb.push(chunk[0]);
+        rgb.push(chunk[1]);
+        rgb.push(chunk[2]);
+    }
+
+    let mut enc = image::codecs::jpeg::JpegEncoder::new_with_quality(out, RECORDING_JPEG_QUALITY);
+    enc.encode(&rgb, width, height, image::ExtendedColorType::Rgb8)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    Ok(())
+}
+
+/// Destination of captured frames.
+pub trait FrameSink: Send {
+    /// Store frame number `index`: RGBA pixels, `width * height * 4` bytes.
+    fn write_frame(&mut self, index: u64, rgba: &[u8], width: u32, height: u32) -> Result<()>;
+
+    /// Flush once capture has stopped.
+    fn finish(self: Box<Self>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// One JPEG per frame: `frame_NNNNNNNN.jpg` in a directory.
+pub struct JpegDirSink {
+    dir: PathBuf,
+}
+
+impl JpegDirSink {
+    pub fn new(dir: &Path) -> Self {
+        Self { dir: dir.to_path_buf() }
+    }
+}
+
+impl FrameSink for JpegDirSink {
+    fn write_frame(&mut self, index: u64, rgba: &[u8], width: u32, height: u32) -> Result<()> {
+        let path = self.dir.join(format!("frame_{:08}.jpg", index));
+        Ok(save_frame_as_jpeg(rgba, width, height, &path)?)
+    }
+}
+
+/// All frames in `frames.pack` (see [`super::frame_store`]). The pack is
+/// created with the first frame's size; every frame must have that size.
+pub struct PackSink {
+    path: PathBuf,
+    writer: Option<super::frame_store::PackWriter>,
+}
+
+impl PackSink {
+    pub fn new(frames_dir: &Path) -> Self {
+        Self { path: frames_dir.join(super::frame_store::FRAME_PACK_FILE), writer: None }
+    }
+}
+
+impl FrameSink for PackSink {
+    fn write_frame(&mut self, _index: u64, rgba: &[u8], width: u32, height: u32) -> Result<()> {
+        let writer = match self.writer.as_mut() {
+            Some(writer) => writer,
+            None => self.writer.insert(super::frame_store::PackWriter::create(&self.path, width, height)?),
+        };
+        writer.write_frame(rgba)
+    }
+
+    fn finish(self: Box<Self>) -> Result<()> {
+        match self.writer {
+            Some(writer) => writer.finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The last `capacity` frames in memory, oldest first.
+pub struct MemorySink {
+    capacity: usize,
+    frames: VecDeque<(u64, image::RgbaImage)>,
+}
+
+impl MemorySink {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, frames: VecDeque::with_capacity(capacity) }
+    }
+
+    /// Held frames with their indices, oldest first.
+    pub fn frames(&self) -> impl Iterator<Item = &(u64, image::RgbaImage)> {
+        self.frames.iter()
+    }
+}
+
+impl FrameSink for MemorySink {
+    fn write_frame(&mut self, index: u64, rgba: &[u8], width: u32, height: u32) -> Result<()> {
+        let image = image::RgbaImage::from_raw(width, height, rgba.to_vec())
+            .ok_or_else(|| anyhow::anyhow!("Frame buffer does not match {}x{}", width, height))?;
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        if self.capacity > 0 {
+            self.frames.push_back((index, image));
+        }
+        Ok(())
+    }
+}
+
+/// The sink for a recording's `frames` directory in the configured storage.
+pub fn storage_sink(frames_dir: &Path, storage: FrameStorage) -> Box<dyn FrameSink> {
+    match storage {
+        FrameStorage::Jpeg => Box::new(JpegDirSink::new(frames_dir)),
+        FrameStorage::Packed => Box::new(PackSink::new(frames_dir)),
+    }
+}
+
+/// `frame_timestamps.txt` is flushed every this many frames, so a crash
+/// loses at most these timestamps
+const TIMESTAMP_FLUSH_FRAMES: u64 = 30;
+
+/// Feeds captured frames to a sink, numbering them and recording their
+/// capture times.
+pub struct FrameRecorder {
+    output_dir: PathBuf,
+    sink: Box<dyn FrameSink>,
+    /// 各フレーム保存時の録画時刻 ms（frame_timestamps.txt）
+    timestamps: Option<std::io::BufWriter<std::fs::File>>,
+    clock: Arc<super::clock::RecordingClock>,
+    /// Shared with the checkpoint thread (see [`super::checkpoint`])
+    frame_count: Arc<AtomicU64>,
+    /// A failing sink is logged once, not on every frame
+    error_logged: bool,
+}
+
+impl FrameRecorder {
+    /// Start recording frames of the recording in `output_dir` into `sink`,
+    /// timestamped with the session's `clock`.
+    pub fn new(output_dir: &Path, sink: Box<dyn FrameSink>, clock: Arc<super::clock::RecordingClock>) -> Self {
+        let timestamps = std::fs::File::create(output_dir.join(super::capture::FRAME_TIMESTAMPS_FILE))
+            .ok()
+            .map(std::io::BufWriter::new);
+        Self {
+            output_dir: output_dir.to_path_buf(),
+            sink,
+            timestamps,
+            clock,
+            frame_count: Arc::new(AtomicU64::new(0)),
+            error_logged: false,
+        }
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count.load(Ordering::SeqCst)
+    }
+
+    /// Current time on the session's recording clock (ms).
+    pub fn now_ms(&self) -> u64 {
+        self.clock.now_ms()
+    }
+
+    /// The running frame count, for reading from another thread.
+    pub fn counter(&self) -> Arc<AtomicU64> {
+        self.frame_count.clone()
+    }
+
+    /// Add a frame. A frame the sink fails to store is dropped like a
+    /// missed capture, so frame indices and capture times stay in step.
+    pub fn write(&mut self, rgba: &[u8], width: u32, height: u32) {
+        let index = self.frame_count();
+        if let Err(e) = self.sink.write_frame(index, rgba, width, height) {
+            if !self.error_logged {
+                log::warn!("Failed to store frame {}: {}", index, e);
+                self.error_logged = true;
+            }
+            return;
+        }
+        if let Some(w) = self.timestamps.as_mut() {
+            let _ = writeln!(w, "{}", self.clock.now_ms());
+            if (index + 1).is_multiple_of(TIMESTAMP_FLUSH_FRAMES) {
+                let _ = w.flush();
+            }
+        }
+        self.frame_count.store(index + 1, Ordering::SeqCst);
+    }
+
+    /// Flush the sink and timestamps and write `frame_count.txt`.
+    pub fn finish(self) -> Result<u64> {
+        let frame_count = self.frame_count();
+        if let Err(e) = self.sink.finish() {
+            log::error!("Failed to finish frame storage: {}", e);
+        }
+        if let Some(mut w) = self.timestamps {
+            let _ = w.flush();
+        }
+        super::checkpoint::write_atomic(&self.output_dir.join("frame_count.txt"), frame_count.to_string().as_bytes())?;
+        Ok(frame_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::capture::FRAME_TIMESTAMPS_FILE;
+    use crate::recording::frame_store::{PackReader, FRAME_PACK_FILE};
+
+    #[test]
+    fn test_recorder_numbers_frames_and_writes_counts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let frames_dir = dir.path().join("frames");
+        std::fs::create_dir_all(&frames_dir).unwrap();
+
+        let mut recorder = FrameRecorder::new(
+            dir.path(),
+            storage_sink(&frames_dir, FrameStorage::Packed),
+            crate::recording::clock::RecordingClock::started(),
+        );
+        let frame = vec![128u8; 4 * 4 * 4];
+        recorder.write(&frame, 4, 4);
+        recorder.write(&frame[..8], 4, 4); // wrong size: dropped
+        recorder.write(&frame, 4, 4);
+        assert_eq!(recorder.finish().unwrap(), 2);
+
+        let timestamps = std::fs::read_to_string(dir.path().join(FRAME_TIMESTAMPS_FILE)).unwrap();
+        assert_eq!(timestamps.lines().count(), 2);
+        assert_eq!(std::fs::read_to_string(dir.path().join("frame_count.txt")).unwrap(), "2");
+        assert_eq!(PackReader::open(&frames_dir.join(FRAME_PACK_FILE)).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_memory_sink_keeps_latest_frames() {
+        let mut sink = MemorySink::new(2);
+        for i in 0..3u8 {
+            sink.write_frame(i as u64, &[i; 4], 1, 1).unwrap();
+        }
+        let kept: Vec<u64> = sink.frames().map(|(i, _)| *i).collect();
+        assert_eq!(kept, vec![1, 2]);
+    }
+}
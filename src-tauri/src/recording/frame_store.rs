@@ -0,0 +1,324 @@
+//! Recorded frame storage.
+//!
+//! Frames are stored either as one JPEG per frame (`frames/frame_NNNNNNNN.jpg`,
+//! `.png` in old recordings) or packed into a single `frames/frames.pack`.
+//! Encoding a JPEG per frame is the slowest part of the capture loop and
+//! drops frames on large screens; the pack only LZ4-compresses the raw
+//! pixels, which is several times faster at the cost of more disk space.
+//!
+//! Pack layout (little endian):
+//! - header: `SNPFRAME`, version, width, height (u32 each)
+//! - one record per frame: compressed length (u32) + LZ4 block with the
+//!   uncompressed size prepended, RGBA pixels
+//! - footer, written when capture finishes: record offsets (u64 each),
+//!   frame count (u64), `SNPINDEX`
+//!
+//! A pack without footer (capture crashed) is still readable: the records
+//! are scanned up to the last complete one.
+//!
+//! Readers go through [`FrameSource`], which hides the two layouts.
+
+use anyhow::{bail, Context, Result};
+use image::RgbaImage;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Packed frames file inside the `frames` directory
+pub const FRAME_PACK_FILE: &str = "frames.pack";
+
+const MAGIC: &[u8; 8] = b"SNPFRAME";
+const INDEX_MAGIC: &[u8; 8] = b"SNPINDEX";
+const VERSION: u32 = 1;
+const HEADER_LEN: u64 = 20;
+
+/// Appends frames to a pack.
+pub struct PackWriter {
+    file: BufWriter<std::fs::File>,
+    width: u32,
+    height: u32,
+    offsets: Vec<u64>,
+    pos: u64,
+}
+
+impl PackWriter {
+    pub fn create(path: &Path, width: u32, height: u32) -> Result<Self> {
+        let mut file = BufWriter::new(std::fs::File::create(path)?);
+        file.write_all(MAGIC)?;
+        for v in [VERSION, width, height] {
+            file.write_all(&v.to_le_bytes())?;
+        }
+        Ok(Self { file, width, height, offsets: Vec::new(), pos: HEADER_LEN })
+    }
+
+    /// Continue an existing pack (its footer is dropped and rewritten by
+    /// [`PackWriter::finish`]).
+    pub fn append(path: &Path) -> Result<Self> {
+        let reader = PackReader::open(path)?;
+        let file = std::fs::OpenOptions::new().write(true).open(path)?;
+        file.set_len(reader.data_end)?;
+        let mut file = BufWriter::new(file);
+        file.seek(SeekFrom::Start(reader.data_end))?;
+        Ok(Self {
+            file,
+            width: reader.width,
+            height: reader.height,
+            offsets: reader.offsets,
+            pos: reader.data_end,
+        })
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Add a frame of RGBA pixels (`width * height * 4` bytes).
+    pub fn write_frame(&mut self, rgba: &[u8]) -> Result<()> {
+        if rgba.len() != (self.width * self.height * 4) as usize {
+            bail!("Frame size does not match the pack ({}x{})", self.width, self.height);
+        }
+        let block = lz4_flex::compress_prepend_size(rgba);
+        self.file.write_all(&(block.len() as u32).to_le_bytes())?;
+        self.file.write_all(&block)?;
+        self.offsets.push(self.pos);
+        self.pos += 4 + block.len() as u64;
+        Ok(())
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.offsets.len() as u64
+    }
+
+    /// Write the index footer and flush.
+    pub fn finish(mut self) -> Result<()> {
+        for offset in &self.offsets {
+            self.file.write_all(&offset.to_le_bytes())?;
+        }
+        self.file.write_all(&(self.offsets.len() as u64).to_le_bytes())?;
+        self.file.write_all(INDEX_MAGIC)?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+/// Random access to the frames of a pack. Reads can run on several
+/// threads: only fetching the compressed bytes is serialized.
+pub struct PackReader {
+    file: Mutex<std::fs::File>,
+    width: u32,
+    height: u32,
+    offsets: Vec<u64>,
+    /// End of the last complete record (start of the footer)
+    data_end: u64,
+}
+
+impl PackReader {
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut file = std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let len = file.metadata()?.len();
+        let mut header = [0u8; HEADER_LEN as usize];
+        file.read_exact(&mut header).context("Frame pack too short")?;
+        if &header[0..8] != MAGIC {
+            bail!("Not a frame pack: {}", path.display());
+        }
+        let field = |i: usize| u32::from_le_bytes([header[i], header[i + 1], header[i + 2], header[i + 3]]);
+        if field(8) > VERSION {
+            bail!("Unsupported frame pack version {}", field(8));
+        }
+        let (width, height) = (field(12), field(16));
+
+        let (offsets, data_end) = match read_footer(&mut file, len)? {
+            Some(index) => index,
+            None => scan_records(&mut file, len)?,
+        };
+        Ok(Self { file: Mutex::new(file), width, height, offsets, data_end })
+    }
+
+    pub fn len(&self) -> u64 {
+        self.offsets.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub fn read(&self, idx: u64) -> Result<RgbaImage> {
+        let offset = *self.offsets.get(idx as usize).with_context(|| format!("Frame {} not in pack", idx))?;
+        let block = {
+            let mut file = self.file.lock().map_err(|_| anyhow::anyhow!("Frame pack lock poisoned"))?;
+            file.seek(SeekFrom::Start(offset))?;
+            let mut len = [0u8; 4];
+            file.read_exact(&mut len)?;
+            let mut block = vec![0u8; u32::from_le_bytes(len) as usize];
+            file.read_exact(&mut block)?;
+            block
+        };
+        let pixels = lz4_flex::decompress_size_prepended(&block).context("Corrupt frame in pack")?;
+        RgbaImage::from_raw(self.width, self.height, pixels).context("Frame size does not match the pack")
+    }
+}
+
+/// Record offsets and data end from the footer, if the pack has one. A
+/// footer whose frame count doesn't fit in the file is an error.
+fn read_footer(file: &mut std::fs::File, len: u64) -> Result<Option<(Vec<u64>, u64)>> {
+    if len < HEADER_LEN + 16 {
+        return Ok(None);
+    }
+    let mut tail = [0u8; 16];
+    file.seek(SeekFrom::Start(len - 16))?;
+    file.read_exact(&mut tail)?;
+    if &tail[8..16] != INDEX_MAGIC {
+        return Ok(None);
+    }
+    let count = u64::from_le_bytes(tail[0..8].try_into()?);
+    // The count comes from the file (possibly an imported archive): keep the whole bound checked
+    let index_len = count
+        .checked_mul(8)
+        .filter(|n| n.checked_add(16 + HEADER_LEN).is_some_and(|end| end <= len));
+    let Some(index_len) = index_len else {
+        bail!("Corrupt frame pack index ({} frames in {} bytes)", count, len);
+    };
+    let data_end = len - 16 - index_len;
+    let mut raw = vec![0u8; index_len as usize];
+    file.seek(SeekFrom::Start(data_end))?;
+    file.read_exact(&mut raw)?;
+    let offsets = raw.chunks_exact(8).map(|c| u64::from_le_bytes(c.try_into().unwrap())).collect();
+    Ok(Some((offsets, data_end)))
+}
+
+/// Walk the records of a pack without footer, stopping at the first
+/// incomplete one.
+fn scan_records(file: &mut std::fs::File, len: u64) -> Result<(Vec<u64>, u64)> {
+    let mut offsets = Vec::new();
+    let mut pos = HEADER_LEN;
+    let mut size = [0u8; 4];
+    while pos + 4 <= len {
+        file.seek(SeekFrom::Start(pos))?;
+        file.read_exact(&mut size)?;
+        let end = pos + 4 + u32::from_le_bytes(size) as u64;
+        if end > len {
+            break;
+        }
+        offsets.push(pos);
+        pos = end;
+    }
+    Ok((offsets, pos))
+}
+
+/// The recorded frames of a recording, whichever way they are stored.
+pub enum FrameSource {
+    /// One image file per frame
+    Files(PathBuf),
+    Pack(PackReader),
+}
+
+impl FrameSource {
+    /// Open the frames in `frames_dir` (the recording's `frames` directory).
+    pub fn open(frames_dir: &Path) -> Result<Self> {
+        let pack = frames_dir.join(FRAME_PACK_FILE);
+        if pack.exists() {
+            return Ok(Self::Pack(PackReader::open(&pack)?));
+        }
+        Ok(Self::Files(frames_dir.to_path_buf()))
+    }
+
+    /// Like [`FrameSource::open`], but an unreadable pack yields no frames
+    /// instead of an error (readers then skip the missing frames).
+    pub fn open_or_files(frames_dir: &Path) -> Self {
+        Self::open(frames_dir).unwrap_or_else(|e| {
+            log::warn!("{}", e);
+            Self::Files(frames_dir.to_path_buf())
+        })
+    }
+
+    pub fn is_packed(&self) -> bool {
+        matches!(self, Self::Pack(_))
+    }
+
+    pub fn contains(&self, idx: u64) -> bool {
+        match self {
+            Self::Files(dir) => crate::export::encoder::recording_frame_path(dir, idx).exists(),
+            Self::Pack(pack) => idx < pack.len(),
+        }
+    }
+
+    pub fn load(&self, idx: u64) -> Result<image::DynamicImage> {
+        match self {
+            Self::Files(dir) => Ok(image::open(crate::export::encoder::recording_frame_path(dir, idx))?),
+            Self::Pack(pack) => Ok(image::DynamicImage::ImageRgba8(pack.read(idx)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(w: u32, h: u32, shade: u8) -> Vec<u8> {
+        RgbaImage::from_fn(w, h, |x, _| image::Rgba([shade, x as u8, 0, 255])).into_raw()
+    }
+
+    #[test]
+    fn test_pack_roundtrip_and_append() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(FRAME_PACK_FILE);
+        let mut writer = PackWriter::create(&path, 16, 8).unwrap();
+        writer.write_frame(&frame(16, 8, 10)).unwrap();
+        writer.write_frame(&frame(16, 8, 20)).unwrap();
+        assert!(writer.write_frame(&frame(8, 8, 0)).is_err());
+        writer.finish().unwrap();
+
+        let mut writer = PackWriter::append(&path).unwrap();
+        writer.write_frame(&frame(16, 8, 30)).unwrap();
+        writer.finish().unwrap();
+
+        let reader = PackReader::open(&path).unwrap();
+        assert_eq!(reader.len(), 3);
+        assert_eq!(reader.read(1).unwrap().into_raw(), frame(16, 8, 20));
+        assert_eq!(reader.read(2).unwrap().get_pixel(3, 0)[0], 30);
+        assert!(reader.read(3).is_err());
+    }
+
+    #[test]
+    fn test_pack_without_footer_is_scanned() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(FRAME_PACK_FILE);
+        let mut writer = PackWriter::create(&path, 4, 4).unwrap();
+        for shade in [1, 2, 3] {
+            writer.write_frame(&frame(4, 4, shade)).unwrap();
+        }
+        // Simulate a crash: flushed records, no footer, a torn last record
+        writer.file.flush().unwrap();
+        drop(writer);
+        let len = std::fs::metadata(&path).unwrap().len();
+        std::fs::OpenOptions::new().write(true).open(&path).unwrap().set_len(len - 3).unwrap();
+
+        let source = FrameSource::open(dir.path()).unwrap();
+        assert!(source.is_packed());
+        assert!(source.contains(1) && !source.contains(2));
+        assert_eq!(source.load(1).unwrap().to_rgba8().get_pixel(0, 0)[0], 2);
+    }
+
+    #[test]
+    fn test_pack_with_corrupt_footer_count_is_refused() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(FRAME_PACK_FILE);
+        let mut writer = PackWriter::create(&path, 4, 4).unwrap();
+        writer.write_frame(&frame(4, 4, 1)).unwrap();
+        writer.finish().unwrap();
+        let len = std::fs::metadata(&path).unwrap().len();
+
+        // Counts whose index would overflow or not fit in the file
+        for count in [u64::MAX, u64::MAX / 8, 1000] {
+            let mut file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+            file.seek(SeekFrom::Start(len - 16)).unwrap();
+            file.write_all(&count.to_le_bytes()).unwrap();
+            drop(file);
+            assert!(PackReader::open(&path).is_err(), "count {}", count);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! The recordings library: user-editable details of a recording (title,
+//! tags, notes), the in-memory index behind the recordings list, and
+//! filtering, sorting and paging the list.
+//!
+//! The details are stored in the recording's `meta.json`, like its take
+//! group, so they move with the recording folder.
+//!
+//! The index keeps the list entry built from each `meta.json` together with
+//! the file's modification time, so listing only re-reads recordings that
+//! changed since the last call (plus those edited through this module,
+//! which invalidates them explicitly in case the timestamp resolution hides
+//! a quick rewrite).
+
+use crate::config::{RecordingFilter, RecordingInfo, RecordingMeta, RecordingPage, RecordingSort};
+use crate::recording::takes::{read_meta, write_meta};
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+struct IndexEntry {
+    modified: SystemTime,
+    in_progress: bool,
+    info: RecordingInfo,
+}
+
+/// List entries by recording ID
+static INDEX: Mutex<BTreeMap<String, IndexEntry>> = Mutex::new(BTreeMap::new());
+
+/// Largest page `page` returns
+pub const MAX_PAGE_SIZE: u32 = 200;
+
+/// The list entry of every recording in `base_dir` with its `in_progress`
+/// flag, building entries with `load` for new or changed `meta.json` files
+/// only. Folders without a readable `meta.json` are left out.
+pub fn indexed(
+    base_dir: &Path,
+    load: impl Fn(&Path, RecordingMeta) -> RecordingInfo,
+) -> Result<Vec<(RecordingInfo, bool)>> {
+    let mut index = INDEX.lock().unwrap_or_else(|e| e.into_inner());
+    let mut seen = std::collections::HashSet::new();
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(base_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let dir = entry.path();
+        let Ok(modified) = std::fs::metadata(dir.join("meta.json")).and_then(|m| m.modified()) else {
+            continue;
+        };
+        let id = entry.file_name().to_string_lossy().to_string();
+        let fresh = index.get(&id).is_some_and(|e| e.modified == modified);
+        if !fresh {
+            let Ok(content) = std::fs::read_to_string(dir.join("meta.json")) else {
+                continue;
+            };
+            let Ok(meta) = serde_json::from_str::<RecordingMeta>(&content) else {
+                index.remove(&id);
+                continue;
+            };
+            let in_progress = meta.in_progress;
+            index.insert(id.clone(), IndexEntry { modified, in_progress, info: load(&dir, meta) });
+        }
+        let entry = &index[&id];
+        out.push((entry.info.clone(), entry.in_progress));
+        seen.insert(id);
+    }
+    // Deleted or moved away
+    index.retain(|id, _| seen.contains(id));
+    Ok(out)
+}
+
+/// Drop a recording's index entry so the next listing re-reads it.
+pub fn invalidate(recording_id: &str) {
+    INDEX.lock().unwrap_or_else(|e| e.into_inner()).remove(recording_id);
+}
+
+/// Longest title and tag (in characters); longer ones are cut.
+const MAX_TITLE_CHARS: usize = 200;
+const MAX_TAG_CHARS: usize = 50;
+
+/// Apply `edit` to the meta of a stopped recording and save it.
+fn edit_meta(recording_id: &str, edit: impl FnOnce(&mut RecordingMeta)) -> Result<RecordingMeta> {
+    // Checkpoints of the running session rewrite meta.json
+    if crate::recording::session::active_recording_id().as_deref() == Some(recording_id) {
+        bail!("Recording {} is still in progress", recording_id);
+    }
+    let mut meta = read_meta(recording_id)?;
+    edit(&mut meta);
+    write_meta(&meta)?;
+    invalidate(recording_id);
+    Ok(meta)
+}
+
+/// Trimmed title; blank clears it.
+pub fn normalize_title(title: &str) -> Option<String> {
+    let title = title.trim();
+    (!title.is_empty()).then(|| title.chars().take(MAX_TITLE_CHARS).collect())
+}
+
+/// Trimmed, non-blank tags in their first order, without case-insensitive
+/// duplicates.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag: String = tag.trim().chars().take(MAX_TAG_CHARS).collect();
+        if !tag.is_empty() && !out.iter().any(|t| t.to_lowercase() == tag.to_lowercase()) {
+            out.push(tag);
+        }
+    }
+    out
+}
+
+/// Set the title (blank: back to showing the date). Returns the saved title.
+pub fn rename(recording_id: &str, title: &str) -> Result<Option<String>> {
+    Ok(edit_meta(recording_id, |meta| meta.title = normalize_title(title))?.title)
+}
+
+/// Replace the tags. Returns the saved (normalized) tags.
+pub fn set_tags(recording_id: &str, tags: &[String]) -> Result<Vec<String>> {
+    Ok(edit_meta(recording_id, |meta| meta.tags = normalize_tags(tags))?.tags)
+}
+
+pub fn set_notes(recording_id: &str, notes: &str) -> Result<()> {
+    edit_meta(recording_id, |meta| meta.notes = notes.trim_end().to_string())?;
+    Ok(())
+}
+
+/// Whether `recording` passes `filter`.
+pub fn matches(filter: &RecordingFilter, recording: &RecordingInfo) -> bool {
+    let has_tag = |wanted: &String| recording.tags.iter().any(|t| t.to_lowercase() == wanted.trim().to_lowercase());
+    if !filter.tags.iter().filter(|t| !t.trim().is_empty()).all(has_tag) {
+        return false;
+    }
+    let Some(query) = filter.query.as_deref().map(str::trim).filter(|q| !q.is_empty()) else {
+        return true;
+    };
+    let query = query.to_lowercase();
+    recording.title.iter().chain(&recording.tags).chain([&recording.notes]).any(|text| text.to_lowercase().contains(&query))
+}
+
+/// Order `recordings` for the list. [`RecordingSort::Newest`] expects the
+/// order of `list_recordings` (newest first, takes kept together) and keeps
+/// it; the others sort every recording on its own.
+pub fn sort_recordings(recordings: &mut [RecordingInfo], sort: RecordingSort) {
+    match sort {
+        RecordingSort::Newest => {}
+        RecordingSort::Oldest => recordings.sort_by(|a, b| a.date.cmp(&b.date)),
+        RecordingSort::Longest => recordings.sort_by_key(|r| std::cmp::Reverse(r.duration_ms)),
+        RecordingSort::Shortest => recordings.sort_by_key(|r| r.duration_ms),
+        // Untitled recordings after the titled ones, newest first
+        RecordingSort::Title => recordings.sort_by_cached_key(|r| match &r.title {
+            Some(title) => (false, title.to_lowercase(), std::cmp::Reverse(r.date.clone())),
+            None => (true, String::new(), std::cmp::Reverse(r.date.clone())),
+        }),
+    }
+}
+
+/// `limit` recordings (at most [`MAX_PAGE_SIZE`]) starting at `offset`, with
+/// the total count for the pager.
+pub fn page(recordings: Vec<RecordingInfo>, offset: u32, limit: u32) -> RecordingPage {
+    let total = recordings.len() as u32;
+    let recordings = recordings
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit.clamp(1, MAX_PAGE_SIZE) as usize)
+        .collect();
+    RecordingPage { recordings, total, offset }
+}
+
+/// Every tag in use, sorted case-insensitively (for tag suggestions).
+pub fn all_tags(recordings: &[RecordingInfo]) -> Vec<String> {
+    let tags: Vec<String> = recordings.iter().flat_map(|r| r.tags.iter().cloned()).collect();
+    let mut tags = normalize_tags(&tags);
+    tags.sort_by_key(|t| t.to_lowercase());
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(title: Option<&str>, tags: &[&str], notes: &str) -> RecordingInfo {
+        RecordingInfo {
+            id: title.unwrap_or("r").to_string(),
+            date: String::new(),
+            duration_ms: 0,
+            frame_count: 0,
+            thumbnail_path: None,
+            recording_dir: String::new(),
+            screen_width: 0,
+            screen_height: 0,
+            monitor_scale: 1.0,
+            take: None,
+            timelapse_speed: None,
+            title: title.map(str::to_string),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            notes: notes.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_and_filter() {
+        let tags = ["  demo ", "", "Demo", "bug-123"].map(String::from);
+        assert_eq!(normalize_tags(&tags), ["demo", "bug-123"]);
+        assert_eq!(normalize_title("  "), None);
+        assert_eq!(normalize_title(" Login flow "), Some("Login flow".to_string()));
+
+        let rec = info(Some("Login flow"), &["demo", "bug-123"], "Shows the crash on submit");
+        let filter = |query: Option<&str>, tags: &[&str]| RecordingFilter {
+            query: query.map(str::to_string),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        };
+        assert!(matches(&RecordingFilter::default(), &rec));
+        assert!(matches(&filter(Some("LOGIN"), &[]), &rec));
+        assert!(matches(&filter(Some("crash"), &["Demo"]), &rec));
+        assert!(matches(&filter(Some("bug"), &[]), &rec));
+        assert!(!matches(&filter(None, &["demo", "release"]), &rec));
+        assert!(!matches(&filter(Some("signup"), &[]), &rec));
+
+        let recs = [rec, info(None, &["Release", "Demo"], "")];
+        assert_eq!(all_tags(&recs), ["bug-123", "demo", "Release"]);
+    }
+
+    #[test]
+    fn test_sort_and_page() {
+        let rec = |title: Option<&str>, date: &str, duration_ms: u64| RecordingInfo {
+            date: date.to_string(),
+            duration_ms,
+            ..info(title, &[], "")
+        };
+        let ids = |recs: &[RecordingInfo]| recs.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+        let mut recs = vec![
+            rec(Some("b"), "2026-01-03", 5_000),
+            rec(None, "2026-01-02", 9_000),
+            rec(Some("A"), "2026-01-01", 1_000),
+        ];
+        sort_recordings(&mut recs, RecordingSort::Title);
+        assert_eq!(ids(&recs), ["A", "b", "r"]);
+        sort_recordings(&mut recs, RecordingSort::Longest);
+        assert_eq!(ids(&recs), ["r", "b", "A"]);
+        sort_recordings(&mut recs, RecordingSort::Oldest);
+        assert_eq!(ids(&recs), ["A", "r", "b"]);
+
+        let second = page(recs.clone(), 2, 2);
+        assert_eq!((ids(&second.recordings), second.total, second.offset), (vec!["b".to_string()], 3, 2));
+        assert!(page(recs, 5, 2).recordings.is_empty());
+    }
+
+    #[test]
+    fn test_index_rereads_changed_meta_only() {
+        let base = std::env::temp_dir().join(format!("snappi-library-{}", uuid::Uuid::new_v4()));
+        let write = |id: &str, title: &str| {
+            let dir = base.join(id);
+            std::fs::create_dir_all(&dir).unwrap();
+            let meta = serde_json::json!({
+                "version": 3, "id": id, "screen_width": 1, "screen_height": 1, "fps": 30,
+                "start_time": "2026-01-01T00:00:00Z", "duration_ms": 0, "has_audio": false,
+                "monitor_scale": 1.0, "recording_dir": dir, "title": title,
+            });
+            std::fs::write(dir.join("meta.json"), meta.to_string()).unwrap();
+        };
+        let loads = std::cell::Cell::new(0);
+        let load = |_: &Path, meta: RecordingMeta| {
+            loads.set(loads.get() + 1);
+            RecordingInfo { id: meta.id, title: meta.title, ..info(None, &[], "") }
+        };
+        write("idx-a", "first");
+        write("idx-b", "second");
+        assert_eq!(indexed(&base, load).unwrap().len(), 2);
+        assert_eq!(indexed(&base, load).unwrap().len(), 2);
+        assert_eq!(loads.get(), 2);
+
+        write("idx-a", "renamed");
+        invalidate("idx-a");
+        std::fs::remove_dir_all(base.join("idx-b")).unwrap();
+        let listed = indexed(&base, load).unwrap();
+        assert_eq!(loads.get(), 3);
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].0.title.as_deref(), Some("renamed"));
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
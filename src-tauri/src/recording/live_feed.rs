@@ -0,0 +1,98 @@
+//! Live feed of captured input while recording.
+//!
+//! The event hooks and the window focus tracker hand every event they write
+//! to `events.jsonl` / `window_events.jsonl` to a [`LiveFeed`] as well. Clicks,
+//! keys and window focus changes are batched and passed to the sink at most
+//! once per [`EMIT_INTERVAL_MS`]; the app forwards each batch to the frontend
+//! as the `recording-live-events` event (the "what's being captured" feed in
+//! the recording bar, and a quick check that the input hooks work at all).
+
+use crate::config::RecordingEvent;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Minimum time between two batches
+pub const EMIT_INTERVAL_MS: u64 = 250;
+
+/// Events kept per batch; older ones are dropped when input is faster
+const MAX_BATCH: usize = 20;
+
+/// Receives each batch of live events (oldest first)
+pub type LiveSink = Arc<dyn Fn(&[RecordingEvent]) + Send + Sync>;
+
+pub struct LiveFeed {
+    sink: LiveSink,
+    pending: Mutex<Vec<RecordingEvent>>,
+    last_emit: Mutex<Option<Instant>>,
+}
+
+impl LiveFeed {
+    pub fn new(sink: LiveSink) -> Arc<Self> {
+        Arc::new(Self {
+            sink,
+            pending: Mutex::new(Vec::new()),
+            last_emit: Mutex::new(None),
+        })
+    }
+
+    /// Queue the events worth showing and send a batch if the interval has
+    /// passed. Mouse moves, scrolls and UI Automation events are skipped.
+    pub fn push(&self, events: &[RecordingEvent]) {
+        let shown = events.iter().filter(|e| {
+            matches!(
+                e,
+                RecordingEvent::Click { .. } | RecordingEvent::Key { .. } | RecordingEvent::WindowFocus { .. }
+            )
+        });
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.extend(shown.cloned());
+            let excess = pending.len().saturating_sub(MAX_BATCH);
+            pending.drain(..excess);
+        }
+        self.flush(false);
+    }
+
+    /// Send the queued events; unless `force`, only once the interval since
+    /// the last batch has passed.
+    pub fn flush(&self, force: bool) {
+        let Ok(mut last_emit) = self.last_emit.lock() else {
+            return;
+        };
+        let due = last_emit.is_none_or(|t| t.elapsed() >= Duration::from_millis(EMIT_INTERVAL_MS));
+        if !force && !due {
+            return;
+        }
+        let batch = match self.pending.lock() {
+            Ok(mut pending) if !pending.is_empty() => std::mem::take(&mut *pending),
+            _ => return,
+        };
+        *last_emit = Some(Instant::now());
+        (self.sink)(&batch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batches_are_throttled_and_filtered() {
+        let batches: Arc<Mutex<Vec<usize>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink_batches = batches.clone();
+        let feed = LiveFeed::new(Arc::new(move |events: &[RecordingEvent]| {
+            sink_batches.lock().unwrap().push(events.len());
+        }));
+
+        let click = RecordingEvent::Click { t: 0, btn: "left".into(), x: 0.0, y: 0.0 };
+        let mouse_move = RecordingEvent::MouseMove { t: 0, x: 0.0, y: 0.0 };
+        feed.push(&[click.clone(), mouse_move]);
+        // Within the interval: queued, not sent
+        feed.push(&[click.clone(), click.clone()]);
+        assert_eq!(*batches.lock().unwrap(), vec![1]);
+
+        feed.flush(true);
+        assert_eq!(*batches.lock().unwrap(), vec![1, 2]);
+        feed.flush(true);
+        assert_eq!(batches.lock().unwrap().len(), 2);
+    }
+}
@@ -1,6 +1,26 @@
+pub mod append;
 pub mod audio;
 pub mod capture;
+pub mod change_tracker;
+pub mod checkpoint;
+pub mod clock;
+pub mod disk_space;
+pub mod duration_limit;
 pub mod events;
 pub mod focus;
+pub mod frame_sink;
+pub mod frame_store;
+pub mod library;
+pub mod live_feed;
+pub mod replay;
+pub mod scheduler;
+pub mod scroll_capture;
 pub mod session;
+pub mod setup;
+pub mod sidecar;
+pub mod system_theme;
+pub mod takes;
+pub mod template;
 pub mod ui_tracker;
+pub mod video_import;
+pub mod window_track;
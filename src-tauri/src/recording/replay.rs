@@ -0,0 +1,273 @@
+//! Replay buffer: while nothing is being recorded, the screen is captured
+//! into memory and the replay hotkey saves the last
+//! `replay_buffer_seconds` as a new recording.
+//!
+//! Frames go through [`ReplaySink`], a [`FrameSink`] that keeps them as
+//! JPEG (about 300 KB for a 1080p frame instead of 8 MB raw) and drops
+//! those older than the buffer length. Only the full screen is buffered,
+//! at [`REPLAY_FPS`], without input events or audio: a saved replay
+//! exports like an imported video (see [`super::video_import`]).
+//!
+//! The buffer is emptied and idle while a recording runs, so the screen
+//! isn't captured twice and a replay never spans a recording.
+
+use super::frame_sink::FrameSink;
+use crate::config::RecordingMeta;
+use crate::recording::capture::FRAME_TIMESTAMPS_FILE;
+use anyhow::{bail, Result};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Capture rate of the replay buffer
+pub const REPLAY_FPS: u32 = 15;
+
+/// Longest buffer, so memory stays around a few hundred MB at 1080p
+pub const MAX_REPLAY_SECONDS: u32 = 120;
+
+struct BufferedFrame {
+    /// Capture time since the buffer started (ms)
+    at_ms: u64,
+    jpeg: Vec<u8>,
+}
+
+/// The buffered frames, all of the same size, oldest first.
+pub struct ReplayFrames {
+    window_ms: u64,
+    width: u32,
+    height: u32,
+    frames: VecDeque<BufferedFrame>,
+}
+
+impl ReplayFrames {
+    pub fn new(seconds: u32) -> Self {
+        Self { window_ms: seconds as u64 * 1000, width: 0, height: 0, frames: VecDeque::new() }
+    }
+
+    /// Add a frame captured at `at_ms`, dropping frames older than the
+    /// buffer length. A new screen size starts the buffer over.
+    fn push(&mut self, at_ms: u64, jpeg: Vec<u8>, width: u32, height: u32) {
+        if (width, height) != (self.width, self.height) {
+            self.frames.clear();
+            (self.width, self.height) = (width, height);
+        }
+        self.frames.push_back(BufferedFrame { at_ms, jpeg });
+        while self.frames.front().is_some_and(|f| at_ms - f.at_ms > self.window_ms) {
+            self.frames.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Time from the first to the last buffered frame (ms).
+    fn span_ms(&self) -> u64 {
+        match (self.frames.front(), self.frames.back()) {
+            (Some(first), Some(last)) => last.at_ms - first.at_ms,
+            _ => 0,
+        }
+    }
+}
+
+/// Keeps captured frames in a shared [`ReplayFrames`].
+pub struct ReplaySink {
+    frames: Arc<Mutex<ReplayFrames>>,
+    started: Instant,
+}
+
+impl ReplaySink {
+    pub fn new(frames: Arc<Mutex<ReplayFrames>>) -> Self {
+        Self { frames, started: Instant::now() }
+    }
+}
+
+impl FrameSink for ReplaySink {
+    fn write_frame(&mut self, _index: u64, rgba: &[u8], width: u32, height: u32) -> Result<()> {
+        if rgba.len() != (width * height * 4) as usize {
+            bail!("Frame buffer does not match {}x{}", width, height);
+        }
+        let at_ms = self.started.elapsed().as_millis() as u64;
+        let mut jpeg = Vec::new();
+        super::frame_sink::encode_frame_jpeg(rgba, width, height, &mut jpeg)?;
+        self.frames
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Replay buffer lock poisoned"))?
+            .push(at_ms, jpeg, width, height);
+        Ok(())
+    }
+}
+
+/// Write `frames` as a new recording in `recordings_dir`, laid out like a
+/// captured one. Returns its ID.
+pub fn write_recording(frames: &ReplayFrames, recordings_dir: &Path) -> Result<String> {
+    if frames.is_empty() {
+        bail!("The replay buffer is empty");
+    }
+    std::fs::create_dir_all(recordings_dir)?;
+    // Built in a hidden folder so a failed save leaves nothing in the list
+    let staging = tempfile::Builder::new().prefix(".replay-").tempdir_in(recordings_dir)?;
+    let frames_dir = staging.path().join("frames");
+    std::fs::create_dir_all(&frames_dir)?;
+
+    let first_ms = frames.frames.front().map_or(0, |f| f.at_ms);
+    let mut timestamps = std::io::BufWriter::new(std::fs::File::create(staging.path().join(FRAME_TIMESTAMPS_FILE))?);
+    for (i, frame) in frames.frames.iter().enumerate() {
+        std::fs::write(frames_dir.join(format!("frame_{:08}.jpg", i)), &frame.jpeg)?;
+        writeln!(timestamps, "{}", frame.at_ms - first_ms)?;
+    }
+    timestamps.flush()?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let target = recordings_dir.join(&id);
+    let frame_count = frames.len() as u64;
+    let meta = RecordingMeta {
+        version: 3,
+        id: id.clone(),
+        screen_width: frames.width,
+        screen_height: frames.height,
+        fps: REPLAY_FPS,
+        start_time: (chrono::Local::now() - chrono::Duration::milliseconds(frames.span_ms() as i64)).to_rfc3339(),
+        // One frame interval past the last frame, like a capture that stopped right after it
+        duration_ms: frames.span_ms() + 1000 / REPLAY_FPS as u64,
+        has_audio: false,
+        monitor_scale: crate::recording::events::monitor_scale(&crate::config::RecordingMode::Display),
+        recording_dir: target.to_string_lossy().to_string(),
+        recording_mode: Some("display".to_string()),
+        window_title: None,
+        window_initial_rect: None,
+        frame_count: Some(frame_count as u32),
+        cursor_scale: None,
+        take: None,
+        splices: Vec::new(),
+        pauses: Vec::new(),
+        timelapse_speed: None,
+        title: None,
+        tags: Vec::new(),
+        notes: String::new(),
+        in_progress: false,
+    };
+    std::fs::write(staging.path().join("meta.json"), serde_json::to_string_pretty(&meta)?)?;
+    std::fs::rename(staging.keep(), &target)?;
+    Ok(id)
+}
+
+/// The running replay buffer. Capturing stops when it is dropped.
+pub struct ReplayBuffer {
+    running: Arc<AtomicBool>,
+    frames: Arc<Mutex<ReplayFrames>>,
+}
+
+impl ReplayBuffer {
+    /// Start buffering the last `seconds` (capped at [`MAX_REPLAY_SECONDS`]).
+    pub fn start(seconds: u32) -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+        let frames = Arc::new(Mutex::new(ReplayFrames::new(seconds.min(MAX_REPLAY_SECONDS))));
+        let sink = ReplaySink::new(frames.clone());
+        let thread_running = running.clone();
+        let thread_frames = frames.clone();
+        std::thread::spawn(move || capture(thread_running, thread_frames, sink));
+        log::info!("Replay buffer started ({}s)", seconds.min(MAX_REPLAY_SECONDS));
+        Self { running, frames }
+    }
+
+    /// Save the buffered frames as a new recording. Returns its ID.
+    pub fn save(&self) -> Result<String> {
+        let recordings_dir = dirs::video_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("Snappi")
+            .join("recordings");
+        let id = {
+            let frames = self.frames.lock().map_err(|_| anyhow::anyhow!("Replay buffer lock poisoned"))?;
+            write_recording(&frames, &recordings_dir)?
+        };
+        if let Err(e) = crate::export::encoder::generate_thumbnail(&id) {
+            log::warn!("Thumbnail generation failed for {}: {}", id, e);
+        }
+        log::info!("Replay buffer saved as {}", id);
+        Ok(id)
+    }
+}
+
+impl Drop for ReplayBuffer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+fn capture(running: Arc<AtomicBool>, frames: Arc<Mutex<ReplayFrames>>, mut sink: ReplaySink) {
+    let frame_interval = Duration::from_nanos(1_000_000_000 / REPLAY_FPS as u64);
+    let mut index = 0u64;
+    while running.load(Ordering::SeqCst) {
+        let frame_start = Instant::now();
+        if super::session::active_recording_id().is_some() {
+            if let Ok(mut frames) = frames.lock() {
+                frames.clear();
+            }
+            std::thread::sleep(Duration::from_millis(200));
+            continue;
+        }
+        let result = super::capture::grab_screen()
+            .and_then(|(image, _)| sink.write_frame(index, image.as_raw(), image.width(), image.height()));
+        if let Err(e) = result {
+            log::warn!("Replay buffer stopped: {}", e);
+            return;
+        }
+        index += 1;
+        let elapsed = frame_start.elapsed();
+        if elapsed < frame_interval {
+            std::thread::sleep(frame_interval - elapsed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_keeps_window_and_saves_recording() {
+        let frames = Arc::new(Mutex::new(ReplayFrames::new(1)));
+        let mut sink = ReplaySink::new(frames.clone());
+        let frame = vec![200u8; 4 * 4 * 4];
+        sink.write_frame(0, &frame, 4, 4).unwrap();
+        assert!(sink.write_frame(1, &frame[..8], 4, 4).is_err());
+        drop(sink);
+
+        let mut frames = Arc::try_unwrap(frames).ok().unwrap().into_inner().unwrap();
+        let jpeg = frames.frames[0].jpeg.clone();
+        frames.push(500, jpeg.clone(), 4, 4);
+        frames.push(1200, jpeg.clone(), 4, 4); // the frame at ~0 ms is now over 1 s old
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames.span_ms(), 700);
+
+        let dir = tempfile::tempdir().unwrap();
+        let id = write_recording(&frames, dir.path()).unwrap();
+        let recording = dir.path().join(&id);
+        let meta: RecordingMeta =
+            serde_json::from_str(&std::fs::read_to_string(recording.join("meta.json")).unwrap()).unwrap();
+        assert_eq!((meta.screen_width, meta.screen_height, meta.frame_count), (4, 4, Some(2)));
+        assert!(!meta.has_audio);
+        assert_eq!(std::fs::read_to_string(recording.join(FRAME_TIMESTAMPS_FILE)).unwrap(), "0\n700\n");
+        assert!(recording.join("frames/frame_00000001.jpg").exists());
+        // Nothing left behind but the recording
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        // A new screen size starts over
+        frames.push(1300, jpeg, 8, 8);
+        assert_eq!(frames.len(), 1);
+        frames.clear();
+        assert!(write_recording(&frames, dir.path()).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Scheduled recordings (`AppSettings::scheduled_recordings`).
+//!
+//! Each schedule starts a session at its start time, with its own
+//! recording mode, and stops it after its duration through the maximum
+//! duration limit (see [`super::duration_limit`]). Schedules are kept in
+//! the settings so they survive a restart; one is removed once it has
+//! started, or when its start time passed while the app wasn't running
+//! (missed).
+
+use crate::config::{RecordingMode, ScheduledRecording};
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset};
+
+/// Interval between checks for due schedules
+pub const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// A schedule whose start time passed less than this long ago still starts
+/// (the app was busy or just launched); older ones are missed.
+const START_GRACE_SECONDS: i64 = 60;
+
+/// Longest schedulable recording
+const MAX_DURATION_SECONDS: u64 = 24 * 60 * 60;
+
+pub fn parse_start(start_time: &str) -> Result<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(start_time).map_err(|e| anyhow::anyhow!("Invalid start time {:?}: {}", start_time, e))
+}
+
+/// A new schedule, checked against `now`: it must start in the future (or
+/// within the grace period) and last between 1 s and 24 h.
+pub fn new_schedule(
+    start_time: &str,
+    duration_seconds: u64,
+    mode: RecordingMode,
+    now: DateTime<FixedOffset>,
+) -> Result<ScheduledRecording> {
+    let start = parse_start(start_time)?;
+    if (now - start).num_seconds() >= START_GRACE_SECONDS {
+        anyhow::bail!("Start time is in the past: {}", start_time);
+    }
+    if duration_seconds == 0 || duration_seconds > MAX_DURATION_SECONDS {
+        anyhow::bail!("Duration must be between 1 and {} seconds", MAX_DURATION_SECONDS);
+    }
+    Ok(ScheduledRecording {
+        id: uuid::Uuid::new_v4().to_string(),
+        start_time: start.to_rfc3339(),
+        duration_seconds,
+        mode,
+    })
+}
+
+/// Remove the schedules due at `now` from `schedules`: returns the earliest
+/// one to start (later due ones stay and start when it's done, or become
+/// missed) and those that were missed. Unparsable schedules count as missed.
+pub fn take_due(
+    schedules: &mut Vec<ScheduledRecording>,
+    now: DateTime<FixedOffset>,
+) -> (Option<ScheduledRecording>, Vec<ScheduledRecording>) {
+    let mut missed = Vec::new();
+    let mut due: Option<ScheduledRecording> = None;
+    let mut kept = Vec::with_capacity(schedules.len());
+    for schedule in schedules.drain(..) {
+        let Ok(start) = parse_start(&schedule.start_time) else {
+            missed.push(schedule);
+            continue;
+        };
+        let late = (now - start).num_seconds();
+        if late >= START_GRACE_SECONDS {
+            missed.push(schedule);
+        } else if start <= now && due.is_none() {
+            due = Some(schedule);
+        } else {
+            kept.push(schedule);
+        }
+    }
+    kept.sort_by_key(|s| parse_start(&s.start_time).ok());
+    *schedules = kept;
+    (due, missed)
+}
+
+/// The schedule that starts next, for the tray.
+pub fn next_upcoming(schedules: &[ScheduledRecording]) -> Option<&ScheduledRecording> {
+    schedules.iter().min_by_key(|s| parse_start(&s.start_time).ok())
+}
+
+/// Tray label of `schedule`: local start time and duration.
+pub fn label(schedule: &ScheduledRecording) -> String {
+    let start = parse_start(&schedule.start_time)
+        .map(|t| t.with_timezone(&chrono::Local).format("%m/%d %H:%M").to_string())
+        .unwrap_or_else(|_| schedule.start_time.clone());
+    let minutes = schedule.duration_seconds.div_ceil(60);
+    format!("{} ({} min)", start, minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<FixedOffset> {
+        parse_start(&format!("2026-03-01T{}+09:00", time)).unwrap()
+    }
+
+    fn schedule(id: &str, time: &str) -> ScheduledRecording {
+        ScheduledRecording {
+            id: id.to_string(),
+            start_time: at(time).to_rfc3339(),
+            duration_seconds: 600,
+            mode: RecordingMode::Display,
+        }
+    }
+
+    #[test]
+    fn test_due_missed_and_upcoming() {
+        let now = at("10:00:30");
+        assert!(new_schedule("2026-03-01T09:00:00+09:00", 60, RecordingMode::Display, now).is_err());
+        assert!(new_schedule("2026-03-01T11:00:00+09:00", 0, RecordingMode::Display, now).is_err());
+        assert!(new_schedule("tomorrow", 60, RecordingMode::Display, now).is_err());
+        let ok = new_schedule("2026-03-01T11:00:00+09:00", 60, RecordingMode::Display, now).unwrap();
+        assert_eq!(parse_start(&ok.start_time).unwrap(), at("11:00:00"));
+
+        let mut schedules = vec![
+            schedule("later", "12:00:00"),
+            schedule("due", "10:00:00"),
+            schedule("missed", "09:30:00"),
+            schedule("soon", "10:05:00"),
+        ];
+        let (due, missed) = take_due(&mut schedules, now);
+        assert_eq!(due.map(|s| s.id), Some("due".to_string()));
+        assert_eq!(missed.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), ["missed"]);
+        assert_eq!(schedules.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), ["soon", "later"]);
+        assert_eq!(next_upcoming(&schedules).map(|s| s.id.as_str()), Some("soon"));
+
+        // Nothing due until the next start time
+        let (due, missed) = take_due(&mut schedules, at("10:04:59"));
+        assert!(due.is_none() && missed.is_empty());
+        assert_eq!(schedules.len(), 2);
+    }
+}
@@ -0,0 +1,77 @@
+//! Scrolling screenshot capture.
+//!
+//! While the user scrolls a window, a thread grabs the capture target every
+//! [`GRAB_INTERVAL`] and feeds the frames to a
+//! [`ScrollStitcher`](crate::engine::scroll_stitch::ScrollStitcher). The
+//! target follows the recording mode: the selected window (at its current
+//! position), the selected area, or the whole screen. Stopping returns the
+//! stitched image.
+
+use crate::config::RecordingMode;
+use crate::engine::scroll_stitch::ScrollStitcher;
+use anyhow::Result;
+use image::RgbaImage;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Time between grabs; short enough that consecutive frames overlap while
+/// scrolling with the wheel.
+const GRAB_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Upper bound on the stitched height, so a runaway capture can't exhaust
+/// memory.
+const MAX_STITCHED_HEIGHT: u32 = 32_000;
+
+pub struct ScrollCapture {
+    is_running: Arc<AtomicBool>,
+    thread: JoinHandle<Result<RgbaImage>>,
+}
+
+fn grab(mode: &RecordingMode) -> Result<RgbaImage> {
+    Ok(super::capture::grab_target(mode)?.0)
+}
+
+impl ScrollCapture {
+    /// Start grabbing the target of `mode`. Fails when the first grab does.
+    pub fn start(mode: RecordingMode) -> Result<Self> {
+        let first = grab(&mode)?;
+        let is_running = Arc::new(AtomicBool::new(true));
+        let running = is_running.clone();
+        let thread = std::thread::spawn(move || {
+            let mut stitcher = ScrollStitcher::new();
+            stitcher.push(first);
+            let mut skipped = 0u32;
+            while running.load(Ordering::SeqCst) {
+                std::thread::sleep(GRAB_INTERVAL);
+                match grab(&mode) {
+                    Ok(frame) => {
+                        if !stitcher.push(frame) {
+                            skipped += 1;
+                        }
+                    }
+                    Err(e) => log::warn!("Scroll capture grab failed: {}", e),
+                }
+                if stitcher.height() >= MAX_STITCHED_HEIGHT {
+                    log::warn!("Scroll capture reached {}px, stopping", MAX_STITCHED_HEIGHT);
+                    break;
+                }
+            }
+            if skipped > 0 {
+                log::info!("Scroll capture skipped {} frames that did not line up", skipped);
+            }
+            stitcher.finish().ok_or_else(|| anyhow::anyhow!("Nothing was captured"))
+        });
+        log::info!("Scroll capture started");
+        Ok(Self { is_running, thread })
+    }
+
+    /// Stop grabbing and return the stitched image.
+    pub fn stop(self) -> Result<RgbaImage> {
+        self.is_running.store(false, Ordering::SeqCst);
+        self.thread
+            .join()
+            .map_err(|_| anyhow::anyhow!("Scroll capture thread panicked"))?
+    }
+}
@@ -4,22 +4,49 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
 
+/// 録画中の ID。チェックポイントの meta.json（in_progress）が一覧に出ないようにし、
+/// 掃除（`cleanup_workspace`）で書き込み中のフォルダを消さないようにする
+static ACTIVE_RECORDING: Mutex<Option<String>> = Mutex::new(None);
+
 pub struct RecordingSession {
     id: String,
     recording_dir: std::path::PathBuf,
     is_running: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
-    start_time: Arc<Mutex<Option<std::time::Instant>>>,
-    /// 一時停止中に蓄積した時間（ms）。実効録画時間の算出用。
-    pause_accumulated_ms: Arc<Mutex<u64>>,
-    /// 現在一時停止中ならその開始時刻。resume() で accumulated に足し込む。
-    pause_start: Arc<Mutex<Option<std::time::Instant>>>,
+    /// 各スレッドが時刻を記録する時計。一時停止中は進まない
+    clock: Arc<super::clock::RecordingClock>,
     fps: u32,
+    /// タイムラプス録画の早送り倍率（通常録画は None）。音声は録らない
+    timelapse_speed: Option<f64>,
     recording_mode: RecordingMode,
     /// キー入力のラベルを平文で events.jsonl に記録するか（既定: false）。
     record_key_labels: bool,
     /// 各キャプチャスレッドのJoinHandle。stop()時にjoinして取りこぼしを防ぐ
     thread_handles: Mutex<Vec<JoinHandle<()>>>,
+    /// 撮り直し元の録画 ID。stop() 時に同じテイクグループへ登録する
+    retake_of: Option<String>,
+    /// 続きを追記する録画 ID。stop() 時にこの録画の末尾へ継ぎ足す
+    append_to: Option<String>,
+    /// テンプレートから始めた録画のエフェクト・出力設定。start() で録画フォルダに保存する
+    template_settings: Option<super::template::TemplateSettings>,
+    /// session.json の送信先（外部ツール連携）
+    session_webhook_url: Option<String>,
+    /// start() 時点の session.json。stop() で停止時の情報を足して書き直す
+    session_info: Mutex<Option<super::sidecar::SessionInfo>>,
+    /// 録画中の入力を UI に流すフィード（未設定なら流さない）
+    live_feed: Option<Arc<super::live_feed::LiveFeed>>,
+    /// 録画中に画面差分を計算する設定（自動ズーム無効時は None）
+    frame_diff: Option<crate::engine::frame_differ::DiffConfig>,
+    /// 全画面録画のフレーム保存形式
+    frame_storage: crate::config::FrameStorage,
+    /// 録画時間の上限（秒、0 = 無制限）
+    max_duration_seconds: u64,
+    /// 上限の予告・到達の通知先（未設定なら上限に達しても止めない）
+    limit_sink: Option<super::duration_limit::LimitSink>,
+    /// 空き容量の下限（MB、0 = 確認しない）
+    min_free_disk_mb: u64,
+    /// 空き容量不足の通知先（未設定なら録画は止めずにログだけ残す）
+    disk_warning: Option<super::disk_space::WarningSink>,
 }
 
 impl RecordingSession {
@@ -32,18 +59,37 @@ impl RecordingSession {
             .join(&id);
         std::fs::create_dir_all(&base_dir)?;
 
+        let timelapse_fps = settings.recording.timelapse_fps;
+        let timelapse_speed = (timelapse_fps > 0)
+            .then(|| crate::config::TIMELAPSE_OUTPUT_FPS as f64 / timelapse_fps as f64)
+            .filter(|speed| *speed > 1.0);
+
         Ok(Self {
             id,
             recording_dir: base_dir,
             is_running: Arc::new(AtomicBool::new(false)),
             is_paused: Arc::new(AtomicBool::new(false)),
-            start_time: Arc::new(Mutex::new(None)),
-            pause_accumulated_ms: Arc::new(Mutex::new(0)),
-            pause_start: Arc::new(Mutex::new(None)),
-            fps: settings.recording.fps,
+            clock: super::clock::RecordingClock::new(),
+            fps: if timelapse_speed.is_some() { timelapse_fps } else { settings.recording.fps },
+            timelapse_speed,
             recording_mode: settings.recording.recording_mode.clone(),
             record_key_labels: settings.recording.record_key_labels,
             thread_handles: Mutex::new(Vec::new()),
+            retake_of: None,
+            append_to: None,
+            template_settings: None,
+            session_webhook_url: settings.recording.session_webhook_url.clone(),
+            session_info: Mutex::new(None),
+            live_feed: None,
+            frame_diff: settings
+                .effects
+                .auto_zoom_enabled
+                .then(|| crate::engine::frame_differ::DiffConfig::from_settings(&settings.effects)),
+            frame_storage: settings.recording.frame_storage,
+            max_duration_seconds: settings.recording.max_duration_seconds,
+            limit_sink: None,
+            min_free_disk_mb: settings.recording.min_free_disk_mb,
+            disk_warning: None,
         })
     }
 
@@ -51,11 +97,67 @@ impl RecordingSession {
         &self.id
     }
 
+    /// Record this session as another take of `recording_id`.
+    pub fn set_retake_of(&mut self, recording_id: String) {
+        self.retake_of = Some(recording_id);
+    }
+
+    /// Append this session to the end of `recording_id` when it is stopped
+    /// (see [`super::append`]).
+    pub fn set_append_to(&mut self, recording_id: String) {
+        self.append_to = Some(recording_id);
+    }
+
+    /// Save the template's effects and output preset with the recording, so
+    /// previews and exports use them (see [`super::template`]).
+    pub fn set_template_settings(&mut self, template_settings: super::template::TemplateSettings) {
+        self.template_settings = Some(template_settings);
+    }
+
+    /// Forward clicks, keys and window focus changes to `live_feed` while
+    /// recording (see [`super::live_feed`]).
+    pub fn set_live_feed(&mut self, live_feed: Arc<super::live_feed::LiveFeed>) {
+        self.live_feed = Some(live_feed);
+    }
+
+    /// Report low disk space to `sink` while recording; on a `Critical`
+    /// warning the sink should stop the recording (see [`super::disk_space`]).
+    pub fn set_disk_warning(&mut self, sink: super::disk_space::WarningSink) {
+        self.disk_warning = Some(sink);
+    }
+
+    /// Warn `sink` before the maximum duration and tell it when the limit
+    /// is reached; it should then stop the recording (see
+    /// [`super::duration_limit`]).
+    pub fn set_limit_sink(&mut self, sink: super::duration_limit::LimitSink) {
+        self.limit_sink = Some(sink);
+    }
+
     pub fn start(&self) -> Result<()> {
+        super::disk_space::check_before_start(&self.recording_dir, self.min_free_disk_mb)?;
+        if let Some(ref template_settings) = self.template_settings {
+            template_settings.save(&self.recording_dir)?;
+        }
         self.is_running.store(true, Ordering::SeqCst);
-        *self.start_time.lock().unwrap() = Some(std::time::Instant::now());
+        self.clock.start();
+        if let Ok(mut active) = ACTIVE_RECORDING.lock() {
+            *active = Some(self.id.clone());
+        }
         log::info!("Recording started: {}", self.id);
 
+        let info = super::sidecar::SessionInfo::started(
+            &self.id,
+            &self.recording_mode,
+            &self.recording_dir,
+            &chrono::Local::now().to_rfc3339(),
+        );
+        if let Err(e) = super::sidecar::publish(&self.recording_dir, &info, self.session_webhook_url.as_deref()) {
+            log::warn!("Failed to write session.json for {}: {}", self.id, e);
+        }
+        if let Ok(mut session_info) = self.session_info.lock() {
+            *session_info = Some(info);
+        }
+
         let mut handles: Vec<JoinHandle<()>> = Vec::new();
 
         // Start capture thread (mode-dependent)
@@ -64,17 +166,24 @@ impl RecordingSession {
         let dir = self.recording_dir.clone();
         let fps = self.fps;
         let mode = self.recording_mode.clone();
+        // Window and area sizes can change (or differ from the screen), so
+        // only full-screen frames may be packed
+        let frames_dir = dir.join("frames");
+        let sink = match mode {
+            RecordingMode::Display => super::frame_sink::storage_sink(&frames_dir, self.frame_storage),
+            _ => Box::new(super::frame_sink::JpegDirSink::new(&frames_dir)),
+        };
+        let recorder = super::frame_sink::FrameRecorder::new(&dir, sink, self.clock.clone());
+        let frame_counter = recorder.counter();
         handles.push(std::thread::spawn(move || {
             let result = match mode {
                 RecordingMode::Window { hwnd, .. } => {
-                    super::capture::capture_window(running, paused, &dir, fps, hwnd)
+                    super::capture::capture_window(running, paused, &dir, fps, hwnd, recorder)
                 }
                 RecordingMode::Area { x, y, width, height } => {
-                    super::capture::capture_area(running, paused, &dir, fps, x, y, width, height)
-                }
-                RecordingMode::Display => {
-                    super::capture::capture_screen(running, paused, &dir, fps)
+                    super::capture::capture_area(running, paused, &dir, fps, x, y, width, height, recorder)
                 }
+                RecordingMode::Display => super::capture::capture_screen(running, paused, &dir, fps, recorder),
             };
             if let Err(e) = result {
                 log::error!("Capture error: {}", e);
@@ -86,42 +195,96 @@ impl RecordingSession {
         let paused = self.is_paused.clone();
         let dir = self.recording_dir.clone();
         let rec_keys = self.record_key_labels;
+        let live = self.live_feed.clone();
+        let clock = self.clock.clone();
         handles.push(std::thread::spawn(move || {
-            if let Err(e) = super::events::collect_events(running, paused, &dir, rec_keys) {
+            if let Err(e) = super::events::collect_events(running, paused, clock, &dir, rec_keys, live) {
                 log::error!("Event collection error: {}", e);
             }
         }));
 
-        // Start audio capture thread
-        let running = self.is_running.clone();
-        let paused = self.is_paused.clone();
-        let dir = self.recording_dir.clone();
-        handles.push(std::thread::spawn(move || {
-            if let Err(e) = super::audio::capture_audio(running, paused, &dir) {
-                log::error!("Audio capture error: {}", e);
-            }
-        }));
+        // Start audio capture thread (a timelapse has no sound: it would
+        // play back sped up)
+        if self.timelapse_speed.is_none() {
+            let running = self.is_running.clone();
+            let paused = self.is_paused.clone();
+            let dir = self.recording_dir.clone();
+            handles.push(std::thread::spawn(move || {
+                if let Err(e) = super::audio::capture_audio(running, paused, &dir) {
+                    log::error!("Audio capture error: {}", e);
+                }
+            }));
+        }
 
         // Start window focus tracking thread
         let running = self.is_running.clone();
         let paused = self.is_paused.clone();
         let dir = self.recording_dir.clone();
+        let live = self.live_feed.clone();
+        let clock = self.clock.clone();
         handles.push(std::thread::spawn(move || {
-            if let Err(e) = super::focus::track_focus(running, paused, &dir) {
+            if let Err(e) = super::focus::track_focus(running, paused, clock, &dir, live) {
                 log::error!("Window focus tracking error: {}", e);
             }
         }));
 
+        // Start frame diff thread (analysis is done while recording so
+        // export can skip it). Packed frames can't be read while they are
+        // written; they are analyzed at export time instead.
+        let packed = self.frame_storage == crate::config::FrameStorage::Packed
+            && self.recording_mode == RecordingMode::Display;
+        if let Some(config) = self.frame_diff.clone().filter(|_| !packed) {
+            let running = self.is_running.clone();
+            let dir = self.recording_dir.clone();
+            let region = self.capture_region();
+            handles.push(std::thread::spawn(move || {
+                if let Err(e) = super::change_tracker::track_changes(running, &dir, config, region) {
+                    log::warn!("Live frame diff error (non-fatal): {}", e);
+                }
+            }));
+        }
+
+        // Start free disk space watch thread
+        if self.min_free_disk_mb > 0 {
+            let running = self.is_running.clone();
+            let dir = self.recording_dir.clone();
+            let min_free_mb = self.min_free_disk_mb;
+            let sink = self.disk_warning.clone().unwrap_or_else(|| Arc::new(|_| {}));
+            handles.push(std::thread::spawn(move || {
+                super::disk_space::watch(running, &dir, min_free_mb, sink);
+            }));
+        }
+
+        // Start duration limit thread
+        if let Some(sink) = self.limit_sink.clone().filter(|_| self.max_duration_seconds > 0) {
+            let running = self.is_running.clone();
+            let limit = self.max_duration_seconds;
+            let clock = self.clock.clone();
+            handles.push(std::thread::spawn(move || {
+                super::duration_limit::watch(running, limit, || clock.now_ms(), sink);
+            }));
+        }
+
         // Start UI Automation tracker thread (best-effort, failure doesn't block recording)
         let running = self.is_running.clone();
         let paused = self.is_paused.clone();
         let dir = self.recording_dir.clone();
+        let clock = self.clock.clone();
         handles.push(std::thread::spawn(move || {
-            if let Err(e) = super::ui_tracker::track_ui_events(running, paused, &dir) {
+            if let Err(e) = super::ui_tracker::track_ui_events(running, paused, clock, &dir) {
                 log::warn!("UI tracker error (non-fatal): {}", e);
             }
         }));
 
+        // Checkpoint meta.json so a crash keeps the frame count and duration
+        let running = self.is_running.clone();
+        let dir = self.recording_dir.clone();
+        let meta = self.base_meta(true);
+        let clock = self.clock.clone();
+        handles.push(std::thread::spawn(move || {
+            super::checkpoint::run(running, &dir, meta, frame_counter, clock);
+        }));
+
         // Store handles for join on stop
         if let Ok(mut stored) = self.thread_handles.lock() {
             *stored = handles;
@@ -130,11 +293,108 @@ impl RecordingSession {
         Ok(())
     }
 
-    pub fn stop(&self) -> Result<()> {
+    /// Stop capturing and finalize the recording directory. `progress` is
+    /// called with a stage name ("flushing", "writing_meta", "compacting",
+    /// "thumbnail", "complete") and the overall progress (0.0 – 1.0).
+    /// Stop capturing and finalize the recording. Returns the ID of the
+    /// recording that holds the frames: the append target when this session
+    /// was appended, otherwise this session's own ID.
+    pub fn stop(&self, progress: &dyn Fn(&str, f64)) -> Result<String> {
         self.is_running.store(false, Ordering::SeqCst);
         log::info!("Recording stopped: {}", self.id);
+        // Clear the active ID once this session's final meta.json is written
+        let _active = ActiveGuard;
+
+        self.join_threads(progress);
 
-        // Join all capture threads to ensure buffered frames/audio are flushed.
+        log::info!("All recording threads joined (or timed out): {}", self.id);
+
+        // Write metadata
+        progress("writing_meta", 0.6);
+        // duration_ms は「実効録画時間」（一時停止時間を除いた時間）。
+        // これを使うことで、エクスポート時のフレームレート算出
+        // (frame_count * 1000 / duration_ms) が実際のキャプチャ fps と一致し、
+        // 長時間録画でも音声/映像/イベントのタイムスタンプがずれない。
+        let duration_ms = self.effective_duration_ms();
+
+        // Read actual screen dimensions from capture thread output
+        let (screen_width, screen_height) = self.read_dimensions();
+
+        // Read frame count from capture thread output
+        let frame_count = self.read_frame_count();
+
+        // Check if audio was actually captured (file exists and has data beyond WAV header)
+        let audio_path = self.recording_dir.join("audio.wav");
+        let has_audio = audio_path.exists()
+            && std::fs::metadata(&audio_path).map(|m| m.len() > 44).unwrap_or(false);
+
+        let meta = RecordingMeta {
+            screen_width,
+            screen_height,
+            duration_ms,
+            has_audio,
+            frame_count: Some(frame_count),
+            pauses: self.clock.pauses(),
+            ..self.base_meta(false)
+        };
+
+        // チェックポイントと同じく一時ファイル経由で置き換え、書きかけを残さない
+        let meta_path = self.recording_dir.join("meta.json");
+        let meta_json = serde_json::to_string_pretty(&meta)?;
+        super::checkpoint::write_atomic(&meta_path, meta_json.as_bytes())?;
+
+        let info = self.session_info.lock().ok().and_then(|mut info| info.take());
+        if let Some(info) = info {
+            let monitor = super::sidecar::SessionMonitor {
+                width: screen_width,
+                height: screen_height,
+                scale: meta.monitor_scale,
+            };
+            let info = info.stopped(monitor, duration_ms, frame_count as u64);
+            if let Err(e) = super::sidecar::publish(&self.recording_dir, &info, self.session_webhook_url.as_deref()) {
+                log::warn!("Failed to write session.json for {}: {}", self.id, e);
+            }
+        }
+
+        if let Some(ref original) = self.retake_of {
+            let ids = [original.clone(), self.id.clone()];
+            if let Err(e) = crate::recording::takes::link_takes(&ids, None) {
+                log::warn!("Failed to link {} as a take of {}: {}", self.id, original, e);
+            }
+        }
+
+        progress("compacting", 0.7);
+        // meta.json への統合が完了したので、冗長な中間ファイルを削除する。
+        // 互換性: 読み込み側は meta.frame_count が None のとき frame_count.txt に
+        // フォールバックするため、旧録画は従来通り動作する。
+        let _ = std::fs::remove_file(self.recording_dir.join("dimensions.txt"));
+        let _ = std::fs::remove_file(self.recording_dir.join("frame_count.txt"));
+
+        if let Some(ref target) = self.append_to {
+            progress("appending", 0.75);
+            match super::append::append_recording(target, &self.recording_dir) {
+                Ok(_) => {
+                    progress("complete", 1.0);
+                    return Ok(target.clone());
+                }
+                // 継ぎ足せなくても録画自体は別の録画として残す
+                Err(e) => log::warn!("Failed to append {} to {}: {}", self.id, target, e),
+            }
+        }
+
+        // 一覧に戻ったときに待たされないよう、サムネイルもここで作っておく
+        progress("thumbnail", 0.8);
+        if let Err(e) = crate::export::encoder::generate_thumbnail(&self.id) {
+            log::warn!("Thumbnail generation failed for {}: {}", self.id, e);
+        }
+
+        progress("complete", 1.0);
+        Ok(self.id.clone())
+    }
+
+    /// Join all capture threads so buffered frames/audio are flushed.
+    /// Reports "flushing" up to 0.6 through `progress`.
+    fn join_threads(&self, progress: &dyn Fn(&str, f64)) {
         // rdev (events.rs) のグローバルフックは listen() がブロッキングのため
         // is_running を落としてもすぐには抜けない可能性がある。そこで全体に
         // タイムアウトを設け、超過した場合は強制的に次へ進む（記録済みデータは保全される）。
@@ -145,6 +405,8 @@ impl RecordingSession {
             .unwrap_or_default();
 
         const MAX_JOIN_WAIT_PER_THREAD_MS: u64 = 2000;
+        let thread_count = handles.len().max(1);
+        progress("flushing", 0.0);
         for (i, handle) in handles.into_iter().enumerate() {
             // JoinHandle::join() はタイムアウト機能を持たないため、
             // Thread::is_finished() でポーリングしつつタイムアウトを実装する。
@@ -172,66 +434,67 @@ impl RecordingSession {
                 // JoinHandle を drop するとスレッドはデタッチされ、プロセス終了まで動作を続ける
                 drop(handle);
             }
+            progress("flushing", 0.6 * (i + 1) as f64 / thread_count as f64);
         }
+    }
 
-        log::info!("All recording threads joined (or timed out): {}", self.id);
-
-        // Write metadata
-        // duration_ms は「実効録画時間」（一時停止時間を除いた時間）。
-        // これを使うことで、エクスポート時のフレームレート算出
-        // (frame_count * 1000 / duration_ms) が実際のキャプチャ fps と一致し、
-        // 長時間録画でも音声/映像/イベントのタイムスタンプがずれない。
-        let duration_ms = self.effective_duration_ms();
-
-        // Read actual screen dimensions from capture thread output
-        let (screen_width, screen_height) = self.read_dimensions();
-
-        // Read frame count from capture thread output
-        let frame_count = self.read_frame_count();
-
-        // Check if audio was actually captured (file exists and has data beyond WAV header)
-        let audio_path = self.recording_dir.join("audio.wav");
-        let has_audio = audio_path.exists()
-            && std::fs::metadata(&audio_path).map(|m| m.len() > 44).unwrap_or(false);
+    /// Stop capturing and delete everything recorded so far: no meta.json,
+    /// no take link, nothing appended. Used by the cancel hotkey.
+    pub fn discard(&self) -> Result<()> {
+        self.is_running.store(false, Ordering::SeqCst);
+        let _active = ActiveGuard;
+        self.join_threads(&|_, _| {});
+        // デタッチしたスレッドがまだ書き込んでいても消せるものは消す
+        std::fs::remove_dir_all(&self.recording_dir)?;
+        log::info!("Recording discarded: {}", self.id);
+        Ok(())
+    }
 
-        let (mode_str, win_title, win_rect) = match &self.recording_mode {
-            RecordingMode::Display => (Some("display".to_string()), None, None),
-            RecordingMode::Window { title, rect, .. } => {
-                (Some("window".to_string()), Some(title.clone()), Some(*rect))
-            }
-            RecordingMode::Area { x, y, width, height } => {
-                (Some("area".to_string()), None, Some([*x as f64, *y as f64, (*x + *width) as f64, (*y + *height) as f64]))
-            }
+    /// meta.json without what is only known once capturing is done (size,
+    /// duration, frame count, audio, pauses).
+    fn base_meta(&self, in_progress: bool) -> RecordingMeta {
+        let (mode_str, win_title) = match &self.recording_mode {
+            RecordingMode::Display => (Some("display".to_string()), None),
+            RecordingMode::Window { title, .. } => (Some("window".to_string()), Some(title.clone())),
+            RecordingMode::Area { .. } => (Some("area".to_string()), None),
         };
-
-        let meta = RecordingMeta {
-            version: 2,
+        RecordingMeta {
+            version: 3,
             id: self.id.clone(),
-            screen_width,
-            screen_height,
+            screen_width: 0,
+            screen_height: 0,
             fps: self.fps,
             start_time: chrono::Local::now().to_rfc3339(),
-            duration_ms,
-            has_audio,
-            monitor_scale: 1.0,
+            duration_ms: 0,
+            has_audio: false,
+            monitor_scale: crate::recording::events::monitor_scale(&self.recording_mode),
             recording_dir: self.recording_dir.to_string_lossy().to_string(),
             recording_mode: mode_str,
             window_title: win_title,
-            window_initial_rect: win_rect,
-            frame_count: Some(frame_count),
-        };
-
-        let meta_path = self.recording_dir.join("meta.json");
-        let meta_json = serde_json::to_string_pretty(&meta)?;
-        std::fs::write(meta_path, meta_json)?;
-
-        // meta.json への統合が完了したので、冗長な中間ファイルを削除する。
-        // 互換性: 読み込み側は meta.frame_count が None のとき frame_count.txt に
-        // フォールバックするため、旧録画は従来通り動作する。
-        let _ = std::fs::remove_file(self.recording_dir.join("dimensions.txt"));
-        let _ = std::fs::remove_file(self.recording_dir.join("frame_count.txt"));
+            window_initial_rect: self.capture_region(),
+            frame_count: None,
+            cursor_scale: Some(crate::recording::events::os_cursor_scale()),
+            take: None,
+            splices: Vec::new(),
+            pauses: Vec::new(),
+            timelapse_speed: self.timelapse_speed,
+            title: None,
+            tags: Vec::new(),
+            notes: String::new(),
+            in_progress,
+        }
+    }
 
-        Ok(())
+    /// The captured window or area in screen coordinates
+    /// (`[left, top, right, bottom]`); `None` for the full screen.
+    fn capture_region(&self) -> Option<[f64; 4]> {
+        match &self.recording_mode {
+            RecordingMode::Display => None,
+            RecordingMode::Window { rect, .. } => Some(*rect),
+            RecordingMode::Area { x, y, width, height } => {
+                Some([*x as f64, *y as f64, (*x + *width) as f64, (*y + *height) as f64])
+            }
+        }
     }
 
     fn read_dimensions(&self) -> (u32, u32) {
@@ -257,25 +520,16 @@ impl RecordingSession {
     }
 
     pub fn pause(&self) -> Result<()> {
-        // 既に pause 済みなら二重計上を避けるために Instant は上書きしない
+        // 既に pause 済みなら時計はそのまま（二重に止めない）
         if !self.is_paused.swap(true, Ordering::SeqCst) {
-            if let Ok(mut p) = self.pause_start.lock() {
-                *p = Some(std::time::Instant::now());
-            }
+            self.clock.pause();
         }
         Ok(())
     }
 
     pub fn resume(&self) -> Result<()> {
         if self.is_paused.swap(false, Ordering::SeqCst) {
-            if let Ok(mut p) = self.pause_start.lock() {
-                if let Some(start) = p.take() {
-                    let delta = start.elapsed().as_millis() as u64;
-                    if let Ok(mut acc) = self.pause_accumulated_ms.lock() {
-                        *acc += delta;
-                    }
-                }
-            }
+            self.clock.resume();
         }
         Ok(())
     }
@@ -283,73 +537,82 @@ impl RecordingSession {
     /// start() からの経過時間のうち、一時停止していない実効録画時間（ms）を返す。
     /// stop() 時に呼ぶとき、まだ pause 中であれば現在時点までの pause 時間も差し引く。
     fn effective_duration_ms(&self) -> u64 {
-        let elapsed_ms = self.start_time.lock().unwrap()
-            .map(|t| t.elapsed().as_millis() as u64)
-            .unwrap_or(0);
-        let mut paused_ms = self.pause_accumulated_ms.lock().map(|g| *g).unwrap_or(0);
-        // まだ pause 中なら未確定の pause 時間も反映
-        if let Ok(guard) = self.pause_start.lock() {
-            if let Some(start) = *guard {
-                paused_ms += start.elapsed().as_millis() as u64;
-            }
+        self.clock.now_ms()
+    }
+}
+
+/// Clears [`ACTIVE_RECORDING`] when `stop()` returns, on every path.
+struct ActiveGuard;
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        if let Ok(mut active) = ACTIVE_RECORDING.lock() {
+            *active = None;
         }
-        elapsed_ms.saturating_sub(paused_ms)
     }
 }
 
+/// 録画中の ID（録画していなければ None）
+pub fn active_recording_id() -> Option<String> {
+    ACTIVE_RECORDING.lock().ok().and_then(|active| active.clone())
+}
+
 pub fn list_recordings() -> Result<Vec<RecordingInfo>> {
     let base_dir = dirs::video_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
         .join("Snappi")
         .join("recordings");
 
-    let mut recordings = Vec::new();
-
     if !base_dir.exists() {
-        return Ok(recordings);
-    }
-
-    for entry in std::fs::read_dir(&base_dir)? {
-        let entry = entry?;
-        if entry.file_type()?.is_dir() {
-            let meta_path = entry.path().join("meta.json");
-            if meta_path.exists() {
-                let content = std::fs::read_to_string(&meta_path)?;
-                if let Ok(meta) = serde_json::from_str::<RecordingMeta>(&content) {
-                    // meta.frame_count を優先。旧録画 (None) は frame_count.txt にフォールバック。
-                    let frame_count = meta.frame_count.unwrap_or_else(|| {
-                        let fc_path = entry.path().join("frame_count.txt");
-                        std::fs::read_to_string(&fc_path)
-                            .ok()
-                            .and_then(|s| s.trim().parse::<u32>().ok())
-                            .unwrap_or(0)
-                    });
-                    let thumb_path = entry.path().join("thumbnail.png");
-                    let thumbnail_path = if thumb_path.exists() {
-                        Some(thumb_path.to_string_lossy().to_string())
-                    } else {
-                        // Auto-generate thumbnail if frames exist
-                        crate::export::encoder::generate_thumbnail(&meta.id).ok()
-                    };
-                    recordings.push(RecordingInfo {
-                        id: meta.id,
-                        date: meta.start_time,
-                        duration_ms: meta.duration_ms,
-                        frame_count,
-                        thumbnail_path,
-                        recording_dir: meta.recording_dir,
-                        screen_width: meta.screen_width,
-                        screen_height: meta.screen_height,
-                    });
-                }
-            }
-        }
+        return Ok(Vec::new());
     }
 
-    recordings.sort_by(|a, b| b.date.cmp(&a.date));
+    // meta.json を読み直すのは前回から変わった録画だけ（recording::library の索引）
+    let active = active_recording_id();
+    let mut recordings: Vec<RecordingInfo> = crate::recording::library::indexed(&base_dir, recording_info)?
+        .into_iter()
+        // 録画中のチェックポイントは出さない。クラッシュで残ったもの
+        // （in_progress のまま）はチェックポイント時点までの録画として出す
+        .filter(|(info, in_progress)| !(*in_progress && active.as_deref() == Some(info.id.as_str())))
+        .map(|(info, _)| info)
+        .collect();
+
+    crate::recording::takes::group_takes(&mut recordings);
     Ok(recordings)
 }
 
+/// 一覧の 1 件分。`dir` は録画フォルダ
+fn recording_info(dir: &std::path::Path, meta: RecordingMeta) -> RecordingInfo {
+    // meta.frame_count を優先。旧録画 (None) は frame_count.txt にフォールバック。
+    let frame_count = meta.frame_count.unwrap_or_else(|| {
+        let fc_path = dir.join("frame_count.txt");
+        std::fs::read_to_string(&fc_path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u32>().ok())
+            .unwrap_or(0)
+    });
+    // Reuse thumbnail.png unless frames changed (or it is missing)
+    let thumbnail_path = crate::export::encoder::ensure_thumbnail(&meta.id).ok();
+    let monitor_scale = meta.display_scale();
+    let timelapse_speed = meta.timelapse_speed();
+    RecordingInfo {
+        id: meta.id,
+        date: meta.start_time,
+        duration_ms: meta.duration_ms,
+        frame_count,
+        thumbnail_path,
+        recording_dir: meta.recording_dir,
+        screen_width: meta.screen_width,
+        screen_height: meta.screen_height,
+        monitor_scale,
+        take: meta.take,
+        timelapse_speed,
+        title: meta.title,
+        tags: meta.tags,
+        notes: meta.notes,
+    }
+}
+
 pub fn delete_recording(recording_id: &str) -> Result<()> {
     let recording_dir = dirs::video_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
@@ -360,5 +623,6 @@ pub fn delete_recording(recording_id: &str) -> Result<()> {
     if recording_dir.exists() {
         std::fs::remove_dir_all(&recording_dir)?;
     }
+    crate::recording::library::invalidate(recording_id);
     Ok(())
 }
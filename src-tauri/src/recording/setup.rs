@@ -0,0 +1,99 @@
+//! First-run setup checks: FFmpeg, input hooks and screen capture speed,
+//! plus the settings recommended from the results.
+
+use crate::config::{AppSettings, SetupDiagnostics};
+
+/// Frames captured to measure capture speed
+const BENCHMARK_FRAMES: u32 = 20;
+
+/// Share of the measured capture speed a recording may use; the rest is
+/// headroom for event hooks, audio and the recorded applications
+const FPS_HEADROOM: f64 = 0.8;
+
+/// Recording fps the recommendation snaps to (descending)
+const FPS_STEPS: &[u32] = &[60, 30, 24, 15, 10];
+
+/// Run every check. Takes a second or two (the capture benchmark).
+pub fn run_diagnostics() -> SetupDiagnostics {
+    let ffmpeg_path = crate::export::encoder::find_ffmpeg().ok();
+    let ffmpeg_version = ffmpeg_path.as_deref().and_then(ffmpeg_version);
+
+    let hooks = super::events::test_input_hooks();
+    let capture = super::capture::benchmark_screen_capture(BENCHMARK_FRAMES);
+    if let Ok((w, h, fps)) = capture {
+        log::info!("Capture benchmark: {}x{} at {:.1} fps", w, h, fps);
+    }
+    let measured_fps = capture.as_ref().ok().map(|&(_, _, fps)| fps);
+    let (screen_width, screen_height) = capture.as_ref().map_or((0, 0), |&(w, h, _)| (w, h));
+
+    SetupDiagnostics {
+        ffmpeg_path,
+        ffmpeg_version,
+        input_hooks_ok: hooks.is_ok(),
+        input_hooks_error: hooks.err().map(|e| e.to_string()),
+        capture_ok: capture.is_ok(),
+        capture_error: capture.err().map(|e| e.to_string()),
+        screen_width,
+        screen_height,
+        measured_fps,
+        recommended_fps: measured_fps.map(recommend_fps),
+    }
+}
+
+fn ffmpeg_version(ffmpeg: &str) -> Option<String> {
+    let output = std::process::Command::new(ffmpeg).arg("-version").output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout.lines().next().map(|line| line.trim().to_string())
+}
+
+/// Highest standard fps that fits in [`FPS_HEADROOM`] of the measured speed.
+pub fn recommend_fps(measured_fps: f64) -> u32 {
+    let usable = measured_fps * FPS_HEADROOM;
+    FPS_STEPS
+        .iter()
+        .copied()
+        .find(|&fps| fps as f64 <= usable)
+        .unwrap_or(*FPS_STEPS.last().unwrap())
+}
+
+/// `settings` with the recommendations from `diagnostics` applied.
+pub fn apply_recommendations(settings: &AppSettings, diagnostics: &SetupDiagnostics) -> AppSettings {
+    let mut settings = settings.clone();
+    if let Some(fps) = diagnostics.recommended_fps {
+        settings.recording.fps = fps;
+    }
+    settings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommend_fps_keeps_headroom() {
+        assert_eq!(recommend_fps(120.0), 60);
+        assert_eq!(recommend_fps(70.0), 30);
+        assert_eq!(recommend_fps(31.0), 24);
+        assert_eq!(recommend_fps(9.0), 10);
+    }
+
+    #[test]
+    fn test_apply_recommendations_only_with_measurement() {
+        let mut diagnostics = SetupDiagnostics {
+            ffmpeg_path: None,
+            ffmpeg_version: None,
+            input_hooks_ok: true,
+            input_hooks_error: None,
+            capture_ok: false,
+            capture_error: Some("no screen".into()),
+            screen_width: 0,
+            screen_height: 0,
+            measured_fps: None,
+            recommended_fps: None,
+        };
+        let settings = AppSettings::default();
+        assert_eq!(apply_recommendations(&settings, &diagnostics).recording.fps, settings.recording.fps);
+        diagnostics.recommended_fps = Some(15);
+        assert_eq!(apply_recommendations(&settings, &diagnostics).recording.fps, 15);
+    }
+}
@@ -0,0 +1,130 @@
+//! `session.json`: a machine-readable summary of a recording session for
+//! external tools (e.g. test-automation frameworks that correlate recordings
+//! with test runs).
+//!
+//! Written into the recording directory when recording starts and rewritten
+//! when it stops. When `RecordingSettings::session_webhook_url` is set, each
+//! version is also POSTed there as JSON. Delivery is best-effort: it runs on
+//! its own thread and failures are only logged, so a slow or missing
+//! endpoint never holds up recording.
+
+use crate::config::RecordingMode;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const SESSION_FILE: &str = "session.json";
+
+const WEBHOOK_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionEvent {
+    Started,
+    Stopped,
+}
+
+/// Size of the recorded screen (display, window or area)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SessionMonitor {
+    pub width: u32,
+    pub height: u32,
+    pub scale: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub event: SessionEvent,
+    pub id: String,
+    /// "display" / "window" / "area"
+    pub mode: String,
+    /// Known once capture has reported its size (on stop)
+    pub monitor: Option<SessionMonitor>,
+    pub window_title: Option<String>,
+    pub recording_dir: String,
+    /// RFC 3339 local time
+    pub started_at: String,
+    pub stopped_at: Option<String>,
+    /// Recorded time without pauses
+    pub duration_ms: Option<u64>,
+    pub frame_count: Option<u64>,
+}
+
+impl SessionInfo {
+    pub fn started(id: &str, mode: &RecordingMode, recording_dir: &Path, started_at: &str) -> Self {
+        let (mode, window_title) = match mode {
+            RecordingMode::Display => ("display", None),
+            RecordingMode::Window { title, .. } => ("window", Some(title.clone())),
+            RecordingMode::Area { .. } => ("area", None),
+        };
+        Self {
+            event: SessionEvent::Started,
+            id: id.to_string(),
+            mode: mode.to_string(),
+            monitor: None,
+            window_title,
+            recording_dir: recording_dir.to_string_lossy().to_string(),
+            started_at: started_at.to_string(),
+            stopped_at: None,
+            duration_ms: None,
+            frame_count: None,
+        }
+    }
+
+    pub fn stopped(self, monitor: SessionMonitor, duration_ms: u64, frame_count: u64) -> Self {
+        Self {
+            event: SessionEvent::Stopped,
+            monitor: Some(monitor),
+            stopped_at: Some(chrono::Local::now().to_rfc3339()),
+            duration_ms: Some(duration_ms),
+            frame_count: Some(frame_count),
+            ..self
+        }
+    }
+}
+
+/// Write `session.json` and, if configured, post it to the webhook.
+pub fn publish(recording_dir: &Path, info: &SessionInfo, webhook_url: Option<&str>) -> Result<()> {
+    let json = serde_json::to_string_pretty(info)?;
+    std::fs::write(recording_dir.join(SESSION_FILE), &json)?;
+
+    if let Some(url) = webhook_url.map(str::trim).filter(|u| !u.is_empty()) {
+        let url = url.to_string();
+        let id = info.id.clone();
+        std::thread::spawn(move || {
+            let result = ureq::post(&url)
+                .timeout(std::time::Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+                .set("Content-Type", "application/json")
+                .send_string(&json);
+            if let Err(e) = result {
+                log::warn!("Session webhook for {} failed ({}): {}", id, url, e);
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_json_format() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mode = RecordingMode::Window { hwnd: 1, title: "Editor".into(), rect: [0.0, 0.0, 800.0, 600.0] };
+        let started = SessionInfo::started("rec-1", &mode, dir.path(), "2026-01-01T10:00:00+09:00");
+        publish(dir.path(), &started, None).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join(SESSION_FILE)).unwrap()).unwrap();
+        assert_eq!(json["event"], "started");
+        assert_eq!(json["mode"], "window");
+        assert_eq!(json["window_title"], "Editor");
+        assert!(json["stopped_at"].is_null());
+
+        let stopped = started.stopped(SessionMonitor { width: 800, height: 600, scale: 1.0 }, 1500, 45);
+        assert_eq!(stopped.event, SessionEvent::Stopped);
+        assert_eq!(stopped.started_at, "2026-01-01T10:00:00+09:00");
+        assert_eq!(stopped.frame_count, Some(45));
+    }
+}
@@ -0,0 +1,156 @@
+//! The user's Windows theme (accent color, light or dark apps) and an
+//! export style that matches it: a background gradient in the accent's
+//! hue and a click ring in the accent color, so exports look coordinated
+//! with the desktop they were recorded on without picking colors by hand.
+
+use crate::config::{AppSettings, BackgroundConfig};
+use serde::{Deserialize, Serialize};
+
+/// Windows' default accent (blue), used when the accent can't be read
+const DEFAULT_ACCENT: [u8; 3] = [0, 120, 215];
+
+/// Click ring opacity of the proposed style
+const RING_ALPHA: u8 = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemTheme {
+    pub accent_color: [u8; 3],
+    /// Apps use the dark theme
+    pub dark_mode: bool,
+    /// Proposed background for exports
+    pub background: BackgroundConfig,
+    /// Proposed click ring color (RGBA)
+    pub click_ring_color: [u8; 4],
+}
+
+impl SystemTheme {
+    pub fn new(accent_color: [u8; 3], dark_mode: bool) -> Self {
+        Self {
+            accent_color,
+            dark_mode,
+            background: theme_background(accent_color, dark_mode),
+            click_ring_color: theme_ring_color(accent_color, dark_mode),
+        }
+    }
+}
+
+/// Mix `color` toward `target` by `amount` (0.0 = color, 1.0 = target).
+fn mix(color: [u8; 3], target: [u8; 3], amount: f64) -> [u8; 3] {
+    let channel = |c: u8, t: u8| (c as f64 + (t as f64 - c as f64) * amount).round() as u8;
+    [channel(color[0], target[0]), channel(color[1], target[1]), channel(color[2], target[2])]
+}
+
+/// A gradient in the accent's hue: deep shades on a dark desktop, pale
+/// tints on a light one.
+pub fn theme_background(accent: [u8; 3], dark_mode: bool) -> BackgroundConfig {
+    let (from, to) = if dark_mode {
+        (mix(accent, [0, 0, 0], 0.35), mix(accent, [0, 0, 0], 0.75))
+    } else {
+        (mix(accent, [255, 255, 255], 0.3), mix(accent, [255, 255, 255], 0.7))
+    };
+    BackgroundConfig::Gradient { from, to, angle: 135.0 }
+}
+
+/// The accent as a click ring, lifted on dark content and deepened on
+/// light content so it stays visible over the recorded apps.
+pub fn theme_ring_color(accent: [u8; 3], dark_mode: bool) -> [u8; 4] {
+    let [r, g, b] = if dark_mode {
+        mix(accent, [255, 255, 255], 0.25)
+    } else {
+        mix(accent, [0, 0, 0], 0.15)
+    };
+    [r, g, b, RING_ALPHA]
+}
+
+/// `settings` with the background and click ring of `theme`.
+pub fn apply_theme(settings: &AppSettings, theme: &SystemTheme) -> AppSettings {
+    let mut settings = settings.clone();
+    settings.style.background = theme.background.clone();
+    settings.effects.click_ring_color = theme.click_ring_color;
+    settings
+}
+
+/// Read the current theme. Falls back to the default accent and the light
+/// theme for values that can't be read (and off Windows).
+pub fn read_system_theme() -> SystemTheme {
+    let accent = read_accent_color().unwrap_or(DEFAULT_ACCENT);
+    let dark_mode = read_dark_mode().unwrap_or(false);
+    log::info!("System theme: accent {:?}, {}", accent, if dark_mode { "dark" } else { "light" });
+    SystemTheme::new(accent, dark_mode)
+}
+
+#[cfg(windows)]
+fn read_dword(key: windows::core::PCWSTR, value: windows::core::PCWSTR) -> Option<u32> {
+    use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+    let mut data: u32 = 0;
+    let mut len = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            key,
+            value,
+            RRF_RT_REG_DWORD,
+            None,
+            Some(&mut data as *mut u32 as *mut std::ffi::c_void),
+            Some(&mut len),
+        )
+    };
+    status.is_ok().then_some(data)
+}
+
+/// `HKCU\Software\Microsoft\Windows\DWM\AccentColor` (0xAABBGGRR).
+#[cfg(windows)]
+fn read_accent_color() -> Option<[u8; 3]> {
+    use windows::core::w;
+    let abgr = read_dword(w!("Software\\Microsoft\\Windows\\DWM"), w!("AccentColor"))?;
+    Some([(abgr & 0xff) as u8, ((abgr >> 8) & 0xff) as u8, ((abgr >> 16) & 0xff) as u8])
+}
+
+/// `AppsUseLightTheme` = 0 under `Themes\Personalize`.
+#[cfg(windows)]
+fn read_dark_mode() -> Option<bool> {
+    use windows::core::w;
+    let light = read_dword(
+        w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+        w!("AppsUseLightTheme"),
+    )?;
+    Some(light == 0)
+}
+
+#[cfg(not(windows))]
+fn read_accent_color() -> Option<[u8; 3]> {
+    None
+}
+
+#[cfg(not(windows))]
+fn read_dark_mode() -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_style_follows_accent_and_mode() {
+        let accent = [0, 120, 215];
+        let dark = SystemTheme::new(accent, true);
+        let light = SystemTheme::new(accent, false);
+        let luminance = |c: [u8; 3]| c.iter().map(|&v| v as u32).sum::<u32>();
+        match (&dark.background, &light.background) {
+            (BackgroundConfig::Gradient { from: df, to: dt, .. }, BackgroundConfig::Gradient { from: lf, to: lt, .. }) => {
+                assert!(luminance(*df) < luminance(accent) && luminance(*dt) < luminance(*df));
+                assert!(luminance(*lf) > luminance(accent) && luminance(*lt) > luminance(*lf));
+                // Still the accent's hue: blue stays the strongest channel
+                assert!(dt[2] > dt[0] && lf[2] > lf[0]);
+            }
+            _ => panic!("expected gradients"),
+        }
+        assert!(luminance([dark.click_ring_color[0], dark.click_ring_color[1], dark.click_ring_color[2]]) > luminance(accent));
+        assert_eq!(light.click_ring_color[3], RING_ALPHA);
+
+        let settings = apply_theme(&AppSettings::default(), &dark);
+        assert_eq!(settings.effects.click_ring_color, dark.click_ring_color);
+    }
+}
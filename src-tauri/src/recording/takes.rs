@@ -0,0 +1,182 @@
+//! Takes: several recordings of the same scenario.
+//!
+//! Recordings are linked by a shared [`TakeInfo::group_id`] stored in each
+//! recording's `meta.json`, so a group survives moving the recordings folder
+//! and needs no separate index. One take per group can be marked as chosen.
+
+use crate::config::{RecordingInfo, RecordingMeta, TakeInfo};
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+
+fn meta_path(recording_id: &str) -> PathBuf {
+    dirs::video_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+        .join(recording_id)
+        .join("meta.json")
+}
+
+pub(crate) fn read_meta(recording_id: &str) -> Result<RecordingMeta> {
+    let content = std::fs::read_to_string(meta_path(recording_id))
+        .with_context(|| format!("Recording not found: {}", recording_id))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+pub(crate) fn write_meta(meta: &RecordingMeta) -> Result<()> {
+    std::fs::write(meta_path(&meta.id), serde_json::to_string_pretty(meta)?)?;
+    Ok(())
+}
+
+/// Link recordings as takes of one scenario. Recordings already in a group
+/// pull the others into it (the first such group wins); otherwise a new
+/// group is created. `scenario` renames the group when given.
+pub fn link_takes(recording_ids: &[String], scenario: Option<&str>) -> Result<TakeInfo> {
+    if recording_ids.len() < 2 {
+        bail!("At least two recordings are needed to link takes");
+    }
+    let metas = recording_ids.iter().map(|id| read_meta(id)).collect::<Result<Vec<_>>>()?;
+    let existing = metas.iter().find_map(|m| m.take.clone());
+
+    let group_id = existing
+        .as_ref()
+        .map(|t| t.group_id.clone())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let scenario = scenario
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .or_else(|| existing.as_ref().map(|t| t.scenario.clone()))
+        .or_else(|| metas.iter().find_map(|m| m.window_title.clone()))
+        .unwrap_or_default();
+
+    // Recordings being moved out of another group lose their chosen mark
+    for mut meta in metas {
+        let chosen = meta.take.as_ref().is_some_and(|t| t.group_id == group_id && t.chosen);
+        meta.take = Some(TakeInfo { group_id: group_id.clone(), scenario: scenario.clone(), chosen });
+        write_meta(&meta)?;
+    }
+    if existing.is_some() {
+        rename_group(&group_id, &scenario)?;
+    }
+    Ok(TakeInfo { group_id, scenario, chosen: false })
+}
+
+/// Keep the scenario name the same across every take of a group.
+fn rename_group(group_id: &str, scenario: &str) -> Result<()> {
+    for mut meta in group_metas(group_id)? {
+        if let Some(ref mut take) = meta.take {
+            if take.scenario != scenario {
+                take.scenario = scenario.to_string();
+                write_meta(&meta)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Remove a recording from its group.
+pub fn unlink_take(recording_id: &str) -> Result<()> {
+    let mut meta = read_meta(recording_id)?;
+    if meta.take.take().is_some() {
+        write_meta(&meta)?;
+    }
+    Ok(())
+}
+
+/// Mark `recording_id` as the chosen take of its group (and no other).
+pub fn choose_take(recording_id: &str) -> Result<()> {
+    let meta = read_meta(recording_id)?;
+    let Some(take) = meta.take else {
+        bail!("Recording {} is not part of a take group", recording_id);
+    };
+    for mut meta in group_metas(&take.group_id)? {
+        let chosen = meta.id == recording_id;
+        if let Some(ref mut t) = meta.take {
+            if t.chosen != chosen {
+                t.chosen = chosen;
+                write_meta(&meta)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn group_metas(group_id: &str) -> Result<Vec<RecordingMeta>> {
+    let base_dir = dirs::video_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings");
+    let mut metas = Vec::new();
+    if !base_dir.exists() {
+        return Ok(metas);
+    }
+    for entry in std::fs::read_dir(&base_dir)? {
+        let Ok(content) = std::fs::read_to_string(entry?.path().join("meta.json")) else {
+            continue;
+        };
+        if let Ok(meta) = serde_json::from_str::<RecordingMeta>(&content) {
+            if meta.take.as_ref().is_some_and(|t| t.group_id == group_id) {
+                metas.push(meta);
+            }
+        }
+    }
+    Ok(metas)
+}
+
+/// Order the library newest first, with the takes of a group kept together
+/// at the position of the group's newest take (also newest first).
+pub fn group_takes(recordings: &mut [RecordingInfo]) {
+    let mut newest: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    for rec in recordings.iter() {
+        if let Some(ref take) = rec.take {
+            let entry = newest.entry(take.group_id.clone()).or_default();
+            if rec.date > *entry {
+                *entry = rec.date.clone();
+            }
+        }
+    }
+    let key = |rec: &RecordingInfo| {
+        let group = rec.take.as_ref().map(|t| t.group_id.clone());
+        let group_date = group.as_ref().map_or_else(|| rec.date.clone(), |g| newest[g].clone());
+        (group_date, group.unwrap_or_else(|| rec.id.clone()), rec.date.clone())
+    };
+    recordings.sort_by_cached_key(|rec| std::cmp::Reverse(key(rec)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(id: &str, date: &str, group: Option<&str>) -> RecordingInfo {
+        RecordingInfo {
+            id: id.to_string(),
+            date: date.to_string(),
+            duration_ms: 0,
+            frame_count: 0,
+            thumbnail_path: None,
+            recording_dir: String::new(),
+            screen_width: 0,
+            screen_height: 0,
+            monitor_scale: 1.0,
+            take: group.map(|g| TakeInfo { group_id: g.to_string(), scenario: String::new(), chosen: false }),
+            timelapse_speed: None,
+            title: None,
+            tags: Vec::new(),
+            notes: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_takes_grouped_at_newest_take() {
+        let mut recordings = vec![
+            info("a1", "2026-01-01", Some("a")),
+            info("x", "2026-01-02", None),
+            info("a2", "2026-01-03", Some("a")),
+            info("y", "2026-01-04", None),
+        ];
+        group_takes(&mut recordings);
+        let ids: Vec<&str> = recordings.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["y", "a2", "a1", "x"]);
+    }
+}
@@ -0,0 +1,183 @@
+//! Recording templates: resolve a template against the current settings and
+//! the visible windows right before a capture starts.
+//!
+//! A template's effects profile and output preset belong to the recording,
+//! not just the capture: they are saved next to meta.json as
+//! [`TEMPLATE_SETTINGS_FILE`] and applied again whenever the recording is
+//! previewed or exported (see [`with_recording_settings`]).
+
+use crate::config::{AppSettings, EffectsSettings, ExportFormat, QualityPreset, RecordingMode, RecordingTemplate, WindowInfo};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The template settings of a recording started from a template
+pub const TEMPLATE_SETTINGS_FILE: &str = "template_settings.json";
+
+/// What a template overrides in the settings besides the recording mode.
+/// `None` fields keep the current settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateSettings {
+    #[serde(default)]
+    pub effects: Option<EffectsSettings>,
+    #[serde(default)]
+    pub output_format: Option<ExportFormat>,
+    #[serde(default)]
+    pub output_quality: Option<QualityPreset>,
+}
+
+impl TemplateSettings {
+    pub fn from_template(template: &RecordingTemplate) -> Self {
+        Self {
+            effects: template.effects.clone(),
+            output_format: template.output_format.clone(),
+            output_quality: template.output_quality.clone(),
+        }
+    }
+
+    pub fn apply_to(&self, settings: &mut AppSettings) {
+        if let Some(ref effects) = self.effects {
+            settings.effects = effects.clone();
+        }
+        if let Some(ref format) = self.output_format {
+            settings.output.default_format = format.clone();
+        }
+        if let Some(ref quality) = self.output_quality {
+            settings.output.default_quality = quality.clone();
+        }
+    }
+
+    /// Save as the template settings of the recording in `recording_dir`.
+    pub fn save(&self, recording_dir: &Path) -> Result<()> {
+        std::fs::write(recording_dir.join(TEMPLATE_SETTINGS_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// `settings` with the template settings saved with the recording in
+/// `recording_dir` applied; unchanged for a recording that wasn't started
+/// from a template.
+pub fn with_recording_settings(mut settings: AppSettings, recording_dir: &Path) -> AppSettings {
+    let path = recording_dir.join(TEMPLATE_SETTINGS_FILE);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return settings;
+    };
+    match serde_json::from_str::<TemplateSettings>(&content) {
+        Ok(saved) => saved.apply_to(&mut settings),
+        Err(e) => log::warn!("Ignoring unreadable {}: {}", path.display(), e),
+    }
+    settings
+}
+
+/// Case-insensitive title match. `*` matches any run of characters; a
+/// pattern without `*` matches anywhere in the title.
+pub fn title_matches(pattern: &str, title: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let title = title.to_lowercase();
+    if !pattern.contains('*') {
+        return title.contains(&pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let mut rest = title.as_str();
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        if i == 0 {
+            // Anchored at the start
+            match rest.strip_prefix(part) {
+                Some(r) => rest = r,
+                None => return false,
+            }
+        } else if i == parts.len() - 1 {
+            // Anchored at the end
+            return rest.ends_with(part);
+        } else {
+            match rest.find(part) {
+                Some(pos) => rest = &rest[pos + part.len()..],
+                None => return false,
+            }
+        }
+    }
+    true
+}
+
+/// Settings to record with: `base` with the template's mode, effects profile
+/// and output preset applied. Fails if the template targets a window and no
+/// visible window matches its title pattern.
+pub fn apply_template(
+    base: &AppSettings,
+    template: &RecordingTemplate,
+    windows: &[WindowInfo],
+) -> Result<AppSettings> {
+    let mut settings = base.clone();
+
+    settings.recording.recording_mode = match &template.window_title_pattern {
+        Some(pattern) => {
+            let Some(window) = windows.iter().find(|w| title_matches(pattern, &w.title)) else {
+                bail!("No window matches \"{}\"", pattern);
+            };
+            RecordingMode::Window {
+                hwnd: window.hwnd,
+                title: window.title.clone(),
+                rect: window.rect,
+            }
+        }
+        None => RecordingMode::Display,
+    };
+    TemplateSettings::from_template(template).apply_to(&mut settings);
+
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ExportFormat, QualityPreset};
+
+    fn window(hwnd: isize, title: &str) -> WindowInfo {
+        WindowInfo { hwnd, title: title.to_string(), rect: [0.0, 0.0, 800.0, 600.0] }
+    }
+
+    fn template(pattern: Option<&str>) -> RecordingTemplate {
+        RecordingTemplate {
+            id: "t".to_string(),
+            name: "Test".to_string(),
+            window_title_pattern: pattern.map(|p| p.to_string()),
+            countdown_seconds: 3,
+            effects: None,
+            output_format: Some(ExportFormat::Gif),
+            output_quality: Some(QualityPreset::Lightweight),
+        }
+    }
+
+    #[test]
+    fn test_title_matches_substring_and_wildcards() {
+        assert!(title_matches("code", "main.rs - Visual Studio Code"));
+        assert!(title_matches("*visual studio code", "main.rs - Visual Studio Code"));
+        assert!(title_matches("main*code", "main.rs - Visual Studio Code"));
+        assert!(!title_matches("main*chrome", "main.rs - Visual Studio Code"));
+        assert!(!title_matches("code*", "main.rs - Visual Studio Code"));
+    }
+
+    #[test]
+    fn test_apply_template_picks_matching_window() {
+        let windows = vec![window(1, "Google Chrome"), window(2, "Terminal")];
+        let settings = apply_template(&AppSettings::default(), &template(Some("term")), &windows).unwrap();
+        match settings.recording.recording_mode {
+            RecordingMode::Window { hwnd, .. } => assert_eq!(hwnd, 2),
+            other => panic!("unexpected mode {:?}", other),
+        }
+        assert_eq!(settings.output.default_format, ExportFormat::Gif);
+        assert_eq!(settings.output.default_quality, QualityPreset::Lightweight);
+    }
+
+    #[test]
+    fn test_apply_template_fails_without_matching_window() {
+        let windows = vec![window(1, "Google Chrome")];
+        assert!(apply_template(&AppSettings::default(), &template(Some("Slack")), &windows).is_err());
+        let settings = apply_template(&AppSettings::default(), &template(None), &windows).unwrap();
+        assert_eq!(settings.recording.recording_mode, RecordingMode::Display);
+    }
+}
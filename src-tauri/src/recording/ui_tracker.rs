@@ -9,16 +9,17 @@ use std::sync::Arc;
 pub fn track_ui_events(
     is_running: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
+    clock: Arc<super::clock::RecordingClock>,
     output_dir: &Path,
 ) -> Result<()> {
     #[cfg(windows)]
     {
-        track_ui_events_windows(is_running, is_paused, output_dir)
+        track_ui_events_windows(is_running, is_paused, clock, output_dir)
     }
 
     #[cfg(not(windows))]
     {
-        let _ = (is_running, is_paused, output_dir);
+        let _ = (is_running, is_paused, clock, output_dir);
         Ok(())
     }
 }
@@ -27,6 +28,7 @@ pub fn track_ui_events(
 fn track_ui_events_windows(
     is_running: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
+    clock: Arc<super::clock::RecordingClock>,
     output_dir: &Path,
 ) -> Result<()> {
     use std::io::Write;
@@ -42,7 +44,6 @@ fn track_ui_events_windows(
             .append(true)
             .open(&events_path)?,
     );
-    let start_time = std::time::Instant::now();
 
     // Initialize COM in STA mode (required for UI Automation event handlers)
     unsafe {
@@ -77,7 +78,7 @@ fn track_ui_events_windows(
             continue;
         }
 
-        let elapsed_ms = start_time.elapsed().as_millis() as u64;
+        let elapsed_ms = clock.now_ms();
 
         // Debounce: skip if too soon
         if elapsed_ms.saturating_sub(last_event_ms) < debounce_ms {
@@ -98,6 +99,10 @@ fn track_ui_events_windows(
                 .map(|s| s.to_string())
                 .unwrap_or_default();
 
+            let is_password = unsafe { element.CurrentIsPassword() }
+                .map(|b| b.as_bool())
+                .unwrap_or(false);
+
             // Only emit if the focused element actually changed
             let element_key = format!("{}:{}:{}", control_name, name, automation_id);
             if element_key != last_element_name {
@@ -153,10 +158,10 @@ fn track_ui_events_windows(
                     }
                     _ => {
                         format!(
-                            r#"{{"type":"ui_focus","t":{},"control":"{}","name":"{}","rect":[{},{},{},{}],"automation_id":"{}"}}"#,
+                            r#"{{"type":"ui_focus","t":{},"control":"{}","name":"{}","rect":[{},{},{},{}],"automation_id":"{}","password":{}}}"#,
                             elapsed_ms, control_name, escape_json(&name),
                             rect_arr[0], rect_arr[1], rect_arr[2], rect_arr[3],
-                            escape_json(&automation_id),
+                            escape_json(&automation_id), is_password,
                         )
                     }
                 };
@@ -0,0 +1,215 @@
+//! Import a video captured elsewhere (MP4, WebM, MOV, …) as a recording.
+//!
+//! FFmpeg decodes the video into JPEG frames at a constant rate (the
+//! source's, capped at [`MAX_FPS`]) and its first audio track into
+//! `audio.wav`, laid out like a captured recording: `frames/`,
+//! `frame_timestamps.txt` and `meta.json`. There are no input events, so
+//! exports get the background, rounded corners and shadow but no click or
+//! keystroke effects, and auto-zoom only follows screen changes.
+//!
+//! The recording is built in a hidden folder and moved into place when
+//! complete, so a failed import leaves nothing in the list.
+
+use crate::config::RecordingMeta;
+use crate::recording::capture::FRAME_TIMESTAMPS_FILE;
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Highest frame rate of an imported recording
+const MAX_FPS: u32 = 60;
+
+/// Frame rate when the source doesn't report one
+const DEFAULT_FPS: u32 = 30;
+
+/// JPEG quality of the decoded frames (FFmpeg's `-q:v`, 2 = best)
+const FRAME_QUALITY: &str = "3";
+
+/// What `ffmpeg -i` reports about the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoProbe {
+    pub width: u32,
+    pub height: u32,
+    pub fps: Option<f64>,
+    pub duration_ms: Option<u64>,
+    pub has_audio: bool,
+}
+
+/// Parse the stream listing FFmpeg prints to stderr for `ffmpeg -i input`.
+/// `None` when there is no video stream.
+pub fn parse_probe(stderr: &str) -> Option<VideoProbe> {
+    let duration_ms = stderr.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("Duration: ")?;
+        let (h, rest) = rest.split_once(':')?;
+        let (m, rest) = rest.split_once(':')?;
+        let s = rest.split(',').next()?;
+        let secs = h.parse::<f64>().ok()? * 3600.0 + m.parse::<f64>().ok()? * 60.0 + s.parse::<f64>().ok()?;
+        Some((secs * 1000.0).round() as u64)
+    });
+    let has_audio = stderr.lines().any(|l| l.contains("Stream #") && l.contains(": Audio:"));
+    let video = stderr.lines().find(|l| l.contains("Stream #") && l.contains(": Video:"))?;
+    // "1920x1080" is the first field made of two numbers around an 'x'
+    let (width, height) = video.split([',', ' ']).find_map(|field| {
+        let (w, h) = field.split_once('x')?;
+        let (w, h) = (w.parse::<u32>().ok()?, h.parse::<u32>().ok()?);
+        (w > 0 && h > 0).then_some((w, h))
+    })?;
+    let fps = video.split(',').find_map(|field| field.trim().strip_suffix(" fps")?.parse::<f64>().ok());
+    Some(VideoProbe { width, height, fps: fps.filter(|f| f.is_finite() && *f > 0.0), duration_ms, has_audio })
+}
+
+fn probe(ffmpeg: &str, input: &Path) -> Result<VideoProbe> {
+    // Without an output FFmpeg exits with an error after listing the streams
+    let output = Command::new(ffmpeg).args(["-hide_banner", "-i"]).arg(input).output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    parse_probe(&stderr).with_context(|| format!("No video stream in {}", input.display()))
+}
+
+/// Frame rate of the imported recording: the source's, rounded and capped.
+fn import_fps(probe: &VideoProbe) -> u32 {
+    probe.fps.map_or(DEFAULT_FPS, |f| (f.round() as u32).clamp(1, MAX_FPS))
+}
+
+fn run(mut cmd: Command, what: &str) -> Result<()> {
+    let output = cmd.output()?;
+    if !output.status.success() {
+        bail!("FFmpeg {} failed: {}", what, String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// Decode `input` into the recording folder `dir`. Returns the frame count.
+fn decode(ffmpeg: &str, input: &Path, dir: &Path, fps: u32, has_audio: bool) -> Result<u64> {
+    let frames_dir = dir.join("frames");
+    std::fs::create_dir_all(&frames_dir)?;
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(["-y", "-loglevel", "error", "-i"])
+        .arg(input)
+        .args(["-map", "0:v:0", "-vf"])
+        .arg(format!("fps={}", fps))
+        .args(["-q:v", FRAME_QUALITY, "-start_number", "0"])
+        .arg(frames_dir.join("frame_%08d.jpg"));
+    run(cmd, "frame decoding")?;
+
+    if has_audio {
+        let mut cmd = Command::new(ffmpeg);
+        cmd.args(["-y", "-loglevel", "error", "-i"])
+            .arg(input)
+            .args(["-map", "0:a:0", "-vn", "-c:a", "pcm_s16le", "-ar", "48000", "-ac", "2"])
+            .arg(dir.join("audio.wav"));
+        // A broken audio track shouldn't cost the video
+        if let Err(e) = run(cmd, "audio extraction") {
+            log::warn!("{}", e);
+            let _ = std::fs::remove_file(dir.join("audio.wav"));
+        }
+    }
+
+    let frame_count = std::fs::read_dir(&frames_dir)?
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|x| x == "jpg"))
+        .count() as u64;
+    if frame_count == 0 {
+        bail!("FFmpeg produced no frames from {}", input.display());
+    }
+    Ok(frame_count)
+}
+
+/// Constant-rate timestamps, like the capture thread writes.
+fn write_timestamps(dir: &Path, frame_count: u64, fps: u32) -> Result<()> {
+    let mut out = std::io::BufWriter::new(std::fs::File::create(dir.join(FRAME_TIMESTAMPS_FILE))?);
+    for i in 0..frame_count {
+        writeln!(out, "{}", i * 1000 / fps as u64)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn recordings_dir() -> PathBuf {
+    dirs::video_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Snappi")
+        .join("recordings")
+}
+
+/// Import `input` as a new recording. Returns its ID.
+pub fn import_video(input: &Path) -> Result<String> {
+    if !input.is_file() {
+        bail!("File not found: {}", input.display());
+    }
+    let ffmpeg = crate::export::encoder::find_ffmpeg()?;
+    let probe = probe(&ffmpeg, input)?;
+    let fps = import_fps(&probe);
+    log::info!("Importing {} ({}x{}, {} fps)", input.display(), probe.width, probe.height, fps);
+
+    let recordings_dir = recordings_dir();
+    std::fs::create_dir_all(&recordings_dir)?;
+    let staging = tempfile::Builder::new().prefix(".import-").tempdir_in(&recordings_dir)?;
+    let frame_count = decode(&ffmpeg, input, staging.path(), fps, probe.has_audio)?;
+    write_timestamps(staging.path(), frame_count, fps)?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let target = recordings_dir.join(&id);
+    let has_audio = std::fs::metadata(staging.path().join("audio.wav")).is_ok_and(|m| m.len() > 44);
+    let meta = RecordingMeta {
+        version: 3,
+        id: id.clone(),
+        screen_width: probe.width,
+        screen_height: probe.height,
+        fps,
+        start_time: chrono::Local::now().to_rfc3339(),
+        duration_ms: probe.duration_ms.unwrap_or(frame_count * 1000 / fps as u64),
+        has_audio,
+        monitor_scale: 1.0,
+        recording_dir: target.to_string_lossy().to_string(),
+        recording_mode: Some("display".to_string()),
+        window_title: None,
+        window_initial_rect: None,
+        frame_count: Some(frame_count as u32),
+        cursor_scale: None,
+        take: None,
+        splices: Vec::new(),
+        pauses: Vec::new(),
+        timelapse_speed: None,
+        title: input.file_stem().map(|s| s.to_string_lossy().to_string()),
+        tags: Vec::new(),
+        notes: String::new(),
+        in_progress: false,
+    };
+    std::fs::write(staging.path().join("meta.json"), serde_json::to_string_pretty(&meta)?)?;
+    std::fs::rename(staging.keep(), &target)?;
+
+    if let Err(e) = crate::export::encoder::generate_thumbnail(&id) {
+        log::warn!("Thumbnail generation failed for {}: {}", id, e);
+    }
+    log::info!("Imported {} as {} ({} frames)", input.display(), id, frame_count);
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_probe() {
+        let stderr = "\
+Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'demo.mp4':
+  Duration: 00:01:02.50, start: 0.000000, bitrate: 2500 kb/s
+  Stream #0:0[0x1](und): Video: h264 (High) (avc1 / 0x31637661), yuv420p(tv, bt709, progressive), 1920x1080 [SAR 1:1 DAR 16:9], 2300 kb/s, 29.97 fps, 29.97 tbr, 30k tbn (default)
+  Stream #0:1[0x2](und): Audio: aac (LC) (mp4a / 0x6134706D), 48000 Hz, stereo, fltp, 128 kb/s (default)
+At least one output file must be specified";
+        let probe = parse_probe(stderr).unwrap();
+        assert_eq!(
+            probe,
+            VideoProbe { width: 1920, height: 1080, fps: Some(29.97), duration_ms: Some(62_500), has_audio: true }
+        );
+        assert_eq!(import_fps(&probe), 30);
+
+        let webm = "  Duration: N/A, start: 0.000000, bitrate: N/A\n  Stream #0:0: Video: vp9 (Profile 0), yuv420p(tv), 1280x720, SAR 1:1 DAR 16:9, 1k tbr, 1k tbn (default)";
+        let probe = parse_probe(webm).unwrap();
+        assert_eq!((probe.width, probe.height, probe.fps, probe.duration_ms, probe.has_audio), (1280, 720, None, None, false));
+        assert_eq!(import_fps(&VideoProbe { fps: Some(144.0), ..probe }), MAX_FPS);
+
+        assert!(parse_probe("  Stream #0:0: Audio: mp3, 44100 Hz, stereo").is_none());
+    }
+}
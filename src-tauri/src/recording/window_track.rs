@@ -0,0 +1,161 @@
+//! Where the recorded window was over time, for window recordings.
+//!
+//! Frames of a window recording are the window's own pixels, while events
+//! are in screen coordinates. The compositor maps one onto the other with
+//! `meta.window_initial_rect`, which only holds while the window stays
+//! put. The capture thread therefore appends the window rect to
+//! `window_track.jsonl` whenever it changes, and [`follow_window`] moves
+//! each event by how far the window had moved at its time, so cursor,
+//! clicks and zoom targets stay on the window's content.
+
+use crate::config::RecordingEvent;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+pub const WINDOW_TRACK_FILE: &str = "window_track.jsonl";
+
+/// The window rect (`[left, top, right, bottom]`, screen coordinates) from
+/// time `t` (recording ms) on.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowTrackEntry {
+    pub t: u64,
+    pub rect: [f64; 4],
+}
+
+/// Appends a line to `window_track.jsonl` each time the rect changes.
+pub struct WindowTrackWriter {
+    writer: Option<std::io::BufWriter<std::fs::File>>,
+    last: Option<[f64; 4]>,
+}
+
+impl WindowTrackWriter {
+    pub fn create(output_dir: &Path) -> Self {
+        let writer = std::fs::File::create(output_dir.join(WINDOW_TRACK_FILE))
+            .map_err(|e| log::warn!("Failed to create {}: {}", WINDOW_TRACK_FILE, e))
+            .ok()
+            .map(std::io::BufWriter::new);
+        Self { writer, last: None }
+    }
+
+    /// Record the rect seen at `t`; unchanged rects are skipped.
+    pub fn record(&mut self, t: u64, rect: [f64; 4]) {
+        if self.last == Some(rect) {
+            return;
+        }
+        self.last = Some(rect);
+        if let Some(w) = self.writer.as_mut() {
+            if let Ok(line) = serde_json::to_string(&WindowTrackEntry { t, rect }) {
+                let _ = writeln!(w, "{}", line);
+                // Moves are rare; keep the file current in case of a crash
+                let _ = w.flush();
+            }
+        }
+    }
+}
+
+/// The recording's window track, oldest first. Empty for recordings that
+/// have none (other modes, older recordings).
+pub fn load_window_track(recording_dir: &Path) -> Vec<WindowTrackEntry> {
+    let Ok(content) = std::fs::read_to_string(recording_dir.join(WINDOW_TRACK_FILE)) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// The window rect at `t`: the last entry at or before it (the first one
+/// before the track starts).
+pub fn rect_at(track: &[WindowTrackEntry], t: u64) -> Option<[f64; 4]> {
+    let after = track.partition_point(|entry| entry.t <= t);
+    track.get(after.saturating_sub(1)).map(|entry| entry.rect)
+}
+
+/// Move `events` into the coordinates of the window's first position, so
+/// they line up with frames through `window_initial_rect` however the
+/// window moved afterwards.
+pub fn follow_window(events: &mut [RecordingEvent], track: &[WindowTrackEntry]) {
+    let Some(first) = track.first() else {
+        return;
+    };
+    if track.iter().all(|entry| entry.rect[0] == first.rect[0] && entry.rect[1] == first.rect[1]) {
+        return;
+    }
+    for event in events.iter_mut() {
+        let t = crate::engine::analyzer::event_timestamp(event);
+        let Some(rect) = rect_at(track, t) else {
+            continue;
+        };
+        let (dx, dy) = (first.rect[0] - rect[0], first.rect[1] - rect[1]);
+        if dx == 0.0 && dy == 0.0 {
+            continue;
+        }
+        shift_event(event, dx, dy);
+    }
+}
+
+fn shift_event(event: &mut RecordingEvent, dx: f64, dy: f64) {
+    let shift_rect = |rect: &mut [f64; 4]| {
+        *rect = [rect[0] + dx, rect[1] + dy, rect[2] + dx, rect[3] + dy];
+    };
+    match event {
+        RecordingEvent::MouseMove { x, y, .. }
+        | RecordingEvent::Click { x, y, .. }
+        | RecordingEvent::ClickRelease { x, y, .. }
+        | RecordingEvent::Scroll { x, y, .. } => {
+            *x += dx;
+            *y += dy;
+        }
+        RecordingEvent::Focus { rect, .. }
+        | RecordingEvent::WindowFocus { rect, .. }
+        | RecordingEvent::UiFocus { rect, .. }
+        | RecordingEvent::UiMenuOpen { rect, .. }
+        | RecordingEvent::UiDialogOpen { rect, .. } => shift_rect(rect),
+        RecordingEvent::Key { .. } | RecordingEvent::UiMenuClose { .. } | RecordingEvent::UiDialogClose { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_follow_moved_window() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut writer = WindowTrackWriter::create(dir.path());
+        writer.record(0, [100.0, 100.0, 900.0, 700.0]);
+        writer.record(500, [100.0, 100.0, 900.0, 700.0]);
+        writer.record(1000, [300.0, 150.0, 1100.0, 750.0]);
+        drop(writer);
+        let track = load_window_track(dir.path());
+        assert_eq!(track.len(), 2);
+
+        let mut events = vec![
+            RecordingEvent::Click { t: 800, btn: "left".into(), x: 150.0, y: 120.0 },
+            RecordingEvent::Click { t: 1200, btn: "left".into(), x: 350.0, y: 170.0 },
+            RecordingEvent::UiMenuOpen {
+                t: 1500,
+                control: "Menu".into(),
+                name: "File".into(),
+                rect: [300.0, 180.0, 400.0, 300.0],
+            },
+        ];
+        follow_window(&mut events, &track);
+
+        // The same spot of the window before and after the move
+        match (&events[0], &events[1]) {
+            (RecordingEvent::Click { x: x0, y: y0, .. }, RecordingEvent::Click { x: x1, y: y1, .. }) => {
+                assert_eq!((*x0, *y0), (150.0, 120.0));
+                assert_eq!((*x1, *y1), (150.0, 120.0));
+            }
+            _ => unreachable!(),
+        }
+        match &events[2] {
+            RecordingEvent::UiMenuOpen { rect, .. } => assert_eq!(*rect, [100.0, 130.0, 200.0, 250.0]),
+            _ => unreachable!(),
+        }
+    }
+}
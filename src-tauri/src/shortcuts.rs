@@ -1,20 +1,390 @@
+//! Global hotkeys: record, pause/resume, cancel, screenshot and saving the
+//! replay buffer, each set in [`RecordingSettings`] (an empty accelerator
+//! disables it).
+//!
+//! Accelerators are checked before saving: they must parse, include a
+//! modifier (except function keys and Print Screen/Pause, which don't get
+//! typed), not be a combination Windows keeps for itself, and no two
+//! actions may share one. [`register`] replaces the registered hotkeys
+//! whenever the settings change; it checks each hotkey on its own and skips
+//! one that breaks these rules (say, from an older settings file), so the
+//! others still work.
+//!
+//! The settings page captures a hotkey by suspending ours ([`suspend`], so
+//! pressing a current hotkey reaches the page) and checks it with [`check`]:
+//! besides the rules above, ones another app has registered are refused,
+//! since those would never fire.
+
+use crate::config::RecordingSettings;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 use tauri::{Emitter, Manager};
 use tauri_plugin_global_shortcut::ShortcutState;
 
-pub fn setup_shortcuts(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HotkeyAction {
+    /// Start or stop recording (the main window decides which)
+    ToggleRecording,
+    PauseResume,
+    /// Stop the recording and delete it
+    Cancel,
+    Screenshot,
+    /// Save the replay buffer as a recording
+    SaveReplay,
+}
+
+impl HotkeyAction {
+    fn label(self) -> &'static str {
+        match self {
+            Self::ToggleRecording => "Record",
+            Self::PauseResume => "Pause/resume",
+            Self::Cancel => "Cancel recording",
+            Self::Screenshot => "Screenshot",
+            Self::SaveReplay => "Save replay",
+        }
+    }
+}
+
+/// The enabled hotkeys in `settings` with their actions.
+pub fn bindings(settings: &RecordingSettings) -> Vec<(HotkeyAction, &str)> {
+    [
+        (HotkeyAction::ToggleRecording, settings.hotkey.as_str()),
+        (HotkeyAction::PauseResume, settings.pause_hotkey.as_str()),
+        (HotkeyAction::Cancel, settings.cancel_hotkey.as_str()),
+        (HotkeyAction::Screenshot, settings.screenshot_hotkey.as_str()),
+        (HotkeyAction::SaveReplay, settings.replay_hotkey.as_str()),
+    ]
+    .into_iter()
+    .filter(|(_, accelerator)| !accelerator.trim().is_empty())
+    .collect()
+}
+
+fn modifier(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "CTRL" | "CONTROL" | "CMDORCTRL" | "CMDORCONTROL" | "COMMANDORCTRL" | "COMMANDORCONTROL" => "Ctrl",
+        "ALT" | "OPTION" => "Alt",
+        "SHIFT" => "Shift",
+        "SUPER" | "WIN" | "META" | "CMD" | "COMMAND" => "Super",
+        _ => return None,
+    })
+}
+
+/// Keys that are fine without a modifier
+const BARE_KEYS: &[&str] = &["PrintScreen", "Pause"];
+
+fn key(name: &str) -> Option<String> {
+    if name.len() == 1 && name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Some(name.to_string());
+    }
+    if let Some(n) = name.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+        return (1..=24).contains(&n).then(|| format!("F{}", n));
+    }
+    Some(
+        match name {
+            "SPACE" => "Space",
+            "TAB" => "Tab",
+            "ENTER" | "RETURN" => "Enter",
+            "BACKSPACE" => "Backspace",
+            "DELETE" | "DEL" => "Delete",
+            "INSERT" => "Insert",
+            "HOME" => "Home",
+            "END" => "End",
+            "PAGEUP" => "PageUp",
+            "PAGEDOWN" => "PageDown",
+            "UP" | "ARROWUP" => "Up",
+            "DOWN" | "ARROWDOWN" => "Down",
+            "LEFT" | "ARROWLEFT" => "Left",
+            "RIGHT" | "ARROWRIGHT" => "Right",
+            "ESCAPE" | "ESC" => "Escape",
+            "PRINTSCREEN" => "PrintScreen",
+            "PAUSE" => "Pause",
+            _ => return None,
+        }
+        .to_string(),
+    )
+}
+
+/// `accelerator` in canonical form ("Ctrl+Alt+Shift+Super+Key"), so that
+/// "ctrl+shift+r" and "Shift+CmdOrCtrl+R" compare equal.
+pub fn normalize(accelerator: &str) -> Result<String> {
+    let mut modifiers: Vec<&str> = Vec::new();
+    let mut main_key: Option<String> = None;
+    for part in accelerator.split('+').map(str::trim) {
+        if part.is_empty() {
+            bail!("Invalid hotkey: \"{}\"", accelerator);
+        }
+        let upper = part.to_ascii_uppercase();
+        if let Some(m) = modifier(&upper) {
+            if !modifiers.contains(&m) {
+                modifiers.push(m);
+            }
+        } else if main_key.is_some() {
+            bail!("Hotkey \"{}\" has more than one key", accelerator);
+        } else {
+            main_key = Some(key(&upper).ok_or_else(|| anyhow::anyhow!("Unknown key \"{}\" in \"{}\"", part, accelerator))?);
+        }
+    }
+    let Some(main_key) = main_key else {
+        bail!("Hotkey \"{}\" has no key", accelerator);
+    };
+    let is_function_key = main_key.len() > 1 && main_key.starts_with('F');
+    if modifiers.is_empty() && !is_function_key && !BARE_KEYS.contains(&main_key.as_str()) {
+        bail!("Hotkey \"{}\" needs Ctrl, Alt, Shift or Win", accelerator);
+    }
+    modifiers.sort_by_key(|m| ["Ctrl", "Alt", "Shift", "Super"].iter().position(|o| o == m));
+    modifiers.push(&main_key);
+    Ok(modifiers.join("+"))
+}
+
+/// Check every hotkey in `settings` with [`check_accelerator`], so saving
+/// enforces the same rules as the settings page.
+pub fn validate(settings: &RecordingSettings) -> Result<()> {
+    for (action, accelerator) in bindings(settings) {
+        check_accelerator(accelerator, action, settings)?;
+    }
+    Ok(())
+}
+
+/// Combinations Windows keeps for itself. Registering some of them even
+/// succeeds, but the key press never reaches the app.
+const OS_RESERVED: &[&str] = &[
+    "Ctrl+Alt+Delete",
+    "Ctrl+Escape",
+    "Ctrl+Shift+Escape",
+    "Alt+Tab",
+    "Alt+Shift+Tab",
+    "Alt+Escape",
+    "Alt+F4",
+    "Super+D",
+    "Super+E",
+    "Super+I",
+    "Super+L",
+    "Super+R",
+    "Super+V",
+    "Super+X",
+    "Super+Tab",
+    "Super+PrintScreen",
+    "Super+Shift+S",
+];
+
+/// [`normalize`], refusing combinations Windows keeps for itself.
+fn normalize_unreserved(accelerator: &str) -> Result<String> {
+    let normalized = normalize(accelerator)?;
+    if OS_RESERVED.contains(&normalized.as_str()) {
+        bail!("{} is reserved by Windows", normalized);
+    }
+    Ok(normalized)
+}
+
+/// The hotkeys in `settings` that can be registered, in canonical form, and
+/// why each of the others can't. Every hotkey is checked on its own; of two
+/// actions sharing one, the first keeps it.
+fn usable_bindings(settings: &RecordingSettings) -> (Vec<(HotkeyAction, String)>, Vec<String>) {
+    let mut usable: Vec<(HotkeyAction, String)> = Vec::new();
+    let mut rejected = Vec::new();
+    for (action, accelerator) in bindings(settings) {
+        let checked = normalize_unreserved(accelerator).and_then(|normalized| {
+            if let Some((other, _)) = usable.iter().find(|(_, n)| *n == normalized) {
+                bail!("{} is already used for {}", normalized, other.label());
+            }
+            Ok(normalized)
+        });
+        match checked {
+            Ok(normalized) => usable.push((action, normalized)),
+            Err(e) => rejected.push(format!("{} ({})", e, action.label())),
+        }
+    }
+    (usable, rejected)
+}
+
+/// `accelerator` in canonical form if it can be used for `action`: valid,
+/// not reserved by Windows and not used by another action in `settings`.
+/// Doesn't check other apps (see [`check`]).
+pub fn check_accelerator(accelerator: &str, action: HotkeyAction, settings: &RecordingSettings) -> Result<String> {
+    let normalized = normalize_unreserved(accelerator)?;
+    for (other, existing) in bindings(settings) {
+        if other != action && normalize(existing).is_ok_and(|e| e == normalized) {
+            bail!("{} is already used for {}", normalized, other.label());
+        }
+    }
+    Ok(normalized)
+}
+
+/// [`check_accelerator`], then make sure the OS will give us the hotkey by
+/// registering it briefly. Returns the canonical form.
+pub fn check(app: &AppHandle, accelerator: &str, action: HotkeyAction, settings: &RecordingSettings) -> Result<String, String> {
+    let normalized = check_accelerator(accelerator, action, settings).map_err(|e| e.to_string())?;
     #[cfg(desktop)]
     {
         use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
-        app.global_shortcut().on_shortcut("CmdOrCtrl+Shift+R", move |app, _shortcut, event| {
-            if event.state == ShortcutState::Pressed {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.emit("shortcut-toggle-recording", ());
+        let shortcuts = app.global_shortcut();
+        // Registered by us: already the hotkey of `action` (other actions were ruled out above)
+        if shortcuts.is_registered(normalized.as_str()) {
+            return Ok(normalized);
+        }
+        shortcuts
+            .register(normalized.as_str())
+            .map_err(|e| format!("{} is in use by another app ({})", normalized, e))?;
+        if let Err(e) = shortcuts.unregister(normalized.as_str()) {
+            log::warn!("Failed to unregister test hotkey {}: {}", normalized, e);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Unregister all our hotkeys until the next [`register`], so the settings
+/// page can capture key presses that are hotkeys now.
+pub fn suspend(app: &AppHandle) -> Result<(), String> {
+    #[cfg(desktop)]
+    {
+        use tauri_plugin_global_shortcut::GlobalShortcutExt;
+        app.global_shortcut().unregister_all().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn on_action(app: &AppHandle, action: HotkeyAction) {
+    // Record, pause and cancel go through the main window, which tracks the
+    // recording state and timer
+    let event = match action {
+        HotkeyAction::ToggleRecording => "shortcut-toggle-recording",
+        HotkeyAction::PauseResume => "shortcut-toggle-pause",
+        HotkeyAction::Cancel => "shortcut-cancel-recording",
+        HotkeyAction::Screenshot => {
+            let state = app.state::<crate::commands::AppState>();
+            let settings = state.settings.lock().map(|s| s.clone());
+            if let Ok(settings) = settings {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let _ = crate::commands::screenshot(settings, &app).await;
+                });
+            }
+            return;
+        }
+        HotkeyAction::SaveReplay => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = crate::commands::save_replay(&app).await;
+            });
+            return;
+        }
+    };
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(event, ());
+    }
+}
+
+/// Replace the registered hotkeys with those in `settings`. Each hotkey is
+/// checked and registered on its own: one that breaks the rules (see
+/// [`validate`]) or that the OS refuses (usually taken by another app) is
+/// skipped and named in the error; the others stay registered.
+pub fn register(app: &AppHandle, settings: &RecordingSettings) -> Result<(), String> {
+    let (usable, mut failed) = usable_bindings(settings);
+    for rejected in &failed {
+        log::warn!("Skipping hotkey: {}", rejected);
+    }
+    #[cfg(desktop)]
+    {
+        use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+        let shortcuts = app.global_shortcut();
+        shortcuts.unregister_all().map_err(|e| e.to_string())?;
+        for (action, normalized) in usable {
+            let result = shortcuts.on_shortcut(normalized.as_str(), move |app, _shortcut, event| {
+                if event.state == ShortcutState::Pressed {
+                    on_action(app, action);
                 }
+            });
+            if let Err(e) = result {
+                log::warn!("Failed to register {} hotkey {}: {}", action.label(), normalized, e);
+                failed.push(format!("{} ({})", normalized, action.label()));
             }
-        })?;
+        }
+    }
+    if !failed.is_empty() {
+        return Err(format!("Could not register hotkeys: {}", failed.join(", ")));
     }
+    Ok(())
+}
 
+pub fn setup_shortcuts(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let state = app.state::<crate::commands::AppState>();
+    let settings = state.settings.lock().map(|s| s.recording.clone()).map_err(|e| e.to_string())?;
+    // A taken or invalid hotkey must not keep the app from starting
+    if let Err(e) = register(app, &settings) {
+        log::warn!("{}", e);
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_validate() {
+        assert_eq!(normalize("shift + cmdorctrl + r").unwrap(), "Ctrl+Shift+R");
+        assert_eq!(normalize("Alt+Ctrl+esc").unwrap(), "Ctrl+Alt+Escape");
+        assert_eq!(normalize("F9").unwrap(), "F9");
+        assert!(normalize("R").is_err());
+        assert!(normalize("Ctrl+Shift").is_err());
+        assert!(normalize("Ctrl+A+B").is_err());
+        assert!(normalize("Ctrl++").is_err());
+        assert!(normalize("Ctrl+F25").is_err());
+
+        let mut settings = RecordingSettings::default();
+        assert!(validate(&settings).is_ok());
+        // Empty disables the hotkey, so it can't clash
+        assert!(settings.pause_hotkey.is_empty() && settings.cancel_hotkey.is_empty());
+        assert_eq!(bindings(&settings).len(), 2);
+        settings.cancel_hotkey = "CmdOrCtrl+Shift+R".to_string();
+        let err = validate(&settings).unwrap_err().to_string();
+        assert_eq!(err, "Ctrl+Shift+R is already used for Cancel recording");
+        // Saving refuses what the settings page refuses
+        settings.cancel_hotkey = "Alt+F4".to_string();
+        assert_eq!(validate(&settings).unwrap_err().to_string(), "Alt+F4 is reserved by Windows");
+    }
+
+    #[test]
+    fn test_usable_bindings_skip_only_bad_hotkeys() {
+        // A settings file saved before the checks existed
+        let mut settings = RecordingSettings::default();
+        settings.pause_hotkey = "shift+ctrl+r".to_string();
+        settings.cancel_hotkey = "Alt+F4".to_string();
+        settings.replay_hotkey = "Ctrl+Alt+X".to_string();
+
+        let (usable, rejected) = usable_bindings(&settings);
+        let actions: Vec<HotkeyAction> = usable.iter().map(|(action, _)| *action).collect();
+        assert_eq!(
+            actions,
+            [HotkeyAction::ToggleRecording, HotkeyAction::Screenshot, HotkeyAction::SaveReplay]
+        );
+        assert_eq!(usable[2].1, "Ctrl+Alt+X");
+        assert_eq!(
+            rejected,
+            [
+                "Ctrl+Shift+R is already used for Record (Pause/resume)",
+                "Alt+F4 is reserved by Windows (Cancel recording)",
+            ]
+        );
+        // Saving still refuses the whole set
+        assert!(validate(&settings).is_err());
+    }
+
+    #[test]
+    fn test_check_accelerator() {
+        let settings = RecordingSettings::default();
+        // Its own hotkey is fine, another action's is not
+        assert_eq!(
+            check_accelerator("shift+ctrl+r", HotkeyAction::ToggleRecording, &settings).unwrap(),
+            "Ctrl+Shift+R"
+        );
+        let err = check_accelerator("Ctrl+Shift+R", HotkeyAction::Cancel, &settings).unwrap_err();
+        assert_eq!(err.to_string(), "Ctrl+Shift+R is already used for Record");
+        assert!(check_accelerator("Win+L", HotkeyAction::Cancel, &settings).is_err());
+        assert!(check_accelerator("Alt+F4", HotkeyAction::Cancel, &settings).is_err());
+        assert_eq!(check_accelerator("Ctrl+Alt+X", HotkeyAction::Cancel, &settings).unwrap(), "Ctrl+Alt+X");
+    }
+}
@@ -1,16 +1,139 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
 use tauri::{
     image::Image,
-    menu::{Menu, MenuItem},
+    menu::{IsMenuItem, Menu, MenuItem},
     tray::TrayIconBuilder,
-    AppHandle, Emitter, Manager,
+    AppHandle, Emitter, Manager, Wry,
 };
 
-pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    let start_recording = MenuItem::with_id(app, "start_recording", "Start Recording (Ctrl+Shift+R)", true, None::<&str>)?;
+const TRAY_ID: &str = "main";
+/// Menu id prefix for "record with template" items
+const TEMPLATE_ITEM_PREFIX: &str = "template:";
+
+/// Whether a recording is running: the first item then stops it
+static RECORDING: AtomicBool = AtomicBool::new(false);
+/// Seconds left before a recording starts (0 = no countdown): the first
+/// item then cancels it
+static COUNTDOWN: AtomicU32 = AtomicU32::new(0);
+/// Label of the next scheduled recording, shown below the first item
+static NEXT_SCHEDULE: Mutex<Option<String>> = Mutex::new(None);
+
+const DEFAULT_TOOLTIP: &str = "Snappi - Screen Recorder";
+
+/// `label (hotkey)`, or just `label` while the hotkey is disabled
+fn with_hotkey(label: &str, hotkey: &str) -> String {
+    if hotkey.is_empty() {
+        label.to_string()
+    } else {
+        format!("{} ({})", label, hotkey)
+    }
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let (hotkey, templates) = app
+        .state::<crate::commands::AppState>()
+        .settings
+        .lock()
+        .map(|s| (s.recording.hotkey.clone(), s.templates.clone()))
+        .unwrap_or_default();
+
+    let countdown = COUNTDOWN.load(Ordering::SeqCst);
+    let label = if countdown > 0 {
+        format!("Cancel Countdown ({})", countdown)
+    } else if RECORDING.load(Ordering::SeqCst) {
+        with_hotkey("Stop Recording", &hotkey)
+    } else {
+        with_hotkey("Start Recording", &hotkey)
+    };
+    let start_recording = MenuItem::with_id(app, "start_recording", label, true, None::<&str>)?;
     let settings = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
     let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
 
-    let menu = Menu::with_items(app, &[&start_recording, &settings, &quit])?;
+    let template_items = templates
+        .iter()
+        .map(|t| {
+            MenuItem::with_id(
+                app,
+                format!("{}{}", TEMPLATE_ITEM_PREFIX, t.id),
+                format!("Record: {}", t.name),
+                true,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+
+    let next_schedule = NEXT_SCHEDULE
+        .lock()
+        .ok()
+        .and_then(|next| next.clone())
+        .map(|label| MenuItem::with_id(app, "next_schedule", format!("Scheduled: {}", label), false, None::<&str>))
+        .transpose()?;
+
+    let mut items: Vec<&dyn IsMenuItem<Wry>> = vec![&start_recording];
+    if let Some(ref item) = next_schedule {
+        items.push(item);
+    }
+    for item in &template_items {
+        items.push(item);
+    }
+    items.push(&settings);
+    items.push(&quit);
+    Menu::with_items(app, &items)
+}
+
+/// Rebuild the tray menu (after templates or hotkeys changed).
+pub fn refresh_menu(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        tray.set_menu(Some(build_menu(app)?))?;
+    }
+    Ok(())
+}
+
+/// Switch the first item between "Start Recording" and "Stop Recording"
+/// when a recording starts or stops (including automatic stops).
+pub fn set_recording(app: &AppHandle, recording: bool) {
+    RECORDING.store(recording, Ordering::SeqCst);
+    if !recording {
+        notify(app, DEFAULT_TOOLTIP);
+    }
+    if let Err(e) = refresh_menu(app) {
+        log::warn!("Failed to update tray menu: {}", e);
+    }
+}
+
+/// Show the seconds left before the recording starts on the first item
+/// (0 when the countdown ends or is cancelled).
+pub fn set_countdown(app: &AppHandle, remaining: u32) {
+    COUNTDOWN.store(remaining, Ordering::SeqCst);
+    if let Err(e) = refresh_menu(app) {
+        log::warn!("Failed to update tray menu: {}", e);
+    }
+}
+
+/// Show the next scheduled recording (`None`: nothing scheduled).
+pub fn set_next_schedule(app: &AppHandle, label: Option<String>) {
+    if let Ok(mut next) = NEXT_SCHEDULE.lock() {
+        *next = label;
+    }
+    if let Err(e) = refresh_menu(app) {
+        log::warn!("Failed to update tray menu: {}", e);
+    }
+}
+
+/// Tell the user about something that happened in the background (e.g. a
+/// scheduled recording started) through the tray tooltip, until the
+/// recording stops.
+pub fn notify(app: &AppHandle, message: &str) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_tooltip(Some(message)) {
+            log::warn!("Failed to update tray tooltip: {}", e);
+        }
+    }
+}
+
+pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_menu(app)?;
 
     // Embed icon at compile time and decode to RGBA
     let icon_png = include_bytes!("../icons/icon.png");
@@ -18,10 +141,10 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     let (width, height) = img.dimensions();
     let icon = Image::new_owned(img.into_raw(), width, height);
 
-    TrayIconBuilder::new()
+    TrayIconBuilder::with_id(TRAY_ID)
         .icon(icon)
         .menu(&menu)
-        .tooltip("Snappi - Screen Recorder")
+        .tooltip(DEFAULT_TOOLTIP)
         .on_menu_event(|app, event| match event.id.as_ref() {
             "start_recording" => {
                 if let Some(window) = app.get_webview_window("main") {
@@ -38,6 +161,12 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
             "quit" => {
                 app.exit(0);
             }
+            id if id.starts_with(TEMPLATE_ITEM_PREFIX) => {
+                if let Some(window) = app.get_webview_window("main") {
+                    let template_id = &id[TEMPLATE_ITEM_PREFIX.len()..];
+                    let _ = window.emit("tray-start-template", template_id.to_string());
+                }
+            }
             _ => {}
         })
         .build(app)?;
@@ -6,13 +6,33 @@ import Settings from "./pages/Settings";
 import RecordingBar from "./components/RecordingBar";
 import {
   startRecording,
+  startRetake,
+  appendToRecording,
+  startRecordingFromTemplate,
+  cancelCountdown,
   stopRecording,
+  cancelRecording,
+  pauseRecording,
+  resumeRecording,
   getRecordingState,
 } from "./lib/commands";
-import type { RecordingState } from "./lib/types";
+import type { DiskSpaceWarning, LiveEvent, RecordingState } from "./lib/types";
+import type { FinalizingProgress } from "./lib/generated/FinalizingProgress";
 
 type Page = "list" | "preview" | "settings";
 
+const finalizingLabel = (p: FinalizingProgress | null) => {
+  switch (p?.stage) {
+    case "flushing": return "録画データを書き出し中...";
+    case "writing_meta": return "メタデータを保存中...";
+    case "compacting": return "中間ファイルを整理中...";
+    case "appending": return "元の録画に継ぎ足し中...";
+    case "thumbnail": return "サムネイルを生成中...";
+    case "complete": return "完了";
+    default: return "録画を終了しています...";
+  }
+};
+
 function App() {
   const [page, setPage] = createSignal<Page>("list");
   const [recordingState, setRecordingState] =
@@ -21,6 +41,11 @@ function App() {
     string | null
   >(null);
   const [elapsed, setElapsed] = createSignal(0);
+  const [countdown, setCountdown] = createSignal(0);
+  const [finalizing, setFinalizing] = createSignal<FinalizingProgress | null>(null);
+  const [lastLiveEvent, setLastLiveEvent] = createSignal<LiveEvent | null>(null);
+  // 録画中の警告（空き容量不足・録画時間の上限）
+  const [notice, setNotice] = createSignal<string | null>(null);
   let timerRef: number | undefined;
 
   onMount(async () => {
@@ -30,24 +55,122 @@ function App() {
     await listen("tray-start-recording", () => handleToggleRecording());
     await listen("tray-open-settings", () => setPage("settings"));
     await listen("shortcut-toggle-recording", () => handleToggleRecording());
+    await listen("shortcut-toggle-pause", () => handleTogglePause());
+    await listen("shortcut-cancel-recording", () => handleCancelRecording());
+    await listen<string>("tray-start-template", (e) => handleStartTemplate(e.payload));
+    // 開始コマンドはカウントダウン中にすぐ戻り、実際の開始は recording-started で届く
+    await listen<number>("recording-countdown", (e) => {
+      setCountdown(e.payload);
+      if (e.payload > 0) setRecordingState("Countdown");
+      else if (recordingState() === "Countdown") setRecordingState("Idle");
+    });
+    await listen("recording-started", () => beginRecordingTimer());
+    await listen<string>("recording-start-failed", (e) => {
+      setRecordingState("Idle");
+      setNotice(`録画を開始できませんでした: ${e.payload}`);
+    });
+    await listen<FinalizingProgress>("recording-finalizing", (e) => setFinalizing(e.payload));
+    await listen<LiveEvent[]>("recording-live-events", (e) => {
+      const last = e.payload[e.payload.length - 1];
+      if (last) setLastLiveEvent(last);
+    });
+    // 空き容量不足・上限到達ではバックエンドが録画を停止・確定する
+    const stopping = () => {
+      if (timerRef) clearInterval(timerRef);
+      setRecordingState("Processing");
+    };
+    await listen<DiskSpaceWarning>("recording-warning", (e) => {
+      const w = e.payload;
+      if (w.level === "Critical") {
+        setNotice(`ディスクの空き容量が不足したため録画を停止しました（残り ${w.free_mb} MB）`);
+        stopping();
+      } else {
+        setNotice(`ディスクの空き容量が少なくなっています（残り ${w.free_mb} MB）。${w.min_free_mb} MB を下回ると録画を停止します`);
+      }
+    });
+    await listen<number>("recording-limit-warning", (e) =>
+      setNotice(`録画時間の上限まであと ${e.payload} 秒です`)
+    );
+    await listen<number>("recording-limit-reached", (e) => {
+      setNotice(`録画時間の上限（${Math.round(e.payload / 60)} 分）に達したため録画を停止しました`);
+      stopping();
+    });
+    await listen<string>("recording-auto-stopped", (e) => {
+      setFinalizing(null);
+      setRecordingState("Idle");
+      setCurrentRecordingId(e.payload);
+      setPage("preview");
+    });
+    await listen<string>("stream-ended", (e) => setNotice(`ライブ配信が停止しました: ${e.payload}`));
+    // 予約録画の開始は recording-started でも届く（タイマーはそちらで始まる）
+    await listen("scheduled-recording-started", () => setNotice("予約録画を開始しました"));
+    await listen<{ start_time: string }>("scheduled-recording-missed", (e) =>
+      setNotice(`予約録画（${new Date(e.payload.start_time).toLocaleString()}）を開始できませんでした`)
+    );
+    await listen<{ output_path: string }>("screenshot-saved", (e) =>
+      setNotice(`スクリーンショットを保存しました: ${e.payload.output_path}`)
+    );
+    await listen("replay-saved", () => setNotice("リプレイを録画一覧に保存しました"));
   });
 
+  const beginRecordingTimer = () => {
+    if (timerRef) clearInterval(timerRef);
+    setRecordingState("Recording");
+    setNotice(null);
+    setElapsed(0);
+    timerRef = window.setInterval(
+      () => setElapsed((e) => e + 1),
+      1000
+    );
+  };
+
+  const handleStartTemplate = async (templateId: string) => {
+    if (recordingState() !== "Idle") return;
+    try {
+      await startRecordingFromTemplate(templateId);
+    } catch (e) {
+      console.error("Failed to start recording from template:", e);
+    }
+  };
+
+  // 再録画: 新しい録画を元の録画と同じテイクグループに入れる
+  const handleRetake = async (recordingId: string | null) => {
+    if (recordingState() !== "Idle") return;
+    if (!recordingId) return handleToggleRecording();
+    try {
+      await startRetake(recordingId);
+    } catch (e) {
+      console.error("Failed to start retake:", e);
+    }
+  };
+
+  // 続きを録画: 停止時に元の録画の末尾へ継ぎ足す
+  const handleContinue = async (recordingId: string | null) => {
+    if (recordingState() !== "Idle" || !recordingId) return;
+    try {
+      await appendToRecording(recordingId);
+    } catch (e) {
+      console.error("Failed to continue recording:", e);
+    }
+  };
+
   const handleToggleRecording = async () => {
     const state = recordingState();
     if (state === "Idle") {
       try {
         await startRecording();
-        setRecordingState("Recording");
-        setElapsed(0);
-        timerRef = window.setInterval(
-          () => setElapsed((e) => e + 1),
-          1000
-        );
       } catch (e) {
         console.error("Failed to start recording:", e);
       }
+    } else if (state === "Countdown") {
+      try {
+        await cancelCountdown();
+      } catch (e) {
+        console.error("Failed to cancel countdown:", e);
+      }
     } else if (state === "Recording" || state === "Paused") {
       if (timerRef) clearInterval(timerRef);
+      setRecordingState("Processing");
       try {
         const id = await stopRecording();
         setRecordingState("Idle");
@@ -57,12 +180,82 @@ function App() {
         console.error("Failed to stop recording:", e);
         // Reset state so UI doesn't get stuck in Recording mode
         setRecordingState("Idle");
+      } finally {
+        setFinalizing(null);
+      }
+    }
+  };
+
+  const handleTogglePause = async () => {
+    const state = recordingState();
+    try {
+      if (state === "Recording") {
+        await pauseRecording();
+        setRecordingState("Paused");
+        if (timerRef) clearInterval(timerRef);
+      } else if (state === "Paused") {
+        await resumeRecording();
+        setRecordingState("Recording");
+        timerRef = window.setInterval(
+          () => setElapsed((e) => e + 1),
+          1000
+        );
       }
+    } catch (e) {
+      console.error("Failed to pause/resume recording:", e);
+    }
+  };
+
+  // 取り消しホットキー: 録画を保存せずに破棄する
+  const handleCancelRecording = async () => {
+    const state = recordingState();
+    if (state === "Countdown") return handleToggleRecording();
+    if (state !== "Recording" && state !== "Paused") return;
+    if (timerRef) clearInterval(timerRef);
+    setRecordingState("Processing");
+    try {
+      await cancelRecording();
+      setNotice("録画を取り消しました");
+    } catch (e) {
+      console.error("Failed to cancel recording:", e);
+    } finally {
+      setRecordingState("Idle");
+      setFinalizing(null);
     }
   };
 
   return (
     <div class="min-h-screen bg-slate-900 text-slate-200">
+      <Show when={countdown() > 0}>
+        <div class="fixed inset-0 z-50 flex flex-col items-center justify-center gap-6 bg-black/60">
+          <span class="text-8xl font-bold text-white">{countdown()}</span>
+          <button
+            class="rounded-lg bg-slate-700/80 px-4 py-2 text-sm text-slate-200 hover:bg-slate-600"
+            onClick={() => handleToggleRecording()}
+          >
+            キャンセル
+          </button>
+        </div>
+      </Show>
+      <Show when={notice()}>
+        <div class="fixed top-2 left-1/2 z-50 -translate-x-1/2 rounded-lg bg-amber-600/90 px-4 py-2 text-sm text-white shadow-lg">
+          {notice()}
+          <button class="ml-3 text-white/80 hover:text-white" onClick={() => setNotice(null)}>×</button>
+        </div>
+      </Show>
+      <Show when={recordingState() === "Processing"}>
+        <div class="fixed inset-0 z-50 flex items-center justify-center bg-black/60">
+          <div class="w-72 rounded-lg bg-slate-800 p-4 shadow-xl">
+            <p class="mb-2 text-sm text-slate-200">{finalizingLabel(finalizing())}</p>
+            <div class="h-2 overflow-hidden rounded bg-slate-700">
+              <div
+                class="h-full bg-blue-500 transition-all"
+                style={{ width: `${Math.round((finalizing()?.progress ?? 0) * 100)}%` }}
+              />
+            </div>
+          </div>
+        </div>
+      </Show>
       <Show
         when={
           recordingState() === "Recording" || recordingState() === "Paused"
@@ -70,25 +263,10 @@ function App() {
       >
         <RecordingBar
           elapsed={elapsed()}
+          lastEvent={lastLiveEvent()}
           isPaused={recordingState() === "Paused"}
           onStop={handleToggleRecording}
-          onPause={async () => {
-            const { pauseRecording, resumeRecording } = await import(
-              "./lib/commands"
-            );
-            if (recordingState() === "Recording") {
-              await pauseRecording();
-              setRecordingState("Paused");
-              if (timerRef) clearInterval(timerRef);
-            } else {
-              await resumeRecording();
-              setRecordingState("Recording");
-              timerRef = window.setInterval(
-                () => setElapsed((e) => e + 1),
-                1000
-              );
-            }
-          }}
+          onPause={handleTogglePause}
         />
       </Show>
 
@@ -111,7 +289,8 @@ function App() {
             setCurrentRecordingId(null);
             setPage("list");
           }}
-          onRedo={handleToggleRecording}
+          onRedo={() => handleRetake(currentRecordingId())}
+          onContinue={() => handleContinue(currentRecordingId())}
         />
       </Show>
 
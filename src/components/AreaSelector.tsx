@@ -0,0 +1,90 @@
+import { createSignal, Show, onMount, onCleanup } from "solid-js";
+import { finishAreaSelection } from "../lib/commands";
+
+interface Point {
+  x: number;
+  y: number;
+}
+
+/**
+ * 全画面の透明オーバーレイで録画範囲をドラッグして選ぶ（select_area 用のウィンドウ）。
+ * 結果はウィンドウ内の物理ピクセルで finish_area_selection に渡し、Esc でキャンセルする
+ */
+export default function AreaSelector() {
+  const [start, setStart] = createSignal<Point | null>(null);
+  const [current, setCurrent] = createSignal<Point | null>(null);
+
+  const cancel = () => finishAreaSelection(null);
+
+  onMount(() => {
+    // 既定の背景色を消してデスクトップを透かして見せる
+    document.documentElement.style.background = "transparent";
+    document.body.style.background = "transparent";
+    const onKey = (e: KeyboardEvent) => {
+      if (e.key === "Escape") cancel();
+    };
+    window.addEventListener("keydown", onKey);
+    onCleanup(() => window.removeEventListener("keydown", onKey));
+  });
+
+  const rect = () => {
+    const a = start();
+    const b = current();
+    if (!a || !b) return null;
+    return {
+      left: Math.min(a.x, b.x),
+      top: Math.min(a.y, b.y),
+      width: Math.abs(a.x - b.x),
+      height: Math.abs(a.y - b.y),
+    };
+  };
+
+  const handleMouseUp = () => {
+    const r = rect();
+    setStart(null);
+    if (!r) return;
+    const dpr = window.devicePixelRatio || 1;
+    finishAreaSelection([r.left * dpr, r.top * dpr, (r.left + r.width) * dpr, (r.top + r.height) * dpr]);
+  };
+
+  return (
+    <div
+      class="fixed inset-0 cursor-crosshair select-none bg-black/30"
+      onMouseDown={(e) => {
+        if (e.button !== 0) return cancel();
+        setStart({ x: e.clientX, y: e.clientY });
+        setCurrent({ x: e.clientX, y: e.clientY });
+      }}
+      onMouseMove={(e) => {
+        if (start()) setCurrent({ x: e.clientX, y: e.clientY });
+      }}
+      onMouseUp={handleMouseUp}
+      onContextMenu={(e) => e.preventDefault()}
+    >
+      <Show
+        when={start() && rect()}
+        fallback={
+          <div class="absolute top-6 left-1/2 -translate-x-1/2 rounded-lg bg-slate-900/80 px-4 py-2 text-sm text-slate-200">
+            録画する範囲をドラッグで選択してください（Esc / 右クリックでキャンセル）
+          </div>
+        }
+      >
+        {(r) => (
+          <div
+            class="absolute border-2 border-purple-400 bg-white/5"
+            style={{
+              left: `${r().left}px`,
+              top: `${r().top}px`,
+              width: `${r().width}px`,
+              height: `${r().height}px`,
+            }}
+          >
+            <span class="absolute -top-6 left-0 rounded bg-slate-900/80 px-2 text-xs text-slate-200">
+              {Math.round(r().width * (window.devicePixelRatio || 1))} × {Math.round(r().height * (window.devicePixelRatio || 1))}
+            </span>
+          </div>
+        )}
+      </Show>
+    </div>
+  );
+}
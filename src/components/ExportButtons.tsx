@@ -1,34 +1,59 @@
+import { For, Show } from "solid-js";
 import type { ExportFormat } from "../lib/types";
 
 interface Props {
   onExport: (format: ExportFormat) => void;
+  /** エフェクトなしで録画フレームをそのまま MP4 に書き出す */
+  onPlainExport?: () => void;
   exporting: boolean;
+  /** 目立たせて先頭に置く形式（既定: MP4） */
+  primary?: ExportFormat;
 }
 
+/** 書き出し形式とボタンの表示名 */
+const FORMATS: [ExportFormat, string][] = [
+  ["Mp4", "MP4"],
+  ["Gif", "GIF"],
+  ["WebM", "WebM"],
+  ["WebP", "WebP"],
+  ["Apng", "APNG"],
+  ["Mov", "MOV"],
+  ["AudioOnly", "音声"],
+];
+
 export default function ExportButtons(props: Props) {
+  const primary = () => props.primary ?? "Mp4";
+  const label = (format: ExportFormat) => FORMATS.find(([f]) => f === format)?.[1] ?? format;
   return (
     <div class="flex gap-2">
       <button
-        onClick={() => props.onExport("Mp4")}
+        onClick={() => props.onExport(primary())}
         disabled={props.exporting}
         class="py-1.5 px-4 rounded-lg font-medium transition-all bg-gradient-to-r from-purple-500 to-blue-500 hover:from-purple-600 hover:to-blue-600 text-white shadow-lg shadow-purple-500/20 disabled:opacity-50 disabled:cursor-not-allowed text-sm"
       >
-        {props.exporting ? "書出し中..." : "MP4で書出し"}
-      </button>
-      <button
-        onClick={() => props.onExport("Gif")}
-        disabled={props.exporting}
-        class="py-1.5 px-3 rounded-lg font-medium transition-all border border-slate-700 text-slate-300 hover:bg-slate-800 disabled:opacity-50 disabled:cursor-not-allowed text-sm"
-      >
-        GIF
-      </button>
-      <button
-        onClick={() => props.onExport("WebM")}
-        disabled={props.exporting}
-        class="py-1.5 px-3 rounded-lg font-medium transition-all border border-slate-700 text-slate-300 hover:bg-slate-800 disabled:opacity-50 disabled:cursor-not-allowed text-sm"
-      >
-        WebM
+        {props.exporting ? "書出し中..." : `${label(primary())}で書出し`}
       </button>
+      <For each={FORMATS.filter(([format]) => format !== primary())}>
+        {([format, name]) => (
+          <button
+            onClick={() => props.onExport(format)}
+            disabled={props.exporting}
+            class="py-1.5 px-3 rounded-lg font-medium transition-all border border-slate-700 text-slate-300 hover:bg-slate-800 disabled:opacity-50 disabled:cursor-not-allowed text-sm"
+          >
+            {name}
+          </button>
+        )}
+      </For>
+      <Show when={props.onPlainExport}>
+        <button
+          onClick={() => props.onPlainExport!()}
+          disabled={props.exporting}
+          title="エフェクトを適用せず、録画したままの解像度で MP4 に書き出します"
+          class="py-1.5 px-3 rounded-lg font-medium transition-all border border-slate-700 text-slate-300 hover:bg-slate-800 disabled:opacity-50 disabled:cursor-not-allowed text-sm"
+        >
+          素のMP4
+        </button>
+      </Show>
     </div>
   );
 }
@@ -0,0 +1,116 @@
+import { createSignal, Show } from "solid-js";
+import { beginHotkeyCapture, endHotkeyCapture, validateHotkey } from "../lib/commands";
+import type { HotkeyAction } from "../lib/types";
+
+interface Props {
+  value: string;
+  action: HotkeyAction;
+  /** 確認済みのホットキー（空文字 = 無効） */
+  onChange: (accelerator: string) => void;
+}
+
+const NAMED_KEYS: Record<string, string> = {
+  " ": "Space",
+  Tab: "Tab",
+  Enter: "Enter",
+  Backspace: "Backspace",
+  Delete: "Delete",
+  Insert: "Insert",
+  Home: "Home",
+  End: "End",
+  PageUp: "PageUp",
+  PageDown: "PageDown",
+  ArrowUp: "Up",
+  ArrowDown: "Down",
+  ArrowLeft: "Left",
+  ArrowRight: "Right",
+  Escape: "Escape",
+  PrintScreen: "PrintScreen",
+  Pause: "Pause",
+};
+
+/** キーボードイベントをアクセラレータ文字列にする。修飾キーだけのときは null */
+const toAccelerator = (e: KeyboardEvent): string | null => {
+  let key: string | undefined;
+  if (/^Key[A-Z]$/.test(e.code)) key = e.code.slice(3);
+  else if (/^Digit[0-9]$/.test(e.code)) key = e.code.slice(5);
+  else if (/^F([1-9]|1[0-9]|2[0-4])$/.test(e.key)) key = e.key;
+  else key = NAMED_KEYS[e.key];
+  if (!key) return null;
+  const parts = [];
+  if (e.ctrlKey) parts.push("Ctrl");
+  if (e.altKey) parts.push("Alt");
+  if (e.shiftKey) parts.push("Shift");
+  if (e.metaKey) parts.push("Super");
+  return [...parts, key].join("+");
+};
+
+/** 押したキーをそのまま登録するホットキー入力欄。保存前にバックエンドで使えるか確認する */
+export default function HotkeyInput(props: Props) {
+  const [capturing, setCapturing] = createSignal(false);
+  const [error, setError] = createSignal<string | null>(null);
+  let ref: HTMLButtonElement | undefined;
+
+  const start = async () => {
+    if (capturing()) return;
+    setError(null);
+    try {
+      // 今のホットキーを押してもここに届くよう、登録中のものを止める
+      await beginHotkeyCapture();
+      setCapturing(true);
+      ref?.focus();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const stop = async () => {
+    if (!capturing()) return;
+    setCapturing(false);
+    try {
+      await endHotkeyCapture();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleKeyDown = async (e: KeyboardEvent) => {
+    if (!capturing()) return;
+    e.preventDefault();
+    const plain = !e.ctrlKey && !e.altKey && !e.shiftKey && !e.metaKey;
+    // 修飾キーなしの Esc で取りやめ、Backspace / Delete で無効にする
+    if (plain && e.key === "Escape") return stop();
+    if (plain && (e.key === "Backspace" || e.key === "Delete")) {
+      props.onChange("");
+      setError(null);
+      return stop();
+    }
+    const accelerator = toAccelerator(e);
+    if (!accelerator) return;
+    try {
+      props.onChange(await validateHotkey(accelerator, props.action));
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+    await stop();
+  };
+
+  return (
+    <div class="flex flex-col items-end gap-1">
+      <button
+        ref={ref}
+        type="button"
+        onClick={start}
+        onKeyDown={handleKeyDown}
+        onBlur={stop}
+        class={`rounded-lg px-3 py-1 text-sm w-40 text-left ${capturing() ? "bg-purple-700/60 text-white" : "bg-slate-700"}`}
+      >
+        {capturing() ? "キーを押してください…" : props.value || "なし"}
+      </button>
+      <Show when={error()}>
+        <span class="text-xs text-red-400">{error()}</span>
+      </Show>
+    </div>
+  );
+}
@@ -1,5 +1,10 @@
+import { Show } from "solid-js";
+import type { LiveEvent } from "../lib/types";
+
 interface Props {
   elapsed: number;
+  /** 直近に記録された入力 */
+  lastEvent?: LiveEvent | null;
   isPaused: boolean;
   onStop: () => void;
   onPause: () => void;
@@ -12,6 +17,14 @@ export default function RecordingBar(props: Props) {
     return `${String(m).padStart(2, "0")}:${String(s).padStart(2, "0")}`;
   };
 
+  const eventLabel = (e: LiveEvent) => {
+    switch (e.type) {
+      case "click": return `クリック (${e.btn})`;
+      case "key": return [...e.modifiers, e.key].join("+");
+      case "window_focus": return e.title;
+    }
+  };
+
   return (
     <div class="fixed top-4 left-1/2 -translate-x-1/2 z-50">
       <div class="flex items-center gap-3 bg-slate-900/95 backdrop-blur-sm border border-slate-700/50 rounded-full px-4 py-2 shadow-2xl">
@@ -20,6 +33,11 @@ export default function RecordingBar(props: Props) {
           <span class="text-xs font-medium text-slate-300">{props.isPaused ? "PAUSED" : "REC"}</span>
         </div>
         <span class="text-sm font-mono text-slate-200 min-w-[48px] text-center">{formatTime(props.elapsed)}</span>
+        <Show when={props.lastEvent}>
+          {(e) => (
+            <span class="text-xs text-slate-400 max-w-[160px] truncate" title="記録中の入力">{eventLabel(e())}</span>
+          )}
+        </Show>
         <div class="w-px h-4 bg-slate-700" />
         <button onClick={props.onPause} class="p-1.5 rounded-full hover:bg-slate-700/50 transition-colors text-slate-400 hover:text-white" title={props.isPaused ? "Resume" : "Pause"}>
           {props.isPaused ? (
@@ -0,0 +1,159 @@
+import { For } from "solid-js";
+import type { SceneInfo, SceneStyleOverride } from "../lib/types";
+
+interface Props {
+  scene: SceneInfo;
+  /** このシーンの上書き（未設定なら undefined） */
+  override?: SceneStyleOverride;
+  /** null で上書きを削除する */
+  onChange: (override: SceneStyleOverride | null) => void;
+  onClose: () => void;
+}
+
+/** 既定（設定に従う）/ 表示 / 非表示 の 3 択 */
+const TOGGLE_OPTIONS: { value: string; label: string }[] = [
+  { value: "default", label: "設定に従う" },
+  { value: "on", label: "表示" },
+  { value: "off", label: "非表示" },
+];
+
+function toggleValue(value: boolean | null | undefined): string {
+  return value == null ? "default" : value ? "on" : "off";
+}
+
+function parseToggle(value: string): boolean | null {
+  return value === "default" ? null : value === "on";
+}
+
+function toHex(color: number[]): string {
+  return "#" + color.slice(0, 3).map((c) => c.toString(16).padStart(2, "0")).join("");
+}
+
+function fromHex(hex: string): number[] {
+  return [1, 3, 5].map((i) => parseInt(hex.slice(i, i + 2), 16));
+}
+
+function formatTime(ms: number): string {
+  const totalSec = ms / 1000;
+  const min = Math.floor(totalSec / 60);
+  const sec = totalSec % 60;
+  return `${min}:${sec.toFixed(1).padStart(4, "0")}`;
+}
+
+/** シーンごとの書き出しスタイル（背景・角丸・エフェクトの表示）を編集する */
+export default function SceneStyleEditor(props: Props) {
+  const current = (): SceneStyleOverride =>
+    props.override ?? {
+      scene_id: props.scene.id,
+      start_ms: props.scene.start_ms,
+      end_ms: props.scene.end_ms,
+    };
+
+  const update = (changes: Partial<SceneStyleOverride>) => {
+    const next = { ...current(), ...changes };
+    const empty =
+      next.background == null &&
+      next.border_radius == null &&
+      next.click_ring_enabled == null &&
+      next.key_badge_enabled == null;
+    props.onChange(empty ? null : next);
+  };
+
+  const backgroundColor = () => {
+    const bg = current().background;
+    return bg?.type === "Solid" && bg.color ? toHex(bg.color) : "#1e293b";
+  };
+
+  return (
+    <div class="bg-zinc-800/90 border border-zinc-600 rounded-lg p-3 text-xs">
+      <div class="flex items-center justify-between mb-3">
+        <span>
+          <span class="text-purple-300 font-medium">シーン S{props.scene.id} のスタイル</span>
+          <span class="text-zinc-400 ml-1">
+            ({formatTime(props.scene.start_ms)} - {formatTime(props.scene.end_ms)})
+          </span>
+        </span>
+        <button
+          class="p-1 rounded hover:bg-zinc-700 text-zinc-400 hover:text-zinc-200"
+          onClick={props.onClose}
+          title="閉じる"
+        >
+          <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2">
+            <line x1="18" y1="6" x2="6" y2="18" />
+            <line x1="6" y1="6" x2="18" y2="18" />
+          </svg>
+        </button>
+      </div>
+
+      <div class="grid grid-cols-2 gap-x-4 gap-y-2 mb-3">
+        <label class="flex items-center justify-between gap-2">
+          <span class="text-zinc-400">キー表示</span>
+          <select
+            class="bg-zinc-700 rounded px-1.5 py-0.5 text-zinc-200"
+            value={toggleValue(current().key_badge_enabled)}
+            onChange={(e) => update({ key_badge_enabled: parseToggle(e.currentTarget.value) })}
+          >
+            <For each={TOGGLE_OPTIONS}>{(o) => <option value={o.value}>{o.label}</option>}</For>
+          </select>
+        </label>
+        <label class="flex items-center justify-between gap-2">
+          <span class="text-zinc-400">クリックリング</span>
+          <select
+            class="bg-zinc-700 rounded px-1.5 py-0.5 text-zinc-200"
+            value={toggleValue(current().click_ring_enabled)}
+            onChange={(e) => update({ click_ring_enabled: parseToggle(e.currentTarget.value) })}
+          >
+            <For each={TOGGLE_OPTIONS}>{(o) => <option value={o.value}>{o.label}</option>}</For>
+          </select>
+        </label>
+        <label class="flex items-center justify-between gap-2">
+          <span class="text-zinc-400">角丸 (px)</span>
+          <input
+            type="number"
+            min="0"
+            max="64"
+            placeholder="設定に従う"
+            class="w-24 bg-zinc-700 rounded px-1.5 py-0.5 text-zinc-200"
+            value={current().border_radius ?? ""}
+            onChange={(e) => {
+              const value = e.currentTarget.value.trim();
+              update({ border_radius: value === "" ? null : Math.max(0, parseInt(value, 10) || 0) });
+            }}
+          />
+        </label>
+        <div class="flex items-center justify-between gap-2">
+          <label class="flex items-center gap-1.5 text-zinc-400">
+            <input
+              type="checkbox"
+              checked={current().background != null}
+              onChange={(e) =>
+                update({
+                  background: e.currentTarget.checked ? { type: "Solid", color: fromHex(backgroundColor()) } : null,
+                })
+              }
+            />
+            背景色
+          </label>
+          <input
+            type="color"
+            disabled={current().background == null}
+            value={backgroundColor()}
+            onInput={(e) => update({ background: { type: "Solid", color: fromHex(e.currentTarget.value) } })}
+            class="w-8 h-5 bg-transparent disabled:opacity-30"
+          />
+        </div>
+      </div>
+
+      <div class="flex items-center justify-between border-t border-zinc-700 pt-2">
+        <button
+          class="px-2 py-1 rounded text-[10px] bg-zinc-700 text-zinc-300 hover:bg-zinc-600 disabled:opacity-30 transition-colors"
+          disabled={!props.override}
+          onClick={() => props.onChange(null)}
+        >
+          設定のスタイルに戻す
+        </button>
+        <span class="text-zinc-500 text-[10px]">書き出し時にこのシーンだけに適用されます</span>
+      </div>
+    </div>
+  );
+}
@@ -1,12 +1,25 @@
-import { Show } from "solid-js";
+import { For, Show } from "solid-js";
 import { convertFileSrc } from "@tauri-apps/api/core";
 
 interface Props {
   date: string;
+  /** ユーザーが付けた名前（未設定なら日時を見出しにする） */
+  title?: string | null;
+  tags?: string[];
   duration: string;
   thumbnailPath?: string | null;
+  /** テイクグループのシナリオ名（グループ外なら未指定） */
+  takeScenario?: string | null;
+  takeChosen?: boolean;
+  /** タイムラプス録画の早送り倍率（通常録画なら未指定） */
+  timelapseSpeed?: number | null;
   onClick: () => void;
   onDelete: () => void;
+  onChooseTake?: () => void;
+  onEdit?: () => void;
+  /** 一括書き出しの選択（未指定ならチェックボックスを出さない） */
+  selected?: boolean;
+  onToggleSelect?: () => void;
 }
 
 export default function ThumbnailCard(props: Props) {
@@ -42,8 +55,26 @@ export default function ThumbnailCard(props: Props) {
           </Show>
         </div>
         <div class="px-3 py-2 text-left">
-          <p class="text-sm text-slate-300">{props.date}</p>
-          <p class="text-xs text-slate-500">{props.duration}</p>
+          <p class="text-sm text-slate-300 truncate">{props.title || props.date}</p>
+          <p class="text-xs text-slate-500">
+            <Show when={props.title}>{props.date} · </Show>
+            {props.duration}
+            <Show when={props.timelapseSpeed}>
+              <span class="ml-1.5 text-amber-400">タイムラプス ×{Math.round(props.timelapseSpeed!)}</span>
+            </Show>
+          </p>
+          <Show when={props.takeScenario != null}>
+            <p class={`text-xs truncate ${props.takeChosen ? "text-purple-400" : "text-slate-500"}`}>
+              {props.takeChosen ? "★ " : ""}テイク: {props.takeScenario || "無題"}
+            </p>
+          </Show>
+          <Show when={props.tags?.length}>
+            <div class="flex flex-wrap gap-1 mt-1">
+              <For each={props.tags}>
+                {(tag) => <span class="px-1.5 py-0.5 rounded bg-slate-700/60 text-[10px] text-slate-300">{tag}</span>}
+              </For>
+            </div>
+          </Show>
         </div>
       </button>
       <button
@@ -54,6 +85,37 @@ export default function ThumbnailCard(props: Props) {
           <path d="M6 18L18 6M6 6l12 12" />
         </svg>
       </button>
+      <Show when={props.onEdit}>
+        <button
+          onClick={(e) => { e.stopPropagation(); props.onEdit!(); }}
+          title="名前・タグ・メモを編集"
+          class="absolute top-2 right-9 p-1 rounded-lg bg-slate-900/80 opacity-0 group-hover:opacity-100 transition-opacity text-slate-400 hover:text-purple-400"
+        >
+          <svg class="w-3.5 h-3.5" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2">
+            <path d="M12 20h9M16.5 3.5a2.12 2.12 0 013 3L7 19l-4 1 1-4 12.5-12.5z" />
+          </svg>
+        </button>
+      </Show>
+      <Show when={props.onToggleSelect}>
+        <input
+          type="checkbox"
+          checked={props.selected ?? false}
+          onClick={(e) => e.stopPropagation()}
+          onChange={() => props.onToggleSelect!()}
+          title="一括書き出しに含める"
+          class={`absolute bottom-2 right-2 w-4 h-4 rounded transition-opacity ${
+            props.selected ? "opacity-100" : "opacity-0 group-hover:opacity-100"
+          }`}
+        />
+      </Show>
+      <Show when={props.takeScenario != null && !props.takeChosen && props.onChooseTake}>
+        <button
+          onClick={(e) => { e.stopPropagation(); props.onChooseTake!(); }}
+          class="absolute top-2 left-2 px-2 py-0.5 rounded-lg bg-slate-900/80 opacity-0 group-hover:opacity-100 transition-opacity text-xs text-slate-300 hover:text-purple-400"
+        >
+          採用
+        </button>
+      </Show>
     </div>
   );
 }
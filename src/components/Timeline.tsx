@@ -1,8 +1,17 @@
 import { createSignal, createMemo, Show, For, onMount } from "solid-js";
-import { getRecordingScenes, getRecordingEvents } from "../lib/commands";
-import type { SceneInfo, TimelineEvent } from "../lib/types";
+import {
+  getRecordingScenes,
+  getRecordingEvents,
+  getBookmarks,
+  addBookmark,
+  deleteBookmark,
+  getSceneStyles,
+  saveSceneStyles,
+} from "../lib/commands";
+import type { Bookmark, SceneInfo, SceneStyleOverride, TimelineEvent } from "../lib/types";
 import type { ZoomSegment } from "../lib/zoomSegments";
 import ZoomTrack from "./ZoomTrack";
+import SceneStyleEditor from "./SceneStyleEditor";
 
 interface Props {
   recordingId: string;
@@ -41,20 +50,29 @@ interface StateSegment {
 
 const EVENT_TYPE_CONFIG: Record<string, { color: string; lane: number; label: string }> = {
   click:        { color: "rgba(96,165,250,0.9)",  lane: 0, label: "Click" },
+  drag:         { color: "rgba(56,189,248,0.9)",   lane: 0, label: "Drag" },
   key:          { color: "rgba(74,222,128,0.9)",   lane: 1, label: "Key" },
   scroll:       { color: "rgba(251,146,60,0.9)",   lane: 2, label: "Scroll" },
   focus:        { color: "rgba(168,85,247,0.9)",   lane: 3, label: "Focus" },
   window_focus: { color: "rgba(244,114,182,0.9)",  lane: 3, label: "WinFocus" },
+  splice:       { color: "rgba(239,68,68,0.9)",    lane: 3, label: "Splice" },
+  pause:        { color: "rgba(250,204,21,0.9)",   lane: 3, label: "Pause" },
 };
 
 const LANE_COUNT = 4;
 
+const BOOKMARK_COLOR = "rgba(45,212,191,0.9)";
+
 const ZOOM_LEVELS = [1, 2, 4, 8] as const;
 type ZoomLevel = typeof ZOOM_LEVELS[number];
 
 export default function Timeline(props: Props) {
   const [scenes, setScenes] = createSignal<SceneInfo[]>([]);
   const [events, setEvents] = createSignal<TimelineEvent[]>([]);
+  const [bookmarks, setBookmarks] = createSignal<Bookmark[]>([]);
+  const [sceneStyles, setSceneStyles] = createSignal<SceneStyleOverride[]>([]);
+  /** スタイルを編集中のシーン（右クリックで選ぶ） */
+  const [styledSceneIdx, setStyledSceneIdx] = createSignal<number | null>(null);
   const [loading, setLoading] = createSignal(false);
   const [hoveredSceneIdx, setHoveredSceneIdx] = createSignal<number | null>(null);
   const [hoveredEventIdx, setHoveredEventIdx] = createSignal<number | null>(null);
@@ -67,12 +85,17 @@ export default function Timeline(props: Props) {
   onMount(async () => {
     setLoading(true);
     try {
-      const [scns, evts] = await Promise.all([
+      const [scns, evts, bms, styles] = await Promise.all([
         getRecordingScenes(props.recordingId),
         getRecordingEvents(props.recordingId),
+        getBookmarks(props.recordingId),
+        getSceneStyles(props.recordingId),
       ]);
       setScenes(scns);
-      setEvents(evts);
+      // ブックマークはイベントのドットではなく縦線で描く
+      setEvents(evts.filter((e) => e.event_type !== "bookmark"));
+      setBookmarks(bms);
+      setSceneStyles(styles);
     } catch (e) {
       console.error("Failed to load timeline data:", e);
     }
@@ -203,6 +226,39 @@ export default function Timeline(props: Props) {
 
   const TOTAL_HEIGHT = EVENT_LANE_HEIGHT + STATE_BAND_HEIGHT + SCENE_BAND_HEIGHT;
 
+  // 再生位置にブックマークを追加する
+  const handleAddBookmark = async () => {
+    const timeMs = Math.round(props.currentTimeMs ?? 0);
+    try {
+      const bookmark = await addBookmark(props.recordingId, timeMs, `ブックマーク ${bookmarks().length + 1}`);
+      setBookmarks((list) => [...list, bookmark].sort((a, b) => a.time_ms - b.time_ms));
+    } catch (e) {
+      console.error("Failed to add bookmark:", e);
+    }
+  };
+
+  const sceneStyleFor = (scene: SceneInfo) => sceneStyles().find((o) => o.scene_id === scene.id);
+
+  const handleSceneStyleChange = async (scene: SceneInfo, override: SceneStyleOverride | null) => {
+    const next = sceneStyles().filter((o) => o.scene_id !== scene.id);
+    if (override) next.push(override);
+    setSceneStyles(next);
+    try {
+      await saveSceneStyles(props.recordingId, next);
+    } catch (e) {
+      console.error("Failed to save scene styles:", e);
+    }
+  };
+
+  const handleDeleteBookmark = async (id: string) => {
+    try {
+      await deleteBookmark(props.recordingId, id);
+      setBookmarks((list) => list.filter((b) => b.id !== id));
+    } catch (e) {
+      console.error("Failed to delete bookmark:", e);
+    }
+  };
+
   // ズーム段階を循環切り替え
   const cycleZoom = (dir: 1 | -1) => {
     const idx = ZOOM_LEVELS.indexOf(zoomLevel());
@@ -244,6 +300,11 @@ export default function Timeline(props: Props) {
               title="等倍に戻す"
             >フィット</button>
           </Show>
+          <button
+            class="ml-auto px-1.5 py-0.5 rounded bg-slate-700 hover:bg-slate-600 text-slate-200"
+            onClick={handleAddBookmark}
+            title="再生位置にブックマークを追加（右クリックで削除）"
+          >+ ブックマーク</button>
         </div>
 
         {/* 水平スクロール可能なコンテナ: ZoomTrack と Timeline を同期スクロール */}
@@ -302,6 +363,43 @@ export default function Timeline(props: Props) {
                 );
               })}
 
+              {/* Drag spans */}
+              <For each={events().filter((e) => e.event_type === "drag" && e.end_ms != null)}>
+                {(evt) => (
+                  <line
+                    x1={timeToX(evt.time_ms, 1000)} y1={eventDotY("drag")}
+                    x2={timeToX(evt.end_ms!, 1000)} y2={eventDotY("drag")}
+                    stroke={EVENT_TYPE_CONFIG.drag.color} stroke-width="2"
+                    stroke-linecap="round"
+                    vector-effect="non-scaling-stroke"
+                  />
+                )}
+              </For>
+
+              {/* Bookmarks */}
+              <For each={bookmarks()}>
+                {(bookmark) => (
+                  <line
+                    x1={timeToX(bookmark.time_ms, 1000)} y1={EVENT_LANE_Y}
+                    x2={timeToX(bookmark.time_ms, 1000)} y2={EVENT_LANE_Y + EVENT_LANE_HEIGHT}
+                    stroke={bookmark.color ?? BOOKMARK_COLOR} stroke-width="2"
+                    vector-effect="non-scaling-stroke"
+                    class="cursor-pointer"
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      props.onSeekToTime?.(bookmark.time_ms);
+                    }}
+                    onContextMenu={(e) => {
+                      e.preventDefault();
+                      e.stopPropagation();
+                      handleDeleteBookmark(bookmark.id);
+                    }}
+                  >
+                    <title>{bookmark.label}</title>
+                  </line>
+                )}
+              </For>
+
               {/* Event dots */}
               <For each={visibleEvents()}>
                 {(evt, i) => {
@@ -400,8 +498,15 @@ export default function Timeline(props: Props) {
                       height={SCENE_BAND_HEIGHT - 2}
                       rx="1.5"
                       fill={color()}
-                      stroke={hoveredSceneIdx() === i() ? "rgba(255,255,255,0.6)" : "none"}
+                      stroke={
+                        hoveredSceneIdx() === i() || styledSceneIdx() === i()
+                          ? "rgba(255,255,255,0.6)"
+                          : sceneStyleFor(scene)
+                          ? "rgba(250,204,21,0.8)"
+                          : "none"
+                      }
                       stroke-width="1"
+                      stroke-dasharray={sceneStyleFor(scene) ? "3 2" : undefined}
                       vector-effect="non-scaling-stroke"
                       class="cursor-pointer"
                       onMouseEnter={(e) => {
@@ -416,6 +521,10 @@ export default function Timeline(props: Props) {
                         e.stopPropagation();
                         props.onSeekToTime?.(scene.start_ms);
                       }}
+                      onContextMenu={(e) => {
+                        e.preventDefault();
+                        setStyledSceneIdx(i());
+                      }}
                     />
                   );
                 }}
@@ -541,6 +650,18 @@ export default function Timeline(props: Props) {
           </div>
         </Show>
 
+        {/* Per-scene export style (right-click a scene) */}
+        <Show when={styledSceneIdx() !== null && scenes()[styledSceneIdx()!]}>
+          {(scene) => (
+            <SceneStyleEditor
+              scene={scene()}
+              override={sceneStyleFor(scene())}
+              onChange={(override) => handleSceneStyleChange(scene(), override)}
+              onClose={() => setStyledSceneIdx(null)}
+            />
+          )}
+        </Show>
+
         {/* Current state info bar */}
         <Show when={props.currentTimeMs !== undefined}>
           <div class="flex items-center gap-4 px-2 py-1.5 bg-slate-800/60 rounded text-[11px] font-mono text-slate-400">
@@ -573,7 +694,7 @@ export default function Timeline(props: Props) {
           </span>
           <span class="flex items-center gap-1">
             <span class="inline-block w-3 h-2 rounded-sm" style={{ background: "rgba(139,92,246,0.4)" }} />
-            シーン
+            シーン（右クリックでスタイル）
           </span>
           <span class="text-slate-600">|</span>
           <span class="text-slate-600">{props.segments.length} 区間 / {scenes().length} シーン / {events().length} イベント</span>
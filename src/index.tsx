@@ -1,6 +1,13 @@
 /* @refresh reload */
 import { render } from "solid-js/web";
 import App from "./App";
+import AreaSelector from "./components/AreaSelector";
 import "./styles.css";
 
-render(() => <App />, document.getElementById("root") as HTMLElement);
+// 範囲選択のオーバーレイウィンドウ（select_area）は同じページを ?overlay=area で開く
+const overlay = new URLSearchParams(window.location.search).get("overlay");
+
+render(
+  () => (overlay === "area" ? <AreaSelector /> : <App />),
+  document.getElementById("root") as HTMLElement,
+);
@@ -1,6 +1,7 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   RecordingInfo,
+  RecordingPage,
   RecordingState,
   ExportFormat,
   QualityPreset,
@@ -11,16 +12,88 @@ import type {
   SceneInfo,
   TimelineEvent,
   SceneEditOp,
+  CutRange,
+  Redaction,
+  PrivacyReport,
+  Annotation,
+  StylePreset,
+  SettingsProfile,
+  Bookmark,
+  SceneStyleOverride,
+  SystemTheme,
+  RecordingMode,
+  HotkeyAction,
 } from "./types";
+import type { SceneBoundaryPreview } from "./generated/SceneBoundaryPreview";
+import type { GifOptions } from "./generated/GifOptions";
+import type { OutputSize } from "./generated/OutputSize";
+import type { RecordingComparison } from "./generated/RecordingComparison";
+import type { SetupDiagnostics } from "./generated/SetupDiagnostics";
+import type { TakeInfo } from "./generated/TakeInfo";
+import type { CleanupReport } from "./generated/CleanupReport";
+import type { StorageUsage } from "./generated/StorageUsage";
+import type { StoragePolicyReport } from "./generated/StoragePolicyReport";
+import type { ScheduledRecording } from "./generated/ScheduledRecording";
+import type { RecordingFilter } from "./generated/RecordingFilter";
+import type { RecordingSort } from "./generated/RecordingSort";
+import type { ShareTarget } from "./generated/ShareTarget";
 
 export async function startRecording(): Promise<void> {
   return invoke("start_recording");
 }
 
+export async function startRetake(recordingId: string): Promise<void> {
+  return invoke("start_retake", { recordingId });
+}
+
+/** 録画を再開して元の録画の末尾に継ぎ足す（停止時に元の録画 ID が返る） */
+export async function appendToRecording(recordingId: string): Promise<void> {
+  return invoke("append_to_recording", { recordingId });
+}
+
+export async function linkTakes(recordingIds: string[], scenario?: string): Promise<TakeInfo> {
+  return invoke("link_takes", { recordingIds, scenario: scenario ?? null });
+}
+
+export async function unlinkTake(recordingId: string): Promise<void> {
+  return invoke("unlink_take", { recordingId });
+}
+
+export async function chooseTake(recordingId: string): Promise<void> {
+  return invoke("choose_take", { recordingId });
+}
+
+export async function startRecordingFromTemplate(templateId: string): Promise<void> {
+  return invoke("start_recording_from_template", { templateId });
+}
+
+export async function cancelCountdown(): Promise<void> {
+  return invoke("cancel_countdown");
+}
+
 export async function stopRecording(): Promise<string> {
   return invoke("stop_recording");
 }
 
+/** ホットキーを保存前に確認する（形式・他の操作との重複・Windows の予約・他アプリの使用）。保存する正規形を返す */
+export async function validateHotkey(accelerator: string, action: HotkeyAction): Promise<string> {
+  return invoke("validate_hotkey", { accelerator, action });
+}
+
+/** キー入力を受け取る間、登録中のホットキーを止める。終わったら endHotkeyCapture で戻す */
+export async function beginHotkeyCapture(): Promise<void> {
+  return invoke("begin_hotkey_capture");
+}
+
+export async function endHotkeyCapture(): Promise<void> {
+  return invoke("end_hotkey_capture");
+}
+
+/** 録画を止めて保存せずに破棄する */
+export async function cancelRecording(): Promise<void> {
+  return invoke("cancel_recording");
+}
+
 export async function pauseRecording(): Promise<void> {
   return invoke("pause_recording");
 }
@@ -33,22 +106,70 @@ export async function getRecordingState(): Promise<RecordingState> {
   return invoke("get_recording_state");
 }
 
-export async function getRecordingsList(): Promise<RecordingInfo[]> {
-  return invoke("get_recordings_list");
+/** 録画一覧。filter を渡すと名前・タグ・メモで絞り込む */
+export async function getRecordingsList(filter?: RecordingFilter): Promise<RecordingInfo[]> {
+  return invoke("get_recordings_list", { filter: filter ?? null });
+}
+
+/** 録画一覧の 1 ページ（絞り込み・並べ替えのあと offset から limit 件） */
+export async function getRecordingsPage(
+  filter: RecordingFilter,
+  sort: RecordingSort,
+  offset: number,
+  limit: number,
+): Promise<RecordingPage> {
+  return invoke("get_recordings_page", { filter, sort, offset, limit });
+}
+
+/** 録画に使われているタグの一覧 */
+export async function listRecordingTags(): Promise<string[]> {
+  return invoke("list_recording_tags");
+}
+
+/** 録画の名前を変える（空にすると日時表示に戻る）。保存された名前を返す */
+export async function renameRecording(recordingId: string, title: string): Promise<string | null> {
+  return invoke("rename_recording", { recordingId, title });
+}
+
+/** 録画のタグを置き換える。空白除去・重複除去後のタグを返す */
+export async function setRecordingTags(recordingId: string, tags: string[]): Promise<string[]> {
+  return invoke("set_recording_tags", { recordingId, tags });
+}
+
+export async function setRecordingNotes(recordingId: string, notes: string): Promise<void> {
+  return invoke("set_recording_notes", { recordingId, notes });
 }
 
 export async function exportRecording(
   recordingId: string,
   format: ExportFormat,
-  quality: QualityPreset
+  quality: QualityPreset,
+  cuts?: CutRange[],
+  gifOptions?: GifOptions,
+  outputSize?: OutputSize,
+  plain?: boolean,
+  stylePreset?: string
 ): Promise<void> {
   return invoke("export_recording", {
     recordingId,
     format,
     quality,
+    cuts: cuts ?? null,
+    gifOptions: gifOptions ?? null,
+    outputSize: outputSize ?? null,
+    plain: plain ?? null,
+    stylePreset: stylePreset ?? null,
   });
 }
 
+/**
+ * 複数の録画を現在の設定で順に書き出す（バックグラウンド）。進捗は batch-export-progress、
+ * 結果は batch-export-complete イベントで届く
+ */
+export async function batchExport(recordingIds: string[], format: ExportFormat, quality: QualityPreset): Promise<void> {
+  return invoke("batch_export", { recordingIds, format, quality });
+}
+
 export async function getExportProgress(): Promise<ExportProgress | null> {
   return invoke("get_export_progress");
 }
@@ -57,14 +178,73 @@ export async function getSettings(): Promise<AppSettings> {
   return invoke("get_settings");
 }
 
+/** 録画のプレビュー・書き出しに使う設定（テンプレートから録画した場合はその設定を反映したもの） */
+export async function getRecordingSettings(recordingId: string): Promise<AppSettings> {
+  return invoke("get_recording_settings", { recordingId });
+}
+
 export async function saveSettings(newSettings: AppSettings): Promise<void> {
   return invoke("save_settings", { newSettings });
 }
 
+export async function cleanupWorkspace(): Promise<CleanupReport> {
+  return invoke("cleanup_workspace");
+}
+
+/** 録画を .snappi ファイルにまとめる（path 省略時は保存先フォルダ）。書き出したパスを返す */
+export async function exportProject(recordingId: string, path?: string): Promise<string> {
+  return invoke("export_project", { recordingId, path: path ?? null });
+}
+
+/** .snappi ファイルから録画を復元する。録画 ID を返す */
+export async function importProject(path: string): Promise<string> {
+  return invoke("import_project", { path });
+}
+
+/** 他のツールで撮った動画（MP4・WebM など）を入力イベントなしの録画として取り込む。録画 ID を返す */
+export async function importVideo(path: string): Promise<string> {
+  return invoke("import_video", { path });
+}
+
+/** 録画ごと・録画フォルダ全体のディスク使用量 */
+export async function getStorageUsage(): Promise<StorageUsage> {
+  return invoke("get_storage_usage");
+}
+
+/** 自動整理のルールを今すぐ適用する（保存済みの設定を使う） */
+export async function applyStoragePolicies(): Promise<StoragePolicyReport> {
+  return invoke("apply_storage_policies");
+}
+
 export async function deleteRecording(recordingId: string): Promise<void> {
   return invoke("delete_recording", { recordingId });
 }
 
+/** 録画のフォルダをエクスプローラーで開く */
+export async function openRecordingDir(recordingId: string): Promise<void> {
+  return invoke("open_recording_dir", { recordingId });
+}
+
+/** 書き出したファイルを選択した状態でエクスプローラーを開く */
+export async function revealExport(path: string): Promise<void> {
+  return invoke("reveal_export", { path });
+}
+
+/** 書き出したファイルをクリップボードへコピーする (GIF / PNG は画像としても貼り付けられる) */
+export async function copyExportToClipboard(path: string): Promise<void> {
+  return invoke("copy_export_to_clipboard", { path });
+}
+
+/** 書き出したファイルをアップロードして共有用 URL を返す。進捗は upload-progress イベントで届く */
+export async function uploadExport(path: string, target: ShareTarget): Promise<string> {
+  return invoke("upload_export", { path, target });
+}
+
+/** ブラウザで Google の許可画面を開き、YouTube へのアップロードを許可する。保存後の設定を返す */
+export async function authorizeYoutube(): Promise<AppSettings> {
+  return invoke("authorize_youtube");
+}
+
 export async function getRecordingThumbnail(
   recordingId: string
 ): Promise<string> {
@@ -81,6 +261,21 @@ export async function getZoomKeyframes(
   return invoke("get_zoom_keyframes", { recordingId });
 }
 
+/** Timeline で編集して保存したキーフレーム（未保存なら空配列） */
+export async function loadKeyframes(recordingId: string): Promise<ZoomKeyframe[]> {
+  return invoke("load_keyframes", { recordingId });
+}
+
+/** keyframes.json に保存する。空配列を渡すと削除され、自動ズームに戻る */
+export async function saveKeyframes(recordingId: string, keyframes: ZoomKeyframe[]): Promise<void> {
+  return invoke("save_keyframes", { recordingId, keyframes });
+}
+
+/** エディタ再生用の低解像度 MP4 のパス（キャッシュ済みならすぐ返る） */
+export async function generatePreview(recordingId: string): Promise<string> {
+  return invoke("generate_preview", { recordingId });
+}
+
 export async function getRecordingScenes(
   recordingId: string
 ): Promise<SceneInfo[]> {
@@ -97,16 +292,67 @@ export async function exportWithKeyframes(
   recordingId: string,
   keyframes: ZoomKeyframe[],
   format: ExportFormat,
-  quality: QualityPreset
+  quality: QualityPreset,
+  cuts?: CutRange[],
+  gifOptions?: GifOptions,
+  outputSize?: OutputSize,
+  stylePreset?: string
 ): Promise<void> {
   return invoke("export_with_keyframes", {
     recordingId,
     keyframes,
     format,
     quality,
+    cuts: cuts ?? null,
+    gifOptions: gifOptions ?? null,
+    outputSize: outputSize ?? null,
+    stylePreset: stylePreset ?? null,
   });
 }
 
+/** 現在のスタイル・エフェクト・出力設定を .snappi-style ファイルに書き出す */
+export async function exportStylePreset(name: string, path: string, description?: string): Promise<void> {
+  return invoke("export_style_preset", { name, description: description ?? null, path });
+}
+
+/** .snappi-style ファイルを読み込み、書き出し時に名前で使えるようにする */
+export async function importStylePreset(path: string): Promise<StylePreset> {
+  return invoke("import_style_preset", { path });
+}
+
+export async function listStylePresets(): Promise<StylePreset[]> {
+  return invoke("list_style_presets");
+}
+
+/** 読み込み済みプリセットを現在の設定に適用して保存する（適用後の設定を返す） */
+export async function applyStylePreset(name: string): Promise<AppSettings> {
+  return invoke("apply_style_preset", { name });
+}
+
+export async function listProfiles(): Promise<SettingsProfile[]> {
+  return invoke("list_profiles");
+}
+
+/** 保存済みの設定（スタイル・エフェクト・出力）を name のプロファイルとして保存する。同名は上書き */
+export async function saveProfile(name: string): Promise<SettingsProfile> {
+  return invoke("save_profile", { name });
+}
+
+/** プロファイルに切り替えて保存する（適用後の設定を返す） */
+export async function applyProfile(name: string): Promise<AppSettings> {
+  return invoke("apply_profile", { name });
+}
+
+export async function deleteProfile(name: string): Promise<void> {
+  return invoke("delete_profile", { name });
+}
+
+/** 保存済みのシーン編集（未編集なら空配列） */
+export async function loadSceneEdits(recordingId: string): Promise<SceneEditOp[]> {
+  return invoke("load_scene_edits", { recordingId });
+}
+
+/** 編集リスト全体を渡す。保存され、以後の読み込みと書き出しにも反映される。空配列で編集を破棄 */
 export async function applySceneEdits(
   recordingId: string,
   edits: SceneEditOp[]
@@ -121,3 +367,177 @@ export async function computeActivityCenter(
 ): Promise<{ center_x: number; center_y: number; zoom_level: number }> {
   return invoke("compute_activity_center", { recordingId, startMs, endMs });
 }
+
+export async function optimizeKeyframes(
+  keyframes: ZoomKeyframe[],
+): Promise<ZoomKeyframe[]> {
+  return invoke("optimize_keyframes", { keyframes });
+}
+
+export async function compareRecordings(
+  idA: string,
+  idB: string,
+): Promise<RecordingComparison> {
+  return invoke("compare_recordings", { idA, idB });
+}
+
+export async function getSceneBoundaryPreview(
+  recordingId: string,
+  sceneId: number,
+): Promise<SceneBoundaryPreview> {
+  return invoke("get_scene_boundary_preview", { recordingId, sceneId });
+}
+
+/** time_ms 時点のフレームを書き出し時と同じ見た目で描画し、JPEG のパスを返す。keyframes 省略時は保存済み（なければ自動）のズーム */
+export async function renderPreviewFrame(
+  recordingId: string,
+  timeMs: number,
+  keyframes?: ZoomKeyframe[],
+): Promise<string> {
+  return invoke("render_preview_frame", { recordingId, timeMs, keyframes: keyframes ?? null });
+}
+
+export async function getSuggestedCuts(recordingId: string): Promise<CutRange[]> {
+  return invoke("get_suggested_cuts", { recordingId });
+}
+
+export async function getRedactions(recordingId: string): Promise<Redaction[]> {
+  return invoke("get_redactions", { recordingId });
+}
+
+export async function saveRedactions(
+  recordingId: string,
+  redactions: Redaction[],
+): Promise<void> {
+  return invoke("save_redactions", { recordingId, redactions });
+}
+
+/** 共有前のプライバシーチェック（機密っぽいウィンドウ・パスワード欄・入力文字・画面上のメールアドレス） */
+export async function reviewRecordingPrivacy(recordingId: string): Promise<PrivacyReport> {
+  return invoke("review_recording_privacy", { recordingId });
+}
+
+export async function mapTimeToFrame(recordingId: string, timeMs: number): Promise<number> {
+  return invoke("map_time_to_frame", { recordingId, timeMs });
+}
+
+export async function mapFrameToTime(recordingId: string, frame: number): Promise<number> {
+  return invoke("map_frame_to_time", { recordingId, frame });
+}
+
+export async function getAnnotations(recordingId: string): Promise<Annotation[]> {
+  return invoke("get_annotations", { recordingId });
+}
+
+export async function saveAnnotations(
+  recordingId: string,
+  annotations: Annotation[],
+): Promise<void> {
+  return invoke("save_annotations", { recordingId, annotations });
+}
+
+export async function getSceneStyles(recordingId: string): Promise<SceneStyleOverride[]> {
+  return invoke("get_scene_styles", { recordingId });
+}
+
+export async function saveSceneStyles(
+  recordingId: string,
+  overrides: SceneStyleOverride[],
+): Promise<void> {
+  return invoke("save_scene_styles", { recordingId, overrides });
+}
+
+export async function getBookmarks(recordingId: string): Promise<Bookmark[]> {
+  return invoke("get_bookmarks", { recordingId });
+}
+
+export async function addBookmark(
+  recordingId: string,
+  timeMs: number,
+  label: string,
+  color: string | null = null,
+): Promise<Bookmark> {
+  return invoke("add_bookmark", { recordingId, timeMs, label, color });
+}
+
+export async function updateBookmark(recordingId: string, bookmark: Bookmark): Promise<void> {
+  return invoke("update_bookmark", { recordingId, bookmark });
+}
+
+export async function deleteBookmark(recordingId: string, bookmarkId: string): Promise<void> {
+  return invoke("delete_bookmark", { recordingId, bookmarkId });
+}
+
+/** 画面上で録画範囲をドラッグして選び、設定に保存する。キャンセル時は null */
+export async function selectArea(): Promise<RecordingMode | null> {
+  return invoke("select_area");
+}
+
+/** 範囲選択オーバーレイから結果を返す（[left, top, right, bottom] の物理ピクセル、null でキャンセル） */
+export async function finishAreaSelection(rect: [number, number, number, number] | null): Promise<void> {
+  return invoke("finish_area_selection", { rect });
+}
+
+export async function getSystemTheme(): Promise<SystemTheme> {
+  return invoke("get_system_theme");
+}
+
+export async function applySystemTheme(): Promise<AppSettings> {
+  return invoke("apply_system_theme");
+}
+
+export async function isFirstRun(): Promise<boolean> {
+  return invoke("is_first_run");
+}
+
+export async function runSetupDiagnostics(): Promise<SetupDiagnostics> {
+  return invoke("run_setup_diagnostics");
+}
+
+export async function applyRecommendedSettings(
+  diagnostics: SetupDiagnostics,
+): Promise<AppSettings> {
+  return invoke("apply_recommended_settings", { diagnostics });
+}
+
+export async function takeScreenshot(): Promise<string> {
+  return invoke("take_screenshot");
+}
+
+export async function startScrollingCapture(): Promise<void> {
+  return invoke("start_scrolling_capture");
+}
+
+export async function stopScrollingCapture(): Promise<string> {
+  return invoke("stop_scrolling_capture");
+}
+
+/** `startTime` は RFC 3339（タイムゾーン付き）。開始時刻になるとバックエンドが録画を始め、`durationSeconds` 後に止める */
+export async function scheduleRecording(
+  startTime: string,
+  durationSeconds: number,
+  mode: RecordingMode,
+): Promise<ScheduledRecording> {
+  return invoke("schedule_recording", { startTime, durationSeconds, mode });
+}
+
+export async function listScheduledRecordings(): Promise<ScheduledRecording[]> {
+  return invoke("list_scheduled_recordings");
+}
+
+export async function cancelScheduledRecording(id: string): Promise<void> {
+  return invoke("cancel_scheduled_recording", { id });
+}
+
+/** 録画対象を設定の RTMP サーバーへ配信する。途中で切れたときは stream-ended でエラーが届く */
+export async function startStream(): Promise<void> {
+  return invoke("start_stream");
+}
+
+export async function stopStream(): Promise<void> {
+  return invoke("stop_stream");
+}
+
+export async function isStreaming(): Promise<boolean> {
+  return invoke("is_streaming");
+}
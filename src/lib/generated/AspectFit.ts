@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How the recording is placed on a canvas of a different aspect ratio
+ */
+export type AspectFit = "Letterbox" | "ZoomCrop";
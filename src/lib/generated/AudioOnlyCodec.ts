@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Codec / container for [`ExportFormat::AudioOnly`]
+ */
+export type AudioOnlyCodec = "Aac" | "Opus";
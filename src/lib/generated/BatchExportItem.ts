@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 一括書き出しの 1 件の結果。成功なら `output_path`、失敗なら `error`
+ */
+export type BatchExportItem = { recording_id: string, output_path: string | null, error: string | null, };
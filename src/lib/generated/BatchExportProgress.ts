@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * `batch-export-progress` イベントのペイロード
+ */
+export type BatchExportProgress = { 
+/**
+ * 書き出し中の録画の番号（0 始まり）
+ */
+index: number, count: number, recording_id: string, 
+/**
+ * 書き出し中の録画のステージと進捗 (0〜1)
+ */
+stage: string, progress: number, 
+/**
+ * 一括書き出し全体の進捗 (0〜1)
+ */
+overall: number, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BatchExportItem } from "./BatchExportItem";
+
+/**
+ * `batch-export-complete` イベントのペイロード
+ */
+export type BatchExportReport = { 
+/**
+ * 指定した順
+ */
+items: Array<BatchExportItem>, succeeded: number, failed: number, };
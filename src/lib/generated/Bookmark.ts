@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * タイムライン上のブックマーク（録画ごとに bookmarks.json へ保存）
+ */
+export type Bookmark = { id: string, 
+/**
+ * 録画のタイムライン上の時刻 (ms)
+ */
+time_ms: bigint, label: string, 
+/**
+ * 表示色（CSS カラー）。None = 既定の色
+ */
+color: string | null, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * `cleanup_workspace` で消したものと空いた容量
+ */
+export type CleanupReport = { 
+/**
+ * 異常終了した書き出しが残した一時フォルダ
+ */
+temp_dirs: number, 
+/**
+ * 録画や設定が変わって使われなくなったプレビュー用プロキシ
+ */
+stale_proxies: number, 
+/**
+ * 削除済みの録画のキャッシュだけが残ったフォルダ
+ */
+orphaned_caches: number, 
+/**
+ * 空いた容量 (bytes)
+ */
+reclaimed_bytes: bigint, };
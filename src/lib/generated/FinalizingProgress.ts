@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Recording finalization progress (`recording-finalizing` event)
+ */
+export type FinalizingProgress = { stage: string, progress: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * GIF dithering mode (FFmpeg `paletteuse` dither)
+ */
+export type GifDither = "None" | "Bayer" | "FloydSteinberg" | "Sierra2_4a";
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GifDither } from "./GifDither";
+
+/**
+ * Animated image export options (GIF, and fps / max width / loop for WebP and APNG)
+ */
+export type GifOptions = { fps: number, 
+/**
+ * 出力幅の上限 (px)。キャンバスがこれより小さければ縮小しない
+ */
+max_width: number, dither: GifDither, 
+/**
+ * ループ回数（0 = 無限ループ、-1 = ループしない）
+ */
+loop_count: number, 
+/**
+ * ファイルサイズ上限 (KB)。超えた場合は fps・幅を下げて再エンコードする
+ */
+max_file_size_kb: number | null, };
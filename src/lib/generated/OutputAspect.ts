@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Output canvas aspect ratio
+ */
+export type OutputAspect = "Source" | "Vertical9x16" | "Square1x1" | "Portrait4x5";
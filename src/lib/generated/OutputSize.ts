@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Per-export resolution override. Explicit `width`/`height` take precedence
+ * over `scale`; giving only one of them keeps the source aspect ratio.
+ */
+export type OutputSize = { width: number | null, height: number | null, 
+/**
+ * Factor applied to the recorded screen size (e.g. 0.5)
+ */
+scale: number | null, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 録画を一時停止した位置
+ */
+export type PauseMarker = { 
+/**
+ * 一時停止した時刻 (ms、録画のタイムライン上。再開後もこの時刻から続く)
+ */
+at_ms: bigint, 
+/**
+ * 一時停止していた実時間 (ms)
+ */
+duration_ms: bigint, };
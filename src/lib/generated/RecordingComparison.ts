@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RecordingStats } from "./RecordingStats";
+
+/**
+ * Side-by-side stats of two recordings (QA: two runs of the same flow).
+ */
+export type RecordingComparison = { a: RecordingStats, b: RecordingStats, 
+/**
+ * b - a
+ */
+duration_delta_ms: bigint, scene_count_delta: number, click_count_delta: number, 
+/**
+ * Windows used in both recordings
+ */
+common_windows: Array<string>, 
+/**
+ * 0.0 (unrelated) – 1.0 (identical stats)
+ */
+similarity: number, };
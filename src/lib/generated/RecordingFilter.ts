@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 録画一覧の絞り込み（`get_recordings_list`）。指定した条件をすべて満たす録画だけを返す
+ */
+export type RecordingFilter = { 
+/**
+ * 名前・タグ・メモのどれかに含まれる文字列（大文字小文字無視）
+ */
+query: string | null, 
+/**
+ * すべて付いている必要があるタグ（大文字小文字無視）
+ */
+tags: Array<string>, };
@@ -1,6 +1,11 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TakeInfo } from "./TakeInfo";
 
 /**
  * Recording info for the frontend list
  */
-export type RecordingInfo = { id: string, date: string, duration_ms: bigint, frame_count: number, thumbnail_path: string | null, recording_dir: string, screen_width: number, screen_height: number, };
+export type RecordingInfo = { id: string, date: string, duration_ms: bigint, frame_count: number, thumbnail_path: string | null, recording_dir: string, screen_width: number, screen_height: number, monitor_scale: number, take: TakeInfo | null, 
+/**
+ * タイムラプス録画の早送り倍率（一覧の「×30」表示用）
+ */
+timelapse_speed: number | null, title: string | null, tags: Array<string>, notes: string, };
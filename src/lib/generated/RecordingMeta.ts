@@ -1,4 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PauseMarker } from "./PauseMarker";
+import type { TakeInfo } from "./TakeInfo";
 
 /**
  * Metadata about a completed recording session
@@ -8,4 +10,42 @@ export type RecordingMeta = { version: number, id: string, screen_width: number,
  * 録画されたフレーム数。旧バージョンでは frame_count.txt に保存されていた。
  * None の場合、リスト表示は frame_count.txt にフォールバックする。
  */
-frame_count: number | null, };
+frame_count: number | null, 
+/**
+ * 録画時の OS のマウスポインターサイズ（既定サイズ 32px に対する倍率）。
+ * エクスポート時のカーソル描画サイズに掛ける。旧録画では None（= 1.0）。
+ */
+cursor_scale: number | null, 
+/**
+ * 同じシナリオの撮り直し（テイク）としてのグループ情報
+ */
+take: TakeInfo | null, 
+/**
+ * 続きを追記録画した継ぎ目の時刻 (ms)。追記のたびに末尾へ足す
+ */
+splices: Array<bigint>, 
+/**
+ * 一時停止した位置。version 3 以降の録画はタイムスタンプが一時停止中に進まない
+ */
+pauses: Array<PauseMarker>, 
+/**
+ * タイムラプス録画の早送り倍率（書き出し fps ÷ キャプチャ fps）。通常録画では None
+ */
+timelapse_speed: number | null, 
+/**
+ * ユーザーが付けた名前（None = 日時で表示）
+ */
+title: string | null, 
+/**
+ * ユーザーが付けたタグ（前後の空白を除き、重複なし）
+ */
+tags: Array<string>, 
+/**
+ * メモ
+ */
+notes: string, 
+/**
+ * 録画中のチェックポイント。停止処理まで終わらなかった（クラッシュした）録画では
+ * true のまま残り、フレーム数・長さは最後のチェックポイント時点の値になる
+ */
+in_progress: boolean, };
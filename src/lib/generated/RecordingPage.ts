@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RecordingInfo } from "./RecordingInfo";
+
+/**
+ * 録画一覧の 1 ページ（`get_recordings_page`）
+ */
+export type RecordingPage = { recordings: Array<RecordingInfo>, 
+/**
+ * 絞り込み後の全件数
+ */
+total: number, 
+/**
+ * このページの先頭の位置
+ */
+offset: number, };
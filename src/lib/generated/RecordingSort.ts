@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 録画一覧の並び順
+ */
+export type RecordingSort = "Newest" | "Oldest" | "Longest" | "Shortest" | "Title";
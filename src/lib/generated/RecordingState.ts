@@ -3,4 +3,4 @@
 /**
  * Recording state
  */
-export type RecordingState = "Idle" | "Recording" | "Paused" | "Processing";
+export type RecordingState = "Idle" | "Countdown" | "Recording" | "Paused" | "Processing";
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Per-recording summary used by `compare_recordings`.
+ */
+export type RecordingStats = { id: string, duration_ms: bigint, scene_count: number, click_count: number, key_count: number, scroll_count: number, 
+/**
+ * Distinct window titles focused during the recording, in first-seen order
+ */
+windows: Array<string>, };
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 録画 1 件のディスク使用量（`get_storage_usage`）
+ */
+export type RecordingUsage = { id: string, title: string | null, 
+/**
+ * 録画開始日時（RFC 3339）
+ */
+date: string, 
+/**
+ * 録画フォルダ全体 (bytes)
+ */
+bytes: bigint, 
+/**
+ * うちフレーム (bytes)
+ */
+frames_bytes: bigint, 
+/**
+ * 書き出しに成功したことがあるか
+ */
+exported: boolean, 
+/**
+ * フレームが自動整理で削除済みか
+ */
+frames_removed: boolean, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Before/after preview images around a scene boundary (Timeline merge/split helper).
+ * Paths point to small PNGs with each scene's bbox drawn on top of the raw frame.
+ */
+export type SceneBoundaryPreview = { 
+/**
+ * Scene before the boundary (the boundary is between scene_id and scene_id + 1)
+ */
+scene_id: number, boundary_ms: bigint, before_path: string, after_path: string, };
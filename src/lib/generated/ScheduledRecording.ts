@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RecordingMode } from "./RecordingMode";
+
+/**
+ * 予約録画: 開始時刻になったら `mode` で録画を始め、`duration_seconds` で止める
+ */
+export type ScheduledRecording = { id: string, 
+/**
+ * 開始時刻（RFC 3339）
+ */
+start_time: string, 
+/**
+ * 録画する長さ（秒）
+ */
+duration_seconds: bigint, 
+/**
+ * 録画対象（全画面・ウィンドウ・範囲）
+ */
+mode: RecordingMode, };
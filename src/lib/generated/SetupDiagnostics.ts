@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * First-run setup checks (`run_setup_diagnostics`)
+ */
+export type SetupDiagnostics = { 
+/**
+ * 見つかった FFmpeg（None = 未検出。書き出しに必要）
+ */
+ffmpeg_path: string | null, 
+/**
+ * `ffmpeg -version` の 1 行目
+ */
+ffmpeg_version: string | null, 
+/**
+ * マウス・キーボードの低レベルフックを設定できたか
+ */
+input_hooks_ok: boolean, input_hooks_error: string | null, 
+/**
+ * GDI 画面キャプチャが動いたか
+ */
+capture_ok: boolean, capture_error: string | null, screen_width: number, screen_height: number, 
+/**
+ * キャプチャ + JPEG 保存を全力で回したときの fps
+ */
+measured_fps: number | null, 
+/**
+ * 録画 fps の推奨値（計測できなければ None）
+ */
+recommended_fps: number | null, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 自動整理（`apply_storage_policies`）で消したものと空いた容量
+ */
+export type StoragePolicyReport = { 
+/**
+ * フレームを削除した録画
+ */
+frames_removed: number, 
+/**
+ * 削除した録画
+ */
+recordings_deleted: number, 
+/**
+ * 空いた容量 (bytes)
+ */
+reclaimed_bytes: bigint, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RecordingUsage } from "./RecordingUsage";
+
+/**
+ * 録画フォルダのディスク使用量（新しい録画順）
+ */
+export type StorageUsage = { recordings: Array<RecordingUsage>, 
+/**
+ * 録画フォルダ全体 (bytes)
+ */
+total_bytes: bigint, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 同じシナリオを撮り直した録画（テイク）のグループ情報
+ */
+export type TakeInfo = { 
+/**
+ * 同じグループのテイクで共通の ID
+ */
+group_id: string, 
+/**
+ * シナリオ名（グループ内で共通）
+ */
+scenario: string, 
+/**
+ * グループ内で採用されたテイクか
+ */
+chosen: boolean, };
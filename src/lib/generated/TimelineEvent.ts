@@ -3,4 +3,12 @@
 /**
  * Lightweight event representation for Timeline UI visualization.
  */
-export type TimelineEvent = { time_ms: bigint, event_type: string, x: number | null, y: number | null, label: string | null, };
+export type TimelineEvent = { time_ms: bigint, event_type: string, x: number | null, y: number | null, label: string | null, 
+/**
+ * Span end for "drag" events (x/y is the start, end_x/end_y the drop point)
+ */
+end_ms: bigint | null, end_x: number | null, end_y: number | null, 
+/**
+ * Display color for "bookmark" events
+ */
+color: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ShareTarget } from "./ShareTarget";
+
+/**
+ * `upload-progress` イベントのペイロード
+ */
+export type UploadProgress = { target: ShareTarget, path: string, uploaded_bytes: bigint, total_bytes: bigint, };
@@ -1,3 +1,6 @@
+import type { TakeInfo } from "./generated/TakeInfo";
+import type { ScheduledRecording } from "./generated/ScheduledRecording";
+
 export interface RecordingInfo {
   id: string;
   date: string;
@@ -7,30 +10,82 @@ export interface RecordingInfo {
   recording_dir: string;
   screen_width: number;
   screen_height: number;
+  /** 録画したモニターの表示スケール（100% = 1） */
+  monitor_scale?: number;
+  /** 同じシナリオのテイクとしてのグループ情報 */
+  take?: TakeInfo | null;
+  /** タイムラプス録画の早送り倍率（通常録画は null） */
+  timelapse_speed?: number | null;
+  /** ユーザーが付けた名前・タグ・メモ */
+  title?: string | null;
+  tags?: string[];
+  notes?: string;
+}
+
+/** get_recordings_page の結果（generated/RecordingPage の、一覧側の RecordingInfo 版） */
+export interface RecordingPage {
+  recordings: RecordingInfo[];
+  /** 絞り込み後の全件数 */
+  total: number;
+  offset: number;
 }
 
-export type RecordingState = "Idle" | "Recording" | "Paused" | "Processing";
+export type RecordingState = "Idle" | "Countdown" | "Recording" | "Paused" | "Processing";
+
+/** 録画中に recording-live-events で届く入力（クリック・キー・ウィンドウ切り替え） */
+export type LiveEvent =
+  | { type: "click"; t: number; btn: string; x: number; y: number }
+  | { type: "key"; t: number; key: string; modifiers: string[] }
+  | { type: "window_focus"; t: number; title: string; rect: [number, number, number, number] };
+
+/** 録画中に recording-warning で届く空き容量不足の通知（Critical なら録画は自動停止する） */
+export interface DiskSpaceWarning {
+  level: "Ok" | "Low" | "Critical";
+  free_mb: number;
+  min_free_mb: number;
+}
 
-export type ExportFormat = "Mp4" | "Gif" | "WebM";
+export type ExportFormat = "Mp4" | "Gif" | "WebM" | "WebP" | "Apng" | "Mov" | "AudioOnly";
+
+export type AudioOnlyCodec = "Aac" | "Opus";
+
+export type CompositorBackend = "Cpu" | "Gpu";
+
+export type FrameStorage = "Jpeg" | "Packed";
+
+/** ホットキーで実行する操作 */
+export type HotkeyAction = "ToggleRecording" | "PauseResume" | "Cancel" | "Screenshot" | "SaveReplay";
 
 export type QualityPreset = "Social" | "HighQuality" | "Lightweight";
 
+export type OutputAspect = "Source" | "Vertical9x16" | "Square1x1" | "Portrait4x5";
+
+export type AspectFit = "Letterbox" | "ZoomCrop";
+
 export type ZoomIntensity = "Minimal" | "Balanced" | "Active";
 
 export type AnimationSpeed = "Slow" | "Mellow" | "Quick" | "Rapid";
 
+export type EffectsProfile = "Standard" | "HighVisibility";
+
 export interface ExportProgress {
   stage: string;
   progress: number;
   output_path: string | null;
 }
 
+export type ImageFit = "Cover" | "Contain" | "Stretch";
+
 export interface BackgroundConfig {
-  type: "Gradient" | "Solid" | "Transparent";
+  /** Blurred: 録画の最初のフレームをぼかした背景 */
+  type: "Gradient" | "Solid" | "Transparent" | "Image" | "Blurred";
   from?: number[];
   to?: number[];
   angle?: number;
   color?: number[];
+  /** Image: 背景画像のパス */
+  path?: string;
+  fit?: ImageFit;
 }
 
 export type RecordingMode =
@@ -61,6 +116,7 @@ export interface ZoomKeyframe {
   zoom_level: number;
   transition: TransitionType;
   spring_hint?: SpringHint;
+  pinned?: boolean;
 }
 
 export interface SceneRect {
@@ -81,30 +137,246 @@ export interface SceneInfo {
   window_rect: SceneRect | null;
   window_title: string | null;
   event_count: number;
+  /** Timeline で枠を直接指定したシーン（SetBbox） */
+  manual_bbox?: boolean;
 }
 
 export interface TimelineEvent {
   time_ms: number;
-  event_type: "click" | "key" | "scroll" | "focus" | "window_focus";
+  event_type: "click" | "key" | "scroll" | "focus" | "window_focus" | "drag" | "bookmark";
   x: number | null;
   y: number | null;
   label: string | null;
+  /** drag の終了時刻・ドロップ位置 */
+  end_ms?: number | null;
+  end_x?: number | null;
+  end_y?: number | null;
+  /** bookmark の表示色 */
+  color?: string | null;
+}
+
+/** タイムライン上のブックマーク（録画ごとに保存） */
+export interface Bookmark {
+  id: string;
+  time_ms: number;
+  label: string;
+  color: string | null;
+}
+
+/** 書き出し時に取り除く録画上の区間（ms, 半開区間 [start_ms, end_ms)） */
+export interface CutRange {
+  start_ms: number;
+  end_ms: number;
+}
+
+export type RedactionStyle = "Pixelate" | "Blur";
+
+/** 録画フレーム上で隠す矩形（px, [left, top, right, bottom]）。時刻省略時は録画の端まで */
+export interface Redaction {
+  rect: [number, number, number, number];
+  start_ms?: number | null;
+  end_ms?: number | null;
+  style?: RedactionStyle;
+}
+
+export type PrivacyRiskKind = "SensitiveWindow" | "SecretInput" | "TypedText" | "VisibleEmail";
+
+/** 共有前に確認したい箇所。redaction はそのまま saveRedactions に渡せる */
+export interface PrivacyFinding {
+  kind: PrivacyRiskKind;
+  start_ms: number;
+  /** null の場合は録画の最後まで */
+  end_ms: number | null;
+  /** ウィンドウ名・入力欄名・入力した文字列・メールアドレス */
+  detail: string;
+  redaction: Redaction | null;
+}
+
+export interface PrivacyReport {
+  findings: PrivacyFinding[];
+  /** false の場合は OCR が使えず、画面上の文字は確認していない */
+  ocr_checked: boolean;
+}
+
+/** 注釈の図形。座標は録画フレーム上の px */
+export type AnnotationShape =
+  | { type: "Arrow"; from: [number, number]; to: [number, number] }
+  | { type: "Box"; rect: [number, number, number, number] }
+  | { type: "Text"; at: [number, number] };
+
+/** 大きさは出力映像上の px */
+export interface AnnotationStyle {
+  color: [number, number, number, number];
+  stroke_width: number;
+  font_size: number;
+}
+
+/** [start_ms, end_ms) の間だけ表示する矢印・枠・テキスト */
+export interface Annotation {
+  start_ms: number;
+  end_ms: number;
+  shape: AnnotationShape;
+  text?: string | null;
+  style?: AnnotationStyle;
+}
+
+/**
+ * シーンごとの書き出しスタイル。[start_ms, end_ms) は作成時のシーンの範囲で、
+ * null の項目は設定の値をそのまま使う
+ */
+export interface SceneStyleOverride {
+  scene_id: number;
+  start_ms: number;
+  end_ms: number;
+  background?: BackgroundConfig | null;
+  border_radius?: number | null;
+  click_ring_enabled?: boolean | null;
+  key_badge_enabled?: boolean | null;
 }
 
 export type SceneEditOp =
   | { type: "Merge"; scene_id: number }
-  | { type: "Split"; scene_id: number; split_time_ms: number };
+  | { type: "Split"; scene_id: number; split_time_ms: number }
+  | { type: "SetBbox"; scene_id: number; rect: SceneRect }
+  | { type: "Delete"; scene_id: number }
+  | { type: "Add"; start_ms: number; end_ms: number };
+
+export type RibbonPosition = "Top" | "Bottom";
+
+export interface DisclaimerSettings {
+  enabled: boolean;
+  text: string;
+  /** 表示時間 (ms)。0で全編 */
+  duration_ms: number;
+  position: RibbonPosition;
+  font_size: number;
+  text_color: [number, number, number, number];
+  background_color: [number, number, number, number];
+}
+
+export type BadgePosition = "BottomCenter" | "BottomLeft" | "BottomRight" | "TopCenter";
+
+export interface KeyBadgeStyle {
+  font_size: number;
+  text_color: [number, number, number, number];
+  background_color: [number, number, number, number];
+  position: BadgePosition;
+}
+
+export interface SpotlightSettings {
+  enabled: boolean;
+  /** 明るく残す半径（出力フレームでのpx） */
+  radius: number;
+  /** 周囲を暗くする強さ (0.0 – 1.0) */
+  dim_opacity: number;
+  /** クリック時に半径を広げる割合（0 でアニメーションなし） */
+  click_pulse: number;
+}
+
+export type WatermarkPosition = "TopLeft" | "TopRight" | "BottomLeft" | "BottomRight";
+
+export interface WatermarkSettings {
+  enabled: boolean;
+  /** 透過 PNG のパス */
+  image_path: string | null;
+  position: WatermarkPosition;
+  /** 不透明度 (0.0 – 1.0) */
+  opacity: number;
+  /** キャンバス端からの余白（出力キャンバスでのpx） */
+  margin: number;
+  /** ロゴの幅（出力キャンバスでのpx）。0で元の大きさ */
+  width: number;
+}
+
+/** 録画の四辺に確保する余白。Pixels は出力キャンバスでのpx、Percent はキャンバス短辺に対する % */
+export type CanvasPadding =
+  | { type: "Pixels"; value: number }
+  | { type: "Percent"; value: number };
+
+export type ChromeTheme = "Light" | "Dark";
+
+/** 録画の周りに描くブラウザ風のウィンドウ枠 */
+export interface WindowChromeSettings {
+  enabled: boolean;
+  theme: ChromeTheme;
+  /** タイトルバーの高さ（出力キャンバスでのpx） */
+  title_bar_height: number;
+  /** 閉じる・最小化・最大化ボタンを表示するか */
+  show_buttons: boolean;
+  /** アドレスバーの文字。空ならアドレスバーなし */
+  url: string;
+}
+
+/** 書き出し動画の下端に描く再生位置のバー */
+export interface ProgressBarSettings {
+  enabled: boolean;
+  /** バーの高さ（出力キャンバスでのpx） */
+  height: number;
+  color: [number, number, number, number];
+  /** 未再生部分の帯の色。アルファ 0 で帯なし */
+  track_color: [number, number, number, number];
+}
+
+/** ライブ配信（RTMP）の設定。録画対象をそのまま配信する */
+export interface StreamSettings {
+  /** 配信先のサーバー URL（rtmp:// または rtmps://） */
+  url: string;
+  /** ストリームキー（URL の末尾に付けて送る） */
+  stream_key: string;
+  fps: number;
+  /** 映像のビットレート (kbps) */
+  video_bitrate_kbps: number;
+  /** 書き出しと同じ背景・角丸・影・カーソルを付けて配信するか */
+  apply_style: boolean;
+}
+
+export interface TitleCardSettings {
+  enabled: boolean;
+  /** 表示時間 (ms) */
+  duration_ms: number;
+  /** 画像のパス。指定するとタイトル文字の代わりに画像を表示 */
+  image_path: string | null;
+  title: string;
+  subtitle: string;
+  /** タイトルの文字サイズ（出力キャンバスでのpx） */
+  font_size: number;
+  text_color: [number, number, number, number];
+  /** 背景。null の場合は書き出しの背景と同じ */
+  background: BackgroundConfig | null;
+}
 
 export interface AppSettings {
   recording: {
+    /** 録画の開始・停止のホットキー。各ホットキーは空文字で無効 */
     hotkey: string;
+    /** 一時停止・再開のホットキー（既定: 無効） */
+    pause_hotkey?: string;
+    /** 録画を取り消して破棄するホットキー（既定: 無効） */
+    cancel_hotkey?: string;
+    /** スクリーンショットのホットキー（既定: "Ctrl+Shift+S"） */
+    screenshot_hotkey?: string;
+    /** リプレイバッファを録画として保存するホットキー（既定: 無効） */
+    replay_hotkey?: string;
     fps: number;
     capture_system_audio: boolean;
     capture_microphone: boolean;
+    /** 録画時間の上限（秒）。60 秒前と 10 秒前に予告し、達したら自動停止。0 = 無制限 */
     max_duration_seconds: number;
     recording_mode: RecordingMode;
     /** キー入力ラベルを events.jsonl に平文で記録するか（既定: false） */
     record_key_labels?: boolean;
+    /** 録画の開始・停止時に session.json を POST する URL */
+    session_webhook_url?: string | null;
+    /** 全画面録画のフレーム保存形式（既定: "Jpeg"） */
+    frame_storage?: FrameStorage;
+    /** 空き容量の下限（MB）。下回ると録画を自動停止する。0 = 確認しない（既定: 1024） */
+    min_free_disk_mb?: number;
+    /** 録画開始前のカウントダウン（秒、0 = なし） */
+    countdown_seconds?: number;
+    /** タイムラプス録画のキャプチャ fps（0 = 通常録画）。書き出しは 30fps */
+    timelapse_fps?: number;
+    /** リプレイバッファの長さ（秒、0 = 無効）。録画していない間も直前の画面をメモリに残す */
+    replay_buffer_seconds?: number;
   };
   style: {
     background: BackgroundConfig;
@@ -112,6 +384,23 @@ export interface AppSettings {
     shadow_enabled: boolean;
     shadow_blur: number;
     shadow_offset_y: number;
+    /** 入力記録の注意書きリボン */
+    disclaimer?: DisclaimerSettings;
+    /** キー表示バッジの文字サイズ・色・位置 */
+    key_badge?: KeyBadgeStyle;
+    /** カーソル周辺以外を暗くするスポットライト */
+    spotlight?: SpotlightSettings;
+    /** ロゴ画像の透かし */
+    watermark?: WatermarkSettings;
+    /** 書き出し動画の先頭・末尾に挿入するカード */
+    intro?: TitleCardSettings;
+    outro?: TitleCardSettings;
+    /** 録画とキャンバス端の間の余白 */
+    padding?: CanvasPadding;
+    /** ブラウザ風のウィンドウ枠 */
+    window_chrome?: WindowChromeSettings;
+    /** 下端の進捗バー */
+    progress_bar?: ProgressBarSettings;
   };
   effects: {
     auto_zoom_enabled: boolean;
@@ -127,16 +416,169 @@ export interface AppSettings {
     smart_zoom_enabled: boolean;
     motion_blur_enabled: boolean;
     frame_diff_enabled: boolean;
+    /** 画面差分のピクセル差しきい値 0-255（既定: 10） */
+    frame_diff_pixel_threshold?: number;
+    /** 画面差分で無視する小さな変化の大きさ px（既定: 50） */
+    frame_diff_min_region_size?: number;
+    /** これ以上の割合が変化したフレームを除外する 0-1（既定: 0.5） */
+    frame_diff_max_change_fraction?: number;
     idle_zoom_out_ms: number;
     idle_overview_ms: number;
     min_workarea_dwell_ms: number;
     min_window_dwell_ms: number;
     cluster_lifetime_ms: number;
     cluster_stability_ms: number;
+    move_thin_distance_px?: number;
+    move_thin_interval_ms?: number;
+    drag_min_distance_px?: number;
+    drag_min_duration_ms?: number;
+    click_ring_buttons?: string[];
+    click_ring_alt_buttons?: string[];
+    /** クリックリングの色 (RGBA、既定: [59, 130, 246, 180]) */
+    click_ring_color?: [number, number, number, number];
+    click_ring_alt_color?: [number, number, number, number];
+    /** ダイアログ・メニューを枠線で示す（既定: false） */
+    ui_highlight_enabled?: boolean;
+    /** 入力した文字を入力欄の近くに字幕表示する（既定: false） */
+    typed_text_overlay?: boolean;
+    /** パスワード入力欄をフォーカス中に自動でぼかす（既定: false） */
+    auto_redact_inputs?: boolean;
+    /** 有効にするエフェクトプラグイン名（effect-plugins 付きビルドのみ） */
+    plugins?: string[];
+    /** エクスポート時に実行する Rhai スクリプトのパス（scripting 付きビルドのみ） */
+    script_path?: string | null;
+    /** ディスプレイ録画の全体表示倍率（null = 縦横比から自動） */
+    overview_zoom?: number | null;
+    /** 見やすさのプロファイル（既定: Standard） */
+    profile?: EffectsProfile;
+    /** ズーム・パンをフェード切り替えにする（既定: false） */
+    reduced_motion?: boolean;
+    /** ズームの計画に使わないアプリ（実行ファイル名またはタイトルの一部） */
+    zoom_excluded_apps?: string[];
   };
   output: {
     default_format: ExportFormat;
     default_quality: QualityPreset;
     save_directory: string;
+    /** 書き出し時に長い無操作区間を自動カットするか（既定: false） */
+    auto_trim_idle?: boolean;
+    auto_trim_min_idle_ms?: number;
+    idle_speedup?: number;
+    idle_speedup_min_ms?: number;
+    /** 出力キャンバスの縦横比（既定: Source） */
+    aspect?: OutputAspect;
+    aspect_fit?: AspectFit;
+    /** 書き出しファイル名テンプレート（{date} {time} {title} {id} {speed}） */
+    filename_template?: string;
+    /** 音声のみ書き出しのコーデック（既定: Aac = .m4a） */
+    audio_only_codec?: AudioOnlyCodec;
+    /** フレーム合成のバックエンド（既定: Cpu。Gpu は gpu フィーチャー付きビルドのみ） */
+    compositor_backend?: CompositorBackend;
+    /** 書き出しをこの長さ（分）以下の連番ファイルに分割する（0 = 分割しない） */
+    split_max_minutes?: number;
+    /** ブックマークを動画のチャプターにする（MP4 / MOV のみ） */
+    bookmark_chapters?: boolean;
   };
+  /** 録画テンプレート（トレイから 1 クリックで録画開始） */
+  templates?: RecordingTemplate[];
+  /** 予約録画（予約・取り消しは scheduleRecording / cancelScheduledRecording で行う） */
+  scheduled_recordings?: ScheduledRecording[];
+  /** 録画フォルダの自動整理 */
+  storage?: StorageSettings;
+  /** 書き出したファイルのアップロード先 */
+  share?: ShareSettings;
+  /** ライブ配信の送信先と画質（ストリームキーを含むのでプリセット・プロファイルには入らない） */
+  stream?: StreamSettings;
+}
+
+/** 書き出したファイルのアップロード先（使う先だけ設定すればよい） */
+export interface ShareSettings {
+  s3: S3ShareSettings;
+  http: HttpShareSettings;
+  youtube: YouTubeShareSettings;
+}
+
+/** S3 互換ストレージ（AWS S3・Cloudflare R2・MinIO など） */
+export interface S3ShareSettings {
+  /** エンドポイント（例: https://s3.ap-northeast-1.amazonaws.com） */
+  endpoint: string;
+  /** リージョン（R2 は auto） */
+  region: string;
+  bucket: string;
+  access_key_id: string;
+  secret_access_key: string;
+  /** オブジェクトキーの前に付ける文字列 */
+  key_prefix: string;
+  /** 共有用 URL の先頭。空ならエンドポイント/バケット/キー */
+  public_url_base: string;
+}
+
+/** 任意の HTTP サーバーへの PUT */
+export interface HttpShareSettings {
+  /** アップロード先 URL（{filename} がファイル名に置き換わる） */
+  url: string;
+  /** Authorization ヘッダーの値。空なら付けない */
+  authorization: string;
+  /** 共有用 URL（{filename} が使える）。空ならアップロード先 URL */
+  public_url: string;
+}
+
+/** YouTube（OAuth）。refresh_token は authorizeYoutube で保存される */
+export interface YouTubeShareSettings {
+  client_id: string;
+  client_secret: string;
+  refresh_token: string;
+  privacy: "private" | "unlisted" | "public";
+}
+
+/** 録画フォルダの自動整理（定期的にバックグラウンドで実行。0 / false = 無効） */
+export interface StorageSettings {
+  /** 書き出しに成功した録画のフレームを削除する（プレビュー・再書き出しはできなくなる） */
+  delete_frames_after_export: boolean;
+  /** 新しい順にこの件数だけ残す */
+  keep_last_recordings: number;
+  /** 録画フォルダ全体の上限 (GB)。超えたら古い録画から削除する */
+  max_library_gb: number;
+  /** 件数・容量の上限でまだ書き出していない録画も削除する（既定: false = 書き出し済みだけ） */
+  delete_unexported?: boolean;
+}
+
+/** 共有用スタイルプリセット（.snappi-style ファイル） */
+export interface StylePreset {
+  version: number;
+  name: string;
+  description?: string | null;
+  style: AppSettings["style"];
+  effects: AppSettings["effects"];
+  output: AppSettings["output"];
+}
+
+/** 設定プロファイル（%APPDATA%\Snappi\profiles に保存。保存先フォルダも含めて切り替わる） */
+export interface SettingsProfile {
+  version: number;
+  name: string;
+  /** 保存した日時（RFC 3339） */
+  saved_at: string;
+  style: AppSettings["style"];
+  effects: AppSettings["effects"];
+  output: AppSettings["output"];
+}
+
+export interface RecordingTemplate {
+  id: string;
+  name: string;
+  /** 録画するウィンドウのタイトルパターン（`*` 可）。null = 全画面 */
+  window_title_pattern?: string | null;
+  countdown_seconds?: number;
+  effects?: AppSettings["effects"] | null;
+  output_format?: ExportFormat | null;
+  output_quality?: QualityPreset | null;
+}
+
+/** Windows のテーマと、それに合わせた書き出しスタイルの提案（get_system_theme） */
+export interface SystemTheme {
+  accent_color: [number, number, number];
+  dark_mode: boolean;
+  background: BackgroundConfig;
+  click_ring_color: [number, number, number, number];
 }
@@ -1,7 +1,10 @@
-import { createSignal, createEffect, createMemo, onMount, onCleanup, Show } from "solid-js";
+import { createSignal, createEffect, createMemo, onMount, onCleanup, Show, For } from "solid-js";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
-import { exportRecording, exportWithKeyframes, getRecordingsList, getZoomKeyframes, computeActivityCenter } from "../lib/commands";
-import type { ExportFormat, ExportProgress, QualityPreset, RecordingInfo } from "../lib/types";
+import { exportRecording, exportWithKeyframes, listStylePresets, getRecordingsList, getRecordingSettings, getZoomKeyframes, loadKeyframes, saveKeyframes, computeActivityCenter, revealExport, copyExportToClipboard, uploadExport } from "../lib/commands";
+import type { ExportFormat, ExportProgress, QualityPreset, RecordingInfo, StylePreset } from "../lib/types";
+import type { OutputSize } from "../lib/generated/OutputSize";
+import type { ShareTarget } from "../lib/generated/ShareTarget";
+import type { UploadProgress } from "../lib/generated/UploadProgress";
 import {
   type ZoomSegment,
   keyframesToSegments,
@@ -20,12 +23,27 @@ interface Props {
   recordingId: string | null;
   onClose: () => void;
   onRedo: () => void;
+  onContinue: () => void;
 }
 
 export default function Preview(props: Props) {
   const [exporting, setExporting] = createSignal(false);
   const [exportedPath, setExportedPath] = createSignal<string | null>(null);
+  const [copied, setCopied] = createSignal(false);
+  const [shareTarget, setShareTarget] = createSignal<ShareTarget>("S3");
+  // アップロード中の進捗 (0〜1)。null = アップロードしていない
+  const [uploadProgress, setUploadProgress] = createSignal<number | null>(null);
+  const [sharedUrl, setSharedUrl] = createSignal<string | null>(null);
   const [quality, setQuality] = createSignal<QualityPreset>("Social");
+  // 目立たせる書き出し形式（録画の設定の既定形式）
+  const [defaultFormat, setDefaultFormat] = createSignal<ExportFormat>("Mp4");
+  // "preset" | 倍率 ("0.5" など) | "custom"
+  const [sizeMode, setSizeMode] = createSignal("preset");
+  const [customWidth, setCustomWidth] = createSignal(1280);
+  const [customHeight, setCustomHeight] = createSignal(720);
+  // 書き出しに使うスタイルプリセット（空 = 現在の設定）
+  const [stylePreset, setStylePreset] = createSignal("");
+  const [stylePresets, setStylePresets] = createSignal<StylePreset[]>([]);
   const [error, setError] = createSignal<string | null>(null);
   const [recordingInfo, setRecordingInfo] = createSignal<RecordingInfo | null>(null);
   const [exportProgress, setExportProgress] = createSignal<ExportProgress | null>(null);
@@ -75,15 +93,31 @@ export default function Preview(props: Props) {
         console.error("Failed to load recording info:", e);
       }
 
-      // KFをプリフェッチしてセグメントに変換
+      // テンプレートから録画した場合はテンプレートの出力設定が既定になる
       try {
-        const kfs = await getZoomKeyframes(props.recordingId);
+        const settings = await getRecordingSettings(props.recordingId);
+        setQuality(settings.output.default_quality);
+        setDefaultFormat(settings.output.default_format);
+      } catch (e) {
+        console.error("Failed to load recording settings:", e);
+      }
+
+      // KFをプリフェッチしてセグメントに変換（保存済みの編集があればそちらを優先）
+      try {
+        const saved = await loadKeyframes(props.recordingId);
+        const kfs = saved.length > 0 ? saved : await getZoomKeyframes(props.recordingId);
         setSegments(keyframesToSegments(kfs));
       } catch (e) {
         console.error("Failed to preload keyframes:", e);
       }
     }
 
+    try {
+      setStylePresets(await listStylePresets());
+    } catch (e) {
+      console.error("Failed to list style presets:", e);
+    }
+
     unlistenProgress = await listen<ExportProgress>("export-progress", (event) => {
       setExportProgress(event.payload);
     });
@@ -91,6 +125,8 @@ export default function Preview(props: Props) {
       setExporting(false);
       setExportProgress(null);
       setExportedPath(event.payload.output_path);
+      setCopied(false);
+      setSharedUrl(null);
     });
     unlistenError = await listen<{ message: string }>("export-error", (event) => {
       setExporting(false);
@@ -99,12 +135,40 @@ export default function Preview(props: Props) {
     });
   });
 
+  const handleUpload = async () => {
+    const path = exportedPath();
+    if (!path) return;
+    setError(null);
+    setSharedUrl(null);
+    setUploadProgress(0);
+    const unlisten = await listen<UploadProgress>("upload-progress", (event) => {
+      if (event.payload.path !== path) return;
+      const total = Number(event.payload.total_bytes);
+      setUploadProgress(total > 0 ? Number(event.payload.uploaded_bytes) / total : 0);
+    });
+    try {
+      setSharedUrl(await uploadExport(path, shareTarget()));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      unlisten();
+      setUploadProgress(null);
+    }
+  };
+
   onCleanup(() => {
     unlistenProgress?.();
     unlistenComplete?.();
     unlistenError?.();
   });
 
+  const outputSize = (): OutputSize | undefined => {
+    const mode = sizeMode();
+    if (mode === "preset") return undefined;
+    if (mode === "custom") return { width: customWidth(), height: customHeight(), scale: null };
+    return { width: null, height: null, scale: parseFloat(mode) };
+  };
+
   const handleExport = async (format: ExportFormat) => {
     if (!props.recordingId) return;
     const rec = recordingInfo();
@@ -115,10 +179,12 @@ export default function Preview(props: Props) {
     setExportProgress({ stage: "starting", progress: 0, output_path: null });
     try {
       const kfs = segmentsToKeyframes(segments(), rec.screen_width, rec.screen_height);
+      // 編集内容を keyframes.json に残し、次回のプレビュー・書き出しでも使う
+      await saveKeyframes(props.recordingId, kfs.length > 1 ? kfs : []);
       if (kfs.length > 1) {
-        await exportWithKeyframes(props.recordingId, kfs, format, quality());
+        await exportWithKeyframes(props.recordingId, kfs, format, quality(), undefined, undefined, outputSize(), stylePreset() || undefined);
       } else {
-        await exportRecording(props.recordingId, format, quality());
+        await exportRecording(props.recordingId, format, quality(), undefined, undefined, outputSize(), undefined, stylePreset() || undefined);
       }
     } catch (e) {
       setError(String(e));
@@ -127,6 +193,21 @@ export default function Preview(props: Props) {
     }
   };
 
+  const handlePlainExport = async () => {
+    if (!props.recordingId) return;
+    setExporting(true);
+    setError(null);
+    setExportedPath(null);
+    setExportProgress({ stage: "starting", progress: 0, output_path: null });
+    try {
+      await exportRecording(props.recordingId, "Mp4", quality(), undefined, undefined, undefined, true);
+    } catch (e) {
+      setError(String(e));
+      setExporting(false);
+      setExportProgress(null);
+    }
+  };
+
   const progressLabel = () => {
     const p = exportProgress();
     if (!p) return "";
@@ -461,12 +542,52 @@ export default function Preview(props: Props) {
               <option value="Lightweight">Lightweight (720p / 24fps)</option>
             </select>
 
-            <ExportButtons onExport={handleExport} exporting={exporting()} />
+            <select
+              value={sizeMode()}
+              onChange={(e) => setSizeMode(e.target.value)}
+              class="bg-slate-800 border border-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200 focus:outline-none focus:ring-2 focus:ring-purple-500"
+            >
+              <option value="preset">プリセットの解像度</option>
+              <option value="1">元の解像度 (100%)</option>
+              <Show when={(recordingInfo()?.monitor_scale ?? 1) > 1}>
+                <option value={String(1 / recordingInfo()!.monitor_scale!)}>
+                  論理解像度 (表示スケール {Math.round(recordingInfo()!.monitor_scale! * 100)}%)
+                </option>
+              </Show>
+              <option value="0.75">75%</option>
+              <option value="0.5">50%</option>
+              <option value="custom">カスタム...</option>
+            </select>
+            <Show when={sizeMode() === "custom"}>
+              <div class="flex items-center gap-1 text-sm text-slate-400">
+                <input type="number" min="64" max="7680" step="2" value={customWidth()} onChange={(e) => setCustomWidth(parseInt(e.target.value) || 1280)} class="w-20 bg-slate-800 border border-slate-700 rounded-lg px-2 py-1.5 text-slate-200" />
+                ×
+                <input type="number" min="64" max="4320" step="2" value={customHeight()} onChange={(e) => setCustomHeight(parseInt(e.target.value) || 720)} class="w-20 bg-slate-800 border border-slate-700 rounded-lg px-2 py-1.5 text-slate-200" />
+              </div>
+            </Show>
+
+            <Show when={stylePresets().length > 0}>
+              <select
+                value={stylePreset()}
+                onChange={(e) => setStylePreset(e.target.value)}
+                class="bg-slate-800 border border-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200 focus:outline-none focus:ring-2 focus:ring-purple-500"
+              >
+                <option value="">現在のスタイル</option>
+                <For each={stylePresets()}>
+                  {(preset) => <option value={preset.name}>{preset.name}</option>}
+                </For>
+              </select>
+            </Show>
+
+            <ExportButtons onExport={handleExport} onPlainExport={handlePlainExport} exporting={exporting()} primary={defaultFormat()} />
 
             <div class="ml-auto flex gap-2">
               <button onClick={props.onRedo} class="py-1.5 px-3 rounded-lg border border-slate-700 text-slate-400 hover:bg-slate-800 hover:text-slate-200 transition-colors text-sm">
                 再録画
               </button>
+              <button onClick={props.onContinue} class="py-1.5 px-3 rounded-lg border border-slate-700 text-slate-400 hover:bg-slate-800 hover:text-slate-200 transition-colors text-sm">
+                続きを録画
+              </button>
               <button onClick={props.onClose} class="py-1.5 px-3 rounded-lg border border-slate-700 text-slate-400 hover:bg-slate-800 hover:text-slate-200 transition-colors text-sm">
                 閉じる
               </button>
@@ -494,7 +615,56 @@ export default function Preview(props: Props) {
           </Show>
 
           <Show when={exportedPath()}>
-            <p class="text-green-400 text-sm">エクスポート完了: {exportedPath()}</p>
+            <div class="space-y-2">
+              <p class="text-green-400 text-sm">
+                エクスポート完了: {exportedPath()}
+                <button
+                  onClick={() => revealExport(exportedPath()!).catch((e) => setError(String(e)))}
+                  class="ml-2 text-slate-300 underline hover:text-white"
+                >
+                  エクスプローラーで表示
+                </button>
+                <button
+                  onClick={() =>
+                    copyExportToClipboard(exportedPath()!)
+                      .then(() => setCopied(true))
+                      .catch((e) => setError(String(e)))
+                  }
+                  class="ml-2 text-slate-300 underline hover:text-white"
+                >
+                  {copied() ? "コピーしました" : "クリップボードにコピー"}
+                </button>
+              </p>
+              <div class="flex items-center gap-2 text-sm">
+                <select
+                  value={shareTarget()}
+                  onChange={(e) => setShareTarget(e.target.value as ShareTarget)}
+                  disabled={uploadProgress() !== null}
+                  class="bg-slate-700 rounded-lg px-2 py-1 text-slate-200"
+                >
+                  <option value="S3">S3 互換ストレージ</option>
+                  <option value="Http">HTTP (PUT)</option>
+                  <option value="YouTube">YouTube</option>
+                </select>
+                <button
+                  onClick={handleUpload}
+                  disabled={uploadProgress() !== null}
+                  title="設定の「共有」で指定した先へアップロードし、共有用 URL を表示します"
+                  class="px-3 py-1 rounded-lg bg-slate-700 hover:bg-slate-600 text-slate-300 disabled:opacity-50"
+                >
+                  {uploadProgress() !== null ? `アップロード中… ${Math.round(uploadProgress()! * 100)}%` : "アップロード"}
+                </button>
+                <Show when={sharedUrl()}>
+                  <input
+                    type="text"
+                    readOnly
+                    value={sharedUrl()!}
+                    onFocus={(e) => e.currentTarget.select()}
+                    class="flex-1 bg-slate-800 rounded-lg px-2 py-1 text-slate-200 font-mono text-xs"
+                  />
+                </Show>
+              </div>
+            </div>
           </Show>
         </div>
       </footer>
@@ -1,8 +1,16 @@
-import { createSignal, onMount, For, Show } from "solid-js";
-import { getRecordingsList, deleteRecording, getSettings, saveSettings, listWindows } from "../lib/commands";
-import type { RecordingInfo, RecordingState, RecordingMode, AppSettings, WindowInfo } from "../lib/types";
+import { createSignal, onMount, onCleanup, For, Show } from "solid-js";
+import { getRecordingsPage, deleteRecording, getSettings, saveSettings, listWindows, chooseTake, takeScreenshot, startScrollingCapture, stopScrollingCapture, scheduleRecording, listScheduledRecordings, cancelScheduledRecording, startStream, stopStream, isStreaming, listRecordingTags, renameRecording, setRecordingTags, setRecordingNotes, exportProject, importProject, importVideo, openRecordingDir, batchExport } from "../lib/commands";
+import type { RecordingInfo, RecordingState, RecordingMode, AppSettings, WindowInfo, ExportFormat, QualityPreset } from "../lib/types";
+import type { BatchExportProgress } from "../lib/generated/BatchExportProgress";
+import type { BatchExportReport } from "../lib/generated/BatchExportReport";
+import type { ScheduledRecording } from "../lib/generated/ScheduledRecording";
+import type { RecordingSort } from "../lib/generated/RecordingSort";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import ThumbnailCard from "../components/ThumbnailCard";
 
+/** 一覧に一度に読み込む件数 */
+const PAGE_SIZE = 48;
+
 interface Props {
   onStartRecording: () => void;
   onOpenSettings: () => void;
@@ -15,11 +23,59 @@ export default function RecordingList(props: Props) {
   const [loading, setLoading] = createSignal(true);
   const [settings, setSettingsState] = createSignal<AppSettings | null>(null);
   const [windows, setWindows] = createSignal<WindowInfo[]>([]);
+  const [scrollCapturing, setScrollCapturing] = createSignal(false);
+  const [streaming, setStreaming] = createSignal(false);
+  // 一覧の絞り込み: 検索文字列と選択中のタグ
+  const [query, setQuery] = createSignal("");
+  const [tagFilter, setTagFilter] = createSignal<string | null>(null);
+  const [allTags, setAllTags] = createSignal<string[]>([]);
+  const [sort, setSort] = createSignal<RecordingSort>("Newest");
+  // 絞り込み後の全件数（「さらに表示」を出すか決める）
+  const [total, setTotal] = createSignal(0);
+  // 名前・タグ・メモの編集中の録画と入力中の値（タグはカンマ区切り）
+  const [editing, setEditing] = createSignal<RecordingInfo | null>(null);
+  const [editTitle, setEditTitle] = createSignal("");
+  const [editTags, setEditTags] = createSignal("");
+  const [editNotes, setEditNotes] = createSignal("");
+  // .snappi の書き出し・読み込みの結果表示と、読み込むファイルのパス
+  const [projectMessage, setProjectMessage] = createSignal<string | null>(null);
+  const [importPath, setImportPath] = createSignal("");
+  // 一括書き出しに選んだ録画と、書き出しの形式・品質・進捗・結果
+  const [selected, setSelected] = createSignal<string[]>([]);
+  const [batchFormat, setBatchFormat] = createSignal<ExportFormat>("Mp4");
+  const [batchQuality, setBatchQuality] = createSignal<QualityPreset>("Social");
+  const [batchProgress, setBatchProgress] = createSignal<BatchExportProgress | null>(null);
+  const [batchMessage, setBatchMessage] = createSignal<string | null>(null);
+  const [schedules, setSchedules] = createSignal<ScheduledRecording[]>([]);
+  // 予約フォーム: 開始日時（datetime-local の値）と録画する長さ（分）
+  const [scheduleAt, setScheduleAt] = createSignal("");
+  const [scheduleMinutes, setScheduleMinutes] = createSignal(30);
+  const [scheduleError, setScheduleError] = createSignal<string | null>(null);
 
   const currentMode = () => settings()?.recording.recording_mode ?? { type: "Display" as const };
+  // 空文字のホットキーは無効なので案内を出さない
+  const recordHotkey = () => settings()?.recording.hotkey ?? "";
+  const screenshotHotkey = () => settings()?.recording.screenshot_hotkey ?? "";
+
+  let unlistenStreamEnded: UnlistenFn | undefined;
+  let unlistenReplaySaved: UnlistenFn | undefined;
+  let unlistenBatchProgress: UnlistenFn | undefined;
+  let unlistenBatchComplete: UnlistenFn | undefined;
 
   onMount(async () => {
     await loadRecordings();
+    await loadSchedules();
+    setStreaming(await isStreaming().catch(() => false));
+    // 回線切断などで配信が止まったとき
+    unlistenStreamEnded = await listen("stream-ended", () => setStreaming(false));
+    unlistenReplaySaved = await listen("replay-saved", () => loadRecordings());
+    unlistenBatchProgress = await listen<BatchExportProgress>("batch-export-progress", (e) => setBatchProgress(e.payload));
+    unlistenBatchComplete = await listen<BatchExportReport>("batch-export-complete", (e) => {
+      setBatchProgress(null);
+      const { succeeded, failed } = e.payload;
+      setBatchMessage(failed > 0 ? `${succeeded} 件を書き出し、${failed} 件は失敗しました` : `${succeeded} 件を書き出しました`);
+      loadRecordings();
+    });
     try {
       const s = await getSettings();
       setSettingsState(s);
@@ -34,23 +90,111 @@ export default function RecordingList(props: Props) {
 
   const loadRecordings = async () => {
     try {
-      const list = await getRecordingsList();
-      setRecordings(list);
+      // 表示中の件数ぶん読み直す（削除・編集のあとも同じ位置まで見えるように）
+      const page = await getRecordingsPage(currentFilter(), sort(), 0, Math.max(recordings().length, PAGE_SIZE));
+      setRecordings(page.recordings);
+      setTotal(page.total);
+      setAllTags(await listRecordingTags());
     } catch (e) {
       console.error("Failed to load recordings:", e);
     }
     setLoading(false);
   };
 
+  const currentFilter = () => {
+    const tag = tagFilter();
+    return { query: query().trim() || null, tags: tag ? [tag] : [] };
+  };
+
+  // 絞り込み・並び順を変えたら先頭のページから出し直す
+  const reloadFromStart = async () => {
+    setRecordings([]);
+    await loadRecordings();
+  };
+
+  const loadMore = async () => {
+    try {
+      const page = await getRecordingsPage(currentFilter(), sort(), recordings().length, PAGE_SIZE);
+      setRecordings([...recordings(), ...page.recordings]);
+      setTotal(page.total);
+    } catch (e) {
+      console.error("Failed to load recordings:", e);
+    }
+  };
+
+  const handleExportProject = async (id: string) => {
+    try {
+      setProjectMessage("書き出し中…");
+      setProjectMessage(`書き出しました: ${await exportProject(id)}`);
+    } catch (e) {
+      setProjectMessage(String(e));
+    }
+  };
+
+  // .snappi はプロジェクトとして復元し、それ以外は動画として取り込む
+  const handleImportProject = async () => {
+    const path = importPath().trim().replace(/^"|"$/g, "");
+    try {
+      setProjectMessage("読み込み中…");
+      if (path.toLowerCase().endsWith(".snappi")) {
+        await importProject(path);
+      } else {
+        await importVideo(path);
+      }
+      setImportPath("");
+      setProjectMessage("読み込みました");
+      await loadRecordings();
+    } catch (e) {
+      setProjectMessage(String(e));
+    }
+  };
+
+  const openEditor = (rec: RecordingInfo) => {
+    setProjectMessage(null);
+    setEditTitle(rec.title ?? "");
+    setEditTags((rec.tags ?? []).join(", "));
+    setEditNotes(rec.notes ?? "");
+    setEditing(rec);
+  };
+
+  const saveEditor = async () => {
+    const rec = editing();
+    if (!rec) return;
+    try {
+      await renameRecording(rec.id, editTitle());
+      await setRecordingTags(rec.id, editTags().split(/[,、]/));
+      await setRecordingNotes(rec.id, editNotes());
+      setEditing(null);
+      await loadRecordings();
+    } catch (e) {
+      console.error("Failed to save recording details:", e);
+    }
+  };
+
+  const toggleTagFilter = async (tag: string) => {
+    setTagFilter(tagFilter() === tag ? null : tag);
+    await reloadFromStart();
+  };
+
   const handleDelete = async (id: string) => {
     try {
       await deleteRecording(id);
+      setSelected(selected().filter((s) => s !== id));
       await loadRecordings();
     } catch (e) {
       console.error("Failed to delete recording:", e);
     }
   };
 
+  const handleChooseTake = async (id: string) => {
+    try {
+      await chooseTake(id);
+      await loadRecordings();
+    } catch (e) {
+      console.error("Failed to choose take:", e);
+    }
+  };
+
   const changeMode = async (mode: RecordingMode) => {
     const s = settings();
     if (!s) return;
@@ -63,6 +207,86 @@ export default function RecordingList(props: Props) {
     }
   };
 
+  // スクロールキャプチャ: 開始後にウィンドウをスクロールし、終了で 1 枚の縦長画像に保存
+  const toggleScrollCapture = async () => {
+    try {
+      if (scrollCapturing()) {
+        setScrollCapturing(false);
+        await stopScrollingCapture();
+      } else {
+        await startScrollingCapture();
+        setScrollCapturing(true);
+      }
+    } catch (e) {
+      console.error("Scrolling capture failed:", e);
+    }
+  };
+
+  onCleanup(() => {
+    unlistenStreamEnded?.();
+    unlistenReplaySaved?.();
+    unlistenBatchProgress?.();
+    unlistenBatchComplete?.();
+  });
+
+  const toggleSelected = (id: string) => {
+    setSelected(selected().includes(id) ? selected().filter((s) => s !== id) : [...selected(), id]);
+  };
+
+  const handleBatchExport = async () => {
+    setBatchMessage(null);
+    try {
+      await batchExport(selected(), batchFormat(), batchQuality());
+      setSelected([]);
+    } catch (e) {
+      setBatchMessage(String(e));
+    }
+  };
+
+  const toggleStream = async () => {
+    try {
+      if (streaming()) {
+        setStreaming(false);
+        await stopStream();
+      } else {
+        await startStream();
+        setStreaming(true);
+      }
+    } catch (e) {
+      console.error("Streaming failed:", e);
+    }
+  };
+
+  const loadSchedules = async () => {
+    try {
+      setSchedules(await listScheduledRecordings());
+    } catch (e) {
+      console.error("Failed to load scheduled recordings:", e);
+    }
+  };
+
+  // 予約録画: 今選んでいる録画対象で、指定日時から指定の長さだけ録画する
+  const handleSchedule = async () => {
+    if (!scheduleAt()) return;
+    try {
+      setScheduleError(null);
+      await scheduleRecording(new Date(scheduleAt()).toISOString(), scheduleMinutes() * 60, currentMode());
+      setScheduleAt("");
+      await loadSchedules();
+    } catch (e) {
+      setScheduleError(String(e));
+    }
+  };
+
+  const handleCancelSchedule = async (id: string) => {
+    try {
+      await cancelScheduledRecording(id);
+      await loadSchedules();
+    } catch (e) {
+      console.error("Failed to cancel scheduled recording:", e);
+    }
+  };
+
   const refreshWindows = async () => {
     try {
       const wins = await listWindows();
@@ -113,6 +337,103 @@ export default function RecordingList(props: Props) {
       </header>
 
       <div class="flex-1 overflow-y-auto px-6 py-4">
+        <div class="flex flex-wrap items-center gap-2 mb-4">
+          <input
+            type="search"
+            placeholder="名前・タグ・メモで検索"
+            value={query()}
+            onInput={(e) => {
+              setQuery(e.currentTarget.value);
+              reloadFromStart();
+            }}
+            class="flex-1 min-w-40 bg-slate-800 border border-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200 placeholder-slate-500"
+          />
+          <select
+            value={sort()}
+            onChange={(e) => {
+              setSort(e.currentTarget.value as RecordingSort);
+              reloadFromStart();
+            }}
+            class="bg-slate-800 border border-slate-700 rounded-lg px-2 py-1.5 text-sm text-slate-300"
+          >
+            <option value="Newest">新しい順</option>
+            <option value="Oldest">古い順</option>
+            <option value="Longest">長い順</option>
+            <option value="Shortest">短い順</option>
+            <option value="Title">名前順</option>
+          </select>
+          <For each={allTags()}>
+            {(tag) => (
+              <button
+                onClick={() => toggleTagFilter(tag)}
+                class={`px-2 py-1 rounded-lg text-xs transition-colors ${
+                  tagFilter() === tag
+                    ? "bg-purple-500/30 text-purple-200"
+                    : "bg-slate-800 text-slate-400 hover:text-slate-200"
+                }`}
+              >
+                {tag}
+              </button>
+            )}
+          </For>
+        </div>
+
+        <Show when={selected().length > 0 || batchProgress() || batchMessage()}>
+          <div class="flex flex-wrap items-center gap-2 mb-4 px-3 py-2 rounded-lg bg-slate-800/70 text-sm">
+            <Show
+              when={batchProgress()}
+              fallback={
+                <Show when={selected().length > 0} fallback={<span class="text-slate-300">{batchMessage()}</span>}>
+                  <span class="text-slate-300">{selected().length} 件を選択中</span>
+                  <select
+                    value={batchFormat()}
+                    onChange={(e) => setBatchFormat(e.currentTarget.value as ExportFormat)}
+                    class="bg-slate-700 rounded-lg px-2 py-1 text-slate-200"
+                  >
+                    <option value="Mp4">MP4</option>
+                    <option value="WebM">WebM</option>
+                    <option value="Mov">MOV</option>
+                    <option value="Gif">GIF</option>
+                    <option value="WebP">WebP</option>
+                    <option value="Apng">APNG</option>
+                    <option value="AudioOnly">音声のみ</option>
+                  </select>
+                  <select
+                    value={batchQuality()}
+                    onChange={(e) => setBatchQuality(e.currentTarget.value as QualityPreset)}
+                    class="bg-slate-700 rounded-lg px-2 py-1 text-slate-200"
+                  >
+                    <option value="Social">Social (1080p / 30fps)</option>
+                    <option value="HighQuality">High Quality (元解像度 / 60fps)</option>
+                    <option value="Lightweight">Lightweight (720p / 24fps)</option>
+                  </select>
+                  <button onClick={handleBatchExport} class="px-3 py-1 rounded-lg bg-purple-600 hover:bg-purple-500 text-white">
+                    一括書き出し
+                  </button>
+                  <button onClick={() => setSelected([])} class="px-3 py-1 rounded-lg bg-slate-700 hover:bg-slate-600 text-slate-300">
+                    選択解除
+                  </button>
+                  <Show when={batchMessage()}>
+                    <span class="text-slate-400">{batchMessage()}</span>
+                  </Show>
+                </Show>
+              }
+            >
+              {(p) => (
+                <>
+                  <span class="text-slate-300">
+                    一括書き出し中 {p().index + 1} / {p().count}
+                  </span>
+                  <div class="flex-1 h-1.5 rounded-full bg-slate-700 overflow-hidden">
+                    <div class="h-full bg-purple-500 transition-all" style={{ width: `${Math.round(p().overall * 100)}%` }} />
+                  </div>
+                  <span class="text-slate-500 font-mono text-xs">{Math.round(p().overall * 100)}%</span>
+                </>
+              )}
+            </Show>
+          </div>
+        </Show>
+
         <Show when={loading()}>
           <div class="flex items-center justify-center h-64 text-slate-500">Loading...</div>
         </Show>
@@ -122,8 +443,15 @@ export default function RecordingList(props: Props) {
             <svg class="w-16 h-16 mb-4 text-slate-600" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="1.5">
               <path d="M15 10l4.553-2.276A1 1 0 0121 8.618v6.764a1 1 0 01-1.447.894L15 14M5 18h8a2 2 0 002-2V8a2 2 0 00-2-2H5a2 2 0 00-2 2v8a2 2 0 002 2z" />
             </svg>
-            <p class="text-sm">No recordings yet</p>
-            <p class="text-xs text-slate-600 mt-1">Press Ctrl+Shift+R to start recording</p>
+            <Show
+              when={!query().trim() && !tagFilter()}
+              fallback={<p class="text-sm">条件に合う録画はありません</p>}
+            >
+              <p class="text-sm">No recordings yet</p>
+              <Show when={recordHotkey()}>
+                <p class="text-xs text-slate-600 mt-1">Press {recordHotkey()} to start recording</p>
+              </Show>
+            </Show>
           </div>
         </Show>
 
@@ -133,14 +461,31 @@ export default function RecordingList(props: Props) {
               {(rec) => (
                 <ThumbnailCard
                   date={formatDate(rec.date)}
+                  title={rec.title}
+                  tags={rec.tags}
                   duration={formatDuration(rec.duration_ms)}
                   thumbnailPath={rec.thumbnail_path}
                   onClick={() => props.onOpenPreview(rec.id)}
+                  takeScenario={rec.take?.scenario}
+                  takeChosen={rec.take?.chosen}
+                  timelapseSpeed={rec.timelapse_speed}
                   onDelete={() => handleDelete(rec.id)}
+                  onChooseTake={() => handleChooseTake(rec.id)}
+                  onEdit={() => openEditor(rec)}
+                  selected={selected().includes(rec.id)}
+                  onToggleSelect={() => toggleSelected(rec.id)}
                 />
               )}
             </For>
           </div>
+          <Show when={recordings().length < total()}>
+            <button
+              onClick={loadMore}
+              class="mt-4 w-full py-2 rounded-lg bg-slate-800 hover:bg-slate-700 text-sm text-slate-400"
+            >
+              さらに表示（残り {total() - recordings().length} 件）
+            </button>
+          </Show>
         </Show>
       </div>
 
@@ -235,9 +580,177 @@ export default function RecordingList(props: Props) {
         >
           <div class="w-3 h-3 rounded-full bg-red-400" />
           録画開始
-          <span class="text-xs opacity-70 ml-1">(Ctrl+Shift+R)</span>
+          <Show when={recordHotkey()}>
+            <span class="text-xs opacity-70 ml-1">({recordHotkey()})</span>
+          </Show>
         </button>
+        <div class="flex gap-2">
+          <button
+            onClick={() => takeScreenshot().catch((e) => console.error("Failed to take screenshot:", e))}
+            disabled={props.recordingState !== "Idle" || scrollCapturing()}
+            class="flex-1 py-2 px-4 rounded-xl text-sm transition-colors bg-slate-800 border border-slate-700 hover:bg-slate-700 text-slate-300 disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            スクリーンショット
+            <Show when={screenshotHotkey()}>
+              <span class="text-xs opacity-70 ml-1">({screenshotHotkey()})</span>
+            </Show>
+          </button>
+          <button
+            onClick={toggleScrollCapture}
+            disabled={props.recordingState !== "Idle"}
+            title="開始してから対象のウィンドウをスクロールし、終わったら「キャプチャ終了」を押すと縦に長い 1 枚の画像に保存します"
+            class={`flex-1 py-2 px-4 rounded-xl text-sm transition-colors border disabled:opacity-50 disabled:cursor-not-allowed ${
+              scrollCapturing()
+                ? "bg-red-500/20 border-red-500/50 text-red-300 hover:bg-red-500/30"
+                : "bg-slate-800 border-slate-700 hover:bg-slate-700 text-slate-300"
+            }`}
+          >
+            {scrollCapturing() ? "キャプチャ終了" : "スクロールキャプチャ"}
+          </button>
+          <button
+            onClick={toggleStream}
+            title="設定の「ライブ配信」のサーバーへ録画対象を配信します。録画と同時に使えます"
+            class={`flex-1 py-2 px-4 rounded-xl text-sm transition-colors border ${
+              streaming()
+                ? "bg-red-500/20 border-red-500/50 text-red-300 hover:bg-red-500/30"
+                : "bg-slate-800 border-slate-700 hover:bg-slate-700 text-slate-300"
+            }`}
+          >
+            {streaming() ? "配信停止" : "ライブ配信"}
+          </button>
+        </div>
+        <details class="text-sm text-slate-300">
+          <summary class="cursor-pointer text-slate-400 hover:text-slate-200">読み込み（.snappi・動画ファイル）</summary>
+          <div class="mt-2 flex items-center gap-2">
+            <input
+              type="text"
+              value={importPath()}
+              placeholder="C:\Users\...\recording.snappi または demo.mp4"
+              onInput={(e) => setImportPath(e.currentTarget.value)}
+              class="flex-1 bg-slate-800 border border-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+            />
+            <button
+              onClick={handleImportProject}
+              disabled={!importPath().trim()}
+              class="py-1.5 px-3 rounded-lg bg-slate-800 border border-slate-700 hover:bg-slate-700 disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              読み込み
+            </button>
+          </div>
+          <Show when={!editing() && projectMessage()}>
+            <p class="mt-1 text-xs text-slate-400 break-all">{projectMessage()}</p>
+          </Show>
+        </details>
+        <details class="text-sm text-slate-300" onToggle={loadSchedules}>
+          <summary class="cursor-pointer text-slate-400 hover:text-slate-200">
+            予約録画{schedules().length > 0 ? `（${schedules().length} 件）` : ""}
+          </summary>
+          <div class="mt-2 space-y-2">
+            <div class="flex items-center gap-2">
+              <input
+                type="datetime-local"
+                value={scheduleAt()}
+                onInput={(e) => setScheduleAt(e.currentTarget.value)}
+                class="flex-1 bg-slate-800 border border-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+              />
+              <input
+                type="number"
+                min="1"
+                max="1440"
+                value={scheduleMinutes()}
+                onInput={(e) => setScheduleMinutes(Math.max(1, parseInt(e.currentTarget.value) || 1))}
+                class="w-20 bg-slate-800 border border-slate-700 rounded-lg px-2 py-1.5 text-sm text-slate-200"
+                title="録画する長さ（分）"
+              />
+              <span class="text-xs text-slate-500">分</span>
+              <button
+                onClick={handleSchedule}
+                disabled={!scheduleAt()}
+                class="py-1.5 px-3 rounded-lg bg-slate-800 border border-slate-700 hover:bg-slate-700 disabled:opacity-50 disabled:cursor-not-allowed"
+              >
+                予約
+              </button>
+            </div>
+            <Show when={scheduleError()}>
+              <p class="text-xs text-red-400">{scheduleError()}</p>
+            </Show>
+            <For each={schedules()}>
+              {(schedule) => (
+                <div class="flex items-center justify-between px-3 py-1.5 rounded-lg bg-slate-800/60">
+                  <span>
+                    {formatDate(schedule.start_time)}（{Math.ceil(Number(schedule.duration_seconds) / 60)} 分・
+                    {schedule.mode.type === "Display" ? "画面全体" : schedule.mode.type === "Window" ? schedule.mode.title || "ウィンドウ" : "範囲指定"}）
+                  </span>
+                  <button onClick={() => handleCancelSchedule(schedule.id)} class="text-xs text-slate-400 hover:text-red-400">
+                    取り消し
+                  </button>
+                </div>
+              )}
+            </For>
+          </div>
+        </details>
       </div>
+
+      <Show when={editing()}>
+        <div class="fixed inset-0 z-50 flex items-center justify-center bg-black/60" onClick={() => setEditing(null)}>
+          <div class="w-96 rounded-lg bg-slate-800 p-4 shadow-xl space-y-3 text-sm" onClick={(e) => e.stopPropagation()}>
+            <label class="block">
+              <span class="text-xs text-slate-400">名前（空欄なら日時を表示）</span>
+              <input
+                type="text"
+                value={editTitle()}
+                placeholder={formatDate(editing()!.date)}
+                onInput={(e) => setEditTitle(e.currentTarget.value)}
+                class="mt-1 w-full bg-slate-900 border border-slate-700 rounded px-2 py-1 text-slate-200"
+              />
+            </label>
+            <label class="block">
+              <span class="text-xs text-slate-400">タグ（カンマ区切り）</span>
+              <input
+                type="text"
+                value={editTags()}
+                placeholder="demo, bug-123"
+                onInput={(e) => setEditTags(e.currentTarget.value)}
+                class="mt-1 w-full bg-slate-900 border border-slate-700 rounded px-2 py-1 text-slate-200"
+              />
+            </label>
+            <label class="block">
+              <span class="text-xs text-slate-400">メモ</span>
+              <textarea
+                rows={4}
+                value={editNotes()}
+                onInput={(e) => setEditNotes(e.currentTarget.value)}
+                class="mt-1 w-full bg-slate-900 border border-slate-700 rounded px-2 py-1 text-slate-200"
+              />
+            </label>
+            <Show when={projectMessage()}>
+              <p class="text-xs text-slate-400 break-all">{projectMessage()}</p>
+            </Show>
+            <div class="flex justify-end gap-2">
+              <button
+                onClick={() => handleExportProject(editing()!.id)}
+                title="フレーム・イベント・音声・編集内容を 1 つの .snappi ファイルにまとめて保存先フォルダへ書き出します"
+                class="mr-auto px-3 py-1 rounded-lg bg-slate-700 hover:bg-slate-600 text-slate-300"
+              >
+                .snappi に書き出し
+              </button>
+              <button
+                onClick={() => openRecordingDir(editing()!.id).catch((e) => setProjectMessage(String(e)))}
+                title="録画のフレームや meta.json が入ったフォルダをエクスプローラーで開きます"
+                class="px-3 py-1 rounded-lg bg-slate-700 hover:bg-slate-600 text-slate-300"
+              >
+                フォルダを開く
+              </button>
+              <button onClick={() => setEditing(null)} class="px-3 py-1 rounded-lg bg-slate-700 hover:bg-slate-600 text-slate-300">
+                キャンセル
+              </button>
+              <button onClick={saveEditor} class="px-3 py-1 rounded-lg bg-purple-600 hover:bg-purple-500 text-white">
+                保存
+              </button>
+            </div>
+          </div>
+        </div>
+      </Show>
     </div>
   );
 }
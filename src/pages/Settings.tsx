@@ -1,11 +1,113 @@
 import { createSignal, onMount, Show, For } from "solid-js";
-import { getSettings, saveSettings, listWindows } from "../lib/commands";
-import type { AppSettings, WindowInfo, RecordingMode } from "../lib/types";
+import { getSettings, saveSettings, listWindows, listStylePresets, importStylePreset, exportStylePreset, applyStylePreset, applySystemTheme, selectArea, cleanupWorkspace, getStorageUsage, applyStoragePolicies, authorizeYoutube, listProfiles, saveProfile, applyProfile, deleteProfile } from "../lib/commands";
+import type { AppSettings, StylePreset, SettingsProfile, WindowInfo, RecordingMode, DisclaimerSettings, KeyBadgeStyle, SpotlightSettings, WatermarkSettings, TitleCardSettings, CanvasPadding, WindowChromeSettings, ProgressBarSettings, StreamSettings, StorageSettings, ShareSettings } from "../lib/types";
+import type { StorageUsage } from "../lib/generated/StorageUsage";
+import HotkeyInput from "../components/HotkeyInput";
 
 interface Props {
   onClose: () => void;
 }
 
+const CLICK_BUTTONS = [
+  { value: "left", label: "左" },
+  { value: "right", label: "右" },
+  { value: "middle", label: "中" },
+];
+
+const DEFAULT_DISCLAIMER: DisclaimerSettings = {
+  enabled: false,
+  text: "キーボード・マウス操作を記録しています",
+  duration_ms: 5000,
+  position: "Bottom",
+  font_size: 22,
+  text_color: [255, 255, 255, 255],
+  background_color: [0, 0, 0, 170],
+};
+
+const DEFAULT_KEY_BADGE: KeyBadgeStyle = {
+  font_size: 20,
+  text_color: [255, 255, 255, 255],
+  background_color: [30, 30, 30, 200],
+  position: "BottomCenter",
+};
+
+const DEFAULT_SPOTLIGHT: SpotlightSettings = {
+  enabled: false,
+  radius: 160,
+  dim_opacity: 0.55,
+  click_pulse: 0.35,
+};
+
+const DEFAULT_WATERMARK: WatermarkSettings = {
+  enabled: false,
+  image_path: null,
+  position: "BottomRight",
+  opacity: 0.8,
+  margin: 32,
+  width: 160,
+};
+
+const DEFAULT_PADDING: CanvasPadding = { type: "Pixels", value: 64 };
+
+const DEFAULT_WINDOW_CHROME: WindowChromeSettings = {
+  enabled: false,
+  theme: "Light",
+  title_bar_height: 40,
+  show_buttons: true,
+  url: "",
+};
+
+const DEFAULT_PROGRESS_BAR: ProgressBarSettings = {
+  enabled: false,
+  height: 8,
+  color: [255, 255, 255, 230],
+  track_color: [0, 0, 0, 80],
+};
+
+const DEFAULT_STORAGE: StorageSettings = {
+  delete_frames_after_export: false,
+  keep_last_recordings: 0,
+  max_library_gb: 0,
+  delete_unexported: false,
+};
+
+const DEFAULT_STREAM: StreamSettings = {
+  url: "",
+  stream_key: "",
+  fps: 30,
+  video_bitrate_kbps: 4500,
+  apply_style: true,
+};
+
+const DEFAULT_SHARE: ShareSettings = {
+  s3: { endpoint: "", region: "", bucket: "", access_key_id: "", secret_access_key: "", key_prefix: "", public_url_base: "" },
+  http: { url: "", authorization: "", public_url: "" },
+  youtube: { client_id: "", client_secret: "", refresh_token: "", privacy: "unlisted" },
+};
+
+const DEFAULT_TITLE_CARD: TitleCardSettings = {
+  enabled: false,
+  duration_ms: 2000,
+  image_path: null,
+  title: "",
+  subtitle: "",
+  font_size: 64,
+  text_color: [255, 255, 255, 255],
+  background: null,
+};
+
+type Rgba = [number, number, number, number];
+
+const rgbaToHex = (c: Rgba) => "#" + c.slice(0, 3).map((v) => v.toString(16).padStart(2, "0")).join("");
+
+/** アルファ値は元の色のものを維持する */
+const hexToRgba = (hex: string, alpha: number): Rgba => [
+  parseInt(hex.slice(1, 3), 16),
+  parseInt(hex.slice(3, 5), 16),
+  parseInt(hex.slice(5, 7), 16),
+  alpha,
+];
+
 function SettingRow(props: { label: string; desc?: string; children: any }) {
   return (
     <div class="space-y-1">
@@ -23,7 +125,21 @@ function SettingRow(props: { label: string; desc?: string; children: any }) {
 export default function Settings(props: Props) {
   const [settings, setSettings] = createSignal<AppSettings | null>(null);
   const [saved, setSaved] = createSignal(false);
+  // ホットキーの重複など、保存を拒否された理由
+  const [saveError, setSaveError] = createSignal<string | null>(null);
   const [windows, setWindows] = createSignal<WindowInfo[]>([]);
+  const [presets, setPresets] = createSignal<StylePreset[]>([]);
+  const [presetImportPath, setPresetImportPath] = createSignal("");
+  const [presetName, setPresetName] = createSignal("");
+  const [presetExportPath, setPresetExportPath] = createSignal("");
+  const [presetMessage, setPresetMessage] = createSignal<string | null>(null);
+  const [profiles, setProfiles] = createSignal<SettingsProfile[]>([]);
+  const [profileName, setProfileName] = createSignal("");
+  const [profileMessage, setProfileMessage] = createSignal<string | null>(null);
+  const [themeMessage, setThemeMessage] = createSignal<string | null>(null);
+  const [cleanupMessage, setCleanupMessage] = createSignal<string | null>(null);
+  const [storageUsage, setStorageUsage] = createSignal<StorageUsage | null>(null);
+  const [storageMessage, setStorageMessage] = createSignal<string | null>(null);
 
   onMount(async () => {
     try {
@@ -32,8 +148,140 @@ export default function Settings(props: Props) {
     } catch (e) {
       console.error("Failed to load settings:", e);
     }
+    try {
+      setPresets(await listStylePresets());
+    } catch (e) {
+      console.error("Failed to list style presets:", e);
+    }
+    try {
+      setProfiles(await listProfiles());
+    } catch (e) {
+      console.error("Failed to list profiles:", e);
+    }
   });
 
+  const handleSaveProfile = async () => {
+    const current = settings();
+    if (!current) return;
+    try {
+      // 画面で変更中の値もプロファイルに含める
+      await saveSettings(current);
+      const profile = await saveProfile(profileName().trim());
+      setProfiles(await listProfiles());
+      setProfileName("");
+      setProfileMessage(`「${profile.name}」を保存しました`);
+    } catch (e) {
+      setProfileMessage(String(e));
+    }
+  };
+
+  const handleApplyProfile = async (name: string) => {
+    try {
+      setSettings(await applyProfile(name));
+      setProfileMessage(`「${name}」に切り替えました`);
+    } catch (e) {
+      setProfileMessage(String(e));
+    }
+  };
+
+  const handleDeleteProfile = async (name: string) => {
+    try {
+      await deleteProfile(name);
+      setProfiles(await listProfiles());
+      setProfileMessage(`「${name}」を削除しました`);
+    } catch (e) {
+      setProfileMessage(String(e));
+    }
+  };
+
+  const handleImportPreset = async () => {
+    try {
+      const preset = await importStylePreset(presetImportPath().trim());
+      setPresets(await listStylePresets());
+      setPresetMessage(`「${preset.name}」を読み込みました`);
+    } catch (e) {
+      setPresetMessage(String(e));
+    }
+  };
+
+  const handleApplyPreset = async (name: string) => {
+    try {
+      setSettings(await applyStylePreset(name));
+      setPresetMessage(`「${name}」を適用しました`);
+    } catch (e) {
+      setPresetMessage(String(e));
+    }
+  };
+
+  const handleExportPreset = async () => {
+    try {
+      await exportStylePreset(presetName().trim(), presetExportPath().trim());
+      setPresetMessage("プリセットを書き出しました");
+    } catch (e) {
+      setPresetMessage(String(e));
+    }
+  };
+
+  // Windows のアクセントカラーと明暗テーマから背景とクリックリングの色を決める
+  const handleApplySystemTheme = async () => {
+    try {
+      setSettings(await applySystemTheme());
+      setThemeMessage("システムのテーマに合わせました");
+    } catch (e) {
+      setThemeMessage(String(e));
+    }
+  };
+
+  // 異常終了した書き出しの一時フォルダ・古いプロキシ・削除済み録画のキャッシュを消す
+  const handleCleanup = async () => {
+    try {
+      const report = await cleanupWorkspace();
+      const count = report.temp_dirs + report.stale_proxies + report.orphaned_caches;
+      const mb = (Number(report.reclaimed_bytes) / (1024 * 1024)).toFixed(1);
+      setCleanupMessage(count === 0 ? "削除するファイルはありませんでした" : `${count} 件を削除し、${mb} MB 空きました`);
+    } catch (e) {
+      setCleanupMessage(String(e));
+    }
+  };
+
+  const formatBytes = (bytes: bigint) => {
+    const mb = Number(bytes) / (1024 * 1024);
+    return mb >= 1024 ? `${(mb / 1024).toFixed(1)} GB` : `${mb.toFixed(1)} MB`;
+  };
+
+  const handleLoadUsage = async () => {
+    try {
+      setStorageUsage(await getStorageUsage());
+    } catch (e) {
+      setStorageMessage(String(e));
+    }
+  };
+
+  // 保存済みの設定で整理するので、変更は先に保存してもらう
+  const handleApplyStorage = async () => {
+    try {
+      const report = await applyStoragePolicies();
+      const count = report.frames_removed + report.recordings_deleted;
+      setStorageMessage(
+        count === 0
+          ? "整理するものはありませんでした"
+          : `フレーム ${report.frames_removed} 件・録画 ${report.recordings_deleted} 件を削除し、${formatBytes(report.reclaimed_bytes)} 空きました`,
+      );
+      if (storageUsage()) await handleLoadUsage();
+    } catch (e) {
+      setStorageMessage(String(e));
+    }
+  };
+
+  const handleSelectArea = async () => {
+    try {
+      const mode = await selectArea();
+      if (mode) setRecordingMode(mode);
+    } catch (e) {
+      console.error("Failed to select area:", e);
+    }
+  };
+
   const refreshWindows = async () => {
     try {
       const wins = await listWindows();
@@ -61,15 +309,108 @@ export default function Settings(props: Props) {
     });
   };
 
+  const disclaimer = (): DisclaimerSettings => settings()?.style.disclaimer ?? DEFAULT_DISCLAIMER;
+
+  const updateDisclaimer = <K extends keyof DisclaimerSettings>(key: K, value: DisclaimerSettings[K]) => {
+    updateField("style", "disclaimer", { ...disclaimer(), [key]: value });
+  };
+
+  const spotlight = (): SpotlightSettings => settings()?.style.spotlight ?? DEFAULT_SPOTLIGHT;
+
+  const updateSpotlight = <K extends keyof SpotlightSettings>(key: K, value: SpotlightSettings[K]) => {
+    updateField("style", "spotlight", { ...spotlight(), [key]: value });
+  };
+
+  const watermark = (): WatermarkSettings => settings()?.style.watermark ?? DEFAULT_WATERMARK;
+
+  const updateWatermark = <K extends keyof WatermarkSettings>(key: K, value: WatermarkSettings[K]) => {
+    updateField("style", "watermark", { ...watermark(), [key]: value });
+  };
+
+  const padding = (): CanvasPadding => settings()?.style.padding ?? DEFAULT_PADDING;
+
+  const windowChrome = (): WindowChromeSettings => settings()?.style.window_chrome ?? DEFAULT_WINDOW_CHROME;
+
+  const updateWindowChrome = <K extends keyof WindowChromeSettings>(key: K, value: WindowChromeSettings[K]) => {
+    updateField("style", "window_chrome", { ...windowChrome(), [key]: value });
+  };
+
+  const progressBar = (): ProgressBarSettings => settings()?.style.progress_bar ?? DEFAULT_PROGRESS_BAR;
+
+  const updateProgressBar = <K extends keyof ProgressBarSettings>(key: K, value: ProgressBarSettings[K]) => {
+    updateField("style", "progress_bar", { ...progressBar(), [key]: value });
+  };
+
+  const storage = (): StorageSettings => settings()?.storage ?? DEFAULT_STORAGE;
+
+  const updateStorage = <K extends keyof StorageSettings>(key: K, value: StorageSettings[K]) => {
+    const current = settings();
+    if (!current) return;
+    setSettings({ ...current, storage: { ...storage(), [key]: value } });
+  };
+
+  const stream = (): StreamSettings => settings()?.stream ?? DEFAULT_STREAM;
+
+  const updateStream = <K extends keyof StreamSettings>(key: K, value: StreamSettings[K]) => {
+    const current = settings();
+    if (!current) return;
+    setSettings({ ...current, stream: { ...stream(), [key]: value } });
+  };
+
+  const share = (): ShareSettings => settings()?.share ?? DEFAULT_SHARE;
+
+  const updateShare = <G extends keyof ShareSettings, K extends keyof ShareSettings[G]>(group: G, key: K, value: ShareSettings[G][K]) => {
+    const current = settings();
+    if (!current) return;
+    setSettings({ ...current, share: { ...share(), [group]: { ...share()[group], [key]: value } } });
+  };
+
+  const [youtubeMessage, setYoutubeMessage] = createSignal<string | null>(null);
+
+  const handleAuthorizeYoutube = async () => {
+    const current = settings();
+    if (!current) return;
+    try {
+      // クライアント ID・シークレットを先に保存しておく
+      await saveSettings(current);
+      setYoutubeMessage("ブラウザで許可してください…");
+      setSettings(await authorizeYoutube());
+      setYoutubeMessage("YouTube と連携しました");
+    } catch (e) {
+      setYoutubeMessage(String(e));
+    }
+  };
+
+  const titleCard = (which: "intro" | "outro"): TitleCardSettings => settings()?.style[which] ?? DEFAULT_TITLE_CARD;
+
+  const updateTitleCard = <K extends keyof TitleCardSettings>(which: "intro" | "outro", key: K, value: TitleCardSettings[K]) => {
+    updateField("style", which, { ...titleCard(which), [key]: value });
+  };
+
+  const keyBadge = (): KeyBadgeStyle => settings()?.style.key_badge ?? DEFAULT_KEY_BADGE;
+
+  const updateKeyBadge = <K extends keyof KeyBadgeStyle>(key: K, value: KeyBadgeStyle[K]) => {
+    updateField("style", "key_badge", { ...keyBadge(), [key]: value });
+  };
+
+  const clickRingButtons = () => settings()?.effects.click_ring_buttons ?? ["left", "right", "middle"];
+
+  const toggleButton = (key: string, current: string[], btn: string, on: boolean) => {
+    const next = on ? [...current.filter((b) => b !== btn), btn] : current.filter((b) => b !== btn);
+    updateField("effects", key, next);
+  };
+
   const handleSave = async () => {
     const s = settings();
     if (!s) return;
     try {
       await saveSettings(s);
+      setSaveError(null);
       setSaved(true);
       setTimeout(() => setSaved(false), 2000);
     } catch (e) {
       console.error("Failed to save settings:", e);
+      setSaveError(String(e));
     }
   };
 
@@ -95,8 +436,29 @@ export default function Settings(props: Props) {
             <section>
               <h3 class="text-sm font-semibold text-slate-400 uppercase tracking-wider mb-3">録画</h3>
               <div class="space-y-3 bg-slate-800/50 rounded-xl p-4">
-                <SettingRow label="ショートカットキー" desc="録画の開始・停止に使うキーボードショートカットです">
-                  <span class="text-sm text-slate-400 bg-slate-700/50 px-3 py-1 rounded-lg">{s().recording.hotkey}</span>
+                <SettingRow label="ショートカットキー" desc="録画の開始・停止に使うキーボードショートカットです。クリックしてキーを押すと変更できます（Backspace で無効、Esc で取りやめ）">
+                  <HotkeyInput value={s().recording.hotkey} action="ToggleRecording" onChange={(v) => updateField("recording", "hotkey", v)} />
+                </SettingRow>
+                <SettingRow label="一時停止・再開のキー" desc="録画中に押すと一時停止し、もう一度押すと再開します。他のアプリのショートカットと重ならないよう既定では無効です">
+                  <HotkeyInput value={s().recording.pause_hotkey ?? ""} action="PauseResume" onChange={(v) => updateField("recording", "pause_hotkey", v)} />
+                </SettingRow>
+                <SettingRow label="録画を取り消すキー" desc="録画を止めて、保存せずに破棄します。誤って押さないよう既定では無効です">
+                  <HotkeyInput value={s().recording.cancel_hotkey ?? ""} action="Cancel" onChange={(v) => updateField("recording", "cancel_hotkey", v)} />
+                </SettingRow>
+                <SettingRow label="スクリーンショットのキー" desc="画面全体の静止画を保存します">
+                  <HotkeyInput value={s().recording.screenshot_hotkey ?? "Ctrl+Shift+S"} action="Screenshot" onChange={(v) => updateField("recording", "screenshot_hotkey", v)} />
+                </SettingRow>
+                <SettingRow label="リプレイバッファ" desc="録画していない間も画面全体を 15fps でメモリに録り続け、リプレイのキーで直前の数秒を録画として保存します。長いほどメモリを使います（1080p で 30 秒あたり約 150MB）">
+                  <select value={s().recording.replay_buffer_seconds ?? 0} onChange={(e) => updateField("recording", "replay_buffer_seconds", parseInt(e.target.value))} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                    <option value="0">オフ</option>
+                    <option value="15">15秒</option>
+                    <option value="30">30秒</option>
+                    <option value="60">60秒</option>
+                    <option value="120">120秒</option>
+                  </select>
+                </SettingRow>
+                <SettingRow label="リプレイを保存するキー" desc="リプレイバッファの内容を録画一覧に保存します">
+                  <HotkeyInput value={s().recording.replay_hotkey ?? ""} action="SaveReplay" onChange={(v) => updateField("recording", "replay_hotkey", v)} />
                 </SettingRow>
                 <SettingRow label="フレームレート (FPS)" desc="1秒あたりのキャプチャ枚数。高いほど滑らかですがファイルサイズが増えます">
                   <select value={s().recording.fps} onChange={(e) => updateField("recording", "fps", parseInt(e.target.value))} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
@@ -105,6 +467,33 @@ export default function Settings(props: Props) {
                     <option value="60">60</option>
                   </select>
                 </SettingRow>
+                <SettingRow label="タイムラプス" desc="少ない枚数で長時間の作業を撮り、書き出しでは 30fps に詰めて早送りにします（1fps なら 30 倍速）。クリックやズームも同じ速さで合わせます。音声は録音しません">
+                  <select value={s().recording.timelapse_fps ?? 0} onChange={(e) => updateField("recording", "timelapse_fps", parseInt(e.target.value))} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                    <option value="0">オフ</option>
+                    <option value="1">1fps（30倍速）</option>
+                    <option value="2">2fps（15倍速）</option>
+                  </select>
+                </SettingRow>
+                <SettingRow label="フレーム保存形式" desc="パックは LZ4 圧縮で 1 ファイルにまとめて保存します。CPU 負荷が低く大画面でもコマ落ちしにくい反面、ディスク使用量が増えます（画面全体の録画のみ）">
+                  <select value={s().recording.frame_storage ?? "Jpeg"} onChange={(e) => updateField("recording", "frame_storage", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                    <option value="Jpeg">JPEG</option>
+                    <option value="Packed">パック (LZ4)</option>
+                  </select>
+                </SettingRow>
+                <SettingRow label="開始前のカウントダウン" desc="録画開始の操作から実際にキャプチャが始まるまでの秒数。カウント中にもう一度操作するとキャンセルします">
+                  <select value={s().recording.countdown_seconds ?? 0} onChange={(e) => updateField("recording", "countdown_seconds", parseInt(e.target.value))} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                    <option value="0">なし</option>
+                    <option value="3">3秒</option>
+                    <option value="5">5秒</option>
+                    <option value="10">10秒</option>
+                  </select>
+                </SettingRow>
+                <SettingRow label="録画時間の上限 (分)" desc="この長さに達すると録画を自動で停止します（一時停止中は数えません）。60秒前と10秒前にお知らせします。0 で無制限">
+                  <input type="number" min="0" step="1" value={Math.round(s().recording.max_duration_seconds / 60)} onChange={(e) => updateField("recording", "max_duration_seconds", Math.max(0, parseInt(e.target.value) || 0) * 60)} class={numInput()} />
+                </SettingRow>
+                <SettingRow label="空き容量の下限 (MB)" desc="録画先ドライブの空きがこれを下回ると録画を自動で停止します。2倍を下回った時点で警告を表示します。0 で確認しません">
+                  <input type="number" min="0" step="256" value={s().recording.min_free_disk_mb ?? 1024} onChange={(e) => updateField("recording", "min_free_disk_mb", Math.max(0, parseInt(e.target.value) || 0))} class={numInput()} />
+                </SettingRow>
                 <SettingRow label="システム音声" desc="PCから出力されている音声（アプリの音など）を一緒に録音します">
                   <input type="checkbox" checked={s().recording.capture_system_audio} onChange={(e) => updateField("recording", "capture_system_audio", e.target.checked)} class="rounded" />
                 </SettingRow>
@@ -119,6 +508,15 @@ export default function Settings(props: Props) {
                     class="rounded"
                   />
                 </SettingRow>
+                <SettingRow label="セッション通知 URL" desc="録画の開始・停止時に session.json（ID・モード・時刻など）をこの URL へ POST します。テスト自動化ツールとの連携用で、空欄なら送信しません">
+                  <input
+                    type="text"
+                    placeholder="https://"
+                    value={s().recording.session_webhook_url ?? ""}
+                    onChange={(e) => updateField("recording", "session_webhook_url", e.target.value.trim() || null)}
+                    class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64"
+                  />
+                </SettingRow>
                 <SettingRow label="録画モード" desc="画面全体・特定ウィンドウ・指定範囲から選べます">
                   <select
                     value={s().recording.recording_mode.type}
@@ -167,6 +565,9 @@ export default function Settings(props: Props) {
                   </SettingRow>
                 </Show>
                 <Show when={s().recording.recording_mode.type === "Area"}>
+                  <SettingRow label="範囲" desc="画面上をドラッグして録画する範囲を選びます。座標を直接入力することもできます">
+                    <button onClick={handleSelectArea} class="px-3 py-1 bg-slate-700 hover:bg-slate-600 rounded-lg text-sm">画面上で選択</button>
+                  </SettingRow>
                   <div class="grid grid-cols-2 gap-2">
                     <div class="flex items-center gap-2">
                       <label class="text-xs text-slate-400 w-6">X</label>
@@ -225,12 +626,72 @@ export default function Settings(props: Props) {
                 <SettingRow label="最大ズーム倍率" desc="ズームの上限値。これを超えてズームインすることはありません">
                   <input type="number" min="1.5" max="5.0" step="0.1" value={s().effects.max_zoom} onChange={(e) => updateField("effects", "max_zoom", parseFloat(e.target.value) || 2.0)} class={numInput()} />
                 </SettingRow>
+                <SettingRow label="全体表示の倍率" desc="ディスプレイ録画でズームアウトしたときの倍率です。空欄なら自動（ウルトラワイド画面では中央を16:9相当で切り出します）">
+                  <input type="number" min="1.0" max="5.0" step="0.1" placeholder="自動" value={s().effects.overview_zoom ?? ""} onChange={(e) => updateField("effects", "overview_zoom", parseFloat(e.target.value) || null)} class={numInput()} />
+                </SettingRow>
                 <SettingRow label="クリックエフェクト" desc="クリック位置にリング状のアニメーションを表示します">
                   <input type="checkbox" checked={s().effects.click_ring_enabled} onChange={(e) => updateField("effects", "click_ring_enabled", e.target.checked)} class="rounded" />
                 </SettingRow>
+                <Show when={s().effects.click_ring_enabled}>
+                  <SettingRow label="対象ボタン" desc="右クリックはメニュー表示で分かるため、左のみにすることもできます">
+                    <div class="flex gap-3 text-sm">
+                      <For each={CLICK_BUTTONS}>
+                        {(btn) => (
+                          <label class="flex items-center gap-1">
+                            <input type="checkbox" checked={clickRingButtons().includes(btn.value)} onChange={(e) => toggleButton("click_ring_buttons", clickRingButtons(), btn.value, e.target.checked)} class="rounded" />
+                            {btn.label}
+                          </label>
+                        )}
+                      </For>
+                    </div>
+                  </SettingRow>
+                  <SettingRow label="リングの色" desc="通常のクリックリングの色です。ハイビジビリティでは黄色になります">
+                    <input type="color" value={rgbaToHex(s().effects.click_ring_color ?? [59, 130, 246, 180])} onChange={(e) => updateField("effects", "click_ring_color", hexToRgba(e.target.value, (s().effects.click_ring_color ?? [59, 130, 246, 180])[3]))} />
+                  </SettingRow>
+                  <SettingRow label="別スタイルのボタン" desc="選択したボタンは別の色の二重リングで表示します">
+                    <div class="flex gap-3 text-sm">
+                      <For each={CLICK_BUTTONS}>
+                        {(btn) => (
+                          <label class="flex items-center gap-1">
+                            <input type="checkbox" checked={(s().effects.click_ring_alt_buttons ?? []).includes(btn.value)} onChange={(e) => toggleButton("click_ring_alt_buttons", s().effects.click_ring_alt_buttons ?? [], btn.value, e.target.checked)} class="rounded" />
+                            {btn.label}
+                          </label>
+                        )}
+                      </For>
+                    </div>
+                  </SettingRow>
+                </Show>
                 <SettingRow label="キー表示" desc="押されたキーをバッジとして画面に表示します">
                   <input type="checkbox" checked={s().effects.key_badge_enabled} onChange={(e) => updateField("effects", "key_badge_enabled", e.target.checked)} class="rounded" />
                 </SettingRow>
+                <Show when={s().effects.key_badge_enabled}>
+                  <SettingRow label="キー表示の文字サイズ (px)" desc="出力フレーム上でのキー表示の文字の大きさです">
+                    <input type="number" min="8" max="72" step="1" value={keyBadge().font_size} onChange={(e) => updateKeyBadge("font_size", parseFloat(e.target.value) || 20)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="キー表示の位置" desc="キー表示バッジを画面のどこに表示するかを選びます">
+                    <select value={keyBadge().position} onChange={(e) => updateKeyBadge("position", e.target.value as KeyBadgeStyle["position"])} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                      <option value="BottomCenter">下中央</option>
+                      <option value="BottomLeft">左下</option>
+                      <option value="BottomRight">右下</option>
+                      <option value="TopCenter">上中央</option>
+                    </select>
+                  </SettingRow>
+                  <SettingRow label="キー表示の色" desc="文字色と背景色です。背景の透明度は維持されます">
+                    <div class="flex gap-2">
+                      <input type="color" value={rgbaToHex(keyBadge().text_color)} onChange={(e) => updateKeyBadge("text_color", hexToRgba(e.target.value, keyBadge().text_color[3]))} />
+                      <input type="color" value={rgbaToHex(keyBadge().background_color)} onChange={(e) => updateKeyBadge("background_color", hexToRgba(e.target.value, keyBadge().background_color[3]))} />
+                    </div>
+                  </SettingRow>
+                </Show>
+                <SettingRow label="ダイアログ・メニューの強調" desc="ダイアログやメニューが開いている間、その範囲を枠線と名前ラベルで示します。カーソルが触れない一瞬のUIにも気付きやすくなります">
+                  <input type="checkbox" checked={s().effects.ui_highlight_enabled ?? false} onChange={(e) => updateField("effects", "ui_highlight_enabled", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <SettingRow label="入力文字の字幕" desc="キー入力をまとめて、入力欄の近くに入力中の文字を表示します。文字を復元するには「キー入力の詳細を記録」が必要で、未記録の録画では伏せ字になります">
+                  <input type="checkbox" checked={s().effects.typed_text_overlay ?? false} onChange={(e) => updateField("effects", "typed_text_overlay", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <SettingRow label="パスワード欄の自動ぼかし" desc="パスワード入力欄にフォーカスがある間、その範囲をぼかして書き出します。入力文字の字幕もその間は表示しません">
+                  <input type="checkbox" checked={s().effects.auto_redact_inputs ?? false} onChange={(e) => updateField("effects", "auto_redact_inputs", e.target.checked)} class="rounded" />
+                </SettingRow>
                 <SettingRow label="カーソル補間" desc="マウスカーソルの動きをなめらかに補間します">
                   <input type="checkbox" checked={s().effects.cursor_smoothing} onChange={(e) => updateField("effects", "cursor_smoothing", e.target.checked)} class="rounded" />
                 </SettingRow>
@@ -248,9 +709,32 @@ export default function Settings(props: Props) {
                 <SettingRow label="モーションブラー" desc="ズーム・パン中に動きのブレを加えて映像に臨場感を出します">
                   <input type="checkbox" checked={s().effects.motion_blur_enabled} onChange={(e) => updateField("effects", "motion_blur_enabled", e.target.checked)} class="rounded" />
                 </SettingRow>
+                <SettingRow label="見やすさプロファイル" desc="「高視認性」はカーソルを大きく、クリックリングを高コントラストにし、アニメーションをゆっくり・モーションブラーなしにします">
+                  <select value={s().effects.profile ?? "Standard"} onChange={(e) => updateField("effects", "profile", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                    <option value="Standard">標準</option>
+                    <option value="HighVisibility">高視認性</option>
+                  </select>
+                </SettingRow>
+                <SettingRow label="動きを抑える" desc="ズーム・パンをアニメーションさせず、短いフェードで切り替えます。小さな画角の変化は行いません。画面の動きが苦手な視聴者向けです">
+                  <input type="checkbox" checked={s().effects.reduced_motion ?? false} onChange={(e) => updateField("effects", "reduced_motion", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <SettingRow label="ズームで追わないアプリ" desc="ここに挙げたアプリ（実行ファイル名かウィンドウタイトルの一部、カンマ区切り）を操作してもズームしません。画面には映ります">
+                  <input type="text" placeholder="spotify.exe, Slack" value={(s().effects.zoom_excluded_apps ?? []).join(", ")} onChange={(e) => updateField("effects", "zoom_excluded_apps", e.target.value.split(",").map((v) => v.trim()).filter((v) => v))} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                </SettingRow>
                 <SettingRow label="画面差分でズーム調整" desc="画面の変化範囲を検出し、ズーム領域を拡張します。OFFにすると操作座標のみでズーム範囲を決定します">
                   <input type="checkbox" checked={s().effects.frame_diff_enabled} onChange={(e) => updateField("effects", "frame_diff_enabled", e.target.checked)} class="rounded" />
                 </SettingRow>
+                <Show when={s().effects.frame_diff_enabled}>
+                  <SettingRow label="差分しきい値" desc="変化とみなすピクセル差 (0-255)。動く壁紙で誤検出する場合は上げ、変化を取りこぼす場合は下げます">
+                    <input type="number" min="1" max="255" step="1" value={s().effects.frame_diff_pixel_threshold ?? 10} onChange={(e) => updateField("effects", "frame_diff_pixel_threshold", parseInt(e.target.value) || 10)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="最小変化サイズ (px)" desc="これより小さな変化は無視します。点滅するカーソルを拾う場合は大きくします">
+                    <input type="number" min="0" max="1000" step="10" value={s().effects.frame_diff_min_region_size ?? 50} onChange={(e) => updateField("effects", "frame_diff_min_region_size", parseInt(e.target.value) || 0)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="最大変化率" desc="画面のこの割合以上が一度に変わったフレームは画面切り替えとみなして除外します (0.0-1.0)">
+                    <input type="number" min="0.05" max="1" step="0.05" value={s().effects.frame_diff_max_change_fraction ?? 0.5} onChange={(e) => updateField("effects", "frame_diff_max_change_fraction", parseFloat(e.target.value) || 0.5)} class={numInput()} />
+                  </SettingRow>
+                </Show>
               </div>
             </section>
 
@@ -306,12 +790,156 @@ export default function Settings(props: Props) {
             <section>
               <h3 class="text-sm font-semibold text-slate-400 uppercase tracking-wider mb-3">スタイル</h3>
               <div class="space-y-3 bg-slate-800/50 rounded-xl p-4">
+                <SettingRow label="システムのテーマに合わせる" desc="Windows のアクセントカラーとライト/ダークテーマから、背景のグラデーションとクリックリングの色を設定して保存します">
+                  <div class="flex flex-col items-end gap-1">
+                    <button onClick={handleApplySystemTheme} class="px-3 py-1 bg-slate-700 hover:bg-slate-600 rounded-lg text-sm">適用</button>
+                    <Show when={themeMessage()}>
+                      <span class="text-xs text-slate-400">{themeMessage()}</span>
+                    </Show>
+                  </div>
+                </SettingRow>
                 <SettingRow label="角丸 (px)" desc="動画の角の丸みをピクセル単位で指定します。0で角丸なし">
                   <input type="number" min="0" max="48" step="1" value={s().style.border_radius} onChange={(e) => updateField("style", "border_radius", parseInt(e.target.value) || 0)} class={numInput()} />
                 </SettingRow>
+                <SettingRow label="余白" desc="録画の周囲に背景を見せる幅です。px はキャンバス上の大きさ、% はキャンバス短辺に対する割合です">
+                  <div class="flex items-center gap-2">
+                    <input type="number" min="0" max={padding().type === "Percent" ? 40 : 1000} step="1" value={padding().value} onChange={(e) => updateField("style", "padding", { ...padding(), value: Math.max(0, parseFloat(e.target.value) || 0) })} class={numInput()} />
+                    <select value={padding().type} onChange={(e) => updateField("style", "padding", { type: e.target.value as CanvasPadding["type"], value: e.target.value === "Percent" ? 5 : 64 })} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                      <option value="Pixels">px</option>
+                      <option value="Percent">%</option>
+                    </select>
+                  </div>
+                </SettingRow>
+                <SettingRow label="ウィンドウ枠" desc="録画の上にブラウザ風のタイトルバーを表示します。タイトルバーは余白の部分に描かれます">
+                  <input type="checkbox" checked={windowChrome().enabled} onChange={(e) => updateWindowChrome("enabled", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <Show when={windowChrome().enabled}>
+                  <SettingRow label="枠のテーマ" desc="タイトルバーの配色です">
+                    <select value={windowChrome().theme} onChange={(e) => updateWindowChrome("theme", e.target.value as WindowChromeSettings["theme"])} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                      <option value="Light">ライト</option>
+                      <option value="Dark">ダーク</option>
+                    </select>
+                  </SettingRow>
+                  <SettingRow label="タイトルバーの高さ (px)" desc="キャンバス上の高さです">
+                    <input type="number" min="16" max="120" step="2" value={windowChrome().title_bar_height} onChange={(e) => updateWindowChrome("title_bar_height", parseInt(e.target.value) || 40)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="ウィンドウボタン" desc="左上に閉じる・最小化・最大化ボタンを表示します">
+                    <input type="checkbox" checked={windowChrome().show_buttons} onChange={(e) => updateWindowChrome("show_buttons", e.target.checked)} class="rounded" />
+                  </SettingRow>
+                  <SettingRow label="アドレスバーの文字" desc="空欄にするとアドレスバーを表示しません">
+                    <input type="text" value={windowChrome().url} onChange={(e) => updateWindowChrome("url", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                  </SettingRow>
+                </Show>
+                <SettingRow label="進捗バー" desc="書き出した動画の下端に、再生位置を示すバーを表示します">
+                  <input type="checkbox" checked={progressBar().enabled} onChange={(e) => updateProgressBar("enabled", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <Show when={progressBar().enabled}>
+                  <SettingRow label="バーの高さ (px)" desc="キャンバス上の高さです">
+                    <input type="number" min="1" max="64" step="1" value={progressBar().height} onChange={(e) => updateProgressBar("height", parseInt(e.target.value) || 8)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="バーの色" desc="再生済みの部分と、まだ再生していない部分の帯の色です">
+                    <div class="flex items-center gap-2">
+                      <input type="color" value={rgbaToHex(progressBar().color)} onChange={(e) => updateProgressBar("color", hexToRgba(e.target.value, progressBar().color[3]))} />
+                      <input type="color" value={rgbaToHex(progressBar().track_color)} onChange={(e) => updateProgressBar("track_color", hexToRgba(e.target.value, progressBar().track_color[3]))} />
+                    </div>
+                  </SettingRow>
+                </Show>
                 <SettingRow label="影" desc="動画の周囲にドロップシャドウを表示して立体感を出します">
                   <input type="checkbox" checked={s().style.shadow_enabled} onChange={(e) => updateField("style", "shadow_enabled", e.target.checked)} class="rounded" />
                 </SettingRow>
+                <SettingRow label="スポットライト" desc="カーソルの周囲だけを明るく残し、それ以外を暗くします。クリックすると明るい範囲が一瞬広がります">
+                  <input type="checkbox" checked={spotlight().enabled} onChange={(e) => updateSpotlight("enabled", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <Show when={spotlight().enabled}>
+                  <SettingRow label="スポットライトの半径 (px)" desc="明るく残す範囲の半径です（出力フレーム上の大きさ）">
+                    <input type="number" min="20" max="1000" step="10" value={spotlight().radius} onChange={(e) => updateSpotlight("radius", parseFloat(e.target.value) || 160)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="暗さ" desc="周囲を暗くする強さです (0.0 – 1.0)">
+                    <input type="number" min="0" max="1" step="0.05" value={spotlight().dim_opacity} onChange={(e) => updateSpotlight("dim_opacity", parseFloat(e.target.value) || 0)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="クリック時の広がり" desc="クリック時に半径を広げる割合です。0でアニメーションなし">
+                    <input type="number" min="0" max="2" step="0.05" value={spotlight().click_pulse} onChange={(e) => updateSpotlight("click_pulse", parseFloat(e.target.value) || 0)} class={numInput()} />
+                  </SettingRow>
+                </Show>
+                <SettingRow label="入力記録の注意書き" desc="書き出した動画の冒頭に「キーボード・マウス操作を記録しています」等の帯を表示します">
+                  <input type="checkbox" checked={disclaimer().enabled} onChange={(e) => updateDisclaimer("enabled", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <Show when={disclaimer().enabled}>
+                  <SettingRow label="注意書きの文言" desc="帯に表示するテキストです">
+                    <input type="text" value={disclaimer().text} onChange={(e) => updateDisclaimer("text", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                  </SettingRow>
+                  <SettingRow label="表示時間 (ms)" desc="書き出し動画の先頭からこの時間だけ表示します。0で全編に表示">
+                    <input type="number" min="0" max="600000" step="1000" value={disclaimer().duration_ms} onChange={(e) => updateDisclaimer("duration_ms", parseInt(e.target.value) || 0)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="表示位置" desc="帯を画面の上端と下端のどちらに表示するかを選びます">
+                    <select value={disclaimer().position} onChange={(e) => updateDisclaimer("position", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                      <option value="Bottom">下</option>
+                      <option value="Top">上</option>
+                    </select>
+                  </SettingRow>
+                  <SettingRow label="文字サイズ (px)" desc="出力キャンバス上での文字の大きさです">
+                    <input type="number" min="8" max="96" step="1" value={disclaimer().font_size} onChange={(e) => updateDisclaimer("font_size", parseFloat(e.target.value) || 22)} class={numInput()} />
+                  </SettingRow>
+                </Show>
+                <SettingRow label="ロゴの透かし" desc="書き出した動画の隅にロゴ画像（PNG）を重ねて表示します">
+                  <input type="checkbox" checked={watermark().enabled} onChange={(e) => updateWatermark("enabled", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <Show when={watermark().enabled}>
+                  <SettingRow label="ロゴ画像のパス" desc="透過 PNG のファイルパスを指定します">
+                    <input type="text" value={watermark().image_path ?? ""} onChange={(e) => updateWatermark("image_path", e.target.value.trim() || null)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                  </SettingRow>
+                  <SettingRow label="表示位置" desc="ロゴを表示する隅を選びます">
+                    <select value={watermark().position} onChange={(e) => updateWatermark("position", e.target.value as WatermarkSettings["position"])} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                      <option value="BottomRight">右下</option>
+                      <option value="BottomLeft">左下</option>
+                      <option value="TopRight">右上</option>
+                      <option value="TopLeft">左上</option>
+                    </select>
+                  </SettingRow>
+                  <SettingRow label="不透明度" desc="ロゴの濃さです (0.0 – 1.0)">
+                    <input type="number" min="0" max="1" step="0.05" value={watermark().opacity} onChange={(e) => updateWatermark("opacity", parseFloat(e.target.value) || 0)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="余白 (px)" desc="キャンバスの端からロゴまでの距離です">
+                    <input type="number" min="0" max="400" step="4" value={watermark().margin} onChange={(e) => updateWatermark("margin", parseFloat(e.target.value) || 0)} class={numInput()} />
+                  </SettingRow>
+                  <SettingRow label="ロゴの幅 (px)" desc="出力キャンバス上でのロゴの幅です。0で画像の元の大きさ">
+                    <input type="number" min="0" max="2000" step="10" value={watermark().width} onChange={(e) => updateWatermark("width", parseFloat(e.target.value) || 0)} class={numInput()} />
+                  </SettingRow>
+                </Show>
+                <SettingRow label="オープニングカード" desc="書き出した動画の先頭に、タイトル文字または画像のカードを挿入します">
+                  <input type="checkbox" checked={titleCard("intro").enabled} onChange={(e) => updateTitleCard("intro", "enabled", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <Show when={titleCard("intro").enabled}>
+                  <SettingRow label="タイトル" desc="カードの中央に表示する文字です">
+                    <input type="text" value={titleCard("intro").title} onChange={(e) => updateTitleCard("intro", "title", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                  </SettingRow>
+                  <SettingRow label="サブタイトル" desc="タイトルの下に小さく表示する文字です">
+                    <input type="text" value={titleCard("intro").subtitle} onChange={(e) => updateTitleCard("intro", "subtitle", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                  </SettingRow>
+                  <SettingRow label="画像のパス" desc="指定すると文字の代わりに画像を表示します（空欄で文字を表示）">
+                    <input type="text" value={titleCard("intro").image_path ?? ""} onChange={(e) => updateTitleCard("intro", "image_path", e.target.value.trim() || null)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                  </SettingRow>
+                  <SettingRow label="表示時間 (ms)" desc="カードを表示する長さです">
+                    <input type="number" min="500" max="30000" step="500" value={titleCard("intro").duration_ms} onChange={(e) => updateTitleCard("intro", "duration_ms", parseInt(e.target.value) || 2000)} class={numInput()} />
+                  </SettingRow>
+                </Show>
+                <SettingRow label="エンディングカード" desc="書き出した動画の末尾に、タイトル文字または画像のカードを挿入します">
+                  <input type="checkbox" checked={titleCard("outro").enabled} onChange={(e) => updateTitleCard("outro", "enabled", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <Show when={titleCard("outro").enabled}>
+                  <SettingRow label="タイトル" desc="カードの中央に表示する文字です">
+                    <input type="text" value={titleCard("outro").title} onChange={(e) => updateTitleCard("outro", "title", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                  </SettingRow>
+                  <SettingRow label="サブタイトル" desc="タイトルの下に小さく表示する文字です">
+                    <input type="text" value={titleCard("outro").subtitle} onChange={(e) => updateTitleCard("outro", "subtitle", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                  </SettingRow>
+                  <SettingRow label="画像のパス" desc="指定すると文字の代わりに画像を表示します（空欄で文字を表示）">
+                    <input type="text" value={titleCard("outro").image_path ?? ""} onChange={(e) => updateTitleCard("outro", "image_path", e.target.value.trim() || null)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm w-64" />
+                  </SettingRow>
+                  <SettingRow label="表示時間 (ms)" desc="カードを表示する長さです">
+                    <input type="number" min="500" max="30000" step="500" value={titleCard("outro").duration_ms} onChange={(e) => updateTitleCard("outro", "duration_ms", parseInt(e.target.value) || 2000)} class={numInput()} />
+                  </SettingRow>
+                </Show>
               </div>
             </section>
 
@@ -324,8 +952,30 @@ export default function Settings(props: Props) {
                     <option value="Mp4">MP4</option>
                     <option value="Gif">GIF</option>
                     <option value="WebM">WebM</option>
+                    <option value="WebP">WebP</option>
+                    <option value="Apng">APNG</option>
+                    <option value="Mov">MOV (ProRes)</option>
+                    <option value="AudioOnly">音声のみ</option>
                   </select>
                 </SettingRow>
+                <SettingRow label="音声のみ書き出しの形式" desc="ナレーションをポッドキャスト等に使う場合の音声ファイル形式です">
+                  <select value={s().output.audio_only_codec ?? "Aac"} onChange={(e) => updateField("output", "audio_only_codec", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                    <option value="Aac">M4A (AAC)</option>
+                    <option value="Opus">Opus</option>
+                  </select>
+                </SettingRow>
+                <SettingRow label="合成エンジン" desc="GPU を使うと長い録画の書き出しが速くなります。GPU が使えない環境では自動的に CPU で合成します">
+                  <select value={s().output.compositor_backend ?? "Cpu"} onChange={(e) => updateField("output", "compositor_backend", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                    <option value="Cpu">CPU</option>
+                    <option value="Gpu">GPU (実験的)</option>
+                  </select>
+                </SettingRow>
+                <SettingRow label="分割書き出し (分)" desc="長い動画をこの長さ以下の連番ファイル（_part1, _part2…）に分けて書き出します。できるだけシーンの切れ目で区切ります。0 で分割しません（MP4 / WebM / MOV のみ）">
+                  <input type="number" min="0" max="600" step="1" value={s().output.split_max_minutes ?? 0} onChange={(e) => updateField("output", "split_max_minutes", Math.max(0, parseInt(e.target.value) || 0))} class={numInput()} />
+                </SettingRow>
+                <SettingRow label="ブックマークをチャプターに" desc="タイムラインのブックマークを動画のチャプターとして書き込みます（MP4 / MOV、分割しない書き出しのみ）">
+                  <input type="checkbox" checked={s().output.bookmark_chapters ?? false} onChange={(e) => updateField("output", "bookmark_chapters", e.target.checked)} class="rounded" />
+                </SettingRow>
                 <SettingRow label="品質プリセット" desc="解像度とフレームレートの組み合わせです">
                   <select value={s().output.default_quality} onChange={(e) => updateField("output", "default_quality", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
                     <option value="Social">ソーシャル (1080p/30fps)</option>
@@ -333,6 +983,22 @@ export default function Settings(props: Props) {
                     <option value="Lightweight">軽量 (720p/24fps)</option>
                   </select>
                 </SettingRow>
+                <SettingRow label="縦横比" desc="ショート動画やSNS向けに縦型・正方形のキャンバスで書き出します">
+                  <select value={s().output.aspect ?? "Source"} onChange={(e) => updateField("output", "aspect", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                    <option value="Source">元の画面に合わせる</option>
+                    <option value="Vertical9x16">縦型 9:16 (Shorts / Reels)</option>
+                    <option value="Square1x1">正方形 1:1</option>
+                    <option value="Portrait4x5">縦長 4:5</option>
+                  </select>
+                </SettingRow>
+                <Show when={(s().output.aspect ?? "Source") !== "Source"}>
+                  <SettingRow label="配置方法" desc="ズームクロップは操作中の位置を追いかけて切り抜き、レターボックスは画面全体を収めて余白に背景を表示します">
+                    <select value={s().output.aspect_fit ?? "ZoomCrop"} onChange={(e) => updateField("output", "aspect_fit", e.target.value)} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                      <option value="ZoomCrop">ズームクロップ</option>
+                      <option value="Letterbox">レターボックス</option>
+                    </select>
+                  </SettingRow>
+                </Show>
                 <div class="space-y-1">
                   <SettingRow label="保存先フォルダ" desc="エクスポートした動画ファイルの保存先ディレクトリです">
                     <span />
@@ -345,6 +1011,330 @@ export default function Settings(props: Props) {
                     placeholder="C:\Users\...\Videos\Snappi"
                   />
                 </div>
+                <div class="space-y-1">
+                  <SettingRow label="ファイル名" desc="{date}=日付, {time}=時刻, {title}=録画したウィンドウ名, {id}=録画ID, {speed}=タイムラプスの倍速（例: 30x）。ファイル名に使えない文字は自動で置き換えます">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="text"
+                    value={s().output.filename_template ?? "{date}_{time}"}
+                    onChange={(e) => updateField("output", "filename_template", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                    placeholder="{date}_{time}"
+                  />
+                </div>
+                <SettingRow label="不要なファイルの削除" desc="異常終了した書き出しの一時フォルダ、古くなったプレビュー、削除済みの録画のキャッシュを削除します">
+                  <div class="flex flex-col items-end gap-1">
+                    <button onClick={handleCleanup} class="px-3 py-1 bg-slate-700 hover:bg-slate-600 rounded-lg text-sm">削除</button>
+                    <Show when={cleanupMessage()}>
+                      <span class="text-xs text-slate-400">{cleanupMessage()}</span>
+                    </Show>
+                  </div>
+                </SettingRow>
+              </div>
+            </section>
+
+            {/* ===== ストレージ ===== */}
+            <section>
+              <h3 class="text-sm font-semibold text-slate-400 uppercase tracking-wider mb-3">ストレージ</h3>
+              <div class="space-y-3 bg-slate-800/50 rounded-xl p-4">
+                <SettingRow label="書き出し後にフレームを削除" desc="書き出しに成功した録画の元フレームを削除します。一覧には残りますが、プレビュー・再書き出しはできなくなります">
+                  <input type="checkbox" checked={storage().delete_frames_after_export} onChange={(e) => updateStorage("delete_frames_after_export", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <SettingRow label="残す録画の数" desc="新しい順にこの件数だけ残し、古い録画を削除します（0 = 無制限）">
+                  <input type="number" min="0" max="10000" value={storage().keep_last_recordings} onChange={(e) => updateStorage("keep_last_recordings", Math.max(0, parseInt(e.target.value) || 0))} class={numInput()} />
+                </SettingRow>
+                <SettingRow label="録画フォルダの上限 (GB)" desc="超えたら古い録画から削除します（0 = 無制限）">
+                  <input type="number" min="0" step="1" value={storage().max_library_gb} onChange={(e) => updateStorage("max_library_gb", Math.max(0, parseFloat(e.target.value) || 0))} class={numInput()} />
+                </SettingRow>
+                <SettingRow label="書き出していない録画も削除" desc="オフの場合、件数・容量の上限で削除するのは書き出し済みの録画だけです。書き出し中の録画は削除しません">
+                  <input type="checkbox" checked={storage().delete_unexported ?? false} onChange={(e) => updateStorage("delete_unexported", e.target.checked)} class="rounded" />
+                </SettingRow>
+                <SettingRow label="ディスク使用量" desc="自動整理は 10 分ごとに実行されます。「今すぐ整理」は保存済みの設定を使います">
+                  <div class="flex flex-col items-end gap-1">
+                    <div class="flex gap-2">
+                      <button onClick={handleLoadUsage} class="px-3 py-1 bg-slate-700 hover:bg-slate-600 rounded-lg text-sm">表示</button>
+                      <button onClick={handleApplyStorage} class="px-3 py-1 bg-slate-700 hover:bg-slate-600 rounded-lg text-sm">今すぐ整理</button>
+                    </div>
+                    <Show when={storageMessage()}>
+                      <span class="text-xs text-slate-400">{storageMessage()}</span>
+                    </Show>
+                  </div>
+                </SettingRow>
+                <Show when={storageUsage()}>
+                  <div class="text-xs text-slate-400 space-y-1">
+                    <p>合計 {formatBytes(storageUsage()!.total_bytes)}（{storageUsage()!.recordings.length} 件）</p>
+                    <div class="max-h-48 overflow-y-auto space-y-0.5">
+                      <For each={storageUsage()!.recordings}>
+                        {(rec) => (
+                          <div class="flex justify-between gap-2">
+                            <span class="truncate">
+                              {rec.title || new Date(rec.date).toLocaleString()}
+                              {rec.frames_removed ? "（フレーム削除済み）" : rec.exported ? "（書き出し済み）" : ""}
+                            </span>
+                            <span class="shrink-0">{formatBytes(rec.bytes)}</span>
+                          </div>
+                        )}
+                      </For>
+                    </div>
+                  </div>
+                </Show>
+              </div>
+            </section>
+
+            {/* ===== ライブ配信 ===== */}
+            <section>
+              <h3 class="text-sm font-semibold text-slate-400 uppercase tracking-wider mb-3">ライブ配信</h3>
+              <div class="space-y-3 bg-slate-800/50 rounded-xl p-4">
+                <div class="space-y-1">
+                  <SettingRow label="サーバー URL" desc="配信サービスの RTMP サーバー（rtmp:// または rtmps://）。録画対象（画面全体・ウィンドウ・範囲）をそのまま配信します">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="text"
+                    value={stream().url}
+                    onChange={(e) => updateStream("url", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                    placeholder="rtmp://live.example.com/app"
+                  />
+                </div>
+                <div class="space-y-1">
+                  <SettingRow label="ストリームキー" desc="サーバー URL の末尾に付けて送ります。ログには残しません">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="password"
+                    value={stream().stream_key}
+                    onChange={(e) => updateStream("stream_key", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                  />
+                </div>
+                <SettingRow label="フレームレート (FPS)" desc="配信の 1 秒あたりの枚数">
+                  <select value={stream().fps} onChange={(e) => updateStream("fps", parseInt(e.target.value))} class="bg-slate-700 rounded-lg px-3 py-1 text-sm">
+                    <option value="15">15</option>
+                    <option value="24">24</option>
+                    <option value="30">30</option>
+                  </select>
+                </SettingRow>
+                <SettingRow label="ビットレート (kbps)" desc="映像のビットレート。回線の上り速度より低くします">
+                  <input type="number" min="500" max="20000" step="500" value={stream().video_bitrate_kbps} onChange={(e) => updateStream("video_bitrate_kbps", parseInt(e.target.value) || 4500)} class={numInput()} />
+                </SettingRow>
+                <SettingRow label="スタイルを付けて配信" desc="書き出しと同じ背景・角丸・影・カーソルを付けます。ズームなど録画後の解析が必要なエフェクトは付きません">
+                  <input type="checkbox" checked={stream().apply_style} onChange={(e) => updateStream("apply_style", e.target.checked)} class="rounded" />
+                </SettingRow>
+              </div>
+            </section>
+
+            {/* ===== 共有（アップロード） ===== */}
+            <section>
+              <h3 class="text-sm font-semibold text-slate-400 uppercase tracking-wider mb-3">共有（アップロード）</h3>
+              <div class="space-y-3 bg-slate-800/50 rounded-xl p-4">
+                <p class="text-xs text-slate-500">書き出し完了後にプレビュー画面からアップロードし、共有用 URL を受け取れます。使う先だけ設定してください</p>
+                <p class="text-xs font-semibold text-slate-400">S3 互換ストレージ</p>
+                <div class="space-y-1">
+                  <SettingRow label="エンドポイント" desc="AWS S3・Cloudflare R2・MinIO などの URL">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="text"
+                    value={share().s3.endpoint}
+                    onChange={(e) => updateShare("s3", "endpoint", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                    placeholder="https://s3.ap-northeast-1.amazonaws.com"
+                  />
+                </div>
+                <SettingRow label="リージョン" desc="R2 は auto">
+                  <input type="text" value={share().s3.region} onChange={(e) => updateShare("s3", "region", e.target.value)} class="w-40 bg-slate-700 rounded-lg px-3 py-1 text-sm text-slate-200" placeholder="ap-northeast-1" />
+                </SettingRow>
+                <SettingRow label="バケット" desc="アップロード先のバケット名">
+                  <input type="text" value={share().s3.bucket} onChange={(e) => updateShare("s3", "bucket", e.target.value)} class="w-40 bg-slate-700 rounded-lg px-3 py-1 text-sm text-slate-200" />
+                </SettingRow>
+                <div class="space-y-1">
+                  <SettingRow label="アクセスキー ID">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="text"
+                    value={share().s3.access_key_id}
+                    onChange={(e) => updateShare("s3", "access_key_id", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                  />
+                </div>
+                <div class="space-y-1">
+                  <SettingRow label="シークレットアクセスキー" desc="ログには残しません">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="password"
+                    value={share().s3.secret_access_key}
+                    onChange={(e) => updateShare("s3", "secret_access_key", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                  />
+                </div>
+                <div class="space-y-1">
+                  <SettingRow label="キーの接頭辞" desc="オブジェクトキーの前に付けます（例: snappi/）">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="text"
+                    value={share().s3.key_prefix}
+                    onChange={(e) => updateShare("s3", "key_prefix", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                  />
+                </div>
+                <div class="space-y-1">
+                  <SettingRow label="共有用 URL の先頭" desc="CDN や公開バケットのドメイン。空ならエンドポイント/バケット/キーの URL を返します">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="text"
+                    value={share().s3.public_url_base}
+                    onChange={(e) => updateShare("s3", "public_url_base", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                    placeholder="https://cdn.example.com"
+                  />
+                </div>
+                <p class="text-xs font-semibold text-slate-400 pt-2">HTTP (PUT)</p>
+                <div class="space-y-1">
+                  <SettingRow label="アップロード先 URL" desc="ファイルを PUT で送ります。{filename} がファイル名に置き換わります">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="text"
+                    value={share().http.url}
+                    onChange={(e) => updateShare("http", "url", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                    placeholder="https://upload.example.com/{filename}"
+                  />
+                </div>
+                <div class="space-y-1">
+                  <SettingRow label="Authorization ヘッダー" desc="例: Bearer xxx。空なら付けません">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="password"
+                    value={share().http.authorization}
+                    onChange={(e) => updateShare("http", "authorization", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                  />
+                </div>
+                <div class="space-y-1">
+                  <SettingRow label="共有用 URL" desc="{filename} が使えます。空ならアップロード先 URL を返します">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="text"
+                    value={share().http.public_url}
+                    onChange={(e) => updateShare("http", "public_url", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                  />
+                </div>
+                <p class="text-xs font-semibold text-slate-400 pt-2">YouTube</p>
+                <div class="space-y-1">
+                  <SettingRow label="OAuth クライアント ID" desc="Google Cloud で作成した OAuth クライアント（デスクトップ アプリ）">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="text"
+                    value={share().youtube.client_id}
+                    onChange={(e) => updateShare("youtube", "client_id", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                  />
+                </div>
+                <div class="space-y-1">
+                  <SettingRow label="クライアント シークレット">
+                    <span />
+                  </SettingRow>
+                  <input
+                    type="password"
+                    value={share().youtube.client_secret}
+                    onChange={(e) => updateShare("youtube", "client_secret", e.target.value)}
+                    class="w-full bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200"
+                  />
+                </div>
+                <SettingRow label="公開設定" desc="アップロードした動画の公開範囲">
+                  <select
+                    value={share().youtube.privacy}
+                    onChange={(e) => updateShare("youtube", "privacy", e.target.value as ShareSettings["youtube"]["privacy"])}
+                    class="bg-slate-700 rounded-lg px-3 py-1 text-sm"
+                  >
+                    <option value="private">非公開</option>
+                    <option value="unlisted">限定公開</option>
+                    <option value="public">公開</option>
+                  </select>
+                </SettingRow>
+                <SettingRow label="YouTube と連携" desc={share().youtube.refresh_token ? "連携済みです。別のアカウントにするときはもう一度連携します" : "ブラウザで Google の許可画面を開きます"}>
+                  <button onClick={handleAuthorizeYoutube} class="px-3 py-1 rounded-lg bg-slate-700 hover:bg-slate-600 text-sm text-slate-300">
+                    {share().youtube.refresh_token ? "再連携" : "連携"}
+                  </button>
+                </SettingRow>
+                <Show when={youtubeMessage()}>
+                  <p class="text-xs text-slate-400">{youtubeMessage()}</p>
+                </Show>
+              </div>
+            </section>
+
+            {/* ===== プロファイル ===== */}
+            <section>
+              <h3 class="text-sm font-semibold text-slate-400 uppercase tracking-wider mb-3">プロファイル</h3>
+              <div class="space-y-3 bg-slate-800/50 rounded-xl p-4">
+                <p class="text-xs text-slate-500">スタイル・エフェクト・出力設定（保存先フォルダを含む）に名前を付けて保存し、仕事用・SNS 用などをまとめて切り替えられます</p>
+                <For each={profiles()}>
+                  {(profile) => (
+                    <SettingRow label={profile.name}>
+                      <div class="flex gap-3">
+                        <button onClick={() => handleApplyProfile(profile.name)} class="text-xs text-purple-400 hover:text-purple-300 transition-colors">
+                          切り替え
+                        </button>
+                        <button onClick={() => handleDeleteProfile(profile.name)} class="text-xs text-slate-500 hover:text-red-400 transition-colors">
+                          削除
+                        </button>
+                      </div>
+                    </SettingRow>
+                  )}
+                </For>
+                <div class="flex gap-2">
+                  <input type="text" value={profileName()} onInput={(e) => setProfileName(e.target.value)} class="flex-1 bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200" placeholder="プロファイル名（例: 仕事用）" />
+                  <button onClick={handleSaveProfile} disabled={!profileName().trim()} class="px-3 py-1.5 rounded-lg text-sm bg-slate-700 hover:bg-slate-600 text-slate-200 disabled:opacity-50">
+                    今の設定を保存
+                  </button>
+                </div>
+                <Show when={profileMessage()}>
+                  <p class="text-xs text-slate-400">{profileMessage()}</p>
+                </Show>
+              </div>
+            </section>
+
+            {/* ===== スタイルプリセット ===== */}
+            <section>
+              <h3 class="text-sm font-semibold text-slate-400 uppercase tracking-wider mb-3">スタイルプリセット</h3>
+              <div class="space-y-3 bg-slate-800/50 rounded-xl p-4">
+                <p class="text-xs text-slate-500">スタイル・エフェクト・出力設定を .snappi-style ファイルで共有できます。保存先フォルダなどこのPC固有の設定は適用されません</p>
+                <For each={presets()}>
+                  {(preset) => (
+                    <SettingRow label={preset.name} desc={preset.description ?? ""}>
+                      <button onClick={() => handleApplyPreset(preset.name)} class="text-xs text-purple-400 hover:text-purple-300 transition-colors">
+                        適用
+                      </button>
+                    </SettingRow>
+                  )}
+                </For>
+                <div class="flex gap-2">
+                  <input type="text" value={presetImportPath()} onInput={(e) => setPresetImportPath(e.target.value)} class="flex-1 bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200" placeholder="C:\...\team.snappi-style" />
+                  <button onClick={handleImportPreset} disabled={!presetImportPath().trim()} class="px-3 py-1.5 rounded-lg text-sm bg-slate-700 hover:bg-slate-600 text-slate-200 disabled:opacity-50">
+                    読み込む
+                  </button>
+                </div>
+                <div class="flex gap-2">
+                  <input type="text" value={presetName()} onInput={(e) => setPresetName(e.target.value)} class="w-40 bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200" placeholder="プリセット名" />
+                  <input type="text" value={presetExportPath()} onInput={(e) => setPresetExportPath(e.target.value)} class="flex-1 bg-slate-700 rounded-lg px-3 py-1.5 text-sm text-slate-200" placeholder="C:\...\team.snappi-style" />
+                  <button onClick={handleExportPreset} disabled={!presetName().trim() || !presetExportPath().trim()} class="px-3 py-1.5 rounded-lg text-sm bg-slate-700 hover:bg-slate-600 text-slate-200 disabled:opacity-50">
+                    書き出す
+                  </button>
+                </div>
+                <Show when={presetMessage()}>
+                  <p class="text-xs text-slate-400">{presetMessage()}</p>
+                </Show>
               </div>
             </section>
           </div>
@@ -352,6 +1342,9 @@ export default function Settings(props: Props) {
       </Show>
 
       <div class="px-6 py-4 border-t border-slate-700/50">
+        <Show when={saveError()}>
+          <p class="mb-2 text-xs text-red-400">{saveError()}</p>
+        </Show>
         <button onClick={handleSave} class="w-full py-2.5 px-4 rounded-xl font-medium transition-all bg-purple-600 hover:bg-purple-700 text-white">
           {saved() ? "保存しました" : "設定を保存"}
         </button>